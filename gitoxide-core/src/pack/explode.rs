@@ -16,6 +16,7 @@ use gix::{
 #[derive(Default, Clone, Eq, PartialEq, Debug)]
 pub enum SafetyCheck {
     SkipFileChecksumVerification,
+    SkipFileChecksumAndObjectHashVerification,
     SkipFileAndObjectChecksumVerification,
     SkipFileAndObjectChecksumVerificationAndNoAbortOnDecodeError,
     #[default]
@@ -27,6 +28,7 @@ impl SafetyCheck {
         &[
             "all",
             "skip-file-checksum",
+            "skip-file-checksum-and-object-hash",
             "skip-file-and-object-checksum",
             "skip-file-and-object-checksum-and-no-abort-on-decode",
         ]
@@ -39,6 +41,7 @@ impl std::str::FromStr for SafetyCheck {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
             "skip-file-checksum" => SafetyCheck::SkipFileChecksumVerification,
+            "skip-file-checksum-and-object-hash" => SafetyCheck::SkipFileChecksumAndObjectHashVerification,
             "skip-file-and-object-checksum" => SafetyCheck::SkipFileAndObjectChecksumVerification,
             "skip-file-and-object-checksum-and-no-abort-on-decode" => {
                 SafetyCheck::SkipFileAndObjectChecksumVerificationAndNoAbortOnDecodeError
@@ -55,6 +58,7 @@ impl From<SafetyCheck> for pack::index::traverse::SafetyCheck {
         match v {
             SafetyCheck::All => All,
             SafetyCheck::SkipFileChecksumVerification => SkipFileChecksumVerification,
+            SafetyCheck::SkipFileChecksumAndObjectHashVerification => SkipFileChecksumAndObjectHashVerification,
             SafetyCheck::SkipFileAndObjectChecksumVerification => SkipFileAndObjectChecksumVerification,
             SafetyCheck::SkipFileAndObjectChecksumVerificationAndNoAbortOnDecodeError => {
                 SkipFileAndObjectChecksumVerificationAndNoAbortOnDecodeError
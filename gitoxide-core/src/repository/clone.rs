@@ -7,6 +7,7 @@ pub struct Options {
     pub no_tags: bool,
     pub shallow: gix::remote::fetch::Shallow,
     pub ref_name: Option<gix::refs::PartialName>,
+    pub local: gix::clone::Local,
 }
 
 pub const PROGRESS_RANGE: std::ops::RangeInclusive<u8> = 1..=3;
@@ -34,6 +35,7 @@ pub(crate) mod function {
             no_tags,
             ref_name,
             shallow,
+            local,
         }: Options,
     ) -> anyhow::Result<()>
     where
@@ -73,10 +75,11 @@ pub(crate) mod function {
             },
         )?;
         if no_tags {
-            prepare = prepare.configure_remote(|r| Ok(r.with_fetch_tags(gix::remote::fetch::Tags::None)));
+            prepare = prepare.with_tags(gix::remote::fetch::Tags::None);
         }
         let (mut checkout, fetch_outcome) = prepare
             .with_shallow(shallow)
+            .with_local(local)
             .with_ref_name(ref_name.as_ref())?
             .fetch_then_checkout(&mut progress, &gix::interrupt::IS_INTERRUPTED)?;
 
@@ -93,10 +96,30 @@ pub(crate) mod function {
         }
 
         match fetch_outcome.status {
-            Status::NoPackReceived { dry_run, .. } => {
+            Status::NoPackReceived {
+                dry_run, update_refs, ..
+            } if update_refs.edits.is_empty() => {
                 assert!(!dry_run, "dry-run unsupported");
                 writeln!(err, "The cloned repository appears to be empty")?;
             }
+            Status::NoPackReceived {
+                update_refs, negotiate, ..
+            } => {
+                // Objects were obtained from a local repository directly, so only references were updated.
+                let remote = repo
+                    .find_default_remote(gix::remote::Direction::Fetch)
+                    .expect("one origin remote")?;
+                let ref_specs = remote.refspecs(gix::remote::Direction::Fetch);
+                print_updates(
+                    &repo,
+                    &negotiate.unwrap_or_default(),
+                    update_refs,
+                    ref_specs,
+                    fetch_outcome.ref_map,
+                    &mut out,
+                    &mut err,
+                )?;
+            }
             Status::Change {
                 update_refs, negotiate, ..
             } => {
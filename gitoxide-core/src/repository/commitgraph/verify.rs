@@ -7,6 +7,8 @@ pub struct Context<W1: std::io::Write, W2: std::io::Write> {
     /// A stream to which to output operation results
     pub out: W1,
     pub output_statistics: Option<OutputFormat>,
+    /// If set, remove the first invalid layer of the commit-graph along with all layers on top of it.
+    pub remove_invalid_layers: bool,
 }
 
 pub(crate) mod function {
@@ -19,15 +21,28 @@ pub(crate) mod function {
     pub fn verify<W1, W2>(
         repo: gix::Repository,
         Context {
-            err: _err,
+            mut err,
             mut out,
             output_statistics,
+            remove_invalid_layers,
         }: Context<W1, W2>,
     ) -> Result<gix::commitgraph::verify::Outcome>
     where
         W1: io::Write,
         W2: io::Write,
     {
+        let outcome =
+            repo.verify_commit_graph(gix::repository::verify_commit_graph::Options { remove_invalid_layers })?;
+        for (path, mismatch) in outcome.iter_mismatches() {
+            writeln!(err, "{}: {mismatch}", path.display())?;
+        }
+        for path in &outcome.removed_layers {
+            writeln!(err, "removed {}", path.display())?;
+        }
+        if !outcome.is_valid() {
+            anyhow::bail!("The commit-graph doesn't match the object database");
+        }
+
         let g = repo.commit_graph()?;
 
         #[allow(clippy::unnecessary_wraps, unknown_lints)]
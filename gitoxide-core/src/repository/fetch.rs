@@ -9,6 +9,8 @@ pub struct Options {
     /// If non-empty, override all ref-specs otherwise configured in the remote
     pub ref_specs: Vec<BString>,
     pub shallow: gix::remote::fetch::Shallow,
+    /// If `true`, fetch all objects again as if we didn't have any, and remove packs that became redundant.
+    pub refetch: bool,
     pub handshake_info: bool,
     pub negotiation_info: bool,
     pub open_negotiation_graph: Option<std::path::PathBuf>,
@@ -45,6 +47,7 @@ pub(crate) mod function {
             negotiation_info,
             open_negotiation_graph,
             shallow,
+            refetch,
             ref_specs,
         }: Options,
     ) -> anyhow::Result<()>
@@ -66,6 +69,7 @@ pub(crate) mod function {
             .prepare_fetch(&mut progress, Default::default())?
             .with_dry_run(dry_run)
             .with_shallow(shallow)
+            .with_refetch(refetch)
             .receive(&mut progress, &gix::interrupt::IS_INTERRUPTED)?;
 
         if handshake_info {
@@ -3,13 +3,22 @@ use std::path::PathBuf;
 use anyhow::{Context as AnyhowContext, Result};
 use gix::bstr::BString;
 
-pub fn init(directory: Option<PathBuf>) -> Result<gix::discover::repository::Path> {
-    gix::create::into(
+pub fn init(directory: Option<PathBuf>, template_dir: Option<PathBuf>) -> Result<gix::discover::repository::Path> {
+    let path = gix::create::into(
         directory.unwrap_or_default(),
         gix::create::Kind::WithWorktree,
-        gix::create::Options::default(),
+        gix::create::Options {
+            ignore_templates: template_dir.is_some(),
+            ..Default::default()
+        },
     )
-    .with_context(|| "Repository initialization failed")
+    .with_context(|| "Repository initialization failed")?;
+    if let Some(template_dir) = template_dir {
+        let (git_dir, _worktree_dir) = path.clone().into_repository_and_work_tree_directories();
+        gix::create::copy_templates(&template_dir, &git_dir)
+            .with_context(|| "Could not copy templates into the new repository")?;
+    }
+    Ok(path)
 }
 
 pub enum PathsOrPatterns {
@@ -131,3 +131,7 @@ pub(crate) mod write {
 ///
 pub mod decode;
 pub use decode::function::decode;
+
+///
+#[cfg(feature = "serde")]
+pub mod time_as_string;
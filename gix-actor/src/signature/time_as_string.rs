@@ -0,0 +1,73 @@
+//! Serialize a [`Signature`] with its time as string, for use with `#[serde(with = "…")]`.
+//!
+//! This is the equivalent of [`gix_date::time::as_string`] for signatures, so that
+//! `#[serde(with = "gix_actor::signature::time_as_string::iso8601")]` serializes the time of a signature
+//! like `2022-08-17T21:43:13+08:00`. Deserialization accepts all formats a [`Time`] can be parsed from.
+use bstr::{BStr, BString};
+use gix_date::{time::Format, Time};
+
+use crate::Signature;
+
+#[derive(serde::Serialize)]
+struct BorrowedSignature<'a> {
+    name: &'a BStr,
+    email: &'a BStr,
+    time: String,
+}
+
+#[derive(serde::Deserialize)]
+struct OwnedSignature {
+    name: BString,
+    email: BString,
+    time: String,
+}
+
+fn serialize<S: serde::Serializer>(signature: &Signature, serializer: S, format: Format) -> Result<S::Ok, S::Error> {
+    serde::Serialize::serialize(
+        &BorrowedSignature {
+            name: signature.name.as_ref(),
+            email: signature.email.as_ref(),
+            time: signature.time.format(format),
+        },
+        serializer,
+    )
+}
+
+fn deserialize<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Signature, D::Error> {
+    let OwnedSignature { name, email, time } = serde::Deserialize::deserialize(deserializer)?;
+    Ok(Signature {
+        name,
+        email,
+        time: time.parse::<Time>().map_err(serde::de::Error::custom)?,
+    })
+}
+
+/// Serialize the time as [raw](gix_date::time::format::RAW) git time, like `1660874655 +0800`.
+pub mod raw {
+    use crate::Signature;
+
+    /// Serialize `signature` with `serializer`.
+    pub fn serialize<S: serde::Serializer>(signature: &Signature, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serialize(signature, serializer, gix_date::time::format::RAW)
+    }
+
+    /// Deserialize a signature from `deserializer`.
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Signature, D::Error> {
+        super::deserialize(deserializer)
+    }
+}
+
+/// Serialize the time as [strict ISO8601](gix_date::time::format::ISO8601_STRICT) time, like `2022-08-17T21:43:13+08:00`.
+pub mod iso8601 {
+    use crate::Signature;
+
+    /// Serialize `signature` with `serializer`.
+    pub fn serialize<S: serde::Serializer>(signature: &Signature, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serialize(signature, serializer, gix_date::time::format::ISO8601_STRICT.into())
+    }
+
+    /// Deserialize a signature from `deserializer`.
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Signature, D::Error> {
+        super::deserialize(deserializer)
+    }
+}
@@ -80,13 +80,23 @@ impl Search {
         collection: &mut MetadataCollection,
         allow_macros: bool,
     ) {
-        self.patterns.push(pattern::List::from_bytes(bytes, source, root));
-        let last = self.patterns.last_mut().expect("just added");
+        self.add_pattern_list(pattern::List::from_bytes(bytes, source, root), collection, allow_macros);
+    }
+
+    /// Add the already parsed pattern `list`, for instance one that was cached, and update `collection` with newly added attribute names.
+    /// If `allow_macros` is `true`, macros will be processed like normal, otherwise they will be skipped entirely.
+    pub fn add_pattern_list(
+        &mut self,
+        mut list: pattern::List<Attributes>,
+        collection: &mut MetadataCollection,
+        allow_macros: bool,
+    ) {
         if !allow_macros {
-            last.patterns
+            list.patterns
                 .retain(|p| !matches!(p.value, Value::MacroAssignments { .. }));
         }
-        collection.update_from_list(last);
+        collection.update_from_list(&mut list);
+        self.patterns.push(list);
     }
 
     /// Pop the last attribute patterns list from our queue.
@@ -0,0 +1,259 @@
+//! Write commit-graph files, possibly as new layer on top of an existing commit-graph.
+use std::io::Write;
+
+use crate::{
+    file::{
+        BASE_GRAPHS_LIST_CHUNK_ID, COMMIT_DATA_CHUNK_ID, COMMIT_DATA_ENTRY_SIZE_SANS_HASH,
+        EXTENDED_EDGES_LIST_CHUNK_ID, EXTENDED_EDGES_MASK, FAN_LEN, HEADER_LEN, LAST_EXTENDED_EDGE_MASK, NO_PARENT,
+        OID_FAN_CHUNK_ID, OID_LOOKUP_CHUNK_ID, SIGNATURE,
+    },
+    File, Graph, GENERATION_NUMBER_MAX, MAX_COMMITS,
+};
+
+/// The error returned by [`File::write_to()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Parent {parent} of commit {id} is neither part of the commits to write nor of the base graph")]
+    MissingParent {
+        id: gix_hash::ObjectId,
+        parent: gix_hash::ObjectId,
+    },
+    #[error(
+        "Commit-graph would contain {0} commits altogether, but only {} commits are allowed",
+        MAX_COMMITS
+    )]
+    TooManyCommits(u64),
+    #[error("Commit-graph would be based on {0} graphs, but only 255 base graphs are allowed")]
+    TooManyBaseGraphs(usize),
+}
+
+/// A commit to write into a commit-graph file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Commit {
+    /// The id of the commit.
+    pub id: gix_hash::ObjectId,
+    /// The id of the tree the commit points to.
+    pub tree: gix_hash::ObjectId,
+    /// The ids of all parents of the commit, in order.
+    pub parents: Vec<gix_hash::ObjectId>,
+    /// The committer time in seconds since unix epoch.
+    ///
+    /// Note that only the lower 34 bits are stored, just like `git` does.
+    pub commit_time: u64,
+}
+
+/// Options for use in [`File::write_to()`].
+#[derive(Debug, Copy, Clone)]
+pub struct Options {
+    /// The kind of hash to use for the ids of commits and trees and for the checksum of the file.
+    pub object_hash: gix_hash::Kind,
+}
+
+/// The result of [`File::write_to()`].
+#[derive(Debug, Copy, Clone)]
+pub struct Outcome {
+    /// The checksum of the written file, which is also used as name of layers in a commit-graph chain,
+    /// as in `graph-<checksum>.graph`.
+    pub checksum: gix_hash::ObjectId,
+    /// The amount of commits that were written.
+    pub num_commits: u32,
+}
+
+/// Writing
+impl File {
+    /// Write a commit-graph file with all `commits` to `out`, which is stacked on top of all files in `base` if set,
+    /// to become the newest layer of the commit-graph chain of `base`.
+    ///
+    /// Commits that are already part of `base` are skipped, and all parents of `commits` must either be part of `commits`
+    /// or of `base`.
+    /// Generation numbers are computed as topological levels, as done by generation-number version 1.
+    pub fn write_to(
+        mut commits: Vec<Commit>,
+        base: Option<&Graph>,
+        out: &mut dyn Write,
+        Options { object_hash }: Options,
+    ) -> Result<Outcome, Error> {
+        commits.sort_by_key(|c| c.id);
+        commits.dedup_by(|a, b| a.id == b.id);
+        if let Some(base) = base {
+            commits.retain(|c| base.lookup(c.id).is_none());
+        }
+
+        let num_base_commits = base.map_or(0, Graph::num_commits);
+        let total_commits = u64::from(num_base_commits) + commits.len() as u64;
+        if total_commits > u64::from(MAX_COMMITS) {
+            return Err(Error::TooManyCommits(total_commits));
+        }
+        let base_graph_ids: Vec<_> = base
+            .map(|base| base.files.iter().map(|f| f.checksum().to_owned()).collect())
+            .unwrap_or_default();
+        let num_base_graphs =
+            u8::try_from(base_graph_ids.len()).map_err(|_| Error::TooManyBaseGraphs(base_graph_ids.len()))?;
+
+        let parent_positions = parent_positions(&commits, base, num_base_commits)?;
+        let generations = generations(&commits, &parent_positions, base, num_base_commits);
+        let num_extra_edges: usize = parent_positions
+            .iter()
+            .filter(|p| p.len() > 2)
+            .map(|p| p.len() - 1)
+            .sum();
+
+        let hash_len = object_hash.len_in_bytes();
+        let mut cf = gix_chunk::file::Index::for_writing();
+        cf.plan_chunk(OID_FAN_CHUNK_ID, (FAN_LEN * 4) as u64);
+        cf.plan_chunk(OID_LOOKUP_CHUNK_ID, (commits.len() * hash_len) as u64);
+        cf.plan_chunk(
+            COMMIT_DATA_CHUNK_ID,
+            (commits.len() * (hash_len + COMMIT_DATA_ENTRY_SIZE_SANS_HASH)) as u64,
+        );
+        if num_extra_edges != 0 {
+            cf.plan_chunk(EXTENDED_EDGES_LIST_CHUNK_ID, (num_extra_edges * 4) as u64);
+        }
+        if num_base_graphs != 0 {
+            cf.plan_chunk(BASE_GRAPHS_LIST_CHUNK_ID, (base_graph_ids.len() * hash_len) as u64);
+        }
+
+        let mut out = gix_features::hash::Write::new(out, object_hash);
+        out.write_all(SIGNATURE)?;
+        out.write_all(&[1 /* version */, object_hash as u8])?;
+        out.write_all(&[
+            cf.num_chunks().try_into().expect("BUG: wrote more than 256 chunks"),
+            num_base_graphs,
+        ])?;
+
+        let mut chunk_write = cf.into_write(&mut out, HEADER_LEN)?;
+        while let Some(chunk_to_write) = chunk_write.next_chunk() {
+            match chunk_to_write {
+                OID_FAN_CHUNK_ID => {
+                    let mut fan = [0u32; FAN_LEN];
+                    for commit in &commits {
+                        fan[usize::from(commit.id.first_byte())] += 1;
+                    }
+                    let mut count = 0;
+                    for entry in fan {
+                        count += entry;
+                        chunk_write.write_all(&count.to_be_bytes())?;
+                    }
+                }
+                OID_LOOKUP_CHUNK_ID => {
+                    for commit in &commits {
+                        chunk_write.write_all(commit.id.as_slice())?;
+                    }
+                }
+                COMMIT_DATA_CHUNK_ID => {
+                    let mut extra_edge_index = 0;
+                    for ((commit, parents), generation) in commits.iter().zip(&parent_positions).zip(&generations) {
+                        chunk_write.write_all(commit.tree.as_slice())?;
+                        let (parent1, parent2) = match parents.as_slice() {
+                            [] => (NO_PARENT, NO_PARENT),
+                            [parent1] => (*parent1, NO_PARENT),
+                            [parent1, parent2] => (*parent1, *parent2),
+                            [parent1, rest @ ..] => {
+                                let parent2 = EXTENDED_EDGES_MASK | extra_edge_index;
+                                extra_edge_index += rest.len() as u32;
+                                (*parent1, parent2)
+                            }
+                        };
+                        chunk_write.write_all(&parent1.to_be_bytes())?;
+                        chunk_write.write_all(&parent2.to_be_bytes())?;
+                        let generation_and_time_high = (*generation << 2) | ((commit.commit_time >> 32) & 0x3) as u32;
+                        chunk_write.write_all(&generation_and_time_high.to_be_bytes())?;
+                        chunk_write.write_all(&(commit.commit_time as u32).to_be_bytes())?;
+                    }
+                }
+                EXTENDED_EDGES_LIST_CHUNK_ID => {
+                    for parents in parent_positions.iter().filter(|p| p.len() > 2) {
+                        let (last, extra) = parents[1..].split_last().expect("more than two parents");
+                        for parent in extra {
+                            chunk_write.write_all(&parent.to_be_bytes())?;
+                        }
+                        chunk_write.write_all(&(LAST_EXTENDED_EDGE_MASK | last).to_be_bytes())?;
+                    }
+                }
+                BASE_GRAPHS_LIST_CHUNK_ID => {
+                    for id in &base_graph_ids {
+                        chunk_write.write_all(id.as_slice())?;
+                    }
+                }
+                unknown => unreachable!("BUG: forgot to implement chunk {:?}", std::str::from_utf8(&unknown)),
+            }
+        }
+
+        // write trailing checksum
+        let checksum: gix_hash::ObjectId = out.hash.digest().into();
+        out.inner.write_all(checksum.as_slice())?;
+
+        Ok(Outcome {
+            checksum,
+            num_commits: commits.len() as u32,
+        })
+    }
+}
+
+/// Return the graph position of each parent of each commit in `commits`, which are assumed to be sorted.
+fn parent_positions(commits: &[Commit], base: Option<&Graph>, num_base_commits: u32) -> Result<Vec<Vec<u32>>, Error> {
+    commits
+        .iter()
+        .map(|commit| {
+            commit
+                .parents
+                .iter()
+                .map(|parent| {
+                    commits
+                        .binary_search_by(|c| c.id.cmp(parent))
+                        .ok()
+                        .map(|pos| num_base_commits + pos as u32)
+                        .or_else(|| base.and_then(|base| base.lookup(parent)).map(|pos| pos.0))
+                        .ok_or(Error::MissingParent {
+                            id: commit.id,
+                            parent: *parent,
+                        })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Compute the topological level of each commit, using the generations stored in `base` for its commits.
+fn generations(
+    commits: &[Commit],
+    parent_positions: &[Vec<u32>],
+    base: Option<&Graph>,
+    num_base_commits: u32,
+) -> Vec<u32> {
+    let mut generations = vec![0u32; commits.len()];
+    let mut stack = Vec::new();
+    for start in 0..commits.len() {
+        if generations[start] != 0 {
+            continue;
+        }
+        stack.push(start);
+        while let Some(&idx) = stack.last() {
+            let mut generation = 0;
+            let mut parents_are_done = true;
+            for &parent in &parent_positions[idx] {
+                let parent_generation = match parent.checked_sub(num_base_commits) {
+                    Some(pos) if generations[pos as usize] == 0 => {
+                        parents_are_done = false;
+                        stack.push(pos as usize);
+                        continue;
+                    }
+                    Some(pos) => generations[pos as usize],
+                    None => base
+                        .expect("parent positions below the base commit count are in the base")
+                        .commit_at(crate::Position(parent))
+                        .generation(),
+                };
+                generation = generation.max(parent_generation);
+            }
+            if parents_are_done {
+                generations[idx] = (generation + 1).min(GENERATION_NUMBER_MAX);
+                stack.pop();
+            }
+        }
+    }
+    generations
+}
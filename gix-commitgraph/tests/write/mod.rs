@@ -0,0 +1,146 @@
+use gix_commitgraph::{file::write, File, Graph};
+
+use crate::{check_common, graph_and_expected, RefInfo};
+
+type Result<T = ()> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+#[test]
+fn roundtrip_with_octopus_merges() -> Result {
+    let (expected, refs) = graph_and_expected(
+        "octopus_merges.sh",
+        &[
+            "root",
+            "parent1",
+            "parent2",
+            "parent3",
+            "parent4",
+            "three_parents",
+            "four_parents",
+        ],
+    );
+    let dir = gix_testtools::tempfile::tempdir()?;
+    let file = write_file(dir.path(), to_write_commits(refs.values()), None)?;
+    assert_eq!(file.base_graph_count(), 0);
+    assert_eq!(file.num_commits(), 7);
+
+    let actual = Graph::new(vec![file])?;
+    check_common(&actual, &refs);
+    assert_same_commits(&actual, &expected);
+    Ok(())
+}
+
+#[test]
+fn layer_on_top_of_base_graph() -> Result {
+    let (expected, refs) = graph_and_expected(
+        "octopus_merges.sh",
+        &[
+            "root",
+            "parent1",
+            "parent2",
+            "parent3",
+            "parent4",
+            "three_parents",
+            "four_parents",
+        ],
+    );
+    let dir = gix_testtools::tempfile::tempdir()?;
+    let base = write_file(
+        dir.path(),
+        to_write_commits(
+            ["root", "parent1", "parent2", "parent3", "parent4"]
+                .iter()
+                .map(|name| &refs[*name]),
+        ),
+        None,
+    )?;
+    let base_checksum = base.checksum().to_owned();
+    let base = Graph::new(vec![base])?;
+
+    let layer = write_file(dir.path(), to_write_commits(refs.values()), Some(&base))?;
+    assert_eq!(layer.base_graph_count(), 1);
+    assert_eq!(
+        layer.num_commits(),
+        2,
+        "commits that are already in the base graph are skipped"
+    );
+    assert_eq!(
+        layer.iter_base_graph_ids().collect::<Vec<_>>(),
+        [base_checksum.as_ref()]
+    );
+
+    let base = File::at(dir.path().join(format!("graph-{base_checksum}.graph")))?;
+    let actual = Graph::new(vec![base, layer])?;
+    actual.verify_integrity(|_| Ok::<_, std::convert::Infallible>(()))?;
+    assert_same_commits(&actual, &expected);
+    Ok(())
+}
+
+#[test]
+fn missing_parents_are_an_error() -> Result {
+    let (_, refs) = graph_and_expected("single_parent.sh", &["parent", "child"]);
+    let err = File::write_to(
+        to_write_commits(Some(&refs["child"])),
+        None,
+        &mut Vec::new(),
+        write::Options {
+            object_hash: gix_hash::Kind::Sha1,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, write::Error::MissingParent { .. }));
+    Ok(())
+}
+
+fn to_write_commits<'a>(refs: impl IntoIterator<Item = &'a RefInfo>) -> Vec<write::Commit> {
+    refs.into_iter()
+        .map(|info| write::Commit {
+            id: info.id().to_owned(),
+            tree: info.root_tree_id().to_owned(),
+            parents: info.parent_ids().map(ToOwned::to_owned).collect(),
+            commit_time: info.time.seconds as u64,
+        })
+        .collect()
+}
+
+fn write_file(dir: &std::path::Path, commits: Vec<write::Commit>, base: Option<&Graph>) -> Result<File> {
+    let mut buf = Vec::new();
+    let outcome = File::write_to(
+        commits,
+        base,
+        &mut buf,
+        write::Options {
+            object_hash: gix_hash::Kind::Sha1,
+        },
+    )?;
+    let path = dir.join(format!("graph-{}.graph", outcome.checksum));
+    std::fs::write(&path, buf)?;
+    Ok(File::at(path)?)
+}
+
+fn assert_same_commits(actual: &Graph, expected: &Graph) {
+    fn parent_ids(graph: &Graph, commit: gix_commitgraph::file::Commit<'_>) -> Vec<gix_hash::ObjectId> {
+        commit
+            .iter_parents()
+            .map(|pos| graph.id_at(pos.expect("valid parent")).to_owned())
+            .collect()
+    }
+
+    assert_eq!(actual.num_commits(), expected.num_commits());
+    for expected_commit in expected.iter_commits() {
+        let actual_commit = actual
+            .commit_by_id(expected_commit.id())
+            .expect("all commits are present");
+        assert_eq!(actual_commit.root_tree_id(), expected_commit.root_tree_id());
+        assert_eq!(
+            actual_commit.committer_timestamp(),
+            expected_commit.committer_timestamp()
+        );
+        assert_eq!(
+            actual_commit.generation(),
+            expected_commit.generation(),
+            "{}",
+            expected_commit.id()
+        );
+        assert_eq!(parent_ids(actual, actual_commit), parent_ids(expected, expected_commit));
+    }
+}
@@ -5,7 +5,7 @@ use gix_features::threading::OwnShared;
 
 use crate::file::Metadata;
 use crate::{
-    file::{self, rename_section, write::ends_with_newline, SectionBodyIdsLut, SectionId, SectionMut},
+    file::{self, rename_section, write::ends_with_newline, SectionId, SectionMut},
     lookup,
     parse::{section, Event, FrontMatterEvents},
     File,
@@ -256,29 +256,7 @@ impl<'event> File<'event> {
         self.section_order
             .remove(self.section_order.iter().position(|v| *v == id)?);
         let section = self.sections.remove(&id)?;
-        let lut = self
-            .section_lookup_tree
-            .get_mut(&section.header.name)
-            .expect("lookup cache still has name to be deleted");
-        // NOTE: this leaves empty lists in the data structure which our code now has to deal with.
-        for entry in lut {
-            match section.header.subsection_name.as_deref() {
-                Some(subsection_name) => {
-                    if let SectionBodyIdsLut::NonTerminal(map) = entry {
-                        if let Some(ids) = map.get_mut(subsection_name) {
-                            ids.remove(ids.iter().position(|v| *v == id).expect("present"));
-                            break;
-                        }
-                    }
-                }
-                None => {
-                    if let SectionBodyIdsLut::Terminal(ids) = entry {
-                        ids.remove(ids.iter().position(|v| *v == id).expect("present"));
-                        break;
-                    }
-                }
-            }
-        }
+        self.remove_section_id_from_lookup(id, &section.header);
         Some(section)
     }
 
@@ -306,13 +284,7 @@ impl<'event> File<'event> {
             .ok()?
             .rev()
             .find(|id| filter(self.sections.get(id).expect("each id has a section").meta()))?;
-        self.section_order.remove(
-            self.section_order
-                .iter()
-                .position(|v| *v == id)
-                .expect("known section id"),
-        );
-        self.sections.remove(&id)
+        self.remove_section_by_id(id)
     }
 
     /// Adds the provided `section` to the config, returning a mutable reference to it for immediate editing.
@@ -337,8 +309,7 @@ impl<'event> File<'event> {
             .section_ids_by_name_and_subname(name.as_ref(), subsection_name.into())?
             .next_back()
             .expect("list of sections were empty, which violates invariant");
-        let section = self.sections.get_mut(&id).expect("known section-id");
-        section.header = section::Header::new(new_name, new_subsection_name)?;
+        self.rename_section_by_id(id, section::Header::new(new_name, new_subsection_name)?);
         Ok(())
     }
 
@@ -360,8 +331,7 @@ impl<'event> File<'event> {
             .rev()
             .find(|id| filter(self.sections.get(id).expect("each id has a section").meta()))
             .ok_or(rename_section::Error::Lookup(lookup::existing::Error::KeyMissing))?;
-        let section = self.sections.get_mut(&id).expect("known section-id");
-        section.header = section::Header::new(new_name, new_subsection_name)?;
+        self.rename_section_by_id(id, section::Header::new(new_name, new_subsection_name)?);
         Ok(())
     }
 
@@ -113,6 +113,91 @@ impl<'event> File<'event> {
         new_section_id
     }
 
+    /// Change the header of the section with `id` to `header`, keeping its position but moving it to its new place
+    /// in all of our lookup structures.
+    pub(crate) fn rename_section_by_id(&mut self, id: SectionId, header: section::Header<'event>) {
+        let section = self.sections.get_mut(&id).expect("known section-id");
+        let previous_header = std::mem::replace(&mut section.header, header);
+        self.remove_section_id_from_lookup(id, &previous_header);
+
+        let section_order = &self.section_order;
+        let order_of = move |section_id| {
+            section_order
+                .iter()
+                .position(|id| *id == section_id)
+                .expect("section exists")
+        };
+        let order = order_of(id);
+        let header = &self.sections[&id].header;
+        let lookup = self.section_lookup_tree.entry(header.name.clone()).or_default();
+        let ids = match header.subsection_name.clone() {
+            Some(subsection_name) => {
+                let subsections = match lookup
+                    .iter_mut()
+                    .position(|node| matches!(node, SectionBodyIdsLut::NonTerminal(_)))
+                {
+                    Some(pos) => &mut lookup[pos],
+                    None => {
+                        lookup.push(SectionBodyIdsLut::NonTerminal(HashMap::new()));
+                        lookup.last_mut().expect("just pushed")
+                    }
+                };
+                match subsections {
+                    SectionBodyIdsLut::NonTerminal(subsections) => subsections.entry(subsection_name).or_default(),
+                    SectionBodyIdsLut::Terminal(_) => unreachable!("found or pushed a non-terminal node"),
+                }
+            }
+            None => {
+                let node = match lookup
+                    .iter_mut()
+                    .position(|node| matches!(node, SectionBodyIdsLut::Terminal(_)))
+                {
+                    Some(pos) => &mut lookup[pos],
+                    None => {
+                        lookup.push(SectionBodyIdsLut::Terminal(Vec::new()));
+                        lookup.last_mut().expect("just pushed")
+                    }
+                };
+                match node {
+                    SectionBodyIdsLut::Terminal(ids) => ids,
+                    SectionBodyIdsLut::NonTerminal(_) => unreachable!("found or pushed a terminal node"),
+                }
+            }
+        };
+        let insert_pos = ids
+            .iter()
+            .position(|candidate_id| order_of(*candidate_id) > order)
+            .unwrap_or(ids.len());
+        ids.insert(insert_pos, id);
+    }
+
+    /// Remove `id` from the lookup table entry of sections with `header`.
+    pub(crate) fn remove_section_id_from_lookup(&mut self, id: SectionId, header: &section::Header<'event>) {
+        let lut = self
+            .section_lookup_tree
+            .get_mut(&header.name)
+            .expect("lookup cache still has name to be deleted");
+        // NOTE: this leaves empty lists in the data structure which our code now has to deal with.
+        for entry in lut {
+            match header.subsection_name.as_deref() {
+                Some(subsection_name) => {
+                    if let SectionBodyIdsLut::NonTerminal(map) = entry {
+                        if let Some(ids) = map.get_mut(subsection_name) {
+                            ids.remove(ids.iter().position(|v| *v == id).expect("present"));
+                            break;
+                        }
+                    }
+                }
+                None => {
+                    if let SectionBodyIdsLut::Terminal(ids) = entry {
+                        ids.remove(ids.iter().position(|v| *v == id).expect("present"));
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
     /// Returns the mapping between section and subsection name to section ids.
     pub(crate) fn section_ids_by_name_and_subname<'a>(
         &'a self,
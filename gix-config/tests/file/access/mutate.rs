@@ -43,6 +43,20 @@ mod remove_section {
         file.section_mut_or_create_new("core", Some("name".into()))
             .expect("creation succeeds");
     }
+
+    #[test]
+    fn removal_with_filter_until_no_section_is_left() {
+        let mut file = gix_config::File::try_from("[core \"name\"]\na = 1\n[core \"name\"]\nb = 2").unwrap();
+        let mut count = 0;
+        while file
+            .remove_section_filter("core", Some("name".into()), |_| true)
+            .is_some()
+        {
+            count += 1;
+        }
+        assert_eq!(count, 2);
+        assert_eq!(file.sections().count(), 0);
+    }
 }
 mod rename_section {
     use std::borrow::Cow;
@@ -64,6 +78,30 @@ mod rename_section {
             ))
         ));
     }
+
+    #[test]
+    fn renamed_sections_can_be_found_by_their_new_name_only() -> crate::Result {
+        let mut file = gix_config::File::try_from("[a \"x\"]\nk = 1\n[b]\nk = 2\n[a \"x\"]\nk = 3")?;
+        let mut count = 0;
+        while file
+            .rename_section_filter("a", Some("x".into()), "b", None, |_| true)
+            .is_ok()
+        {
+            count += 1;
+        }
+        assert_eq!(count, 2, "each section is renamed only once");
+        assert!(file.section("a", Some("x".into())).is_err());
+        assert_eq!(
+            file.strings("b.k")
+                .expect("present")
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["1", "2", "3"],
+            "the order of sections is retained"
+        );
+        Ok(())
+    }
 }
 mod set_meta {
     use gix_config::file;
@@ -65,6 +65,7 @@ impl Cascade {
 impl Cascade {
     /// Invoke the cascade by `invoking` each program with `action`, and configuring potential prompts with `prompt` options.
     /// The latter can also be used to disable the prompt entirely when setting the `mode` to [`Disable`][gix_prompt::Mode::Disable];=.
+    /// If a [`handler`][gix_prompt::Options::handler] is set, it will be asked for missing credentials even if prompts are disabled.
     ///
     /// When _getting_ credentials, all programs are asked until the credentials are complete, stopping the cascade.
     /// When _storing_ or _erasing_ all programs are instructed in order.
@@ -128,7 +129,7 @@ impl Cascade {
                 if ctx.username.is_none() {
                     let message = ctx.to_prompt("Username");
                     prompt.mode = gix_prompt::Mode::Visible;
-                    ctx.username = gix_prompt::ask(&message, &prompt)
+                    ctx.username = gix_prompt::ask_for(gix_prompt::Kind::Username, &message, &prompt)
                         .map_err(|err| protocol::Error::Prompt {
                             prompt: message,
                             source: err,
@@ -138,7 +139,7 @@ impl Cascade {
                 if ctx.password.is_none() {
                     let message = ctx.to_prompt("Password");
                     prompt.mode = gix_prompt::Mode::Hidden;
-                    ctx.password = gix_prompt::ask(&message, &prompt)
+                    ctx.password = gix_prompt::ask_for(gix_prompt::Kind::Password, &message, &prompt)
                         .map_err(|err| protocol::Error::Prompt {
                             prompt: message,
                             source: err,
@@ -146,6 +147,16 @@ impl Cascade {
                         .into();
                 }
             }
+        } else if let Some(handler) = prompt.handler.as_deref() {
+            if let Some(ctx) = action.context_mut() {
+                ctx.url = url;
+                if ctx.username.is_none() {
+                    ctx.username = handler(gix_prompt::Kind::Username, &ctx.to_prompt("Username"), prompt.mode);
+                }
+                if ctx.password.is_none() {
+                    ctx.password = handler(gix_prompt::Kind::Password, &ctx.to_prompt("Password"), prompt.mode);
+                }
+            }
         }
 
         protocol::helper_outcome_to_result(
@@ -126,16 +126,35 @@ mod invoke {
             .extend(fixtures(["username", "password"]))
             .invoke(
                 Action::get_for_url("ssh://git@host/repo"),
-                gix_prompt::Options {
-                    mode: gix_prompt::Mode::Disable,
-                    askpass: None,
-                },
+                gix_prompt::Options::default().with_mode(gix_prompt::Mode::Disable),
             )
             .unwrap()
             .expect("credentials");
         assert_eq!(actual.identity, identity("user", ""));
     }
 
+    #[test]
+    fn prompt_handler_provides_missing_credentials_even_if_prompts_are_disabled() {
+        let actual = Cascade::default()
+            .extend(fixtures(["username"]))
+            .invoke(
+                action_get(),
+                gix_prompt::Options::default()
+                    .with_mode(gix_prompt::Mode::Disable)
+                    .with_handler(|kind, _prompt, _mode| {
+                        assert_eq!(
+                            kind,
+                            gix_prompt::Kind::Password,
+                            "the username was provided by the helper"
+                        );
+                        Some("from-handler".into())
+                    }),
+            )
+            .unwrap()
+            .expect("credentials");
+        assert_eq!(actual.identity, identity("user", "from-handler"));
+    }
+
     fn action_get() -> Action {
         Action::get_for_url("does/not/matter")
     }
@@ -151,10 +170,7 @@ mod invoke {
     fn invoke_cascade<'a>(names: impl IntoIterator<Item = &'a str>, action: Action) -> protocol::Result {
         Cascade::default().use_http_path(true).extend(fixtures(names)).invoke(
             action,
-            gix_prompt::Options {
-                mode: gix_prompt::Mode::Disable,
-                askpass: None,
-            },
+            gix_prompt::Options::default().with_mode(gix_prompt::Mode::Disable),
         )
     }
 
@@ -0,0 +1,44 @@
+//! Serialize [`Time`] as string instead of its fields, for use with `#[serde(with = "…")]`.
+//!
+//! For example, `#[serde(with = "gix_date::time::as_string::iso8601")]` serializes a time as `2022-08-17T21:43:13+08:00`.
+//! Deserialization accepts all formats that [`Time`] can be parsed from, so both representations can be read back.
+use crate::{time::Format, Time};
+
+fn serialize<S: serde::Serializer>(time: &Time, serializer: S, format: Format) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&time.format(format))
+}
+
+fn deserialize<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Time, D::Error> {
+    let time = <String as serde::Deserialize>::deserialize(deserializer)?;
+    time.parse().map_err(serde::de::Error::custom)
+}
+
+/// Serialize as [raw](crate::time::format::RAW) git time, like `1660874655 +0800`, which preserves a `-0000` offset.
+pub mod raw {
+    use crate::Time;
+
+    /// Serialize `time` with `serializer`.
+    pub fn serialize<S: serde::Serializer>(time: &Time, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serialize(time, serializer, crate::time::format::RAW)
+    }
+
+    /// Deserialize a time from `deserializer`.
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Time, D::Error> {
+        super::deserialize(deserializer)
+    }
+}
+
+/// Serialize as [strict ISO8601](crate::time::format::ISO8601_STRICT) time, like `2022-08-17T21:43:13+08:00`.
+pub mod iso8601 {
+    use crate::Time;
+
+    /// Serialize `time` with `serializer`.
+    pub fn serialize<S: serde::Serializer>(time: &Time, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serialize(time, serializer, crate::time::format::ISO8601_STRICT.into())
+    }
+
+    /// Deserialize a time from `deserializer`.
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Time, D::Error> {
+        super::deserialize(deserializer)
+    }
+}
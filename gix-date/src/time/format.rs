@@ -40,6 +40,17 @@ impl Time {
         self.format_inner(format.into())
     }
 
+    /// Format this instance according to `format`, a `strftime`-like format string as understood by [`jiff`], which
+    /// unlike a [`CustomFormat`] doesn't have to be known at compile time.
+    ///
+    /// Returns an error if `format` is invalid.
+    pub fn format_custom(&self, format: &str) -> Result<String, std::fmt::Error> {
+        use std::fmt::Write;
+        let mut out = String::new();
+        write!(out, "{}", self.to_time().strftime(format))?;
+        Ok(out)
+    }
+
     fn format_inner(&self, format: Format) -> String {
         match format {
             Format::Custom(CustomFormat(format)) => self.to_time().strftime(format).to_string(),
@@ -49,6 +60,20 @@ impl Time {
     }
 }
 
+/// Conversion
+impl Time {
+    /// Return this instance with the offset of the local timezone at this point in time, which may differ from the
+    /// current offset of the local timezone due to daylight saving time.
+    pub fn to_local(&self) -> Self {
+        let offset = jiff::Timestamp::from_second(self.seconds)
+            .expect("always valid unix time")
+            .to_zoned(jiff::tz::TimeZone::system())
+            .offset()
+            .seconds();
+        Time::new(self.seconds, offset)
+    }
+}
+
 impl Time {
     fn to_time(self) -> jiff::Zoned {
         let offset = jiff::tz::Offset::from_seconds(self.offset).expect("valid offset");
@@ -45,6 +45,9 @@ impl From<CustomFormat> for Format {
     }
 }
 
+///
+#[cfg(feature = "serde")]
+pub mod as_string;
 ///
 pub mod format;
 mod init;
@@ -67,6 +70,17 @@ mod sign {
 mod impls {
     use crate::{time::Sign, Time};
 
+    /// Parse any format understood by [`parse()`](crate::parse()) except for relative dates, which includes
+    /// the [raw](crate::time::format::RAW) and [ISO8601](crate::time::format::ISO8601_STRICT) formats
+    /// used by [`as_string`](crate::time::as_string).
+    impl std::str::FromStr for Time {
+        type Err = crate::parse::Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            crate::parse(s, None)
+        }
+    }
+
     impl Default for Time {
         fn default() -> Self {
             Time {
@@ -82,6 +82,22 @@ fn git_default() {
     );
 }
 
+#[test]
+fn custom() {
+    assert_eq!(
+        time().format_custom("%d.%m.%Y %H:%M").expect("valid"),
+        "30.11.1973 00:03"
+    );
+    assert!(time().format_custom("%Y%").is_err(), "invalid formats are an error");
+}
+
+#[test]
+fn to_local_keeps_the_point_in_time() {
+    let local = time().to_local();
+    assert_eq!(local.seconds, time().seconds);
+    assert_eq!(local.format(Format::Unix), time().format(Format::Unix));
+}
+
 fn time() -> Time {
     Time {
         seconds: 123456789,
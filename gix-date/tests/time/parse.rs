@@ -143,6 +143,29 @@ fn git_default() {
     );
 }
 
+#[test]
+fn from_str_round_trips_raw_and_iso8601_strict() -> gix_testtools::Result {
+    for time in [
+        Time::new(1660874655, 28800),
+        Time::new(1660874655, -7200),
+        Time {
+            seconds: 1660874655,
+            offset: 0,
+            sign: Sign::Minus,
+        },
+    ] {
+        assert_eq!(time.format(gix_date::time::format::RAW).parse::<Time>()?, time);
+    }
+    for time in [Time::new(1660874655, 28800), Time::new(1660874655, -7200)] {
+        assert_eq!(
+            time.format(gix_date::time::format::ISO8601_STRICT).parse::<Time>()?,
+            time
+        );
+    }
+    assert!("foobar".parse::<Time>().is_err());
+    Ok(())
+}
+
 #[test]
 fn invalid_dates_can_be_produced_without_current_time() {
     assert!(matches!(
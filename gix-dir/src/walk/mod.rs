@@ -193,6 +193,11 @@ pub struct Options<'a> {
     ///
     /// In other words, for Git compatibility this flag should be `false`, the default, for `git2` compatibility it should be `true`.
     pub symlinks_to_directories_are_ignored_like_directories: bool,
+    /// Control what happens with named pipes, sockets and other non-regular files.
+    ///
+    /// With [`Skip`](gix_fs::NonRegularFiles::Skip), the default, they are not emitted, similar to Git, and with
+    /// [`AsEmptyFile`](gix_fs::NonRegularFiles::AsEmptyFile) they are classified as [file](crate::entry::Kind::File).
+    pub non_regular_files: gix_fs::NonRegularFiles,
     /// A set of all git worktree checkouts that are located within the main worktree directory.
     ///
     /// They will automatically be detected as 'tracked', but without providing index information (as there is no actual index entry).
@@ -306,6 +311,8 @@ pub enum Error {
     DirEntryFileType { path: PathBuf, source: std::io::Error },
     #[error("Could not obtain symlink metadata on '{}'", path.display())]
     SymlinkMetadata { path: PathBuf, source: std::io::Error },
+    #[error("Encountered non-regular file at '{}'", path.display())]
+    NonRegularFile { path: PathBuf },
 }
 
 mod classify;
@@ -47,6 +47,19 @@ pub(super) fn recursive(
         // That is, this should be independent of the kind.
         num_entries += 1;
 
+        let file_type = entry.file_type().ok();
+        if file_type.map_or(false, gix_fs::NonRegularFiles::is_non_regular) {
+            match opts.non_regular_files {
+                gix_fs::NonRegularFiles::Skip => continue,
+                gix_fs::NonRegularFiles::Error => {
+                    return Err(Error::NonRegularFile {
+                        path: current.join(entry.file_name()),
+                    })
+                }
+                gix_fs::NonRegularFiles::AsEmptyFile => {}
+            }
+        }
+
         let prev_len = current_bstr.len();
         if prev_len != 0 {
             current_bstr.push(b'/');
@@ -64,7 +77,7 @@ pub(super) fn recursive(
             current_bstr,
             if prev_len == 0 { 0 } else { prev_len + 1 },
             None,
-            || entry.file_type().ok().map(Into::into),
+            || file_type.map(Into::into),
             opts,
             ctx,
         )?;
@@ -2628,6 +2628,56 @@ fn precious_are_not_expendable() {
     );
 }
 
+#[test]
+#[cfg(unix)]
+fn non_regular_files_are_skipped_by_default() -> crate::Result {
+    let root = gix_testtools::tempfile::TempDir::new()?;
+    std::fs::write(root.path().join("file"), [])?;
+    let status = std::process::Command::new("mkfifo")
+        .arg(root.path().join("fifo"))
+        .status()?;
+    assert!(status.success(), "mkfifo should be available");
+
+    let (_out, entries) = collect(root.path(), None, |keep, ctx| walk(root.path(), ctx, options(), keep));
+    assert_eq!(
+        entries,
+        [entry("file", Untracked, File)],
+        "just like Git, named pipes are invisible"
+    );
+
+    let (_out, entries) = collect(root.path(), None, |keep, ctx| {
+        walk(
+            root.path(),
+            ctx,
+            walk::Options {
+                non_regular_files: gix_fs::NonRegularFiles::AsEmptyFile,
+                ..options()
+            },
+            keep,
+        )
+    });
+    assert_eq!(
+        entries,
+        [entry("fifo", Untracked, File), entry("file", Untracked, File)],
+        "they can also be treated like files"
+    );
+
+    let err = try_collect(root.path(), None, |keep, ctx| {
+        walk(
+            root.path(),
+            ctx,
+            walk::Options {
+                non_regular_files: gix_fs::NonRegularFiles::Error,
+                ..options()
+            },
+            keep,
+        )
+    })
+    .unwrap_err();
+    assert!(matches!(err, walk::Error::NonRegularFile { path } if path == root.path().join("fifo")));
+    Ok(())
+}
+
 #[test]
 #[cfg_attr(
     not(target_vendor = "apple"),
@@ -32,6 +32,33 @@ pub struct Capabilities {
 }
 mod capabilities;
 
+/// Determines how files that are neither regular files, directories nor symlinks are handled when they are encountered
+/// in the worktree, like named pipes, sockets or device files.
+///
+/// Git can't store such files, and reading them may block indefinitely, so they are never read.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
+pub enum NonRegularFiles {
+    /// Leave them alone, which is what Git does.
+    ///
+    /// Untracked non-regular files aren't listed, a tracked file that is now a non-regular file is considered to have
+    /// changed its type, and non-regular files that are in the way during checkout are only replaced if existing
+    /// files may be overwritten.
+    #[default]
+    Skip,
+    /// Fail the operation once the first non-regular file is encountered.
+    Error,
+    /// Treat non-regular files as if they were empty regular files.
+    AsEmptyFile,
+}
+
+impl NonRegularFiles {
+    /// Return `true` if `file_type` is neither a regular file, nor a directory, nor a symlink.
+    pub fn is_non_regular(file_type: std::fs::FileType) -> bool {
+        !(file_type.is_file() || file_type.is_dir() || file_type.is_symlink())
+    }
+}
+
 mod snapshot;
 pub use snapshot::{FileSnapshot, SharedFileSnapshot, SharedFileSnapshotMut};
 
@@ -39,7 +39,9 @@ pub struct Mapping<T> {
     pub sequence_number: usize,
 }
 
-fn read_in_full_ignore_missing(path: &Path, follow_symlinks: bool, buf: &mut Vec<u8>) -> std::io::Result<bool> {
+/// Read the file at `path` into `buf` while optionally following symlinks with `follow_symlinks`, and return `true` if it was read.
+/// Return `false` if it doesn't exist or is a directory.
+pub fn read_in_full_ignore_missing(path: &Path, follow_symlinks: bool, buf: &mut Vec<u8>) -> std::io::Result<bool> {
     buf.clear();
     let file = if follow_symlinks {
         std::fs::File::open(path)
@@ -37,14 +37,14 @@ impl File {
         Ok((version, hash))
     }
 
-    /// Write ourselves to the path we were read from after acquiring a lock, using `options`.
+    /// Write ourselves to the path we were read from after acquiring a lock with [`lock_mode`](write::Options::lock_mode), using `options`.
     ///
     /// Note that the hash produced will be stored which is why we need to be mutable.
     pub fn write(&mut self, options: write::Options) -> Result<(), Error> {
         let _span = gix_features::trace::detail!("gix_index::File::write()", path = ?self.path);
         let mut lock = std::io::BufWriter::with_capacity(
             64 * 1024,
-            gix_lock::File::acquire_to_update_resource(&self.path, gix_lock::acquire::Fail::Immediately, None)?,
+            gix_lock::File::acquire_to_update_resource(&self.path, options.lock_mode, None)?,
         );
         let (version, digest) = self.write_to(&mut lock, options)?;
         match lock.into_inner() {
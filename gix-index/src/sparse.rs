@@ -0,0 +1,97 @@
+///
+pub mod expand {
+    /// The error returned by [State::expand_sparse_directories()](crate::State::expand_sparse_directories()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not expand the sparse directory entry at \"{path}\"")]
+        FromTree {
+            path: bstr::BString,
+            source: crate::init::from_tree::Error,
+        },
+    }
+}
+
+use bstr::{BStr, BString, ByteVec};
+
+use crate::{Entry, State};
+
+/// Sparse directories
+impl State {
+    /// Replace all [sparse directory entries](crate::entry::Mode::DIR) with the files contained in their trees,
+    /// looking up trees in `objects`, and return the amount of expanded directories.
+    ///
+    /// The expanded entries retain the flags of the directory they replace, which includes
+    /// [`SKIP_WORKTREE`](crate::entry::Flags::SKIP_WORKTREE), and receive a default `stat`.
+    /// Afterwards, the index isn't [sparse](State::is_sparse()) anymore and will be written as full index.
+    pub fn expand_sparse_directories(&mut self, objects: impl gix_object::Find) -> Result<usize, expand::Error> {
+        let num_expanded = self.expand_sparse_directories_by(objects, |_| true)?;
+        self.is_sparse = false;
+        Ok(num_expanded)
+    }
+
+    /// Like [`expand_sparse_directories()`](Self::expand_sparse_directories()), but only expand directory entries
+    /// for which `filter(path)` returns `true`, with `path` being the directory path including its trailing slash.
+    ///
+    /// This allows to expand only the portions of the index that an operation actually needs, for instance
+    /// all directories that are a prefix of a path to look up.
+    /// The index remains [sparse](State::is_sparse()) if any directory entry is left.
+    pub fn expand_sparse_directories_by(
+        &mut self,
+        objects: impl gix_object::Find,
+        mut filter: impl FnMut(&BStr) -> bool,
+    ) -> Result<usize, expand::Error> {
+        let _span = gix_features::trace::detail!("gix_index::State::expand_sparse_directories()");
+        let to_expand: Vec<_> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.mode.is_sparse() && filter(e.path_in(&self.path_backing)))
+            .map(|(idx, _)| idx)
+            .collect();
+        if to_expand.is_empty() {
+            return Ok(0);
+        }
+
+        let mut path = BString::default();
+        for idx in to_expand.iter().copied() {
+            let dir = self.entries[idx].clone();
+            let dir_path = dir.path_in(&self.path_backing).to_owned();
+            let tree =
+                State::from_tree(&dir.id, &objects, Default::default()).map_err(|err| expand::Error::FromTree {
+                    path: dir_path.clone(),
+                    source: err,
+                })?;
+            for entry in tree.entries() {
+                path.clear();
+                path.push_str(&dir_path);
+                if !path.is_empty() && !path.ends_with(b"/") {
+                    path.push_byte(b'/');
+                }
+                path.push_str(entry.path(&tree));
+                let path_start = self.path_backing.len();
+                self.path_backing.extend_from_slice(&path);
+                self.entries.push(Entry {
+                    stat: entry.stat,
+                    id: entry.id,
+                    flags: dir.flags,
+                    mode: entry.mode,
+                    path: path_start..self.path_backing.len(),
+                });
+            }
+        }
+
+        let mut to_remove = to_expand.iter().copied().peekable();
+        self.remove_entries(|idx, _, _| {
+            if to_remove.peek() == Some(&idx) {
+                to_remove.next();
+                true
+            } else {
+                false
+            }
+        });
+        self.sort_entries();
+        self.is_sparse = self.entries.iter().any(|e| e.mode.is_sparse());
+        Ok(to_expand.len())
+    }
+}
@@ -56,6 +56,10 @@ pub struct Options {
     /// via [`File::write()`](crate::File::write()) and [`File::write_to()`](crate::File::write_to()).
     /// Note that
     pub skip_hash: bool,
+    /// Determines how to acquire the lock for the index file when writing it with [`File::write()`](crate::File::write()).
+    ///
+    /// By default, writing fails immediately if the index is locked by another process.
+    pub lock_mode: gix_lock::acquire::Fail,
 }
 
 impl State {
@@ -66,6 +70,7 @@ impl State {
         Options {
             extensions,
             skip_hash: _,
+            lock_mode: _,
         }: Options,
     ) -> std::io::Result<Version> {
         let _span = gix_features::trace::detail!("gix_index::State::write()");
@@ -48,6 +48,7 @@ fn skip_hash() -> crate::Result {
     expected.write(Options {
         extensions: Default::default(),
         skip_hash: false,
+        ..Default::default()
     })?;
 
     let actual = gix_index::File::at(
@@ -65,6 +66,7 @@ fn skip_hash() -> crate::Result {
     expected.write(Options {
         extensions: Default::default(),
         skip_hash: true,
+        ..Default::default()
     })?;
 
     let actual = gix_index::File::at(
@@ -295,6 +297,7 @@ fn only_tree_ext() -> Options {
             tree_cache: true,
         },
         skip_hash: false,
+        ..Default::default()
     }
 }
 
@@ -302,5 +305,6 @@ fn options_with(extensions: write::Extensions) -> Options {
     Options {
         extensions,
         skip_hash: false,
+        ..Default::default()
     }
 }
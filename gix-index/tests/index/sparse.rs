@@ -0,0 +1,67 @@
+use std::path::Path;
+
+use bstr::ByteSlice;
+use gix_index::entry::Mode;
+use gix_testtools::scripted_fixture_read_only_standalone;
+
+use crate::index::Fixture;
+
+fn sparse_index_and_odb() -> crate::Result<(gix_index::File, gix_odb::Handle)> {
+    let worktree_dir = scripted_fixture_read_only_standalone(Path::new("make_index").join("v3_sparse_index.sh"))?;
+    let git_dir = worktree_dir.join(".git");
+    let index = gix_index::File::at(git_dir.join("index"), gix_hash::Kind::Sha1, false, Default::default())?;
+    Ok((index, gix_odb::at(git_dir.join("objects"))?))
+}
+
+#[test]
+fn expand_all_directories_yields_the_same_entries_as_a_full_index() -> crate::Result {
+    let (mut index, odb) = sparse_index_and_odb()?;
+    assert!(index.is_sparse());
+
+    let num_expanded = index.expand_sparse_directories(&odb)?;
+    assert_eq!(num_expanded, 2, "c1/c3/ and d/ are expanded");
+    assert!(!index.is_sparse(), "there are no directory entries left");
+    index.verify_entries()?;
+
+    let expected = Fixture::Generated("v3_skip_worktree").open();
+    assert_eq!(index.entries().len(), expected.entries().len());
+    for (actual, expected_entry) in index.entries().iter().zip(expected.entries()) {
+        assert_eq!(actual.path(&index), expected_entry.path(&expected));
+        assert_eq!(actual.id, expected_entry.id);
+        assert_eq!(actual.mode, expected_entry.mode);
+        assert_eq!(
+            actual.flags, expected_entry.flags,
+            "expanded entries inherit the skip-worktree flag of their directory"
+        );
+    }
+
+    assert_eq!(
+        index.expand_sparse_directories(&odb)?,
+        0,
+        "there is nothing left to expand"
+    );
+    Ok(())
+}
+
+#[test]
+fn expand_directories_by_filter() -> crate::Result {
+    let (mut index, odb) = sparse_index_and_odb()?;
+
+    let num_expanded = index.expand_sparse_directories_by(&odb, |dir| "d/c4/c5".as_bytes().starts_with(dir))?;
+    assert_eq!(num_expanded, 1, "only the directory containing the path is expanded");
+    assert!(index.is_sparse(), "c1/c3/ is still a directory entry");
+    index.verify_entries()?;
+
+    let dirs: Vec<_> = index
+        .entries()
+        .iter()
+        .filter(|e| e.mode == Mode::DIR)
+        .map(|e| e.path(&index).to_str_lossy().into_owned())
+        .collect();
+    assert_eq!(dirs, ["c1/c3/"]);
+    assert!(
+        index.entry_by_path("d/c4/c5".into()).is_some(),
+        "the expanded files can be looked up"
+    );
+    Ok(())
+}
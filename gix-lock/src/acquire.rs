@@ -46,14 +46,46 @@ impl From<Duration> for Fail {
 pub enum Error {
     #[error("Another IO error occurred while obtaining the lock")]
     Io(#[from] std::io::Error),
-    #[error("The lock for resource '{resource_path}' could not be obtained {mode} after {attempts} attempt(s). The lockfile at '{resource_path}{}' might need manual deletion.", super::DOT_LOCK_SUFFIX)]
+    #[error("The lock for resource '{resource_path}' could not be obtained {mode} after {attempts} attempt(s){holder}. The lockfile at '{resource_path}{}' might need manual deletion.", super::DOT_LOCK_SUFFIX)]
     PermanentlyLocked {
         resource_path: PathBuf,
         mode: Fail,
         attempts: usize,
+        holder: Holder,
     },
 }
 
+/// Information about the holder of a lock that couldn't be acquired, as far as it's known, to help diagnosing contention.
+///
+/// Note that lock files don't carry information about their owner, so the process holding the lock can't be identified.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Holder {
+    /// The time since the lock file was created or last written to, if it could be determined.
+    ///
+    /// Old lock files are likely to be [orphaned](gix_tempfile::orphan) by a process that crashed.
+    pub age: Option<Duration>,
+}
+
+impl Holder {
+    fn of_lock(lock_path: &Path) -> Self {
+        Holder {
+            age: std::fs::metadata(lock_path)
+                .and_then(|meta| meta.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok()),
+        }
+    }
+}
+
+impl fmt::Display for Holder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.age {
+            None => Ok(()),
+            Some(age) => write!(f, " (held for {:.02}s)", age.as_secs_f32()),
+        }
+    }
+}
+
 impl File {
     /// Create a writable lock file with failure `mode` whose content will eventually overwrite the given resource `at_path`.
     ///
@@ -196,15 +228,16 @@ fn lock_with_mode<T>(
             try_lock(&lock_path, directory, cleanup)
         }
     }
-    .map(|v| (lock_path, v))
     .map_err(|err| match err.kind() {
         AlreadyExists => Error::PermanentlyLocked {
             resource_path: resource.into(),
             mode,
             attempts,
+            holder: Holder::of_lock(&lock_path),
         },
         _ => Error::Io(err),
     })
+    .map(|v| (lock_path, v))
 }
 
 fn add_lock_suffix(resource_path: &Path) -> PathBuf {
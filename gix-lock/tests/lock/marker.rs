@@ -41,6 +41,21 @@ mod acquire {
         assert!(err_str.contains("the-resource.lock"), "it mentions the lockfile itself");
         Ok(())
     }
+
+    #[test]
+    fn contention_errors_provide_information_about_the_holder() -> crate::Result {
+        let dir = tempfile::tempdir()?;
+        let resource = dir.path().join("the-resource");
+        let _guard = gix_lock::Marker::acquire_to_hold_resource(&resource, Fail::Immediately, None)?;
+        let err = gix_lock::Marker::acquire_to_hold_resource(&resource, Fail::Immediately, None)
+            .expect_err("the lock is taken");
+        let gix_lock::acquire::Error::PermanentlyLocked { holder, .. } = &err else {
+            unreachable!("contention is detected: {err:?}")
+        };
+        assert!(holder.age.is_some(), "the age of the lock file is known");
+        assert!(err.to_string().contains("(held for "));
+        Ok(())
+    }
 }
 mod commit {
     use gix_lock::acquire::Fail;
@@ -24,18 +24,7 @@ impl<'a> TreeRef<'a> {
     ///
     /// Note that it's impossible to binary search by name alone as the sort order is special.
     pub fn bisect_entry(&self, name: &BStr, is_dir: bool) -> Option<EntryRef<'a>> {
-        static NULL_HASH: gix_hash::ObjectId = gix_hash::Kind::shortest().null();
-
-        let search = EntryRef {
-            mode: if is_dir {
-                tree::EntryKind::Tree
-            } else {
-                tree::EntryKind::Blob
-            }
-            .into(),
-            filename: name,
-            oid: &NULL_HASH,
-        };
+        let search = search_key(name, is_dir);
         self.entries
             .binary_search_by(|e| e.cmp(&search))
             .ok()
@@ -55,6 +44,64 @@ impl<'a> TreeRefIter<'a> {
     pub fn entries(self) -> Result<Vec<EntryRef<'a>>, crate::decode::Error> {
         self.collect()
     }
+
+    /// Find the entry named `name` without knowing if it's a directory or not, by iterating the remaining entries
+    /// without collecting them.
+    ///
+    /// As entries are sorted, the search stops as soon as all entries that could match were seen, which makes this
+    /// suitable even for trees with a huge amount of entries.
+    /// Note that the iterator is advanced past the returned entry, or the first one that sorts after `name`.
+    pub fn lookup_entry(&mut self, name: &BStr) -> Result<Option<EntryRef<'a>>, crate::decode::Error> {
+        // A directory named `name` sorts last among all entries named `name`.
+        let last_possible_match = search_key(name, true);
+        for entry in self.by_ref() {
+            let entry = entry?;
+            if entry.filename == name {
+                return Ok(Some(entry));
+            }
+            if entry > last_possible_match {
+                return Ok(None);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Advance the iterator so that the next entry it returns is the first one that doesn't sort before an entry named `name`,
+    /// which is a directory if `is_dir` is `true`, using the canonical ordering of tree entries.
+    ///
+    /// This allows to skip over entries without collecting them, for instance to join the entries of two trees
+    /// with lots of entries, or to resume an iteration. All skipped entries are parsed, but not returned.
+    pub fn seek(&mut self, name: &BStr, is_dir: bool) -> Result<(), crate::decode::Error> {
+        let search = search_key(name, is_dir);
+        while !self.data.is_empty() {
+            match decode::fast_entry(self.data) {
+                Some((data_left, entry)) if entry < search => self.data = data_left,
+                Some(_) => break,
+                None => {
+                    return Err(self
+                        .next()
+                        .and_then(Result::err)
+                        .expect("decoding fails here just like it did before"))
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Return an entry to compare other entries with for finding the position of an entry named `name`.
+fn search_key(name: &BStr, is_dir: bool) -> EntryRef<'_> {
+    static NULL_HASH: gix_hash::ObjectId = gix_hash::Kind::shortest().null();
+    EntryRef {
+        mode: if is_dir {
+            tree::EntryKind::Tree
+        } else {
+            tree::EntryKind::Blob
+        }
+        .into(),
+        filename: name,
+        oid: &NULL_HASH,
+    }
 }
 
 impl<'a> Iterator for TreeRefIter<'a> {
@@ -52,3 +52,59 @@ fn everything() -> crate::Result {
     );
     Ok(())
 }
+
+#[test]
+fn lookup_entry() -> crate::Result {
+    let data = fixture_name("tree", "everything.tree");
+    for (name, expected) in [
+        ("exe", Some("exe")),
+        ("subdir", Some("subdir")),
+        ("symlink", Some("symlink")),
+        ("a", None),
+        ("missing", None),
+        ("zzz", None),
+    ] {
+        let mut iter = TreeRefIter::from_bytes(&data);
+        let entry = iter.lookup_entry(name.into())?;
+        assert_eq!(entry.map(|e| e.filename.to_owned()), expected.map(Into::into), "{name}");
+    }
+
+    let mut iter = TreeRefIter::from_bytes(&data);
+    assert!(iter.lookup_entry("file".into())?.is_some());
+    assert_eq!(
+        iter.next().transpose()?.map(|e| e.filename),
+        Some(b"grit-submodule".as_bstr()),
+        "the iterator continues right after the found entry"
+    );
+    Ok(())
+}
+
+#[test]
+fn seek() -> crate::Result {
+    let data = fixture_name("tree", "everything.tree");
+    for (name, is_dir, expected_next) in [
+        ("", false, Some("exe")),
+        ("file", false, Some("file")),
+        ("g", false, Some("grit-submodule")),
+        ("subdir", true, Some("subdir")),
+        ("subdir", false, Some("subdir")),
+        ("subdir0", false, Some("symlink")),
+        ("zzz", false, None),
+    ] {
+        let mut iter = TreeRefIter::from_bytes(&data);
+        iter.seek(name.into(), is_dir)?;
+        assert_eq!(
+            iter.next().transpose()?.map(|e| e.filename.to_owned()),
+            expected_next.map(Into::into),
+            "{name}, is_dir = {is_dir}"
+        );
+    }
+
+    let mut iter = TreeRefIter::from_bytes(&data[..data.len() / 2]);
+    assert!(
+        iter.seek("zzz".into(), false).is_err(),
+        "decode errors are reported when seeking past them"
+    );
+    assert!(iter.next().is_none(), "the iterator is exhausted after an error");
+    Ok(())
+}
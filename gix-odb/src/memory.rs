@@ -67,6 +67,33 @@ impl Proxy<Cache<crate::store::Handle<Rc<crate::Store>>>> {
     }
 }
 
+impl<S> Proxy<Cache<crate::store::Handle<S>>>
+where
+    S: Deref<Target = crate::Store> + Clone,
+{
+    /// Return `true` for each of the given `ids` if it is contained in memory or in the object database, in the same order as `ids`.
+    ///
+    /// See [`Handle::contains_many()`](crate::store::Handle::contains_many()) for details.
+    pub fn contains_many(&self, ids: &[impl AsRef<gix_hash::oid>]) -> Vec<bool> {
+        let Some(map) = self.memory.as_ref() else {
+            return self.inner.contains_many(ids);
+        };
+        let mut out: Vec<_> = {
+            let map = map.borrow();
+            ids.iter().map(|id| map.contains_key(id.as_ref())).collect()
+        };
+        let (positions, missing): (Vec<_>, Vec<_>) = ids
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, id)| (!out[idx]).then_some((idx, id.as_ref())))
+            .unzip();
+        for (idx, present) in positions.into_iter().zip(self.inner.contains_many(&missing)) {
+            out[idx] = present;
+        }
+        out
+    }
+}
+
 impl From<crate::Handle> for Proxy<crate::Handle> {
     fn from(odb: crate::Handle) -> Self {
         let object_hash = odb.store.object_hash;
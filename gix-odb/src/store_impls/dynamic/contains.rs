@@ -0,0 +1,57 @@
+use std::ops::Deref;
+
+use gix_hash::oid;
+
+impl<S> super::Handle<S>
+where
+    S: Deref<Target = super::Store> + Clone,
+{
+    /// Return `true` for each of the given `ids` if it is contained in the object database, in the same order as `ids`.
+    ///
+    /// This is faster than calling [`contains()`](gix_pack::Find::contains()) for each id, as the ids are sorted to
+    /// search each pack index in ascending order, with each search starting where the previous one ended.
+    /// Further, each pack index is only searched for ids that weren't found yet, and indices are refreshed
+    /// at most once for all ids that are missing, subject to the [refresh mode](crate::store::RefreshMode).
+    pub fn contains_many(&self, ids: &[impl AsRef<oid>]) -> Vec<bool> {
+        let mut out = vec![false; ids.len()];
+        let mut missing: Vec<usize> = (0..ids.len()).collect();
+        missing.sort_by(|a, b| ids[*a].as_ref().cmp(ids[*b].as_ref()));
+
+        let mut snapshot = self.snapshot.borrow_mut();
+        loop {
+            for index in snapshot.indices.iter() {
+                if missing.is_empty() {
+                    return out;
+                }
+                let mut cursor = 0;
+                missing.retain(|idx| {
+                    let found = index.contains_from(ids[*idx].as_ref(), &mut cursor);
+                    out[*idx] = found;
+                    !found
+                });
+            }
+
+            for lodb in snapshot.loose_dbs.iter() {
+                if missing.is_empty() {
+                    return out;
+                }
+                missing.retain(|idx| {
+                    let found = lodb.contains(ids[*idx].as_ref());
+                    out[*idx] = found;
+                    !found
+                });
+            }
+
+            if missing.is_empty() {
+                return out;
+            }
+            match self.store.load_one_index(self.refresh, snapshot.marker) {
+                Ok(Some(new_snapshot)) => {
+                    *snapshot = new_snapshot;
+                    self.clear_cache();
+                }
+                Ok(None) | Err(_) => return out,
+            }
+        }
+    }
+}
@@ -119,6 +119,15 @@ pub(crate) mod index_lookup {
             }
         }
 
+        /// Like [`contains()`](Self::contains()), but don't search entries before `cursor` and set it to where the search ended,
+        /// which is faster when checking ids in ascending order.
+        pub(crate) fn contains_from(&self, object_id: &oid, cursor: &mut u32) -> bool {
+            match &self.file {
+                handle::SingleOrMultiIndex::Single { index, .. } => index.lookup_from(object_id, cursor).is_some(),
+                handle::SingleOrMultiIndex::Multi { index, .. } => index.lookup_from(object_id, cursor).is_some(),
+            }
+        }
+
         /// Return true if the given object id exists in this index
         pub(crate) fn oid_at_index(&self, entry_index: u32) -> &gix_hash::oid {
             match &self.file {
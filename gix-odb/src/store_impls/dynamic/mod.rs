@@ -58,6 +58,8 @@ pub mod prefix;
 
 mod header;
 
+mod contains;
+
 ///
 pub mod iter;
 
@@ -425,6 +425,37 @@ fn contains() {
     assert_eq!(handle.store_ref().structure().unwrap().len(), 4);
 }
 
+#[test]
+fn contains_many() {
+    let mut handle = db();
+    let ids = [
+        hex_to_id("dd25c539efbb0ab018caa4cda2d133285634e9b5"), // pack c043
+        hex_to_id("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"), // missing
+        hex_to_id("37d4e6c5c48ba0d245164c4e10d5f41140cab980"), // loose object
+        hex_to_id("501b297447a8255d3533c6858bb692575cdefaa0"), // the smallest pack
+        hex_to_id("dd25c539efbb0ab018caa4cda2d133285634e9b5"), // duplicate
+    ];
+    assert_eq!(
+        handle.contains_many(&ids),
+        [true, false, true, true, true],
+        "results are in the order of the input ids"
+    );
+    assert_eq!(
+        handle.store_ref().metrics().num_refreshes,
+        2,
+        "all indices are loaded once, and a single refresh happens for all missing objects"
+    );
+
+    handle.refresh_never();
+    assert_eq!(handle.contains_many(&ids[1..2]), [false]);
+    assert_eq!(
+        handle.store_ref().metrics().num_refreshes,
+        2,
+        "if no refreshes are allowed, there is no additional refresh"
+    );
+    assert!(handle.contains_many(&[] as &[gix_hash::ObjectId]).is_empty());
+}
+
 #[test]
 fn lookup() {
     let mut handle = db();
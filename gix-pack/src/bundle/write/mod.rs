@@ -318,21 +318,29 @@ impl crate::Bundle {
                         let keep_path = data_path.with_extension("keep");
 
                         std::fs::write(&keep_path, b"")?;
-                        Arc::try_unwrap(data_file)
+                        match Arc::try_unwrap(data_file)
                             .expect("only one handle left after pack was consumed")
                             .into_inner()
                             .into_inner()
                             .map_err(|err| Error::from(err.into_error()))?
-                            .persist(&data_path)?;
-                        Some(keep_path)
+                            .persist(&data_path)
+                        {
+                            Ok(_) => Some(keep_path),
+                            // A concurrent writer placed the very same pack first, and it owns the keep-file now.
+                            Err(_) if data_path.is_file() => None,
+                            Err(err) => return Err(err.into()),
+                        }
                     };
                     if !index_path.is_file() {
-                        index_file
-                            .persist(&index_path)
-                            .map_err(|err| {
+                        match index_file.persist(&index_path) {
+                            Ok(_) => {}
+                            // A concurrent writer placed the same index first, which is all we need.
+                            Err(_) if index_path.is_file() => {}
+                            Err(err) => {
                                 gix_features::trace::warn!("pack file at \"{}\" is retained despite failing to move the index file into place. You can use plumbing to make it usable.",data_path.display());
-                                err
-                            })?;
+                                return Err(err.into());
+                            }
+                        }
                     }
                     WriteOutcome {
                         outcome,
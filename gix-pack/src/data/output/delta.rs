@@ -0,0 +1,184 @@
+use std::{collections::HashMap, ops::Range};
+
+/// Configure how objects are split into chunks to find data they have in common in [`encode()`].
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Options {
+    /// No chunk is smaller than this amount of bytes, unless it's the last one.
+    pub min_chunk_size: usize,
+    /// The amount of bytes each chunk should have on average, which is rounded down to a power of two.
+    pub avg_chunk_size: usize,
+    /// No chunk is larger than this amount of bytes.
+    pub max_chunk_size: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            min_chunk_size: 256,
+            avg_chunk_size: 1024,
+            max_chunk_size: 8 * 1024,
+        }
+    }
+}
+
+/// The size of the largest copy instruction that `git` itself produces.
+const MAX_COPY_SIZE: usize = 0x10000;
+/// The size of the largest insert instruction.
+const MAX_INSERT_SIZE: usize = 0x7f;
+
+/// Produce a delta which turns `base` into `target`, or return `None` if it wouldn't be smaller than `target`.
+///
+/// Both objects are split into chunks whose boundaries are determined by their content, which is why data that was inserted
+/// or removed only affects the chunks around it. Each chunk of `target` that also exists in `base` is copied from there,
+/// and all other data is inserted. This finds shared data even if it moved by more than any delta window would cover,
+/// which makes it suitable for large binary objects that evolve over time.
+///
+/// The result can be written as delta entry into a pack, and is understood by `git`.
+pub fn encode(base: &[u8], target: &[u8], options: &Options) -> Option<Vec<u8>> {
+    if u32::try_from(base.len()).is_err() {
+        return None;
+    }
+    let mut base_chunks = HashMap::<u64, Range<usize>>::new();
+    let mut pos = 0;
+    while pos < base.len() {
+        let end = pos + chunk_len(&base[pos..], options);
+        base_chunks.entry(fingerprint(&base[pos..end])).or_insert(pos..end);
+        pos = end;
+    }
+
+    let mut out = Vec::new();
+    write_size(&mut out, base.len());
+    write_size(&mut out, target.len());
+    let mut insert_start = 0;
+    let mut copy: Option<Range<usize>> = None;
+    let mut pos = 0;
+    while pos < target.len() {
+        let end = pos + chunk_len(&target[pos..], options);
+        let chunk = &target[pos..end];
+        match base_chunks
+            .get(&fingerprint(chunk))
+            .filter(|range| &base[(*range).clone()] == chunk)
+        {
+            Some(range) => {
+                if insert_start < pos {
+                    write_insert(&mut out, &target[insert_start..pos]);
+                }
+                copy = match copy {
+                    Some(copy) if copy.end == range.start => Some(copy.start..range.end),
+                    Some(copy) => {
+                        write_copy(&mut out, copy);
+                        Some(range.clone())
+                    }
+                    None => Some(range.clone()),
+                };
+                insert_start = end;
+            }
+            None => {
+                if let Some(copy) = copy.take() {
+                    write_copy(&mut out, copy);
+                }
+            }
+        }
+        if out.len() >= target.len() {
+            return None;
+        }
+        pos = end;
+    }
+    if let Some(copy) = copy {
+        write_copy(&mut out, copy);
+    }
+    write_insert(&mut out, &target[insert_start..]);
+    (out.len() < target.len()).then_some(out)
+}
+
+/// Return the length of the first chunk of `data`, using a gear hash to find a content-defined boundary.
+fn chunk_len(data: &[u8], options: &Options) -> usize {
+    let max = data.len().min(options.max_chunk_size.max(1));
+    if max <= options.min_chunk_size {
+        return max;
+    }
+    let bits = (usize::BITS - 1).saturating_sub(options.avg_chunk_size.max(2).leading_zeros());
+    let mask = ((1u64 << bits) - 1) << (64 - bits);
+    let mut hash = 0u64;
+    for (pos, byte) in data[..max].iter().enumerate().skip(options.min_chunk_size) {
+        hash = (hash << 1).wrapping_add(GEAR[*byte as usize]);
+        if hash & mask == 0 {
+            return pos + 1;
+        }
+    }
+    max
+}
+
+/// A 64 bit FNV-1a hash to identify chunks with the same content.
+fn fingerprint(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn write_size(out: &mut Vec<u8>, mut size: usize) {
+    loop {
+        let byte = (size & 0x7f) as u8;
+        size >>= 7;
+        if size == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn write_insert(out: &mut Vec<u8>, data: &[u8]) {
+    for data in data.chunks(MAX_INSERT_SIZE) {
+        out.push(data.len() as u8);
+        out.extend_from_slice(data);
+    }
+}
+
+fn write_copy(out: &mut Vec<u8>, mut range: Range<usize>) {
+    while !range.is_empty() {
+        let size = range.len().min(MAX_COPY_SIZE);
+        let cmd_pos = out.len();
+        let mut cmd = 0x80u8;
+        out.push(cmd);
+        for (shift, flag) in [(0, 0x01), (8, 0x02), (16, 0x04), (24, 0x08)] {
+            let byte = (range.start >> shift) as u8;
+            if byte != 0 {
+                cmd |= flag;
+                out.push(byte);
+            }
+        }
+        // A size of zero means `MAX_COPY_SIZE`.
+        if size != MAX_COPY_SIZE {
+            for (shift, flag) in [(0, 0x10), (8, 0x20), (16, 0x40)] {
+                let byte = (size >> shift) as u8;
+                if byte != 0 {
+                    cmd |= flag;
+                    out.push(byte);
+                }
+            }
+        }
+        out[cmd_pos] = cmd;
+        range.start += size;
+    }
+}
+
+/// Random values for each byte, as used by the gear hash.
+static GEAR: [u64; 256] = gear_table();
+
+const fn gear_table() -> [u64; 256] {
+    let mut out = [0u64; 256];
+    let mut state = 0u64;
+    let mut idx = 0;
+    while idx < out.len() {
+        // splitmix64
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut value = state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        out[idx] = value ^ (value >> 31);
+        idx += 1;
+    }
+    out
+}
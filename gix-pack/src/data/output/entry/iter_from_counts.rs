@@ -1,5 +1,5 @@
 pub(crate) mod function {
-    use std::{cmp::Ordering, sync::Arc};
+    use std::{cmp::Ordering, collections::VecDeque, sync::Arc};
 
     use gix_features::{
         parallel,
@@ -10,7 +10,7 @@ pub(crate) mod function {
         },
     };
 
-    use super::{reduce, util, Error, Mode, Options, Outcome, ProgressId};
+    use super::{reduce, util, Error, LargeBlobDeltas, Mode, Options, Outcome, ProgressId};
     use crate::data::output;
 
     /// Given a known list of object `counts`, calculate entries ready to be put into a data pack.
@@ -52,6 +52,7 @@ pub(crate) mod function {
             allow_thin_pack,
             thread_limit,
             chunk_size,
+            large_blob_deltas,
         }: Options,
     ) -> impl Iterator<Item = Result<(SequenceId, Vec<output::Entry>), Error>>
            + parallel::reduce::Finalize<Reduce = reduce::Statistics<Error>>
@@ -157,12 +158,14 @@ pub(crate) mod function {
                 let counts = Arc::clone(&counts);
                 move |(chunk_id, chunk_range): (SequenceId, std::ops::Range<usize>), (buf, progress)| {
                     let mut out = Vec::new();
+                    let first_object_index = chunk_range.start;
                     let chunk = &counts[chunk_range];
                     let mut stats = Outcome::default();
+                    let mut window = VecDeque::new();
                     let mut pack_offsets_to_id = None;
                     progress.init(Some(chunk.len()), gix_features::progress::count("objects"));
 
-                    for count in chunk.iter() {
+                    for (object_index, count) in (first_object_index..).zip(chunk.iter()) {
                         out.push(match count
                             .entry_pack_location
                             .as_ref()
@@ -214,7 +217,14 @@ pub(crate) mod function {
                                     None => match db.try_find(&count.id, buf).map_err(Error::Find)? {
                                         Some((obj, _location)) => {
                                             stats.decoded_and_recompressed_objects += 1;
-                                            output::Entry::from_data(count, &obj)
+                                            entry_from_data(
+                                                count,
+                                                &obj,
+                                                object_index,
+                                                large_blob_deltas.as_ref(),
+                                                &mut window,
+                                                &mut stats,
+                                            )
                                         }
                                         None => {
                                             stats.missing_objects += 1;
@@ -226,7 +236,14 @@ pub(crate) mod function {
                             None => match db.try_find(&count.id, buf).map_err(Error::Find)? {
                                 Some((obj, _location)) => {
                                     stats.decoded_and_recompressed_objects += 1;
-                                    output::Entry::from_data(count, &obj)
+                                    entry_from_data(
+                                        count,
+                                        &obj,
+                                        object_index,
+                                        large_blob_deltas.as_ref(),
+                                        &mut window,
+                                        &mut stats,
+                                    )
                                 }
                                 None => {
                                     stats.missing_objects += 1;
@@ -242,6 +259,48 @@ pub(crate) mod function {
             reduce::Statistics::default(),
         )
     }
+
+    /// Create an entry for `obj` at `object_index`, which is a delta against one of the large blobs in `window` if
+    /// `large_blob_deltas` are enabled and that is smaller, and remember `obj` as potential base for the following objects.
+    fn entry_from_data(
+        count: &output::Count,
+        obj: &gix_object::Data<'_>,
+        object_index: usize,
+        large_blob_deltas: Option<&LargeBlobDeltas>,
+        window: &mut VecDeque<(usize, Vec<u8>)>,
+        stats: &mut Outcome,
+    ) -> Result<output::Entry, output::entry::Error> {
+        let Some(options) = large_blob_deltas.filter(|options| {
+            obj.kind == gix_object::Kind::Blob && obj.data.len() >= options.min_size && options.window > 0
+        }) else {
+            return output::Entry::from_data(count, obj);
+        };
+        let best_delta = window
+            .iter()
+            .filter(|(_, base)| base.len() / 2 <= obj.data.len() && obj.data.len() / 2 <= base.len())
+            .filter_map(|(base_index, base)| {
+                output::delta::encode(base, obj.data, &options.chunking).map(|delta| (*base_index, delta))
+            })
+            .min_by_key(|(_, delta)| delta.len());
+        let entry = match best_delta {
+            Some((base_index, delta)) => {
+                stats.deltified_objects += 1;
+                output::Entry::from_delta(
+                    count,
+                    output::entry::Kind::DeltaRef {
+                        object_index: base_index,
+                    },
+                    &delta,
+                )?
+            }
+            None => output::Entry::from_data(count, obj)?,
+        };
+        if window.len() == options.window {
+            window.pop_front();
+        }
+        window.push_back((object_index, obj.data.to_vec()));
+        Ok(entry)
+    }
 }
 
 mod util {
@@ -335,6 +394,9 @@ mod types {
         pub objects_copied_from_pack: usize,
         /// The amount of objects that ref to their base as ref-delta, an indication for a thin back being created.
         pub ref_delta_objects: usize,
+        /// The amount of decoded objects that were stored as delta against another object in the pack, as configured
+        /// with [`Options::large_blob_deltas`].
+        pub deltified_objects: usize,
     }
 
     impl Outcome {
@@ -345,12 +407,14 @@ mod types {
                 missing_objects,
                 objects_copied_from_pack,
                 ref_delta_objects,
+                deltified_objects,
             }: Self,
         ) {
             self.decoded_and_recompressed_objects += decoded_objects;
             self.missing_objects += missing_objects;
             self.objects_copied_from_pack += objects_copied_from_pack;
             self.ref_delta_objects += ref_delta_objects;
+            self.deltified_objects += deltified_objects;
         }
     }
 
@@ -385,6 +449,35 @@ mod types {
         pub chunk_size: usize,
         /// The pack data version to produce for each entry
         pub version: crate::data::Version,
+        /// If set, large blobs that are decoded are stored as delta against one of the previous large blobs in the same chunk,
+        /// if that is smaller. It's `None` by default, which stores all decoded objects as base objects.
+        pub large_blob_deltas: Option<LargeBlobDeltas>,
+    }
+
+    /// Configuration for storing large blobs as deltas against each other, for use in [`Options::large_blob_deltas`].
+    ///
+    /// Deltas are produced with [content-defined chunking](crate::data::output::delta::encode()), which finds data that
+    /// large binary objects have in common even if it moved far, and which is fast enough for objects of many megabytes.
+    /// Note that up to `window` blobs are kept in memory per thread.
+    #[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct LargeBlobDeltas {
+        /// Blobs smaller than this amount of bytes are always stored as base objects.
+        pub min_size: usize,
+        /// The amount of previous large blobs to try as base for each large blob.
+        pub window: usize,
+        /// How to split blobs into chunks to find the data they have in common.
+        pub chunking: crate::data::output::delta::Options,
+    }
+
+    impl Default for LargeBlobDeltas {
+        fn default() -> Self {
+            LargeBlobDeltas {
+                min_size: 512 * 1024,
+                window: 10,
+                chunking: Default::default(),
+            }
+        }
     }
 
     impl Default for Options {
@@ -395,6 +488,7 @@ mod types {
                 allow_thin_pack: false,
                 chunk_size: 10,
                 version: Default::default(),
+                large_blob_deltas: None,
             }
         }
     }
@@ -429,4 +523,4 @@ mod types {
         }
     }
 }
-pub use types::{Error, Mode, Options, Outcome, ProgressId};
+pub use types::{Error, LargeBlobDeltas, Mode, Options, Outcome, ProgressId};
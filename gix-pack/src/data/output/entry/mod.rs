@@ -31,7 +31,7 @@ pub enum Kind {
     },
 }
 
-/// The error returned by [`output::Entry::from_data()`].
+/// The error returned by [`output::Entry::from_data()`] and [`output::Entry::from_delta()`].
 #[allow(missing_docs)]
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -137,17 +137,23 @@ impl output::Entry {
             id: count.id.to_owned(),
             kind: Kind::Base(obj.kind),
             decompressed_size: obj.data.len(),
-            compressed_data: {
-                let mut out = gix_features::zlib::stream::deflate::Write::new(Vec::new());
-                if let Err(err) = std::io::copy(&mut &*obj.data, &mut out) {
-                    match err.kind() {
-                        std::io::ErrorKind::Other => return Err(Error::ZlibDeflate(err)),
-                        err => unreachable!("Should never see other errors than zlib, but got {:?}", err,),
-                    }
-                };
-                out.flush()?;
-                out.into_inner()
-            },
+            compressed_data: deflate(obj.data)?,
+        })
+    }
+
+    /// Create a new instance for the object identified by `count` from a `delta` against the base object identified by `kind`,
+    /// as produced by [`output::delta::encode()`].
+    ///
+    /// # Panics
+    ///
+    /// If `kind` is a base object.
+    pub fn from_delta(count: &output::Count, kind: Kind, delta: &[u8]) -> Result<Self, Error> {
+        assert!(!matches!(kind, Kind::Base(_)), "BUG: deltas need a base object");
+        Ok(output::Entry {
+            id: count.id.to_owned(),
+            kind,
+            decompressed_size: delta.len(),
+            compressed_data: deflate(delta)?,
         })
     }
 
@@ -184,3 +190,15 @@ impl output::Entry {
         }
     }
 }
+
+fn deflate(data: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut out = gix_features::zlib::stream::deflate::Write::new(Vec::new());
+    if let Err(err) = std::io::copy(&mut &*data, &mut out) {
+        match err.kind() {
+            std::io::ErrorKind::Other => return Err(Error::ZlibDeflate(err)),
+            err => unreachable!("Should never see other errors than zlib, but got {:?}", err,),
+        }
+    };
+    out.flush()?;
+    Ok(out.into_inner())
+}
@@ -39,3 +39,6 @@ pub mod entry;
 
 ///
 pub mod bytes;
+
+/// Produce deltas between objects with content-defined chunking.
+pub mod delta;
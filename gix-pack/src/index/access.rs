@@ -127,6 +127,13 @@ impl index::File {
         lookup(id.as_ref(), &self.fan, &|idx| self.oid_at_index(idx))
     }
 
+    /// Like [`lookup()`](Self::lookup()), but don't search entries before `cursor`, and set it to where the search ended.
+    ///
+    /// This makes looking up many ids faster if they are sorted in ascending order, with `cursor` starting at `0`.
+    pub fn lookup_from(&self, id: impl AsRef<gix_hash::oid>, cursor: &mut EntryIndex) -> Option<EntryIndex> {
+        lookup_from(id.as_ref(), cursor, &self.fan, &|idx| self.oid_at_index(idx))
+    }
+
     /// Given a `prefix`, find an object that matches it uniquely within this index and return `Some(Ok(entry_index))`.
     /// If there is more than one object matching the object `Some(Err(())` is returned.
     ///
@@ -274,10 +281,20 @@ pub(crate) fn lookup<'a>(
     id: &gix_hash::oid,
     fan: &[u32; FAN_LEN],
     oid_at_index: &dyn Fn(EntryIndex) -> &'a gix_hash::oid,
+) -> Option<EntryIndex> {
+    lookup_from(id, &mut 0, fan, oid_at_index)
+}
+
+pub(crate) fn lookup_from<'a>(
+    id: &gix_hash::oid,
+    cursor: &mut EntryIndex,
+    fan: &[u32; FAN_LEN],
+    oid_at_index: &dyn Fn(EntryIndex) -> &'a gix_hash::oid,
 ) -> Option<EntryIndex> {
     let first_byte = id.first_byte() as usize;
     let mut upper_bound = fan[first_byte];
-    let mut lower_bound = if first_byte != 0 { fan[first_byte - 1] } else { 0 };
+    let fan_lower_bound = if first_byte != 0 { fan[first_byte - 1] } else { 0 };
+    let mut lower_bound = fan_lower_bound.max(*cursor).min(upper_bound);
 
     while lower_bound < upper_bound {
         let mid = (lower_bound + upper_bound) / 2;
@@ -286,9 +303,13 @@ pub(crate) fn lookup<'a>(
         use std::cmp::Ordering::*;
         match id.cmp(mid_sha) {
             Less => upper_bound = mid,
-            Equal => return Some(mid),
+            Equal => {
+                *cursor = mid;
+                return Some(mid);
+            }
             Greater => lower_bound = mid + 1,
         }
     }
+    *cursor = lower_bound;
     None
 }
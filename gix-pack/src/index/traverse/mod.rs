@@ -219,6 +219,8 @@ where
                 kind: object_kind,
             });
         }
+    }
+    if check.crc32_checksum() {
         if let Some(desired_crc32) = index_entry.crc32 {
             let actual_crc32 = pack_entry_crc32();
             if actual_crc32 != desired_crc32 {
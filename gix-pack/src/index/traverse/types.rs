@@ -53,7 +53,13 @@ pub enum SafetyCheck {
     /// Don't verify the validity of the checksums stored in the index and pack file
     SkipFileChecksumVerification,
 
-    /// All of the above, and also don't perform any object checksum verification
+    /// All of the above, and don't hash objects to verify their id either, but still verify the CRC32 of each compressed
+    /// pack entry if the index provides it.
+    ///
+    /// This is considerably faster for read-heavy workloads on trusted data, while still detecting most corruption on disk.
+    SkipFileChecksumAndObjectHashVerification,
+
+    /// All of the above, and also don't perform any object checksum verification, including the CRC32.
     SkipFileAndObjectChecksumVerification,
 
     /// All of the above, and only log object decode errors.
@@ -74,10 +80,14 @@ impl SafetyCheck {
     pub(crate) fn object_checksum(&self) -> bool {
         matches!(self, SafetyCheck::All | SafetyCheck::SkipFileChecksumVerification)
     }
+    pub(crate) fn crc32_checksum(&self) -> bool {
+        self.object_checksum() || matches!(self, SafetyCheck::SkipFileChecksumAndObjectHashVerification)
+    }
     pub(crate) fn fatal_decode_error(&self) -> bool {
         match self {
             SafetyCheck::All
             | SafetyCheck::SkipFileChecksumVerification
+            | SafetyCheck::SkipFileChecksumAndObjectHashVerification
             | SafetyCheck::SkipFileAndObjectChecksumVerification => true,
             SafetyCheck::SkipFileAndObjectChecksumVerificationAndNoAbortOnDecodeError => false,
         }
@@ -101,6 +101,13 @@ impl File {
         crate::index::access::lookup(id.as_ref(), &self.fan, &|idx| self.oid_at_index(idx))
     }
 
+    /// Like [`lookup()`](Self::lookup()), but don't search entries before `cursor`, and set it to where the search ended.
+    ///
+    /// This makes looking up many ids faster if they are sorted in ascending order, with `cursor` starting at `0`.
+    pub fn lookup_from(&self, id: impl AsRef<gix_hash::oid>, cursor: &mut EntryIndex) -> Option<EntryIndex> {
+        crate::index::access::lookup_from(id.as_ref(), cursor, &self.fan, &|idx| self.oid_at_index(idx))
+    }
+
     /// Given the `index` ranging from 0 to [File::num_objects()], return the pack index and its absolute offset into the pack.
     ///
     /// The pack-index refers to an entry in the [`index_names`][File::index_names()] list, from which the pack can be derived.
@@ -0,0 +1,78 @@
+use gix_pack::data::{output, output::entry};
+
+fn pseudo_random_bytes(len: usize, mut state: u64) -> Vec<u8> {
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+#[test]
+fn encode_finds_moved_and_shared_data() {
+    let base = pseudo_random_bytes(300_000, 42);
+    let mut target = base.clone();
+    target.splice(100_000..100_000, pseudo_random_bytes(1_000, 7));
+    target.drain(200_000..210_000);
+    target.rotate_left(50_000);
+
+    let delta = output::delta::encode(&base, &target, &Default::default()).expect("much in common");
+    assert!(
+        delta.len() < target.len() / 50,
+        "only the inserted data and copy instructions are stored: {} bytes",
+        delta.len()
+    );
+    assert_eq!(
+        output::delta::encode(&base, &pseudo_random_bytes(300_000, 1), &Default::default()),
+        None,
+        "deltas against unrelated objects aren't smaller than the object itself"
+    );
+}
+
+#[test]
+fn delta_entries_can_be_decoded_from_a_written_pack() -> crate::Result {
+    let base = pseudo_random_bytes(100_000, 42);
+    let mut target = base.clone();
+    target.splice(50_000..50_000, pseudo_random_bytes(100, 7));
+    let count = |data: &[u8]| {
+        output::Count::from_data(
+            gix_object::compute_hash(gix_hash::Kind::Sha1, gix_object::Kind::Blob, data),
+            None,
+        )
+    };
+
+    let delta = output::delta::encode(&base, &target, &Default::default()).expect("delta is smaller");
+    let entries = vec![
+        output::Entry::from_data(&count(&base), &gix_object::Data::new(gix_object::Kind::Blob, &base))?,
+        output::Entry::from_delta(&count(&target), entry::Kind::DeltaRef { object_index: 0 }, &delta)?,
+    ];
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let pack_path = tmp.path().join("new.pack");
+    for written in output::bytes::FromEntriesIter::new(
+        std::iter::once(Ok::<_, entry::iter_from_counts::Error>(entries)),
+        std::fs::File::create(&pack_path)?,
+        2,
+        gix_pack::data::Version::V2,
+        gix_hash::Kind::Sha1,
+    ) {
+        written?;
+    }
+
+    let pack = gix_pack::data::File::at(&pack_path, gix_hash::Kind::Sha1)?;
+    let delta_offset = pack.streaming_iter()?.nth(1).expect("two entries")?.pack_offset;
+    let mut out = Vec::new();
+    let outcome = pack.decode_entry(
+        pack.entry(delta_offset)?,
+        &mut out,
+        &mut Default::default(),
+        &|_, _| None,
+        &mut gix_pack::cache::Never,
+    )?;
+    assert_eq!(outcome.kind, gix_object::Kind::Blob);
+    assert_eq!(outcome.num_deltas, 1);
+    assert!(out == target, "the delta reproduces the target object");
+    Ok(())
+}
@@ -42,3 +42,4 @@ fn db(kind: DbKind) -> crate::Result<gix_odb::HandleArc> {
 }
 
 mod count_and_entries;
+mod delta;
@@ -224,6 +224,27 @@ mod version {
             Ok(())
         }
 
+        #[test]
+        fn lookup_from() -> Result<(), Box<dyn std::error::Error>> {
+            let file = index::File::at(fixture_path(INDEX_V2), gix_hash::Kind::Sha1)?;
+            let mut cursor = 0;
+            for (entry_index, entry) in file.iter().enumerate() {
+                assert_eq!(file.lookup_from(entry.oid, &mut cursor), Some(entry_index as u32));
+                assert_eq!(cursor, entry_index as u32, "the cursor points to the last match");
+            }
+
+            let mut cursor = 0;
+            let missing = gix_hash::ObjectId::from_hex(b"0000000000000000000000000000000000000000")?;
+            assert_eq!(file.lookup_from(missing, &mut cursor), None);
+            let first = file.oid_at_index(0);
+            assert_eq!(
+                file.lookup_from(first, &mut cursor),
+                Some(0),
+                "a missing id leaves the cursor where it would have been"
+            );
+            Ok(())
+        }
+
         #[test]
         fn lookup_missing() {
             let file = index::File::at(&fixture_path(INDEX_V2), gix_hash::Kind::Sha1).unwrap();
@@ -261,6 +282,53 @@ fn traverse_with_index_and_forward_ref_deltas() {
     assert_eq!(count.load(Ordering::SeqCst), 9, "we traverse all objects");
 }
 
+#[test]
+fn traverse_safety_checks_control_crc32_and_object_hash_verification() -> Result<(), Box<dyn std::error::Error>> {
+    let mut index_data = std::fs::read(fixture_path(SMALL_PACK_INDEX))?;
+    let pack = pack::data::File::at(fixture_path(SMALL_PACK), gix_hash::Kind::Sha1)?;
+    let num_objects = pack.num_objects() as usize;
+    let first_crc32_offset = 8 /* header */ + 256 * 4 /* fan */ + num_objects * SHA1_SIZE;
+    index_data[first_crc32_offset] ^= 0xff;
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let index_path = tmp.path().join("pack.idx");
+    std::fs::write(&index_path, index_data)?;
+    let idx = index::File::at(index_path, gix_hash::Kind::Sha1)?;
+
+    let traverse = |check: index::traverse::SafetyCheck| {
+        idx.traverse(
+            &pack,
+            &mut progress::Discard,
+            &AtomicBool::new(false),
+            |_, _, _, _| Ok::<_, std::io::Error>(()),
+            index::traverse::Options {
+                check,
+                ..Default::default()
+            },
+        )
+    };
+    for check in [
+        index::traverse::SafetyCheck::SkipFileChecksumVerification,
+        index::traverse::SafetyCheck::SkipFileChecksumAndObjectHashVerification,
+    ] {
+        assert!(
+            matches!(traverse(check), Err(index::traverse::Error::Crc32Mismatch { .. })),
+            "{check:?} verifies the CRC32 of each entry"
+        );
+    }
+    let outcome = traverse(index::traverse::SafetyCheck::SkipFileAndObjectChecksumVerification)?;
+    assert_eq!(
+        outcome
+            .statistics
+            .objects_per_chain_length
+            .values()
+            .map(|v| *v as usize)
+            .sum::<usize>(),
+        num_objects,
+        "without checks, the corrupted CRC32 goes unnoticed"
+    );
+    Ok(())
+}
+
 use gix_features::progress;
 use gix_pack::{cache, data::decode::entry::Outcome, index};
 use maplit::btreemap;
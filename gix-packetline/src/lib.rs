@@ -1,6 +1,8 @@
 //! Read and write the git packet line wire format without copying it.
 //!
 //! For reading the packet line format use the [`StreamingPeekableIter`], and for writing the [`Writer`].
+//! Data multiplexed over side-band channels can be read with [`read::WithSidebands`], and written using the
+//! [`SidebandWriter`]. Special lines like flush packets can be written with the functions in the [`encode`] module.
 //! ## Feature Flags
 #![cfg_attr(
     all(doc, all(doc, feature = "document-features")),
@@ -12,6 +14,9 @@
 const U16_HEX_BYTES: usize = 4;
 const MAX_DATA_LEN: usize = 65516;
 const MAX_LINE_LEN: usize = MAX_DATA_LEN + U16_HEX_BYTES;
+/// The maximum length of a line when using `side-band` instead of `side-band-64k`.
+#[cfg(any(feature = "async-io", feature = "blocking-io"))]
+const MAX_SMALL_SIDEBAND_LINE_LEN: usize = 1000;
 const FLUSH_LINE: &[u8] = b"0000";
 const DELIMITER_LINE: &[u8] = b"0001";
 const RESPONSE_END_LINE: &[u8] = b"0002";
@@ -37,9 +42,9 @@ pub mod read;
 #[cfg(any(feature = "async-io", feature = "blocking-io"))]
 mod write;
 #[cfg(all(not(feature = "blocking-io"), feature = "async-io"))]
-pub use write::async_io::Writer;
+pub use write::async_io::{SidebandWriter, Writer};
 #[cfg(feature = "blocking-io")]
-pub use write::blocking_io::Writer;
+pub use write::blocking_io::{SidebandWriter, Writer};
 
 /// A borrowed packet line as it refers to a slice of data by reference.
 #[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
@@ -6,7 +6,7 @@ use std::{
 
 use futures_io::AsyncWrite;
 
-use crate::{encode, MAX_DATA_LEN, U16_HEX_BYTES};
+use crate::{encode, Channel, MAX_DATA_LEN, U16_HEX_BYTES};
 
 pin_project_lite::pin_project! {
     /// An implementor of [`Write`][io::Write] which passes all input to an inner `Write` in packet line data encoding,
@@ -43,6 +43,28 @@ impl<T: AsyncWrite + Unpin> Writer<T> {
     }
 }
 
+/// Special lines
+impl<T: AsyncWrite + Unpin> Writer<T> {
+    /// Write a flush packet, typically to end a message.
+    pub async fn write_flush(&mut self) -> io::Result<()> {
+        encode::flush_to_write(&mut self.inner.writer).await.map(|_| ())
+    }
+    /// Write a delimiter packet to separate sections of a message.
+    pub async fn write_delimiter(&mut self) -> io::Result<()> {
+        encode::delim_to_write(&mut self.inner.writer).await.map(|_| ())
+    }
+    /// Write a response-end packet to signal the end of a response in stateless protocols.
+    pub async fn write_response_end(&mut self) -> io::Result<()> {
+        encode::response_end_to_write(&mut self.inner.writer).await.map(|_| ())
+    }
+    /// Write an error line with the given `message`, which causes the receiving side to abort.
+    pub async fn write_error(&mut self, message: &[u8]) -> io::Result<()> {
+        encode::error_to_write(message, &mut self.inner.writer)
+            .await
+            .map(|_| ())
+    }
+}
+
 /// Non-IO methods
 impl<T> Writer<T> {
     /// If called, each call to [`write()`][io::Write::write()] will write bytes as is.
@@ -95,3 +117,92 @@ impl<T: AsyncWrite + Unpin> AsyncWrite for Writer<T> {
         self.project().inner.poll_close(cx)
     }
 }
+
+pin_project_lite::pin_project! {
+    /// An implementor of [`AsyncWrite`] which multiplexes all input into a side-band `channel` of an inner `AsyncWrite`,
+    /// using as many lines as it takes if the data doesn't fit into the maximum allowed line length.
+    pub struct SidebandWriter<T> {
+        #[pin]
+        inner: encode::LineWriter<'static, T>,
+        state: State,
+        pub(super) channel: Channel,
+        pub(super) max_band_data_len: usize,
+    }
+}
+
+impl<T: AsyncWrite + Unpin> SidebandWriter<T> {
+    /// Create a new instance from the given `write` which writes all data into the given side-band `channel`.
+    pub fn new(write: T, channel: Channel) -> Self {
+        SidebandWriter {
+            inner: encode::LineWriter::new(write, super::band_prefix(channel), &[]),
+            state: State::Idle,
+            channel,
+            max_band_data_len: super::max_band_data_len(false),
+        }
+    }
+
+    /// Return the inner writer, consuming self.
+    pub fn into_inner(self) -> T {
+        self.inner.into_inner()
+    }
+
+    /// Return a mutable reference to the inner writer, useful if packet lines should be serialized directly.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner.writer
+    }
+
+    /// Write a flush packet, typically to signal the end of the side-band multiplexed data.
+    pub async fn write_flush(&mut self) -> io::Result<()> {
+        encode::flush_to_write(&mut self.inner.writer).await.map(|_| ())
+    }
+}
+
+/// Non-IO methods
+impl<T> SidebandWriter<T> {
+    /// Write all future data into `channel`, which is useful to interleave data with progress or error messages.
+    pub fn set_channel(&mut self, channel: Channel) -> &mut Self {
+        self.channel = channel;
+        self.inner.prefix = super::band_prefix(channel);
+        self
+    }
+}
+
+impl<T: AsyncWrite + Unpin> AsyncWrite for SidebandWriter<T> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let mut this = self.project();
+        loop {
+            match this.state {
+                State::Idle => {
+                    if buf.is_empty() {
+                        return Poll::Ready(Err(io::Error::new(
+                            io::ErrorKind::Other,
+                            "empty packet lines are not permitted as '0004' is invalid",
+                        )));
+                    }
+                    *this.state = State::WriteData(0);
+                }
+                State::WriteData(written) => {
+                    while *written != buf.len() {
+                        let data = &buf[*written..*written + (buf.len() - *written).min(*this.max_band_data_len)];
+                        let n = futures_lite::ready!(this.inner.as_mut().poll_write(cx, data))?;
+                        if n == 0 {
+                            return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+                        }
+                        *written += n;
+                        *written -= U16_HEX_BYTES + this.inner.prefix.len();
+                    }
+                    *this.state = State::Idle;
+                    return Poll::Ready(Ok(buf.len()));
+                }
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_close(cx)
+    }
+}
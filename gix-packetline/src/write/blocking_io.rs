@@ -1,6 +1,6 @@
 use std::io;
 
-use crate::{MAX_DATA_LEN, U16_HEX_BYTES};
+use crate::{encode, Channel, MAX_DATA_LEN, U16_HEX_BYTES};
 
 /// An implementor of [`Write`][io::Write] which passes all input to an inner `Write` in packet line data encoding,
 /// one line per `write(…)` call or as many lines as it takes if the data doesn't fit into the maximum allowed line length.
@@ -41,6 +41,26 @@ impl<T> Writer<T> {
     }
 }
 
+/// Special lines
+impl<T: io::Write> Writer<T> {
+    /// Write a flush packet, typically to end a message.
+    pub fn write_flush(&mut self) -> io::Result<()> {
+        encode::flush_to_write(&mut self.inner).map(|_| ())
+    }
+    /// Write a delimiter packet to separate sections of a message.
+    pub fn write_delimiter(&mut self) -> io::Result<()> {
+        encode::delim_to_write(&mut self.inner).map(|_| ())
+    }
+    /// Write a response-end packet to signal the end of a response in stateless protocols.
+    pub fn write_response_end(&mut self) -> io::Result<()> {
+        encode::response_end_to_write(&mut self.inner).map(|_| ())
+    }
+    /// Write an error line with the given `message`, which causes the receiving side to abort.
+    pub fn write_error(&mut self, message: &[u8]) -> io::Result<()> {
+        encode::error_to_write(message, &mut self.inner).map(|_| ())
+    }
+}
+
 impl<T: io::Write> io::Write for Writer<T> {
     fn write(&mut self, mut buf: &[u8]) -> io::Result<usize> {
         if buf.is_empty() {
@@ -69,3 +89,64 @@ impl<T: io::Write> io::Write for Writer<T> {
         self.inner.flush()
     }
 }
+
+/// An implementor of [`Write`][io::Write] which multiplexes all input into a side-band `channel` of an inner `Write`,
+/// using as many lines as it takes if the data doesn't fit into the maximum allowed line length.
+pub struct SidebandWriter<T> {
+    /// the `Write` implementation to which to propagate packet lines
+    inner: T,
+    pub(super) channel: Channel,
+    pub(super) max_band_data_len: usize,
+}
+
+impl<T: io::Write> SidebandWriter<T> {
+    /// Create a new instance from the given `write` which writes all data into the given side-band `channel`.
+    pub fn new(write: T, channel: Channel) -> Self {
+        SidebandWriter {
+            inner: write,
+            channel,
+            max_band_data_len: super::max_band_data_len(false),
+        }
+    }
+
+    /// Write a flush packet, typically to signal the end of the side-band multiplexed data.
+    pub fn write_flush(&mut self) -> io::Result<()> {
+        encode::flush_to_write(&mut self.inner).map(|_| ())
+    }
+}
+
+/// Non-IO methods
+impl<T> SidebandWriter<T> {
+    /// Write all future data into `channel`, which is useful to interleave data with progress or error messages.
+    pub fn set_channel(&mut self, channel: Channel) -> &mut Self {
+        self.channel = channel;
+        self
+    }
+    /// Return the inner writer, consuming self.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+    /// Return a mutable reference to the inner writer, useful if packet lines should be serialized directly.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+impl<T: io::Write> io::Write for SidebandWriter<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "empty packet lines are not permitted as '0004' is invalid",
+            ));
+        }
+        for data in buf.chunks(self.max_band_data_len) {
+            encode::band_to_write(self.channel, data, &mut self.inner)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Writer;
+use crate::{Channel, SidebandWriter, Writer, MAX_DATA_LEN, MAX_SMALL_SIDEBAND_LINE_LEN, U16_HEX_BYTES};
 
 #[cfg(all(not(feature = "blocking-io"), feature = "async-io"))]
 pub(crate) mod async_io;
@@ -19,3 +19,44 @@ impl<T> Writer<T> {
         self
     }
 }
+
+/// Common methods
+impl<T> SidebandWriter<T> {
+    /// As [`set_channel()`][SidebandWriter::set_channel()], but suitable for chaining.
+    pub fn with_channel(mut self, channel: Channel) -> Self {
+        self.set_channel(channel);
+        self
+    }
+
+    /// Limit lines to 1000 bytes as needed if only the `side-band` capability was negotiated, instead of `side-band-64k`
+    /// which is assumed by default.
+    pub fn with_small_lines(mut self) -> Self {
+        self.max_band_data_len = max_band_data_len(true);
+        self
+    }
+
+    /// Return the channel that data is currently written to.
+    pub fn channel(&self) -> Channel {
+        self.channel
+    }
+}
+
+/// Return the maximum amount of data bytes that fit into a single side-band line.
+fn max_band_data_len(small_lines: bool) -> usize {
+    let band_byte = 1;
+    if small_lines {
+        MAX_SMALL_SIDEBAND_LINE_LEN - U16_HEX_BYTES - band_byte
+    } else {
+        MAX_DATA_LEN - band_byte
+    }
+}
+
+/// Return the prefix to put in front of each line written to `channel`.
+#[cfg_attr(feature = "blocking-io", allow(dead_code))]
+fn band_prefix(channel: Channel) -> &'static [u8] {
+    match channel {
+        Channel::Data => &[Channel::Data as u8],
+        Channel::Progress => &[Channel::Progress as u8],
+        Channel::Error => &[Channel::Error as u8],
+    }
+}
@@ -55,3 +55,68 @@ async fn empty_writes_fail_with_error() {
         "empty packet lines are not permitted as '0004' is invalid"
     );
 }
+
+#[maybe_async::test(feature = "blocking-io", async(feature = "async-io", async_std::test))]
+async fn special_lines_can_be_written_in_between() -> crate::Result {
+    let mut w = Writer::new(Vec::new());
+    w.write_all(b"command=fetch").await?;
+    w.write_delimiter().await?;
+    w.write_all(b"done").await?;
+    w.write_flush().await?;
+    w.write_error(b"oops").await?;
+    w.write_response_end().await?;
+    let buf = w.into_inner();
+    assert_eq!(
+        buf.as_bstr(),
+        b"0011command=fetch00010008done0000000cERR oops0002".as_bstr()
+    );
+    Ok(())
+}
+
+mod sideband_writer {
+    use bstr::ByteSlice;
+    #[cfg(all(feature = "async-io", not(feature = "blocking-io")))]
+    use futures_lite::prelude::*;
+    use gix_packetline::{Channel, SidebandWriter};
+    #[cfg(feature = "blocking-io")]
+    use std::io::Write;
+
+    #[maybe_async::test(feature = "blocking-io", async(feature = "async-io", async_std::test))]
+    async fn each_write_is_multiplexed_into_the_current_channel() -> crate::Result {
+        let mut w = SidebandWriter::new(Vec::new(), Channel::Progress);
+        w.write_all(b"hello").await?;
+        w.set_channel(Channel::Data);
+        assert_eq!(w.channel(), Channel::Data);
+        w.write_all(b"world").await?;
+        w = w.with_channel(Channel::Error);
+        w.write_all(b"bad").await?;
+        w.write_flush().await?;
+        let buf = w.into_inner();
+        assert_eq!(buf.as_bstr(), b"000a\x02hello000a\x01world0008\x03bad0000".as_bstr());
+        Ok(())
+    }
+
+    #[maybe_async::test(feature = "blocking-io", async(feature = "async-io", async_std::test))]
+    async fn huge_writes_are_split_into_lines_according_to_the_line_limit() -> crate::Result {
+        let data = vec![0u8; 2000];
+        let mut w = SidebandWriter::new(Vec::new(), Channel::Data);
+        w.write_all(&data).await?;
+        assert_eq!(w.into_inner().len(), 4 + 1 + 2000, "it all fits into one 64k line");
+
+        let mut w = SidebandWriter::new(Vec::new(), Channel::Data).with_small_lines();
+        w.write_all(&data).await?;
+        let buf = w.into_inner();
+        assert_eq!(buf.len(), 2000 + 3 * 5, "three lines are needed");
+        assert_eq!(&buf[..5], b"03e8\x01", "the first line is 1000 bytes long");
+        Ok(())
+    }
+
+    #[maybe_async::test(feature = "blocking-io", async(feature = "async-io", async_std::test))]
+    async fn empty_writes_fail_with_error() {
+        let res = SidebandWriter::new(Vec::new(), Channel::Data).write(&[]).await;
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "empty packet lines are not permitted as '0004' is invalid"
+        );
+    }
+}
@@ -5,10 +5,9 @@ use gix_prompt::{Mode, Options};
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let pass = gix_prompt::ask(
         "Password: ",
-        &Options {
-            askpass: Some(Cow::Owned(std::env::current_exe()?.parent().unwrap().join("askpass"))),
-            mode: Mode::Disable,
-        },
+        &Options::default()
+            .with_askpass(Cow::Owned(std::env::current_exe()?.parent().unwrap().join("askpass")))
+            .with_mode(Mode::Disable),
     )?;
     eprintln!("{pass:?}");
     Ok(())
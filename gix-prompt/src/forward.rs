@@ -0,0 +1,146 @@
+//! Answer the prompts of spawned programs which support `askpass` programs, like `ssh` with `SSH_ASKPASS`, using a [`Handler`].
+use std::{
+    io::{Read, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use crate::{Handler, Kind, Mode};
+
+/// The script to use as `askpass` program, which passes its prompt to us and prints our answer.
+///
+/// Answers are prefixed with `ok:` as otherwise the script can't tell an empty answer from one that wasn't given.
+const SCRIPT: &str = r#"#!/bin/sh
+dir=$(dirname "$0")
+printf '%s' "$1" >"$dir/prompt" || exit 1
+answer=$(cat "$dir/answer") || exit 1
+case "$answer" in
+  ok:*) printf '%s\n' "${answer#ok:}" ;;
+  *) exit 1 ;;
+esac
+"#;
+
+/// A function to determine the kind of a prompt from its text, along with the mode the terminal would be prompted with.
+pub type Classify = fn(&str) -> (Kind, Mode);
+
+/// An `askpass` program which forwards all prompts it receives to a [`Handler`] for as long as this instance is alive.
+///
+/// Pass its [`program()`](Askpass::program()) to a spawned program as `askpass` program, for example by setting it
+/// as `SSH_ASKPASS` for `ssh`. If the handler doesn't answer a prompt, the `askpass` program fails.
+///
+/// It's implemented as shell script in a private temporary directory, which communicates with a thread owned by this
+/// instance through named pipes.
+#[derive(Debug)]
+pub struct Askpass {
+    dir: PathBuf,
+    stop: Arc<AtomicBool>,
+    thread: Option<std::thread::JoinHandle<std::io::Result<()>>>,
+}
+
+impl Askpass {
+    /// Create a new `askpass` program which passes each prompt to `handler`, along with the kind and mode determined
+    /// by `classify`.
+    pub fn new(handler: Arc<Handler>, classify: Classify) -> std::io::Result<Self> {
+        let mut askpass = Askpass {
+            dir: private_temp_dir()?,
+            stop: Arc::new(AtomicBool::new(false)),
+            thread: None,
+        };
+        askpass.write_script_and_pipes()?;
+        let (prompt, answer, stop) = (askpass.prompt_pipe(), askpass.answer_pipe(), askpass.stop.clone());
+        askpass.thread = std::thread::Builder::new()
+            .name("gix-prompt askpass".into())
+            .spawn(move || -> std::io::Result<()> {
+                loop {
+                    let mut text = String::new();
+                    std::fs::File::open(&prompt)?.read_to_string(&mut text)?;
+                    if stop.load(Ordering::SeqCst) {
+                        return Ok(());
+                    }
+                    let (kind, mode) = classify(&text);
+                    let response = handler(kind, &text, mode);
+                    let mut out = std::fs::OpenOptions::new().write(true).open(&answer)?;
+                    if let Some(response) = response {
+                        write!(out, "ok:{response}")?;
+                    }
+                }
+            })?
+            .into();
+        Ok(askpass)
+    }
+
+    /// The path to the `askpass` program to pass to spawned programs.
+    pub fn program(&self) -> PathBuf {
+        self.dir.join("askpass")
+    }
+
+    fn prompt_pipe(&self) -> PathBuf {
+        self.dir.join("prompt")
+    }
+
+    fn answer_pipe(&self) -> PathBuf {
+        self.dir.join("answer")
+    }
+
+    fn write_script_and_pipes(&self) -> std::io::Result<()> {
+        use std::os::unix::fs::OpenOptionsExt;
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o700)
+            .open(self.program())?
+            .write_all(SCRIPT.as_bytes())?;
+        let status = std::process::Command::new("mkfifo")
+            .args(["-m", "600"])
+            .arg(self.prompt_pipe())
+            .arg(self.answer_pipe())
+            .stdin(std::process::Stdio::null())
+            .status()?;
+        if !status.success() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("`mkfifo` failed with {status}"),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Askpass {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            // Opening pipes for reading and writing doesn't block, but unblocks the thread if it waits for
+            // either a prompt or a reader of the answer, which may be gone if the `askpass` program was killed.
+            while !thread.is_finished() {
+                for pipe in [self.answer_pipe(), self.prompt_pipe()] {
+                    std::fs::OpenOptions::new().read(true).write(true).open(pipe).ok();
+                }
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+            thread.join().ok();
+        }
+        std::fs::remove_dir_all(&self.dir).ok();
+    }
+}
+
+fn private_temp_dir() -> std::io::Result<PathBuf> {
+    use std::os::unix::fs::DirBuilderExt;
+    static COUNT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+    let base = std::env::temp_dir();
+    loop {
+        let dir = base.join(format!(
+            "gix-askpass-{}-{}",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        match std::fs::DirBuilder::new().mode(0o700).create(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(err) => return Err(err),
+        }
+    }
+}
@@ -8,10 +8,13 @@
 #![forbid(unsafe_code)]
 
 mod types;
-pub use types::{Error, Mode, Options};
+pub use types::{Error, Handler, Kind, Mode, Options};
 
 ///
 pub mod unix;
+
+#[cfg(unix)]
+pub mod forward;
 #[cfg(unix)]
 use unix::imp;
 
@@ -25,7 +28,24 @@ mod imp {
 }
 
 /// Ask the user given a `prompt`, returning the result.
+///
+/// Use [`ask_for()`] to let a [`handler`][Options::handler] know what kind of information is requested.
 pub fn ask(prompt: &str, opts: &Options<'_>) -> Result<String, Error> {
+    ask_for(Kind::Other, prompt, opts)
+}
+
+/// Ask the user given a `prompt` for information of the given `kind`, returning the result.
+///
+/// The [`handler`][Options::handler] is consulted first, followed by the [`askpass`][Options::askpass] program,
+/// and finally the terminal is used according to [`mode`][Options::mode].
+pub fn ask_for(kind: Kind, prompt: &str, opts: &Options<'_>) -> Result<String, Error> {
+    if let Some(answer) = opts
+        .handler
+        .as_ref()
+        .and_then(|handler| handler(kind, prompt, opts.mode))
+    {
+        return Ok(answer);
+    }
     if let Some(askpass) = opts.askpass.as_deref() {
         match gix_command::prepare(askpass).arg(prompt).spawn() {
             Ok(cmd) => {
@@ -53,24 +73,12 @@ pub fn ask(prompt: &str, opts: &Options<'_>) -> Result<String, Error> {
 ///
 /// Use [`ask()`] for more control.
 pub fn openly(prompt: impl AsRef<str>) -> Result<String, Error> {
-    imp::ask(
-        prompt.as_ref(),
-        &Options {
-            mode: Mode::Visible,
-            askpass: None,
-        },
-    )
+    imp::ask(prompt.as_ref(), &Options::default().with_mode(Mode::Visible))
 }
 
 /// Ask for information _securely_ after showing the `prompt` (like `"password: "`) by not showing what's typed.
 ///
 /// Use [`ask()`] for more control.
 pub fn securely(prompt: impl AsRef<str>) -> Result<String, Error> {
-    imp::ask(
-        prompt.as_ref(),
-        &Options {
-            mode: Mode::Hidden,
-            askpass: None,
-        },
-    )
+    imp::ask(prompt.as_ref(), &Options::default().with_mode(Mode::Hidden))
 }
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, path::Path};
+use std::{borrow::Cow, path::Path, sync::Arc};
 
 /// The error returned by [ask()][crate::ask()].
 #[derive(Debug, thiserror::Error)]
@@ -30,9 +30,40 @@ pub enum Mode {
     Disable,
 }
 
+/// The kind of information a prompt asks for, passed to a [`Handler`] to allow it to respond appropriately.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Kind {
+    /// The prompt asks for a username.
+    Username,
+    /// The prompt asks for a password.
+    Password,
+    /// The prompt asks for the passphrase of a key.
+    Passphrase,
+    /// The prompt asks whether or not the key of a host should be trusted.
+    HostKey,
+    /// The kind of prompt isn't known.
+    #[default]
+    Other,
+}
+
+/// A function to intercept prompts before any `askpass` program or the terminal are used.
+///
+/// It receives the kind of prompt, the prompt text itself and the [mode](Mode) the terminal would be prompted with.
+/// Returning `Some(answer)` uses `answer` as response, while `None` falls through to the `askpass` program or the terminal.
+pub type Handler = dyn Fn(Kind, &str, Mode) -> Option<String> + Send + Sync;
+
 /// The options used in `[ask()]`.
+///
+/// Use the builder methods like [`with_mode()`](Options::with_mode()) to create an instance, as more options
+/// may be added in future.
 #[derive(Default, Clone)]
+#[non_exhaustive]
 pub struct Options<'a> {
+    /// A programmatic override to answer prompts, which is consulted before any other method of obtaining an answer.
+    ///
+    /// This allows headless or GUI applications to intercept every prompt. Note that it is also called if the [`mode`][Options::mode]
+    /// is [`Disable`][Mode::Disable].
+    pub handler: Option<Arc<Handler>>,
     /// The path or name (for lookup in `PATH`) to the askpass program to call before prompting the user.
     ///
     /// It's called like this `askpass <prompt>`, but note that it won't know if the input should be hidden or not.
@@ -41,7 +72,38 @@ pub struct Options<'a> {
     pub mode: Mode,
 }
 
-impl Options<'_> {
+impl std::fmt::Debug for Options<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Options")
+            .field("handler", &self.handler.as_ref().map(|_| "<fn>"))
+            .field("askpass", &self.askpass)
+            .field("mode", &self.mode)
+            .finish()
+    }
+}
+
+impl<'a> Options<'a> {
+    /// Set the [`mode`][Options::mode] to `mode`.
+    pub fn with_mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set the [`askpass`][Options::askpass] program to `askpass`.
+    pub fn with_askpass(mut self, askpass: impl Into<Option<Cow<'a, Path>>>) -> Self {
+        self.askpass = askpass.into();
+        self
+    }
+
+    /// Set the [`handler`][Options::handler] to `handler` to intercept all prompts.
+    pub fn with_handler(
+        mut self,
+        handler: impl Fn(Kind, &str, Mode) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.handler = Some(Arc::new(handler));
+        self
+    }
+
     /// Change this instance to incorporate information from the environment.
     ///
     /// - if `use_git_askpass` is true, use `GIT_ASKPASS` to override any existing [`askpass`][Options::askpass] program
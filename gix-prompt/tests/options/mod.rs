@@ -13,14 +13,12 @@ mod apply_environment {
             .set("SSH_ASKPASS", "does not matter");
 
         assert_eq!(
-            Options {
-                askpass: Some(Cow::Borrowed(Path::new("current"))),
-                ..Default::default()
-            }
-            .apply_environment(true, true, false)
-            .askpass
-            .expect("set")
-            .as_ref(),
+            Options::default()
+                .with_askpass(Cow::Borrowed(Path::new("current")))
+                .apply_environment(true, true, false)
+                .askpass
+                .expect("set")
+                .as_ref(),
             Path::new("override")
         );
     }
@@ -48,14 +46,12 @@ mod apply_environment {
         let _env = Env::new().unset("GIT_ASKPASS").set("SSH_ASKPASS", "fallback");
 
         assert_eq!(
-            Options {
-                mode: Mode::Visible,
-                ..Default::default()
-            }
-            .apply_environment(true, true, false)
-            .askpass
-            .expect("set")
-            .as_ref(),
+            Options::default()
+                .with_mode(Mode::Visible)
+                .apply_environment(true, true, false)
+                .askpass
+                .expect("set")
+                .as_ref(),
             Path::new("fallback")
         );
     }
@@ -66,14 +62,12 @@ mod apply_environment {
         let _env = Env::new().unset("GIT_ASKPASS").set("SSH_ASKPASS", "fallback");
 
         assert_eq!(
-            Options {
-                askpass: Some(Cow::Borrowed(Path::new("current"))),
-                ..Default::default()
-            }
-            .apply_environment(true, true, false)
-            .askpass
-            .expect("set")
-            .as_ref(),
+            Options::default()
+                .with_askpass(Cow::Borrowed(Path::new("current")))
+                .apply_environment(true, true, false)
+                .askpass
+                .expect("set")
+                .as_ref(),
             Path::new("current")
         );
     }
@@ -84,12 +78,10 @@ mod apply_environment {
         let _env = Env::new().set("GIT_TERMINAL_PROMPT", "true");
 
         assert_eq!(
-            Options {
-                mode: Mode::Hidden,
-                ..Default::default()
-            }
-            .apply_environment(false, false, true)
-            .mode,
+            Options::default()
+                .with_mode(Mode::Hidden)
+                .apply_environment(false, false, true)
+                .mode,
             Mode::Hidden
         );
     }
@@ -100,12 +92,10 @@ mod apply_environment {
         let _env = Env::new().set("GIT_TERMINAL_PROMPT", "0");
 
         assert_eq!(
-            Options {
-                mode: Mode::Hidden,
-                ..Default::default()
-            }
-            .apply_environment(false, false, true)
-            .mode,
+            Options::default()
+                .with_mode(Mode::Hidden)
+                .apply_environment(false, false, true)
+                .mode,
             Mode::Disable
         );
     }
@@ -114,12 +104,10 @@ mod apply_environment {
     #[serial]
     fn mode_is_unchanged_if_git_terminal_prompt_is_not_set() {
         assert_eq!(
-            Options {
-                mode: Mode::Hidden,
-                ..Default::default()
-            }
-            .apply_environment(false, false, true)
-            .mode,
+            Options::default()
+                .with_mode(Mode::Hidden)
+                .apply_environment(false, false, true)
+                .mode,
             Mode::Hidden
         );
     }
@@ -68,4 +68,73 @@ mod ask {
     #[cfg(not(unix))]
     #[ignore]
     fn username_password_not_available() {}
+
+    #[test]
+    fn handler_intercepts_prompts_even_if_they_are_disabled() {
+        let opts = gix_prompt::Options::default()
+            .with_mode(gix_prompt::Mode::Disable)
+            .with_handler(|kind, prompt, mode| {
+                assert_eq!(mode, gix_prompt::Mode::Disable);
+                (kind == gix_prompt::Kind::Username).then(|| format!("answer to {prompt}"))
+            });
+        assert_eq!(
+            gix_prompt::ask_for(gix_prompt::Kind::Username, "Username: ", &opts).expect("handled"),
+            "answer to Username: "
+        );
+        assert!(
+            matches!(
+                gix_prompt::ask_for(gix_prompt::Kind::Password, "Password: ", &opts),
+                Err(gix_prompt::Error::Disabled | gix_prompt::Error::UnsupportedPlatform)
+            ),
+            "unhandled prompts fall through to the terminal, which is disabled"
+        );
+    }
+}
+
+#[cfg(unix)]
+mod forward {
+    use std::sync::Arc;
+
+    use gix_prompt::{forward::Askpass, Kind, Mode};
+
+    fn run(program: &std::path::Path, prompt: &str) -> std::process::Output {
+        std::process::Command::new(program)
+            .arg(prompt)
+            .output()
+            .expect("askpass program can be executed")
+    }
+
+    #[test]
+    fn askpass_program_receives_answers_from_handler() -> std::io::Result<()> {
+        let askpass = Askpass::new(
+            Arc::new(|kind, prompt, mode| {
+                assert_eq!(mode, Mode::Hidden);
+                (kind == Kind::Passphrase).then(|| format!("answer to {prompt}"))
+            }),
+            |prompt| {
+                (
+                    if prompt.contains("passphrase") {
+                        Kind::Passphrase
+                    } else {
+                        Kind::Other
+                    },
+                    Mode::Hidden,
+                )
+            },
+        )?;
+        let program = askpass.program();
+        for _ in 0..2 {
+            let out = run(&program, "Enter passphrase for key: ");
+            assert!(out.status.success());
+            assert_eq!(out.stdout, b"answer to Enter passphrase for key: \n");
+        }
+
+        let out = run(&program, "something else");
+        assert!(!out.status.success(), "unanswered prompts fail the askpass program");
+
+        let dir = program.parent().expect("in directory").to_owned();
+        drop(askpass);
+        assert!(!dir.exists(), "everything is cleaned up once dropped");
+        Ok(())
+    }
 }
@@ -0,0 +1,235 @@
+use bstr::{BStr, BString, ByteSlice};
+
+mod error {
+    use bstr::BString;
+
+    /// The error returned by [`bundle_uri()`][crate::bundle_uri()] and [`List::from_lines()`][super::List::from_lines()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        Transport(#[from] gix_transport::client::Error),
+        #[error(transparent)]
+        DecodePacketline(#[from] gix_transport::packetline::decode::Error),
+        #[error("Bundle list line {line:?} is not a 'key=value' pair")]
+        MalformedLine { line: BString },
+        #[error("Bundle list version {version:?} is not supported")]
+        UnsupportedVersion { version: BString },
+        #[error("Bundle list mode {mode:?} is unknown, expected 'all' or 'any'")]
+        UnknownMode { mode: BString },
+        #[error("The creation token {token:?} of bundle {id:?} is not a number")]
+        InvalidCreationToken { id: BString, token: BString },
+    }
+
+    impl gix_transport::IsSpuriousError for Error {
+        fn is_spurious(&self) -> bool {
+            match self {
+                Error::Io(err) => err.is_spurious(),
+                Error::Transport(err) => err.is_spurious(),
+                _ => false,
+            }
+        }
+    }
+}
+pub use error::Error;
+
+/// Define which bundles of a [`List`] have to be obtained.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy, Default)]
+pub enum Mode {
+    /// All bundles are needed to obtain the full set of objects they advertise.
+    #[default]
+    All,
+    /// Each bundle contains all objects, so any one of them is sufficient.
+    Any,
+}
+
+/// A bundle as advertised by the server.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+pub struct Bundle {
+    /// The identifier of the bundle, unique within its list.
+    pub id: BString,
+    /// The location to download the bundle from, which may be relative to the URL of the remote.
+    ///
+    /// It's `None` if the server didn't send it, which makes the bundle unusable.
+    pub uri: Option<BString>,
+    /// A number which increases with each bundle that is created, so bundles can be applied in order.
+    pub creation_token: Option<u64>,
+}
+
+/// A list of bundles as advertised by the server in response to the `bundle-uri` command.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Default)]
+pub struct List {
+    /// Which of the `bundles` are needed.
+    pub mode: Mode,
+    /// The heuristic to use to decide which bundles to download, like `creationToken`.
+    pub heuristic: Option<BString>,
+    /// All bundles in the order they were first mentioned by the server.
+    pub bundles: Vec<Bundle>,
+}
+
+impl List {
+    /// Parse a bundle list from `lines` of `key=value` pairs, as sent by the server in response to the `bundle-uri` command.
+    ///
+    /// Keys that aren't known are ignored.
+    pub fn from_lines<'a>(lines: impl IntoIterator<Item = &'a BStr>) -> Result<Self, Error> {
+        let mut out = List::default();
+        for line in lines {
+            let line = line.trim_end_with(|c| c == '\n');
+            let (key, value) = line
+                .find_byte(b'=')
+                .map(|pos| (line[..pos].as_bstr(), line[pos + 1..].as_bstr()))
+                .ok_or_else(|| Error::MalformedLine { line: line.into() })?;
+            let Some(key) = key.strip_prefix(b"bundle.") else {
+                continue;
+            };
+            match key {
+                b"version" => {
+                    if value.as_bytes() != b"1" {
+                        return Err(Error::UnsupportedVersion { version: value.into() });
+                    }
+                }
+                b"mode" => {
+                    out.mode = match value.as_bytes() {
+                        b"all" => Mode::All,
+                        b"any" => Mode::Any,
+                        _ => return Err(Error::UnknownMode { mode: value.into() }),
+                    };
+                }
+                b"heuristic" => out.heuristic = Some(value.into()),
+                _ => {
+                    let Some(pos) = key.rfind_byte(b'.') else {
+                        continue;
+                    };
+                    let (id, bundle_key) = (key[..pos].as_bstr(), &key[pos + 1..]);
+                    let bundle = match out.bundles.iter_mut().position(|b| b.id == id) {
+                        Some(idx) => &mut out.bundles[idx],
+                        None => {
+                            out.bundles.push(Bundle {
+                                id: id.into(),
+                                uri: None,
+                                creation_token: None,
+                            });
+                            out.bundles.last_mut().expect("just pushed")
+                        }
+                    };
+                    if bundle_key.eq_ignore_ascii_case(b"uri") {
+                        bundle.uri = Some(value.into());
+                    } else if bundle_key.eq_ignore_ascii_case(b"creationToken") {
+                        bundle.creation_token =
+                            Some(value.to_str().ok().and_then(|v| v.parse().ok()).ok_or_else(|| {
+                                Error::InvalidCreationToken {
+                                    id: id.into(),
+                                    token: value.into(),
+                                }
+                            })?);
+                    }
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+pub(crate) mod function {
+    use bstr::BString;
+    use gix_transport::client::{Capabilities, Transport, TransportV2Ext};
+    use maybe_async::maybe_async;
+
+    use super::{Error, List};
+    use crate::{command::Feature, Command};
+
+    /// Invoke a `bundle-uri` V2 command on `transport`, which requires a prior handshake that yielded server `capabilities`
+    /// which advertise the `bundle-uri` capability, and return the list of bundles the server suggests to download before fetching.
+    /// The `agent` feature is sent along as well.
+    ///
+    /// If `trace` is `true`, all packetlines received or sent will be passed to the facilities of the `gix-trace` crate.
+    #[maybe_async]
+    pub async fn bundle_uri(
+        mut transport: impl Transport,
+        capabilities: &Capabilities,
+        agent: Feature,
+        trace: bool,
+    ) -> Result<List, Error> {
+        let _span = gix_features::trace::detail!("gix_protocol::bundle_uri()", capabilities = ?capabilities);
+        let bundle_uri = Command::BundleUri;
+        let mut features = bundle_uri.default_features(gix_transport::Protocol::V2, capabilities);
+        features.push(agent);
+        let mut reader = transport
+            .invoke(
+                bundle_uri.as_str(),
+                features.into_iter(),
+                None::<std::iter::Empty<BString>>,
+                trace,
+            )
+            .await?;
+        let mut lines = Vec::new();
+        while let Some(line) = reader
+            .readline()
+            .await
+            .transpose()?
+            .transpose()?
+            .and_then(|l| l.as_bstr())
+        {
+            lines.push(BString::from(line));
+        }
+        List::from_lines(lines.iter().map(AsRef::as_ref))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bstr::ByteSlice;
+
+    use super::{Bundle, List, Mode};
+
+    #[test]
+    fn from_lines() -> Result<(), super::Error> {
+        let list = List::from_lines(
+            [
+                "bundle.version=1",
+                "bundle.mode=any",
+                "bundle.heuristic=creationToken",
+                "bundle.first.uri=https://example.com/first.bundle",
+                "bundle.first.creationToken=1",
+                "bundle.second.uri=second.bundle\n",
+                "unrelated.key=value",
+            ]
+            .into_iter()
+            .map(|l| l.as_bytes().as_bstr()),
+        )?;
+        assert_eq!(
+            list,
+            List {
+                mode: Mode::Any,
+                heuristic: Some("creationToken".into()),
+                bundles: vec![
+                    Bundle {
+                        id: "first".into(),
+                        uri: Some("https://example.com/first.bundle".into()),
+                        creation_token: Some(1),
+                    },
+                    Bundle {
+                        id: "second".into(),
+                        uri: Some("second.bundle".into()),
+                        creation_token: None,
+                    }
+                ]
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn from_lines_rejects_unknown_versions_and_malformed_lines() {
+        assert!(matches!(
+            List::from_lines(Some(b"bundle.version=2".as_bstr())),
+            Err(super::Error::UnsupportedVersion { .. })
+        ));
+        assert!(matches!(
+            List::from_lines(Some(b"bundle.mode".as_bstr())),
+            Err(super::Error::MalformedLine { .. })
+        ));
+    }
+}
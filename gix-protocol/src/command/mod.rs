@@ -12,6 +12,8 @@ impl Command {
         match self {
             Command::LsRefs => "ls-refs",
             Command::Fetch => "fetch",
+            Command::BundleUri => "bundle-uri",
+            Command::ObjectInfo => "object-info",
         }
     }
 }
@@ -28,6 +30,8 @@ mod with_io {
         fn all_argument_prefixes(&self) -> &'static [&'static str] {
             match self {
                 Command::LsRefs => &["symrefs", "peel", "ref-prefix ", "unborn"],
+                Command::BundleUri => &[],
+                Command::ObjectInfo => &["size", "type", "oid "],
                 Command::Fetch => &[
                     "want ", // hex oid
                     "have ", // hex oid
@@ -58,7 +62,7 @@ mod with_io {
 
         fn all_features(&self, version: gix_transport::Protocol) -> &'static [&'static str] {
             match self {
-                Command::LsRefs => &[],
+                Command::LsRefs | Command::BundleUri | Command::ObjectInfo => &[],
                 Command::Fetch => match version {
                     gix_transport::Protocol::V0 | gix_transport::Protocol::V1 => &[
                         "multi_ack",
@@ -100,7 +104,7 @@ mod with_io {
                     .chain(
                         [
                             "sideband-all",
-                            /* "packfile-uris" */ // packfile-uris must be configurable and can't just be used. Some servers advertise it and reject it later.
+                            /* "packfile-uris" */ // packfile-uris must be configurable and can't just be used, see `Arguments::use_packfile_uris()`.
                         ]
                         .iter()
                         .filter(|f| features.iter().any(|(sf, _)| sf == *f))
@@ -108,6 +112,8 @@ mod with_io {
                     )
                     .collect(),
                 Command::LsRefs => vec![b"symrefs".as_bstr().to_owned(), b"peel".as_bstr().to_owned()],
+                Command::BundleUri => Vec::new(),
+                Command::ObjectInfo => vec![b"size".as_bstr().to_owned()],
             }
         }
 
@@ -154,7 +160,7 @@ mod with_io {
                             .collect()
                     }
                 },
-                Command::LsRefs => vec![],
+                Command::LsRefs | Command::BundleUri | Command::ObjectInfo => vec![],
             }
         }
         /// Panics if the given arguments and features don't match what's statically known. It's considered a bug in the delegate.
@@ -200,7 +206,7 @@ mod with_io {
                             continue;
                         }
                         match *feature {
-                            "agent" => {}
+                            "agent" | "session-id" => {}
                             _ => panic!("{}: V2 feature/capability {} is not supported", self.as_str(), feature),
                         }
                     }
@@ -18,6 +18,7 @@ pub struct Arguments {
     deepen_not: bool,
     deepen_relative: bool,
     ref_in_want: bool,
+    packfile_uris: bool,
     supports_include_tag: bool,
 
     features_for_first_want: Option<Vec<String>>,
@@ -76,6 +77,12 @@ impl Arguments {
     pub fn can_use_ref_in_want(&self) -> bool {
         self.ref_in_want
     }
+    /// Return true if the 'packfile-uris' capability is supported.
+    ///
+    /// This is a V2 only feature which allows the server to offload parts of the pack to other locations, like a CDN.
+    pub fn can_use_packfile_uris(&self) -> bool {
+        self.packfile_uris
+    }
     /// Return true if the 'include-tag' capability is supported.
     pub fn can_use_include_tag(&self) -> bool {
         self.supports_include_tag
@@ -161,6 +168,22 @@ impl Arguments {
             self.prefixed("filter ", spec);
         }
     }
+    /// Allow the server to send parts of the pack as URIs to download separately, for all URIs using one of the given
+    /// `protocols`, like `https`.
+    ///
+    /// Packs sent that way will be listed in [`Response::packfile_uris()`](crate::fetch::Response::packfile_uris())
+    /// and have to be downloaded and indexed by the caller, as the pack sent inline will not contain their objects.
+    ///
+    /// Needs to only be called once.
+    pub fn use_packfile_uris<'a>(&mut self, protocols: impl IntoIterator<Item = &'a str>) {
+        debug_assert!(self.packfile_uris, "'packfile-uris' feature required");
+        if self.packfile_uris {
+            let protocols = protocols.into_iter().collect::<Vec<_>>().join(",");
+            if !protocols.is_empty() {
+                self.prefixed("packfile-uris ", protocols);
+            }
+        }
+    }
     /// Permanently allow the server to include tags that point to commits or objects it would return.
     ///
     /// Needs to only be called once.
@@ -204,6 +227,7 @@ impl Arguments {
         let filter = has("filter");
         let shallow = has("shallow");
         let ref_in_want = has("ref-in-want");
+        let packfile_uris = version == gix_transport::Protocol::V2 && has("packfile-uris");
         let mut deepen_since = shallow;
         let mut deepen_not = shallow;
         let mut deepen_relative = shallow;
@@ -243,6 +267,7 @@ impl Arguments {
             deepen_not,
             deepen_relative,
             ref_in_want,
+            packfile_uris,
             deepen_since,
             features_for_first_want,
             trace,
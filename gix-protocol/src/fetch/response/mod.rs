@@ -79,6 +79,18 @@ pub struct WantedRef {
     pub path: BString,
 }
 
+/// A line of the `packfile-uris` section received from the server, pointing to a pack that the server didn't send inline
+/// and which has to be downloaded separately.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PackfileUri {
+    /// The hash of the pack as stored in its trailer, which is also used in its file name like `pack-<hash>.pack`.
+    pub hash: gix_hash::ObjectId,
+    /// The location to download the pack from, using one of the protocols the client passed to
+    /// [`Arguments::use_packfile_uris()`](crate::fetch::Arguments::use_packfile_uris()).
+    pub uri: BString,
+}
+
 impl ShallowUpdate {
     /// Parse a `ShallowUpdate` from a `line` as received to the server.
     pub fn from_line(line: &str) -> Result<ShallowUpdate, Error> {
@@ -148,12 +160,27 @@ impl WantedRef {
     }
 }
 
+impl PackfileUri {
+    /// Parse a `PackfileUri` from a `line` as received from the server.
+    pub fn from_line(line: &str) -> Result<PackfileUri, Error> {
+        match line.trim_end().split_once(' ') {
+            Some((hash, uri)) if !uri.is_empty() => {
+                let hash = gix_hash::ObjectId::from_hex(hash.as_bytes())
+                    .map_err(|_| Error::UnknownLineType { line: line.to_owned() })?;
+                Ok(PackfileUri { hash, uri: uri.into() })
+            }
+            _ => Err(Error::UnknownLineType { line: line.to_owned() }),
+        }
+    }
+}
+
 /// A representation of a complete fetch response
 #[derive(Debug)]
 pub struct Response {
     acks: Vec<Acknowledgement>,
     shallows: Vec<ShallowUpdate>,
     wanted_refs: Vec<WantedRef>,
+    packfile_uris: Vec<PackfileUri>,
     has_pack: bool,
 }
 
@@ -216,6 +243,14 @@ impl Response {
     pub fn wanted_refs(&self) -> &[WantedRef] {
         &self.wanted_refs
     }
+
+    /// Return all packs [parsed previously][Response::from_line_reader()] that the server expects us to download
+    /// separately in addition to reading the pack it sends inline.
+    ///
+    /// Only sent if [`Arguments::use_packfile_uris()`](crate::fetch::Arguments::use_packfile_uris()) was called.
+    pub fn packfile_uris(&self) -> &[PackfileUri] {
+        &self.packfile_uris
+    }
 }
 
 #[cfg(any(feature = "async-client", feature = "blocking-client"))]
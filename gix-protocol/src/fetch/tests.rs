@@ -408,6 +408,29 @@ mod arguments {
 000eofs-delta
 001dwant-ref refs/heads/main
 0009done
+0000"
+                    .as_bstr()
+            );
+        }
+
+        #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+        async fn packfile_uris() {
+            let mut out = Vec::new();
+            let mut t = transport(&mut out, false);
+            let mut arguments = arguments_v2(["packfile-uris"].iter().copied());
+            assert!(arguments.can_use_packfile_uris());
+
+            arguments.use_packfile_uris(["https", "http"]);
+            arguments.want(id("7b333369de1221f9bfbbe03a3a13e9a09bc1c907"));
+            arguments.send(&mut t, true).await.expect("sending to buffer to work");
+            assert_eq!(
+                out.as_bstr(),
+                b"0012command=fetch
+0001000ethin-pack
+000eofs-delta
+001dpackfile-uris https,http
+0032want 7b333369de1221f9bfbbe03a3a13e9a09bc1c907
+0009done
 0000"
                     .as_bstr()
             );
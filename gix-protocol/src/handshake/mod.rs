@@ -63,6 +63,13 @@ pub struct Outcome {
     pub capabilities: Capabilities,
 }
 
+impl Outcome {
+    /// Return the id the server advertised with the `session-id` capability, which identifies the operation in its logs.
+    pub fn session_id(&self) -> Option<&bstr::BStr> {
+        self.capabilities.capability("session-id")?.value()
+    }
+}
+
 mod error {
     use bstr::BString;
     use gix_transport::client;
@@ -103,3 +110,22 @@ pub(crate) mod function;
 
 ///
 pub mod refs;
+
+#[cfg(test)]
+mod tests {
+    use gix_transport::client::Capabilities;
+
+    use super::Outcome;
+
+    #[test]
+    fn session_id_is_provided_by_the_server_capabilities() {
+        let (capabilities, _) =
+            Capabilities::from_bytes(b"\0agent=git/2.46.0 session-id=server-sid").expect("valid capabilities");
+        let outcome = Outcome {
+            capabilities,
+            ..Default::default()
+        };
+        assert_eq!(outcome.session_id(), Some("server-sid".into()));
+        assert_eq!(Outcome::default().session_id(), None);
+    }
+}
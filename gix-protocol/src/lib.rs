@@ -17,6 +17,10 @@ pub enum Command {
     LsRefs,
     /// Fetch a pack.
     Fetch,
+    /// List the URIs of bundles to download before fetching.
+    BundleUri,
+    /// Obtain information like the size of objects without fetching them.
+    ObjectInfo,
 }
 pub mod command;
 
@@ -58,6 +62,24 @@ pub mod ls_refs;
 #[cfg(any(feature = "blocking-client", feature = "async-client"))]
 pub use ls_refs::function::ls_refs;
 
+///
+#[cfg(any(feature = "blocking-client", feature = "async-client"))]
+pub mod bundle_uri;
+#[cfg(any(feature = "blocking-client", feature = "async-client"))]
+pub use bundle_uri::function::bundle_uri;
+
+///
+#[cfg(any(feature = "blocking-client", feature = "async-client"))]
+pub mod object_info;
+#[cfg(any(feature = "blocking-client", feature = "async-client"))]
+pub use object_info::function::object_info;
+
+///
+#[cfg(any(feature = "blocking-client", feature = "async-client"))]
+pub mod push;
+#[cfg(any(feature = "blocking-client", feature = "async-client"))]
+pub use push::function::push;
+
 mod util;
 pub use util::agent;
 #[cfg(any(feature = "blocking-client", feature = "async-client"))]
@@ -0,0 +1,237 @@
+use bstr::{BStr, BString, ByteSlice};
+
+mod error {
+    use bstr::BString;
+
+    /// The error returned by [`object_info()`][crate::object_info()] and [`Info::from_lines()`][super::Info::from_lines()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        Transport(#[from] gix_transport::client::Error),
+        #[error(transparent)]
+        DecodePacketline(#[from] gix_transport::packetline::decode::Error),
+        #[error("The server didn't send the list of attributes before the object information")]
+        MissingAttributes,
+        #[error("Object information line {line:?} doesn't match the attributes {attributes:?}")]
+        MalformedLine { line: BString, attributes: BString },
+        #[error("The object id in line {line:?} could not be decoded")]
+        InvalidObjectId { line: BString },
+        #[error("The size {size:?} of object {id} is not a number")]
+        InvalidSize { id: gix_hash::ObjectId, size: BString },
+    }
+
+    impl gix_transport::IsSpuriousError for Error {
+        fn is_spurious(&self) -> bool {
+            match self {
+                Error::Io(err) => err.is_spurious(),
+                Error::Transport(err) => err.is_spurious(),
+                _ => false,
+            }
+        }
+    }
+}
+pub use error::Error;
+
+/// Information about a single object as sent by the server in response to the `object-info` command.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+pub struct Info {
+    /// The id of the object.
+    pub id: gix_hash::ObjectId,
+    /// The size of the object in bytes, or `None` if the server doesn't have the object.
+    pub size: Option<u64>,
+    /// The type of the object, like `blob` or `commit`, or `None` if the server doesn't have the object or doesn't support
+    /// sending it.
+    pub kind: Option<BString>,
+}
+
+impl Info {
+    /// Parse the response to the `object-info` command from `lines`, with the first line listing the attributes provided
+    /// for each object, like `size`, followed by one line per object with its id and the values of all attributes.
+    ///
+    /// Attributes that aren't known are ignored.
+    pub fn from_lines<'a>(lines: impl IntoIterator<Item = &'a BStr>) -> Result<Vec<Self>, Error> {
+        let mut lines = lines.into_iter().map(|line| line.trim_end_with(|c| c == '\n'));
+        let attributes = lines.next().ok_or(Error::MissingAttributes)?;
+        let attribute_names: Vec<_> = attributes.split_str(" ").collect();
+        let mut out = Vec::new();
+        for line in lines {
+            let malformed = || Error::MalformedLine {
+                line: line.into(),
+                attributes: attributes.into(),
+            };
+            let mut tokens = line.split_str(" ");
+            let id = tokens.next().ok_or_else(malformed)?;
+            let id = gix_hash::ObjectId::from_hex(id).map_err(|_| Error::InvalidObjectId { line: line.into() })?;
+            let mut info = Info {
+                id,
+                size: None,
+                kind: None,
+            };
+            for name in &attribute_names {
+                // Objects the server doesn't have are sent with empty values.
+                let value = tokens.next().unwrap_or_default();
+                if value.is_empty() {
+                    continue;
+                }
+                match *name {
+                    b"size" => {
+                        info.size = Some(
+                            value
+                                .to_str()
+                                .ok()
+                                .and_then(|v| v.parse().ok())
+                                .ok_or_else(|| Error::InvalidSize { id, size: value.into() })?,
+                        );
+                    }
+                    b"type" => info.kind = Some(value.into()),
+                    _ => {}
+                }
+            }
+            if tokens.next().is_some() {
+                return Err(malformed());
+            }
+            out.push(info);
+        }
+        Ok(out)
+    }
+}
+
+pub(crate) mod function {
+    use bstr::{BString, ByteSlice};
+    use gix_transport::client::{Capabilities, Transport, TransportV2Ext};
+    use maybe_async::maybe_async;
+
+    use super::{Error, Info};
+    use crate::{command::Feature, Command};
+
+    /// Invoke an `object-info` V2 command on `transport`, which requires a prior handshake that yielded server `capabilities`
+    /// which advertise the `object-info` capability, and return the size of each object in `ids` without fetching it.
+    /// The type of each object is requested as well if the server advertises support for it.
+    /// The `agent` feature is sent along as well.
+    ///
+    /// The returned information is in the order in which the server sent it, which typically matches the order of `ids`.
+    ///
+    /// If `trace` is `true`, all packetlines received or sent will be passed to the facilities of the `gix-trace` crate.
+    #[maybe_async]
+    pub async fn object_info(
+        mut transport: impl Transport,
+        capabilities: &Capabilities,
+        ids: impl IntoIterator<Item = gix_hash::ObjectId>,
+        agent: Feature,
+        trace: bool,
+    ) -> Result<Vec<Info>, Error> {
+        let _span = gix_features::trace::detail!("gix_protocol::object_info()", capabilities = ?capabilities);
+        let object_info = Command::ObjectInfo;
+        let mut features = object_info.default_features(gix_transport::Protocol::V2, capabilities);
+        features.push(agent);
+        let mut arguments = object_info.initial_arguments(&features);
+        if capabilities
+            .capability(object_info.as_str())
+            .and_then(|c| c.supports("type"))
+            .unwrap_or(false)
+        {
+            arguments.push("type".into());
+        }
+        arguments.extend(ids.into_iter().map(|id| BString::from(format!("oid {id}"))));
+        object_info.validate_argument_prefixes_or_panic(
+            gix_transport::Protocol::V2,
+            capabilities,
+            &arguments,
+            &features,
+        );
+        let mut reader = transport
+            .invoke(
+                object_info.as_str(),
+                features.into_iter(),
+                Some(arguments.into_iter()),
+                trace,
+            )
+            .await?;
+        let mut lines = Vec::new();
+        while let Some(line) = reader
+            .readline()
+            .await
+            .transpose()?
+            .transpose()?
+            .and_then(|l| l.as_bstr())
+        {
+            lines.push(BString::from(line));
+        }
+        Info::from_lines(lines.iter().map(|line| line.as_bstr()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bstr::ByteSlice;
+
+    use super::Info;
+
+    fn id(hex: &str) -> gix_hash::ObjectId {
+        gix_hash::ObjectId::from_hex(hex.as_bytes()).expect("valid hex")
+    }
+
+    #[test]
+    fn from_lines() -> Result<(), super::Error> {
+        let infos = Info::from_lines(
+            [
+                "size type",
+                "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391 0 blob\n",
+                "4b825dc642cb6eb9a060e54bf8d69288fbee4904 ",
+                "ffffffffffffffffffffffffffffffffffffffff 42",
+            ]
+            .into_iter()
+            .map(|l| l.as_bytes().as_bstr()),
+        )?;
+        assert_eq!(
+            infos,
+            vec![
+                Info {
+                    id: id("e69de29bb2d1d6434b8b29ae775ad8c2e48c5391"),
+                    size: Some(0),
+                    kind: Some("blob".into()),
+                },
+                Info {
+                    id: id("4b825dc642cb6eb9a060e54bf8d69288fbee4904"),
+                    size: None,
+                    kind: None,
+                },
+                Info {
+                    id: id("ffffffffffffffffffffffffffffffffffffffff"),
+                    size: Some(42),
+                    kind: None,
+                }
+            ],
+            "objects the server doesn't have come without values, and missing trailing values are fine"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn from_lines_rejects_malformed_input() {
+        assert!(matches!(Info::from_lines(None), Err(super::Error::MissingAttributes)));
+        assert!(matches!(
+            Info::from_lines(["size", "not-a-hash 5"].into_iter().map(|l| l.as_bytes().as_bstr())),
+            Err(super::Error::InvalidObjectId { .. })
+        ));
+        assert!(matches!(
+            Info::from_lines(
+                ["size", "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391 five"]
+                    .into_iter()
+                    .map(|l| l.as_bytes().as_bstr())
+            ),
+            Err(super::Error::InvalidSize { .. })
+        ));
+        assert!(matches!(
+            Info::from_lines(
+                ["size", "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391 5 extra"]
+                    .into_iter()
+                    .map(|l| l.as_bytes().as_bstr())
+            ),
+            Err(super::Error::MalformedLine { .. })
+        ));
+    }
+}
@@ -0,0 +1,371 @@
+use bstr::{BStr, BString, ByteSlice};
+
+mod error {
+    use bstr::BString;
+
+    /// The error returned by [`push()`][crate::push()] and [`Report::from_lines()`][super::Report::from_lines()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        Transport(#[from] gix_transport::client::Error),
+        #[error(transparent)]
+        DecodePacketline(#[from] gix_transport::packetline::decode::Error),
+        #[error("The remote doesn't support the '{name}' capability which is required for this push")]
+        MissingCapability { name: &'static str },
+        #[error("The remote didn't report whether it could unpack the objects we sent")]
+        MissingUnpackStatus,
+        #[error("Could not parse line {line:?} of the status report sent by the remote")]
+        MalformedReportLine { line: BString },
+        #[error("Could not sign the push certificate")]
+        SignCertificate(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+        #[error("Could not produce the pack to send")]
+        WritePack(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+    }
+
+    impl gix_transport::IsSpuriousError for Error {
+        fn is_spurious(&self) -> bool {
+            match self {
+                Error::Io(err) => err.is_spurious(),
+                Error::Transport(err) => err.is_spurious(),
+                _ => false,
+            }
+        }
+    }
+}
+pub use error::Error;
+
+/// A request to change a single reference on the remote, as sent to `git-receive-pack`.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+pub struct Command {
+    /// The object the reference currently points to on the remote, or the null id if it should be created.
+    pub old: gix_hash::ObjectId,
+    /// The object the reference should point to, or the null id if it should be deleted.
+    pub new: gix_hash::ObjectId,
+    /// The full name of the reference on the remote, like `refs/heads/main`.
+    pub name: BString,
+}
+
+impl Command {
+    /// Return `true` if this command deletes the reference on the remote.
+    pub fn is_delete(&self) -> bool {
+        self.new.is_null()
+    }
+
+    /// Return `true` if this command creates the reference on the remote.
+    pub fn is_create(&self) -> bool {
+        self.old.is_null()
+    }
+}
+
+/// The information needed to send the commands of a push as part of a push certificate that is signed by the pusher,
+/// for the remote to be able to verify who requested the changes.
+pub struct Certificate<'a> {
+    /// The identity of the pusher along with the time of the push, like `Name <email> 1700000000 +0100`.
+    pub pusher: &'a BStr,
+    /// The URL of the remote without credentials, if known.
+    pub pushee: Option<&'a BStr>,
+    /// A function to produce a detached and ASCII-armored signature over the certificate passed as input,
+    /// as created by GPG or SSH for example.
+    pub sign: &'a mut SignFn<'a>,
+}
+
+/// A function to sign the certificate passed as input, returning the signature.
+pub type SignFn<'a> = dyn FnMut(&[u8]) -> Result<BString, Box<dyn std::error::Error + Send + Sync + 'static>> + 'a;
+
+impl Certificate<'_> {
+    /// Return the certificate over `commands` and `push_options` for the remote that handed out `nonce`, which is to be signed.
+    pub fn to_bstring(&self, nonce: &BStr, push_options: &[BString], commands: &[Command]) -> BString {
+        let mut out = BString::from("certificate version 0.1\n");
+        out.extend_from_slice(b"pusher ");
+        out.extend_from_slice(self.pusher);
+        out.push(b'\n');
+        if let Some(pushee) = self.pushee {
+            out.extend_from_slice(b"pushee ");
+            out.extend_from_slice(pushee);
+            out.push(b'\n');
+        }
+        out.extend_from_slice(b"nonce ");
+        out.extend_from_slice(nonce);
+        out.push(b'\n');
+        for option in push_options {
+            out.extend_from_slice(b"push-option ");
+            out.extend_from_slice(option);
+            out.push(b'\n');
+        }
+        out.push(b'\n');
+        for Command { old, new, name } in commands {
+            out.extend_from_slice(format!("{old} {new} ").as_bytes());
+            out.extend_from_slice(name);
+            out.push(b'\n');
+        }
+        out
+    }
+}
+
+/// The status of a single reference as reported by the remote after a push.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+pub struct RefStatus {
+    /// The full name of the reference on the remote.
+    pub name: BString,
+    /// The reason for the remote to reject the update, or `None` if it was applied.
+    pub rejection: Option<BString>,
+}
+
+/// The status report sent by the remote in response to a push, if the `report-status` capability was used.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Default)]
+pub struct Report {
+    /// The reason for the remote to fail unpacking the objects we sent, or `None` if that succeeded.
+    pub unpack_error: Option<BString>,
+    /// The status of each reference we requested to change, in the order the remote sent them.
+    pub refs: Vec<RefStatus>,
+}
+
+impl Report {
+    /// Parse a report from the `lines` sent by the remote in response to a push, like `unpack ok` followed by
+    /// `ok <refname>` or `ng <refname> <reason>` for each reference.
+    pub fn from_lines<'a>(lines: impl IntoIterator<Item = &'a BStr>) -> Result<Self, Error> {
+        let mut lines = lines.into_iter().map(|line| line.trim_end_with(|c| c == '\n'));
+        let malformed = |line: &[u8]| Error::MalformedReportLine { line: line.into() };
+        let unpack = lines.next().ok_or(Error::MissingUnpackStatus)?;
+        let unpack_error = match unpack.strip_prefix(b"unpack ") {
+            Some(b"ok") => None,
+            Some(reason) => Some(reason.into()),
+            None => return Err(malformed(unpack)),
+        };
+        let refs = lines
+            .map(|line| {
+                if let Some(name) = line.strip_prefix(b"ok ") {
+                    Ok(RefStatus {
+                        name: name.into(),
+                        rejection: None,
+                    })
+                } else if let Some(rest) = line.strip_prefix(b"ng ") {
+                    let (name, reason) = rest
+                        .find_byte(b' ')
+                        .map(|pos| (&rest[..pos], &rest[pos + 1..]))
+                        .ok_or_else(|| malformed(line))?;
+                    Ok(RefStatus {
+                        name: name.into(),
+                        rejection: Some(reason.into()),
+                    })
+                } else {
+                    Err(malformed(line))
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Report { unpack_error, refs })
+    }
+}
+
+pub(crate) mod function {
+    use bstr::{BString, ByteSlice};
+    #[cfg(feature = "async-client")]
+    use futures_lite::AsyncWriteExt;
+    use gix_transport::client::{Capabilities, MessageKind, Transport, WriteMode};
+    use maybe_async::maybe_async;
+    #[cfg(feature = "blocking-client")]
+    use std::io::Write;
+
+    use super::{Certificate, Command, Error, Report};
+    use crate::command::Feature;
+
+    /// Send `commands` to a `git-receive-pack` on the other side of `transport`, followed by the `pack` containing all objects
+    /// needed by the remote to apply them, and return the status report of the remote.
+    /// This requires a prior handshake for the `receive-pack` service that yielded server `capabilities`.
+    ///
+    /// `pack` is an iterator over consecutive chunks of the pack, each of which is sent as soon as it is produced so the pack
+    /// never has to be held in memory as a whole.
+    /// It should be `None` if all `commands` are deletions, and `commands` must not be empty.
+    /// If `atomic` is `true`, the remote is asked to apply either all `commands` or none of them, which fails if it
+    /// doesn't support the `atomic` capability.
+    /// `push_options` are sent to the remote after the commands, which requires it to support the `push-options` capability
+    /// if there is at least one.
+    /// If `certificate` is set, the commands are sent as part of a signed push certificate, which requires the remote to
+    /// support the `push-cert` capability.
+    /// The `report-status` capability is always requested, along with `ofs-delta` if supported, and `delete-refs` if needed.
+    /// The `agent` feature is sent along as well, and so is `session_id` if it is set and the remote supports the
+    /// `session-id` capability, which allows correlating the push with the logs of the remote.
+    ///
+    /// If `trace` is `true`, all packetlines received or sent will be passed to the facilities of the `gix-trace` crate.
+    #[maybe_async]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn push<E>(
+        mut transport: impl Transport,
+        commands: &[Command],
+        capabilities: &Capabilities,
+        agent: Feature,
+        session_id: Option<&str>,
+        pack: Option<impl Iterator<Item = Result<Vec<u8>, E>>>,
+        atomic: bool,
+        push_options: &[BString],
+        certificate: Option<Certificate<'_>>,
+        trace: bool,
+    ) -> Result<Report, Error>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let _span = gix_features::trace::detail!("gix_protocol::push()", capabilities = ?capabilities);
+        let mut requested = Vec::new();
+        for (name, needed) in [
+            ("report-status", true),
+            ("delete-refs", commands.iter().any(Command::is_delete)),
+            ("atomic", atomic),
+            ("push-options", !push_options.is_empty()),
+        ] {
+            if !needed {
+                continue;
+            }
+            if !capabilities.contains(name) {
+                return Err(Error::MissingCapability { name });
+            }
+            requested.push(name.to_owned());
+        }
+        if capabilities.contains("ofs-delta") {
+            requested.push("ofs-delta".into());
+        }
+        if let (name, Some(value)) = agent {
+            requested.push(format!("{name}={value}"));
+        }
+        if let Some(session_id) = session_id.filter(|_| capabilities.contains("session-id")) {
+            requested.push(format!("session-id={session_id}"));
+        }
+
+        let certificate = match certificate {
+            Some(certificate) => {
+                let nonce = capabilities
+                    .capability("push-cert")
+                    .and_then(|cap| cap.value().map(ToOwned::to_owned))
+                    .ok_or(Error::MissingCapability { name: "push-cert" })?;
+                let mut text = certificate.to_bstring(nonce.as_ref(), push_options, commands);
+                let signature = (certificate.sign)(&text).map_err(Error::SignCertificate)?;
+                text.extend_from_slice(&signature);
+                if !text.ends_with(b"\n") {
+                    text.push(b'\n');
+                }
+                Some(text)
+            }
+            None => None,
+        };
+
+        let mut writer = transport.request(WriteMode::Binary, MessageKind::Flush, trace)?;
+        match certificate {
+            Some(certificate) => {
+                let mut line = BString::from("push-cert\0");
+                line.extend_from_slice(requested.join(" ").as_bytes());
+                line.push(b'\n');
+                writer.write_all(&line).await?;
+                for line in certificate.lines_with_terminator() {
+                    writer.write_all(line).await?;
+                }
+                writer.write_all(b"push-cert-end\n").await?;
+            }
+            None => {
+                for (idx, Command { old, new, name }) in commands.iter().enumerate() {
+                    let mut line = BString::from(format!("{old} {new} "));
+                    line.extend_from_slice(name);
+                    if idx == 0 {
+                        line.push(0);
+                        line.extend_from_slice(requested.join(" ").as_bytes());
+                    }
+                    line.push(b'\n');
+                    writer.write_all(&line).await?;
+                }
+            }
+        }
+        writer.write_message(MessageKind::Flush).await?;
+        if !push_options.is_empty() {
+            for option in push_options {
+                let mut line = option.clone();
+                line.push(b'\n');
+                writer.write_all(&line).await?;
+            }
+            writer.write_message(MessageKind::Flush).await?;
+        }
+        let (mut writer, mut reader) = writer.into_parts();
+        for chunk in pack.into_iter().flatten() {
+            let chunk = chunk.map_err(|err| Error::WritePack(err.into()))?;
+            writer.write_all(&chunk).await?;
+        }
+        writer.flush().await?;
+        drop(writer);
+
+        let mut lines = Vec::new();
+        while let Some(line) = reader
+            .readline()
+            .await
+            .transpose()?
+            .transpose()?
+            .and_then(|l| l.as_bstr())
+        {
+            lines.push(BString::from(line));
+        }
+        Report::from_lines(lines.iter().map(|l| l.as_bstr()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bstr::ByteSlice;
+
+    use super::{RefStatus, Report};
+
+    #[test]
+    fn from_lines() -> Result<(), super::Error> {
+        let report = Report::from_lines(
+            [
+                "unpack ok\n",
+                "ok refs/heads/main\n",
+                "ng refs/heads/feature non-fast-forward",
+            ]
+            .into_iter()
+            .map(|l| l.as_bytes().as_bstr()),
+        )?;
+        assert_eq!(
+            report,
+            Report {
+                unpack_error: None,
+                refs: vec![
+                    RefStatus {
+                        name: "refs/heads/main".into(),
+                        rejection: None,
+                    },
+                    RefStatus {
+                        name: "refs/heads/feature".into(),
+                        rejection: Some("non-fast-forward".into()),
+                    }
+                ]
+            }
+        );
+
+        let report = Report::from_lines(Some(b"unpack index-pack abnormal exit".as_bstr()))?;
+        assert_eq!(
+            report.unpack_error.as_ref().map(|e| e.as_bstr()),
+            Some("index-pack abnormal exit".into())
+        );
+        assert!(report.refs.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn from_lines_rejects_missing_unpack_status_and_malformed_lines() {
+        assert!(matches!(
+            Report::from_lines(None),
+            Err(super::Error::MissingUnpackStatus)
+        ));
+        assert!(matches!(
+            Report::from_lines(Some(b"ok refs/heads/main".as_bstr())),
+            Err(super::Error::MalformedReportLine { .. })
+        ));
+        assert!(matches!(
+            Report::from_lines(
+                ["unpack ok", "ng refs/heads/main"]
+                    .into_iter()
+                    .map(|l| l.as_bytes().as_bstr())
+            ),
+            Err(super::Error::MalformedReportLine { .. })
+        ));
+    }
+}
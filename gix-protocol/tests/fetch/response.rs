@@ -209,7 +209,7 @@ mod v2 {
         use gix_packetline::read::ProgressAction;
         use gix_protocol::fetch::{
             self,
-            response::{Acknowledgement, ShallowUpdate},
+            response::{Acknowledgement, PackfileUri, ShallowUpdate},
         };
         use gix_transport::Protocol;
 
@@ -324,6 +324,34 @@ mod v2 {
             Ok(())
         }
 
+        #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+        async fn clone_with_packfile_uris() -> crate::Result {
+            let mut provider = mock_reader("v2/clone-packfile-uris.response");
+            let mut reader = provider.as_read_without_sidebands();
+            let r = fetch::Response::from_line_reader(Protocol::V2, &mut reader, true, true).await?;
+            assert_eq!(
+                r.packfile_uris(),
+                &[
+                    PackfileUri {
+                        hash: id("f34c9be7e0c3ef2c3ed7c62cc7791dbf6dc5ec9a"),
+                        uri: "https://cdn.example.com/pack-1.pack".into()
+                    },
+                    PackfileUri {
+                        hash: id("0123456789012345678901234567890123456789"),
+                        uri: "https://cdn.example.com/pack-2.pack".into()
+                    }
+                ]
+            );
+            assert!(r.has_pack(), "the inline pack follows the uris");
+            reader.set_progress_handler(Some(Box::new(|_is_err, _text| {
+                gix_transport::packetline::read::ProgressAction::Continue
+            })));
+            let mut buf = Vec::new();
+            let bytes_read = reader.read_to_end(&mut buf).await?;
+            assert_eq!(bytes_read, 876, "should be able to read the whole pack");
+            Ok(())
+        }
+
         #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
         async fn fetch_acks_without_pack() -> crate::Result {
             let mut provider = mock_reader("v2/fetch-no-pack.response");
@@ -99,3 +99,6 @@ pub mod prepare;
 
 ///
 pub mod commit;
+
+///
+pub mod symbolic;
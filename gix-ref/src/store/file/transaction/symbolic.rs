@@ -0,0 +1,188 @@
+use gix_hash::ObjectId;
+use gix_object::bstr::BStr;
+
+use crate::{
+    store_impl::{file, file::transaction},
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    Category, FullNameRef, Target,
+};
+
+mod error {
+    use gix_object::bstr::BString;
+
+    use crate::store_impl::file;
+
+    /// The error returned by [`file::Store::set_symbolic_reference()`] and [`file::Store::delete_symbolic_reference()`].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Refusing to let the symbolic reference {full_name:?} point to itself")]
+        SelfReference { full_name: BString },
+        #[error("Refusing to point {full_name:?} outside of refs/, to {target:?}")]
+        TargetOutsideOfRefs { full_name: BString, target: BString },
+        #[error("Refusing to delete {full_name:?}")]
+        DeleteHead { full_name: BString },
+        #[error("The reference {full_name:?} is not symbolic")]
+        NotSymbolic { full_name: BString },
+        #[error(transparent)]
+        Prepare(#[from] file::transaction::prepare::Error),
+        #[error(transparent)]
+        Commit(#[from] file::transaction::commit::Error),
+        #[error("Could not resolve the previous or new object to write into the reflog")]
+        Find(#[from] file::find::Error),
+        #[error(transparent)]
+        Reflog(#[from] file::log::create_or_update::Error),
+    }
+}
+pub use error::Error;
+
+/// Symbolic references
+impl file::Store {
+    /// Let the symbolic reference `name`, like `HEAD` or `refs/remotes/origin/HEAD`, point to the reference `target`,
+    /// creating it if needed while adhering to `expected`, and return the edit as committed, with the value it had
+    /// before in its `expected` field.
+    ///
+    /// Like `git symbolic-ref`, `HEAD` may only point to references in `refs/`, and if `log_message` is set, it's written into the
+    /// reflog of `name` by `committer` along with the object `name` resolved to before and the one `target` resolves to now.
+    /// Nothing is logged if `target` doesn't exist yet.
+    /// This is unlike [transactions](file::Store::transaction()), which don't write reflogs for changes to symbolic references.
+    ///
+    /// `lock_fail_mode` determines how to deal with a reference that is already locked.
+    pub fn set_symbolic_reference(
+        &self,
+        name: &FullNameRef,
+        target: &FullNameRef,
+        expected: PreviousValue,
+        log_message: Option<&BStr>,
+        committer: Option<gix_actor::SignatureRef<'_>>,
+        lock_fail_mode: gix_lock::acquire::Fail,
+    ) -> Result<RefEdit, Error> {
+        if name == target {
+            return Err(Error::SelfReference {
+                full_name: name.as_bstr().into(),
+            });
+        }
+        if is_head(name) && !target.as_bstr().starts_with(b"refs/") {
+            return Err(Error::TargetOutsideOfRefs {
+                full_name: name.as_bstr().into(),
+                target: target.as_bstr().into(),
+            });
+        }
+
+        let mut transaction = self.transaction().prepare(
+            Some(RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: false,
+                        message: log_message.map(ToOwned::to_owned).unwrap_or_default(),
+                    },
+                    expected,
+                    new: Target::Symbolic(target.to_owned()),
+                },
+                name: name.to_owned(),
+                deref: false,
+            }),
+            lock_fail_mode,
+            lock_fail_mode,
+        )?;
+        let previous = match &mut single_edit(&mut transaction).change {
+            Change::Update { expected, .. } => match expected {
+                PreviousValue::MustExistAndMatch(previous) => Some(previous.clone()),
+                // The reference doesn't exist, and we don't want the transaction to write a reflog on its own.
+                _ => {
+                    *expected = PreviousValue::MustNotExist;
+                    None
+                }
+            },
+            Change::Delete { .. } => unreachable!("BUG: we only create updates"),
+        };
+
+        if let Some(message) = log_message {
+            if let Some(new_oid) = self.resolve_to_id(Target::Symbolic(target.to_owned()))? {
+                let previous_oid = match previous {
+                    Some(previous) => self.resolve_to_id(previous)?,
+                    None => None,
+                };
+                self.reflog_create_or_append(name, previous_oid, &new_oid, committer, message, false)?;
+            }
+        }
+        Ok(transaction.commit(committer)?.pop().expect("exactly one edit"))
+    }
+
+    /// Delete the symbolic reference `name` along with its reflog, and return the edit as committed, with the value it had in its
+    /// `expected` field.
+    ///
+    /// If `expected_target` is set, the deletion fails unless `name` points to it, and it always fails if `name` doesn't exist
+    /// or isn't symbolic. Like `git symbolic-ref --delete`, `HEAD` can't be deleted.
+    ///
+    /// `lock_fail_mode` determines how to deal with a reference that is already locked.
+    pub fn delete_symbolic_reference(
+        &self,
+        name: &FullNameRef,
+        expected_target: Option<&FullNameRef>,
+        lock_fail_mode: gix_lock::acquire::Fail,
+    ) -> Result<RefEdit, Error> {
+        if is_head(name) {
+            return Err(Error::DeleteHead {
+                full_name: name.as_bstr().into(),
+            });
+        }
+        let mut transaction = self.transaction().prepare(
+            Some(RefEdit {
+                change: Change::Delete {
+                    expected: expected_target.map_or(PreviousValue::MustExist, |target| {
+                        PreviousValue::MustExistAndMatch(Target::Symbolic(target.to_owned()))
+                    }),
+                    log: RefLog::AndReference,
+                },
+                name: name.to_owned(),
+                deref: false,
+            }),
+            lock_fail_mode,
+            lock_fail_mode,
+        )?;
+        if !matches!(
+            single_edit(&mut transaction).change,
+            Change::Delete {
+                expected: PreviousValue::MustExistAndMatch(Target::Symbolic(_)),
+                ..
+            }
+        ) {
+            return Err(Error::NotSymbolic {
+                full_name: name.as_bstr().into(),
+            });
+        }
+        Ok(transaction.commit(None)?.pop().expect("exactly one edit"))
+    }
+
+    /// Follow `target` to the object it ultimately points to, or return `None` if one of the references on the way doesn't exist
+    /// or if there are too many of them.
+    fn resolve_to_id(&self, mut target: Target) -> Result<Option<ObjectId>, file::find::Error> {
+        const MAX_REF_DEPTH: usize = 5;
+        for _ in 0..MAX_REF_DEPTH {
+            match target {
+                Target::Object(id) => return Ok(Some(id)),
+                Target::Symbolic(name) => match self.try_find(name.as_ref().as_partial_name())? {
+                    Some(reference) => target = reference.target,
+                    None => return Ok(None),
+                },
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn single_edit<'a>(transaction: &'a mut file::Transaction<'_, '_>) -> &'a mut RefEdit {
+    let updates: &mut Vec<transaction::Edit> = transaction.updates.as_mut().expect("prepared");
+    assert_eq!(updates.len(), 1, "symbolic references are never split");
+    &mut updates[0].update
+}
+
+fn is_head(name: &FullNameRef) -> bool {
+    name.file_name() == "HEAD"
+        && matches!(
+            name.category(),
+            Some(Category::PseudoRef | Category::MainPseudoRef | Category::LinkedPseudoRef { .. })
+        )
+}
@@ -0,0 +1,210 @@
+use gix_lock::acquire::Fail;
+use gix_ref::{
+    file::transaction::symbolic,
+    transaction::{Change, LogChange, PreviousValue, RefEdit},
+    FullName, Target,
+};
+
+use crate::{
+    file::transaction::prepare_and_commit::{committer, empty_store, log_line, reflog_lines},
+    hex_to_id,
+};
+
+fn name(name: &str) -> FullName {
+    name.try_into().expect("valid")
+}
+
+fn create_branches(store: &gix_ref::file::Store) -> crate::Result {
+    store
+        .transaction()
+        .prepare(
+            [
+                ("refs/heads/main", "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391"),
+                ("refs/heads/other", "4b825dc642cb6eb9a060e54bf8d69288fbee4904"),
+            ]
+            .into_iter()
+            .map(|(branch, hex)| RefEdit {
+                change: Change::Update {
+                    log: LogChange::default(),
+                    expected: PreviousValue::MustNotExist,
+                    new: Target::Object(hex_to_id(hex)),
+                },
+                name: name(branch),
+                deref: false,
+            }),
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+    Ok(())
+}
+
+#[test]
+fn set_symbolic_reference_logs_resolved_ids() -> crate::Result {
+    let (_dir, store) = empty_store()?;
+    create_branches(&store)?;
+    let (main_id, other_id) = (
+        hex_to_id("e69de29bb2d1d6434b8b29ae775ad8c2e48c5391"),
+        hex_to_id("4b825dc642cb6eb9a060e54bf8d69288fbee4904"),
+    );
+
+    let edit = store.set_symbolic_reference(
+        name("HEAD").as_ref(),
+        name("refs/heads/main").as_ref(),
+        PreviousValue::MustNotExist,
+        Some("init".into()),
+        Some(committer().to_ref()),
+        Fail::Immediately,
+    )?;
+    assert!(
+        matches!(
+            edit.change,
+            Change::Update {
+                expected: PreviousValue::MustNotExist,
+                ..
+            }
+        ),
+        "HEAD didn't exist"
+    );
+    assert_eq!(store.find("HEAD")?.target, Target::Symbolic(name("refs/heads/main")));
+
+    let edit = store.set_symbolic_reference(
+        name("HEAD").as_ref(),
+        name("refs/heads/other").as_ref(),
+        PreviousValue::MustExistAndMatch(Target::Symbolic(name("refs/heads/main"))),
+        Some("checkout: moving from main to other".into()),
+        Some(committer().to_ref()),
+        Fail::Immediately,
+    )?;
+    assert_eq!(
+        edit.change.previous_value(),
+        Some(Target::Symbolic(name("refs/heads/main")).to_ref()),
+        "the previous value is returned"
+    );
+
+    store.set_symbolic_reference(
+        name("HEAD").as_ref(),
+        name("refs/heads/unborn").as_ref(),
+        PreviousValue::Any,
+        Some("ignored as the target doesn't exist".into()),
+        Some(committer().to_ref()),
+        Fail::Immediately,
+    )?;
+    assert_eq!(
+        reflog_lines(&store, "HEAD")?,
+        vec![
+            log_line(main_id.kind().null(), main_id, "init"),
+            log_line(main_id, other_id, "checkout: moving from main to other"),
+        ],
+        "like git, the ids the reference resolves to are logged, unless the new target doesn't exist"
+    );
+    Ok(())
+}
+
+#[test]
+fn set_symbolic_reference_validates_its_target() -> crate::Result {
+    let (_dir, store) = empty_store()?;
+    let err = store
+        .set_symbolic_reference(
+            name("HEAD").as_ref(),
+            name("FETCH_HEAD").as_ref(),
+            PreviousValue::Any,
+            None,
+            None,
+            Fail::Immediately,
+        )
+        .unwrap_err();
+    assert!(matches!(err, symbolic::Error::TargetOutsideOfRefs { .. }));
+
+    let err = store
+        .set_symbolic_reference(
+            name("refs/remotes/origin/HEAD").as_ref(),
+            name("refs/remotes/origin/HEAD").as_ref(),
+            PreviousValue::Any,
+            None,
+            None,
+            Fail::Immediately,
+        )
+        .unwrap_err();
+    assert!(matches!(err, symbolic::Error::SelfReference { .. }));
+    assert!(store.try_find("HEAD")?.is_none(), "nothing was written");
+    Ok(())
+}
+
+#[test]
+fn delete_symbolic_reference_only_deletes_symbolic_references() -> crate::Result {
+    let (_dir, store) = empty_store()?;
+    create_branches(&store)?;
+    store.set_symbolic_reference(
+        name("refs/remotes/origin/HEAD").as_ref(),
+        name("refs/heads/main").as_ref(),
+        PreviousValue::MustNotExist,
+        None,
+        None,
+        Fail::Immediately,
+    )?;
+
+    let err = store
+        .delete_symbolic_reference(name("refs/heads/main").as_ref(), None, Fail::Immediately)
+        .unwrap_err();
+    assert!(matches!(err, symbolic::Error::NotSymbolic { .. }));
+    assert!(
+        store.try_find("refs/heads/main")?.is_some(),
+        "the deletion was rolled back"
+    );
+
+    let err = store
+        .delete_symbolic_reference(
+            name("refs/remotes/origin/HEAD").as_ref(),
+            Some(name("refs/heads/other").as_ref()),
+            Fail::Immediately,
+        )
+        .unwrap_err();
+    assert!(
+        matches!(err, symbolic::Error::Prepare(_)),
+        "the expected target must match"
+    );
+
+    let err = store
+        .delete_symbolic_reference(name("HEAD").as_ref(), None, Fail::Immediately)
+        .unwrap_err();
+    assert!(matches!(err, symbolic::Error::DeleteHead { .. }));
+
+    let edit = store.delete_symbolic_reference(
+        name("refs/remotes/origin/HEAD").as_ref(),
+        Some(name("refs/heads/main").as_ref()),
+        Fail::Immediately,
+    )?;
+    assert_eq!(
+        edit.change.previous_value(),
+        Some(Target::Symbolic(name("refs/heads/main")).to_ref())
+    );
+    assert!(store.try_find("refs/remotes/origin/HEAD")?.is_none());
+    assert!(
+        store.try_find("refs/heads/main")?.is_some(),
+        "the referent is left untouched"
+    );
+    Ok(())
+}
+
+#[test]
+fn set_symbolic_reference_validates_worktree_heads_like_head() -> crate::Result {
+    let (_dir, store) = empty_store()?;
+    for head in ["main-worktree/HEAD", "worktrees/wt/HEAD"] {
+        let err = store
+            .set_symbolic_reference(
+                name(head).as_ref(),
+                name("ORIG_HEAD").as_ref(),
+                PreviousValue::Any,
+                None,
+                None,
+                Fail::Immediately,
+            )
+            .unwrap_err();
+        assert!(
+            matches!(err, symbolic::Error::TargetOutsideOfRefs { .. }),
+            "{head} is validated like HEAD"
+        );
+    }
+    Ok(())
+}
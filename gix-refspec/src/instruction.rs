@@ -26,6 +26,11 @@ pub enum Push<'a> {
         /// The reference or pattern to delete on the remote.
         ref_or_pattern: &'a BStr,
     },
+    /// Exclude a single ref or all refs matching a pattern from being pushed by other specs.
+    Exclude {
+        /// A full local ref name to exclude, or a pattern with a single `*`. It cannot be a spelled out object hash.
+        src: &'a BStr,
+    },
     /// Push a single ref or refspec to a known destination ref.
     Matching {
         /// The source ref or refspec to push. If pattern, it contains a single `*`.
@@ -50,9 +55,9 @@ pub enum Fetch<'a> {
         /// Note that this may not be a glob pattern, as those need to be matched by a destination which isn't present here.
         src: &'a BStr,
     },
-    /// Exclude a single ref.
+    /// Exclude a single ref or all refs matching a pattern from being fetched by other specs.
     Exclude {
-        /// A single partial or full ref name to exclude on the remote, or a pattern with a single `*`. It cannot be a spelled out object hash.
+        /// A full ref name to exclude on the remote, or a pattern with a single `*`. It cannot be a spelled out object hash.
         src: &'a BStr,
     },
     /// Fetch from `src` and update the corresponding destination branches in `dst` accordingly.
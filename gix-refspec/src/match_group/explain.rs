@@ -0,0 +1,161 @@
+use bstr::BString;
+use gix_hash::ObjectId;
+
+use super::{Matcher, Needle};
+use crate::{
+    match_group::{validate, Item},
+    types::Mode,
+    MatchGroup,
+};
+
+/// A positive spec that matched an item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecMatch {
+    /// The index of the matching spec as seen from the match group.
+    pub spec_index: usize,
+    /// The name the item would be written to, or `None` if the spec has no destination.
+    pub destination: Option<BString>,
+}
+
+/// Everything that all specs of a match group have to say about a single item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemExplanation {
+    /// The index into the initial `items` list.
+    pub item_index: usize,
+    /// The full name of the item.
+    pub full_ref_name: BString,
+    /// All positive specs that matched the item, in the order of the match group.
+    pub matches: Vec<SpecMatch>,
+    /// The index of the first negative spec that matched the item, which removes all of its `matches`.
+    pub excluded_by: Option<usize>,
+}
+
+impl ItemExplanation {
+    /// Return `true` if the item is part of the outcome of [`MatchGroup::match_remotes()`],
+    /// i.e. if it was matched by a positive spec and not excluded by a negative one.
+    pub fn is_mapped(&self) -> bool {
+        !self.matches.is_empty() && self.excluded_by.is_none()
+    }
+
+    /// Return `true` if more than one positive spec matched the item, which is fine unless they write different destinations
+    /// that are also written by other items, and which may be a sign of redundant specs.
+    pub fn is_overlap(&self) -> bool {
+        self.matches.len() > 1
+    }
+}
+
+/// A spec that names an object directly, which always matches as the object is expected to exist on the remote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectSpec {
+    /// The index of the spec as seen from the match group.
+    pub spec_index: usize,
+    /// The object to obtain.
+    pub id: ObjectId,
+    /// The name the object would be written to, or `None` if the spec has no destination.
+    pub destination: Option<BString>,
+}
+
+/// A structured account of how each spec of a [`MatchGroup`] relates to each item, to understand complex sets of specs.
+#[derive(Debug, Clone)]
+pub struct Explanation<'spec> {
+    /// The match group that produced this explanation.
+    pub group: MatchGroup<'spec>,
+    /// One explanation per item that was matched by at least one spec, positive or negative, in the order of the input items.
+    pub items: Vec<ItemExplanation>,
+    /// All specs that name an object instead of a reference.
+    pub objects: Vec<ObjectSpec>,
+    /// The indices of all specs, positive or negative, that didn't match a single item and thus have no effect.
+    pub unmatched_specs: Vec<usize>,
+    /// All issues that would cause [validation](crate::match_group::Outcome::validated()) to fail, like multiple sources
+    /// being written to the same destination.
+    pub conflicts: Vec<validate::Issue>,
+}
+
+impl Explanation<'_> {
+    /// Return all items that were matched by more than one positive spec.
+    pub fn overlaps(&self) -> impl Iterator<Item = &ItemExplanation> + '_ {
+        self.items.iter().filter(|item| item.is_overlap())
+    }
+
+    /// Return all items that were matched by a positive spec but excluded by a negative one.
+    pub fn excluded(&self) -> impl Iterator<Item = &ItemExplanation> + '_ {
+        self.items
+            .iter()
+            .filter(|item| !item.matches.is_empty() && item.excluded_by.is_some())
+    }
+}
+
+/// Explaining
+impl<'spec> MatchGroup<'spec> {
+    /// Match all `items` against all specs like [`match_remotes()`](Self::match_remotes()) would, but instead of producing
+    /// mappings, explain which specs match which items, where they would be written to, which items are excluded by
+    /// negative specs, which specs have no effect and which destinations are in conflict.
+    ///
+    /// This is meant for debugging sets of specs, and is more costly than matching.
+    pub fn explain<'item>(self, items: impl Iterator<Item = Item<'item>> + Clone) -> Explanation<'spec> {
+        let matchers: Vec<_> = self.specs.iter().copied().map(Matcher::from).collect();
+        let mut objects = Vec::new();
+        for (spec_index, matcher) in matchers.iter().enumerate() {
+            if let Some(Needle::Object(id)) = matcher.lhs {
+                objects.push(ObjectSpec {
+                    spec_index,
+                    id,
+                    destination: matcher.rhs.map(|rhs| rhs.to_bstr().into_owned()),
+                });
+            }
+        }
+
+        let mut spec_matched = vec![false; self.specs.len()];
+        let mut explained = Vec::new();
+        for (item_index, item) in items.clone().enumerate() {
+            let mut matches = Vec::new();
+            let mut excluded_by = None;
+            for (spec_index, (spec, matcher)) in self.specs.iter().zip(matchers.iter()).enumerate() {
+                if matches!(matcher.lhs, Some(Needle::Object(_))) {
+                    continue;
+                }
+                let (matched, destination) = matcher.matches_lhs(item);
+                if !matched {
+                    continue;
+                }
+                spec_matched[spec_index] = true;
+                if spec.mode == Mode::Negative {
+                    excluded_by.get_or_insert(spec_index);
+                } else {
+                    matches.push(SpecMatch {
+                        spec_index,
+                        destination: destination.map(std::borrow::Cow::into_owned),
+                    });
+                }
+            }
+            if !matches.is_empty() || excluded_by.is_some() {
+                explained.push(ItemExplanation {
+                    item_index,
+                    full_ref_name: item.full_ref_name.to_owned(),
+                    matches,
+                    excluded_by,
+                });
+            }
+        }
+        for object in &objects {
+            spec_matched[object.spec_index] = true;
+        }
+        let unmatched_specs = spec_matched
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, matched)| (!matched).then_some(idx))
+            .collect();
+
+        let conflicts = match self.clone().match_remotes(items).validated() {
+            Ok(_) => Vec::new(),
+            Err(err) => err.issues,
+        };
+        Explanation {
+            group: self,
+            items: explained,
+            objects,
+            unmatched_specs,
+            conflicts,
+        }
+    }
+}
@@ -8,6 +8,9 @@ pub use types::{Item, Mapping, Outcome, Source, SourceRef};
 ///
 pub mod validate;
 
+///
+pub mod explain;
+
 /// Initialization
 impl<'a> MatchGroup<'a> {
     /// Take all the fetch ref specs from `specs` get a match group ready.
@@ -109,6 +112,17 @@ impl<'a> MatchGroup<'a> {
     }
 }
 
+/// Exclusion
+impl MatchGroup<'_> {
+    /// Return `true` if `item` is matched by any negative spec of this group, and thus would never be part of a mapping.
+    pub fn is_excluded(&self, item: Item<'_>) -> bool {
+        self.specs
+            .iter()
+            .filter(|spec| spec.mode == Mode::Negative)
+            .any(|spec| Matcher::from(*spec).matches_lhs(item).0)
+    }
+}
+
 fn calculate_hash<T: std::hash::Hash>(t: &T) -> u64 {
     use std::hash::Hasher;
     let mut s = std::collections::hash_map::DefaultHasher::new();
@@ -8,7 +8,7 @@ use crate::{
 };
 
 /// All possible issues found while validating matched mappings.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Issue {
     /// Multiple sources try to write the same destination.
     ///
@@ -8,14 +8,10 @@ pub enum Error {
     NegativeWithDestination,
     #[error("Negative specs must not be empty")]
     NegativeEmpty,
-    #[error("Negative specs are only supported when fetching")]
-    NegativeUnsupported,
     #[error("Negative specs must be object hashes")]
     NegativeObjectHash,
     #[error("Negative specs must be full ref names, starting with \"refs/\"")]
     NegativePartialName,
-    #[error("Negative glob patterns are not allowed")]
-    NegativeGlobPattern,
     #[error("Fetch destinations must be ref-names, like 'HEAD:refs/heads/branch'")]
     InvalidFetchDestination,
     #[error("Cannot push into an empty destination")]
@@ -62,9 +58,6 @@ pub(crate) mod function {
         let mode = match spec.first() {
             Some(&b'^') => {
                 spec = &spec[1..];
-                if operation == Operation::Push {
-                    return Err(Error::NegativeUnsupported);
-                }
                 Mode::Negative
             }
             Some(&b'+') => {
@@ -130,9 +123,7 @@ pub(crate) mod function {
         if mode == Mode::Negative {
             match src {
                 Some(spec) => {
-                    if src_had_pattern {
-                        return Err(Error::NegativeGlobPattern);
-                    } else if looks_like_object_hash(spec) {
+                    if looks_like_object_hash(spec) {
                         return Err(Error::NegativeObjectHash);
                     } else if !spec.starts_with(b"refs/") && spec != "HEAD" {
                         return Err(Error::NegativePartialName);
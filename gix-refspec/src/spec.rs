@@ -147,6 +147,9 @@ impl<'a> RefSpecRef<'a> {
     ///
     /// Note that only the `source` side is considered.
     pub fn expand_prefixes(&self, out: &mut Vec<BString>) {
+        if self.mode == Mode::Negative {
+            return;
+        }
         match self.prefix() {
             Some(prefix) => out.push(prefix.into()),
             None => {
@@ -208,6 +211,7 @@ impl<'a> RefSpecRef<'a> {
                     dst,
                     allow_non_fast_forward: matches!(self.mode, Mode::Force),
                 }),
+                (Mode::Negative, Some(src), None) => Instruction::Push(Push::Exclude { src }),
                 (mode, src, dest) => {
                     unreachable!(
                         "BUG: push instructions with {:?} {:?} {:?} are not possible",
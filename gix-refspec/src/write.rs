@@ -52,6 +52,10 @@ impl Instruction<'_> {
                 out.write_all(b":")?;
                 out.write_all(ref_or_pattern)
             }
+            Instruction::Push(Push::Exclude { src }) => {
+                out.write_all(b"^")?;
+                out.write_all(src)
+            }
             Instruction::Fetch(Fetch::Only { src }) => out.write_all(src),
             Instruction::Fetch(Fetch::Exclude { src }) => {
                 out.write_all(b"^")?;
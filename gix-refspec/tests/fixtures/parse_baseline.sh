@@ -97,10 +97,12 @@ baseline fetch 'HEAD'
 baseline push '@'
 baseline fetch '@'
 
-baseline push '^@' fail
+baseline push '^@'
 baseline fetch '^@'
 baseline fetch '^refs/heads/main'
+baseline push '^refs/heads/main'
 baseline fetch '^refs/heads/*'
+baseline push '^refs/heads/*'
 baseline fetch '^heads/main'
 baseline fetch '^heads/*'
 
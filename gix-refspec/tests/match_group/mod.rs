@@ -184,3 +184,214 @@ mod multiple {
         );
     }
 }
+
+mod explain {
+    use gix_refspec::{
+        match_group::{
+            explain::{ItemExplanation, ObjectSpec, SpecMatch},
+            validate::Issue,
+            Item, Source,
+        },
+        parse::Operation,
+        MatchGroup,
+    };
+
+    fn explain(specs: &[&'static str]) -> gix_refspec::match_group::explain::Explanation<'static> {
+        let null_id = gix_hash::Kind::Sha1.null();
+        let items = [
+            "refs/heads/main",
+            "refs/heads/feature/a",
+            "refs/heads/feature/b",
+            "refs/tags/v1",
+        ]
+        .map(|name| Item {
+            full_ref_name: name.into(),
+            target: &null_id,
+            object: None,
+        });
+        MatchGroup::from_fetch_specs(
+            specs
+                .iter()
+                .map(|spec| gix_refspec::parse((*spec).into(), Operation::Fetch).unwrap()),
+        )
+        .explain(items.iter().copied())
+    }
+
+    fn matched(spec_index: usize, destination: &str) -> SpecMatch {
+        SpecMatch {
+            spec_index,
+            destination: Some(destination.into()),
+        }
+    }
+
+    #[test]
+    fn matches_exclusions_unmatched_specs_objects_and_conflicts() {
+        let out = explain(&[
+            "+refs/heads/*:refs/remotes/origin/*",
+            "refs/heads/feature/a:refs/remotes/origin/main",
+            "^refs/heads/feature/b",
+            "refs/heads/unknown:refs/heads/x",
+            "1111111111111111111111111111111111111111:refs/heads/obj",
+        ]);
+        assert_eq!(
+            out.items,
+            [
+                ItemExplanation {
+                    item_index: 0,
+                    full_ref_name: "refs/heads/main".into(),
+                    matches: vec![matched(0, "refs/remotes/origin/main")],
+                    excluded_by: None,
+                },
+                ItemExplanation {
+                    item_index: 1,
+                    full_ref_name: "refs/heads/feature/a".into(),
+                    matches: vec![
+                        matched(0, "refs/remotes/origin/feature/a"),
+                        matched(1, "refs/remotes/origin/main")
+                    ],
+                    excluded_by: None,
+                },
+                ItemExplanation {
+                    item_index: 2,
+                    full_ref_name: "refs/heads/feature/b".into(),
+                    matches: vec![matched(0, "refs/remotes/origin/feature/b")],
+                    excluded_by: Some(2),
+                },
+            ],
+            "the tag wasn't matched by any spec and thus isn't mentioned"
+        );
+        assert_eq!(
+            out.items.iter().map(ItemExplanation::is_mapped).collect::<Vec<_>>(),
+            [true, true, false]
+        );
+        assert_eq!(
+            out.overlaps().map(|item| item.item_index).collect::<Vec<_>>(),
+            [1],
+            "feature/a is matched by two specs"
+        );
+        assert_eq!(out.excluded().map(|item| item.item_index).collect::<Vec<_>>(), [2]);
+        assert_eq!(out.unmatched_specs, [3], "there is no 'unknown' branch");
+        assert_eq!(
+            out.objects,
+            [ObjectSpec {
+                spec_index: 4,
+                id: gix_hash::ObjectId::from_hex(b"1111111111111111111111111111111111111111").unwrap(),
+                destination: Some("refs/heads/obj".into()),
+            }]
+        );
+        assert_eq!(
+            out.conflicts,
+            [Issue::Conflict {
+                destination_full_ref_name: "refs/remotes/origin/main".into(),
+                sources: vec![
+                    Source::FullName("refs/heads/main".into()),
+                    Source::FullName("refs/heads/feature/a".into())
+                ],
+                specs: vec![
+                    "+refs/heads/*:refs/remotes/origin/*".into(),
+                    "refs/heads/feature/a:refs/remotes/origin/main".into()
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn without_issues() {
+        let out = explain(&["refs/heads/main:refs/remotes/origin/main", "refs/tags/*:refs/tags/*"]);
+        assert_eq!(
+            out.items
+                .iter()
+                .map(|item| (item.full_ref_name.to_string(), item.is_mapped()))
+                .collect::<Vec<_>>(),
+            [("refs/heads/main".into(), true), ("refs/tags/v1".into(), true)]
+        );
+        assert_eq!(out.overlaps().count(), 0);
+        assert!(out.unmatched_specs.is_empty());
+        assert!(out.objects.is_empty());
+        assert!(out.conflicts.is_empty());
+    }
+}
+
+mod negative {
+    use gix_refspec::{
+        match_group::{Item, SourceRef},
+        parse::Operation,
+        MatchGroup,
+    };
+
+    const NAMES: [&str; 4] = [
+        "refs/heads/main",
+        "refs/heads/wip/a",
+        "refs/heads/wip/b",
+        "refs/heads/feature",
+    ];
+
+    fn matched_sources(group: MatchGroup<'_>) -> Vec<String> {
+        let null_id = gix_hash::Kind::Sha1.null();
+        let items = NAMES.map(|name| Item {
+            full_ref_name: name.into(),
+            target: &null_id,
+            object: None,
+        });
+        group
+            .match_remotes(items.iter().copied())
+            .mappings
+            .into_iter()
+            .map(|mapping| match mapping.lhs {
+                SourceRef::FullName(name) => name.to_string(),
+                SourceRef::ObjectId(id) => id.to_string(),
+            })
+            .collect()
+    }
+
+    fn parse(specs: &[&'static str], op: Operation) -> Vec<gix_refspec::RefSpecRef<'static>> {
+        specs
+            .iter()
+            .map(|spec| gix_refspec::parse((*spec).into(), op).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn glob_patterns_exclude_fetched_refs() {
+        let specs = parse(
+            &["refs/heads/*:refs/remotes/origin/*", "^refs/heads/wip/*"],
+            Operation::Fetch,
+        );
+        assert_eq!(
+            matched_sources(MatchGroup::from_fetch_specs(specs)),
+            ["refs/heads/main", "refs/heads/feature"]
+        );
+    }
+
+    #[test]
+    fn glob_patterns_and_full_names_exclude_pushed_refs() {
+        let specs = parse(
+            &["refs/heads/*:refs/heads/*", "^refs/heads/wip/*", "^refs/heads/main"],
+            Operation::Push,
+        );
+        assert_eq!(
+            matched_sources(MatchGroup::from_push_specs(specs)),
+            ["refs/heads/feature"]
+        );
+    }
+
+    #[test]
+    fn is_excluded() {
+        let null_id = gix_hash::Kind::Sha1.null();
+        let group = MatchGroup::from_fetch_specs(parse(
+            &["refs/heads/*:refs/remotes/origin/*", "^refs/heads/wip/*"],
+            Operation::Fetch,
+        ));
+        let excluded: Vec<_> = NAMES
+            .into_iter()
+            .filter(|name| {
+                group.is_excluded(Item {
+                    full_ref_name: (*name).into(),
+                    target: &null_id,
+                    object: None,
+                })
+            })
+            .collect();
+        assert_eq!(excluded, ["refs/heads/wip/a", "refs/heads/wip/b"]);
+    }
+}
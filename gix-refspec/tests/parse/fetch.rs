@@ -72,12 +72,18 @@ fn exclude() {
     ));
     assert!(matches!(
         try_parse("^a*", Operation::Fetch).unwrap_err(),
-        Error::NegativeGlobPattern
+        Error::NegativePartialName
     ));
     assert_parse(
         "^refs/heads/a",
         Instruction::Fetch(Fetch::Exclude { src: b("refs/heads/a") }),
     );
+    assert_parse(
+        "^refs/heads/wip/*",
+        Instruction::Fetch(Fetch::Exclude {
+            src: b("refs/heads/wip/*"),
+        }),
+    );
 }
 
 #[test]
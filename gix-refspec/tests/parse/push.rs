@@ -7,13 +7,36 @@ use gix_refspec::{
 use crate::parse::{assert_parse, b, try_parse};
 
 #[test]
-fn negative_unsupported() {
-    for spec in ["^a:b", "^a:", "^:", "^:b", "^"] {
+fn negative_with_destination() {
+    for spec in ["^a:b", "^a:", "^:", "^:b"] {
         assert!(matches!(
             try_parse(spec, Operation::Push).unwrap_err(),
-            Error::NegativeUnsupported
+            Error::NegativeWithDestination
         ));
     }
+    assert!(matches!(
+        try_parse("^", Operation::Push).unwrap_err(),
+        Error::NegativeEmpty
+    ));
+}
+
+#[test]
+fn exclude() {
+    assert!(matches!(
+        try_parse("^a", Operation::Push).unwrap_err(),
+        Error::NegativePartialName
+    ));
+    assert_parse(
+        "^refs/heads/a",
+        Instruction::Push(Push::Exclude { src: b("refs/heads/a") }),
+    );
+    assert_parse(
+        "^refs/heads/wip/*",
+        Instruction::Push(Push::Exclude {
+            src: b("refs/heads/wip/*"),
+        }),
+    );
+    assert_parse("^@", Instruction::Push(Push::Exclude { src: b("HEAD") }));
 }
 
 #[test]
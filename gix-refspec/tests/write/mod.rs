@@ -30,6 +30,17 @@ mod push {
         );
     }
 
+    #[test]
+    fn exclude() {
+        assert_eq!(
+            Instruction::Push(instruction::Push::Exclude {
+                src: "refs/heads/excluded".into(),
+            })
+            .to_bstring(),
+            "^refs/heads/excluded"
+        );
+    }
+
     #[test]
     fn matching() {
         assert_eq!(
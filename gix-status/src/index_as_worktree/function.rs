@@ -9,6 +9,7 @@ use bstr::BStr;
 use filetime::FileTime;
 use gix_features::parallel::{in_parallel_if, Reduce};
 use gix_filter::pipeline::convert::ToGitOutcome;
+use gix_fs::NonRegularFiles;
 use gix_object::FindExt;
 
 use crate::index_as_worktree::Context;
@@ -387,8 +388,30 @@ impl<'index> State<'_, 'index> {
         if entry.flags.contains(gix_index::entry::Flags::INTENT_TO_ADD) {
             return Ok(Some(EntryStatus::IntentToAdd));
         }
+        // Non-regular files like named pipes must never be read, as this could block forever.
+        let is_non_regular = !metadata.is_file() && !metadata.is_symlink();
+        if is_non_regular {
+            match self.options.non_regular_files {
+                NonRegularFiles::Skip => return Ok(Some(Change::Type.into())),
+                NonRegularFiles::Error => {
+                    return Err(Error::NonRegularFile {
+                        rela_path: rela_path.into(),
+                    })
+                }
+                NonRegularFiles::AsEmptyFile => {
+                    if !matches!(
+                        entry.mode,
+                        gix_index::entry::Mode::FILE | gix_index::entry::Mode::FILE_EXECUTABLE
+                    ) {
+                        return Ok(Some(Change::Type.into()));
+                    }
+                }
+            }
+        }
         let new_stat = gix_index::entry::Stat::from_fs(&metadata)?;
-        let executable_bit_changed =
+        let executable_bit_changed = if is_non_regular {
+            false
+        } else {
             match entry
                 .mode
                 .change_to_match_fs(&metadata, self.options.fs.symlink, self.options.fs.executable_bit)
@@ -396,7 +419,8 @@ impl<'index> State<'_, 'index> {
                 Some(gix_index::entry::mode::Change::Type { .. }) => return Ok(Some(Change::Type.into())),
                 Some(gix_index::entry::mode::Change::ExecutableBit) => true,
                 None => false,
-            };
+            }
+        };
 
         // Here we implement racy-git. See racy-git.txt in the git documentation for a detailed documentation.
         //
@@ -424,7 +448,9 @@ impl<'index> State<'_, 'index> {
 
         self.buf.clear();
         self.buf2.clear();
-        let file_size_bytes = if cfg!(windows) && metadata.is_symlink() {
+        let file_size_bytes = if is_non_regular {
+            0
+        } else if cfg!(windows) && metadata.is_symlink() {
             // symlinks on Windows seem to have a length of zero, so just pretend
             // they have the correct length to avoid short-cutting, and enforce a full buffer check.
             u64::from(entry.stat.size)
@@ -437,6 +463,7 @@ impl<'index> State<'_, 'index> {
             rela_path,
             entry,
             file_len: file_size_bytes,
+            is_non_regular,
             filter: &mut self.filter,
             attr_stack: &mut self.attr_stack,
             options: self.options,
@@ -504,6 +531,8 @@ where
     path: &'a Path,
     rela_path: &'a BStr,
     file_len: u64,
+    /// If `true`, the worktree file is a non-regular file that must be treated as empty file.
+    is_non_regular: bool,
     entry: &'a gix_index::Entry,
     filter: &'a mut gix_filter::Pipeline,
     attr_stack: &'a mut gix_worktree::Stack,
@@ -535,7 +564,13 @@ where
         //
         let is_symlink = self.entry.mode == gix_index::entry::Mode::SYMLINK;
         // TODO: what to do about precompose unicode and ignore_case for symlinks
-        let out = if is_symlink && self.options.fs.symlink {
+        let out = if self.is_non_regular {
+            Stream {
+                inner: ToGitOutcome::Buffer(self.buf),
+                bytes: None,
+                len: Some(0),
+            }
+        } else if is_symlink && self.options.fs.symlink {
             // conversion to bstr can never fail because symlinks are only used
             // on unix (by git) so no reason to use the try version here
             let symlink_path =
@@ -18,6 +18,8 @@ pub enum Error {
         rela_path: BString,
         source: Box<dyn std::error::Error + Send + Sync + 'static>,
     },
+    #[error("The tracked file at '{rela_path}' is a non-regular file in the worktree")]
+    NonRegularFile { rela_path: BString },
 }
 
 /// Options that control how the index status with a worktree is computed.
@@ -31,6 +33,12 @@ pub struct Options {
     pub thread_limit: Option<usize>,
     /// Options that control how stat comparisons are made when checking if a file is fresh.
     pub stat: gix_index::entry::stat::Options,
+    /// Control how tracked files are compared if they are a named pipe, socket or other non-regular file in the worktree.
+    ///
+    /// With [`Skip`](gix_fs::NonRegularFiles::Skip), the default, they are considered to have [changed their type](Change::Type),
+    /// and with [`AsEmptyFile`](gix_fs::NonRegularFiles::AsEmptyFile) they are compared as if they were empty files.
+    /// Their content is never read.
+    pub non_regular_files: gix_fs::NonRegularFiles,
 }
 
 /// The context for [index_as_worktree()`](crate::index_as_worktree()).
@@ -703,6 +703,81 @@ fn racy_git() {
     );
 }
 
+#[test]
+#[cfg(unix)]
+fn non_regular_files_are_never_read() -> crate::Result {
+    use gix_fs::NonRegularFiles;
+
+    let dir = gix_testtools::scripted_fixture_writable_standalone("racy_git.sh")?;
+    let worktree = dir.path();
+    let git_dir = worktree.join(".git");
+    let fs = gix_fs::Capabilities::probe(&git_dir);
+    let index = gix_index::File::at(git_dir.join("index"), gix_hash::Kind::Sha1, false, Default::default())?;
+    std::fs::remove_file(worktree.join("content"))?;
+    let status = std::process::Command::new("mkfifo")
+        .arg(worktree.join("content"))
+        .status()?;
+    assert!(status.success(), "mkfifo should be available");
+
+    let run = |non_regular_files: NonRegularFiles| {
+        let mut recorder = Recorder::default();
+        let stack = gix_worktree::Stack::from_state_and_ignore_case(
+            worktree,
+            false,
+            gix_worktree::stack::State::AttributesStack(Default::default()),
+            &index,
+            index.path_backing(),
+        );
+        index_as_worktree(
+            &index,
+            worktree,
+            &mut recorder,
+            FastEq,
+            SubmoduleStatusMock { dirty: false },
+            gix_object::find::Never,
+            &mut gix_features::progress::Discard,
+            Context {
+                pathspec: default_pathspec(),
+                stack,
+                filter: Default::default(),
+                should_interrupt: &AtomicBool::default(),
+            },
+            Options {
+                fs,
+                stat: TEST_OPTIONS,
+                non_regular_files,
+                ..Options::default()
+            },
+        )
+        .map(|_out| records_to_tuple(recorder.records))
+    };
+
+    assert_eq!(
+        run(NonRegularFiles::Skip)?,
+        &[(BStr::new(b"content"), 0, Change::Type.into())],
+        "just like Git, a named pipe is considered a change of type"
+    );
+    assert_eq!(
+        run(NonRegularFiles::AsEmptyFile)?,
+        &[(
+            BStr::new(b"content"),
+            0,
+            Change::Modification {
+                executable_bit_changed: false,
+                content_change: Some(()),
+                set_entry_stat_size_zero: false
+            }
+            .into(),
+        )],
+        "the named pipe is compared like an empty file"
+    );
+    assert!(matches!(
+        run(NonRegularFiles::Error),
+        Err(gix_status::index_as_worktree::Error::NonRegularFile { rela_path }) if rela_path == "content"
+    ));
+    Ok(())
+}
+
 fn default_pathspec() -> gix_pathspec::Search {
     gix_pathspec::Search::from_specs(to_pathspecs(&[]), None, std::path::Path::new("")).expect("empty is always valid")
 }
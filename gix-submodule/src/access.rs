@@ -116,6 +116,9 @@ impl File {
     ///
     /// Git currently allows absolute paths to be used when adding submodules, but fails later as it can't find the submodule by
     /// relative path anymore. Let's play it safe here.
+    ///
+    /// Paths that [aren't safe](gix_validate::submodule::path()) to check out a submodule into, like those
+    /// that could be mistaken for a command-line option or that lead into a `.git` directory, are rejected as well.
     pub fn path(&self, name: &BStr) -> Result<Cow<'_, BStr>, config::path::Error> {
         let path_bstr =
             self.config
@@ -141,10 +144,24 @@ impl File {
                 actual: path_bstr.into_owned(),
             });
         }
+        let options = gix_validate::path::component::Options {
+            protect_windows: cfg!(windows),
+            ..Default::default()
+        };
+        if let Err(err) = gix_validate::submodule::path(path_bstr.as_ref(), options) {
+            return Err(config::path::Error::Unsafe {
+                submodule: name.to_owned(),
+                actual: path_bstr.into_owned(),
+                source: err,
+            });
+        }
         Ok(path_bstr)
     }
 
     /// Retrieve the `url` field of the submodule named `name`. It's an error if it doesn't exist or is empty.
+    ///
+    /// It's also an error if the url [isn't safe](gix_validate::submodule::url()) to be passed to other programs,
+    /// for instance because it could be mistaken for a command-line option.
     pub fn url(&self, name: &BStr) -> Result<gix_url::Url, config::url::Error> {
         let url = self
             .config
@@ -158,6 +175,13 @@ impl File {
                 submodule: name.to_owned(),
             });
         }
+        if let Err(err) = gix_validate::submodule::url(url.as_ref()) {
+            return Err(config::url::Error::Unsafe {
+                submodule: name.to_owned(),
+                actual: url.into_owned(),
+                source: err,
+            });
+        }
         gix_url::Url::from_bytes(url.as_ref()).map_err(|err| config::url::Error::Parse {
             submodule: name.to_owned(),
             source: err,
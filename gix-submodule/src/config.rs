@@ -193,6 +193,12 @@ pub mod url {
         },
         #[error("The submodule '{submodule}' was missing its 'url' field or it was empty")]
         Missing { submodule: BString },
+        #[error("The url '{actual}' of submodule '{submodule}' is unsafe to use")]
+        Unsafe {
+            submodule: BString,
+            actual: BString,
+            source: gix_validate::submodule::url::Error,
+        },
     }
 }
 
@@ -210,5 +216,11 @@ pub mod path {
         Missing { submodule: BString },
         #[error("The path '{actual}' would lead outside of the repository worktree")]
         OutsideOfWorktree { actual: BString, submodule: BString },
+        #[error("The path '{actual}' of submodule '{submodule}' is unsafe to use")]
+        Unsafe {
+            actual: BString,
+            submodule: BString,
+            source: gix_validate::submodule::path::Error,
+        },
     }
 }
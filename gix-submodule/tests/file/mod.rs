@@ -191,6 +191,31 @@ mod path {
         ));
         assert!(matches!(submodule_path(""), Error::Missing { .. }));
         assert!(matches!(submodule_path("../attack"), Error::OutsideOfWorktree { .. }));
+        assert!(matches!(
+            submodule_path("a/../../attack"),
+            Error::OutsideOfWorktree { .. }
+        ));
+        assert!(matches!(
+            submodule_path("-attack"),
+            Error::Unsafe {
+                source: gix_validate::submodule::path::Error::OptionLike,
+                ..
+            }
+        ));
+        assert!(matches!(
+            submodule_path("a/../attack"),
+            Error::Unsafe {
+                source: gix_validate::submodule::path::Error::ParentComponent,
+                ..
+            }
+        ));
+        assert!(matches!(
+            submodule_path("a/.git/hooks"),
+            Error::Unsafe {
+                source: gix_validate::submodule::path::Error::Component { .. },
+                ..
+            }
+        ));
 
         {
             let module = submodule("[submodule.a]\n path");
@@ -235,6 +260,20 @@ mod url {
         }
 
         assert!(matches!(submodule_url("file://"), Error::Parse { .. }));
+        assert!(matches!(
+            submodule_url("-u./payload"),
+            Error::Unsafe {
+                source: gix_validate::submodule::url::Error::OptionLike,
+                ..
+            }
+        ));
+        assert!(matches!(
+            submodule_url("../../:attack"),
+            Error::Unsafe {
+                source: gix_validate::submodule::url::Error::RelativeWithoutPath,
+                ..
+            }
+        ));
     }
 }
 
@@ -30,6 +30,12 @@ impl ForksafeTempfile {
 }
 
 impl ForksafeTempfile {
+    pub fn path(&self) -> &Path {
+        match &self.inner {
+            TempfileOrTemppath::Tempfile(file) => file.path(),
+            TempfileOrTemppath::Temppath(path) => path,
+        }
+    }
     pub fn as_mut_tempfile(&mut self) -> Option<&mut NamedTempFile> {
         match &mut self.inner {
             TempfileOrTemppath::Tempfile(file) => Some(file),
@@ -24,6 +24,8 @@
 //! * The application is performing a write operation on the tempfile when a signal arrives, preventing this tempfile to be removed,
 //!   but not others. Any other operation dealing with the tempfile suffers from the same issue.
 //!
+//! Use the [`orphan`] module to find and remove such files later, for instance when opening a repository or during maintenance.
+//!
 //! [`signal-hook`]: https://docs.rs/signal-hook
 //!
 //! ## Feature Flags
@@ -80,6 +82,10 @@ mod hashmap {
             self.inner.lock().remove(key).map(|v| (key.clone(), v))
         }
 
+        pub fn collect_values<T>(&self, mut cb: impl FnMut(&V) -> Option<T>) -> Vec<T> {
+            self.inner.lock().values().filter_map(|v| cb(v)).collect()
+        }
+
         pub fn for_each<F>(&self, cb: F)
         where
             Self: Sized,
@@ -110,6 +116,9 @@ use crate::handle::{Closed, Writable};
 ///
 pub mod registry;
 
+///
+pub mod orphan;
+
 static NEXT_MAP_INDEX: AtomicUsize = AtomicUsize::new(0);
 static REGISTRY: Lazy<HashMap<usize, Option<ForksafeTempfile>>> = Lazy::new(|| {
     #[cfg(feature = "signals")]
@@ -0,0 +1,147 @@
+//! Find tempfiles and lock files that were left behind by processes which crashed or were killed.
+//!
+//! Tempfiles created with [`new()`](crate::new()) have names that no other file has, which is why they can be removed
+//! once they are old enough. Lock files like `index.lock` however may be held by any process for any amount of time,
+//! also by `git` itself, so they are only reported as candidates and it's up to the caller to decide if they should be removed.
+//!
+//! Files that are currently [registered](crate::registry::registered_tempfiles()) in this process are never considered orphaned.
+#![allow(clippy::empty_docs)]
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+/// The prefix of the names of tempfiles created in a directory.
+const TEMPFILE_PREFIX: &str = ".tmp";
+/// The extension of lock files, as created by `gix-lock`.
+const LOCK_EXTENSION: &str = "lock";
+
+/// Options for use in [`find()`] and [`remove()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Only consider files as orphaned if they weren't modified for at least this long.
+    ///
+    /// Processes that are still alive may hold locks or write tempfiles for a long time, so this should be generous.
+    pub min_age: Duration,
+    /// If `true`, descend into subdirectories, which is useful to find lock files of references in `refs/`.
+    pub recursive: bool,
+}
+
+impl Default for Options {
+    /// Use a `min_age` of one hour, similar to the grace period `git` uses before removing stale temporary packs.
+    fn default() -> Self {
+        Options {
+            min_age: Duration::from_secs(60 * 60),
+            recursive: true,
+        }
+    }
+}
+
+/// The kind of an [orphaned file](Orphan).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// A tempfile with a non-clashing name, as created by [`new()`](crate::new()).
+    Tempfile,
+    /// A lock file like `index.lock`, whose owning process isn't known and which may still be in use.
+    ///
+    /// These are never removed by [`remove()`].
+    LockFile,
+}
+
+/// A file that seems to be left behind by a process that didn't get to clean it up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Orphan {
+    /// The path to the file.
+    pub path: PathBuf,
+    /// The time since the file was last modified.
+    pub age: Duration,
+    /// The kind of file.
+    pub kind: Kind,
+}
+
+/// Find all tempfiles and lock files in `directory` that are at least as old as configured in `options`.
+///
+/// A directory that doesn't exist contains no orphans.
+pub fn find(directory: &Path, options: Options) -> std::io::Result<Vec<Orphan>> {
+    let registered: Vec<_> = crate::registry::registered_tempfiles()
+        .into_iter()
+        .map(|tf| tf.path)
+        .collect();
+    let now = SystemTime::now();
+    let mut out = Vec::new();
+    let mut dirs = vec![directory.to_owned()];
+    while let Some(dir) = dirs.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        };
+        for entry in entries {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            let path = entry.path();
+            if file_type.is_dir() {
+                if options.recursive {
+                    dirs.push(path);
+                }
+                continue;
+            }
+            let Some(kind) = kind_of(&entry.file_name()) else {
+                continue;
+            };
+            if registered.contains(&path) {
+                continue;
+            }
+            let age = entry
+                .metadata()?
+                .modified()
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .unwrap_or_default();
+            if age >= options.min_age {
+                out.push(Orphan { path, age, kind });
+            }
+        }
+    }
+    out.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(out)
+}
+
+/// Remove all orphaned [tempfiles](Kind::Tempfile) in `directory` as determined by [`find()`] with `options`, and return them.
+///
+/// Lock files are left alone as they may still be held by a slow process. Use [`find()`] to obtain them and remove them
+/// individually if that's known to be safe.
+/// Files that vanished in the meantime are ignored.
+pub fn remove(directory: &Path, options: Options) -> std::io::Result<Vec<Orphan>> {
+    let mut orphans = find(directory, options)?;
+    let mut err = None;
+    orphans.retain(|orphan| {
+        if orphan.kind != Kind::Tempfile {
+            return false;
+        }
+        match std::fs::remove_file(&orphan.path) {
+            Ok(()) => true,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => false,
+            Err(e) => {
+                err.get_or_insert(e);
+                false
+            }
+        }
+    });
+    match err {
+        Some(err) => Err(err),
+        None => Ok(orphans),
+    }
+}
+
+fn kind_of(file_name: &OsStr) -> Option<Kind> {
+    let name = file_name.to_str()?;
+    if name.starts_with(TEMPFILE_PREFIX) {
+        return Some(Kind::Tempfile);
+    }
+    Path::new(name)
+        .extension()
+        .map_or(false, |ext| ext == LOCK_EXTENSION)
+        .then_some(Kind::LockFile)
+}
@@ -1,5 +1,40 @@
+use std::path::PathBuf;
+
 use crate::REGISTRY;
 
+/// A tempfile that is currently registered, as returned by [`registered_tempfiles()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Registered {
+    /// The path to the tempfile.
+    pub path: PathBuf,
+    /// The id of the process that created the tempfile, which differs from the current one if it was inherited by a fork.
+    pub owning_process_id: u32,
+}
+
+/// Return all tempfiles that are currently registered in our global registry, for instance to assure they are not
+/// considered [orphaned](crate::orphan).
+///
+/// # Note
+///
+/// Must not be called from within signal hooks as it allocates.
+pub fn registered_tempfiles() -> Vec<Registered> {
+    let to_registered = |tf: &crate::ForksafeTempfile| Registered {
+        path: tf.path().to_owned(),
+        owning_process_id: tf.owning_process_id,
+    };
+    #[cfg(feature = "hp-hashmap")]
+    {
+        REGISTRY
+            .iter()
+            .filter_map(|tf| tf.value().as_ref().map(to_registered))
+            .collect()
+    }
+    #[cfg(not(feature = "hp-hashmap"))]
+    {
+        REGISTRY.collect_values(|tf| tf.as_ref().map(to_registered))
+    }
+}
+
 /// Remove all tempfiles still registered on our global registry, and leak their data to be signal-safe.
 /// This happens on a best-effort basis with all errors being ignored.
 ///
@@ -1,4 +1,5 @@
 mod handle;
+mod orphan;
 
 #[cfg(feature = "signals")]
 mod setup {
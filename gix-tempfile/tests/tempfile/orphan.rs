@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use gix_tempfile::{
+    orphan::{self, Kind, Options},
+    AutoRemove, ContainingDirectory,
+};
+
+fn min_age(min_age: Duration) -> Options {
+    Options {
+        min_age,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn registered_tempfiles_are_never_orphaned() -> crate::Result {
+    let dir = tempfile::tempdir()?;
+    let tempfile = gix_tempfile::new(dir.path(), ContainingDirectory::Exists, AutoRemove::Tempfile)?;
+    let lock = gix_tempfile::mark_at(
+        dir.path().join("index.lock"),
+        ContainingDirectory::Exists,
+        AutoRemove::Tempfile,
+    )?;
+    assert!(orphan::find(dir.path(), min_age(Duration::ZERO))?.is_empty());
+    drop((tempfile, lock));
+    Ok(())
+}
+
+#[test]
+fn lock_files_are_orphaned_by_age() -> crate::Result {
+    let dir = tempfile::tempdir()?;
+    std::fs::write(dir.path().join("index.lock"), b"")?;
+    std::fs::create_dir_all(dir.path().join("refs").join("heads"))?;
+    let nested_lock = dir.path().join("refs").join("heads").join("main.lock");
+    std::fs::write(&nested_lock, b"")?;
+    std::fs::write(dir.path().join("index"), b"")?;
+
+    assert!(
+        orphan::find(dir.path(), Options::default())?.is_empty(),
+        "fresh lock files may still be in use"
+    );
+    let orphans = orphan::find(
+        dir.path(),
+        Options {
+            min_age: Duration::ZERO,
+            recursive: false,
+        },
+    )?;
+    assert_eq!(orphans.len(), 1, "only the top-level lock file is found");
+    assert_eq!(orphans[0].path, dir.path().join("index.lock"));
+    assert_eq!(orphans[0].kind, Kind::LockFile);
+
+    let orphans = orphan::find(dir.path(), min_age(Duration::ZERO))?;
+    assert_eq!(orphans.len(), 2);
+    assert_eq!(orphans[1].path, nested_lock);
+
+    let removed = orphan::remove(dir.path(), min_age(Duration::ZERO))?;
+    assert!(
+        removed.is_empty(),
+        "lock files are never removed as they may still be held"
+    );
+    assert!(nested_lock.is_file());
+    assert!(dir.path().join("index").is_file(), "other files are left alone");
+    Ok(())
+}
+
+#[test]
+fn missing_directories_have_no_orphans() -> crate::Result {
+    let dir = tempfile::tempdir()?;
+    assert!(orphan::find(&dir.path().join("missing"), Options::default())?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn old_tempfiles_are_removed() -> crate::Result {
+    let dir = tempfile::tempdir()?;
+    let orphaned = dir.path().join(".tmpabcdef");
+    std::fs::write(&orphaned, b"")?;
+
+    assert!(
+        orphan::find(dir.path(), Options::default())?.is_empty(),
+        "fresh tempfiles may still be in use"
+    );
+    let removed = orphan::remove(dir.path(), min_age(Duration::ZERO))?;
+    assert_eq!(removed.len(), 1);
+    assert_eq!(removed[0].path, orphaned);
+    assert_eq!(removed[0].kind, Kind::Tempfile);
+    assert!(!orphaned.exists());
+    Ok(())
+}
@@ -9,8 +9,12 @@ pub(crate) mod function {
     /// [local repositories][crate::client::file::connect()],
     /// [repositories over ssh][crate::client::ssh::connect()],
     /// [git daemons][crate::client::git::connect()],
+    /// [remote helpers][crate::client::remote_helper::connect()] for unknown schemes and `<transport>::<address>` urls,
     /// and if compiled in connections to [git repositories over https][crate::client::http::connect()].
     ///
+    /// Transports for custom schemes, or replacements for the built-in ones, can be [registered](crate::client::registry::register())
+    /// and take precedence.
+    ///
     /// Use `options` to further control specifics of the transport resulting from the connection.
     pub fn connect<Url, E>(url: Url, options: super::Options) -> Result<Box<dyn Transport + Send>, Error>
     where
@@ -18,8 +22,14 @@ pub(crate) mod function {
         gix_url::parse::Error: From<E>,
     {
         let mut url = url.try_into().map_err(gix_url::parse::Error::from)?;
+        if let Some(factory) = crate::client::registry::lookup(&url.scheme) {
+            return factory(url, &options).map_err(Error::Connection);
+        }
         Ok(match url.scheme {
-            gix_url::Scheme::Ext(_) => return Err(Error::UnsupportedScheme(url.scheme)),
+            gix_url::Scheme::Ext(_) => Box::new(
+                crate::client::blocking_io::remote_helper::connect(url, options.version, options.trace)
+                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
+            ),
             gix_url::Scheme::File => {
                 if url.user().is_some() || url.password().is_some() || url.host().is_some() || url.port.is_some() {
                     return Err(Error::UnsupportedUrlTokens {
@@ -50,6 +60,7 @@ pub(crate) mod function {
                         path,
                         options.version,
                         url.port,
+                        options.proxy.as_deref(),
                         options.trace,
                     )
                     .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?
@@ -5,6 +5,7 @@ use std::{
     ffi::{OsStr, OsString},
     io::Write,
     process::{self, Stdio},
+    sync::Arc,
 };
 
 use bstr::{io::BufReadExt, BStr, BString, ByteSlice};
@@ -45,18 +46,30 @@ pub struct SpawnProcessOnDemand {
     /// The environment variables to set in the invoked command.
     envs: Vec<(&'static str, String)>,
     ssh_disallow_shell: bool,
+    ssh_host_key_policy: Option<ssh::host_key::Policy>,
+    ssh_on_unknown_host: Option<Arc<ssh::host_key::Callback>>,
+    #[cfg_attr(not(unix), allow(dead_code))]
+    ssh_prompt: Option<Arc<gix_prompt::Handler>>,
+    #[cfg(unix)]
+    ssh_askpass: Option<gix_prompt::forward::Askpass>,
+    ssh_config: Option<ssh::config::Host>,
     connection: Option<git::Connection<Box<dyn std::io::Read + Send>, process::ChildStdin>>,
     child: Option<process::Child>,
     trace: bool,
 }
 
 impl SpawnProcessOnDemand {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new_ssh(
         url: gix_url::Url,
         program: impl Into<OsString>,
         path: BString,
         ssh_kind: ssh::ProgramKind,
         ssh_disallow_shell: bool,
+        ssh_host_key_policy: Option<ssh::host_key::Policy>,
+        ssh_on_unknown_host: Option<Arc<ssh::host_key::Callback>>,
+        ssh_prompt: Option<Arc<gix_prompt::Handler>>,
+        ssh_config: Option<ssh::config::Host>,
         version: Protocol,
         trace: bool,
     ) -> SpawnProcessOnDemand {
@@ -66,6 +79,12 @@ impl SpawnProcessOnDemand {
             ssh_cmd: Some((program.into(), ssh_kind)),
             envs: Default::default(),
             ssh_disallow_shell,
+            ssh_host_key_policy,
+            ssh_on_unknown_host,
+            ssh_prompt,
+            #[cfg(unix)]
+            ssh_askpass: None,
+            ssh_config,
             child: None,
             connection: None,
             desired_version: version,
@@ -82,6 +101,12 @@ impl SpawnProcessOnDemand {
                 .then(|| vec![("GIT_PROTOCOL", format!("version={}", version as usize))])
                 .unwrap_or_default(),
             ssh_disallow_shell: false,
+            ssh_host_key_policy: None,
+            ssh_on_unknown_host: None,
+            ssh_prompt: None,
+            #[cfg(unix)]
+            ssh_askpass: None,
+            ssh_config: None,
             child: None,
             connection: None,
             desired_version: version,
@@ -189,8 +214,14 @@ fn supervise_stderr(
         .stack_size(128 * 1024)
         .spawn(move || -> std::io::Result<()> {
             let mut process_stderr = std::io::stderr();
+            let mut host_key = ssh::host_key::Parser::default();
             for line in std::io::BufReader::new(stderr).byte_lines() {
                 let line = line?;
+                if let Some(err) = host_key.parse_line(line.as_bstr()) {
+                    send.send(std::io::Error::new(std::io::ErrorKind::PermissionDenied, err))
+                        .ok();
+                    continue;
+                }
                 match ssh_kind.line_to_err(line.into()) {
                     Ok(err) => {
                         send.send(err).ok();
@@ -213,11 +244,67 @@ impl client::Transport for SpawnProcessOnDemand {
         service: Service,
         extra_parameters: &'a [(&'a str, Option<&'a str>)],
     ) -> Result<SetServiceResponse<'_>, client::Error> {
+        self.spawn(service)?;
+        if let Some(on_unknown_host) = self.ssh_on_unknown_host.clone() {
+            if let Some(io_err) = self.peek_connection_error() {
+                let Some(err) = ssh::host_key::Error::from_io_error(&io_err) else {
+                    return Err(client::Error::Io(io_err));
+                };
+                let decision = if err.kind == ssh::host_key::Mismatch::Unknown {
+                    on_unknown_host(err)
+                } else {
+                    ssh::host_key::Decision::Reject
+                };
+                let previous_policy = self.ssh_host_key_policy;
+                self.ssh_host_key_policy = Some(match decision {
+                    ssh::host_key::Decision::Accept => ssh::host_key::Policy::AcceptNew,
+                    ssh::host_key::Decision::AcceptOnce => ssh::host_key::Policy::AcceptOnce,
+                    ssh::host_key::Decision::Reject => return Err(client::Error::Io(io_err)),
+                });
+                gix_features::trace::debug!(host = ?err.host, decision = ?decision, "accepting key of unknown host and reconnecting");
+                self.spawn(service)?;
+                if decision == ssh::host_key::Decision::AcceptOnce {
+                    // Let the next connection ask again.
+                    self.ssh_host_key_policy = previous_policy;
+                }
+            }
+        }
+        self.connection
+            .as_mut()
+            .expect("connection to be there right after setting it")
+            .handshake(service, extra_parameters)
+    }
+}
+
+impl SpawnProcessOnDemand {
+    /// Peek at the first line sent by the remote to see if the connection failed, for instance due to host key verification.
+    ///
+    /// The returned error is the one that was read, as it won't be produced again by the connection.
+    fn peek_connection_error(&mut self) -> Option<std::io::Error> {
+        let connection = self.connection.as_mut().expect("spawned");
+        match connection.line_provider.peek_line() {
+            Some(Err(err)) => Some(err),
+            _ => None,
+        }
+    }
+
+    fn spawn(&mut self, service: Service) -> Result<(), client::Error> {
+        if let Some(mut child) = self.child.take() {
+            child.kill().ok();
+            child.wait().ok();
+        }
         let (mut cmd, ssh_kind, cmd_name) = match &self.ssh_cmd {
             Some((command, kind)) => (
-                kind.prepare_invocation(command, &self.url, self.desired_version, self.ssh_disallow_shell)
-                    .map_err(client::Error::SshInvocation)?
-                    .stderr(Stdio::piped()),
+                kind.prepare_invocation(
+                    command,
+                    &self.url,
+                    self.desired_version,
+                    self.ssh_disallow_shell,
+                    self.ssh_host_key_policy,
+                    self.ssh_config.as_ref(),
+                )
+                .map_err(client::Error::SshInvocation)?
+                .stderr(Stdio::piped()),
                 Some(*kind),
                 Cow::Owned(command.to_owned()),
             ),
@@ -246,7 +333,20 @@ impl client::Transport for SpawnProcessOnDemand {
         for env_to_remove in ENV_VARS_TO_REMOVE {
             cmd.env_remove(env_to_remove);
         }
-        cmd.envs(std::mem::take(&mut self.envs));
+        cmd.envs(self.envs.iter().map(|(name, value)| (*name, value)));
+        #[cfg(unix)]
+        if let Some(prompt) = self
+            .ssh_prompt
+            .clone()
+            .filter(|_| ssh_kind == Some(ssh::ProgramKind::Ssh))
+        {
+            if self.ssh_askpass.is_none() {
+                self.ssh_askpass = Some(gix_prompt::forward::Askpass::new(prompt, ssh::prompt_kind)?);
+            }
+            let askpass = self.ssh_askpass.as_ref().expect("just set");
+            cmd.env("SSH_ASKPASS", askpass.program())
+                .env("SSH_ASKPASS_REQUIRE", "force");
+        }
 
         gix_features::trace::debug!(command = ?cmd, "gix_transport::SpawnProcessOnDemand");
         let mut child = cmd.spawn().map_err(|err| client::Error::InvokeProgram {
@@ -269,10 +369,7 @@ impl client::Transport for SpawnProcessOnDemand {
             self.trace,
         ));
         self.child = Some(child);
-        self.connection
-            .as_mut()
-            .expect("connection to be there right after setting it")
-            .handshake(service, extra_parameters)
+        Ok(())
     }
 }
 
@@ -319,8 +416,7 @@ mod tests {
                     let url = gix_url::parse((*url).into()).expect("valid url");
                     let options = ssh::connect::Options {
                         command: Some("unrecognized".into()),
-                        disallow_shell: false,
-                        kind: None,
+                        ..Default::default()
                     };
                     assert!(matches!(
                         ssh::connect(url, Protocol::V1, options, false),
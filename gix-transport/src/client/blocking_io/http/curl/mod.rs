@@ -62,6 +62,7 @@ pub struct Curl {
     res: Receiver<remote::Response>,
     handle: Option<thread::JoinHandle<Result<(), Error>>>,
     config: http::Options,
+    identity: Option<gix_sec::identity::Account>,
 }
 
 impl Curl {
@@ -99,6 +100,7 @@ impl Curl {
                 headers: list,
                 upload_body_kind,
                 config: self.config.clone(),
+                identity: self.identity.clone(),
             })
             .is_err()
         {
@@ -128,6 +130,7 @@ impl Default for Curl {
             req,
             res,
             config: http::Options::default(),
+            identity: None,
         }
     }
 }
@@ -157,6 +160,17 @@ impl http::Http for Curl {
         self.make_request(url, base_url, headers, Some(body))
     }
 
+    fn authenticate(&mut self, identity: Option<&gix_sec::identity::Account>) -> bool {
+        let authenticates = self
+            .config
+            .auth_method
+            .map_or(false, |method| method != http::options::AuthMethod::Basic);
+        if authenticates {
+            self.identity = identity.cloned();
+        }
+        authenticates
+    }
+
     fn configure(
         &mut self,
         config: &dyn std::any::Any,
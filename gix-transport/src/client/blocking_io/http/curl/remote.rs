@@ -13,7 +13,7 @@ use crate::client::{
     blocking_io::http::{self, curl::Error, redirect},
     http::{
         curl::curl_is_spurious,
-        options::{FollowRedirects, HttpVersion, ProxyAuthMethod, SslVersion},
+        options::{AuthMethod, FollowRedirects, HttpVersion, SslVersion},
         traits::PostBodyDataKind,
     },
 };
@@ -92,7 +92,7 @@ impl curl::easy::Handler for Handler {
                         .send(Err(io::Error::new(
                             if status == 401 {
                                 io::ErrorKind::PermissionDenied
-                            } else if (500..600).contains(&status) {
+                            } else if status == 429 || (500..600).contains(&status) {
                                 io::ErrorKind::ConnectionAborted
                             } else {
                                 io::ErrorKind::Other
@@ -113,6 +113,8 @@ pub struct Request {
     pub headers: curl::easy::List,
     pub upload_body_kind: Option<PostBodyDataKind>,
     pub config: http::Options,
+    /// The identity to authenticate with if the `auth_method` is handled by curl.
+    pub identity: Option<gix_sec::identity::Account>,
 }
 
 pub struct Response {
@@ -136,6 +138,7 @@ pub fn new() -> (
 
         let mut follow = None;
         let mut redirected_base_url = None::<String>;
+        let mut downgraded_to_http1 = false;
 
         for Request {
             url,
@@ -149,9 +152,13 @@ pub fn new() -> (
                     low_speed_limit_bytes_per_second,
                     low_speed_time_seconds,
                     connect_timeout,
+                    // Retries are performed by the transport, which knows which requests can be repeated.
+                    retries: _,
+                    retry_delay: _,
                     proxy,
                     no_proxy,
                     proxy_auth_method,
+                    auth_method,
                     user_agent,
                     proxy_authenticate,
                     verbose,
@@ -161,6 +168,7 @@ pub fn new() -> (
                     http_version,
                     backend,
                 },
+            identity,
         } in req_recv
         {
             let effective_url = redirect::swap_tails(redirected_base_url.as_deref(), &base_url, url.clone());
@@ -198,6 +206,11 @@ pub fn new() -> (
             handle.ssl_verify_peer(ssl_verify)?;
             handle.ssl_verify_host(ssl_verify)?;
 
+            let http_version = if downgraded_to_http1 {
+                Some(HttpVersion::V1_1)
+            } else {
+                http_version
+            };
             if let Some(http_version) = http_version {
                 let version = match http_version {
                     HttpVersion::V1_1 => curl::easy::HttpVersion::V11,
@@ -244,22 +257,16 @@ pub fn new() -> (
             if let Some(timeout) = connect_timeout {
                 handle.connect_timeout(timeout)?;
             }
-            {
-                let mut auth = Auth::new();
-                match proxy_auth_method {
-                    ProxyAuthMethod::AnyAuth => auth
-                        .basic(true)
-                        .digest(true)
-                        .digest_ie(true)
-                        .gssnegotiate(true)
-                        .ntlm(true)
-                        .aws_sigv4(true),
-                    ProxyAuthMethod::Basic => auth.basic(true),
-                    ProxyAuthMethod::Digest => auth.digest(true),
-                    ProxyAuthMethod::Negotiate => auth.digest_ie(true),
-                    ProxyAuthMethod::Ntlm => auth.ntlm(true),
-                };
-                handle.proxy_auth(&auth)?;
+            handle.proxy_auth(&to_curl_auth(proxy_auth_method))?;
+            if let Some(auth_method) = auth_method.filter(|method| *method != AuthMethod::Basic) {
+                // Let curl perform the handshake, which may take multiple round-trips.
+                // Without identity, `Negotiate` can still authenticate as the current user.
+                handle.http_auth(&to_curl_auth(auth_method))?;
+                let (username, password) = identity.as_ref().map_or(("", ""), |identity| {
+                    (identity.username.as_str(), identity.password.as_str())
+                });
+                handle.username(username)?;
+                handle.password(password)?;
             }
             handle.tcp_keepalive(true)?;
 
@@ -308,7 +315,27 @@ pub fn new() -> (
             });
             handle.http_headers(headers)?;
 
-            if let Err(err) = handle.perform() {
+            let mut res = handle.perform();
+            if let Err(err) = &res {
+                let handler = handle.get_mut();
+                // Only retry if the server didn't respond yet, and if we can send the request body once more.
+                let can_retry = http_version != Some(HttpVersion::V1_1)
+                    && (err.is_http2_error() || err.is_http2_stream_error())
+                    && !handler.checked_status
+                    && match handler.receive_body.as_mut() {
+                        Some(StreamOrBuffer::Buffer(cursor)) => {
+                            cursor.set_position(0);
+                            true
+                        }
+                        Some(StreamOrBuffer::Stream(_)) => upload_body_kind.is_none(),
+                        None => false,
+                    };
+                if can_retry && handle.http_version(curl::easy::HttpVersion::V11).is_ok() {
+                    downgraded_to_http1 = true;
+                    res = handle.perform();
+                }
+            }
+            if let Err(err) = res {
                 let handler = handle.get_mut();
                 handler.reset();
 
@@ -365,6 +392,24 @@ pub fn new() -> (
     (handle, req_send, res_recv)
 }
 
+fn to_curl_auth(method: AuthMethod) -> Auth {
+    let mut auth = Auth::new();
+    match method {
+        AuthMethod::AnyAuth => auth
+            .basic(true)
+            .digest(true)
+            .digest_ie(true)
+            .gssnegotiate(true)
+            .ntlm(true)
+            .aws_sigv4(true),
+        AuthMethod::Basic => auth.basic(true),
+        AuthMethod::Digest => auth.digest(true),
+        AuthMethod::Negotiate => auth.gssnegotiate(true),
+        AuthMethod::Ntlm => auth.ntlm(true),
+    };
+    auth
+}
+
 fn to_curl_ssl_version(vers: SslVersion) -> curl::easy::SslVersion {
     use curl::easy::SslVersion::*;
     match vers {
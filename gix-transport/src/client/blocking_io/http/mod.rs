@@ -19,7 +19,7 @@ use crate::{
         http::options::{HttpVersion, SslVersionRangeInclusive},
         Capabilities, ExtendedBufRead, HandleProgress, MessageKind, RequestWriter,
     },
-    Protocol, Service,
+    IsSpuriousError, Protocol, Service,
 };
 
 #[cfg(all(feature = "http-client-reqwest", feature = "http-client-curl"))]
@@ -56,9 +56,11 @@ pub mod options {
         None,
     }
 
-    /// The way to configure a proxy for authentication if a username is present in the configured proxy.
+    /// The way to authenticate against a proxy or a server.
+    ///
+    /// `Negotiate` and `Ntlm` need multiple round-trips to complete a handshake, which is performed by the HTTP backend.
     #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
-    pub enum ProxyAuthMethod {
+    pub enum AuthMethod {
         /// Automatically pick a suitable authentication method.
         #[default]
         AnyAuth,
@@ -66,12 +68,15 @@ pub mod options {
         Basic,
         /// Http digest authentication to prevent a password to be passed in clear text.
         Digest,
-        /// GSS negotiate authentication.
+        /// GSS negotiate authentication, typically using Kerberos or SPNEGO, which works without password.
         Negotiate,
         /// NTLM authentication
         Ntlm,
     }
 
+    /// The way to configure a proxy for authentication if a username is present in the configured proxy.
+    pub type ProxyAuthMethod = AuthMethod;
+
     /// Available SSL version numbers.
     #[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd)]
     #[allow(missing_docs)]
@@ -158,6 +163,15 @@ pub struct Options {
         gix_credentials::helper::Action,
         Arc<std::sync::Mutex<options::AuthenticateFn>>,
     )>,
+    /// The way to authenticate against the server, or `None` to send the identity of the transport with basic authentication
+    /// along with each request, which is the default.
+    ///
+    /// Methods other than [`Basic`](options::AuthMethod::Basic) are only used if the backend [can authenticate](Http::authenticate())
+    /// by itself, which currently is only the case for `curl`.
+    /// `Negotiate` also works without identity, using the credentials of the current user instead.
+    ///
+    /// Refers to `gitoxide.http.authMethod`.
+    pub auth_method: Option<options::AuthMethod>,
     /// The `HTTP` `USER_AGENT` string presented to an `HTTP` server, notably not the user agent present to the `git` server.
     ///
     /// If not overridden, it defaults to the user agent provided by `curl`, which is a deviation from how `git` handles this.
@@ -173,6 +187,18 @@ pub struct Options {
     /// If `None`, this typically defaults to 2 minutes to 5 minutes.
     /// Refers to `gitoxide.http.connectTimeout`.
     pub connect_timeout: Option<std::time::Duration>,
+    /// The amount of times to retry idempotent requests, like the `GET` request for the initial ref advertisement,
+    /// if they fail with a [spurious](crate::IsSpuriousError) error, like a server error, `429 Too Many Requests`
+    /// or a reset connection.
+    ///
+    /// Retries are disabled if `0`, which is the default.
+    /// Refers to `gitoxide.http.retries`.
+    pub retries: u32,
+    /// The time to wait before the first retry, which doubles with each subsequent retry and is subject to jitter.
+    ///
+    /// If `None`, it defaults to one second.
+    /// Refers to `gitoxide.http.retryDelay`.
+    pub retry_delay: Option<std::time::Duration>,
     /// If enabled, emit additional information about connections and possibly the data received or written.
     pub verbose: bool,
     /// If set, use this path to point to a file with CA certificates to verify peers.
@@ -184,6 +210,10 @@ pub struct Options {
     /// certificate and the user accepts the associated security risks.
     pub ssl_verify: bool,
     /// The HTTP version to enforce. If unset, it is implementation defined.
+    ///
+    /// Unless [`HttpVersion::V1_1`] is enforced, HTTP/2 may be negotiated via ALPN when connecting to `https://` servers.
+    /// If a request then fails due to a problem with the HTTP/2 connection and can be repeated, it is retried with HTTP/1.1,
+    /// which is used for all subsequent requests of the transport.
     pub http_version: Option<HttpVersion>,
     /// Backend specific options, if available.
     pub backend: Option<Arc<Mutex<dyn Any + Send + Sync + 'static>>>,
@@ -200,8 +230,11 @@ impl Default for Options {
             no_proxy: None,
             proxy_auth_method: Default::default(),
             proxy_authenticate: None,
+            auth_method: None,
             user_agent: None,
             connect_timeout: None,
+            retries: 0,
+            retry_delay: None,
             verbose: false,
             ssl_ca_info: None,
             ssl_version: None,
@@ -230,6 +263,8 @@ pub struct Transport<H: Http> {
     line_provider: Option<gix_packetline::StreamingPeekableIter<H::ResponseBody>>,
     identity: Option<gix_sec::identity::Account>,
     trace: bool,
+    retries: u32,
+    retry_delay: std::time::Duration,
 }
 
 impl<H: Http> Transport<H> {
@@ -254,6 +289,8 @@ impl<H: Http> Transport<H> {
             line_provider: None,
             identity,
             trace,
+            retries: 0,
+            retry_delay: DEFAULT_RETRY_DELAY,
         }
     }
 }
@@ -295,7 +332,10 @@ impl<H: Http> Transport<H> {
     }
 
     #[allow(clippy::unnecessary_wraps, unknown_lints)]
-    fn add_basic_auth_if_present(&self, headers: &mut Vec<Cow<'_, str>>) -> Result<(), client::Error> {
+    fn add_basic_auth_if_present(&mut self, headers: &mut Vec<Cow<'_, str>>) -> Result<(), client::Error> {
+        if self.http.authenticate(self.identity.as_ref()) {
+            return Ok(());
+        }
         if let Some(gix_sec::identity::Account { username, password }) = &self.identity {
             #[cfg(not(debug_assertions))]
             if self.url.starts_with("http://") {
@@ -312,6 +352,17 @@ impl<H: Http> Transport<H> {
     }
 }
 
+const DEFAULT_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Return the time to wait before retrying for the `attempt`th time, starting at 0, doubling `delay` with each attempt
+/// and randomly shortening it by up to half to avoid many clients retrying at the same time.
+fn backoff(delay: std::time::Duration, attempt: u32) -> std::time::Duration {
+    use std::hash::{BuildHasher, Hasher};
+    let delay = delay.saturating_mul(1 << attempt.min(16));
+    let jitter = std::collections::hash_map::RandomState::new().build_hasher().finish() % 1024;
+    delay / 2 + (delay / 2).mul_f64(jitter as f64 / 1024.0)
+}
+
 fn append_url(base: &str, suffix: &str) -> String {
     let mut buf = base.to_owned();
     if base.as_bytes().last() != Some(&b'/') {
@@ -386,6 +437,10 @@ impl<H: Http> client::TransportWithoutIO for Transport<H> {
     }
 
     fn configure(&mut self, config: &dyn Any) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        if let Some(options) = config.downcast_ref::<Options>() {
+            self.retries = options.retries;
+            self.retry_delay = options.retry_delay.unwrap_or(DEFAULT_RETRY_DELAY);
+        }
         self.http.configure(config)
     }
 }
@@ -422,10 +477,24 @@ impl<H: Http> client::Transport for Transport<H> {
             dynamic_headers.push(format!("Git-Protocol: {parameters}").into());
         }
         self.add_basic_auth_if_present(&mut dynamic_headers)?;
-        let GetResponse { headers, body } =
-            self.http
-                .get(url.as_ref(), &self.url, static_headers.iter().chain(&dynamic_headers))?;
-        <Transport<H>>::check_content_type(service, "advertisement", headers)?;
+        let mut attempt = 0;
+        let body = loop {
+            let res = self
+                .http
+                .get(url.as_ref(), &self.url, static_headers.iter().chain(&dynamic_headers))
+                .map_err(client::Error::from)
+                .and_then(|GetResponse { headers, body }| {
+                    <Transport<H>>::check_content_type(service, "advertisement", headers)?;
+                    Ok(body)
+                });
+            match res {
+                Err(err) if attempt < self.retries && err.is_spurious() => {
+                    std::thread::sleep(backoff(self.retry_delay, attempt));
+                    attempt += 1;
+                }
+                res => break res?,
+            }
+        };
 
         let line_reader = self.line_provider.get_or_insert_with(|| {
             gix_packetline::StreamingPeekableIter::new(body, &[PacketLineRef::Flush], self.trace)
@@ -7,7 +7,13 @@ use std::{
 
 use gix_features::io::pipe;
 
-use crate::client::http::{self, options::FollowRedirects, redirect, reqwest::Remote, traits::PostBodyDataKind};
+use crate::client::http::{
+    self,
+    options::{FollowRedirects, HttpVersion},
+    redirect,
+    reqwest::Remote,
+    traits::PostBodyDataKind,
+};
 
 /// The error returned by the 'remote' helper, a purely internal construct to perform http requests.
 #[derive(Debug, thiserror::Error)]
@@ -27,7 +33,11 @@ impl crate::IsSpuriousError for Error {
     fn is_spurious(&self) -> bool {
         match self {
             Error::Reqwest(err) => {
-                err.is_timeout() || err.is_connect() || err.status().map_or(false, |status| status.is_server_error())
+                err.is_timeout()
+                    || err.is_connect()
+                    || err.status().map_or(false, |status| {
+                        status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+                    })
             }
             _ => false,
         }
@@ -43,39 +53,57 @@ impl Default for Remote {
             let mut redirected_base_url = None::<String>;
             let allow_redirects = Arc::new(atomic::AtomicBool::new(false));
 
-            // We may error while configuring, which is expected as part of the internal protocol. The error will be
-            // received and the sender of the request might restart us.
-            let client = reqwest::blocking::ClientBuilder::new()
-                .connect_timeout(std::time::Duration::from_secs(20))
-                .http1_title_case_headers()
-                .redirect(reqwest::redirect::Policy::custom({
-                    let allow_redirects = allow_redirects.clone();
-                    move |attempt| {
-                        if allow_redirects.load(atomic::Ordering::Relaxed) {
-                            let curr_url = attempt.url();
-                            let prev_urls = attempt.previous();
+            let new_client = |http1_only: bool,
+                              proxy: Option<&str>,
+                              no_proxy: Option<&str>|
+             -> reqwest::Result<reqwest::blocking::Client> {
+                let builder = reqwest::blocking::ClientBuilder::new()
+                    .connect_timeout(std::time::Duration::from_secs(20))
+                    .http1_title_case_headers()
+                    .redirect(reqwest::redirect::Policy::custom({
+                        let allow_redirects = allow_redirects.clone();
+                        move |attempt| {
+                            if allow_redirects.load(atomic::Ordering::Relaxed) {
+                                let curr_url = attempt.url();
+                                let prev_urls = attempt.previous();
 
-                            match prev_urls.first() {
-                                Some(prev_url) if prev_url.host_str() != curr_url.host_str() => {
-                                    // git does not want to be redirected to a different host.
-                                    attempt.stop()
-                                }
-                                _ => {
-                                    // emulate default git behaviour which relies on curl default behaviour apparently.
-                                    const CURL_DEFAULT_REDIRS: usize = 50;
-                                    if prev_urls.len() >= CURL_DEFAULT_REDIRS {
-                                        attempt.error("too many redirects")
-                                    } else {
-                                        attempt.follow()
+                                match prev_urls.first() {
+                                    Some(prev_url) if prev_url.host_str() != curr_url.host_str() => {
+                                        // git does not want to be redirected to a different host.
+                                        attempt.stop()
+                                    }
+                                    _ => {
+                                        // emulate default git behaviour which relies on curl default behaviour apparently.
+                                        const CURL_DEFAULT_REDIRS: usize = 50;
+                                        if prev_urls.len() >= CURL_DEFAULT_REDIRS {
+                                            attempt.error("too many redirects")
+                                        } else {
+                                            attempt.follow()
+                                        }
                                     }
                                 }
+                            } else {
+                                attempt.stop()
                             }
-                        } else {
-                            attempt.stop()
                         }
-                    }
-                }))
-                .build()?;
+                    }));
+                let builder = match proxy {
+                    // An empty proxy disables proxying, as does a `*` in the list of hosts not to proxy.
+                    Some(proxy) if proxy.is_empty() => builder.no_proxy(),
+                    Some(_) if no_proxy.map_or(false, |no_proxy| no_proxy.trim() == "*") => builder.no_proxy(),
+                    // This also supports `socks5://` and `socks5h://` proxies.
+                    Some(proxy) => builder
+                        .proxy(reqwest::Proxy::all(proxy)?.no_proxy(no_proxy.and_then(reqwest::NoProxy::from_string))),
+                    None => builder,
+                };
+                if http1_only { builder.http1_only() } else { builder }.build()
+            };
+            // We may error while configuring, which is expected as part of the internal protocol. The error will be
+            // received and the sender of the request might restart us.
+            let mut proxy_settings = (None::<String>, None::<String>);
+            let mut default_client = new_client(false, None, None)?;
+            let mut http1_client = None;
+            let mut downgraded_to_http1 = false;
 
             for Request {
                 url,
@@ -85,6 +113,26 @@ impl Default for Remote {
                 config,
             } in req_recv
             {
+                if (&config.proxy, &config.no_proxy) != (&proxy_settings.0, &proxy_settings.1) {
+                    proxy_settings = (config.proxy.clone(), config.no_proxy.clone());
+                    default_client = new_client(false, proxy_settings.0.as_deref(), proxy_settings.1.as_deref())?;
+                    http1_client = None;
+                }
+                let use_http1 = downgraded_to_http1 || config.http_version == Some(HttpVersion::V1_1);
+                let client = if use_http1 {
+                    match &http1_client {
+                        Some(client) => client.clone(),
+                        None => http1_client
+                            .insert(new_client(
+                                true,
+                                proxy_settings.0.as_deref(),
+                                proxy_settings.1.as_deref(),
+                            )?)
+                            .clone(),
+                    }
+                } else {
+                    default_client.clone()
+                };
                 let effective_url = redirect::swap_tails(redirected_base_url.as_deref(), &base_url, url.clone());
                 let mut req_builder = if upload_body_kind.is_some() {
                     client.post(&effective_url)
@@ -135,8 +183,28 @@ impl Default for Remote {
                     *follow = FollowRedirects::None;
                 }
 
+                // Streaming bodies can't be cloned, which is when we can't fall back to HTTP/1.1 either.
+                let fallback_req = if use_http1 { None } else { req.try_clone() };
                 let mut res = match client
                     .execute(req)
+                    .or_else(|err| match fallback_req {
+                        // Without a status, the server didn't respond, which might be due to a failing HTTP/2 connection.
+                        Some(req) if err.status().is_none() && !err.is_timeout() => {
+                            let client = match &http1_client {
+                                Some(client) => client.clone(),
+                                None => http1_client
+                                    .insert(new_client(
+                                        true,
+                                        proxy_settings.0.as_deref(),
+                                        proxy_settings.1.as_deref(),
+                                    )?)
+                                    .clone(),
+                            };
+                            downgraded_to_http1 = true;
+                            client.execute(req)
+                        }
+                        _ => Err(err),
+                    })
                     .and_then(reqwest::blocking::Response::error_for_status)
                 {
                     Ok(res) => res,
@@ -145,7 +213,7 @@ impl Default for Remote {
                             Some(status) => {
                                 let kind = if status == reqwest::StatusCode::UNAUTHORIZED {
                                     std::io::ErrorKind::PermissionDenied
-                                } else if status.is_server_error() {
+                                } else if status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
                                     std::io::ErrorKind::ConnectionAborted
                                 } else {
                                     std::io::ErrorKind::Other
@@ -84,7 +84,8 @@ impl<A, B, C> From<PostResponse<A, B, C>> for GetResponse<A, B> {
 }
 
 /// A trait to abstract the HTTP operations needed to power all git interactions: read via GET and write via POST.
-/// Note that 401 must be turned into `std::io::Error(PermissionDenied)`, and other non-success http statuses must be transformed
+/// Note that 401 must be turned into `std::io::Error(PermissionDenied)`, server errors and 429 must be turned into
+/// `std::io::Error(ConnectionAborted)` so the request can be retried, and other non-success http statuses must be transformed
 /// into `std::io::Error(Other)`
 #[allow(clippy::type_complexity)]
 pub trait Http {
@@ -123,6 +124,16 @@ pub trait Http {
         body: PostBodyDataKind,
     ) -> Result<PostResponse<Self::Headers, Self::ResponseBody, Self::PostBody>, Error>;
 
+    /// Let the implementation authenticate the following requests as `identity` according to its configured
+    /// [authentication method](super::Options::auth_method), which is required for methods like `NTLM` or `Negotiate`
+    /// that need multiple round-trips.
+    ///
+    /// Return `true` if it does, or `false` to have the caller send basic authentication headers instead, which is the default.
+    fn authenticate(&mut self, identity: Option<&gix_sec::identity::Account>) -> bool {
+        let _ = identity;
+        false
+    }
+
     /// Pass `config` which can deserialize in the implementation's configuration, as documented separately.
     ///
     /// The caller must know how that `config` data looks like for the intended implementation.
@@ -10,9 +10,18 @@ pub mod http;
 mod bufread_ext;
 pub use bufread_ext::{ExtendedBufRead, HandleProgress, ReadlineBufRead};
 
+///
+pub mod registry;
+
+///
+pub mod remote_helper;
+
 mod request;
 pub use request::RequestWriter;
 
+///
+pub mod socks5;
+
 ///
 pub mod ssh;
 
@@ -0,0 +1,55 @@
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::client::{connect, Transport};
+
+/// A function to create a transport for the given URL, configured by the options passed to [`connect()`](crate::client::connect()).
+pub type Factory = dyn Fn(gix_url::Url, &connect::Options) -> Result<Box<dyn Transport + Send>, Box<dyn std::error::Error + Send + Sync>>
+    + Send
+    + Sync;
+
+static FACTORIES: RwLock<Vec<(String, Arc<Factory>)>> = RwLock::new(Vec::new());
+
+/// Install `factory` to create transports for URLs whose scheme is `scheme`, like `s3` for `s3://bucket/repo`,
+/// and return the factory previously installed for it, if any.
+///
+/// [`connect()`](crate::client::connect()) consults installed factories before the built-in transports, which
+/// makes it possible to support custom schemes, or to replace the transport of built-in ones like `https`, for the
+/// whole process.
+/// Schemes are compared case-insensitively.
+pub fn register(
+    scheme: impl Into<String>,
+    factory: impl Fn(
+            gix_url::Url,
+            &connect::Options,
+        ) -> Result<Box<dyn Transport + Send>, Box<dyn std::error::Error + Send + Sync>>
+        + Send
+        + Sync
+        + 'static,
+) -> Option<Arc<Factory>> {
+    let scheme = scheme.into();
+    let mut factories = FACTORIES.write().unwrap_or_else(PoisonError::into_inner);
+    let previous = unregister_locked(&mut factories, &scheme);
+    factories.push((scheme, Arc::new(factory)));
+    previous
+}
+
+/// Remove the factory for `scheme` and return it, if one was [installed](register()).
+pub fn unregister(scheme: &str) -> Option<Arc<Factory>> {
+    unregister_locked(&mut FACTORIES.write().unwrap_or_else(PoisonError::into_inner), scheme)
+}
+
+/// Return the factory installed for `scheme`, if there is one.
+pub fn lookup(scheme: &gix_url::Scheme) -> Option<Arc<Factory>> {
+    FACTORIES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find_map(|(name, factory)| name.eq_ignore_ascii_case(scheme.as_str()).then(|| factory.clone()))
+}
+
+fn unregister_locked(factories: &mut Vec<(String, Arc<Factory>)>, scheme: &str) -> Option<Arc<Factory>> {
+    let pos = factories
+        .iter()
+        .position(|(name, _)| name.eq_ignore_ascii_case(scheme))?;
+    Some(factories.remove(pos).1)
+}
@@ -0,0 +1,256 @@
+use std::{
+    any::Any,
+    borrow::Cow,
+    io::{BufRead, Write},
+    process::{self, Stdio},
+};
+
+use bstr::{BStr, BString, ByteSlice};
+
+use crate::{
+    client::{self, git, MessageKind, RequestWriter, SetServiceResponse, WriteMode},
+    Protocol, Service,
+};
+
+/// The error used when talking to a remote helper.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(
+        "The remote helper {program:?} does not support the 'connect' capability, which is the only one supported"
+    )]
+    MissingConnectCapability { program: String },
+    #[error("The remote helper {program:?} can only serve {service} by falling back to its own commands, which is unsupported")]
+    Fallback { program: String, service: &'static str },
+    #[error("The remote helper {program:?} exited before completing the '{command}' command")]
+    UnexpectedEof { program: String, command: &'static str },
+    #[error("The remote helper {program:?} sent the unexpected line {line:?} in response to the '{command}' command")]
+    UnexpectedResponse {
+        program: String,
+        command: &'static str,
+        line: BString,
+    },
+}
+
+impl crate::IsSpuriousError for Error {}
+
+/// A transport that spawns a `git-remote-<transport>` helper program and speaks the
+/// [remote helper protocol](https://git-scm.com/docs/gitremote-helpers) with it.
+///
+/// Only helpers with the `connect` capability are supported, as these connect their standard input and output to the
+/// `git-upload-pack` or `git-receive-pack` service on the remote side and thus can be driven like any other spawned process.
+/// Helpers which only implement the `fetch`, `push`, `import` or `export` commands are rejected with an error.
+///
+/// It can only be instantiated using [`connect()`].
+pub struct RemoteHelper {
+    desired_version: Protocol,
+    url: gix_url::Url,
+    transport: String,
+    address: BString,
+    connection: Option<git::Connection<Box<dyn std::io::Read + Send>, process::ChildStdin>>,
+    child: Option<process::Child>,
+    trace: bool,
+}
+
+impl RemoteHelper {
+    fn program(&self) -> String {
+        format!("git-remote-{}", self.transport)
+    }
+
+    fn spawn(&mut self, service: Service) -> Result<(), client::Error> {
+        if let Some(mut child) = self.child.take() {
+            child.kill().ok();
+            child.wait().ok();
+        }
+        let program = self.program();
+        let mut cmd = gix_command::prepare(program.as_str())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .arg(self.url.to_bstring().to_os_str_lossy().into_owned())
+            .arg(self.address.to_os_str_lossy().into_owned());
+        if self.desired_version != Protocol::V1 {
+            cmd = cmd.env("GIT_PROTOCOL", format!("version={}", self.desired_version as usize));
+        }
+
+        gix_features::trace::debug!(command = ?cmd, "gix_transport::RemoteHelper");
+        let mut child = cmd.spawn().map_err(|err| client::Error::InvokeProgram {
+            source: err,
+            command: program.clone().into(),
+        })?;
+        let mut stdin = child.stdin.take().expect("stdin configured");
+        let mut stdout = std::io::BufReader::new(child.stdout.take().expect("stdout configured"));
+        self.child = Some(child);
+
+        const CAPABILITIES: &str = "capabilities";
+        writeln!(stdin, "{CAPABILITIES}")?;
+        stdin.flush()?;
+        let mut supports_connect = false;
+        loop {
+            let line = read_line(&mut stdout, &program, CAPABILITIES)?;
+            if line.is_empty() {
+                break;
+            }
+            let capability = line.strip_prefix(b"*").unwrap_or(&line);
+            supports_connect |= capability == b"connect";
+        }
+        if !supports_connect {
+            return Err(client::Error::RemoteHelper(Error::MissingConnectCapability { program }));
+        }
+
+        const CONNECT: &str = "connect";
+        writeln!(stdin, "{CONNECT} {}", service.as_str())?;
+        stdin.flush()?;
+        let line = read_line(&mut stdout, &program, CONNECT)?;
+        match line.as_slice() {
+            b"" => {}
+            b"fallback" => {
+                return Err(client::Error::RemoteHelper(Error::Fallback {
+                    program,
+                    service: service.as_str(),
+                }))
+            }
+            _ => {
+                return Err(client::Error::RemoteHelper(Error::UnexpectedResponse {
+                    program,
+                    command: CONNECT,
+                    line,
+                }))
+            }
+        }
+
+        let stdout: Box<dyn std::io::Read + Send> = Box::new(stdout);
+        self.connection = Some(git::Connection::new_for_spawned_process(
+            stdout,
+            stdin,
+            self.desired_version,
+            self.address.clone(),
+            self.trace,
+        ));
+        Ok(())
+    }
+}
+
+/// Read a single line terminated by a newline, and return it without the newline.
+fn read_line(read: &mut impl BufRead, program: &str, command: &'static str) -> Result<BString, client::Error> {
+    let mut buf = Vec::new();
+    if read.read_until(b'\n', &mut buf)? == 0 || buf.last() != Some(&b'\n') {
+        return Err(client::Error::RemoteHelper(Error::UnexpectedEof {
+            program: program.into(),
+            command,
+        }));
+    }
+    buf.pop();
+    Ok(buf.trim_end_with(|c| c == '\r').into())
+}
+
+impl client::TransportWithoutIO for RemoteHelper {
+    fn request(
+        &mut self,
+        write_mode: WriteMode,
+        on_into_read: MessageKind,
+        trace: bool,
+    ) -> Result<RequestWriter<'_>, client::Error> {
+        self.connection
+            .as_mut()
+            .expect("handshake() to have been called first")
+            .request(write_mode, on_into_read, trace)
+    }
+
+    fn to_url(&self) -> Cow<'_, BStr> {
+        Cow::Owned(self.url.to_bstring())
+    }
+
+    fn connection_persists_across_multiple_requests(&self) -> bool {
+        true
+    }
+
+    fn configure(&mut self, _config: &dyn Any) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        Ok(())
+    }
+}
+
+impl client::Transport for RemoteHelper {
+    fn handshake<'a>(
+        &mut self,
+        service: Service,
+        extra_parameters: &'a [(&'a str, Option<&'a str>)],
+    ) -> Result<SetServiceResponse<'_>, client::Error> {
+        self.spawn(service)?;
+        self.connection
+            .as_mut()
+            .expect("connection to be there right after setting it")
+            .handshake(service, extra_parameters)
+    }
+}
+
+impl Drop for RemoteHelper {
+    fn drop(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            child.kill().ok();
+            child.wait().ok();
+        }
+    }
+}
+
+/// Connect to the remote identified by `url` through the `git-remote-<transport>` helper program, using the given `desired_version`.
+/// If `trace` is `true`, all packetlines received or sent will be passed to the facilities of the `gix-trace` crate.
+///
+/// The `<transport>` is the scheme of `url`, which is either parsed from `<transport>::<address>`
+/// (with `<address>` passed to the helper) or from `<transport>://…` (with the whole url passed to the helper).
+/// The helper program is looked up in `PATH` and spawned only once [handshake()](client::Transport::handshake()) is called.
+pub fn connect(
+    url: gix_url::Url,
+    desired_version: Protocol,
+    trace: bool,
+) -> Result<RemoteHelper, std::convert::Infallible> {
+    let (transport, address) = match url.remote_helper() {
+        Some((transport, address)) => (transport.to_owned(), address.to_owned()),
+        None => (url.scheme.as_str().to_owned(), url.to_bstring()),
+    };
+    Ok(RemoteHelper {
+        desired_version,
+        url,
+        transport,
+        address,
+        connection: None,
+        child: None,
+        trace,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        client::{blocking_io::remote_helper, Transport},
+        Protocol, Service,
+    };
+
+    fn connect(url: &str) -> remote_helper::RemoteHelper {
+        let url = gix_url::parse(url.into()).expect("valid url");
+        remote_helper::connect(url, Protocol::V2, false).expect("infallible")
+    }
+
+    #[test]
+    fn transport_and_address() {
+        for (url, transport, address) in [
+            ("hg::https://example.com/repo", "hg", "https://example.com/repo"),
+            ("corp::some-address", "corp", "some-address"),
+            ("corp://host/repo", "corp", "corp://host/repo"),
+        ] {
+            let helper = connect(url);
+            assert_eq!(helper.transport, transport);
+            assert_eq!(helper.address, address);
+            assert_eq!(helper.program(), format!("git-remote-{transport}"));
+        }
+    }
+
+    #[test]
+    fn missing_helper_fails_on_handshake() {
+        let mut helper = connect("gix-nonexisting-helper::address");
+        assert!(matches!(
+            helper.handshake(Service::UploadPack, &[]),
+            Err(crate::client::Error::InvokeProgram { command, .. }) if command == "git-remote-gix-nonexisting-helper"
+        ));
+    }
+}
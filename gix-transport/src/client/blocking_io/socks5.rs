@@ -0,0 +1,231 @@
+use std::{
+    io::{Read, Write},
+    net::{IpAddr, TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+/// The error returned by [`connect()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Proxy '{url}' isn't a SOCKS5 proxy URL of the form socks5[h]://[user[:password]@]host[:port]")]
+    InvalidUrl { url: String },
+    #[error("An IO error occurred when communicating with the SOCKS5 proxy")]
+    Io(#[from] std::io::Error),
+    #[error("The proxy didn't respond like a SOCKS5 proxy")]
+    InvalidResponse,
+    #[error("The proxy didn't accept any of the offered authentication methods")]
+    NoAcceptableAuthMethod,
+    #[error("The proxy rejected the username or password")]
+    AuthenticationFailed,
+    #[error("The host name '{host}' is too long to be sent to the proxy")]
+    HostTooLong { host: String },
+    #[error("The proxy could not connect to the destination: {}", reply_message(*reply))]
+    ConnectFailed { reply: u8 },
+}
+
+impl crate::IsSpuriousError for Error {
+    fn is_spurious(&self) -> bool {
+        match self {
+            Error::Io(err) => err.is_spurious(),
+            _ => false,
+        }
+    }
+}
+
+/// A parsed SOCKS5 proxy URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proxy {
+    /// The host name or IP address of the proxy.
+    pub host: String,
+    /// The port of the proxy, which defaults to 1080.
+    pub port: u16,
+    /// If `true`, the URL used the `socks5h://` scheme and the proxy resolves host names.
+    /// Otherwise they are resolved locally.
+    pub resolve_remotely: bool,
+    /// The username and password to authenticate with, if present in the URL.
+    pub credentials: Option<(String, String)>,
+}
+
+impl Proxy {
+    /// Parse `url` of the form `socks5[h]://[user[:password]@]host[:port]`, or return `None` if it's not a SOCKS5 proxy URL.
+    pub fn from_url(url: &str) -> Option<Self> {
+        let (resolve_remotely, rest) = if let Some(rest) = url.strip_prefix("socks5h://") {
+            (true, rest)
+        } else {
+            (false, url.strip_prefix("socks5://")?)
+        };
+        let rest = rest.trim_end_matches('/');
+        let (credentials, host_and_port) = match rest.rsplit_once('@') {
+            Some((user_info, host_and_port)) => {
+                let (user, password) = user_info.split_once(':').unwrap_or((user_info, ""));
+                (Some((percent_decode(user)?, percent_decode(password)?)), host_and_port)
+            }
+            None => (None, rest),
+        };
+        let (host, port) = match host_and_port.strip_prefix('[') {
+            Some(ipv6) => {
+                let (host, port) = ipv6.split_once(']')?;
+                (host, port.strip_prefix(':'))
+            }
+            None => match host_and_port.split_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (host_and_port, None),
+            },
+        };
+        if host.is_empty() || host.contains('/') {
+            return None;
+        }
+        Some(Proxy {
+            host: host.to_owned(),
+            port: port.map_or(Some(1080), |port| port.parse().ok())?,
+            resolve_remotely,
+            credentials,
+        })
+    }
+}
+
+/// Connect to `host` and `port` through the SOCKS5 proxy at `proxy_url`, which is of the form
+/// `socks5[h]://[user[:password]@]host[:port]`, and return the stream once the proxy established the connection.
+///
+/// With `socks5h://`, `host` is resolved by the proxy, which is required to reach hosts that are only known to it,
+/// like onion services. Otherwise it's resolved locally.
+/// `timeout` applies to connecting to the proxy itself.
+pub fn connect(proxy_url: &str, host: &str, port: u16, timeout: Duration) -> Result<TcpStream, Error> {
+    let proxy = Proxy::from_url(proxy_url).ok_or_else(|| Error::InvalidUrl {
+        url: proxy_url.to_owned(),
+    })?;
+    let address = (proxy.host.as_str(), proxy.port)
+        .to_socket_addrs()?
+        .next()
+        .expect("after successful resolution there is an IP address");
+    let mut stream = TcpStream::connect_timeout(&address, timeout)?;
+    let destination = if proxy.resolve_remotely {
+        match host.parse::<IpAddr>() {
+            Ok(ip) => Destination::Ip(ip),
+            Err(_) => Destination::Name(host),
+        }
+    } else {
+        Destination::Ip(
+            (host, port)
+                .to_socket_addrs()?
+                .next()
+                .expect("after successful resolution there is an IP address")
+                .ip(),
+        )
+    };
+    handshake(&mut stream, proxy.credentials.as_ref(), destination, port)?;
+    Ok(stream)
+}
+
+enum Destination<'a> {
+    Ip(IpAddr),
+    Name(&'a str),
+}
+
+/// Perform the handshake as described in RFC 1928, with username and password authentication according to RFC 1929.
+fn handshake(
+    stream: &mut (impl Read + Write),
+    credentials: Option<&(String, String)>,
+    destination: Destination<'_>,
+    port: u16,
+) -> Result<(), Error> {
+    const VERSION: u8 = 5;
+    const NO_AUTHENTICATION: u8 = 0;
+    const USERNAME_PASSWORD: u8 = 2;
+
+    match credentials {
+        Some(_) => stream.write_all(&[VERSION, 2, NO_AUTHENTICATION, USERNAME_PASSWORD])?,
+        None => stream.write_all(&[VERSION, 1, NO_AUTHENTICATION])?,
+    }
+    let mut buf = [0u8; 2];
+    stream.read_exact(&mut buf)?;
+    match buf {
+        [VERSION, NO_AUTHENTICATION] => {}
+        [VERSION, USERNAME_PASSWORD] => {
+            let (user, password) = credentials.ok_or(Error::InvalidResponse)?;
+            let mut msg = vec![1];
+            for field in [user, password] {
+                msg.push(u8::try_from(field.len()).map_err(|_| Error::AuthenticationFailed)?);
+                msg.extend_from_slice(field.as_bytes());
+            }
+            stream.write_all(&msg)?;
+            stream.read_exact(&mut buf)?;
+            if buf[1] != 0 {
+                return Err(Error::AuthenticationFailed);
+            }
+        }
+        [VERSION, 0xff] => return Err(Error::NoAcceptableAuthMethod),
+        _ => return Err(Error::InvalidResponse),
+    }
+
+    let mut msg = vec![VERSION, 1 /* connect */, 0];
+    match destination {
+        Destination::Ip(IpAddr::V4(ip)) => {
+            msg.push(1);
+            msg.extend_from_slice(&ip.octets());
+        }
+        Destination::Ip(IpAddr::V6(ip)) => {
+            msg.push(4);
+            msg.extend_from_slice(&ip.octets());
+        }
+        Destination::Name(host) => {
+            msg.push(3);
+            msg.push(u8::try_from(host.len()).map_err(|_| Error::HostTooLong { host: host.to_owned() })?);
+            msg.extend_from_slice(host.as_bytes());
+        }
+    }
+    msg.extend_from_slice(&port.to_be_bytes());
+    stream.write_all(&msg)?;
+
+    let mut reply = [0u8; 4];
+    stream.read_exact(&mut reply)?;
+    if reply[0] != VERSION {
+        return Err(Error::InvalidResponse);
+    }
+    if reply[1] != 0 {
+        return Err(Error::ConnectFailed { reply: reply[1] });
+    }
+    // Skip the address the proxy bound to, along with its port.
+    let address_len = match reply[3] {
+        1 => 4,
+        4 => 16,
+        3 => {
+            let mut len = [0u8; 1];
+            stream.read_exact(&mut len)?;
+            usize::from(len[0])
+        }
+        _ => return Err(Error::InvalidResponse),
+    };
+    let mut bound_address = [0u8; 255 + 2];
+    stream.read_exact(&mut bound_address[..address_len + 2])?;
+    Ok(())
+}
+
+fn reply_message(reply: u8) -> &'static str {
+    match reply {
+        1 => "general SOCKS server failure",
+        2 => "connection not allowed by ruleset",
+        3 => "network unreachable",
+        4 => "host unreachable",
+        5 => "connection refused",
+        6 => "TTL expired",
+        7 => "command not supported",
+        8 => "address type not supported",
+        _ => "unknown error",
+    }
+}
+
+fn percent_decode(input: &str) -> Option<String> {
+    let mut out = Vec::with_capacity(input.len());
+    let mut bytes = input.bytes();
+    while let Some(byte) = bytes.next() {
+        if byte == b'%' {
+            let hex = [bytes.next()?, bytes.next()?];
+            out.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            out.push(byte);
+        }
+    }
+    String::from_utf8(out).ok()
+}
@@ -0,0 +1,193 @@
+use std::path::{Path, PathBuf};
+
+/// The settings for a single host as resolved from an OpenSSH client configuration file, like `~/.ssh/config`.
+///
+/// Only the settings that affect how programs other than [`Ssh`](super::ProgramKind::Ssh) are invoked are retained,
+/// as `ssh` itself reads its configuration files.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Host {
+    /// The real name of the host to connect to, set with `HostName`, with `%h` tokens expanded.
+    pub host_name: Option<String>,
+    /// The name of the user to log in as, set with `User`.
+    pub user: Option<String>,
+    /// The port to connect to, set with `Port`.
+    pub port: Option<u16>,
+    /// All identity files to try, set with one or more `IdentityFile` lines, with `~` and tokens expanded.
+    pub identity_files: Vec<PathBuf>,
+    /// The jump host to connect through, set with `ProxyJump`, like `[user@]host[:port]`.
+    pub proxy_jump: Option<String>,
+}
+
+impl Host {
+    /// Return `true` if no setting was found for the host.
+    pub fn is_empty(&self) -> bool {
+        *self == Host::default()
+    }
+
+    /// Read the configuration file at `path` and resolve the settings for `host`, using the home directory of the
+    /// current user to expand `~` in paths.
+    pub fn from_file(path: &Path, host: &str) -> std::io::Result<Self> {
+        let config = std::fs::read_to_string(path)?;
+        Ok(Self::from_config(&config, host, home_dir().as_deref()))
+    }
+
+    /// Resolve the settings for `host` from the content of an ssh client configuration file in `config`,
+    /// using `home` to expand `~` and `%d` in paths.
+    ///
+    /// Like `ssh`, the first value obtained for each setting is used, except for `IdentityFile`, which accumulates.
+    /// `Host` sections are matched with `*` and `?` wildcards and support negation with `!`,
+    /// while `Match` sections other than `Match all` are ignored as their criteria are not evaluated.
+    /// `Include` directives are not followed.
+    pub fn from_config(config: &str, host: &str, home: Option<&Path>) -> Self {
+        let mut out = Host::default();
+        let mut identity_files = Vec::new();
+        let mut proxy_jump = None;
+        let mut is_active = true;
+        for line in config.lines() {
+            let (keyword, args) = match parse_line(line) {
+                Some(v) => v,
+                None => continue,
+            };
+            match keyword.to_ascii_lowercase().as_str() {
+                "host" => is_active = host_matches(&args, host),
+                "match" => is_active = args.len() == 1 && args[0].eq_ignore_ascii_case("all"),
+                _ if !is_active => {}
+                "hostname" => {
+                    if out.host_name.is_none() {
+                        out.host_name = args.first().map(|name| expand_tokens(name, host, None, None));
+                    }
+                }
+                "user" => {
+                    if out.user.is_none() {
+                        out.user = args.first().cloned();
+                    }
+                }
+                "port" => {
+                    if out.port.is_none() {
+                        out.port = args.first().and_then(|port| port.parse().ok());
+                    }
+                }
+                "identityfile" => identity_files.extend(args.first().cloned()),
+                "proxyjump" => {
+                    if proxy_jump.is_none() {
+                        proxy_jump = args.first().cloned();
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let host_name = out.host_name.as_deref().unwrap_or(host);
+        out.identity_files = identity_files
+            .iter()
+            .filter(|path| !path.eq_ignore_ascii_case("none"))
+            .map(|path| match (path.strip_prefix("~/"), home) {
+                (Some(relative), Some(home)) => {
+                    home.join(expand_tokens(relative, host_name, out.user.as_deref(), Some(home)))
+                }
+                _ => PathBuf::from(expand_tokens(path, host_name, out.user.as_deref(), home)),
+            })
+            .collect();
+        out.proxy_jump = proxy_jump.filter(|jump| !jump.eq_ignore_ascii_case("none"));
+        out
+    }
+}
+
+/// Split `line` into its keyword and arguments, or return `None` if it's empty or a comment.
+fn parse_line(line: &str) -> Option<(&str, Vec<String>)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let keyword_end = line.find(|c: char| c.is_whitespace() || c == '=').unwrap_or(line.len());
+    let (keyword, rest) = line.split_at(keyword_end);
+    let rest = rest.trim_start();
+    let rest = rest.strip_prefix('=').unwrap_or(rest);
+
+    let mut args = Vec::new();
+    let mut chars = rest.chars().peekable();
+    loop {
+        while chars.peek().map_or(false, |c| c.is_whitespace()) {
+            chars.next();
+        }
+        let mut arg = String::new();
+        let mut in_quotes = false;
+        match chars.peek() {
+            None => break,
+            Some('#') => break,
+            Some(_) => {}
+        }
+        for c in chars.by_ref() {
+            match c {
+                '"' => in_quotes = !in_quotes,
+                c if c.is_whitespace() && !in_quotes => break,
+                c => arg.push(c),
+            }
+        }
+        args.push(arg);
+    }
+    Some((keyword, args))
+}
+
+/// Return `true` if any of the `patterns` matches `host`, and none of the negated ones does.
+pub(super) fn host_matches(patterns: &[String], host: &str) -> bool {
+    let mut matched = false;
+    for pattern in patterns {
+        match pattern.strip_prefix('!') {
+            Some(negated) => {
+                if wildcard_match(negated.as_bytes(), host.as_bytes()) {
+                    return false;
+                }
+            }
+            None => matched |= wildcard_match(pattern.as_bytes(), host.as_bytes()),
+        }
+    }
+    matched
+}
+
+fn wildcard_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((b'*', rest)) => (0..=text.len()).any(|skip| wildcard_match(rest, &text[skip..])),
+        Some((b'?', rest)) => !text.is_empty() && wildcard_match(rest, &text[1..]),
+        Some((c, rest)) => text.split_first().map_or(false, |(t, text)| {
+            c.eq_ignore_ascii_case(t) && wildcard_match(rest, text)
+        }),
+    }
+}
+
+/// Expand `%h`, `%r`, `%d` and `%%` in `input`, leaving unknown tokens untouched.
+fn expand_tokens(input: &str, host: &str, user: Option<&str>, home: Option<&Path>) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('%') => out.push('%'),
+            Some('h') => out.push_str(host),
+            Some('r') if user.is_some() => out.push_str(user.unwrap_or_default()),
+            Some('d') if home.is_some() => out.push_str(&home.map(Path::to_string_lossy).unwrap_or_default()),
+            Some(other) => {
+                out.push('%');
+                out.push(other);
+            }
+            None => out.push('%'),
+        }
+    }
+    out
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| {
+            if cfg!(windows) {
+                std::env::var_os("USERPROFILE")
+            } else {
+                None
+            }
+        })
+        .map(PathBuf::from)
+}
@@ -0,0 +1,105 @@
+use std::path::{Path, PathBuf};
+
+/// A marker at the beginning of a line in a `known_hosts` file.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Marker {
+    /// `@cert-authority`, the key is a certificate authority for the matching hosts.
+    CertAuthority,
+    /// `@revoked`, the key must not be accepted for the matching hosts.
+    Revoked,
+}
+
+/// A single entry of a `known_hosts` file, as maintained by `ssh`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Entry {
+    /// The marker of the entry, if present.
+    pub marker: Option<Marker>,
+    /// The comma-separated host patterns, like `example.com,[example.com]:2222,*.example.org`, or a hashed host name
+    /// of the form `|1|<salt>|<hash>`.
+    pub hosts: String,
+    /// The type of the key, like `ssh-ed25519`.
+    pub key_type: String,
+    /// The public key, encoded as base64.
+    pub key: String,
+    /// The comment following the key, if present.
+    pub comment: Option<String>,
+}
+
+impl Entry {
+    /// Return `true` if the host names of this entry are hashed, in which case they can't be [matched](Self::matches()).
+    pub fn is_hashed(&self) -> bool {
+        self.hosts.starts_with('|')
+    }
+
+    /// Return `true` if this entry applies to `host` when connecting to `port`, using the wildcards `*` and `?`
+    /// as well as negation with `!` like `ssh` does.
+    ///
+    /// Note that [hashed](Self::is_hashed()) entries never match.
+    pub fn matches(&self, host: &str, port: Option<u16>) -> bool {
+        if self.is_hashed() {
+            return false;
+        }
+        let host = match port {
+            Some(port) if port != 22 => format!("[{host}]:{port}"),
+            _ => host.to_owned(),
+        };
+        let patterns: Vec<_> = self.hosts.split(',').map(ToOwned::to_owned).collect();
+        crate::client::ssh::config::host_matches(&patterns, &host)
+    }
+}
+
+/// Parse all entries from the content of a `known_hosts` file, skipping comments and lines that can't be parsed.
+pub fn parse(content: &str) -> Vec<Entry> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let mut tokens = line.split_whitespace();
+            let mut hosts = tokens.next()?;
+            let marker = match hosts {
+                "@cert-authority" => Some(Marker::CertAuthority),
+                "@revoked" => Some(Marker::Revoked),
+                _ => None,
+            };
+            if marker.is_some() {
+                hosts = tokens.next()?;
+            }
+            let key_type = tokens.next()?;
+            let key = tokens.next()?;
+            let comment = tokens.collect::<Vec<_>>().join(" ");
+            Some(Entry {
+                marker,
+                hosts: hosts.to_owned(),
+                key_type: key_type.to_owned(),
+                key: key.to_owned(),
+                comment: (!comment.is_empty()).then_some(comment),
+            })
+        })
+        .collect()
+}
+
+/// Read the `known_hosts` file at `path` and return all entries that [match](Entry::matches()) `host` and `port`,
+/// or an empty list if the file doesn't exist.
+///
+/// This is useful to show which keys are already known for a host when [deciding](super::Callback)
+/// whether to trust it.
+pub fn for_host(path: &Path, host: &str, port: Option<u16>) -> std::io::Result<Vec<Entry>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    Ok(parse(&content)
+        .into_iter()
+        .filter(|entry| entry.matches(host, port))
+        .collect())
+}
+
+/// Return the paths to the `known_hosts` files that `ssh` uses by default for the user whose home directory is `home`.
+pub fn default_files(home: &Path) -> [PathBuf; 2] {
+    let dir = home.join(".ssh");
+    [dir.join("known_hosts"), dir.join("known_hosts2")]
+}
@@ -0,0 +1,154 @@
+use bstr::{BStr, ByteSlice};
+
+///
+pub mod known_hosts;
+
+/// Determines how the `ssh` program should treat keys of hosts it doesn't know yet, or whose key changed.
+///
+/// It's passed as `StrictHostKeyChecking` option to programs of kind [`Ssh`](super::ProgramKind::Ssh), and ignored
+/// for all other kinds.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Policy {
+    /// Never connect to hosts whose key isn't known, nor to hosts whose key changed.
+    Strict,
+    /// Add the keys of hosts that aren't known yet to the `known_hosts` file, but refuse to connect to hosts whose key changed.
+    AcceptNew,
+    /// Let the `ssh` program ask the user to confirm keys of unknown hosts, using the terminal or `SSH_ASKPASS`.
+    Ask,
+    /// Connect to hosts that aren't known yet without adding their keys to the `known_hosts` file, but refuse to connect
+    /// to hosts whose key changed.
+    ///
+    /// Note that this overrides the `UserKnownHostsFile` option of `ssh` to use the default files.
+    AcceptOnce,
+}
+
+impl Policy {
+    /// Return the value to use for the `StrictHostKeyChecking` option of the `ssh` program.
+    pub fn as_ssh_option_value(&self) -> &'static str {
+        match self {
+            Policy::Strict => "yes",
+            Policy::AcceptNew | Policy::AcceptOnce => "accept-new",
+            Policy::Ask => "ask",
+        }
+    }
+}
+
+/// The reason for the host key verification to fail.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Mismatch {
+    /// The host isn't contained in the `known_hosts` file.
+    Unknown,
+    /// The host is known, but presented a different key than the one on record, which may indicate an attack.
+    Changed,
+}
+
+impl Mismatch {
+    fn description(&self) -> &'static str {
+        match self {
+            Mismatch::Unknown => "the host is unknown",
+            Mismatch::Changed => "the host key changed",
+        }
+    }
+}
+
+/// A host key verification failure as parsed from the output of the `ssh` program.
+///
+/// It's the inner error of an [`std::io::Error`] of kind [`PermissionDenied`](std::io::ErrorKind::PermissionDenied),
+/// which can be obtained using [`Error::from_io_error()`].
+#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+#[error("Host key verification failed for {}: {}", .host.as_deref().unwrap_or("unknown host"), .kind.description())]
+pub struct Error {
+    /// The reason for the failure.
+    pub kind: Mismatch,
+    /// The host whose key couldn't be verified, if it was mentioned by `ssh`.
+    pub host: Option<String>,
+    /// The fingerprint of the key presented by the host, like `SHA256:<base64>`, if it was mentioned by `ssh`.
+    pub fingerprint: Option<String>,
+}
+
+impl Error {
+    /// Return the host key verification error contained in `err`, if there is one.
+    pub fn from_io_error(err: &std::io::Error) -> Option<&Error> {
+        err.get_ref().and_then(|err| err.downcast_ref::<Error>())
+    }
+}
+
+/// The decision made by a [`Callback`] for a host that isn't known.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Decision {
+    /// Do not connect to the host.
+    Reject,
+    /// Add the host key to the `known_hosts` file and connect again.
+    Accept,
+    /// Connect again without adding the host key to the `known_hosts` file, so the decision has to be made again
+    /// the next time.
+    AcceptOnce,
+}
+
+/// A function called when [verification fails](Error) due to a host being [unknown](Mismatch::Unknown), to decide whether to trust it.
+///
+/// The [fingerprint](Error::fingerprint) of the key presented by the host can be shown to the user, along with the keys
+/// that are already [known](known_hosts::for_host()) for the host, if any.
+/// Note that hosts whose key [changed](Mismatch::Changed) are always rejected.
+pub type Callback = dyn Fn(&Error) -> Decision + Send + Sync;
+
+/// Collect information about host key verification failures line by line, as printed by `ssh`.
+#[derive(Default)]
+pub(crate) struct Parser {
+    changed: bool,
+    host: Option<String>,
+    fingerprint: Option<String>,
+    fingerprint_on_next_line: bool,
+}
+
+impl Parser {
+    /// Parse `line` and return an error once `ssh` indicates that host key verification failed.
+    pub(crate) fn parse_line(&mut self, line: &BStr) -> Option<Error> {
+        let line = line.trim();
+        if std::mem::take(&mut self.fingerprint_on_next_line) {
+            let fingerprint = line.strip_suffix(b".").unwrap_or(line);
+            self.fingerprint = Some(fingerprint.to_str_lossy().into_owned());
+        } else if line.contains_str("REMOTE HOST IDENTIFICATION HAS CHANGED") {
+            self.changed = true;
+        } else if line.ends_with_str("key sent by the remote host is") {
+            self.fingerprint_on_next_line = true;
+        } else if let Some(host) = line
+            .strip_prefix(b"The authenticity of host '")
+            .and_then(|rest| rest.find_byte(b'\'').map(|end| &rest[..end]))
+        {
+            let host = host.split_str(" (").next().unwrap_or(host);
+            self.host = Some(host.to_str_lossy().into_owned());
+        } else if let Some(fingerprint) = line
+            .find(" key fingerprint is ")
+            .map(|pos| &line[pos + " key fingerprint is ".len()..])
+        {
+            let fingerprint = fingerprint.strip_suffix(b".").unwrap_or(fingerprint);
+            self.fingerprint = Some(fingerprint.to_str_lossy().into_owned());
+        } else if let Some(host) = line.strip_prefix(b"Host key for ").and_then(|rest| {
+            rest.find(" has changed").map(|end| {
+                self.changed = true;
+                &rest[..end]
+            })
+        }) {
+            self.host = Some(host.to_str_lossy().into_owned());
+        } else if let Some(host) = line.find(" host key is known for ").and_then(|pos| {
+            let rest = &line[pos + " host key is known for ".len()..];
+            rest.find(" and you have requested strict checking")
+                .map(|end| &rest[..end])
+        }) {
+            self.host = Some(host.to_str_lossy().into_owned());
+        } else if line == b"Host key verification failed." {
+            let state = std::mem::take(self);
+            return Some(Error {
+                kind: if state.changed {
+                    Mismatch::Changed
+                } else {
+                    Mismatch::Unknown
+                },
+                host: state.host,
+                fingerprint: state.fingerprint,
+            });
+        }
+        None
+    }
+}
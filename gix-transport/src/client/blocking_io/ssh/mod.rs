@@ -35,6 +35,29 @@ pub enum ProgramKind {
 
 mod program_kind;
 
+///
+pub mod config;
+
+///
+pub mod host_key;
+
+/// Determine the kind of a `prompt` shown by the `ssh` program, along with the mode the terminal would be prompted with.
+///
+/// It's used to tell [prompt handlers](connect::Options::prompt) what is asked for.
+pub fn prompt_kind(prompt: &str) -> (gix_prompt::Kind, gix_prompt::Mode) {
+    use gix_prompt::{Kind, Mode};
+    let lowercase = prompt.to_lowercase();
+    if lowercase.contains("continue connecting") {
+        (Kind::HostKey, Mode::Visible)
+    } else if lowercase.contains("passphrase") {
+        (Kind::Passphrase, Mode::Hidden)
+    } else if lowercase.contains("password") {
+        (Kind::Password, Mode::Hidden)
+    } else {
+        (Kind::Other, Mode::Hidden)
+    }
+}
+
 ///
 pub mod invocation {
     use std::ffi::OsString;
@@ -59,12 +82,16 @@ pub mod invocation {
 
 ///
 pub mod connect {
-    use std::ffi::{OsStr, OsString};
+    use std::{
+        ffi::{OsStr, OsString},
+        path::PathBuf,
+        sync::Arc,
+    };
 
-    use crate::client::ssh::ProgramKind;
+    use crate::client::ssh::{host_key, ProgramKind};
 
     /// The options for use when [connecting][super::connect()] via the `ssh` protocol.
-    #[derive(Debug, Clone, Default)]
+    #[derive(Clone, Default)]
     pub struct Options {
         /// The program or script to use.
         /// If unset, it defaults to `ssh` or `ssh.exe`, or the program implied by `kind` if that one is set.
@@ -77,6 +104,39 @@ pub mod connect {
         /// when invoking the program.
         /// If unset, the `program` basename determines the variant, or an invocation of the `command` itself.
         pub kind: Option<ProgramKind>,
+        /// How to treat keys of unknown hosts or hosts whose key changed.
+        /// If unset, the configuration of the `ssh` program is used.
+        pub host_key_policy: Option<host_key::Policy>,
+        /// A function to call if a host is [unknown](host_key::Mismatch::Unknown) to decide whether it should be trusted.
+        /// If it is, the connection is attempted once more while adding the host key to the `known_hosts` file.
+        pub on_unknown_host: Option<Arc<host_key::Callback>>,
+        /// A function to answer all prompts of the `ssh` program, like those for the passphrase of keys, passwords or
+        /// for confirming keys of unknown hosts, instead of showing them in the terminal.
+        ///
+        /// It's only used for programs of kind [`Ssh`](ProgramKind::Ssh) on unix, which receive a program forwarding
+        /// their prompts to it as `SSH_ASKPASS`. The [kind](gix_prompt::Kind) of each prompt is determined
+        /// by [`prompt_kind()`](super::prompt_kind()).
+        pub prompt: Option<Arc<gix_prompt::Handler>>,
+        /// The ssh client configuration file to resolve the host of the URL with, typically `~/.ssh/config`.
+        /// If unset, no configuration file is read.
+        ///
+        /// It's only used for programs of a kind other than [`Ssh`](ProgramKind::Ssh), as `ssh` reads its
+        /// configuration by itself. See [`Host`](super::config::Host) for the supported settings.
+        pub config_file: Option<PathBuf>,
+    }
+
+    impl std::fmt::Debug for Options {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("Options")
+                .field("command", &self.command)
+                .field("disallow_shell", &self.disallow_shell)
+                .field("kind", &self.kind)
+                .field("host_key_policy", &self.host_key_policy)
+                .field("on_unknown_host", &self.on_unknown_host.as_ref().map(|_| "<fn>"))
+                .field("prompt", &self.prompt.as_ref().map(|_| "<fn>"))
+                .field("config_file", &self.config_file)
+                .finish()
+        }
     }
 
     impl Options {
@@ -132,13 +192,24 @@ pub fn connect(
         };
     }
 
+    let config = options
+        .config_file
+        .as_deref()
+        .filter(|_| kind != ProgramKind::Ssh)
+        .and_then(|path| config::Host::from_file(path, url.host()?).ok())
+        .filter(|config| !config.is_empty());
     let path = gix_url::expand_path::for_shell(url.path.clone());
+    let ssh_cmd = ssh_cmd.to_owned();
     Ok(blocking_io::file::SpawnProcessOnDemand::new_ssh(
         url,
         ssh_cmd,
         path,
         kind,
         options.disallow_shell,
+        options.host_key_policy,
+        options.on_unknown_host,
+        options.prompt,
+        config,
         desired_version,
         trace,
     ))
@@ -22,13 +22,21 @@ impl ProgramKind {
     }
 
     /// Prepare all information needed to invoke the ssh command
+    ///
+    /// If set, `config` provides the host name, user and port if these aren't set in `url`, along with identity files and
+    /// jump hosts. It's ignored for [`Ssh`](ProgramKind::Ssh), which reads its configuration by itself.
     pub(crate) fn prepare_invocation(
         &self,
         ssh_cmd: &OsStr,
         url: &gix_url::Url,
         desired_version: Protocol,
         disallow_shell: bool,
+        host_key_policy: Option<ssh::host_key::Policy>,
+        config: Option<&ssh::config::Host>,
     ) -> Result<gix_command::Prepare, ssh::invocation::Error> {
+        let config = config.filter(|_| *self != ProgramKind::Ssh);
+        let port = url.port.or_else(|| config.and_then(|config| config.port));
+        let proxy_jump = config.and_then(|config| config.proxy_jump.as_deref());
         let mut prepare = gix_command::prepare(ssh_cmd).with_shell();
         if disallow_shell {
             prepare.use_shell = false;
@@ -40,7 +48,19 @@ impl ProgramKind {
                         .args(["-o", "SendEnv=GIT_PROTOCOL"])
                         .env("GIT_PROTOCOL", format!("version={}", desired_version as usize));
                 }
-                if let Some(port) = url.port {
+                if let Some(policy) = host_key_policy {
+                    prepare = prepare
+                        .arg("-o")
+                        .arg(format!("StrictHostKeyChecking={}", policy.as_ssh_option_value()));
+                }
+                if host_key_policy == Some(ssh::host_key::Policy::AcceptOnce) {
+                    // New keys are written to the first file only, which discards them.
+                    let null_device = if cfg!(windows) { "NUL" } else { "/dev/null" };
+                    prepare = prepare.arg("-o").arg(format!(
+                        "UserKnownHostsFile={null_device} ~/.ssh/known_hosts ~/.ssh/known_hosts2"
+                    ));
+                }
+                if let Some(port) = port {
                     prepare = prepare.arg(format!("-p{port}"));
                 }
             }
@@ -48,22 +68,42 @@ impl ProgramKind {
                 if *self == ProgramKind::TortoisePlink {
                     prepare = prepare.arg("-batch");
                 }
-                if let Some(port) = url.port {
+                if let Some(port) = port {
                     prepare = prepare.arg("-P");
                     prepare = prepare.arg(port.to_string());
                 }
+                if let Some(identity_file) = config.and_then(|config| config.identity_files.first()) {
+                    prepare = prepare.arg("-i").arg(identity_file);
+                }
+                if let Some(jump) = proxy_jump {
+                    prepare = prepare.arg("-proxycmd").arg(plink_proxy_command(ssh_cmd, jump)?);
+                }
             }
             ProgramKind::Simple => {
-                if url.port.is_some() {
+                if port.is_some() {
                     return Err(ssh::invocation::Error::Unsupported {
                         command: ssh_cmd.into(),
                         function: "setting the port",
                     });
                 }
+                if proxy_jump.is_some() {
+                    return Err(ssh::invocation::Error::Unsupported {
+                        command: ssh_cmd.into(),
+                        function: "connecting through a jump host",
+                    });
+                }
             }
         };
 
-        let host_maybe_with_user_as_ssh_arg = match (url.user_as_argument(), url.host_as_argument()) {
+        let user = match (url.user_as_argument(), config.and_then(|config| config.user.as_deref())) {
+            (Absent, Some(user)) => as_argument(user),
+            (user, _) => user,
+        };
+        let host = match config.and_then(|config| config.host_name.as_deref()) {
+            Some(host) => as_argument(host),
+            None => url.host_as_argument(),
+        };
+        let host_maybe_with_user_as_ssh_arg = match (user, host) {
             (Usable(user), Usable(host)) => format!("{user}@{host}"),
             (Usable(user), Dangerous(host)) => format!("{user}@{host}"), // The `user@` makes it safe.
             (Absent, Usable(host)) => host.into(),
@@ -113,6 +153,60 @@ impl ProgramKind {
     }
 }
 
+fn as_argument(value: &str) -> gix_url::ArgumentSafety<'_> {
+    if value.starts_with('-') {
+        Dangerous(value)
+    } else {
+        Usable(value)
+    }
+}
+
+/// Produce a proxy command for `plink`-like programs which makes `ssh_cmd` forward the connection through `jump`,
+/// which is of the form `[user@]host[:port]`.
+fn plink_proxy_command(ssh_cmd: &OsStr, jump: &str) -> Result<String, ssh::invocation::Error> {
+    if jump.contains(',') {
+        return Err(ssh::invocation::Error::Unsupported {
+            command: ssh_cmd.into(),
+            function: "connecting through more than one jump host",
+        });
+    }
+    let (user, host_and_port) = match jump.rsplit_once('@') {
+        Some((user, host_and_port)) => (Some(user), host_and_port),
+        None => (None, jump),
+    };
+    let (host, port) = match host_and_port
+        .rsplit_once(':')
+        .and_then(|(host, port)| Some((host, port.parse::<u16>().ok()?)))
+    {
+        Some((host, port)) => (host, Some(port)),
+        None => (host_and_port, None),
+    };
+    if let Dangerous(user) = user.map_or(Absent, as_argument) {
+        return Err(ssh::invocation::Error::AmbiguousUserName { user: user.into() });
+    }
+    if let Dangerous(host) = as_argument(host) {
+        return Err(ssh::invocation::Error::AmbiguousHostName { host: host.into() });
+    }
+
+    let ssh_cmd = ssh_cmd.to_string_lossy();
+    let mut command = if ssh_cmd.contains(' ') {
+        format!("\"{ssh_cmd}\" -batch")
+    } else {
+        format!("{ssh_cmd} -batch")
+    };
+    if let Some(port) = port {
+        command.push_str(" -P ");
+        command.push_str(&port.to_string());
+    }
+    command.push_str(" -nc %host:%port ");
+    if let Some(user) = user {
+        command.push_str(user);
+        command.push('@');
+    }
+    command.push_str(host);
+    Ok(command)
+}
+
 impl<'a> From<&'a OsStr> for ProgramKind {
     fn from(v: &'a OsStr) -> Self {
         let p = std::path::Path::new(v);
@@ -233,13 +233,25 @@ mod program_kind {
             let url = gix_url::parse("ssh://host/path".into()).expect("valid url");
 
             let disallow_shell = false;
-            let prepare =
-                ProgramKind::Ssh.prepare_invocation(OsStr::new("echo hi"), &url, Protocol::V1, disallow_shell)?;
+            let prepare = ProgramKind::Ssh.prepare_invocation(
+                OsStr::new("echo hi"),
+                &url,
+                Protocol::V1,
+                disallow_shell,
+                None,
+                None,
+            )?;
             assert!(prepare.use_shell, "shells are used when needed");
 
             let disallow_shell = true;
-            let prepare =
-                ProgramKind::Ssh.prepare_invocation(OsStr::new("echo hi"), &url, Protocol::V1, disallow_shell)?;
+            let prepare = ProgramKind::Ssh.prepare_invocation(
+                OsStr::new("echo hi"),
+                &url,
+                Protocol::V1,
+                disallow_shell,
+                None,
+                None,
+            )?;
             assert!(
                 !prepare.use_shell,
                 "but we can enforce it not to be used as well for historical reasons"
@@ -247,6 +259,160 @@ mod program_kind {
             Ok(())
         }
 
+        #[test]
+        fn host_key_policy_is_passed_to_ssh_only() -> Result {
+            let url = gix_url::parse("ssh://host/path".into()).expect("valid url");
+            for (policy, expected) in [
+                (ssh::host_key::Policy::Strict, "StrictHostKeyChecking=yes"),
+                (ssh::host_key::Policy::AcceptNew, "StrictHostKeyChecking=accept-new"),
+                (ssh::host_key::Policy::Ask, "StrictHostKeyChecking=ask"),
+            ] {
+                let prepare = ProgramKind::Ssh.prepare_invocation(
+                    OsStr::new("ssh"),
+                    &url,
+                    Protocol::V1,
+                    false,
+                    Some(policy),
+                    None,
+                )?;
+                assert_eq!(prepare.args, ["-o", expected, "host"]);
+
+                let prepare = ProgramKind::Plink.prepare_invocation(
+                    OsStr::new("plink"),
+                    &url,
+                    Protocol::V1,
+                    false,
+                    Some(policy),
+                    None,
+                )?;
+                assert_eq!(prepare.args, ["host"], "other programs don't know this option");
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn host_key_policy_accept_once_discards_new_keys() -> Result {
+            let url = gix_url::parse("ssh://host/path".into()).expect("valid url");
+            let prepare = ProgramKind::Ssh.prepare_invocation(
+                OsStr::new("ssh"),
+                &url,
+                Protocol::V1,
+                false,
+                Some(ssh::host_key::Policy::AcceptOnce),
+                None,
+            )?;
+            let null_device = if cfg!(windows) { "NUL" } else { "/dev/null" };
+            let known_hosts_files = format!("UserKnownHostsFile={null_device} ~/.ssh/known_hosts ~/.ssh/known_hosts2");
+            assert_eq!(
+                prepare.args,
+                [
+                    "-o",
+                    "StrictHostKeyChecking=accept-new",
+                    "-o",
+                    known_hosts_files.as_str(),
+                    "host"
+                ]
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn config_applies_to_programs_other_than_ssh() -> Result {
+            let config = ssh::config::Host {
+                host_name: Some("real.example.com".into()),
+                user: Some("config-user".into()),
+                port: Some(2222),
+                identity_files: vec!["/home/user/.ssh/id_work".into(), "/home/user/.ssh/id_other".into()],
+                proxy_jump: Some("jumper@bastion:2200".into()),
+            };
+            let url = gix_url::parse("ssh://alias/path".into()).expect("valid url");
+            let prepare = ProgramKind::Ssh.prepare_invocation(
+                OsStr::new("ssh"),
+                &url,
+                Protocol::V1,
+                false,
+                None,
+                Some(&config),
+            )?;
+            assert_eq!(prepare.args, ["alias"], "ssh reads its configuration by itself");
+
+            let prepare = ProgramKind::Plink.prepare_invocation(
+                OsStr::new("plink"),
+                &url,
+                Protocol::V1,
+                false,
+                None,
+                Some(&config),
+            )?;
+            assert_eq!(
+                prepare.args,
+                [
+                    "-P",
+                    "2222",
+                    "-i",
+                    "/home/user/.ssh/id_work",
+                    "-proxycmd",
+                    "plink -batch -P 2200 -nc %host:%port jumper@bastion",
+                    "config-user@real.example.com"
+                ]
+            );
+
+            let url = gix_url::parse("ssh://url-user@alias:22/path".into()).expect("valid url");
+            let prepare = ProgramKind::Putty.prepare_invocation(
+                OsStr::new("putty"),
+                &url,
+                Protocol::V1,
+                false,
+                None,
+                Some(&ssh::config::Host {
+                    proxy_jump: None,
+                    ..config.clone()
+                }),
+            )?;
+            assert_eq!(
+                prepare.args,
+                ["-P", "22", "-i", "/home/user/.ssh/id_work", "url-user@real.example.com"],
+                "the user and port of the URL take precedence"
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn config_with_unsupported_settings_fails_for_simple() {
+            let url = gix_url::parse("ssh://alias/path".into()).expect("valid url");
+            let config = ssh::config::Host {
+                proxy_jump: Some("bastion".into()),
+                ..Default::default()
+            };
+            assert!(matches!(
+                ProgramKind::Simple.prepare_invocation(
+                    OsStr::new("simple"),
+                    &url,
+                    Protocol::V1,
+                    false,
+                    None,
+                    Some(&config)
+                ),
+                Err(ssh::invocation::Error::Unsupported {
+                    function: "connecting through a jump host",
+                    ..
+                })
+            ));
+        }
+
+        #[test]
+        fn config_with_ambiguous_host_name_is_rejected() {
+            let url = gix_url::parse("ssh://alias/path".into()).expect("valid url");
+            let config = ssh::config::Host {
+                host_name: Some("-oProxyCommand=open$IFS-aCalculator".into()),
+                ..Default::default()
+            };
+            assert!(matches!(
+                ProgramKind::Plink.prepare_invocation(OsStr::new("plink"), &url, Protocol::V1, false, None, Some(&config)),
+                Err(ssh::invocation::Error::AmbiguousHostName { host }) if host == "-oProxyCommand=open$IFS-aCalculator"
+            ));
+        }
+
         fn joined(input: &[&str]) -> String {
             input.to_vec().join(" ")
         }
@@ -257,7 +423,7 @@ mod program_kind {
         ) -> std::result::Result<gix_command::Prepare, ssh::invocation::Error> {
             let ssh_cmd = kind.exe().unwrap_or_else(|| OsStr::new("simple"));
             let url = gix_url::parse(url.into()).expect("valid url");
-            kind.prepare_invocation(ssh_cmd, &url, version, false)
+            kind.prepare_invocation(ssh_cmd, &url, version, false, None, None)
         }
         fn call(kind: ProgramKind, url: &str, version: Protocol) -> gix_command::Prepare {
             try_call(kind, url, version).expect("no error")
@@ -346,4 +512,260 @@ mod program_kind {
             }
         }
     }
+
+    mod host_key {
+        use crate::client::ssh::host_key::{Error, Mismatch, Parser};
+
+        fn parse(lines: &[&str]) -> Vec<Error> {
+            let mut parser = Parser::default();
+            lines
+                .iter()
+                .filter_map(|line| parser.parse_line((*line).into()))
+                .collect()
+        }
+
+        #[test]
+        fn unknown_host_with_fingerprint() {
+            assert_eq!(
+                parse(&[
+                    "The authenticity of host 'example.com (192.0.2.1)' can't be established.",
+                    "ED25519 key fingerprint is SHA256:+DiY3wvvV6TuJJhbpZisF/zLDA0zPMSvHdkr4UvCOqU.",
+                    "No ED25519 host key is known for example.com and you have requested strict checking.",
+                    "Host key verification failed.",
+                ]),
+                [Error {
+                    kind: Mismatch::Unknown,
+                    host: Some("example.com".into()),
+                    fingerprint: Some("SHA256:+DiY3wvvV6TuJJhbpZisF/zLDA0zPMSvHdkr4UvCOqU".into()),
+                }]
+            );
+        }
+
+        #[test]
+        fn changed_host_key() {
+            assert_eq!(
+                parse(&[
+                    "@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@",
+                    "@    WARNING: REMOTE HOST IDENTIFICATION HAS CHANGED!     @",
+                    "@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@",
+                    "The fingerprint for the ED25519 key sent by the remote host is",
+                    "SHA256:+DiY3wvvV6TuJJhbpZisF/zLDA0zPMSvHdkr4UvCOqU.",
+                    "Host key for example.com has changed and you have requested strict checking.",
+                    "Host key verification failed.",
+                ]),
+                [Error {
+                    kind: Mismatch::Changed,
+                    host: Some("example.com".into()),
+                    fingerprint: Some("SHA256:+DiY3wvvV6TuJJhbpZisF/zLDA0zPMSvHdkr4UvCOqU".into()),
+                }]
+            );
+        }
+
+        #[test]
+        fn unrelated_lines_are_ignored() {
+            assert_eq!(parse(&["Permission denied (publickey).", "something else"]), []);
+        }
+
+        #[test]
+        fn io_errors_carry_the_host_key_error() {
+            let err = Error {
+                kind: Mismatch::Unknown,
+                host: None,
+                fingerprint: None,
+            };
+            let io_err = std::io::Error::new(std::io::ErrorKind::PermissionDenied, err.clone());
+            assert_eq!(Error::from_io_error(&io_err), Some(&err));
+        }
+    }
+}
+
+mod config {
+    use std::path::Path;
+
+    use crate::client::ssh::config::Host;
+
+    const CONFIG: &str = r#"
+# A comment
+Host work
+    HostName %h.example.com
+    User alice
+    Port 2222
+    IdentityFile ~/.ssh/id_work
+    ProxyJump bob@bastion:2200
+
+Host *.example.com !secret.example.com
+    User=bob
+    IdentityFile "/keys/%h %r"
+
+Match exec "false"
+    User never
+
+Host *
+    User fallback
+    Port 22
+    IdentityFile %d/.ssh/id_default
+    ProxyJump none
+"#;
+
+    fn resolve(host: &str) -> Host {
+        Host::from_config(CONFIG, host, Some(Path::new("/home/alice")))
+    }
+
+    #[test]
+    fn first_value_wins_and_identity_files_accumulate() {
+        assert_eq!(
+            resolve("work"),
+            Host {
+                host_name: Some("work.example.com".into()),
+                user: Some("alice".into()),
+                port: Some(2222),
+                identity_files: vec!["/home/alice/.ssh/id_work".into(), "/home/alice/.ssh/id_default".into()],
+                proxy_jump: Some("bob@bastion:2200".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn wildcards_negation_and_quoted_values() {
+        assert_eq!(
+            resolve("git.EXAMPLE.com"),
+            Host {
+                host_name: None,
+                user: Some("bob".into()),
+                port: Some(22),
+                identity_files: vec!["/keys/git.EXAMPLE.com bob".into(), "/home/alice/.ssh/id_default".into()],
+                proxy_jump: None,
+            }
+        );
+        assert_eq!(
+            resolve("secret.example.com").user.as_deref(),
+            Some("fallback"),
+            "negated patterns prevent the section from matching, and `Match` sections are ignored"
+        );
+    }
+
+    #[test]
+    fn empty_without_matching_sections() {
+        assert!(Host::from_config("Host other\n  User alice", "host", None).is_empty());
+    }
+}
+
+mod known_hosts {
+    use crate::client::ssh::host_key::known_hosts::{parse, Entry, Marker};
+
+    const KNOWN_HOSTS: &str = r#"
+# A comment
+example.com,192.168.0.1 ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIExample
+[example.com]:2222 ssh-rsa AAAAB3NzaC1yc2EAAAADAQABAAABAQExample user@host with spaces
+@cert-authority *.example.org,!secret.example.org ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAICa
+|1|F1E1KeoE/eEWhi10WpGv4OdiO6Y=|3988QV0VE8wmZL7suNrYQLITLCg= ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHashed
+incomplete-line ssh-ed25519
+"#;
+
+    #[test]
+    fn entries_with_markers_and_comments() {
+        let entries = parse(KNOWN_HOSTS);
+        assert_eq!(entries.len(), 4, "comments and incomplete lines are skipped");
+        assert_eq!(
+            entries[1],
+            Entry {
+                marker: None,
+                hosts: "[example.com]:2222".into(),
+                key_type: "ssh-rsa".into(),
+                key: "AAAAB3NzaC1yc2EAAAADAQABAAABAQExample".into(),
+                comment: Some("user@host with spaces".into()),
+            }
+        );
+        assert_eq!(entries[2].marker, Some(Marker::CertAuthority));
+        assert!(entries[3].is_hashed());
+    }
+
+    #[test]
+    fn matching_hosts_and_ports() {
+        let entries = parse(KNOWN_HOSTS);
+        assert!(entries[0].matches("example.com", None));
+        assert!(entries[0].matches("192.168.0.1", Some(22)), "22 is the default port");
+        assert!(!entries[0].matches("example.com", Some(2222)));
+        assert!(entries[1].matches("example.com", Some(2222)));
+        assert!(entries[2].matches("git.example.org", None));
+        assert!(!entries[2].matches("secret.example.org", None), "negations are honored");
+        assert!(
+            !entries[3].matches("example.com", None),
+            "hashed entries can't be matched"
+        );
+    }
+}
+
+mod prompt {
+    use std::sync::{Arc, Mutex};
+
+    use gix_prompt::{Kind, Mode};
+
+    use crate::{client::ssh, Protocol, Service};
+
+    #[test]
+    fn prompt_kind() {
+        for (prompt, expected) in [
+            ("Enter passphrase for key '/home/user/.ssh/id_ed25519': ", (Kind::Passphrase, Mode::Hidden)),
+            ("user@example.com's password: ", (Kind::Password, Mode::Hidden)),
+            (
+                "The authenticity of host 'example.com (127.0.0.1)' can't be established.\nED25519 key fingerprint is SHA256:abc.\nAre you sure you want to continue connecting (yes/no/[fingerprint])? ",
+                (Kind::HostKey, Mode::Visible),
+            ),
+            ("Confirm user presence for key ED25519-SK", (Kind::Other, Mode::Hidden)),
+        ] {
+            assert_eq!(ssh::prompt_kind(prompt), expected, "{prompt}");
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn prompts_of_ssh_are_forwarded_to_the_handler() -> Result<(), Box<dyn std::error::Error>> {
+        use std::os::unix::fs::OpenOptionsExt;
+        let dir = std::env::temp_dir().join(format!("gix-transport-ssh-prompt-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let fake_ssh = dir.join("ssh");
+        std::io::Write::write_all(
+            &mut std::fs::OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .mode(0o700)
+                .open(&fake_ssh)?,
+            br#"#!/bin/sh
+test "$SSH_ASKPASS_REQUIRE" = force || exit 1
+answer=$("$SSH_ASKPASS" "Enter passphrase for key 'id': ") || exit 1
+test "$answer" = secret || { echo "fatal: wrong passphrase" >&2; exit 1; }
+printf '0000'
+"#,
+        )?;
+
+        let prompts = Arc::new(Mutex::new(Vec::new()));
+        let mut transport = ssh::connect(
+            gix_url::parse("ssh://host/repo".into()).expect("valid"),
+            Protocol::V1,
+            ssh::connect::Options {
+                command: Some(fake_ssh.into()),
+                kind: Some(ssh::ProgramKind::Ssh),
+                prompt: Some(Arc::new({
+                    let prompts = prompts.clone();
+                    move |kind, prompt: &str, mode| {
+                        prompts.lock().unwrap().push((kind, prompt.to_owned(), mode));
+                        Some("secret".into())
+                    }
+                })),
+                ..Default::default()
+            },
+            false,
+        )
+        .expect("valid url");
+        let res = crate::client::Transport::handshake(&mut transport, Service::UploadPack, &[]);
+        std::fs::remove_dir_all(&dir).ok();
+        res?;
+        assert_eq!(
+            *prompts.lock().unwrap(),
+            [(Kind::Passphrase, "Enter passphrase for key 'id': ".into(), Mode::Hidden)]
+        );
+        Ok(())
+    }
 }
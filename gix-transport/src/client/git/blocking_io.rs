@@ -146,12 +146,15 @@ pub mod connect {
         Io(#[from] std::io::Error),
         #[error("Could not parse {host:?} as virtual host with format <host>[:port]")]
         VirtualHostInvalid { host: String },
+        #[error("Could not connect through the SOCKS5 proxy")]
+        Socks5(#[from] crate::client::socks5::Error),
     }
 
     impl crate::IsSpuriousError for Error {
         fn is_spurious(&self) -> bool {
             match self {
                 Error::Io(err) => err.is_spurious(),
+                Error::Socks5(err) => err.is_spurious(),
                 _ => false,
             }
         }
@@ -172,21 +175,27 @@ pub mod connect {
     /// Connect to a git daemon running on `host` and optionally `port` and a repository at `path`.
     ///
     /// Use `desired_version` to specify a preferred protocol to use, knowing that it can be downgraded by a server not supporting it.
+    /// If `proxy` is set, it's the URL of a [SOCKS5 proxy](crate::client::socks5::connect()) to connect through.
     /// If `trace` is `true`, all packetlines received or sent will be passed to the facilities of the `gix-trace` crate.
     pub fn connect(
         host: &str,
         path: BString,
         desired_version: crate::Protocol,
         port: Option<u16>,
+        proxy: Option<&str>,
         trace: bool,
     ) -> Result<git::Connection<TcpStream, TcpStream>, Error> {
-        let read = TcpStream::connect_timeout(
-            &(host, port.unwrap_or(9418))
-                .to_socket_addrs()?
-                .next()
-                .expect("after successful resolution there is an IP address"),
-            std::time::Duration::from_secs(5),
-        )?;
+        let timeout = std::time::Duration::from_secs(5);
+        let read = match proxy {
+            Some(proxy) => crate::client::socks5::connect(proxy, host, port.unwrap_or(9418), timeout)?,
+            None => TcpStream::connect_timeout(
+                &(host, port.unwrap_or(9418))
+                    .to_socket_addrs()?
+                    .next()
+                    .expect("after successful resolution there is an IP address"),
+                timeout,
+            )?,
+        };
         let write = read.try_clone()?;
         let vhost = std::env::var("GIT_OVERRIDE_VIRTUAL_HOST")
             .ok()
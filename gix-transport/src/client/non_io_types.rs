@@ -40,6 +40,11 @@ pub(crate) mod connect {
         #[cfg(feature = "blocking-client")]
         /// Options to use if the scheme of the URL is `ssh`.
         pub ssh: crate::client::ssh::connect::Options,
+        #[cfg(feature = "blocking-client")]
+        /// The URL of a SOCKS5 proxy like `socks5h://localhost:9050` to connect through if the scheme of the URL is `git`.
+        ///
+        /// Proxies for `http` and `https` URLs are configured with the `proxy` field of the http transport options instead.
+        pub proxy: Option<String>,
         /// If `true`, all packetlines received or sent will be passed to the facilities of the `gix-trace` crate.
         pub trace: bool,
     }
@@ -103,10 +108,14 @@ mod error {
     type HttpError = http::Error;
     #[cfg(feature = "blocking-client")]
     type SshInvocationError = ssh::invocation::Error;
+    #[cfg(feature = "blocking-client")]
+    type RemoteHelperError = crate::client::remote_helper::Error;
     #[cfg(not(feature = "http-client"))]
     type HttpError = std::convert::Infallible;
     #[cfg(not(feature = "blocking-client"))]
     type SshInvocationError = std::convert::Infallible;
+    #[cfg(not(feature = "blocking-client"))]
+    type RemoteHelperError = std::convert::Infallible;
 
     /// The error used in most methods of the [`client`][crate::client] module
     #[derive(thiserror::Error, Debug)]
@@ -140,6 +149,8 @@ mod error {
         Http(#[from] HttpError),
         #[error(transparent)]
         SshInvocation(SshInvocationError),
+        #[error(transparent)]
+        RemoteHelper(RemoteHelperError),
         #[error("The repository path '{path}' could be mistaken for a command-line argument")]
         AmbiguousPath { path: BString },
     }
@@ -47,6 +47,58 @@ fn http_status_500_is_communicated_via_special_io_error() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn http_status_429_is_communicated_via_special_io_error() -> crate::Result {
+    assert_error_status(429, std::io::ErrorKind::ConnectionAborted)?;
+    Ok(())
+}
+
+#[test]
+fn handshake_is_retried_after_spurious_errors_if_configured() -> crate::Result {
+    let (server, mut client) = mock::serve_and_connect("http-500.response", "path/not-important", Protocol::V1)?;
+    client
+        .configure(&http::Options {
+            retries: 1,
+            retry_delay: Some(std::time::Duration::from_millis(1)),
+            ..Default::default()
+        })
+        .expect("http options are supported");
+    let server = std::thread::spawn(move || {
+        let first_request = server.received_as_string();
+        server.next_read_and_respond_with(fixture_bytes("v1/http-handshake.response"));
+        (first_request, server.received_as_string())
+    });
+
+    let SetServiceResponse { actual_protocol, .. } = client.handshake(Service::UploadPack, &[])?;
+    assert_eq!(actual_protocol, Protocol::V1, "the second attempt succeeded");
+    let (first_request, second_request) = server.join().expect("no panic");
+    assert_eq!(
+        first_request.lines().next(),
+        second_request.lines().next(),
+        "the same request was sent again"
+    );
+    Ok(())
+}
+
+#[test]
+fn handshake_is_not_retried_after_permanent_errors() -> crate::Result {
+    let (server, mut client) = mock::serve_and_connect("http-404.response", "path/not-important", Protocol::V1)?;
+    client
+        .configure(&http::Options {
+            retries: 1,
+            retry_delay: Some(std::time::Duration::from_millis(1)),
+            ..Default::default()
+        })
+        .expect("http options are supported");
+    let error = client
+        .handshake(Service::UploadPack, &[])
+        .err()
+        .expect("404 fails the handshake without retrying");
+    assert!(!gix_transport::IsSpuriousError::is_spurious(&error));
+    drop(server.received());
+    Ok(())
+}
+
 #[test]
 fn http_identity_is_picked_up_from_url() -> crate::Result {
     let transport =
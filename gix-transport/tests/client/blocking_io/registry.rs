@@ -0,0 +1,41 @@
+use std::sync::{Arc, Mutex};
+
+use gix_transport::{
+    client::{connect, file, registry},
+    Protocol,
+};
+
+#[test]
+fn registered_factories_take_precedence_for_their_scheme() -> crate::Result {
+    let received = Arc::new(Mutex::new(None));
+    let previous = registry::register("custom-test", {
+        let received = received.clone();
+        move |url: gix_url::Url, options: &connect::Options| {
+            *received.lock().unwrap() = Some((url.to_bstring(), options.version));
+            Ok(Box::new(file::connect("/path/to/repo", options.version, options.trace)?) as _)
+        }
+    });
+    assert!(previous.is_none(), "nothing was registered for this scheme yet");
+    assert!(registry::lookup(&gix_url::Scheme::Ext("CUSTOM-test".into())).is_some());
+
+    let transport = gix_transport::connect(
+        "custom-test://host/repo",
+        connect::Options {
+            version: Protocol::V1,
+            ..Default::default()
+        },
+    )?;
+    assert!(
+        transport.to_url().ends_with(b"/path/to/repo"),
+        "the factory created the transport"
+    );
+    assert_eq!(
+        received.lock().unwrap().take(),
+        Some(("custom-test://host/repo".into(), Protocol::V1)),
+        "the factory receives the url and options"
+    );
+
+    assert!(registry::unregister("custom-test").is_some());
+    assert!(registry::lookup(&gix_url::Scheme::Ext("custom-test".into())).is_none());
+    Ok(())
+}
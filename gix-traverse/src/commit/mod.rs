@@ -1,6 +1,7 @@
 //! Provide multiple traversal implementations with different performance envelopes.
 //!
 //! Use [`Simple`] for fast walks that maintain minimal state, or [`Topo`] for a more elaborate traversal.
+//! [`Prioritized`] allows to control the order of the traversal entirely.
 use gix_hash::ObjectId;
 use gix_object::FindExt;
 use gix_revwalk::graph::IdMap;
@@ -40,6 +41,24 @@ pub struct Topo<Find, Predicate> {
 
 pub mod topo;
 
+/// An iterator over the ancestors of one or more starting commits, in an order determined by a caller-provided priority.
+///
+/// Instantiate with [`Prioritized::new()`].
+pub struct Prioritized<Find, Priority, Key: Ord> {
+    objects: Find,
+    cache: Option<gix_commitgraph::Graph>,
+    priority: Priority,
+    queue: PriorityQueue<Key, (ObjectId, gix_date::SecondsSinceUnixEpoch)>,
+    seen: gix_hashtable::HashSet<ObjectId>,
+    parents: Parents,
+    buf: Vec<u8>,
+    parents_buf: Vec<u8>,
+    parent_ids: SmallVec<[(ObjectId, gix_date::SecondsSinceUnixEpoch); 2]>,
+}
+
+/// A traversal whose order is determined by a pluggable priority.
+pub mod prioritized;
+
 /// Specify how to handle commit parents during traversal.
 #[derive(Default, Copy, Clone)]
 pub enum Parents {
@@ -0,0 +1,157 @@
+use gix_date::SecondsSinceUnixEpoch;
+use gix_hash::{oid, ObjectId};
+use gix_object::FindExt;
+
+use super::{simple::collect_parents, Either, Info, ParentIds, Parents, Prioritized};
+
+pub use super::simple::Error;
+
+/// A commit that was discovered during the traversal and is about to be queued, passed to the priority function
+/// to determine its position in the queue.
+#[derive(Debug, Copy, Clone)]
+pub struct Candidate<'a> {
+    /// The id of the commit.
+    pub id: &'a oid,
+    /// The time at which the commit was created, in seconds since the unix epoch.
+    pub commit_time: SecondsSinceUnixEpoch,
+}
+
+/// Lifecycle
+impl<Find, Priority, Key> Prioritized<Find, Priority, Key>
+where
+    Find: gix_object::Find,
+    Priority: FnMut(&Candidate<'_>) -> Option<Key>,
+    Key: Ord,
+{
+    /// Create a new instance.
+    ///
+    /// * `tips`
+    ///   * the starting points of the iteration, usually commits
+    ///   * each commit they lead to will only be returned once, including the tip that started it
+    /// * `find` - a way to lookup new object data during traversal by their `ObjectId`.
+    /// * `priority` - return the key of each commit to order the traversal by, with the commit of the greatest key being
+    ///   returned next, or `None` to neither return the commit nor traverse its parents.
+    ///
+    /// Note that all keys are compared with each other no matter when they were produced, so
+    /// `|c| Some(c.commit_time)` is similar to [`Sorting::ByCommitTime`](super::simple::Sorting::ByCommitTime).
+    pub fn new(
+        tips: impl IntoIterator<Item = impl Into<ObjectId>>,
+        find: Find,
+        mut priority: Priority,
+    ) -> Result<Self, Error> {
+        let mut buf = Vec::new();
+        let mut seen = gix_hashtable::HashSet::default();
+        let mut queue = gix_revwalk::PriorityQueue::new();
+        for tip in tips.into_iter().map(Into::into) {
+            if !seen.insert(tip) {
+                continue;
+            }
+            let commit_time = find.find_commit_iter(&tip, &mut buf)?.committer()?.time.seconds;
+            if let Some(key) = priority(&Candidate { id: &tip, commit_time }) {
+                queue.insert(key, (tip, commit_time));
+            }
+        }
+        Ok(Prioritized {
+            objects: find,
+            cache: None,
+            priority,
+            queue,
+            seen,
+            parents: Default::default(),
+            buf,
+            parents_buf: Vec::new(),
+            parent_ids: Default::default(),
+        })
+    }
+}
+
+/// Builder
+impl<Find, Priority, Key: Ord> Prioritized<Find, Priority, Key> {
+    /// Change our commit parent handling mode to the given one.
+    pub fn parents(mut self, mode: Parents) -> Self {
+        self.parents = mode;
+        self
+    }
+
+    /// Set the commitgraph as `cache` to greatly accelerate any traversal.
+    ///
+    /// The cache will be used if possible, but we will fall-back without error to using the object
+    /// database for commit lookup. If the cache is corrupt, we will fall back to the object database as well.
+    pub fn commit_graph(mut self, cache: Option<gix_commitgraph::Graph>) -> Self {
+        self.cache = cache;
+        self
+    }
+}
+
+impl<Find, Priority, Key> Iterator for Prioritized<Find, Priority, Key>
+where
+    Find: gix_object::Find,
+    Priority: FnMut(&Candidate<'_>) -> Option<Key>,
+    Key: Ord,
+{
+    type Item = Result<Info, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, (id, commit_time)) = self.queue.pop()?;
+        let mut parents: ParentIds = Default::default();
+        match super::find(self.cache.as_ref(), &self.objects, &id, &mut self.buf) {
+            Ok(Either::CachedCommit(commit)) => {
+                if !collect_parents(&mut self.parent_ids, self.cache.as_ref(), commit.iter_parents()) {
+                    // drop corrupt caches and try again with ODB
+                    self.cache = None;
+                    self.queue.insert(key, (id, commit_time));
+                    return self.next();
+                }
+                for (parent_id, parent_commit_time) in self.parent_ids.drain(..) {
+                    parents.push(parent_id);
+                    if self.seen.insert(parent_id) {
+                        if let Some(key) = (self.priority)(&Candidate {
+                            id: &parent_id,
+                            commit_time: parent_commit_time,
+                        }) {
+                            self.queue.insert(key, (parent_id, parent_commit_time));
+                        }
+                    }
+                    if matches!(self.parents, Parents::First) {
+                        break;
+                    }
+                }
+            }
+            Ok(Either::CommitRefIter(commit_iter)) => {
+                for token in commit_iter {
+                    match token {
+                        Ok(gix_object::commit::ref_iter::Token::Tree { .. }) => {}
+                        Ok(gix_object::commit::ref_iter::Token::Parent { id: parent_id }) => {
+                            parents.push(parent_id);
+                            if self.seen.insert(parent_id) {
+                                let parent_commit_time = self
+                                    .objects
+                                    .find_commit_iter(&parent_id, &mut self.parents_buf)
+                                    .ok()
+                                    .and_then(|parent| parent.committer().ok().map(|committer| committer.time.seconds))
+                                    .unwrap_or_default();
+                                if let Some(key) = (self.priority)(&Candidate {
+                                    id: &parent_id,
+                                    commit_time: parent_commit_time,
+                                }) {
+                                    self.queue.insert(key, (parent_id, parent_commit_time));
+                                }
+                            }
+                            if matches!(self.parents, Parents::First) {
+                                break;
+                            }
+                        }
+                        Ok(_a_token_past_the_parents) => break,
+                        Err(err) => return Some(Err(err.into())),
+                    }
+                }
+            }
+            Err(err) => return Some(Err(err.into())),
+        }
+        Some(Ok(Info {
+            id,
+            parent_ids: parents,
+            commit_time: Some(commit_time),
+        }))
+    }
+}
@@ -67,3 +67,7 @@ pub mod recorder;
 ///
 pub mod breadthfirst;
 pub use breadthfirst::impl_::traverse as breadthfirst;
+
+/// Traverse trees using multiple threads.
+pub mod parallel;
+pub use parallel::impl_::traverse as parallel;
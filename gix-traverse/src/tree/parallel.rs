@@ -0,0 +1,202 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+use gix_hash::ObjectId;
+use gix_object::bstr::{BStr, BString};
+
+pub use super::breadthfirst::Error;
+use super::visit::Action;
+
+/// A trait to observe all entries of a tree recursively from multiple threads at once.
+///
+/// Unlike [`tree::Visit`](super::Visit), the full path of each entry is passed along as path tracking
+/// can't be shared among threads.
+pub trait Visit {
+    /// Observe a tree entry at `path` that is a tree and return an instruction whether to continue or not.
+    /// [`Action::Skip`] can be used to prevent traversing it, for example if it's known to the caller already.
+    fn visit_tree(&mut self, path: &BStr, entry: &gix_object::tree::EntryRef<'_>) -> Action;
+
+    /// Observe a tree entry at `path` that is NO tree and return an instruction whether to continue or not.
+    /// [`Action::Skip`] has no effect here.
+    fn visit_nontree(&mut self, path: &BStr, entry: &gix_object::tree::EntryRef<'_>) -> Action;
+}
+
+/// Options for use in [`traverse()`](impl_::traverse()).
+#[derive(Default, Debug, Copy, Clone)]
+pub struct Options {
+    /// The amount of threads to use, or `None` or `Some(0)` to use one per logical core.
+    pub thread_limit: Option<usize>,
+}
+
+/// A tree to traverse along with its path.
+struct Item {
+    id: ObjectId,
+    path: BString,
+}
+
+/// The state shared among all threads.
+struct Shared {
+    /// One queue per thread, with the owner working on its back and all other threads stealing from its front.
+    queues: Vec<Mutex<VecDeque<Item>>>,
+    /// The amount of trees that are queued or currently being traversed.
+    pending: AtomicUsize,
+    /// Set if the traversal is cancelled or failed.
+    stop: AtomicBool,
+    /// The first error that occurred.
+    error: Mutex<Option<Error>>,
+}
+
+impl Shared {
+    fn push(&self, thread: usize, item: Item) {
+        self.pending.fetch_add(1, Ordering::SeqCst);
+        self.queues[thread].lock().expect("no panic").push_back(item);
+    }
+
+    fn pop(&self, thread: usize) -> Option<Item> {
+        if let Some(item) = self.queues[thread].lock().expect("no panic").pop_back() {
+            return Some(item);
+        }
+        let num_queues = self.queues.len();
+        (1..num_queues)
+            .map(|offset| (thread + offset) % num_queues)
+            .find_map(|victim| self.queues[victim].lock().expect("no panic").pop_front())
+    }
+
+    fn fail(&self, err: Error) {
+        self.stop.store(true, Ordering::SeqCst);
+        self.error.lock().expect("no panic").get_or_insert(err);
+    }
+}
+
+pub(crate) mod impl_ {
+    use std::sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex,
+    };
+
+    use gix_hash::ObjectId;
+    use gix_object::{bstr::BString, FindExt};
+
+    use super::{Action, Error, Item, Options, Shared, Visit};
+
+    /// Traverse the tree with id `root` and all of its subtrees using multiple threads, with idle threads stealing queued
+    /// trees from busy ones, and return all delegates once the traversal is complete.
+    ///
+    /// * `objects` - a way to lookup trees, which is cloned once per thread.
+    /// * `make_delegate` - called once per thread to create a delegate to observe the entries seen by the thread.
+    ///   As each thread only sees a portion of all entries, the caller is expected to combine the results of all returned
+    ///   delegates.
+    /// * `options` - control the amount of threads.
+    ///
+    /// Note that the order in which entries are observed is undefined, and that a delegate returning [`Action::Cancel`]
+    /// stops the traversal on all threads with [`Error::Cancelled`].
+    pub fn traverse<Find, V>(
+        root: ObjectId,
+        objects: Find,
+        make_delegate: impl Fn() -> V + Sync,
+        options: Options,
+    ) -> Result<Vec<V>, Error>
+    where
+        Find: gix_object::Find + Clone + Send,
+        V: Visit + Send,
+    {
+        let num_threads = match options.thread_limit {
+            None | Some(0) => std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get),
+            Some(n) => n,
+        };
+        let shared = Shared {
+            queues: (0..num_threads).map(|_| Mutex::new(Default::default())).collect(),
+            pending: AtomicUsize::new(0),
+            stop: AtomicBool::new(false),
+            error: Mutex::new(None),
+        };
+        shared.push(
+            0,
+            Item {
+                id: root,
+                path: BString::default(),
+            },
+        );
+
+        let delegates = std::thread::scope(|scope| {
+            let threads: Vec<_> = (0..num_threads)
+                .map(|thread| {
+                    let objects = objects.clone();
+                    let shared = &shared;
+                    let make_delegate = &make_delegate;
+                    scope.spawn(move || {
+                        let mut delegate = make_delegate();
+                        let mut buf = Vec::new();
+                        while !shared.stop.load(Ordering::SeqCst) {
+                            let Some(item) = shared.pop(thread) else {
+                                if shared.pending.load(Ordering::SeqCst) == 0 {
+                                    break;
+                                }
+                                std::thread::yield_now();
+                                continue;
+                            };
+                            if let Err(err) = traverse_one(item, &objects, &mut buf, &mut delegate, shared, thread) {
+                                shared.fail(err);
+                            }
+                            shared.pending.fetch_sub(1, Ordering::SeqCst);
+                        }
+                        delegate
+                    })
+                })
+                .collect();
+            threads
+                .into_iter()
+                .map(|thread| thread.join().expect("no panic"))
+                .collect::<Vec<_>>()
+        });
+
+        match shared.error.into_inner().expect("no panic") {
+            Some(err) => Err(err),
+            None => Ok(delegates),
+        }
+    }
+
+    fn traverse_one<Find, V>(
+        item: Item,
+        objects: &Find,
+        buf: &mut Vec<u8>,
+        delegate: &mut V,
+        shared: &Shared,
+        thread: usize,
+    ) -> Result<(), Error>
+    where
+        Find: gix_object::Find,
+        V: Visit,
+    {
+        let mut path = item.path.clone();
+        for entry in objects.find_tree_iter(&item.id, buf)? {
+            let entry = entry?;
+            path.truncate(item.path.len());
+            if !path.is_empty() {
+                path.push(b'/');
+            }
+            path.extend_from_slice(entry.filename);
+            if entry.mode.is_tree() {
+                match delegate.visit_tree(path.as_ref(), &entry) {
+                    Action::Skip => {}
+                    Action::Continue => shared.push(
+                        thread,
+                        Item {
+                            id: entry.oid.to_owned(),
+                            path: path.clone(),
+                        },
+                    ),
+                    Action::Cancel => return Err(Error::Cancelled),
+                }
+            } else if delegate.visit_nontree(path.as_ref(), &entry).cancelled() {
+                return Err(Error::Cancelled);
+            }
+        }
+        Ok(())
+    }
+}
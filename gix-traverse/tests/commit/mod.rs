@@ -1,2 +1,3 @@
+mod prioritized;
 mod simple;
 mod topo;
@@ -0,0 +1,59 @@
+use std::cmp::Reverse;
+
+use gix_hash::ObjectId;
+use gix_traverse::commit::{prioritized::Candidate, Prioritized};
+
+use crate::hex_to_id;
+
+const M1B1: &str = "288e509293165cb5630d08f4185bdf2445bf6170";
+const B1C1: &str = "bcb05040a6925f2ff5e10d3ae1f9264f2e8c43ac";
+const C2: &str = "9902e3c3e8f0c569b4ab295ddf473e6de763e1e7";
+const C1: &str = "134385f6d781b7e97062102c6a483440bfda2a03";
+
+fn traverse<Key: Ord>(
+    priority: impl FnMut(&Candidate<'_>) -> Option<Key> + Clone,
+) -> crate::Result<Vec<Vec<ObjectId>>> {
+    let dir = gix_testtools::scripted_fixture_read_only_standalone("make_traversal_repo_for_commits_with_dates.sh")?;
+    let store = gix_odb::at(dir.join(".git").join("objects"))?;
+    let mut out = Vec::new();
+    for use_commitgraph in [false, true] {
+        let graph = use_commitgraph
+            .then(|| gix_commitgraph::at(store.store_ref().path().join("info")))
+            .transpose()?;
+        out.push(
+            Prioritized::new(Some(hex_to_id(M1B1)), &store, priority.clone())?
+                .commit_graph(graph)
+                .map(|res| res.map(|info| info.id))
+                .collect::<Result<Vec<_>, _>>()?,
+        );
+    }
+    Ok(out)
+}
+
+fn ids(hex: &[&str]) -> Vec<ObjectId> {
+    hex.iter().copied().map(hex_to_id).collect()
+}
+
+#[test]
+fn the_greatest_key_is_returned_first() -> crate::Result {
+    for actual in traverse(|c| Some((c.commit_time, c.id.to_owned())))? {
+        assert_eq!(
+            actual,
+            ids(&[M1B1, B1C1, C2, C1]),
+            "newest first, with the greater id first on equal commit times"
+        );
+    }
+    for actual in traverse(|c| Some(Reverse((c.commit_time, c.id.to_owned()))))? {
+        assert_eq!(actual, ids(&[M1B1, C2, C1, B1C1]), "oldest first");
+    }
+    Ok(())
+}
+
+#[test]
+fn commits_without_key_are_skipped_along_with_parents_only_reachable_through_them() -> crate::Result {
+    let c2 = hex_to_id(C2);
+    for actual in traverse(|c| (c.id != c2).then_some(c.commit_time))? {
+        assert_eq!(actual, ids(&[M1B1, B1C1, C1]), "c1 is still reachable through b1c1");
+    }
+    Ok(())
+}
@@ -147,3 +147,135 @@ fn breadth_first_no_location() -> crate::Result<()> {
     }
     Ok(())
 }
+
+mod parallel {
+    use std::collections::BTreeSet;
+
+    use gix_object::bstr::{BStr, BString};
+    use gix_odb::pack::FindExt;
+    use gix_traverse::tree::{self, visit::Action};
+
+    use crate::hex_to_id;
+
+    #[derive(Default)]
+    struct Collect {
+        skip: Option<&'static str>,
+        cancel: Option<&'static str>,
+        paths: Vec<(BString, bool)>,
+    }
+
+    impl tree::parallel::Visit for Collect {
+        fn visit_tree(&mut self, path: &BStr, _entry: &gix_object::tree::EntryRef<'_>) -> Action {
+            self.paths.push((path.to_owned(), true));
+            if self.skip.map_or(false, |skip| path == skip) {
+                Action::Skip
+            } else {
+                Action::Continue
+            }
+        }
+
+        fn visit_nontree(&mut self, path: &BStr, _entry: &gix_object::tree::EntryRef<'_>) -> Action {
+            self.paths.push((path.to_owned(), false));
+            if self.cancel.map_or(false, |cancel| path == cancel) {
+                Action::Cancel
+            } else {
+                Action::Continue
+            }
+        }
+    }
+
+    /// The object database must be shareable across threads, even without the `parallel` feature of `gix-features`.
+    fn db() -> crate::Result<gix_odb::HandleArc> {
+        Ok(super::db()?.into_arc()?)
+    }
+
+    fn root_tree(db: &gix_odb::HandleArc) -> crate::Result<gix_hash::ObjectId> {
+        let mut buf = Vec::new();
+        Ok(db
+            .find_commit_iter(&hex_to_id("85df34aa34848b8138b2b3dcff5fb5c2b734e0ce"), &mut buf)?
+            .0
+            .tree_id()
+            .expect("a tree is available in a commit"))
+    }
+
+    fn all_paths(delegates: Vec<Collect>) -> BTreeSet<(String, bool)> {
+        delegates
+            .into_iter()
+            .flat_map(|d| d.paths)
+            .map(|(path, is_tree)| (path.to_string(), is_tree))
+            .collect()
+    }
+
+    #[test]
+    fn sees_the_same_entries_as_breadth_first_regardless_of_thread_count() -> crate::Result {
+        let db = db()?;
+        let root = root_tree(&db)?;
+        let mut recorder = tree::Recorder::default();
+        let mut buf = Vec::new();
+        tree::breadthfirst(
+            db.find_tree_iter(&root, &mut buf)?.0,
+            tree::breadthfirst::State::default(),
+            &db,
+            &mut recorder,
+        )?;
+        let expected: BTreeSet<_> = recorder
+            .records
+            .into_iter()
+            .map(|e| (e.filepath.to_string(), e.mode.is_tree()))
+            .collect();
+
+        for thread_limit in [Some(1), Some(4), None] {
+            let delegates = tree::parallel(
+                root,
+                db.clone(),
+                Collect::default,
+                tree::parallel::Options { thread_limit },
+            )?;
+            assert_eq!(
+                delegates.len(),
+                thread_limit.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get())),
+                "one delegate per thread"
+            );
+            let num_entries: usize = delegates.iter().map(|d| d.paths.len()).sum();
+            assert_eq!(num_entries, expected.len(), "each entry is seen exactly once");
+            assert_eq!(all_paths(delegates), expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn skip_and_cancel() -> crate::Result {
+        let db = db()?;
+        let root = root_tree(&db)?;
+        let delegates = tree::parallel(
+            root,
+            db.clone(),
+            || Collect {
+                skip: Some("f"),
+                ..Default::default()
+            },
+            Default::default(),
+        )?;
+        let paths = all_paths(delegates);
+        assert!(paths.contains(&("f".into(), true)));
+        assert!(
+            paths.iter().all(|(path, _)| !path.starts_with("f/")),
+            "skipped trees aren't entered"
+        );
+        assert!(paths.contains(&("d/a".into(), false)));
+
+        let err = tree::parallel(
+            root,
+            db,
+            || Collect {
+                cancel: Some("e/b"),
+                ..Default::default()
+            },
+            tree::parallel::Options { thread_limit: Some(2) },
+        )
+        .err()
+        .expect("cancelled");
+        assert!(matches!(err, tree::parallel::Error::Cancelled));
+        Ok(())
+    }
+}
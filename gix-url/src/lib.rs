@@ -32,6 +32,7 @@ pub fn parse(input: &BStr) -> Result<Url, parse::Error> {
     use parse::InputScheme;
     match parse::find_scheme(input) {
         InputScheme::Local => parse::local(input),
+        InputScheme::RemoteHelper { separator } => parse::remote_helper(input, separator),
         InputScheme::Url { protocol_end } if input[..protocol_end].eq_ignore_ascii_case(b"file") => {
             parse::file_url(input, protocol_end)
         }
@@ -219,6 +220,21 @@ impl Url {
         }
     }
 
+    /// Return `true` if this url uses the alternative form when serialized, like `/path` instead of `file:///path`,
+    /// which typically is the case if it was parsed from such a form.
+    pub fn is_alternate_form(&self) -> bool {
+        self.serialize_alternative_form
+    }
+
+    /// Return `(transport, address)` if this url was parsed from the `<transport>::<address>` form, which indicates that
+    /// the `address` is to be handled by the `git-remote-<transport>` helper program.
+    pub fn remote_helper(&self) -> Option<(&str, &BStr)> {
+        match &self.scheme {
+            Scheme::Ext(transport) if self.serialize_alternative_form => Some((transport.as_str(), self.path.as_ref())),
+            _ => None,
+        }
+    }
+
     /// Return the password mentioned in the url, if present.
     pub fn password(&self) -> Option<&str> {
         self.password.as_deref()
@@ -312,6 +328,11 @@ impl Url {
 impl Url {
     /// Write this URL losslessly to `out`, ready to be parsed again.
     pub fn write_to(&self, mut out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        if let Some((transport, address)) = self.remote_helper() {
+            out.write_all(transport.as_bytes())?;
+            out.write_all(b"::")?;
+            return out.write_all(address);
+        }
         if !(self.serialize_alternative_form && (self.scheme == Scheme::File || self.scheme == Scheme::Ssh)) {
             out.write_all(self.scheme.as_str().as_bytes())?;
             out.write_all(b"://")?;
@@ -57,12 +57,17 @@ impl UrlKind {
 }
 
 pub(crate) enum InputScheme {
+    RemoteHelper { separator: usize },
     Url { protocol_end: usize },
     Scp { colon: usize },
     Local,
 }
 
 pub(crate) fn find_scheme(input: &BStr) -> InputScheme {
+    if let Some(separator) = remote_helper_separator(input) {
+        return InputScheme::RemoteHelper { separator };
+    }
+
     // TODO: url's may only contain `:/`, we should additionally check if the characters used for
     //       protocol are all valid
     if let Some(protocol_end) = input.find("://") {
@@ -83,6 +88,34 @@ pub(crate) fn find_scheme(input: &BStr) -> InputScheme {
     InputScheme::Local
 }
 
+/// Return the position of `::` in `<transport>::<address>` if `input` uses this form, like git does when deciding
+/// to pass the `address` to the `git-remote-<transport>` helper program.
+///
+/// Note that `<transport>:://…` is not considered to be of this form as it's more likely to be a URL with an invalid scheme.
+fn remote_helper_separator(input: &BStr) -> Option<usize> {
+    let separator = input.find("::")?;
+    let (transport, address) = (&input[..separator], &input[separator + 2..]);
+    let is_valid_transport = transport.first().map_or(false, u8::is_ascii_alphabetic)
+        && transport
+            .iter()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'.' | b'-'));
+    (is_valid_transport && !address.is_empty() && !address.starts_with(b"//")).then_some(separator)
+}
+
+pub(crate) fn remote_helper(input: &BStr, separator: usize) -> Result<crate::Url, Error> {
+    let input = input_to_utf8(input, UrlKind::Url)?;
+    let (transport, address) = (&input[..separator], &input[separator + 2..]);
+    Ok(crate::Url {
+        serialize_alternative_form: true,
+        scheme: Scheme::Ext(transport.into()),
+        user: None,
+        password: None,
+        host: None,
+        port: None,
+        path: address.into(),
+    })
+}
+
 pub(crate) fn url(input: &BStr, protocol_end: usize) -> Result<crate::Url, Error> {
     const MAX_LEN: usize = 1024;
     let bytes_to_path = input[protocol_end + "://".len()..]
@@ -134,3 +134,48 @@ mod unknown {
         )
     }
 }
+
+mod remote_helper {
+    use bstr::ByteSlice;
+    use gix_url::Scheme;
+
+    use crate::parse::{assert_url_roundtrip, parse, url_alternate};
+
+    #[test]
+    fn transport_and_address_with_url() -> crate::Result {
+        let expected = url_alternate(Scheme::Ext("hg".into()), None, None, None, b"https://example.com/repo");
+        assert_url_roundtrip("hg::https://example.com/repo", expected.clone())?;
+        assert_eq!(
+            expected.remote_helper(),
+            Some(("hg", b"https://example.com/repo".as_bstr()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn transport_and_arbitrary_address() -> crate::Result {
+        assert_url_roundtrip(
+            "corp-vcs+1.0::some opaque address",
+            url_alternate(
+                Scheme::Ext("corp-vcs+1.0".into()),
+                None,
+                None,
+                None,
+                b"some opaque address",
+            ),
+        )
+    }
+
+    #[test]
+    fn other_urls_are_not_handled_by_remote_helpers() -> crate::Result {
+        for input in [
+            "abc://example.com/repo",
+            "user@host.xz:path",
+            "/path/to/repo",
+            "1hg::addr",
+        ] {
+            assert_eq!(parse(input)?.remote_helper(), None, "{input}");
+        }
+        Ok(())
+    }
+}
@@ -13,20 +13,120 @@ pub mod name {
     }
 }
 
+///
+pub mod url {
+    /// The error used in [url()](super::url()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Submodule URLs must not start with '-' as they could be mistaken for a command-line option")]
+        OptionLike,
+        #[error("Submodule URLs must not contain newlines, literally or percent-encoded")]
+        Newline,
+        #[error("Relative submodule URLs must not resolve to a URL without host or path, like '../:' or '..//'")]
+        RelativeWithoutPath,
+    }
+}
+
+///
+pub mod path {
+    use bstr::BString;
+
+    /// The error used in [path()](super::path()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Submodule paths cannot be empty")]
+        Empty,
+        #[error("Submodule paths must not start with '-' as they could be mistaken for a command-line option")]
+        OptionLike,
+        #[error("Submodule paths must be relative")]
+        Absolute,
+        #[error("Submodule paths must not contain '..'")]
+        ParentComponent,
+        #[error("The submodule path component '{component}' is invalid")]
+        Component {
+            component: BString,
+            source: crate::path::component::Error,
+        },
+    }
+}
+
 /// Return the original `name` if it is valid, or the respective error indicating what was wrong with it.
+///
+/// As the name is used as path to the repository of the submodule within `.git/modules`, it must not contain
+/// `..` components to prevent escaping that directory.
 pub fn name(name: &BStr) -> Result<&BStr, name::Error> {
     if name.is_empty() {
         return Err(name::Error::Empty);
     }
-    match name.find(b"..") {
-        Some(pos) => {
-            let &b = name.get(pos + 2).ok_or(name::Error::ParentComponent)?;
-            if b == b'/' || b == b'\\' {
-                Err(name::Error::ParentComponent)
-            } else {
-                Ok(name)
-            }
+    if name
+        .split(|b| *b == b'/' || *b == b'\\')
+        .any(|component| component == b"..")
+    {
+        return Err(name::Error::ParentComponent);
+    }
+    Ok(name)
+}
+
+/// Return the original `url` if it is safe to pass to a program like `git` or `ssh` when cloning or updating a submodule,
+/// or the respective error indicating what was wrong with it.
+///
+/// Note that this doesn't assure that `url` can be parsed.
+pub fn url(url: &BStr) -> Result<&BStr, url::Error> {
+    if url.first() == Some(&b'-') {
+        return Err(url::Error::OptionLike);
+    }
+    if url.find_byte(b'\n').is_some() || url.windows(3).any(|w| w.eq_ignore_ascii_case(b"%0a")) {
+        return Err(url::Error::Newline);
+    }
+    let mut rest = url.as_bytes();
+    let mut num_parent_components = 0;
+    loop {
+        if let Some(stripped) = rest.strip_prefix(b"./").or_else(|| rest.strip_prefix(b".\\")) {
+            rest = stripped;
+        } else if let Some(stripped) = rest.strip_prefix(b"../").or_else(|| rest.strip_prefix(b"..\\")) {
+            rest = stripped;
+            num_parent_components += 1;
+        } else {
+            break;
+        }
+    }
+    if num_parent_components > 0 && matches!(rest.first(), Some(b':' | b'/' | b'\\')) {
+        return Err(url::Error::RelativeWithoutPath);
+    }
+    Ok(url)
+}
+
+/// Return the original `path` if it is a safe location for the worktree of a submodule, relative to the worktree of the
+/// superproject, or the respective error indicating what was wrong with it.
+///
+/// Each component of `path` is checked with [`path::component()`](crate::path::component()) and `options`,
+/// which prevents submodules from being checked out into `.git` directories, also in their disguised forms.
+pub fn path(path: &BStr, options: crate::path::component::Options) -> Result<&BStr, path::Error> {
+    if path.is_empty() {
+        return Err(path::Error::Empty);
+    }
+    if path.first() == Some(&b'-') {
+        return Err(path::Error::OptionLike);
+    }
+    if path.first() == Some(&b'/')
+        || (options.protect_windows && (path.first() == Some(&b'\\') || path.get(1) == Some(&b':')))
+    {
+        return Err(path::Error::Absolute);
+    }
+    let separators: &[u8] = if options.protect_windows { b"/\\" } else { b"/" };
+    for component in path.split(|b| separators.contains(b)) {
+        if component.is_empty() || component == b"." {
+            continue;
+        }
+        if component == b".." {
+            return Err(path::Error::ParentComponent);
         }
-        None => Ok(name),
+        crate::path::component(component.as_bstr(), None, options).map_err(|err| path::Error::Component {
+            component: component.into(),
+            source: err,
+        })?;
     }
+    Ok(path)
 }
@@ -6,7 +6,7 @@ fn valid() {
         gix_validate::submodule::name(name.into()).map(|_| ())
     }
 
-    for valid_name in ["a/./b/..[", "..a/./b/", "..a\\./b\\", "你好"] {
+    for valid_name in ["a/./b/..[", "..a/./b/", "..a\\./b\\", "你好", "a../b", "a/..."] {
         validate(valid_name).unwrap_or_else(|err| panic!("{valid_name} should be valid: {err:?}"));
     }
 }
@@ -34,4 +34,113 @@ mod invalid {
     mktest!(starts_with_parent_component_backslash, b"..\\", ParentComponent);
     mktest!(parent_component_in_middle_backslash, b"hi\\..\\ho", ParentComponent);
     mktest!(ends_with_parent_component_backslash, b"hi\\ho\\..", ParentComponent);
+    mktest!(parent_component_after_dots_in_name, b"a..b/../c", ParentComponent);
+}
+
+mod url {
+    use gix_validate::submodule::url::Error;
+
+    fn validate(url: &str) -> Result<(), Error> {
+        gix_validate::submodule::url(url.into()).map(|_| ())
+    }
+
+    #[test]
+    fn valid() {
+        for valid_url in [
+            "https://example.com/repo",
+            "git@example.com:org/repo.git",
+            "../sibling",
+            "./../../sibling",
+            "..\\sibling",
+            "file:///path/to/a-repo",
+        ] {
+            validate(valid_url).unwrap_or_else(|err| panic!("{valid_url} should be valid: {err:?}"));
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        for (url, expected) in [
+            ("-upload-pack=touch pwned", Error::OptionLike),
+            ("--template=/tmp", Error::OptionLike),
+            ("https://example.com/repo\nhost=evil.com", Error::Newline),
+            ("https://example.com/repo%0Ahost=evil.com", Error::Newline),
+            ("../:", Error::RelativeWithoutPath),
+            ("../../:example.com/repo", Error::RelativeWithoutPath),
+            ("./..//example.com", Error::RelativeWithoutPath),
+        ] {
+            let actual = validate(url).expect_err(url);
+            assert_eq!(
+                std::mem::discriminant(&actual),
+                std::mem::discriminant(&expected),
+                "{url}: {actual:?}"
+            );
+        }
+    }
+}
+
+mod path {
+    use gix_validate::{path::component, submodule::path::Error};
+
+    const UNIX: component::Options = component::Options {
+        protect_windows: false,
+        protect_hfs: true,
+        protect_ntfs: true,
+    };
+
+    fn validate(path: &str) -> Result<(), Error> {
+        gix_validate::submodule::path(path.into(), UNIX).map(|_| ())
+    }
+
+    #[test]
+    fn valid() {
+        for valid_path in ["sub", "a/b/sub", "./sub", "a//sub/", ".gitsub", "sub.git", "a\\b"] {
+            validate(valid_path).unwrap_or_else(|err| panic!("{valid_path} should be valid: {err:?}"));
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(matches!(validate(""), Err(Error::Empty)));
+        assert!(matches!(validate("-sub"), Err(Error::OptionLike)));
+        assert!(matches!(validate("/abs/sub"), Err(Error::Absolute)));
+        assert!(matches!(validate("../sub"), Err(Error::ParentComponent)));
+        assert!(matches!(validate("a/../../sub"), Err(Error::ParentComponent)));
+        for git_dir in [
+            ".git",
+            "a/.git/hooks",
+            ".GIT/modules",
+            "a/git~1",
+            "a/.git. . /b",
+            ".git\u{200c}/b",
+        ] {
+            assert!(
+                matches!(
+                    validate(git_dir),
+                    Err(Error::Component {
+                        source: component::Error::DotGitDir,
+                        ..
+                    })
+                ),
+                "{git_dir} must not be used to embed a git directory"
+            );
+        }
+    }
+
+    #[test]
+    fn windows_separators_and_prefixes_are_checked_if_protected() {
+        let windows = component::Options::default();
+        assert!(matches!(
+            gix_validate::submodule::path("a\\..\\..\\sub".into(), windows),
+            Err(Error::ParentComponent)
+        ));
+        assert!(matches!(
+            gix_validate::submodule::path("c:\\sub".into(), windows),
+            Err(Error::Absolute)
+        ));
+        assert!(matches!(
+            gix_validate::submodule::path("a\\.git\\hooks".into(), windows),
+            Err(Error::Component { .. })
+        ));
+    }
 }
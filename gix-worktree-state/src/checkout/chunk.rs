@@ -85,6 +85,7 @@ pub struct Options {
     pub overwrite_existing: bool,
     pub keep_going: bool,
     pub filter_process_delay: gix_filter::driver::apply::Delay,
+    pub non_regular_files: gix_fs::NonRegularFiles,
 }
 
 impl From<&checkout::Options> for Options {
@@ -95,6 +96,7 @@ impl From<&checkout::Options> for Options {
             overwrite_existing: opts.overwrite_existing,
             keep_going: opts.keep_going,
             filter_process_delay: opts.filter_process_delay,
+            non_regular_files: opts.non_regular_files,
         }
     }
 }
@@ -168,6 +170,7 @@ where
         destination_is_initially_empty,
         overwrite_existing,
         keep_going,
+        non_regular_files,
         ..
     } = ctx.options;
     let mut bytes_written = 0;
@@ -209,6 +212,7 @@ where
                     &std::mem::take(&mut delayed.validated_file_path), // mark it as seen, relevant for `unprocessed_paths`
                     destination_is_initially_empty,
                     overwrite_existing,
+                    non_regular_files,
                     delayed.needs_executable_bit,
                     delayed.entry.mode,
                 ) {
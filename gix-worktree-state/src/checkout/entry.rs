@@ -72,6 +72,7 @@ pub fn checkout<'entry, Find>(
         destination_is_initially_empty,
         overwrite_existing,
         filter_process_delay,
+        non_regular_files,
         ..
     }: crate::checkout::chunk::Options,
 ) -> Result<Outcome<'entry>, crate::checkout::Error>
@@ -107,6 +108,7 @@ where
                         dest,
                         destination_is_initially_empty,
                         overwrite_existing,
+                        non_regular_files,
                         executable_bit,
                         entry.mode,
                     )?;
@@ -118,6 +120,7 @@ where
                         dest,
                         destination_is_initially_empty,
                         overwrite_existing,
+                        non_regular_files,
                         executable_bit,
                         entry.mode,
                     )?;
@@ -161,6 +164,9 @@ where
                     gix_fs::symlink::create(symlink_destination.as_ref(), p)
                 })?;
             } else {
+                if overwrite_existing || !destination_is_initially_empty {
+                    handle_non_regular_file(dest, overwrite_existing, non_regular_files)?;
+                }
                 let mut file = try_op_or_unlink(dest, overwrite_existing, |p| {
                     open_options(p, destination_is_initially_empty, overwrite_existing).open(dest)
                 })?;
@@ -212,6 +218,29 @@ fn try_op_or_unlink<T>(
     }
 }
 
+/// Assure we don't write into a non-regular file at `path`, like a named pipe, as it might block forever.
+fn handle_non_regular_file(
+    path: &Path,
+    overwrite_existing: bool,
+    non_regular_files: gix_fs::NonRegularFiles,
+) -> std::io::Result<()> {
+    use gix_fs::NonRegularFiles;
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if NonRegularFiles::is_non_regular(meta.file_type()) => match non_regular_files {
+            NonRegularFiles::Skip if !overwrite_existing => Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("Non-regular file at '{}' is in the way", path.display()),
+            )),
+            NonRegularFiles::Skip | NonRegularFiles::AsEmptyFile => std::fs::remove_file(path),
+            NonRegularFiles::Error => Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Refusing to replace non-regular file at '{}'", path.display()),
+            )),
+        },
+        _ => Ok(()),
+    }
+}
+
 fn try_unlink_path_recursively(path: &Path, path_meta: &std::fs::Metadata) -> std::io::Result<()> {
     if path_meta.is_dir() {
         std::fs::remove_dir_all(path)
@@ -254,9 +283,13 @@ pub(crate) fn open_file(
     path: &Path,
     destination_is_initially_empty: bool,
     overwrite_existing: bool,
+    non_regular_files: gix_fs::NonRegularFiles,
     fs_supports_executable_bit: bool,
     entry_mode: gix_index::entry::Mode,
 ) -> std::io::Result<(std::fs::File, bool)> {
+    if overwrite_existing || !destination_is_initially_empty {
+        handle_non_regular_file(path, overwrite_existing, non_regular_files)?;
+    }
     #[cfg_attr(windows, allow(unused_mut))]
     let mut options = open_options(path, destination_is_initially_empty, overwrite_existing);
     let needs_executable_bit = fs_supports_executable_bit && entry_mode == gix_index::entry::Mode::FILE_EXECUTABLE;
@@ -62,6 +62,13 @@ pub struct Options {
     pub keep_going: bool,
     /// Control how stat comparisons are made when checking if a file is fresh.
     pub stat_options: stat::Options,
+    /// Control what happens if a named pipe, socket or other non-regular file is in the way of a file to check out,
+    /// as writing into it might block forever.
+    ///
+    /// With [`Skip`](gix_fs::NonRegularFiles::Skip), the default, it is replaced only if `overwrite_existing` is set,
+    /// and is a [collision](Collision) otherwise. With [`AsEmptyFile`](gix_fs::NonRegularFiles::AsEmptyFile) it is replaced
+    /// like any other file, and with [`Error`](gix_fs::NonRegularFiles::Error) it's an error.
+    pub non_regular_files: gix_fs::NonRegularFiles,
     /// A stack of attributes to use with the filesystem cache to use as driver for filters.
     pub attributes: gix_worktree::stack::state::Attributes,
     /// The filter pipeline to use for applying mandatory filters before writing to the worktree.
@@ -1,5 +1,5 @@
 #[cfg(unix)]
-use std::os::unix::prelude::MetadataExt;
+use std::os::unix::prelude::{FileTypeExt, MetadataExt};
 use std::{
     fs,
     io::{ErrorKind, ErrorKind::AlreadyExists},
@@ -255,6 +255,65 @@ fn overwriting_files_and_lone_directories_works() -> crate::Result {
     Ok(())
 }
 
+#[test]
+#[cfg(unix)]
+fn non_regular_files_in_the_way_are_never_written_into() -> crate::Result {
+    use gix_fs::NonRegularFiles;
+    for non_regular_files in [
+        NonRegularFiles::Skip,
+        NonRegularFiles::Error,
+        NonRegularFiles::AsEmptyFile,
+    ] {
+        let mut opts = opts_from_probe();
+        opts.destination_is_initially_empty = false;
+        opts.keep_going = true;
+        opts.non_regular_files = non_regular_files;
+        let (_source, destination, _index, outcome) = checkout_index_in_tmp_dir_opts(
+            opts,
+            "make_mixed",
+            None,
+            |_| true,
+            |d| {
+                let status = std::process::Command::new("mkfifo")
+                    .arg(d.join("executable"))
+                    .status()?;
+                assert!(status.success(), "mkfifo should be available");
+                Ok(())
+            },
+        )?;
+
+        let exe = destination.path().join("executable");
+        match non_regular_files {
+            NonRegularFiles::Skip => {
+                assert_eq!(
+                    outcome.collisions,
+                    vec![Collision {
+                        path: "executable".into(),
+                        error_kind: AlreadyExists
+                    }]
+                );
+                assert!(
+                    std::fs::symlink_metadata(&exe)?.file_type().is_fifo(),
+                    "it was left alone"
+                );
+            }
+            NonRegularFiles::Error => {
+                assert_eq!(outcome.errors.len(), 1);
+                assert_eq!(outcome.errors[0].path, "executable");
+                assert!(
+                    std::fs::symlink_metadata(&exe)?.file_type().is_fifo(),
+                    "it was left alone"
+                );
+            }
+            NonRegularFiles::AsEmptyFile => {
+                assert!(outcome.collisions.is_empty());
+                assert_eq!(std::fs::read(&exe)?, b"content", "it was replaced like any other file");
+            }
+        }
+    }
+    Ok(())
+}
+
 #[test]
 fn symlinks_become_files_if_disabled() -> crate::Result {
     let mut opts = opts_from_probe();
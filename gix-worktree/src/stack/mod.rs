@@ -186,6 +186,26 @@ impl Stack {
         self.case = case;
         self
     }
+
+    /// Obtain parsed `.gitattributes` and `.gitignore` files from `cache` and store them there, so that all stacks sharing
+    /// the same `cache` parse them only once. Set it to `None` to stop using a shared cache.
+    pub fn set_shared_cache(&mut self, cache: Option<SharedCache>) -> &mut Self {
+        match &mut self.state {
+            State::IgnoreStack(ignore) => {
+                ignore.set_shared_cache(cache);
+            }
+            #[cfg(feature = "attributes")]
+            State::AttributesAndIgnoreStack { attributes, ignore } => {
+                attributes.set_shared_cache(cache.clone());
+                ignore.set_shared_cache(cache);
+            }
+            #[cfg(feature = "attributes")]
+            State::AttributesStack(attributes) | State::CreateDirectoryAndAttributesStack { attributes, .. } => {
+                attributes.set_shared_cache(cache);
+            }
+        }
+        self
+    }
 }
 
 /// Access
@@ -212,5 +232,7 @@ pub mod delegate;
 use delegate::StackDelegate;
 
 mod platform;
+mod shared_cache;
+pub use shared_cache::SharedCache;
 ///
 pub mod state;
@@ -0,0 +1,141 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use gix_glob::search::{pattern, Pattern};
+use gix_object::FindExt;
+
+/// A cache for parsed `.gitattributes` and `.gitignore` files, keyed by their location, which can be shared by any amount
+/// of [`Stack`](crate::Stack) instances, even across threads.
+///
+/// That way, operations like status, checkout, diff or add that each configure their own stack, or that clone stacks
+/// for use in multiple threads, parse each of these files only once.
+///
+/// Files read from the worktree are only reused if their content didn't change, while files read from the object database
+/// are identified by their object id.
+///
+/// Clones of this instance share the same cache.
+#[derive(Default, Clone)]
+pub struct SharedCache {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    #[cfg(feature = "attributes")]
+    attributes: Lists<gix_attributes::search::Attributes>,
+    ignore: Lists<gix_ignore::search::Ignore>,
+}
+
+/// Pattern lists read from the worktree along with the data they were parsed from, keyed by their path and root.
+type FileLists<T> = HashMap<(PathBuf, Option<PathBuf>), (Vec<u8>, pattern::List<T>)>;
+
+/// Pattern lists, either read from the worktree along with the data they were parsed from, or read from the object database.
+struct Lists<T: Pattern> {
+    files: Mutex<FileLists<T>>,
+    blobs: Mutex<HashMap<(gix_hash::ObjectId, PathBuf), pattern::List<T>>>,
+}
+
+impl<T: Pattern> Default for Lists<T> {
+    fn default() -> Self {
+        Lists {
+            files: Default::default(),
+            blobs: Default::default(),
+        }
+    }
+}
+
+impl std::fmt::Debug for SharedCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedCache").finish_non_exhaustive()
+    }
+}
+
+/// Access
+impl SharedCache {
+    /// Remove all cached pattern lists, to release their memory or to start from scratch.
+    pub fn clear(&self) {
+        #[cfg(feature = "attributes")]
+        self.inner.attributes.clear();
+        self.inner.ignore.clear();
+    }
+
+    #[cfg(feature = "attributes")]
+    pub(crate) fn attributes(&self) -> Handle<'_, gix_attributes::search::Attributes> {
+        Handle(&self.inner.attributes)
+    }
+
+    pub(crate) fn ignore(&self) -> Handle<'_, gix_ignore::search::Ignore> {
+        Handle(&self.inner.ignore)
+    }
+}
+
+/// Provide cached access to pattern lists of type `T`.
+pub(crate) struct Handle<'a, T: Pattern>(&'a Lists<T>);
+
+impl<T: Pattern> Handle<'_, T> {
+    /// Like [`pattern::List::from_file()`], but return a copy of a previously parsed list if the file at `source` still has the same content.
+    /// `hits` is incremented if a cached list was returned.
+    pub(crate) fn list_from_file(
+        &self,
+        source: PathBuf,
+        root: Option<&Path>,
+        follow_symlinks: bool,
+        buf: &mut Vec<u8>,
+        hits: &mut usize,
+    ) -> std::io::Result<Option<pattern::List<T>>> {
+        if !pattern::read_in_full_ignore_missing(&source, follow_symlinks, buf)? {
+            return Ok(None);
+        }
+        let key = (source, root.map(ToOwned::to_owned));
+        if let Some((data, list)) = self.0.files.lock().expect("no panic while holding the lock").get(&key) {
+            if data == buf {
+                *hits += 1;
+                return Ok(Some(list.clone()));
+            }
+        }
+        let list = pattern::List::from_bytes(buf, key.0.clone(), root);
+        self.0
+            .files
+            .lock()
+            .expect("no panic while holding the lock")
+            .insert(key, (buf.clone(), list.clone()));
+        Ok(Some(list))
+    }
+
+    /// Return the pattern list parsed from the blob with `id` as read from `objects`, with `source` being its repository-relative path.
+    /// `hits` is incremented if a cached list was returned.
+    pub(crate) fn list_from_blob(
+        &self,
+        id: &gix_hash::oid,
+        source: PathBuf,
+        objects: &dyn gix_object::Find,
+        buf: &mut Vec<u8>,
+        hits: &mut usize,
+    ) -> std::io::Result<pattern::List<T>> {
+        let key = (id.to_owned(), source);
+        if let Some(list) = self.0.blobs.lock().expect("no panic while holding the lock").get(&key) {
+            *hits += 1;
+            return Ok(list.clone());
+        }
+        let blob = objects
+            .find_blob(id, buf)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+        let list = pattern::List::from_bytes(blob.data, key.1.clone(), Some(Path::new("")));
+        self.0
+            .blobs
+            .lock()
+            .expect("no panic while holding the lock")
+            .insert(key, list.clone());
+        Ok(list)
+    }
+}
+
+impl<T: Pattern> Lists<T> {
+    fn clear(&self) {
+        self.files.lock().expect("no panic while holding the lock").clear();
+        self.blobs.lock().expect("no panic while holding the lock").clear();
+    }
+}
@@ -5,7 +5,10 @@ use gix_glob::pattern::Case;
 use gix_object::FindExt;
 
 use crate::{
-    stack::state::{AttributeMatchGroup, Attributes},
+    stack::{
+        state::{AttributeMatchGroup, Attributes},
+        SharedCache,
+    },
     PathIdMapping, Stack,
 };
 
@@ -19,6 +22,8 @@ pub struct Statistics {
     pub pattern_files: usize,
     /// Amount of pattern files we tried to find on disk.
     pub tried_pattern_files: usize,
+    /// Amount of pattern files or buffers that were obtained from a [`SharedCache`] instead of parsing them.
+    pub shared_cache_hits: usize,
 }
 
 /// Decide where to read `.gitattributes` files from.
@@ -78,8 +83,15 @@ impl Attributes {
             info_attributes,
             source,
             collection,
+            shared_cache: None,
         }
     }
+
+    /// Use `cache` to obtain parsed `.gitattributes` files, or stop using a shared cache if `None`.
+    pub fn set_shared_cache(&mut self, cache: Option<SharedCache>) -> &mut Self {
+        self.shared_cache = cache;
+        self
+    }
 }
 
 impl Attributes {
@@ -108,58 +120,48 @@ impl Attributes {
         match self.source {
             Source::IdMapping | Source::IdMappingThenWorktree => {
                 if let Ok(idx) = attr_file_in_index {
-                    let blob = objects
-                        .find_blob(&id_mappings[idx].1, buf)
-                        .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
                     let attr_path = gix_path::from_bstring(attr_path_relative.into_owned());
-                    self.stack.add_patterns_buffer(
-                        blob.data,
+                    self.add_patterns_blob(
+                        &id_mappings[idx].1,
                         attr_path,
-                        Some(Path::new("")),
-                        &mut self.collection,
+                        objects,
+                        buf,
                         read_macros_as_dir_is_root,
-                    );
+                        stats,
+                    )?;
                     added = true;
-                    stats.patterns_buffers += 1;
                 }
                 if !added && matches!(self.source, Source::IdMappingThenWorktree) {
-                    added = self.stack.add_patterns_file(
+                    added = self.add_patterns_file(
                         dir.join(".gitattributes"),
                         no_follow_symlinks,
-                        Some(root),
+                        root,
                         buf,
-                        &mut self.collection,
                         read_macros_as_dir_is_root,
+                        stats,
                     )?;
-                    stats.pattern_files += usize::from(added);
-                    stats.tried_pattern_files += 1;
                 }
             }
             Source::WorktreeThenIdMapping => {
-                added = self.stack.add_patterns_file(
+                added = self.add_patterns_file(
                     dir.join(".gitattributes"),
                     no_follow_symlinks,
-                    Some(root),
+                    root,
                     buf,
-                    &mut self.collection,
                     read_macros_as_dir_is_root,
+                    stats,
                 )?;
-                stats.pattern_files += usize::from(added);
-                stats.tried_pattern_files += 1;
                 if let Some(idx) = attr_file_in_index.ok().filter(|_| !added) {
-                    let blob = objects
-                        .find_blob(&id_mappings[idx].1, buf)
-                        .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
                     let attr_path = gix_path::from_bstring(attr_path_relative.into_owned());
-                    self.stack.add_patterns_buffer(
-                        blob.data,
+                    self.add_patterns_blob(
+                        &id_mappings[idx].1,
                         attr_path,
-                        Some(Path::new("")),
-                        &mut self.collection,
+                        objects,
+                        buf,
                         read_macros_as_dir_is_root,
-                    );
+                        stats,
+                    )?;
                     added = true;
-                    stats.patterns_buffers += 1;
                 }
             }
         }
@@ -188,6 +190,79 @@ impl Attributes {
         Ok(())
     }
 
+    /// Read the `.gitattributes` file at `path`, possibly from the shared cache, and return `true` if it existed.
+    fn add_patterns_file(
+        &mut self,
+        path: PathBuf,
+        follow_symlinks: bool,
+        root: &Path,
+        buf: &mut Vec<u8>,
+        allow_macros: bool,
+        stats: &mut Statistics,
+    ) -> std::io::Result<bool> {
+        let added = match self.shared_cache.as_ref() {
+            Some(cache) => match cache.attributes().list_from_file(
+                path,
+                Some(root),
+                follow_symlinks,
+                buf,
+                &mut stats.shared_cache_hits,
+            )? {
+                Some(list) => {
+                    self.stack.add_pattern_list(list, &mut self.collection, allow_macros);
+                    true
+                }
+                None => false,
+            },
+            None => self.stack.add_patterns_file(
+                path,
+                follow_symlinks,
+                Some(root),
+                buf,
+                &mut self.collection,
+                allow_macros,
+            )?,
+        };
+        stats.pattern_files += usize::from(added);
+        stats.tried_pattern_files += 1;
+        Ok(added)
+    }
+
+    /// Read the `.gitattributes` file at the repository-relative `attr_path` from the blob with `id`, possibly from the shared cache.
+    fn add_patterns_blob(
+        &mut self,
+        id: &gix_hash::oid,
+        attr_path: PathBuf,
+        objects: &dyn gix_object::Find,
+        buf: &mut Vec<u8>,
+        allow_macros: bool,
+        stats: &mut Statistics,
+    ) -> std::io::Result<()> {
+        match self.shared_cache.as_ref() {
+            Some(cache) => {
+                let list =
+                    cache
+                        .attributes()
+                        .list_from_blob(id, attr_path, objects, buf, &mut stats.shared_cache_hits)?;
+                self.stack.add_pattern_list(list, &mut self.collection, allow_macros);
+            }
+            None => {
+                let blob = objects
+                    .find_blob(id, buf)
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+                self.stack.add_patterns_buffer(
+                    blob.data,
+                    attr_path,
+                    Some(Path::new("")),
+                    &mut self.collection,
+                    allow_macros,
+                );
+            }
+        }
+        stats.patterns_buffers += 1;
+        Ok(())
+    }
+
     pub(crate) fn matching_attributes(
         &self,
         relative_path: &BStr,
@@ -1,11 +1,14 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use bstr::{BStr, ByteSlice};
 use gix_glob::pattern::Case;
 use gix_object::FindExt;
 
 use crate::{
-    stack::state::{Ignore, IgnoreMatchGroup},
+    stack::{
+        state::{Ignore, IgnoreMatchGroup},
+        SharedCache,
+    },
     PathIdMapping,
 };
 
@@ -47,6 +50,8 @@ pub struct Statistics {
     pub pattern_files: usize,
     /// Amount of pattern files we tried to find on disk.
     pub tried_pattern_files: usize,
+    /// Amount of pattern files or buffers that were obtained from a [`SharedCache`] instead of parsing them.
+    pub shared_cache_hits: usize,
 }
 
 impl Ignore {
@@ -69,8 +74,15 @@ impl Ignore {
             exclude_file_name_for_directories: exclude_file_name_for_directories
                 .map_or_else(|| ".gitignore".into(), ToOwned::to_owned),
             source,
+            shared_cache: None,
         }
     }
+
+    /// Use `cache` to obtain parsed ignore files, or stop using a shared cache if `None`.
+    pub fn set_shared_cache(&mut self, cache: Option<SharedCache>) -> &mut Self {
+        self.shared_cache = cache;
+        self
+    }
 }
 
 impl Ignore {
@@ -175,44 +187,47 @@ impl Ignore {
         let ignore_path_relative = gix_path::join_bstr_unix_pathsep(rela_dir, ".gitignore");
         let ignore_file_in_index = id_mappings.binary_search_by(|t| t.0.as_bstr().cmp(ignore_path_relative.as_ref()));
         match self.source {
-            Source::IdMapping => {
-                match ignore_file_in_index {
-                    Ok(idx) => {
-                        let ignore_blob = objects
-                            .find_blob(&id_mappings[idx].1, buf)
-                            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
-                        let ignore_path = gix_path::from_bstring(ignore_path_relative.into_owned());
-                        self.stack
-                            .add_patterns_buffer(ignore_blob.data, ignore_path, Some(Path::new("")));
-                        stats.patterns_buffers += 1;
-                    }
-                    Err(_) => {
-                        // Need one stack level per component so push and pop matches.
-                        self.stack.patterns.push(Default::default());
-                    }
+            Source::IdMapping => match ignore_file_in_index {
+                Ok(idx) => {
+                    let ignore_path = gix_path::from_bstring(ignore_path_relative.into_owned());
+                    self.add_patterns_blob(&id_mappings[idx].1, ignore_path, objects, buf, stats)?;
                 }
-            }
+                Err(_) => {
+                    // Need one stack level per component so push and pop matches.
+                    self.stack.patterns.push(Default::default());
+                }
+            },
             Source::WorktreeThenIdMappingIfNotSkipped => {
                 let follow_symlinks = ignore_file_in_index.is_err();
-                let added = gix_glob::search::add_patterns_file(
-                    &mut self.stack.patterns,
-                    dir.join(".gitignore"),
-                    follow_symlinks,
-                    Some(root),
-                    buf,
-                )?;
+                let added = match self.shared_cache.as_ref() {
+                    Some(cache) => match cache.ignore().list_from_file(
+                        dir.join(".gitignore"),
+                        Some(root),
+                        follow_symlinks,
+                        buf,
+                        &mut stats.shared_cache_hits,
+                    )? {
+                        Some(list) => {
+                            self.stack.patterns.push(list);
+                            true
+                        }
+                        None => false,
+                    },
+                    None => gix_glob::search::add_patterns_file(
+                        &mut self.stack.patterns,
+                        dir.join(".gitignore"),
+                        follow_symlinks,
+                        Some(root),
+                        buf,
+                    )?,
+                };
                 stats.pattern_files += usize::from(added);
                 stats.tried_pattern_files += 1;
                 if !added {
                     match ignore_file_in_index {
                         Ok(idx) => {
-                            let ignore_blob = objects
-                                .find_blob(&id_mappings[idx].1, buf)
-                                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
                             let ignore_path = gix_path::from_bstring(ignore_path_relative.into_owned());
-                            self.stack
-                                .add_patterns_buffer(ignore_blob.data, ignore_path, Some(Path::new("")));
-                            stats.patterns_buffers += 1;
+                            self.add_patterns_blob(&id_mappings[idx].1, ignore_path, objects, buf, stats)?;
                         }
                         Err(_) => {
                             // Need one stack level per component so push and pop matches.
@@ -224,4 +239,33 @@ impl Ignore {
         }
         Ok(())
     }
+
+    /// Read the ignore file at the repository-relative `ignore_path` from the blob with `id`, possibly from the shared cache.
+    fn add_patterns_blob(
+        &mut self,
+        id: &gix_hash::oid,
+        ignore_path: PathBuf,
+        objects: &dyn gix_object::Find,
+        buf: &mut Vec<u8>,
+        stats: &mut Statistics,
+    ) -> std::io::Result<()> {
+        match self.shared_cache.as_ref() {
+            Some(cache) => {
+                let list =
+                    cache
+                        .ignore()
+                        .list_from_blob(id, ignore_path, objects, buf, &mut stats.shared_cache_hits)?;
+                self.stack.patterns.push(list);
+            }
+            None => {
+                let ignore_blob = objects
+                    .find_blob(id, buf)
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+                self.stack
+                    .add_patterns_buffer(ignore_blob.data, ignore_path, Some(Path::new("")));
+            }
+        }
+        stats.patterns_buffers += 1;
+        Ok(())
+    }
 }
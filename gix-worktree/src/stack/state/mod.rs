@@ -25,6 +25,8 @@ pub struct Attributes {
     collection: gix_attributes::search::MetadataCollection,
     /// Where to read `.gitattributes` data from.
     source: attributes::Source,
+    /// If set, parsed `.gitattributes` files are obtained from and stored in this cache.
+    shared_cache: Option<super::SharedCache>,
 }
 
 /// State related to the exclusion of files, supporting static overrides and globals, along with a stack of dynamically read
@@ -47,6 +49,8 @@ pub struct Ignore {
     pub(crate) exclude_file_name_for_directories: BString,
     /// Where to read ignore files from
     source: ignore::Source,
+    /// If set, parsed ignore files are obtained from and stored in this cache.
+    shared_cache: Option<super::SharedCache>,
 }
 
 ///
@@ -176,3 +176,71 @@ fn check_against_baseline() -> crate::Result {
     }
     Ok(())
 }
+
+#[test]
+fn shared_cache_avoids_parsing_pattern_files_again() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only_standalone("make_ignore_and_attributes_setup.sh")?;
+    let worktree_dir = dir.join("repo");
+    let git_dir = worktree_dir.join(".git");
+    let mut buf = Vec::new();
+    let user_exclude_path = dir.join("user.exclude");
+
+    let case = probe_case()?;
+    let mut index = gix_index::File::at(git_dir.join("index"), gix_hash::Kind::Sha1, false, Default::default())?;
+    let odb = gix_odb::at(git_dir.join("objects"))?;
+    let state = gix_worktree::stack::State::for_add(
+        Default::default(),
+        gix_worktree::stack::state::Ignore::new(
+            gix_ignore::Search::from_overrides(["!force-include"]),
+            gix_ignore::Search::from_git_dir(&git_dir, Some(user_exclude_path), &mut buf)?,
+            None,
+            Source::WorktreeThenIdMappingIfNotSkipped,
+        ),
+    );
+    let paths_storage = index.take_path_backing();
+    let attribute_files_in_index = state.id_mappings_from_index(&index, &paths_storage, case);
+    let baseline = std::fs::read(git_dir.parent().unwrap().join("git-check-ignore.baseline"))?;
+
+    let shared_cache = gix_worktree::stack::SharedCache::default();
+    let mut previous_matches = None;
+    for (round, shared_cache) in [None, Some(shared_cache.clone()), Some(shared_cache)]
+        .into_iter()
+        .enumerate()
+    {
+        let mut cache = Stack::new(
+            &worktree_dir,
+            state.clone(),
+            case,
+            buf.clone(),
+            attribute_files_in_index.clone(),
+        );
+        cache.set_shared_cache(shared_cache);
+
+        let mut matches = Vec::new();
+        for (relative_entry, _source_and_line) in (IgnoreExpectations {
+            lines: baseline.lines(),
+        }) {
+            let relative_path = gix_path::from_byte_slice(relative_entry);
+            let is_dir = worktree_dir.join(relative_path).metadata().ok().map(metadata_to_mode);
+            let platform = cache.at_entry(relative_entry, is_dir, &odb)?;
+            matches.push(platform.matching_exclude_pattern().map(|m| m.pattern.to_string()));
+        }
+
+        let stats = cache.statistics().ignore;
+        if round == 2 {
+            assert_eq!(
+                stats.shared_cache_hits,
+                stats.pattern_files + stats.patterns_buffers,
+                "the second stack using the shared cache doesn't have to parse anything"
+            );
+            assert_ne!(stats.shared_cache_hits, 0);
+        } else if round == 0 {
+            assert_eq!(stats.shared_cache_hits, 0, "there is no shared cache");
+        }
+        if let Some(previous_matches) = previous_matches.take() {
+            assert_eq!(matches, previous_matches, "cached pattern lists match the same way");
+        }
+        previous_matches = Some(matches);
+    }
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::bstr::BString;
+
+/// All possible values of `branch.autoSetupMerge`, which controls if tracking is set up for new branches.
+#[derive(Default, Copy, Clone, PartialOrd, PartialEq, Ord, Eq, Hash, Debug)]
+pub enum AutoSetupMerge {
+    /// Never set up tracking.
+    Never,
+    /// Set up tracking if the start point is a remote tracking branch, which corresponds to `true`.
+    #[default]
+    RemoteTrackingBranches,
+    /// Set up tracking if the start point is a remote tracking branch or a local branch.
+    Always,
+    /// Copy the tracking configuration of the start point if it is a local branch.
+    Inherit,
+    /// Like [`RemoteTrackingBranches`](AutoSetupMerge::RemoteTrackingBranches), but only if the branch on the remote
+    /// has the same name as the new branch.
+    Simple,
+}
+
+/// All possible values of `branch.autoSetupRebase`, which controls if branches that track another branch are set up
+/// to rebase onto it instead of merging it when pulling.
+#[derive(Default, Copy, Clone, PartialOrd, PartialEq, Ord, Eq, Hash, Debug)]
+pub enum AutoSetupRebase {
+    /// Never set up rebasing.
+    #[default]
+    Never,
+    /// Set up rebasing for branches that track other local branches.
+    Local,
+    /// Set up rebasing for branches that track remote tracking branches.
+    Remote,
+    /// Set up rebasing for all branches that track another branch.
+    Always,
+}
+
+/// The tracking configuration of a branch, as written to `branch.<name>.remote`, `branch.<name>.merge` and
+/// `branch.<name>.rebase`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tracking {
+    /// The name of the remote to fetch from, or `.` if the branch tracks another local branch.
+    pub remote: BString,
+    /// The full name of the branch on `remote` to merge with.
+    pub merge: gix_ref::FullName,
+    /// If `true`, the branch is rebased onto `merge` instead of merging it when pulling.
+    pub rebase: bool,
+}
+
+impl Tracking {
+    /// Write the tracking configuration of the branch named `short_branch_name`, like `main`, to `config`,
+    /// replacing all values that are already present.
+    pub fn write_to(
+        &self,
+        short_branch_name: &crate::bstr::BStr,
+        config: &mut gix_config::File<'static>,
+    ) -> Result<(), gix_config::parse::section::header::Error> {
+        use crate::config::tree::Branch;
+
+        let mut section = config.section_mut_or_create_new("branch", Some(short_branch_name))?;
+        for key in [Branch::REMOTE.name, Branch::MERGE.name, Branch::REBASE.name] {
+            while section.remove(key).is_some() {}
+        }
+        let key =
+            |name: &'static str| -> gix_config::parse::section::ValueName<'static> { name.try_into().expect("valid") };
+        section.push(key(Branch::REMOTE.name), Some(self.remote.as_ref()));
+        section.push(key(Branch::MERGE.name), Some(self.merge.as_bstr()));
+        if self.rebase {
+            section.push(key(Branch::REBASE.name), Some("true".into()));
+        }
+        Ok(())
+    }
+}
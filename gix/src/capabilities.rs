@@ -0,0 +1,96 @@
+//! Report which git features a repository uses, and how well they are supported, to pre-flight compatibility.
+#![allow(clippy::empty_docs)]
+use crate::bstr::BString;
+
+/// A git feature that is used by a repository and may affect what `gix` can do with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Feature {
+    /// The version of the repository format as set in `core.repositoryFormatVersion`, which is `0` if unset.
+    RepositoryFormatVersion(u64),
+    /// The hash function used for objects as set in `extensions.objectFormat`, like `sha256`.
+    ObjectFormat(BString),
+    /// Per-worktree configuration in `config.worktree` files is enabled with `extensions.worktreeConfig`.
+    WorktreeConfig,
+    /// The backend used to store references as set in `extensions.refStorage`, like `reftable`.
+    ///
+    /// It's also reported as `reftable` if there is a `reftable` directory even without this extension.
+    RefStorage(BString),
+    /// The repository is a partial clone, with missing objects available from the promisor `remote` as set in `extensions.partialClone`.
+    PartialClone {
+        /// The name of the remote to fetch missing objects from.
+        remote: BString,
+    },
+    /// Objects must never be deleted as set in `extensions.preciousObjects`, typically because they are shared with other repositories.
+    PreciousObjects,
+    /// An extension set in the `extensions` section which isn't known.
+    UnknownExtension {
+        /// The lower-cased name of the extension, like `compatobjectformat` for `extensions.compatObjectFormat`.
+        name: BString,
+    },
+    /// The version of the index file.
+    #[cfg(feature = "index")]
+    IndexVersion(gix_index::Version),
+    /// A sparse checkout is configured with `core.sparseCheckout`.
+    SparseCheckout {
+        /// If `true`, only directories are used as patterns as configured with `core.sparseCheckoutCone`.
+        cone: bool,
+    },
+    /// The index is sparse, i.e. it contains directory entries for excluded directories.
+    #[cfg(feature = "index")]
+    SparseIndex,
+}
+
+/// The level of support for a [`Feature`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Support {
+    /// The feature is supported in full.
+    Full,
+    /// The feature is supported by some operations, but not by others, as explained in `note`.
+    Partial {
+        /// What is not supported.
+        note: &'static str,
+    },
+    /// The feature isn't supported, and operations that depend on it will fail or may produce incorrect results.
+    None,
+}
+
+/// A [`Feature`] along with its level of [`Support`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item {
+    /// The feature that is used by the repository.
+    pub feature: Feature,
+    /// How well it is supported.
+    pub support: Support,
+}
+
+/// The result of [`Repository::capabilities()`](crate::Repository::capabilities()).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// All features used by the repository in the order they were discovered.
+    pub items: Vec<Item>,
+}
+
+impl Outcome {
+    /// Return `true` if all features used by the repository are fully supported.
+    pub fn is_fully_supported(&self) -> bool {
+        self.items.iter().all(|item| item.support == Support::Full)
+    }
+
+    /// Return all items whose features are not fully supported.
+    pub fn limitations(&self) -> impl Iterator<Item = &Item> + '_ {
+        self.items.iter().filter(|item| item.support != Support::Full)
+    }
+}
+
+/// The error returned by [`Repository::capabilities()`](crate::Repository::capabilities()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    ConfigBoolean(#[from] crate::config::boolean::Error),
+    #[error(transparent)]
+    ConfigUnsignedInteger(#[from] crate::config::unsigned_integer::Error),
+    #[cfg(feature = "index")]
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+}
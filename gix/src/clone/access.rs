@@ -23,7 +23,8 @@ impl PrepareFetch {
     /// Set the remote's name to the given value after it was configured using the function provided via
     /// [`configure_remote()`](Self::configure_remote()).
     ///
-    /// If not set here, it defaults to `origin` or the value of `clone.defaultRemoteName`.
+    /// If not set here, it defaults to the value of `clone.defaultRemoteName`, or `origin` if it isn't set.
+    /// Configured names are validated in the same way, and ignored if they are invalid and configuration is lenient.
     pub fn with_remote_name(mut self, name: impl Into<BString>) -> Result<Self, crate::remote::name::Error> {
         self.remote_name = Some(crate::remote::name::validated(name)?);
         Ok(self)
@@ -35,6 +36,57 @@ impl PrepareFetch {
         self
     }
 
+    /// Set the kind of tags to fetch with `tags`, which is also persisted as `remote.<name>.tagOpt` in the configuration of
+    /// the new repository, so future fetches will do the same.
+    ///
+    /// Use [`Tags::None`](crate::remote::fetch::Tags::None) to not fetch any tags at all, like `git clone --no-tags`.
+    /// This overrides the tags configured via [`configure_remote()`](Self::configure_remote()). If unset, all tags are
+    /// fetched unless configured otherwise in `configure_remote()`.
+    pub fn with_tags(mut self, tags: crate::remote::fetch::Tags) -> Self {
+        self.fetch_tags = Some(tags);
+        self
+    }
+
+    /// Control how objects are obtained if the url to clone from is a local path, with `local`.
+    ///
+    /// By default, objects will be hardlinked, or copied if that's not possible.
+    pub fn with_local(mut self, local: crate::clone::Local) -> Self {
+        self.local = local;
+        self
+    }
+
+    /// If `enabled`, keep the partially received pack on disk if the fetch fails, for instance due to a connection loss,
+    /// so that objects in it can be reused the next time [`fetch_only()`](Self::fetch_only()) is called.
+    ///
+    /// See [`Prepare::with_resumable()`](crate::remote::fetch::Prepare::with_resumable()) for details, and note that
+    /// the repository must be kept around between attempts, either by retrying with the same instance or by
+    /// [persisting](Self::persist()) it and fetching into it later.
+    pub fn with_resumable(mut self, enabled: bool) -> Self {
+        self.resumable = enabled;
+        self
+    }
+
+    /// If `enabled`, download and unbundle the bundles the remote advertises via `bundle-uri` before fetching what's missing
+    /// on top of them, which can take load off the remote for large repositories. If `false`, bundles are never used.
+    ///
+    /// See [`Prepare::with_bundle_uri()`](crate::remote::fetch::Prepare::with_bundle_uri()) for details. If unset,
+    /// `transfer.bundleURI` decides, which is off by default.
+    pub fn with_bundle_uri(mut self, enabled: bool) -> Self {
+        self.bundle_uri = Some(enabled);
+        self
+    }
+
+    /// If `enabled`, submodules should be cloned shallowly with a depth of 1, similar to `git clone --shallow-submodules`,
+    /// unless a submodule overrides it with `submodule.<name>.shallow`.
+    ///
+    /// This clone doesn't clone submodules itself, so callers who do can obtain the value from
+    /// [`PrepareCheckout::shallow_submodules()`](crate::clone::PrepareCheckout::shallow_submodules())
+    /// and pass it to [`Submodule::clone_shallow()`](crate::Submodule::clone_shallow()).
+    pub fn with_shallow_submodules(mut self, enabled: bool) -> Self {
+        self.shallow_submodules = enabled;
+        self
+    }
+
     /// Apply the given configuration `values` right before readying the actual fetch from the remote.
     /// The configuration is marked with [source API](gix_config::Source::Api), and will not be written back, it's
     /// retained only in memory.
@@ -48,6 +100,8 @@ impl PrepareFetch {
     ///
     /// Note that `name` should be a partial name like `main` or `feat/one`, but can be a full ref name.
     /// If a branch on the remote matches, it will automatically be retrieved even without a refspec.
+    /// If it matches a tag or any other reference that isn't a branch, `HEAD` will be detached at the commit it points to,
+    /// similar to `git clone --branch <tag>`.
     pub fn with_ref_name<'a, Name, E>(mut self, name: Option<Name>) -> Result<Self, E>
     where
         Name: TryInto<&'a gix_ref::PartialNameRef, Error = E>,
@@ -55,6 +109,15 @@ impl PrepareFetch {
         self.ref_name = name.map(TryInto::try_into).transpose()?.map(ToOwned::to_owned);
         Ok(self)
     }
+
+    /// Fetch `revision` in addition to what's fetched anyway and detach `HEAD` at the commit it points to,
+    /// similar to `git clone --revision`. No local branch is created in this case.
+    ///
+    /// This takes precedence over the [reference name](Self::with_ref_name()) when setting up `HEAD`.
+    pub fn with_revision(mut self, revision: Option<impl Into<crate::clone::Revision>>) -> Self {
+        self.revision = revision.map(Into::into);
+        self
+    }
 }
 
 /// Consumption
@@ -68,7 +131,7 @@ impl PrepareFetch {
 impl Drop for PrepareFetch {
     fn drop(&mut self) {
         if let Some(repo) = self.repo.take() {
-            std::fs::remove_dir_all(repo.work_dir().unwrap_or_else(|| repo.path())).ok();
+            self.cleanup.remove(&repo);
         }
     }
 }
@@ -24,6 +24,8 @@ pub mod main_worktree {
         #[error(transparent)]
         WriteIndex(#[from] gix_index::file::write::Error),
         #[error(transparent)]
+        LockTimeoutConfiguration(#[from] crate::config::lock_timeout::Error),
+        #[error(transparent)]
         CheckoutOptions(#[from] crate::config::checkout_options::Error),
         #[error(transparent)]
         IndexCheckout(#[from] gix_worktree_state::checkout::Error),
@@ -35,6 +37,15 @@ pub mod main_worktree {
         FindHead(#[from] crate::reference::find::existing::Error),
         #[error("The HEAD reference could not be located")]
         PeelHeadToId(#[from] crate::head::peel::Error),
+        #[error(transparent)]
+        Hook(#[from] crate::repository::hook_command::Error),
+        #[error("Could not run the post-checkout hook at '{}'", path.display())]
+        SpawnPostCheckoutHook { source: std::io::Error, path: PathBuf },
+        #[error("The post-checkout hook at '{}' failed with {status}", path.display())]
+        PostCheckoutHook {
+            status: std::process::ExitStatus,
+            path: PathBuf,
+        },
     }
 
     /// The progress ids used in [`PrepareCheckout::main_worktree()`].
@@ -65,6 +76,13 @@ pub mod main_worktree {
         /// Note that this is a no-op if the remote was empty, leaving this repository empty as well. This can be validated by checking
         /// if the `head()` of the returned repository is *not* unborn.
         ///
+        /// ### Hooks
+        ///
+        /// Like `git clone`, the `post-checkout` hook is run once the worktree was written, but only if the repository is
+        /// [fully trusted](gix_sec::Trust::Full). It's looked up in `core.hooksPath` as configured in trusted configuration files,
+        /// or in the `hooks` directory of the repository otherwise.
+        /// If the hook fails, its error is returned, but the repository and its checked out worktree are kept on disk.
+        ///
         /// # Panics
         ///
         /// If called after it was successful. The reason here is that it auto-deletes the contained repository,
@@ -101,8 +119,11 @@ pub mod main_worktree {
                 None => repo.head()?.try_peel_to_id_in_place()?,
             };
 
-            let root_tree = match root_tree_id {
-                Some(id) => id.object().expect("downloaded from remote").peel_to_tree()?.id,
+            let (head_id, root_tree) = match root_tree_id {
+                Some(id) => (
+                    id.detach(),
+                    id.object().expect("downloaded from remote").peel_to_tree()?.id,
+                ),
                 None => {
                     return Ok((
                         self.repo.take().expect("still present"),
@@ -142,10 +163,110 @@ pub mod main_worktree {
             files.show_throughput(start);
             bytes.show_throughput(start);
 
-            index.write(Default::default())?;
-            Ok((self.repo.take().expect("still present").clone(), outcome))
+            index.write(gix_index::write::Options {
+                lock_mode: repo.config.other_lock_timeout()?,
+                ..Default::default()
+            })?;
+            // The checkout is complete, so the repository must not be deleted on drop even if the hook fails.
+            let repo = self.repo.take().expect("still present");
+            run_post_checkout_hook(&repo, head_id)?;
+            Ok((repo, outcome))
         }
     }
+
+    /// Run the `post-checkout` hook with the arguments `git clone` would use, if it exists and the repository is trusted.
+    fn run_post_checkout_hook(repo: &Repository, new_head: gix_hash::ObjectId) -> Result<(), Error> {
+        let Some((path, mut cmd)) = repo.hook_command("post-checkout")? else {
+            return Ok(());
+        };
+        let status = cmd
+            .args([
+                gix_hash::ObjectId::null(repo.object_hash()).to_string(),
+                new_head.to_string(),
+                // The hook is run after a branch checkout.
+                "1".into(),
+            ])
+            .status()
+            .map_err(|source| Error::SpawnPostCheckoutHook {
+                source,
+                path: path.clone(),
+            })?;
+        if !status.success() {
+            return Err(Error::PostCheckoutHook { status, path });
+        }
+        Ok(())
+    }
+}
+
+///
+#[cfg(feature = "revision")]
+pub mod detach_at {
+    /// The error returned by [`PrepareCheckout::detach_at()`](crate::clone::PrepareCheckout::detach_at()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        RevParse(#[from] crate::revision::spec::parse::single::Error),
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        PeelToCommit(#[from] crate::object::peel::to_kind::Error),
+        #[error(transparent)]
+        EditHead(#[from] crate::reference::edit::Error),
+    }
+}
+
+/// Modification
+impl PrepareCheckout {
+    /// Resolve `spec` in the freshly fetched repository and detach `HEAD` at the commit it points to, so it will be checked out
+    /// instead of the branch the clone would otherwise end up on, similar to `git checkout --detach <spec>` after cloning.
+    ///
+    /// `spec` can be anything that resolves to a single commit, like a tag name, a hexadecimal object id or `origin/main~2`,
+    /// but it must have been fetched. Use [`PrepareFetch::with_revision()`](crate::clone::PrepareFetch::with_revision())
+    /// to also fetch a revision that wouldn't be fetched otherwise.
+    ///
+    /// Return the id of the commit `HEAD` now points to.
+    ///
+    /// # Panics
+    ///
+    /// If called after the checkout was successful.
+    #[cfg(feature = "revision")]
+    pub fn detach_at<'a>(
+        &mut self,
+        spec: impl Into<&'a crate::bstr::BStr>,
+    ) -> Result<gix_hash::ObjectId, detach_at::Error> {
+        use gix_ref::{
+            transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+            Target,
+        };
+        let spec = spec.into();
+        let repo = self
+            .repo
+            .as_ref()
+            .expect("BUG: this method may only be called until the checkout is successful");
+        let id = repo
+            .rev_parse_single(spec)?
+            .object()?
+            .peel_to_kind(gix_object::Kind::Commit)?
+            .id;
+        let mut message = crate::bstr::BString::from("clone: detach at ");
+        message.extend_from_slice(spec);
+        repo.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message,
+                },
+                expected: PreviousValue::Any,
+                new: Target::Object(id),
+            },
+            name: "HEAD".try_into().expect("valid"),
+            deref: false,
+        })?;
+        self.ref_name = None;
+        Ok(id)
+    }
 }
 
 /// Access
@@ -160,6 +281,14 @@ impl PrepareCheckout {
             .as_ref()
             .expect("present as checkout operation isn't complete")
     }
+
+    /// Return `true` if submodules should be cloned with a depth of 1, as configured with
+    /// [`PrepareFetch::with_shallow_submodules()`](crate::clone::PrepareFetch::with_shallow_submodules()).
+    ///
+    /// Pass it to [`Submodule::clone_shallow()`](crate::Submodule::clone_shallow()) to learn how to clone each submodule.
+    pub fn shallow_submodules(&self) -> bool {
+        self.shallow_submodules
+    }
 }
 
 /// Consumption
@@ -173,7 +302,7 @@ impl PrepareCheckout {
 impl Drop for PrepareCheckout {
     fn drop(&mut self) {
         if let Some(repo) = self.repo.take() {
-            std::fs::remove_dir_all(repo.work_dir().unwrap_or_else(|| repo.path())).ok();
+            self.cleanup.remove(&repo);
         }
     }
 }
@@ -30,6 +30,11 @@ pub enum Error {
     SaveConfig(#[from] crate::remote::save::AsError),
     #[error("Failed to write repository configuration to disk")]
     SaveConfigIo(#[from] std::io::Error),
+    #[error("Failed to hardlink or copy objects from the local repository at '{}'", path.display())]
+    LocalObjects {
+        source: std::io::Error,
+        path: std::path::PathBuf,
+    },
     #[error("The remote HEAD points to a reference named {head_ref_name:?} which is invalid.")]
     InvalidHeadRef {
         source: gix_validate::reference::name::Error,
@@ -37,6 +42,10 @@ pub enum Error {
     },
     #[error("Failed to update HEAD with values from remote")]
     HeadUpdate(#[from] crate::reference::edit::Error),
+    #[error("Could not find the object to detach HEAD at")]
+    FindDetachedHeadObject(#[from] crate::object::find::existing::Error),
+    #[error("The object to detach HEAD at could not be peeled to a commit")]
+    PeelDetachedHeadToCommit(#[from] crate::object::peel::to_kind::Error),
     #[error("The remote didn't have any ref that matched '{}'", wanted.as_ref().as_bstr())]
     RefNameMissing { wanted: gix_ref::PartialName },
     #[error("The remote has {} refs for '{}', try to use a specific name: {}", candidates.len(), wanted.as_ref().as_bstr(), candidates.iter().filter_map(|n| n.to_str().ok()).collect::<Vec<_>>().join(", "))]
@@ -96,13 +105,16 @@ impl PrepareFetch {
 
         let remote_name = match self.remote_name.as_ref() {
             Some(name) => name.to_owned(),
-            None => repo
-                .config
-                .resolved
-                .string(crate::config::tree::Clone::DEFAULT_REMOTE_NAME)
-                .map(|n| crate::config::tree::Clone::DEFAULT_REMOTE_NAME.try_into_symbolic_name(n))
-                .transpose()?
-                .unwrap_or_else(|| "origin".into()),
+            None => {
+                use crate::config::cache::util::ApplyLeniency;
+                repo.config
+                    .resolved
+                    .string(crate::config::tree::Clone::DEFAULT_REMOTE_NAME)
+                    .map(|n| crate::config::tree::Clone::DEFAULT_REMOTE_NAME.try_into_symbolic_name(n))
+                    .transpose()
+                    .with_leniency(repo.config.lenient_config)?
+                    .unwrap_or_else(|| "origin".into())
+            }
         };
 
         let mut remote = repo.remote_at(self.url.clone())?;
@@ -117,9 +129,12 @@ impl PrepareFetch {
         let mut clone_fetch_tags = None;
         if let Some(f) = self.configure_remote.as_mut() {
             remote = f(remote).map_err(Error::RemoteConfiguration)?;
-        } else {
+        } else if self.fetch_tags.is_none() {
             clone_fetch_tags = remote::fetch::Tags::All.into();
         }
+        if let Some(fetch_tags) = self.fetch_tags {
+            remote = remote.with_fetch_tags(fetch_tags);
+        }
 
         let config = util::write_remote_to_local_config_file(&mut remote, remote_name.clone())?;
 
@@ -137,6 +152,15 @@ impl PrepareFetch {
         )
         .expect("valid")
         .to_owned();
+        if self.local != crate::clone::Local::Disabled
+            && self.url.scheme == gix_url::Scheme::File
+            && self.url.is_alternate_form()
+            && matches!(self.shallow, remote::fetch::Shallow::NoChange)
+        {
+            // Like `git`, make all objects available upfront so the fetch only has to update references.
+            util::copy_local_objects(repo, &gix_path::from_bstr(self.url.path.as_bstr()), self.local)?;
+        }
+
         let pending_pack: remote::fetch::Prepare<'_, '_, _> = {
             let mut connection = remote.connect(remote::Direction::Fetch).await?;
             if let Some(f) = self.configure_connection.as_mut() {
@@ -154,6 +178,17 @@ impl PrepareFetch {
                             .to_owned(),
                     );
                 }
+                if let Some(revision) = &self.revision {
+                    let spec = match revision {
+                        crate::clone::Revision::Id(id) => BString::from(id.to_string()),
+                        crate::clone::Revision::Name(name) => name.as_ref().as_bstr().to_owned(),
+                    };
+                    opts.extra_refspecs.push(
+                        gix_refspec::parse(spec.as_ref(), gix_refspec::parse::Operation::Fetch)
+                            .expect("partial names and object ids are valid refspecs")
+                            .to_owned(),
+                    );
+                }
                 opts
             };
             match connection.prepare_fetch(&mut *progress, fetch_opts.clone()).await {
@@ -190,6 +225,9 @@ impl PrepareFetch {
         if let Some(ref_name) = &self.ref_name {
             util::find_custom_refname(pending_pack.ref_map(), ref_name)?;
         }
+        if let Some(crate::clone::Revision::Name(name)) = &self.revision {
+            util::find_custom_refname(pending_pack.ref_map(), name)?;
+        }
         if pending_pack.ref_map().object_hash != repo.object_hash() {
             unimplemented!("configure repository to expect a different object hash as advertised by the server")
         }
@@ -198,23 +236,42 @@ impl PrepareFetch {
             b.insert_str(0, "clone: from ");
             b
         };
-        let outcome = pending_pack
+        let mut pending_pack = pending_pack
             .with_write_packed_refs_only(true)
             .with_reflog_message(RefLogMessage::Override {
                 message: reflog_message.clone(),
             })
             .with_shallow(self.shallow.clone())
-            .receive_inner(progress, should_interrupt)
-            .await?;
+            .with_resumable(self.resumable)
+            .with_write_fetch_head(false);
+        if let Some(enabled) = self.bundle_uri {
+            pending_pack = pending_pack.with_bundle_uri(enabled);
+        }
+        if let Some(handler) = self.sideband_handler.as_mut() {
+            pending_pack = pending_pack.with_sideband_handler(handler);
+        }
+        let outcome = pending_pack.receive_inner(progress, should_interrupt).await?;
 
         util::append_config_to_repo_config(repo, config);
-        util::update_head(
-            repo,
-            &outcome.ref_map,
-            reflog_message.as_ref(),
-            remote_name.as_ref(),
-            self.ref_name.as_ref(),
-        )?;
+        match &self.revision {
+            Some(revision) => {
+                let id = match revision {
+                    crate::clone::Revision::Id(id) => *id,
+                    crate::clone::Revision::Name(name) => util::find_custom_refname(&outcome.ref_map, name)?
+                        .0
+                        .expect("matched refs always have an object")
+                        .to_owned(),
+                };
+                util::detach_head(repo, id, reflog_message.as_ref())?;
+            }
+            None => util::update_head(
+                repo,
+                &outcome.ref_map,
+                reflog_message.as_ref(),
+                remote_name.as_ref(),
+                self.ref_name.as_ref(),
+            )?,
+        }
 
         Ok((self.repo.take().expect("still present"), outcome))
     }
@@ -234,7 +291,13 @@ impl PrepareFetch {
         Ok((
             crate::clone::PrepareCheckout {
                 repo: repo.into(),
-                ref_name: self.ref_name.clone(),
+                ref_name: if self.revision.is_some() {
+                    None
+                } else {
+                    self.ref_name.clone()
+                },
+                shallow_submodules: self.shallow_submodules,
+                cleanup: self.cleanup,
             },
             fetch_outcome,
         ))
@@ -73,7 +73,12 @@ pub fn update_head(
         Target,
     };
     let head_info = match ref_name {
-        Some(ref_name) => Some(find_custom_refname(ref_map, ref_name)?),
+        Some(ref_name) => match find_custom_refname(ref_map, ref_name)? {
+            (Some(id), Some(name)) if !name.starts_with(b"refs/heads/") => {
+                return detach_head(repo, id.to_owned(), reflog_message);
+            }
+            info => Some(info),
+        },
         None => ref_map.remote_refs.iter().find_map(|r| {
             Some(match r {
                 gix_protocol::handshake::Ref::Symbolic {
@@ -108,6 +113,10 @@ pub fn update_head(
                 head_ref_name: referent.to_owned(),
                 source: err,
             })?;
+            let (file_lock_fail, packed_refs_lock_fail) = repo
+                .config
+                .lock_timeout()
+                .map_err(crate::reference::edit::Error::from)?;
             repo.refs
                 .transaction()
                 .packed_refs(gix_ref::file::transaction::PackedRefs::DeletionsAndNonSymbolicUpdates(
@@ -137,8 +146,8 @@ pub fn update_head(
                         };
                         edits
                     },
-                    gix_lock::acquire::Fail::Immediately,
-                    gix_lock::acquire::Fail::Immediately,
+                    file_lock_fail,
+                    packed_refs_lock_fail,
                 )
                 .map_err(crate::reference::edit::Error::from)?
                 .commit(
@@ -183,6 +192,29 @@ pub fn update_head(
     Ok(())
 }
 
+/// Point `HEAD` to the commit that `id` peels to, without creating or changing any branch.
+pub fn detach_head(repo: &mut Repository, id: gix_hash::ObjectId, reflog_message: &BStr) -> Result<(), Error> {
+    use gix_ref::{
+        transaction::{PreviousValue, RefEdit},
+        Target,
+    };
+    let commit_id = repo.find_object(id)?.peel_to_kind(gix_object::Kind::Commit)?.id;
+    repo.edit_reference(RefEdit {
+        change: gix_ref::transaction::Change::Update {
+            log: LogChange {
+                mode: RefLog::AndReference,
+                force_create_reflog: false,
+                message: reflog_message.to_owned(),
+            },
+            expected: PreviousValue::Any,
+            new: Target::Object(commit_id),
+        },
+        name: "HEAD".try_into().expect("valid"),
+        deref: false,
+    })?;
+    Ok(())
+}
+
 pub(super) fn find_custom_refname<'a>(
     ref_map: &'a crate::remote::fetch::RefMap,
     ref_name: &PartialName,
@@ -275,3 +307,101 @@ fn setup_branch_config(
     }
     Ok(())
 }
+
+/// Hardlink or copy all objects of the local repository at `source` into the object database of `repo`.
+///
+/// Nothing is done if `source` isn't a repository we could open, or if it's shallow, leaving it to the fetch
+/// to obtain all objects.
+pub(super) fn copy_local_objects(
+    repo: &Repository,
+    source: &std::path::Path,
+    mode: crate::clone::Local,
+) -> Result<(), Error> {
+    let Ok(source_repo) = crate::open_opts(source, crate::open::Options::isolated()) else {
+        return Ok(());
+    };
+    if source_repo.object_hash() != repo.object_hash() || source_repo.is_shallow() {
+        return Ok(());
+    }
+    let source_objects = source_repo.common_dir().join("objects");
+    let destination_objects = repo.common_dir().join("objects");
+    let _span = gix_trace::coarse!("copy_local_objects", source = ?source_objects);
+    copy_objects_recursive(&source_objects, &source_objects, &destination_objects, mode).map_err(|err| {
+        Error::LocalObjects {
+            source: err,
+            path: source_objects.clone(),
+        }
+    })
+}
+
+fn copy_objects_recursive(
+    source_objects: &std::path::Path,
+    source_dir: &std::path::Path,
+    destination_dir: &std::path::Path,
+    mode: crate::clone::Local,
+) -> std::io::Result<()> {
+    std::fs::create_dir_all(destination_dir)?;
+    for entry in std::fs::read_dir(source_dir)? {
+        let entry = entry?;
+        if is_temporary(&entry.file_name()) {
+            continue;
+        }
+        let source_path = entry.path();
+        let destination_path = destination_dir.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_objects_recursive(source_objects, &source_path, &destination_path, mode)?;
+            continue;
+        }
+        if entry.file_name() == "alternates" && source_dir == source_objects.join("info") {
+            write_absolute_alternates(source_objects, &source_path, &destination_path)?;
+            continue;
+        }
+        if destination_path.exists() {
+            continue;
+        }
+        match mode {
+            crate::clone::Local::HardlinkOrCopy => {
+                if std::fs::hard_link(&source_path, &destination_path).is_err() {
+                    std::fs::copy(&source_path, &destination_path)?;
+                }
+            }
+            crate::clone::Local::Copy => {
+                std::fs::copy(&source_path, &destination_path)?;
+            }
+            crate::clone::Local::Disabled => unreachable!("BUG: we are never called if disabled"),
+        }
+    }
+    Ok(())
+}
+
+/// Return `true` if `name` is a file or directory which is only used while objects are written, like partial packs
+/// of interrupted fetches, lock files or the quarantine directories of pushes, which must not be copied.
+fn is_temporary(name: &std::ffi::OsStr) -> bool {
+    let name = name.to_string_lossy();
+    name.starts_with('.') || name.starts_with("tmp_") || name.starts_with("incoming-") || name.ends_with(".lock")
+}
+
+/// Alternates may be relative to the object directory they are in, so make them absolute to keep them valid.
+fn write_absolute_alternates(
+    source_objects: &std::path::Path,
+    source: &std::path::Path,
+    destination: &std::path::Path,
+) -> std::io::Result<()> {
+    let content = std::fs::read(source)?;
+    let mut out = Vec::with_capacity(content.len());
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(b"#") {
+            continue;
+        }
+        let path = gix_path::from_bstr(line.as_bstr());
+        let path = if path.is_relative() {
+            Cow::Owned(source_objects.join(path))
+        } else {
+            path
+        };
+        out.extend_from_slice(gix_path::into_bstr(path).as_ref());
+        out.push(b'\n');
+    }
+    std::fs::write(destination, out)
+}
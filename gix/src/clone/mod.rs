@@ -37,6 +37,91 @@ pub struct PrepareFetch {
     /// The name of the reference to fetch. If `None`, the reference pointed to by `HEAD` will be checked out.
     #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
     ref_name: Option<gix_ref::PartialName>,
+    /// The revision to detach `HEAD` at after fetching, which is fetched in addition to everything else.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    revision: Option<Revision>,
+    /// The kind of tags to fetch, overriding the value configured via [`configure_remote()`](Self::configure_remote()).
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    fetch_tags: Option<remote::fetch::Tags>,
+    /// How to obtain objects if the url is a local path.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    local: Local,
+    /// If `true`, keep a partially received pack so objects in it can be reused when fetching again.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    resumable: bool,
+    /// If set, overrides `transfer.bundleURI` to control if bundles advertised by the remote are used.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    bundle_uri: Option<bool>,
+    /// If `true`, submodules are meant to be cloned with a depth of 1, unless configured otherwise per submodule.
+    shallow_submodules: bool,
+    /// A function to receive messages the remote sends on a sideband channel.
+    #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    sideband_handler: Option<remote::fetch::SidebandHandlerFn<'static>>,
+    /// What to remove from disk when dropped without successfully finishing a fetch.
+    cleanup: Cleanup,
+}
+
+/// Determines what to remove from disk if a clone is dropped before it finished successfully.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Cleanup {
+    /// Remove the repository along with its worktree, as both were created by us.
+    RepositoryAndWorktree,
+    /// Remove only the repository, as its worktree existed before.
+    Repository,
+    /// Remove nothing, as the repository was provided by the caller.
+    Nothing,
+}
+
+impl Cleanup {
+    fn remove(self, repo: &crate::Repository) {
+        let dir = match self {
+            Cleanup::RepositoryAndWorktree => repo.work_dir().unwrap_or_else(|| repo.path()),
+            Cleanup::Repository => repo.path(),
+            Cleanup::Nothing => return,
+        };
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
+
+/// A revision to fetch and to detach `HEAD` at, see [`PrepareFetch::with_revision()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Revision {
+    /// The id of a commit, or of an annotated tag pointing to one.
+    ///
+    /// Note that the remote may refuse to send objects that aren't advertised.
+    Id(gix_hash::ObjectId),
+    /// The name of a reference on the remote, which may be partial and is matched like
+    /// [`PrepareFetch::with_ref_name()`] does.
+    Name(gix_ref::PartialName),
+}
+
+impl From<gix_hash::ObjectId> for Revision {
+    fn from(id: gix_hash::ObjectId) -> Self {
+        Revision::Id(id)
+    }
+}
+
+impl From<gix_ref::PartialName> for Revision {
+    fn from(name: gix_ref::PartialName) -> Self {
+        Revision::Name(name)
+    }
+}
+
+/// Determines how objects are obtained when cloning from a repository identified by a local path, like `git clone /path/to/repo`.
+///
+/// Note that this only applies to plain paths, URLs like `file:///path/to/repo` always use the transport
+/// to receive a pack, similar to what `git` does. Shallow clones also always use the transport.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Local {
+    /// Hardlink all object files of the source repository, or copy them if hardlinks aren't possible,
+    /// like when they are on different devices. This is the default, similar to `git clone --local`.
+    #[default]
+    HardlinkOrCopy,
+    /// Copy all object files of the source repository, similar to `git clone --no-hardlinks`.
+    Copy,
+    /// Do not access the source repository directly but receive a pack through the transport, similar to `git clone --no-local`.
+    Disabled,
 }
 
 /// The error returned by [`PrepareFetch::new()`].
@@ -84,19 +169,93 @@ impl PrepareFetch {
             kind,
             create_opts,
             open_opts,
+            false,
+        )
+    }
+
+    /// Create a new repository in the possibly non-empty directory at `worktree` which is ready to clone from `url`,
+    /// similar to `git init && git remote add origin <url> && git fetch` followed by setting `HEAD`.
+    ///
+    /// This is useful to turn a directory of existing files into a clone, to then compare them with or reset them to
+    /// what was fetched. `worktree` must not contain a `.git` directory yet.
+    ///
+    /// Use [`fetch_only()`](Self::fetch_only()) to leave `worktree` untouched like `git clone --no-checkout` would,
+    /// as checking out would fail if it encounters existing files. Note that there will be no index in the new repository,
+    /// so all files in the worktree appear as untracked or added until the index is created.
+    ///
+    /// If any of it fails, only the newly created `.git` directory will be removed as soon as this instance drops,
+    /// leaving `worktree` and all of its files in place.
+    #[allow(clippy::result_large_err)]
+    pub fn new_in_existing_worktree<Url, E>(
+        url: Url,
+        worktree: impl AsRef<std::path::Path>,
+        create_opts: crate::create::Options,
+        open_opts: crate::open::Options,
+    ) -> Result<Self, Error>
+    where
+        Url: TryInto<gix_url::Url, Error = E>,
+        gix_url::parse::Error: From<E>,
+    {
+        Self::new_inner(
+            url.try_into().map_err(gix_url::parse::Error::from)?,
+            worktree.as_ref(),
+            crate::create::Kind::WithWorktree,
+            create_opts,
+            open_opts,
+            true,
         )
     }
 
     #[allow(clippy::result_large_err)]
     fn new_inner(
-        mut url: gix_url::Url,
+        url: gix_url::Url,
         path: &std::path::Path,
         kind: crate::create::Kind,
         mut create_opts: crate::create::Options,
         open_opts: crate::open::Options,
+        existing_worktree: bool,
+    ) -> Result<Self, Error> {
+        create_opts.destination_must_be_empty = !existing_worktree;
+        let repo = crate::ThreadSafeRepository::init_opts(path, kind, create_opts, open_opts)?.to_thread_local();
+        Self::from_repository_inner(
+            url,
+            repo,
+            if existing_worktree {
+                Cleanup::Repository
+            } else {
+                Cleanup::RepositoryAndWorktree
+            },
+        )
+    }
+
+    /// Prepare to clone from `url` into `repo`, an already opened repository which was typically created with
+    /// [`init_opts()`](crate::init_opts()) or [`init_bare()`](crate::init_bare()) right before, to fully control
+    /// its creation, object format and configuration.
+    ///
+    /// The clone will set up the remote, fetch and update `HEAD` as usual, which is why `repo` should not have any
+    /// references or remotes yet.
+    ///
+    /// Unlike with [`new()`](Self::new()), `repo` is never removed from disk, even if the clone doesn't finish
+    /// successfully. Use [`persist()`](Self::persist()) to get it back in that case.
+    #[allow(clippy::result_large_err)]
+    pub fn from_repository<Url, E>(url: Url, repo: crate::Repository) -> Result<Self, Error>
+    where
+        Url: TryInto<gix_url::Url, Error = E>,
+        gix_url::parse::Error: From<E>,
+    {
+        Self::from_repository_inner(
+            url.try_into().map_err(gix_url::parse::Error::from)?,
+            repo,
+            Cleanup::Nothing,
+        )
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn from_repository_inner(
+        mut url: gix_url::Url,
+        mut repo: crate::Repository,
+        cleanup: Cleanup,
     ) -> Result<Self, Error> {
-        create_opts.destination_must_be_empty = true;
-        let mut repo = crate::ThreadSafeRepository::init_opts(path, kind, create_opts, open_opts)?.to_thread_local();
         url.canonicalize(repo.options.current_dir_or_empty())
             .map_err(|err| Error::CanonicalizeUrl {
                 url: url.clone(),
@@ -126,6 +285,15 @@ impl PrepareFetch {
             configure_connection: None,
             shallow: remote::fetch::Shallow::NoChange,
             ref_name: None,
+            revision: None,
+            fetch_tags: None,
+            local: Local::default(),
+            resumable: false,
+            bundle_uri: None,
+            shallow_submodules: false,
+            #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+            sideband_handler: None,
+            cleanup,
         })
     }
 }
@@ -140,6 +308,10 @@ pub struct PrepareCheckout {
     pub(self) repo: Option<crate::Repository>,
     /// The name of the reference to check out. If `None`, the reference pointed to by `HEAD` will be checked out.
     pub(self) ref_name: Option<gix_ref::PartialName>,
+    /// If `true`, submodules are meant to be cloned with a depth of 1, unless configured otherwise per submodule.
+    pub(self) shallow_submodules: bool,
+    /// What to remove from disk when dropped without successfully checking out.
+    pub(self) cleanup: Cleanup,
 }
 
 // This module encapsulates functionality that works with both feature toggles. Can be combined with `fetch`
@@ -170,6 +342,18 @@ mod access_feat {
             self.fetch_options = opts;
             self
         }
+
+        /// Call `handler` with each message the remote sends on a sideband channel while preparing and sending the pack,
+        /// like `Enumerating objects: 42, done.` or hints meant for the user.
+        ///
+        /// See [`Prepare::with_sideband_handler()`](crate::remote::fetch::Prepare::with_sideband_handler()) for details.
+        pub fn with_sideband_handler(
+            mut self,
+            handler: impl FnMut(crate::remote::fetch::SidebandMessage<'_>) + 'static,
+        ) -> Self {
+            self.sideband_handler = Some(Box::new(handler));
+            self
+        }
     }
 }
 
@@ -7,8 +7,8 @@ use crate::{
     config,
     config::{
         boolean,
-        cache::util::{ApplyLeniency, ApplyLeniencyDefaultValue},
-        tree::{Core, Key},
+        cache::util::{ApplyLeniency, ApplyLeniencyDefault, ApplyLeniencyDefaultValue},
+        tree::{gitoxide, Branch, Core, Key, Log, Push},
         Cache,
     },
     remote,
@@ -198,6 +198,16 @@ impl Cache {
             .unwrap_or_default()
     }
 
+    /// Return `true` if the session id should be sent to servers that support it. Lenient and defaults to `false`.
+    #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+    pub(crate) fn advertise_session_id(&self) -> bool {
+        use config::tree::Transfer;
+        self.resolved
+            .boolean(Transfer::ADVERTISE_SID)
+            .and_then(Result::ok)
+            .unwrap_or_default()
+    }
+
     pub(crate) fn personas(&self) -> &identity::Personas {
         self.personas
             .get_or_init(|| identity::Personas::from_config_and_env(&self.resolved))
@@ -214,6 +224,46 @@ impl Cache {
             .get_or_try_init(|| remote::url::SchemePermission::from_config(&self.resolved, self.filter_config_section))
     }
 
+    pub(crate) fn push_default(&self) -> Result<crate::push::Default, config::key::GenericErrorWithValue> {
+        self.resolved
+            .string(Push::DEFAULT)
+            .map_or(Ok(Default::default()), |value| {
+                Push::DEFAULT
+                    .try_into_default(value)
+                    .with_lenient_default(self.lenient_config)
+            })
+    }
+
+    pub(crate) fn date_format(&self) -> Result<crate::log::DateFormat, config::key::GenericErrorWithValue> {
+        self.resolved.string(Log::DATE).map_or(Ok(Default::default()), |value| {
+            Log::DATE
+                .try_into_date_format(value)
+                .with_lenient_default(self.lenient_config)
+        })
+    }
+
+    pub(crate) fn auto_setup_merge(&self) -> Result<crate::branch::AutoSetupMerge, config::key::GenericErrorWithValue> {
+        self.resolved
+            .boolean(Branch::AUTO_SETUP_MERGE)
+            .map_or(Ok(Default::default()), |value| {
+                Branch::AUTO_SETUP_MERGE
+                    .try_into_auto_setup_merge(value)
+                    .with_lenient_default(self.lenient_config)
+            })
+    }
+
+    pub(crate) fn auto_setup_rebase(
+        &self,
+    ) -> Result<crate::branch::AutoSetupRebase, config::key::GenericErrorWithValue> {
+        self.resolved
+            .string(Branch::AUTO_SETUP_REBASE)
+            .map_or(Ok(Default::default()), |value| {
+                Branch::AUTO_SETUP_REBASE
+                    .try_into_auto_setup_rebase(value)
+                    .with_lenient_default(self.lenient_config)
+            })
+    }
+
     pub(crate) fn may_use_commit_graph(&self) -> Result<bool, config::boolean::Error> {
         const DEFAULT: bool = true;
         self.resolved.boolean("core.commitGraph").map_or(Ok(DEFAULT), |res| {
@@ -243,6 +293,18 @@ impl Cache {
         Ok((out[0], out[1]))
     }
 
+    /// The way to acquire locks for files other than references, like the index, the shallow file or configuration files.
+    pub(crate) fn other_lock_timeout(&self) -> Result<gix_lock::acquire::Fail, config::lock_timeout::Error> {
+        let key = &gitoxide::Core::LOCK_TIMEOUT;
+        Ok(self
+            .resolved
+            .integer_filter(key, &mut self.filter_config_section.clone())
+            .map(|res| key.try_into_lock_timeout(res))
+            .transpose()
+            .with_leniency(self.lenient_config)?
+            .unwrap_or(Fail::Immediately))
+    }
+
     /// The path to the user-level excludes file to ignore certain files in the worktree.
     #[cfg(feature = "excludes")]
     pub(crate) fn excludes_file(&self) -> Option<Result<PathBuf, gix_config::path::interpolate::Error>> {
@@ -281,7 +343,6 @@ impl Cache {
 
     #[cfg(feature = "index")]
     pub(crate) fn stat_options(&self) -> Result<gix_index::entry::stat::Options, config::stat_options::Error> {
-        use crate::config::tree::gitoxide;
         Ok(gix_index::entry::stat::Options {
             trust_ctime: boolean(self, "core.trustCTime", &Core::TRUST_C_TIME, true)?,
             use_nsec: boolean(self, "gitoxide.core.useNsec", &gitoxide::Core::USE_NSEC, false)?,
@@ -335,7 +396,6 @@ impl Cache {
         repo: &crate::Repository,
         attributes_source: gix_worktree::stack::state::attributes::Source,
     ) -> Result<gix_worktree_state::checkout::Options, config::checkout_options::Error> {
-        use crate::config::tree::gitoxide;
         let git_dir = repo.git_dir();
         let thread_limit = self.apply_leniency(
             self.resolved
@@ -367,14 +427,19 @@ impl Cache {
             filter_process_delay,
             validate: self.protect_options()?,
             filters,
-            attributes: self
-                .assemble_attribute_globals(git_dir, attributes_source, self.attributes)?
-                .0,
+            attributes: {
+                let mut attributes = self
+                    .assemble_attribute_globals(git_dir, attributes_source, self.attributes)?
+                    .0;
+                attributes.set_shared_cache(repo.worktree_cache.clone());
+                attributes
+            },
             fs: capabilities,
             thread_limit,
             destination_is_initially_empty: false,
             overwrite_existing: false,
             keep_going: false,
+            non_regular_files: Default::default(),
             stat_options: self.stat_options().map_err(|err| match err {
                 config::stat_options::Error::ConfigCheckStat(err) => {
                     config::checkout_options::Error::ConfigCheckStat(err)
@@ -447,7 +512,6 @@ impl Cache {
     pub(crate) fn pathspec_defaults(
         &self,
     ) -> Result<gix_pathspec::Defaults, gix_pathspec::defaults::from_environment::Error> {
-        use crate::config::tree::gitoxide;
         let res = gix_pathspec::Defaults::from_environment(&mut |name| {
             let key = [
                 &gitoxide::Pathspec::ICASE,
@@ -498,10 +562,7 @@ impl Cache {
     ///
     /// We never fail for here even if the permission is set to deny as we `gix-config` will fail later
     /// if it actually wants to use the home directory - we don't want to fail prematurely.
-    #[cfg(any(
-        feature = "blocking-http-transport-reqwest",
-        feature = "blocking-http-transport-curl"
-    ))]
+    #[cfg(feature = "blocking-network-client")]
     pub(crate) fn home_dir(&self) -> Option<PathBuf> {
         home_dir(self.environment)
     }
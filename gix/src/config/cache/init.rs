@@ -9,7 +9,7 @@ use crate::{
     config,
     config::{
         cache::util::ApplyLeniency,
-        tree::{gitoxide, Core, Gitoxide, Http},
+        tree::{gitoxide, Core, Gitoxide, Http, Init},
         Cache,
     },
     open,
@@ -335,6 +335,15 @@ fn apply_environment_overrides(
                 (env(key), key.name)
             }][..],
         ),
+        (
+            "init",
+            None,
+            git_prefix,
+            &[{
+                let key = &Init::TEMPLATE_DIR;
+                (env(key), key.name)
+            }],
+        ),
         (
             "http",
             None,
@@ -456,6 +465,10 @@ fn apply_environment_overrides(
                     let key = &gitoxide::Core::EXTERNAL_COMMAND_STDERR;
                     (env(key), key.name)
                 },
+                {
+                    let key = &gitoxide::Core::REFLOG_ACTION;
+                    (env(key), key.name)
+                },
             ],
         ),
         (
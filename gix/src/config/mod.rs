@@ -67,6 +67,40 @@ pub mod set_value {
     }
 }
 
+///
+pub mod set_worktree_value {
+    use std::path::PathBuf;
+
+    /// The error produced when calling [`Repository::set_worktree_config_value()`](crate::Repository::set_worktree_config_value()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        SetValue(#[from] super::set_value::Error),
+        #[error(transparent)]
+        ConfigBoolean(#[from] super::boolean::Error),
+        #[error(transparent)]
+        LockTimeout(#[from] super::lock_timeout::Error),
+        #[error("Could not determine if there are linked worktrees")]
+        ListWorktrees(#[source] std::io::Error),
+        #[error("The repository has linked worktrees, so per-worktree configuration requires `extensions.worktreeConfig` to be enabled")]
+        ExtensionRequired,
+        #[error(transparent)]
+        Read(#[from] gix_config::file::init::from_paths::Error),
+        #[error("Could not lock the configuration file at '{}' for writing", path.display())]
+        Lock {
+            source: gix_lock::acquire::Error,
+            path: PathBuf,
+        },
+        #[error("Could not write the configuration file at '{}'", path.display())]
+        Write { source: std::io::Error, path: PathBuf },
+        #[error(transparent)]
+        Commit(#[from] gix_lock::commit::Error<gix_lock::File>),
+        #[error(transparent)]
+        Reload(#[from] super::Error),
+    }
+}
+
 /// The error returned when failing to initialize the repository configuration.
 ///
 /// This configuration is on the critical path when opening a repository.
@@ -286,8 +320,12 @@ pub mod ssh_connect_options {
     /// The error produced when obtaining ssh connection configuration.
     #[derive(Debug, thiserror::Error)]
     #[allow(missing_docs)]
-    #[error(transparent)]
-    pub struct Error(#[from] super::key::GenericErrorWithValue);
+    pub enum Error {
+        #[error(transparent)]
+        Variant(#[from] super::key::GenericErrorWithValue),
+        #[error(transparent)]
+        ReadConfigFile(#[from] super::boolean::Error),
+    }
 }
 
 ///
@@ -177,26 +177,29 @@ pub(super) mod function {
 
         let allow_git_env = environment.git_prefix.is_allowed();
         let allow_ssh_env = environment.ssh_prefix.is_allowed();
-        let prompt_options = gix_prompt::Options {
-            askpass: crate::config::cache::access::trusted_file_path(
-                config,
-                &Core::ASKPASS,
-                &mut filter,
-                is_lenient_config,
-                environment,
-            )
-            .transpose()
-            .ignore_empty()?
-            .map(|c| Cow::Owned(c.into_owned())),
-            mode: config
-                .boolean(&Credentials::TERMINAL_PROMPT)
-                .map(|val| Credentials::TERMINAL_PROMPT.enrich_error(val))
+        let prompt_options = gix_prompt::Options::default()
+            .with_askpass(
+                crate::config::cache::access::trusted_file_path(
+                    config,
+                    &Core::ASKPASS,
+                    &mut filter,
+                    is_lenient_config,
+                    environment,
+                )
                 .transpose()
-                .with_leniency(is_lenient_config)?
-                .and_then(|val| (!val).then_some(gix_prompt::Mode::Disable))
-                .unwrap_or_default(),
-        }
-        .apply_environment(allow_git_env, allow_ssh_env, false /* terminal prompt */);
+                .ignore_empty()?
+                .map(|c| Cow::Owned(c.into_owned())),
+            )
+            .with_mode(
+                config
+                    .boolean(&Credentials::TERMINAL_PROMPT)
+                    .map(|val| Credentials::TERMINAL_PROMPT.enrich_error(val))
+                    .transpose()
+                    .with_leniency(is_lenient_config)?
+                    .and_then(|val| (!val).then_some(gix_prompt::Mode::Disable))
+                    .unwrap_or_default(),
+            )
+            .apply_environment(allow_git_env, allow_ssh_env, false /* terminal prompt */);
         Ok((
             gix_credentials::helper::Cascade {
                 programs,
@@ -37,6 +37,8 @@ pub(crate) mod root {
         pub const EXTENSIONS: sections::Extensions = sections::Extensions;
         /// The `fetch` section.
         pub const FETCH: sections::Fetch = sections::Fetch;
+        /// The `gc` section.
+        pub const GC: sections::Gc = sections::Gc;
         /// The `gitoxide` section.
         pub const GITOXIDE: sections::Gitoxide = sections::Gitoxide;
         /// The `http` section.
@@ -45,8 +47,12 @@ pub(crate) mod root {
         pub const INDEX: sections::Index = sections::Index;
         /// The `init` section.
         pub const INIT: sections::Init = sections::Init;
+        /// The `log` section.
+        pub const LOG: sections::Log = sections::Log;
         /// The `mailmap` section.
         pub const MAILMAP: sections::Mailmap = sections::Mailmap;
+        /// The `maintenance` section.
+        pub const MAINTENANCE: sections::Maintenance = sections::Maintenance;
         /// The `merge` section.
         pub const MERGE: sections::Merge = sections::Merge;
         /// The `pack` section.
@@ -64,6 +70,8 @@ pub(crate) mod root {
         /// The `status` section.
         #[cfg(feature = "status")]
         pub const STATUS: sections::Status = sections::Status;
+        /// The `transfer` section.
+        pub const TRANSFER: sections::Transfer = sections::Transfer;
         /// The `user` section.
         pub const USER: sections::User = sections::User;
         /// The `url` section.
@@ -83,11 +91,14 @@ pub(crate) mod root {
                 &Self::DIFF,
                 &Self::EXTENSIONS,
                 &Self::FETCH,
+                &Self::GC,
                 &Self::GITOXIDE,
                 &Self::HTTP,
                 &Self::INDEX,
                 &Self::INIT,
+                &Self::LOG,
                 &Self::MAILMAP,
+                &Self::MAINTENANCE,
                 &Self::MERGE,
                 &Self::PACK,
                 &Self::PROTOCOL,
@@ -97,6 +108,7 @@ pub(crate) mod root {
                 &Self::SSH,
                 #[cfg(feature = "status")]
                 &Self::STATUS,
+                &Self::TRANSFER,
                 &Self::USER,
                 &Self::URL,
             ]
@@ -106,9 +118,9 @@ pub(crate) mod root {
 
 mod sections;
 pub use sections::{
-    branch, checkout, core, credential, extensions, fetch, gitoxide, http, index, protocol, push, remote, ssh, Author,
-    Branch, Checkout, Clone, Committer, Core, Credential, Extensions, Fetch, Gitoxide, Http, Index, Init, Mailmap,
-    Merge, Pack, Protocol, Push, Remote, Safe, Ssh, Url, User,
+    branch, checkout, core, credential, extensions, fetch, gitoxide, http, index, log, maintenance, protocol, push,
+    remote, ssh, Author, Branch, Checkout, Clone, Committer, Core, Credential, Extensions, Fetch, Gc, Gitoxide, Http,
+    Index, Init, Log, Mailmap, Maintenance, Merge, Pack, Protocol, Push, Remote, Safe, Ssh, Transfer, Url, User,
 };
 #[cfg(feature = "blob-diff")]
 pub use sections::{diff, Diff};
@@ -3,6 +3,15 @@ use crate::config::tree::{keys, traits::SubSectionRequirement, Branch, Key, Sect
 const NAME_PARAMETER: Option<SubSectionRequirement> = Some(SubSectionRequirement::Parameter("name"));
 
 impl Branch {
+    /// The `branch.autoSetupMerge` key.
+    pub const AUTO_SETUP_MERGE: AutoSetupMerge =
+        AutoSetupMerge::new_with_validate("autoSetupMerge", &crate::config::Tree::BRANCH, validate::AutoSetupMerge);
+    /// The `branch.autoSetupRebase` key.
+    pub const AUTO_SETUP_REBASE: AutoSetupRebase = AutoSetupRebase::new_with_validate(
+        "autoSetupRebase",
+        &crate::config::Tree::BRANCH,
+        validate::AutoSetupRebase,
+    );
     /// The `branch.<name>.merge` key.
     pub const MERGE: Merge = Merge::new_with_validate("merge", &crate::config::Tree::BRANCH, validate::FullNameRef)
         .with_subsection_requirement(NAME_PARAMETER);
@@ -10,6 +19,10 @@ impl Branch {
     pub const PUSH_REMOTE: keys::RemoteName =
         keys::RemoteName::new_remote_name("pushRemote", &crate::config::Tree::BRANCH)
             .with_subsection_requirement(NAME_PARAMETER);
+    /// The `branch.<name>.rebase` key.
+    pub const REBASE: keys::Any = keys::Any::new("rebase", &crate::config::Tree::BRANCH)
+        .with_subsection_requirement(NAME_PARAMETER)
+        .with_note("only written when setting up tracking, but not interpreted");
     /// The `branch.<name>.remote` key.
     pub const REMOTE: keys::RemoteName = keys::RemoteName::new_remote_name("remote", &crate::config::Tree::BRANCH)
         .with_subsection_requirement(NAME_PARAMETER);
@@ -21,10 +34,23 @@ impl Section for Branch {
     }
 
     fn keys(&self) -> &[&dyn Key] {
-        &[&Self::MERGE, &Self::PUSH_REMOTE, &Self::REMOTE]
+        &[
+            &Self::AUTO_SETUP_MERGE,
+            &Self::AUTO_SETUP_REBASE,
+            &Self::MERGE,
+            &Self::PUSH_REMOTE,
+            &Self::REBASE,
+            &Self::REMOTE,
+        ]
     }
 }
 
+/// The `branch.autoSetupMerge` key.
+pub type AutoSetupMerge = keys::Any<validate::AutoSetupMerge>;
+
+/// The `branch.autoSetupRebase` key.
+pub type AutoSetupRebase = keys::Any<validate::AutoSetupRebase>;
+
 /// The `branch.<name>.merge` key.
 pub type Merge = keys::Any<validate::FullNameRef>;
 
@@ -48,11 +74,58 @@ mod merge {
     }
 }
 
+mod auto_setup {
+    use std::borrow::Cow;
+
+    use crate::{
+        branch,
+        bstr::{BStr, ByteSlice},
+        config,
+        config::tree::branch::{AutoSetupMerge, AutoSetupRebase},
+    };
+
+    impl AutoSetupMerge {
+        /// Returns the way tracking is set up for new branches as parsed from `value`. If `value` is not a boolean,
+        /// we try to interpret the string value instead.
+        pub fn try_into_auto_setup_merge(
+            &'static self,
+            value: Result<bool, gix_config::value::Error>,
+        ) -> Result<branch::AutoSetupMerge, config::key::GenericErrorWithValue> {
+            Ok(match value {
+                Ok(true) => branch::AutoSetupMerge::RemoteTrackingBranches,
+                Ok(false) => branch::AutoSetupMerge::Never,
+                Err(err) => match err.input.as_bytes() {
+                    b"always" => branch::AutoSetupMerge::Always,
+                    b"inherit" => branch::AutoSetupMerge::Inherit,
+                    b"simple" => branch::AutoSetupMerge::Simple,
+                    _ => return Err(config::key::GenericErrorWithValue::from_value(self, err.input)),
+                },
+            })
+        }
+    }
+
+    impl AutoSetupRebase {
+        /// Returns the kinds of tracking branches that are set up to rebase as parsed from `value`.
+        pub fn try_into_auto_setup_rebase(
+            &'static self,
+            value: Cow<'_, BStr>,
+        ) -> Result<branch::AutoSetupRebase, config::key::GenericErrorWithValue> {
+            Ok(match value.as_ref().as_bytes() {
+                b"never" => branch::AutoSetupRebase::Never,
+                b"local" => branch::AutoSetupRebase::Local,
+                b"remote" => branch::AutoSetupRebase::Remote,
+                b"always" => branch::AutoSetupRebase::Always,
+                _ => return Err(config::key::GenericErrorWithValue::from_value(self, value.into_owned())),
+            })
+        }
+    }
+}
+
 ///
 pub mod validate {
     use crate::{
         bstr::BStr,
-        config::tree::{branch::Merge, keys},
+        config::tree::{branch::Merge, keys, Branch},
     };
 
     pub struct FullNameRef;
@@ -62,4 +135,20 @@ pub mod validate {
             Ok(())
         }
     }
+
+    pub struct AutoSetupMerge;
+    impl keys::Validate for AutoSetupMerge {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            Branch::AUTO_SETUP_MERGE.try_into_auto_setup_merge(gix_config::Boolean::try_from(value).map(|b| b.0))?;
+            Ok(())
+        }
+    }
+
+    pub struct AutoSetupRebase;
+    impl keys::Validate for AutoSetupRebase {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            Branch::AUTO_SETUP_REBASE.try_into_auto_setup_rebase(value.into())?;
+            Ok(())
+        }
+    }
 }
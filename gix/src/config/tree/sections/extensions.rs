@@ -11,6 +11,16 @@ impl Extensions {
         ObjectFormat::new_with_validate("objectFormat", &config::Tree::EXTENSIONS, validate::ObjectFormat).with_note(
             "Support for SHA256 is prepared but not fully implemented yet. For now we abort when encountered",
         );
+    /// The `extensions.refStorage` key.
+    pub const REF_STORAGE: keys::Any = keys::Any::new("refStorage", &config::Tree::EXTENSIONS)
+        .with_note("Only the `files` backend is supported, `reftable` is not");
+    /// The `extensions.partialClone` key.
+    pub const PARTIAL_CLONE: keys::RemoteName =
+        keys::RemoteName::new_remote_name("partialClone", &config::Tree::EXTENSIONS)
+            .with_note("Missing objects are not fetched from the promisor remote on demand");
+    /// The `extensions.preciousObjects` key.
+    pub const PRECIOUS_OBJECTS: keys::Boolean =
+        keys::Boolean::new_boolean("preciousObjects", &config::Tree::EXTENSIONS);
 }
 
 /// The `core.checkStat` key.
@@ -41,7 +51,13 @@ impl Section for Extensions {
     }
 
     fn keys(&self) -> &[&dyn Key] {
-        &[&Self::OBJECT_FORMAT, &Self::WORKTREE_CONFIG]
+        &[
+            &Self::OBJECT_FORMAT,
+            &Self::WORKTREE_CONFIG,
+            &Self::REF_STORAGE,
+            &Self::PARTIAL_CLONE,
+            &Self::PRECIOUS_OBJECTS,
+        ]
     }
 }
 
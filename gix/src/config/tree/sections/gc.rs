@@ -0,0 +1,22 @@
+use crate::{
+    config,
+    config::tree::{keys, Gc, Key, Section},
+};
+
+impl Gc {
+    /// The `gc.auto` key.
+    pub const AUTO: keys::UnsignedInteger = keys::UnsignedInteger::new_unsigned_integer("auto", &config::Tree::GC)
+        .with_note("the amount of loose objects is estimated by looking at objects/17 only, like git does");
+    /// The `gc.writeCommitGraph` key.
+    pub const WRITE_COMMIT_GRAPH: keys::Boolean = keys::Boolean::new_boolean("writeCommitGraph", &config::Tree::GC);
+}
+
+impl Section for Gc {
+    fn name(&self) -> &str {
+        "gc"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::AUTO, &Self::WRITE_COMMIT_GRAPH]
+    }
+}
@@ -133,6 +133,20 @@ mod subsections {
         pub const REFS_NAMESPACE: RefsNamespace =
             keys::Any::new_with_validate("refsNamespace", &Gitoxide::CORE, super::validate::RefsNamespace)
                 .with_environment_override("GIT_NAMESPACE");
+
+        /// The `gitoxide.core.lockTimeout` key.
+        ///
+        /// It controls how long to wait for locks of files other than references, like the index, the shallow file
+        /// or configuration files, in milliseconds, or forever if negative.
+        pub const LOCK_TIMEOUT: keys::LockTimeout = keys::LockTimeout::new_lock_timeout("lockTimeout", &Gitoxide::CORE)
+            .with_note("if unset, we fail immediately just like `git` does");
+
+        /// The `gitoxide.core.reflogAction` key.
+        ///
+        /// The action to prefix reference log messages with, like `pull` instead of `fetch`, for use by tools that
+        /// parse reference logs.
+        pub const REFLOG_ACTION: keys::String =
+            keys::String::new_string("reflogAction", &Gitoxide::CORE).with_environment_override("GIT_REFLOG_ACTION");
     }
 
     impl Section for Core {
@@ -150,6 +164,8 @@ mod subsections {
                 &Self::FILTER_PROCESS_DELAY,
                 &Self::EXTERNAL_COMMAND_STDERR,
                 &Self::REFS_NAMESPACE,
+                &Self::LOCK_TIMEOUT,
+                &Self::REFLOG_ACTION,
             ]
         }
 
@@ -185,6 +201,16 @@ mod subsections {
             keys::DurationInMilliseconds::new_duration("connectTimeout", &Gitoxide::HTTP).with_note(
                 "entirely new, and in milliseconds, to describe how long to wait until a connection attempt is aborted",
             );
+        /// The `gitoxide.http.retries` key.
+        pub const RETRIES: keys::UnsignedInteger =
+            keys::UnsignedInteger::new_unsigned_integer("retries", &Gitoxide::HTTP).with_note(
+                "entirely new, to retry idempotent requests that fail with 429, server errors or network errors",
+            );
+        /// The `gitoxide.http.retryDelay` key.
+        pub const RETRY_DELAY: keys::DurationInMilliseconds =
+            keys::DurationInMilliseconds::new_duration("retryDelay", &Gitoxide::HTTP).with_note(
+                "entirely new, and in milliseconds, to describe how long to wait before the first retry, doubling with each retry",
+            );
         /// The `gitoxide.http.sslVersionMin` key.
         pub const SSL_VERSION_MIN: http::SslVersion =
             http::SslVersion::new_ssl_version("sslVersionMin", &Gitoxide::HTTP).with_note(
@@ -207,6 +233,14 @@ mod subsections {
         pub const PROXY_AUTH_METHOD: http::ProxyAuthMethod =
             http::ProxyAuthMethod::new_proxy_auth_method("proxyAuthMethod", &Gitoxide::HTTP)
                 .with_environment_override("GIT_HTTP_PROXY_AUTHMETHOD");
+        /// The `gitoxide.http.authMethod` key.
+        pub const AUTH_METHOD: http::ProxyAuthMethod = http::ProxyAuthMethod::new_proxy_auth_method(
+            "authMethod",
+            &Gitoxide::HTTP,
+        )
+        .with_note(
+            "entirely new, like `http.proxyAuthMethod` but for the server. If unset, basic authentication is used like in `git`",
+        );
     }
 
     impl Section for Http {
@@ -221,10 +255,13 @@ mod subsections {
                 &Self::VERBOSE,
                 &Self::NO_PROXY,
                 &Self::CONNECT_TIMEOUT,
+                &Self::RETRIES,
+                &Self::RETRY_DELAY,
                 &Self::SSL_VERSION_MIN,
                 &Self::SSL_VERSION_MAX,
                 &Self::SSL_NO_VERIFY,
                 &Self::PROXY_AUTH_METHOD,
+                &Self::AUTH_METHOD,
             ]
         }
 
@@ -350,6 +387,11 @@ mod subsections {
             keys::Executable::new_executable("commandWithoutShellFallback", &Gitoxide::SSH)
                 .with_environment_override("GIT_SSH")
                 .with_note("is always executed without shell and treated as fallback");
+        /// The `gitoxide.ssh.readConfigFile` key.
+        pub const READ_CONFIG_FILE: keys::Boolean = keys::Boolean::new_boolean("readConfigFile", &Gitoxide::SSH)
+            .with_note(
+                "resolve hosts with `~/.ssh/config` for ssh programs that don't read it themselves, like `plink`",
+            );
     }
 
     impl Section for Ssh {
@@ -358,7 +400,7 @@ mod subsections {
         }
 
         fn keys(&self) -> &[&dyn Key] {
-            &[&Self::COMMAND_WITHOUT_SHELL_FALLBACK]
+            &[&Self::COMMAND_WITHOUT_SHELL_FALLBACK, &Self::READ_CONFIG_FILE]
         }
 
         fn parent(&self) -> Option<&dyn Section> {
@@ -0,0 +1,92 @@
+use crate::{
+    config,
+    config::tree::{keys, Key, Log, Section},
+};
+
+impl Log {
+    /// The `log.date` key
+    pub const DATE: Date = Date::new_with_validate("date", &config::Tree::LOG, validate::Date)
+        .with_note("'relative' and 'human' aren't supported and fall back to 'default'");
+}
+
+impl Section for Log {
+    fn name(&self) -> &str {
+        "log"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::DATE]
+    }
+}
+
+/// The `log.date` key type.
+pub type Date = keys::Any<validate::Date>;
+
+mod date {
+    use std::borrow::Cow;
+
+    use crate::{
+        bstr::{BStr, ByteSlice},
+        config,
+        config::tree::log::Date,
+        log::{DateFormat, DateStyle},
+    };
+
+    impl Date {
+        /// Try to interpret `value` as `log.date`, which may be suffixed with `-local` to display dates in
+        /// the local timezone.
+        pub fn try_into_date_format(
+            &'static self,
+            value: Cow<'_, BStr>,
+        ) -> Result<DateFormat, config::key::GenericErrorWithValue> {
+            let bytes = value.as_ref().as_bytes();
+            if let Some(custom) = bytes.strip_prefix(b"format:") {
+                return Ok(DateFormat {
+                    style: DateStyle::Custom(custom.into()),
+                    local: false,
+                });
+            }
+            if let Some(custom) = bytes.strip_prefix(b"format-local:") {
+                return Ok(DateFormat {
+                    style: DateStyle::Custom(custom.into()),
+                    local: true,
+                });
+            }
+            if bytes == b"local" {
+                return Ok(DateFormat {
+                    style: DateStyle::Default,
+                    local: true,
+                });
+            }
+            let (style, local) = match bytes.strip_suffix(b"-local") {
+                Some(style) => (style, true),
+                None => (bytes, false),
+            };
+            let style = match style {
+                b"default" | b"relative" | b"human" => DateStyle::Default,
+                b"iso" | b"iso8601" => DateStyle::Iso8601,
+                b"iso-strict" | b"iso8601-strict" => DateStyle::Iso8601Strict,
+                b"rfc" | b"rfc2822" => DateStyle::Rfc2822,
+                b"short" => DateStyle::Short,
+                b"raw" => DateStyle::Raw,
+                b"unix" => DateStyle::Unix,
+                _ => return Err(config::key::GenericErrorWithValue::from_value(self, value.into_owned())),
+            };
+            Ok(DateFormat { style, local })
+        }
+    }
+}
+
+mod validate {
+    pub struct Date;
+    use std::{borrow::Cow, error::Error};
+
+    use crate::{bstr::BStr, config::tree::keys::Validate};
+
+    impl Validate for Date {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+            super::Log::DATE.try_into_date_format(Cow::Borrowed(value))?;
+            Ok(())
+        }
+    }
+}
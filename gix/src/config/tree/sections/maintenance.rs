@@ -0,0 +1,58 @@
+use crate::{
+    config,
+    config::tree::{keys, Key, Maintenance, Section},
+};
+
+impl Maintenance {
+    /// The `maintenance.auto` key.
+    pub const AUTO: keys::Boolean = keys::Boolean::new_boolean("auto", &config::Tree::MAINTENANCE);
+
+    /// The `maintenance.commit-graph` section.
+    pub const COMMIT_GRAPH: CommitGraph = CommitGraph;
+}
+
+impl Section for Maintenance {
+    fn name(&self) -> &str {
+        "maintenance"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::AUTO]
+    }
+
+    fn sub_sections(&self) -> &[&dyn Section] {
+        &[&Self::COMMIT_GRAPH]
+    }
+}
+
+mod subsections {
+    use crate::config::{
+        tree::{keys, Key, Maintenance, Section},
+        Tree,
+    };
+
+    /// The `commit-graph` sub-section.
+    #[derive(Copy, Clone, Default)]
+    pub struct CommitGraph;
+
+    impl CommitGraph {
+        /// The `maintenance.commit-graph.auto` key.
+        pub const AUTO: keys::UnsignedInteger =
+            keys::UnsignedInteger::new_unsigned_integer("auto", &Maintenance::COMMIT_GRAPH);
+    }
+
+    impl Section for CommitGraph {
+        fn name(&self) -> &str {
+            "commit-graph"
+        }
+
+        fn keys(&self) -> &[&dyn Key] {
+            &[&Self::AUTO]
+        }
+
+        fn parent(&self) -> Option<&dyn Section> {
+            Some(&Tree::MAINTENANCE)
+        }
+    }
+}
+pub use subsections::CommitGraph;
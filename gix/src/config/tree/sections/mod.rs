@@ -52,6 +52,11 @@ pub mod extensions;
 pub struct Fetch;
 pub mod fetch;
 
+/// The `gc` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Gc;
+mod gc;
+
 /// The `gitoxide` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Gitoxide;
@@ -72,10 +77,20 @@ pub mod index;
 pub struct Init;
 mod init;
 
+/// The `log` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Log;
+pub mod log;
+
 #[derive(Copy, Clone, Default)]
 pub struct Mailmap;
 mod mailmap;
 
+/// The `maintenance` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Maintenance;
+pub mod maintenance;
+
 #[derive(Copy, Clone, Default)]
 pub struct Merge;
 mod merge;
@@ -117,6 +132,11 @@ pub struct Status;
 #[cfg(feature = "status")]
 pub mod status;
 
+/// The `transfer` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Transfer;
+mod transfer;
+
 /// The `user` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct User;
@@ -0,0 +1,25 @@
+use crate::{
+    config,
+    config::tree::{keys, Key, Section, Transfer},
+};
+
+impl Transfer {
+    /// The `transfer.advertiseSID` key.
+    pub const ADVERTISE_SID: keys::Boolean = keys::Boolean::new_boolean("advertiseSID", &config::Tree::TRANSFER);
+    /// The `transfer.bundleURI` key.
+    pub const BUNDLE_URI: keys::Boolean = keys::Boolean::new_boolean("bundleURI", &config::Tree::TRANSFER);
+    /// The `transfer.unpackLimit` key.
+    pub const UNPACK_LIMIT: keys::UnsignedInteger =
+        keys::UnsignedInteger::new_unsigned_integer("unpackLimit", &config::Tree::TRANSFER)
+            .with_note("used only if `fetch.unpackLimit` isn't set");
+}
+
+impl Section for Transfer {
+    fn name(&self) -> &str {
+        "transfer"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::ADVERTISE_SID, &Self::BUNDLE_URI, &Self::UNPACK_LIMIT]
+    }
+}
@@ -23,6 +23,16 @@ pub enum Error {
     DirectoryNotEmpty { path: PathBuf },
     #[error("Could not create directory at '{}'", .path.display())]
     CreateDirectory { source: std::io::Error, path: PathBuf },
+    #[error("Could not read template directory at '{}'", .path.display())]
+    ReadTemplateDirectory { source: std::io::Error, path: PathBuf },
+    #[error("Could not copy template file '{}' to '{}'", .source_path.display(), .path.display())]
+    CopyTemplate {
+        source: std::io::Error,
+        source_path: PathBuf,
+        path: PathBuf,
+    },
+    #[error("Could not merge the template configuration with the configuration of the new repository")]
+    MergeTemplateConfig(#[from] gix_config::file::init::from_paths::Error),
 }
 
 /// The kind of repository to create.
@@ -117,6 +127,11 @@ pub struct Options {
     /// If set, use these filesystem capabilities to populate the respective git-config fields.
     /// If `None`, the directory will be probed.
     pub fs_capabilities: Option<gix_fs::Capabilities>,
+    /// If `true`, don't write the built-in templates, and don't use those configured via `init.templateDir`
+    /// when [initializing a repository](crate::ThreadSafeRepository::init_opts()).
+    ///
+    /// Use this with [`copy_templates()`] to use the templates of a specific directory, similar to `git init --template <dir>`.
+    pub ignore_templates: bool,
 }
 
 /// Create a new `.git` repository of `kind` within the possibly non-existing `directory`
@@ -130,6 +145,7 @@ pub fn into(
     Options {
         fs_capabilities,
         destination_must_be_empty,
+        ignore_templates,
     }: Options,
 ) -> Result<gix_discover::repository::Path, Error> {
     let mut dot_git = directory.into();
@@ -163,30 +179,6 @@ pub fn into(
     };
     create_dir(&dot_git)?;
 
-    {
-        let mut cursor = NewDir(&mut dot_git).at("info")?;
-        write_file(TPL_INFO_EXCLUDE, PathCursor(cursor.as_mut()).at("exclude"))?;
-    }
-
-    {
-        let mut cursor = NewDir(&mut dot_git).at("hooks")?;
-        for (tpl, filename) in &[
-            (TPL_HOOKS_DOCS_URL, "docs.url"),
-            (TPL_HOOKS_PREPARE_COMMIT_MSG, "prepare-commit-msg.sample"),
-            (TPL_HOOKS_PRE_REBASE, "pre-rebase.sample"),
-            (TPL_HOOKS_PRE_PUSH, "pre-push.sample"),
-            (TPL_HOOKS_PRE_COMMIT, "pre-commit.sample"),
-            (TPL_HOOKS_PRE_MERGE_COMMIT, "pre-merge-commit.sample"),
-            (TPL_HOOKS_PRE_APPLYPATCH, "pre-applypatch.sample"),
-            (TPL_HOOKS_POST_UPDATE, "post-update.sample"),
-            (TPL_HOOKS_FSMONITOR_WATCHMAN, "fsmonitor-watchman.sample"),
-            (TPL_HOOKS_COMMIT_MSG, "commit-msg.sample"),
-            (TPL_HOOKS_APPLYPATCH_MSG, "applypatch-msg.sample"),
-        ] {
-            write_file(tpl, PathCursor(cursor.as_mut()).at(filename))?;
-        }
-    }
-
     {
         let mut cursor = NewDir(&mut dot_git).at("objects")?;
         create_dir(PathCursor(cursor.as_mut()).at("info"))?;
@@ -199,9 +191,7 @@ pub fn into(
         create_dir(PathCursor(cursor.as_mut()).at("tags"))?;
     }
 
-    for (tpl, filename) in &[(TPL_HEAD, "HEAD"), (TPL_DESCRIPTION, "description")] {
-        write_file(tpl, PathCursor(&mut dot_git).at(filename))?;
-    }
+    write_file(TPL_HEAD, PathCursor(&mut dot_git).at("HEAD"))?;
 
     let caps = {
         let mut config = gix_config::File::default();
@@ -227,6 +217,10 @@ pub fn into(
         caps
     };
 
+    if !ignore_templates {
+        write_builtin_templates(&dot_git)?;
+    }
+
     Ok(gix_discover::repository::Path::from_dot_git_dir(
         dot_git,
         if bare {
@@ -239,6 +233,95 @@ pub fn into(
     .expect("by now the `dot_git` dir is valid as we have accessed it"))
 }
 
+/// Write the built-in templates, like sample hooks, into `dot_git`.
+pub(crate) fn write_builtin_templates(dot_git: &Path) -> Result<(), Error> {
+    let mut dot_git = dot_git.to_owned();
+    {
+        let mut cursor = NewDir(&mut dot_git).at("info")?;
+        write_file(TPL_INFO_EXCLUDE, PathCursor(cursor.as_mut()).at("exclude"))?;
+    }
+
+    {
+        let mut cursor = NewDir(&mut dot_git).at("hooks")?;
+        for (tpl, filename) in &[
+            (TPL_HOOKS_DOCS_URL, "docs.url"),
+            (TPL_HOOKS_PREPARE_COMMIT_MSG, "prepare-commit-msg.sample"),
+            (TPL_HOOKS_PRE_REBASE, "pre-rebase.sample"),
+            (TPL_HOOKS_PRE_PUSH, "pre-push.sample"),
+            (TPL_HOOKS_PRE_COMMIT, "pre-commit.sample"),
+            (TPL_HOOKS_PRE_MERGE_COMMIT, "pre-merge-commit.sample"),
+            (TPL_HOOKS_PRE_APPLYPATCH, "pre-applypatch.sample"),
+            (TPL_HOOKS_POST_UPDATE, "post-update.sample"),
+            (TPL_HOOKS_FSMONITOR_WATCHMAN, "fsmonitor-watchman.sample"),
+            (TPL_HOOKS_COMMIT_MSG, "commit-msg.sample"),
+            (TPL_HOOKS_APPLYPATCH_MSG, "applypatch-msg.sample"),
+        ] {
+            write_file(tpl, PathCursor(cursor.as_mut()).at(filename))?;
+        }
+    }
+
+    let res = write_file(TPL_DESCRIPTION, PathCursor(&mut dot_git).at("description"));
+    res
+}
+
+/// Copy the contents of `template_dir` into `dot_git` like `git` does, skipping hidden files and files that already exist,
+/// and return `true` if the template contained a `config` file which was merged into the existing configuration of the repository.
+///
+/// Values in the configuration of the repository take precedence over the ones of the template.
+/// An empty `template_dir` is ignored, and so is a `template_dir` that doesn't exist.
+pub fn copy_templates(template_dir: &Path, dot_git: &Path) -> Result<bool, Error> {
+    if template_dir.as_os_str().is_empty() || !template_dir.is_dir() {
+        return Ok(false);
+    }
+    copy_template_dir(template_dir, dot_git)?;
+
+    let template_config = template_dir.join("config");
+    if !template_config.is_file() {
+        return Ok(false);
+    }
+    let config_path = dot_git.join("config");
+    let mut config = gix_config::File::from_path_no_includes(template_config, gix_config::Source::Local)?;
+    config.append(gix_config::File::from_path_no_includes(
+        config_path.clone(),
+        gix_config::Source::Local,
+    )?);
+    std::fs::write(&config_path, config.to_bstring()).map_err(|err| Error::IoWrite {
+        source: err,
+        path: config_path,
+    })?;
+    Ok(true)
+}
+
+fn copy_template_dir(template_dir: &Path, dest: &Path) -> Result<(), Error> {
+    let entries = fs::read_dir(template_dir).map_err(|err| Error::ReadTemplateDirectory {
+        source: err,
+        path: template_dir.to_owned(),
+    })?;
+    for entry in entries {
+        let entry = entry.map_err(|err| Error::ReadTemplateDirectory {
+            source: err,
+            path: template_dir.to_owned(),
+        })?;
+        let name = entry.file_name();
+        if name.to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let source_path = entry.path();
+        let path = dest.join(&name);
+        if source_path.is_dir() {
+            create_dir(&path)?;
+            copy_template_dir(&source_path, &path)?;
+        } else if !path.exists() {
+            fs::copy(&source_path, &path).map_err(|err| Error::CopyTemplate {
+                source: err,
+                source_path,
+                path,
+            })?;
+        }
+    }
+    Ok(())
+}
+
 fn key(name: &'static str) -> section::ValueName<'static> {
     section::ValueName::try_from(name).expect("valid key name")
 }
@@ -88,5 +88,6 @@ pub struct Options {
     classify_untracked_bare_repositories: bool,
     emit_collapsed: Option<CollapsedEntriesEmissionMode>,
     symlinks_to_directories_are_ignored_like_directories: bool,
+    non_regular_files: gix_fs::NonRegularFiles,
     pub(crate) empty_patterns_match_prefix: bool,
 }
@@ -18,6 +18,7 @@ impl Options {
             emit_collapsed: None,
             empty_patterns_match_prefix: false,
             symlinks_to_directories_are_ignored_like_directories: false,
+            non_regular_files: Default::default(),
         }
     }
 }
@@ -38,6 +39,7 @@ impl From<Options> for gix_dir::walk::Options<'static> {
             emit_collapsed: v.emit_collapsed,
             symlinks_to_directories_are_ignored_like_directories: v
                 .symlinks_to_directories_are_ignored_like_directories,
+            non_regular_files: v.non_regular_files,
             worktree_relative_worktree_dirs: None,
         }
     }
@@ -184,4 +186,17 @@ impl Options {
         self.symlinks_to_directories_are_ignored_like_directories = value;
         self
     }
+
+    /// Control what happens with named pipes, sockets and other non-regular files. By default, they are skipped
+    /// just like Git does it.
+    pub fn non_regular_files(mut self, value: gix_fs::NonRegularFiles) -> Self {
+        self.non_regular_files = value;
+        self
+    }
+
+    /// Like [`non_regular_files()`](Self::non_regular_files), but only requires a mutably borrowed instance.
+    pub fn set_non_regular_files(&mut self, value: gix_fs::NonRegularFiles) -> &mut Self {
+        self.non_regular_files = value;
+        self
+    }
 }
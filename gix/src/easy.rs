@@ -0,0 +1,412 @@
+//! One-call operations for the most common workflows, built on top of the more granular APIs of this crate.
+//!
+//! These functions make choices for the caller, like discarding progress, using [`interrupt::IS_INTERRUPTED`](crate::interrupt::IS_INTERRUPTED)
+//! for interruption and refusing to do anything that would lose data.
+//! Once more control is needed, look at the implementation of each function to see which building blocks to use instead.
+#![allow(clippy::result_large_err)]
+use std::path::Path;
+
+use crate::Repository;
+
+///
+pub mod open_or_init {
+    /// The error returned by [`open_or_init()`](super::open_or_init()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Open(#[from] crate::open::Error),
+        #[error(transparent)]
+        Init(#[from] crate::init::Error),
+    }
+}
+
+/// Open the repository at `directory`, or initialize a new one with a worktree there if `directory`
+/// isn't a repository yet, like `git init` would.
+///
+/// Note that `directory` is not searched upwards for a repository, use [`discover()`](crate::discover()) for that.
+pub fn open_or_init(directory: impl AsRef<Path>) -> Result<Repository, open_or_init::Error> {
+    let directory = directory.as_ref();
+    match crate::open(directory) {
+        Ok(repo) => Ok(repo),
+        Err(crate::open::Error::NotARepository { .. }) => Ok(crate::init(directory)?),
+        Err(err) => Err(err.into()),
+    }
+}
+
+///
+#[cfg(all(feature = "worktree-mutation", feature = "blocking-network-client"))]
+pub mod clone_url_to_dir {
+    /// The error returned by [`clone_url_to_dir()`](super::clone_url_to_dir()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Prepare(#[from] crate::clone::Error),
+        #[error(transparent)]
+        Fetch(#[from] crate::clone::fetch::Error),
+        #[error(transparent)]
+        Checkout(#[from] crate::clone::checkout::main_worktree::Error),
+    }
+}
+
+/// Clone the repository at `url` into `directory` and checkout its main worktree, just like `git clone <url> <directory>` would,
+/// and return the newly cloned repository.
+///
+/// `directory` must not exist or be empty, and it will be removed again if the clone fails.
+/// See [`prepare_clone()`](crate::prepare_clone()) for configuring the clone.
+#[cfg(all(feature = "worktree-mutation", feature = "blocking-network-client"))]
+pub fn clone_url_to_dir<Url, E>(url: Url, directory: impl AsRef<Path>) -> Result<Repository, clone_url_to_dir::Error>
+where
+    Url: std::convert::TryInto<gix_url::Url, Error = E>,
+    gix_url::parse::Error: From<E>,
+{
+    let should_interrupt = &crate::interrupt::IS_INTERRUPTED;
+    let mut prepare = crate::prepare_clone(url, directory)?;
+    let (mut checkout, _outcome) = prepare.fetch_then_checkout(crate::progress::Discard, should_interrupt)?;
+    let (repo, _outcome) = checkout.main_worktree(crate::progress::Discard, should_interrupt)?;
+    Ok(repo)
+}
+
+///
+#[cfg(all(feature = "dirwalk", feature = "tree-editor"))]
+pub mod commit_all {
+    use std::path::PathBuf;
+
+    use crate::bstr::BString;
+
+    /// The error returned by [`commit_all()`](super::commit_all()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Cannot commit worktree changes in a bare repository")]
+        BareRepository,
+        #[error("Cannot commit as '{path}' is unmerged")]
+        Unmerged { path: BString },
+        #[error("There are no changes to commit")]
+        NothingToCommit,
+        #[error(transparent)]
+        FilesystemOptions(#[from] crate::config::boolean::Error),
+        #[error(transparent)]
+        LockTimeout(#[from] crate::config::lock_timeout::Error),
+        #[error(transparent)]
+        FilterPipeline(#[from] crate::repository::filter::pipeline::Error),
+        #[error(transparent)]
+        ConvertToGit(#[from] crate::filter::pipeline::convert_to_git::Error),
+        #[error(transparent)]
+        Dirwalk(#[from] crate::dirwalk::Error),
+        #[error("Could not read '{}' from the worktree", path.display())]
+        Io { source: std::io::Error, path: PathBuf },
+        #[error(transparent)]
+        WriteBlob(#[from] crate::object::write::Error),
+        #[error(transparent)]
+        EditTree(#[from] crate::repository::edit_tree::Error),
+        #[error(transparent)]
+        TreeEditor(#[from] gix_object::tree::editor::Error),
+        #[error(transparent)]
+        WriteTree(#[from] crate::object::tree::editor::write::Error),
+        #[error(transparent)]
+        FindHead(#[from] crate::reference::find::existing::Error),
+        #[error(transparent)]
+        FindHeadCommit(#[from] crate::object::find::existing::with_conversion::Error),
+        #[error(transparent)]
+        DecodeHeadCommit(#[from] gix_object::decode::Error),
+        #[error(transparent)]
+        Commit(#[from] crate::commit::Error),
+        #[error(transparent)]
+        IndexFromTree(#[from] crate::repository::index_from_tree::Error),
+        #[error(transparent)]
+        WriteIndex(#[from] gix_index::file::write::Error),
+        #[error(transparent)]
+        ExpandSparseIndex(#[from] gix_index::sparse::expand::Error),
+    }
+}
+
+/// Commit all changes in the worktree of `repo`, including untracked files that aren't ignored, with `message` on top of `HEAD`,
+/// and return the id of the new commit. This is like `git add --all && git commit -m <message>`.
+///
+/// Files are passed through the [filter pipeline](crate::filter::Pipeline) and the index is updated to match the new commit.
+/// Deleted files are removed from the commit, while untracked repositories and submodules are ignored. Submodules are
+/// committed as they are recorded in the index.
+///
+/// It's an error if there is nothing to commit, or if the index contains unmerged entries.
+#[cfg(all(feature = "dirwalk", feature = "tree-editor"))]
+pub fn commit_all(repo: &Repository, message: impl AsRef<str>) -> Result<crate::Id<'_>, commit_all::Error> {
+    use commit_all::Error;
+    use gix_index::entry::{Flags, Mode};
+
+    use crate::bstr::ByteSlice;
+
+    let workdir = repo.work_dir().ok_or(Error::BareRepository)?;
+    let caps = repo.filesystem_options()?;
+    let (mut pipeline, mut index) = repo.filter_pipeline(None)?;
+    if index.is_sparse() {
+        let mut full_index = index.into_owned();
+        full_index.expand_sparse_directories(&repo.objects)?;
+        index = full_index.into();
+    }
+
+    let mut editor = repo.edit_tree(gix_hash::ObjectId::empty_tree(repo.object_hash()))?;
+    let mut entries_state = Vec::new();
+    for entry in index.entries() {
+        let rela_path = entry.path(&index);
+        if entry.stage_raw() != 0 {
+            return Err(Error::Unmerged {
+                path: rela_path.to_owned(),
+            });
+        }
+        let (mode, id, stat) = if entry.flags.contains(Flags::SKIP_WORKTREE) || entry.mode == Mode::COMMIT {
+            (entry.mode, entry.id, entry.stat)
+        } else {
+            match commit_all_blob(repo, &mut pipeline, &index, workdir, rela_path, Some(entry.mode), caps)? {
+                Some(blob) => blob,
+                None => continue,
+            }
+        };
+        let kind = mode.to_tree_entry_mode().expect("valid index entry mode").kind();
+        editor.upsert(rela_path, kind, id)?;
+        entries_state.push((rela_path.to_owned(), stat, entry.flags & Flags::SKIP_WORKTREE));
+    }
+
+    let mut untracked = gix_dir::walk::delegate::Collect::default();
+    repo.dirwalk(
+        &index,
+        None::<&str>,
+        &crate::interrupt::IS_INTERRUPTED,
+        repo.dirwalk_options()?
+            .emit_untracked(gix_dir::walk::EmissionMode::Matching),
+        &mut untracked,
+    )?;
+    for (entry, _) in untracked.unorded_entries {
+        if entry.status != gix_dir::entry::Status::Untracked
+            || !matches!(
+                entry.disk_kind,
+                Some(gix_dir::entry::Kind::File | gix_dir::entry::Kind::Symlink)
+            )
+        {
+            continue;
+        }
+        let rela_path = entry.rela_path.as_bstr();
+        if let Some((mode, id, stat)) = commit_all_blob(repo, &mut pipeline, &index, workdir, rela_path, None, caps)? {
+            let kind = mode.to_tree_entry_mode().expect("only files and symlinks").kind();
+            editor.upsert(rela_path, kind, id)?;
+            entries_state.push((entry.rela_path, stat, Flags::empty()));
+        }
+    }
+    let tree = editor.write()?.detach();
+
+    let parent = repo.head()?.id().map(crate::Id::detach);
+    if let Some(parent) = parent {
+        if repo.find_commit(parent)?.tree_id()? == tree {
+            return Err(Error::NothingToCommit);
+        }
+    }
+    let commit_id = repo.commit("HEAD", message, tree, parent)?;
+
+    entries_state.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut new_index = repo.index_from_tree(&tree)?;
+    for (entry, rela_path) in new_index.entries_mut_with_paths() {
+        if let Ok(pos) = entries_state.binary_search_by(|(path, _, _)| path.as_bstr().cmp(rela_path)) {
+            let (_, stat, flags) = &entries_state[pos];
+            entry.stat = *stat;
+            entry.flags |= *flags;
+        }
+    }
+    new_index.write(gix_index::write::Options {
+        lock_mode: repo.config.other_lock_timeout()?,
+        ..Default::default()
+    })?;
+    Ok(commit_id)
+}
+
+/// Write the worktree file at `rela_path` as blob and return its mode, id and stat, or `None` if it's not a file or symlink,
+/// or if it doesn't exist anymore.
+#[cfg(all(feature = "dirwalk", feature = "tree-editor"))]
+fn commit_all_blob(
+    repo: &Repository,
+    pipeline: &mut crate::filter::Pipeline<'_>,
+    index: &gix_index::State,
+    workdir: &Path,
+    rela_path: &crate::bstr::BStr,
+    index_mode: Option<gix_index::entry::Mode>,
+    caps: gix_fs::Capabilities,
+) -> Result<Option<(gix_index::entry::Mode, gix_hash::ObjectId, gix_index::entry::Stat)>, commit_all::Error> {
+    use std::io::Read;
+
+    use gix_index::entry::{Mode, Stat};
+
+    let path = workdir.join(gix_path::from_bstr(rela_path));
+    let io_err = |source| commit_all::Error::Io {
+        source,
+        path: path.clone(),
+    };
+    let metadata = match gix_index::fs::Metadata::from_path_no_follow(&path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(io_err(err)),
+    };
+    let mode = if metadata.is_symlink() {
+        Mode::SYMLINK
+    } else if metadata.is_file() {
+        match index_mode {
+            Some(mode @ (Mode::FILE | Mode::FILE_EXECUTABLE)) if !caps.executable_bit => mode,
+            Some(Mode::SYMLINK) if !caps.symlink => Mode::SYMLINK,
+            _ if caps.executable_bit && metadata.is_executable() => Mode::FILE_EXECUTABLE,
+            _ => Mode::FILE,
+        }
+    } else {
+        return Ok(None);
+    };
+
+    let id = if metadata.is_symlink() {
+        let target = std::fs::read_link(&path).map_err(io_err)?;
+        repo.write_blob(&*gix_path::to_unix_separators_on_windows(gix_path::into_bstr(target)))?
+    } else {
+        let file = std::fs::File::open(&path).map_err(io_err)?;
+        let mut buf = Vec::new();
+        pipeline
+            .convert_to_git(file, gix_path::from_bstr(rela_path).as_ref(), index)?
+            .read_to_end(&mut buf)
+            .map_err(io_err)?;
+        repo.write_blob(&buf)?
+    };
+    Ok(Some((mode, id.detach(), Stat::from_fs(&metadata).unwrap_or_default())))
+}
+
+///
+#[cfg(all(
+    feature = "worktree-mutation",
+    feature = "blocking-network-client",
+    feature = "status",
+    feature = "revision"
+))]
+pub mod fetch_and_fast_forward {
+    use std::path::PathBuf;
+
+    use crate::{bstr::BString, remote};
+
+    /// The error returned by [`fetch_and_fast_forward()`](super::fetch_and_fast_forward()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Cannot fast-forward the worktree of a bare repository")]
+        BareRepository,
+        #[error("HEAD must point to a branch to be fast-forwarded")]
+        DetachedHead,
+        #[error("Branch '{branch}' has no remote to fetch from")]
+        NoRemote { branch: BString },
+        #[error("Branch '{branch}' doesn't track a remote branch")]
+        NoUpstream { branch: BString },
+        #[error("Cannot fast-forward {head} to {upstream} as the branches have diverged")]
+        NotFastForward {
+            head: gix_hash::ObjectId,
+            upstream: gix_hash::ObjectId,
+        },
+        #[error("The worktree or index have changes that would be overwritten")]
+        Dirty,
+        #[error("The untracked file '{}' would be overwritten", path.display())]
+        WouldOverwriteUntracked { path: PathBuf },
+        #[error(transparent)]
+        FindHead(#[from] crate::reference::find::existing::Error),
+        #[error(transparent)]
+        FindRemote(#[from] remote::find::existing::Error),
+        #[error(transparent)]
+        Connect(#[from] remote::connect::Error),
+        #[error(transparent)]
+        PrepareFetch(#[from] remote::fetch::prepare::Error),
+        #[error(transparent)]
+        Fetch(#[from] remote::fetch::Error),
+        #[error(transparent)]
+        UpstreamName(#[from] crate::repository::branch_remote_tracking_ref_name::Error),
+        #[error(transparent)]
+        PeelUpstream(#[from] crate::reference::peel::Error),
+        #[error(transparent)]
+        FastForward(crate::fast_forward::Error),
+    }
+
+    impl From<crate::fast_forward::Error> for Error {
+        fn from(err: crate::fast_forward::Error) -> Self {
+            use crate::fast_forward::Error as FastForward;
+            match err {
+                FastForward::NotFastForward { current, to, .. } => Error::NotFastForward {
+                    head: current,
+                    upstream: to,
+                },
+                FastForward::Dirty => Error::Dirty,
+                FastForward::WouldOverwriteUntracked { path } => Error::WouldOverwriteUntracked { path },
+                err => Error::FastForward(err),
+            }
+        }
+    }
+
+    /// The outcome of [`fetch_and_fast_forward()`](super::fetch_and_fast_forward()).
+    #[derive(Debug)]
+    pub struct Outcome {
+        /// The outcome of fetching from the remote of the current branch.
+        pub fetch: remote::fetch::Outcome,
+        /// The commit `HEAD` pointed to before the fast-forward.
+        pub previous_head: gix_hash::ObjectId,
+        /// The commit `HEAD` points to now, which is the same as `previous_head` if there was nothing to fast-forward.
+        pub head: gix_hash::ObjectId,
+    }
+}
+
+/// Fetch from the remote of the current branch of `repo` and fast-forward the branch and the worktree to its upstream branch,
+/// just like `git pull --ff-only` would.
+///
+/// This fails if the branch and its upstream have diverged, or if the index or tracked files in the worktree have changes.
+/// Untracked files are left alone, and it's an error if one of them would be overwritten.
+#[cfg(all(
+    feature = "worktree-mutation",
+    feature = "blocking-network-client",
+    feature = "status",
+    feature = "revision"
+))]
+pub fn fetch_and_fast_forward(
+    repo: &Repository,
+) -> Result<fetch_and_fast_forward::Outcome, fetch_and_fast_forward::Error> {
+    use fetch_and_fast_forward::{Error, Outcome};
+
+    use crate::remote::Direction;
+
+    let should_interrupt = &crate::interrupt::IS_INTERRUPTED;
+    if repo.work_dir().is_none() {
+        return Err(Error::BareRepository);
+    }
+    let branch = repo.head_name()?.ok_or(Error::DetachedHead)?;
+    let remote = repo
+        .head()?
+        .into_remote(Direction::Fetch)
+        .ok_or_else(|| Error::NoRemote {
+            branch: branch.shorten().to_owned(),
+        })??;
+    let fetch = remote
+        .connect(Direction::Fetch)?
+        .prepare_fetch(crate::progress::Discard, Default::default())?
+        .receive(crate::progress::Discard, should_interrupt)?;
+
+    let upstream = repo
+        .branch_remote_tracking_ref_name(branch.as_ref(), Direction::Fetch)
+        .ok_or_else(|| Error::NoUpstream {
+            branch: branch.shorten().to_owned(),
+        })??;
+    let upstream = repo.find_reference(upstream.as_ref())?.peel_to_id_in_place()?.detach();
+    let outcome = repo.fast_forward(
+        branch.as_ref(),
+        upstream,
+        crate::fast_forward::Options {
+            update_worktree: true,
+            reflog_message: format!(
+                "{}: Fast-forward",
+                repo.reflog_action().unwrap_or_else(|| "pull".into())
+            )
+            .into(),
+            reflog_committer: None,
+        },
+    )?;
+    Ok(Outcome {
+        fetch,
+        previous_head: outcome.previous,
+        head: outcome.new,
+    })
+}
@@ -12,6 +12,26 @@ pub fn agent() -> &'static str {
     concat!("oxide-", env!("CARGO_PKG_VERSION"))
 }
 
+/// Returns the id of this process for identification towards remote servers that support the `session-id` capability,
+/// which allows correlating operations with the logs of the server.
+///
+/// It's generated once per process from the current time and the process id, and sent unless
+/// [a different one](crate::remote::Connection::with_session_id()) is configured for a connection.
+pub fn session_id() -> &'static str {
+    static SESSION_ID: once_cell::sync::OnceCell<String> = once_cell::sync::OnceCell::new();
+    SESSION_ID.get_or_init(|| {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        format!(
+            "{}.{:06}-P{:08x}",
+            now.as_secs(),
+            now.subsec_micros(),
+            std::process::id()
+        )
+    })
+}
+
 /// Equivalent to `std::env::args_os()`, but with precomposed unicode on MacOS and other apple platforms.
 /// It does not change the input arguments on any other platform.
 ///
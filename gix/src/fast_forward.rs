@@ -0,0 +1,90 @@
+//! Fast-forward branches to commits that contain them, similar to `git merge --ff-only`.
+#![allow(clippy::empty_docs)]
+use std::path::PathBuf;
+
+use crate::bstr::BString;
+
+/// Options for use in [`Repository::fast_forward()`](crate::Repository::fast_forward()).
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// If `true`, which is the default, the index and the worktree are updated to match the new commit if the branch is
+    /// checked out in the worktree of the repository.
+    ///
+    /// If `false`, only the branch is updated, which leaves a checked out branch with changes in its index and worktree.
+    pub update_worktree: bool,
+    /// The message to write into the reference logs of the branch, and of `HEAD` if the branch is checked out.
+    /// It defaults to `fast-forward`.
+    pub reflog_message: BString,
+    /// The identity to write into the reference logs, or `None` to use the
+    /// [configured committer](crate::Repository::committer()), which is the default.
+    pub reflog_committer: Option<gix_actor::Signature>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            update_worktree: true,
+            reflog_message: "fast-forward".into(),
+            reflog_committer: None,
+        }
+    }
+}
+
+/// The outcome of [`Repository::fast_forward()`](crate::Repository::fast_forward()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    /// The commit the branch pointed to before the fast-forward.
+    pub previous: gix_hash::ObjectId,
+    /// The commit the branch points to now, which is the same as `previous` if it already contained the desired commit.
+    pub new: gix_hash::ObjectId,
+    /// If `true`, the branch was checked out and the index and worktree were updated to match `new`.
+    pub updated_worktree: bool,
+}
+
+/// The error returned by [`Repository::fast_forward()`](crate::Repository::fast_forward()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Cannot fast-forward '{branch}' from {current} to {to} as they have diverged, a merge is required")]
+    NotFastForward {
+        branch: gix_ref::FullName,
+        current: gix_hash::ObjectId,
+        to: gix_hash::ObjectId,
+    },
+    #[error("The worktree or index have changes that would be overwritten")]
+    Dirty,
+    #[error("The untracked file '{}' would be overwritten", path.display())]
+    WouldOverwriteUntracked { path: PathBuf },
+    #[error(transparent)]
+    FindBranch(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    PeelBranch(#[from] crate::reference::peel::Error),
+    #[error(transparent)]
+    FindCommit(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    PeelToKind(#[from] crate::object::peel::to_kind::Error),
+    #[error(transparent)]
+    MergeBase(#[from] crate::repository::merge_base::Error),
+    #[error(transparent)]
+    IsDirty(#[from] crate::status::is_dirty::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    IndexFromTree(#[from] crate::repository::index_from_tree::Error),
+    #[error(transparent)]
+    CheckoutOptions(#[from] crate::config::checkout_options::Error),
+    #[error(transparent)]
+    Checkout(#[from] gix_worktree_state::checkout::Error),
+    #[error("Failed to reopen object database as Arc (only if thread-safety wasn't compiled in)")]
+    OpenArcOdb(#[from] std::io::Error),
+    #[error("Could not update '{}' in the worktree", path.display())]
+    Io { source: std::io::Error, path: PathBuf },
+    #[error(transparent)]
+    LockTimeout(#[from] crate::config::lock_timeout::Error),
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+    #[error(transparent)]
+    EditReference(#[from] crate::reference::edit::Error),
+    #[error(transparent)]
+    ExpandSparseIndex(#[from] gix_index::sparse::expand::Error),
+}
@@ -33,6 +33,8 @@ pub enum Error {
     },
     #[error("Could not edit HEAD reference with new default name")]
     EditHeadForDefaultBranch(#[from] crate::reference::edit::Error),
+    #[error("The template directory configured in init.templateDir could not be interpolated")]
+    TemplateDir(#[from] gix_config::path::interpolate::Error),
 }
 
 impl ThreadSafeRepository {
@@ -56,18 +58,46 @@ impl ThreadSafeRepository {
     ///
     /// Instead of naming the default branch `master`, we name it `main` unless configured explicitly using the `init.defaultBranch`
     /// configuration key.
+    ///
+    /// ### Templates
+    ///
+    /// Unless templates are [ignored](crate::create::Options::ignore_templates), the template directory is taken from `GIT_TEMPLATE_DIR` or `init.templateDir` in trusted configuration as permitted
+    /// by `open_options`, falling back to the built-in templates.
     pub fn init_opts(
         directory: impl AsRef<Path>,
         kind: crate::create::Kind,
-        create_options: crate::create::Options,
+        mut create_options: crate::create::Options,
         mut open_options: crate::open::Options,
     ) -> Result<Self, Error> {
+        // We need the configuration of the new repository to know which templates to use, so write them afterwards.
+        let templates_from_config = !create_options.ignore_templates;
+        if templates_from_config {
+            create_options.ignore_templates = true;
+        }
         let path = crate::create::into(directory.as_ref(), kind, create_options)?;
         let (git_dir, worktree_dir) = path.into_repository_and_work_tree_directories();
         open_options.git_dir_trust = Some(gix_sec::Trust::Full);
         // The repo will use `core.precomposeUnicode` to adjust the value as needed.
         open_options.current_dir = gix_fs::current_dir(false)?.into();
-        let repo = ThreadSafeRepository::open_from_paths(git_dir, worktree_dir, open_options)?;
+        let mut repo =
+            ThreadSafeRepository::open_from_paths(git_dir.clone(), worktree_dir.clone(), open_options.clone())?;
+
+        if templates_from_config {
+            let template_dir = repo
+                .config
+                .trusted_file_path(Init::TEMPLATE_DIR)
+                .transpose()?
+                .map(Cow::into_owned);
+            match template_dir {
+                Some(template_dir) => {
+                    if crate::create::copy_templates(&template_dir, &git_dir)? {
+                        // The template configuration is now part of the repository configuration.
+                        repo = ThreadSafeRepository::open_from_paths(git_dir, worktree_dir, open_options)?;
+                    }
+                }
+                None => crate::create::write_builtin_templates(&git_dir)?,
+            }
+        }
 
         let branch_name = repo
             .config
@@ -170,6 +170,10 @@ pub(crate) type Config = OwnShared<gix_config::File<'static>>;
 mod types;
 #[cfg(any(feature = "excludes", feature = "attributes"))]
 pub use types::AttributeStack;
+/// A thread-safe cache of parsed `.gitattributes` and `.gitignore` files to be shared by all [attribute stacks](AttributeStack)
+/// of the repositories it is set on with [`Repository::set_worktree_cache()`].
+#[cfg(feature = "excludes")]
+pub type WorktreeCache = gix_worktree::stack::SharedCache;
 pub use types::{
     Blob, Commit, Head, Id, Object, ObjectDetached, Reference, Remote, Repository, Tag, ThreadSafeRepository, Tree,
     Worktree,
@@ -196,6 +200,12 @@ pub mod tag;
 #[cfg(any(feature = "dirwalk", feature = "status"))]
 pub(crate) mod util;
 
+///
+pub mod branch;
+///
+pub mod easy;
+///
+pub mod log;
 ///
 pub mod progress;
 ///
@@ -330,6 +340,25 @@ pub mod status;
 ///
 pub mod shallow;
 
+pub mod lost_found;
+
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+pub mod fast_forward;
+
+#[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+pub mod maintenance;
+
+#[cfg(feature = "repair")]
+pub mod repair;
+
+pub mod capabilities;
+
+pub mod stats;
+
+pub mod plumbing;
+
+pub mod topology;
+
 ///
 pub mod discover;
 
@@ -0,0 +1,58 @@
+//! Types to display commit and reflog information like `git log` would.
+use crate::bstr::BString;
+
+/// The way a date is displayed, as configured by `log.date`.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DateFormat {
+    /// The style in which the date is displayed.
+    pub style: DateStyle,
+    /// If `true`, the date will be displayed in the local timezone instead of the timezone it was recorded in.
+    pub local: bool,
+}
+
+/// All possible styles of `log.date`, without the `-local` suffix.
+///
+/// Note that `relative` and `human` aren't supported and fall back to [`Default`](DateStyle::Default).
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DateStyle {
+    /// Like `Thu Sep 4 10:45:06 2022 -0400`.
+    #[default]
+    Default,
+    /// Like `2022-09-04 10:45:06 -0400`.
+    Iso8601,
+    /// Like `2022-09-04T10:45:06-04:00`.
+    Iso8601Strict,
+    /// Like `Thu, 4 Sep 2022 10:45:06 -0400`.
+    Rfc2822,
+    /// Like `2022-09-04`.
+    Short,
+    /// Like `1662302706 -0400`.
+    Raw,
+    /// Like `1662302706`, the seconds since unix epoch.
+    Unix,
+    /// A `strftime`-like format string, as passed with `format:<format>`.
+    Custom(BString),
+}
+
+impl DateFormat {
+    /// Format `time` according to our configuration.
+    ///
+    /// Note that custom formats which can't be used for formatting fall back to the [default](DateStyle::Default) style.
+    pub fn format(&self, time: gix_date::Time) -> String {
+        use gix_date::time::format;
+        let time = if self.local { time.to_local() } else { time };
+        match &self.style {
+            DateStyle::Default => time.format(format::DEFAULT),
+            DateStyle::Iso8601 => time.format(format::ISO8601),
+            DateStyle::Iso8601Strict => time.format(format::ISO8601_STRICT),
+            DateStyle::Rfc2822 => time.format(format::GIT_RFC2822),
+            DateStyle::Short => time.format(format::SHORT),
+            DateStyle::Raw => time.format(format::RAW),
+            DateStyle::Unix => time.format(format::UNIX),
+            DateStyle::Custom(custom) => std::str::from_utf8(custom)
+                .ok()
+                .and_then(|custom| time.format_custom(custom).ok())
+                .unwrap_or_else(|| time.format(format::DEFAULT)),
+        }
+    }
+}
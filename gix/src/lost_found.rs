@@ -0,0 +1,160 @@
+//! Find objects that aren't reachable anymore, similar to `git fsck --unreachable --lost-found`.
+#![allow(clippy::empty_docs)]
+use std::path::PathBuf;
+
+use crate::bstr::BString;
+
+/// Options for use in [`Repository::lost_found()`](crate::Repository::lost_found()).
+#[derive(Debug, Copy, Clone)]
+pub struct Options {
+    /// If `true`, all objects mentioned in reference logs are considered reachable, which is the default like in `git`.
+    ///
+    /// If `false`, objects only reachable through reference logs are reported as unreachable, and
+    /// [suggestions](Outcome::suggestions) for recovering them are provided.
+    pub reflogs_are_reachable: bool,
+    /// If `true`, which is the default, objects in the index of the current worktree are considered reachable.
+    pub index_is_reachable: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            reflogs_are_reachable: true,
+            index_is_reachable: true,
+        }
+    }
+}
+
+/// An object that isn't reachable from any reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Object {
+    /// The id of the object.
+    pub id: gix_hash::ObjectId,
+    /// The kind of the object.
+    pub kind: gix_object::Kind,
+    /// The time most closely associated with the object's creation, which is the committer time for commits,
+    /// the tagger time for tags, and the modification time of loose objects otherwise.
+    ///
+    /// It's `None` if no such time is available, like for blobs and trees stored in packs.
+    pub time: Option<gix_date::Time>,
+    /// If `true`, the object is dangling, i.e. it's not even referenced by any other unreachable object.
+    ///
+    /// Only dangling objects are written to the `lost-found` directory as they allow to recover all other unreachable objects.
+    pub is_dangling: bool,
+}
+
+/// A commit that is only reachable through a reference log, making it a candidate for recovering lost work, like deleted branches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// The id of the commit that isn't reachable from any reference anymore.
+    pub id: gix_hash::ObjectId,
+    /// The name of the reference whose log mentions the commit.
+    pub log: gix_ref::FullName,
+    /// The message of the most recent log line mentioning the commit, like `checkout: moving from feature to main`.
+    pub message: BString,
+    /// The time of the most recent log line mentioning the commit.
+    pub time: gix_date::Time,
+}
+
+/// The outcome of [`Repository::lost_found()`](crate::Repository::lost_found()).
+#[derive(Debug, Clone, Default)]
+pub struct Outcome {
+    /// All objects that aren't reachable, sorted by id.
+    pub unreachable: Vec<Object>,
+    /// Commits mentioned in reference logs which aren't reachable from references, most recent first.
+    ///
+    /// Note that it's only filled in if [`Options::reflogs_are_reachable`] is `false`.
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl Outcome {
+    /// Return an iterator over all dangling objects, i.e. the unreachable objects that aren't referenced by other unreachable objects.
+    pub fn dangling(&self) -> impl Iterator<Item = &Object> + '_ {
+        self.unreachable.iter().filter(|obj| obj.is_dangling)
+    }
+
+    /// Write all dangling objects to the `lost-found` directory of `repo`, and return the paths of all written files.
+    ///
+    /// Like `git`, commits are written to `lost-found/commit/<id>` and all other objects are written to `lost-found/other/<id>`.
+    /// Blobs are written with their data as content, which makes it easy to recover them, while all other objects are written
+    /// with their id as content.
+    pub fn write_lost_found(&self, repo: &crate::Repository) -> Result<Vec<PathBuf>, write::Error> {
+        let base = repo.common_dir().join("lost-found");
+        let mut paths = Vec::new();
+        for obj in self.dangling() {
+            let dir = if obj.kind == gix_object::Kind::Commit {
+                "commit"
+            } else {
+                "other"
+            };
+            let content = if obj.kind == gix_object::Kind::Blob {
+                repo.find_object(obj.id)?.detach().data
+            } else {
+                format!("{}\n", obj.id).into_bytes()
+            };
+            let dir = base.join(dir);
+            std::fs::create_dir_all(&dir).map_err(|source| write::Error::Io {
+                source,
+                path: dir.clone(),
+            })?;
+            let path = dir.join(obj.id.to_hex().to_string());
+            std::fs::write(&path, content).map_err(|source| write::Error::Io {
+                source,
+                path: path.clone(),
+            })?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+///
+pub mod write {
+    /// The error returned by [`Outcome::write_lost_found()`](super::Outcome::write_lost_found()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error("Could not write to '{}'", path.display())]
+        Io {
+            source: std::io::Error,
+            path: std::path::PathBuf,
+        },
+    }
+}
+
+/// The error returned by [`Repository::lost_found()`](crate::Repository::lost_found()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not list the packs of the object database")]
+    ListPacks(#[source] std::io::Error),
+    #[error(transparent)]
+    OpenPackIndex(#[from] gix_pack::index::init::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    TryFindObject(#[from] crate::object::find::Error),
+    #[error(transparent)]
+    DecodeObject(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    InitRefsIterator(#[from] crate::reference::iter::init::Error),
+    #[error(transparent)]
+    InitRefsIteratorPlatform(#[from] crate::reference::iter::Error),
+    #[error(transparent)]
+    IterateRefs(#[from] gix_ref::file::iter::loose_then_packed::Error),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error("Could not read reference log")]
+    ReadReflog(#[from] std::io::Error),
+    #[error(transparent)]
+    DecodeReflog(#[from] gix_ref::file::log::iter::decode::Error),
+    #[error("Could not list linked worktrees")]
+    ListWorktrees(#[source] std::io::Error),
+    #[error(transparent)]
+    OpenWorktree(#[from] crate::open::Error),
+    #[cfg(feature = "index")]
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+}
@@ -0,0 +1,323 @@
+//! Keep repositories fast by packing loose objects and updating auxiliary files like the commit-graph as needed,
+//! similar to `git maintenance run --auto`.
+#![allow(clippy::empty_docs)]
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use gix_features::{
+    parallel::InOrderIter,
+    progress::{self, Count, Progress},
+};
+use gix_hash::ObjectId;
+
+use crate::{
+    config::{
+        cache::util::ApplyLeniency,
+        tree::{keys, maintenance, Gc, Maintenance},
+    },
+    Repository,
+};
+
+/// The amount of loose objects above which they are packed if `gc.auto` isn't set, as used by `git`.
+const DEFAULT_GC_AUTO: u64 = 6700;
+/// The amount of reachable commits that aren't in the commit-graph above which it is updated if
+/// `maintenance.commit-graph.auto` isn't set, as used by `git`.
+const DEFAULT_COMMIT_GRAPH_AUTO: u64 = 100;
+
+/// The error returned by [`auto()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    ConfigBoolean(#[from] crate::config::boolean::Error),
+    #[error(transparent)]
+    ConfigUnsignedInteger(#[from] crate::config::unsigned_integer::Error),
+    #[error("Could not read or remove loose objects at '{}'", path.display())]
+    Io { source: std::io::Error, path: PathBuf },
+    #[error("Could not open the object database to create a pack")]
+    OpenObjectDatabase(#[source] std::io::Error),
+    #[error(transparent)]
+    CountObjects(#[from] gix_pack::data::output::count::objects::Error),
+    #[error(transparent)]
+    CreatePack(#[from] gix_pack::data::output::bytes::Error<gix_pack::data::output::entry::iter_from_counts::Error>),
+    #[error(transparent)]
+    WritePack(#[from] gix_pack::bundle::write::Error),
+    #[error(transparent)]
+    InitRefsIterator(#[from] crate::reference::iter::init::Error),
+    #[error(transparent)]
+    InitRefsIteratorPlatform(#[from] crate::reference::iter::Error),
+    #[error(transparent)]
+    ObtainRefDuringIteration(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error(transparent)]
+    FindHeader(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    OpenCommitGraph(#[from] crate::repository::commit_graph_if_enabled::Error),
+    #[error(transparent)]
+    InitWalk(#[from] crate::revision::walk::Error),
+    #[error(transparent)]
+    Walk(#[from] crate::revision::walk::iter::Error),
+    #[error(transparent)]
+    WriteCommitGraph(#[from] crate::repository::write_commit_graph::Error),
+    #[error("The operation was interrupted")]
+    Interrupted,
+}
+
+/// Information about the loose objects that were moved into a pack.
+#[derive(Debug, Clone)]
+pub struct LooseObjects {
+    /// Information collected while writing the pack and its index.
+    pub write_pack_bundle: gix_pack::bundle::write::Outcome,
+    /// The amount of loose object files that were removed after they were written to the pack.
+    pub num_removed: usize,
+}
+
+/// The outcome of [`auto()`], which is empty if no maintenance was needed.
+#[derive(Debug, Clone, Default)]
+pub struct Outcome {
+    /// Information about the pack that all loose objects were moved into, or `None` if there weren't enough loose objects.
+    pub loose_objects: Option<LooseObjects>,
+    /// Information about the layer that was added to the commit-graph chain, or `None` if it wasn't updated.
+    pub commit_graph: Option<crate::repository::write_commit_graph::Outcome>,
+}
+
+/// Perform maintenance on `repo` if its configuration indicates it's needed, similar to `git maintenance run --auto`, which
+/// is useful to call after operations that add objects, like fetches, so that long-lived processes don't degrade
+/// repository performance over time.
+///
+/// Nothing is done if `maintenance.auto` is `false`. Otherwise, all loose objects are moved into a new pack if there are more
+/// than `gc.auto` (6700 by default, `0` disables it), which is estimated like `git` does by looking at the
+/// `objects/17` directory only. The commit-graph is then updated with all commits reachable from references and `HEAD`
+/// if `gc.writeCommitGraph` is `true` (the default), or if at least `maintenance.commit-graph.auto` (100 by default,
+/// `0` disables it) of these commits aren't in the commit-graph yet.
+///
+/// ### Deviation
+///
+/// Unlike `git gc`, loose objects are packed whether or not they are reachable, and existing packs are never consolidated.
+/// The commit-graph is never written if commit-graphs are disabled via `core.commitGraph`.
+pub fn auto(
+    repo: &Repository,
+    progress: &mut dyn crate::DynNestedProgress,
+    should_interrupt: &AtomicBool,
+) -> Result<Outcome, Error> {
+    let _span = gix_trace::coarse!("gix::maintenance::auto()");
+    let mut out = Outcome::default();
+    if !boolean(repo, &Maintenance::AUTO, true)? {
+        return Ok(out);
+    }
+
+    let gc_auto = unsigned_integer(repo, &Gc::AUTO, DEFAULT_GC_AUTO)?;
+    if gc_auto != 0 && too_many_loose_objects(repo, gc_auto)? {
+        out.loose_objects = pack_loose_objects(repo, progress, should_interrupt)?;
+    }
+
+    if !repo.config.may_use_commit_graph()? {
+        return Ok(out);
+    }
+    let tips = reference_tips(repo)?;
+    let write_commit_graph = if out.loose_objects.is_some() {
+        boolean(repo, &Gc::WRITE_COMMIT_GRAPH, true)?
+    } else {
+        let limit = unsigned_integer(repo, &maintenance::CommitGraph::AUTO, DEFAULT_COMMIT_GRAPH_AUTO)?;
+        limit != 0 && num_commits_missing_from_commit_graph(repo, &tips, limit)? >= limit
+    };
+    if write_commit_graph {
+        progress.set_name("write commit-graph".into());
+        out.commit_graph = repo.write_commit_graph(tips)?;
+    }
+    Ok(out)
+}
+
+fn boolean(repo: &Repository, key: &'static keys::Boolean, default: bool) -> Result<bool, Error> {
+    Ok(repo
+        .config
+        .resolved
+        .boolean(key)
+        .map(|value| key.enrich_error(value))
+        .transpose()
+        .with_leniency(repo.config.lenient_config)?
+        .unwrap_or(default))
+}
+
+fn unsigned_integer(repo: &Repository, key: &'static keys::UnsignedInteger, default: u64) -> Result<u64, Error> {
+    Ok(repo
+        .config
+        .resolved
+        .integer(key)
+        .map(|value| key.try_into_u64(value))
+        .transpose()
+        .with_leniency(repo.config.lenient_config)?
+        .unwrap_or(default))
+}
+
+/// Like `git`, assume objects are evenly distributed and only count the loose objects in `objects/17` to compare them
+/// with the share of `limit` that would fall into this directory.
+fn too_many_loose_objects(repo: &Repository, limit: u64) -> Result<bool, Error> {
+    let dir = repo.objects.store_ref().path().join("17");
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(err) => return Err(io_err(&dir)(err)),
+    };
+    let name_len = repo.object_hash().len_in_hex() - 2;
+    let mut count = 0;
+    for entry in entries {
+        let entry = entry.map_err(io_err(&dir))?;
+        let name = entry.file_name();
+        if name.to_str().map_or(false, |name| {
+            name.len() == name_len && name.bytes().all(|b| b.is_ascii_hexdigit())
+        }) {
+            count += 1;
+        }
+    }
+    Ok(count > (limit + 255) / 256)
+}
+
+/// Write all loose objects into a new pack and remove them once it's in place.
+fn pack_loose_objects(
+    repo: &Repository,
+    progress: &mut dyn crate::DynNestedProgress,
+    should_interrupt: &AtomicBool,
+) -> Result<Option<LooseObjects>, Error> {
+    let objects_dir = repo.objects.store_ref().path();
+    let loose = gix_odb::loose::Store::at(objects_dir, repo.object_hash());
+    let ids: Vec<ObjectId> = loose.iter().filter_map(Result::ok).collect();
+    if ids.is_empty() {
+        return Ok(None);
+    }
+
+    let mut db = repo
+        .objects
+        .clone()
+        .into_arc()
+        .map_err(Error::OpenObjectDatabase)?
+        .into_inner();
+    db.prevent_pack_unload();
+    let counts = {
+        let mut progress = progress.add_child("counting".into());
+        progress.init(Some(ids.len()), progress::count("objects"));
+        let (counts, _outcome) = gix_pack::data::output::count::objects_unthreaded(
+            &db,
+            &mut ids
+                .iter()
+                .copied()
+                .map(Ok::<_, Box<dyn std::error::Error + Send + Sync + 'static>>),
+            &progress,
+            should_interrupt,
+            gix_pack::data::output::count::objects::ObjectExpansion::AsIs,
+        )?;
+        counts
+    };
+
+    let num_objects = counts.len();
+    let entries = InOrderIter::from(gix_pack::data::output::entry::iter_from_counts(
+        counts,
+        db,
+        Box::new(progress.add_child("creating entries".into())),
+        Default::default(),
+    ));
+    let mut pack = Vec::new();
+    {
+        let mut write_progress = progress.add_child("writing".into());
+        write_progress.init(None, progress::bytes());
+        for written in gix_pack::data::output::bytes::FromEntriesIter::new(
+            entries,
+            &mut pack,
+            num_objects as u32,
+            gix_pack::data::Version::V2,
+            repo.object_hash(),
+        ) {
+            write_progress.inc_by(written? as usize);
+            if should_interrupt.load(Ordering::Relaxed) {
+                return Err(Error::Interrupted);
+            }
+        }
+    }
+
+    let mut write_pack_bundle = gix_pack::Bundle::write_to_directory(
+        &mut pack.as_slice(),
+        Some(&objects_dir.join("pack")),
+        progress,
+        should_interrupt,
+        None::<gix_object::find::Never>,
+        gix_pack::bundle::write::Options {
+            object_hash: repo.object_hash(),
+            ..Default::default()
+        },
+    )?;
+
+    // Only remove loose objects if they all made it into the pack, which is kept until then to protect it from other
+    // processes performing maintenance.
+    let mut num_removed = 0;
+    if write_pack_bundle.index.num_objects as usize == ids.len() {
+        let mut dirs = BTreeSet::new();
+        for id in &ids {
+            let path = loose.object_path(id);
+            match std::fs::remove_file(&path) {
+                Ok(()) => num_removed += 1,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(io_err(&path)(err)),
+            }
+            dirs.extend(path.parent().map(ToOwned::to_owned));
+        }
+        for dir in dirs {
+            // It's fine if the directory isn't empty as new loose objects were written in the meantime.
+            std::fs::remove_dir(dir).ok();
+        }
+    }
+    if let Some(path) = write_pack_bundle.keep_path.take() {
+        std::fs::remove_file(&path).map_err(io_err(&path))?;
+    }
+    Ok(Some(LooseObjects {
+        write_pack_bundle,
+        num_removed,
+    }))
+}
+
+/// Return the ids of all commits that references and `HEAD` point to after peeling.
+fn reference_tips(repo: &Repository) -> Result<Vec<ObjectId>, Error> {
+    let mut tips = Vec::new();
+    for reference in repo.references()?.all()? {
+        if let Ok(id) = reference?.peel_to_id_in_place() {
+            tips.push(id.detach());
+        }
+    }
+    tips.extend(repo.head_id().ok().map(crate::Id::detach));
+    tips.sort();
+    tips.dedup();
+    let mut commits = Vec::with_capacity(tips.len());
+    for id in tips {
+        if repo.find_header(id)?.kind() == gix_object::Kind::Commit {
+            commits.push(id);
+        }
+    }
+    Ok(commits)
+}
+
+/// Count the commits reachable from `tips` which aren't in the commit-graph, but stop once `limit` is reached.
+fn num_commits_missing_from_commit_graph(repo: &Repository, tips: &[ObjectId], limit: u64) -> Result<u64, Error> {
+    let graph = repo.commit_graph_if_enabled()?;
+    let is_missing = move |id: &gix_hash::oid| graph.as_ref().map_or(true, |graph| graph.lookup(id).is_none());
+    let tips: Vec<_> = tips.iter().copied().filter(|id| is_missing(id.as_ref())).collect();
+    if tips.is_empty() {
+        return Ok(0);
+    }
+    let mut count = 0;
+    for info in repo.rev_walk(tips).selected(is_missing)? {
+        info?;
+        count += 1;
+        if count >= limit {
+            break;
+        }
+    }
+    Ok(count)
+}
+
+fn io_err(path: &Path) -> impl FnOnce(std::io::Error) -> Error + '_ {
+    move |source| Error::Io {
+        source,
+        path: path.to_owned(),
+    }
+}
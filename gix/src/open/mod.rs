@@ -35,10 +35,35 @@ pub struct Options {
     pub(crate) api_config_overrides: Vec<BString>,
     pub(crate) cli_config_overrides: Vec<BString>,
     pub(crate) open_path_as_is: bool,
+    pub(crate) lazy: bool,
     /// Internal to pass an already obtained CWD on to where it may also be used. This avoids the CWD being queried more than once per repo.
     pub(crate) current_dir: Option<PathBuf>,
 }
 
+/// The amount of slots for indices of the object database if [opening lazily](Options::lazy()).
+pub const LAZY_OBJECT_STORE_SLOTS: u16 = 256;
+
+/// The time spent in each phase of opening a repository, as obtained by [`Repository::open_metrics()`](crate::Repository::open_metrics()).
+///
+/// Note that the time it takes to find the repository, or to discover it, isn't included.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Metrics {
+    /// The time it took to read the repository-local configuration file.
+    pub repository_config: std::time::Duration,
+    /// The time it took to set up the reference store and to read `HEAD`.
+    pub references: std::time::Duration,
+    /// The time it took to load all other configuration files, apply overrides and extract often-used values.
+    pub config: std::time::Duration,
+    /// The time it took to check for safe directories and to determine the worktree directory.
+    pub worktree: std::time::Duration,
+    /// The time it took to find replacement objects in references, which typically involves reading the `packed-refs` file.
+    pub replacement_objects: std::time::Duration,
+    /// The time it took to set up the object database, which may involve listing its pack indices.
+    pub object_store: std::time::Duration,
+    /// The time it took to open the repository, which is the sum of all phases.
+    pub total: std::time::Duration,
+}
+
 /// The error returned by [`crate::open()`].
 #[derive(Debug, thiserror::Error)]
 #[allow(missing_docs)]
@@ -14,6 +14,7 @@ impl Default for Options {
             lenient_config: true,
             bail_if_untrusted: false,
             open_path_as_is: false,
+            lazy: false,
             api_config_overrides: Vec::new(),
             cli_config_overrides: Vec::new(),
             current_dir: None,
@@ -142,6 +143,23 @@ impl Options {
         self
     }
 
+    /// If `true`, default `false`, defer work that isn't needed to open the repository until it's first needed,
+    /// to open as quickly as possible. This is useful for tools that are invoked very often, like shell prompts.
+    ///
+    /// * The object database doesn't list its pack indices to determine how many it has to hold, but instead
+    ///   uses up to [`LAZY_OBJECT_STORE_SLOTS`](crate::open::LAZY_OBJECT_STORE_SLOTS) of them, unless slots
+    ///   were given explicitly with [`object_store_slots()`](Self::object_store_slots()).
+    /// * [Replacement objects](Options#replacement-objects-for-the-object-database) are not used, as finding them
+    ///   requires reading the `packed-refs` file, which then is only read once references are accessed.
+    ///
+    /// Note that the commit-graph and the index are always loaded on first use, while all configuration is always loaded
+    /// when opening the repository as it affects how the repository is opened.
+    /// Use [`Repository::open_metrics()`](crate::Repository::open_metrics()) to learn where time is spent.
+    pub fn lazy(mut self, toggle: bool) -> Self {
+        self.lazy = toggle;
+        self
+    }
+
     /// Open a repository at `path` with the options set so far.
     #[allow(clippy::result_large_err)]
     pub fn open(self, path: impl Into<PathBuf>) -> Result<ThreadSafeRepository, Error> {
@@ -167,6 +185,7 @@ impl gix_sec::trust::DefaultForLevel for Options {
                 bail_if_untrusted: false,
                 lenient_config: true,
                 open_path_as_is: false,
+                lazy: false,
                 api_config_overrides: Vec::new(),
                 cli_config_overrides: Vec::new(),
                 current_dir: None,
@@ -179,6 +198,7 @@ impl gix_sec::trust::DefaultForLevel for Options {
                 bail_if_untrusted: false,
                 lenient_config: true,
                 open_path_as_is: false,
+                lazy: false,
                 lossy_config: None,
                 api_config_overrides: Vec::new(),
                 cli_config_overrides: Vec::new(),
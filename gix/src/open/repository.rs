@@ -3,7 +3,7 @@ use gix_features::threading::OwnShared;
 use std::ffi::OsStr;
 use std::{borrow::Cow, path::PathBuf};
 
-use super::{Error, Options};
+use super::{Error, Metrics, Options};
 use crate::{
     config,
     config::{
@@ -154,6 +154,14 @@ impl ThreadSafeRepository {
         mut options: Options,
     ) -> Result<Self, Error> {
         let _span = gix_trace::detail!("open_from_paths()");
+        let start = std::time::Instant::now();
+        let mut metrics = Metrics::default();
+        let mut phase = start;
+        let mut end_phase = |duration: &mut std::time::Duration| {
+            let now = std::time::Instant::now();
+            *duration = now - phase;
+            phase = now;
+        };
         let Options {
             git_dir_trust,
             object_store_slots,
@@ -162,6 +170,7 @@ impl ThreadSafeRepository {
             lenient_config,
             bail_if_untrusted,
             open_path_as_is: _,
+            lazy,
             permissions:
                 Permissions {
                     ref env,
@@ -184,6 +193,7 @@ impl ThreadSafeRepository {
             lossy_config,
             lenient_config,
         )?;
+        end_phase(&mut metrics.repository_config);
 
         if repo_config.precompose_unicode {
             git_dir = gix_utils::str::precompose_path(git_dir.into()).into_owned();
@@ -227,6 +237,7 @@ impl ThreadSafeRepository {
             }
         };
         let head = refs.find("HEAD").ok();
+        end_phase(&mut metrics.references);
         let git_install_dir = crate::path::install_dir().ok();
         let home = gix_path::env::home_dir().and_then(|home| env.home.check_opt(home));
 
@@ -245,6 +256,7 @@ impl ThreadSafeRepository {
             api_config_overrides,
             cli_config_overrides,
         )?;
+        end_phase(&mut metrics.config);
 
         if bail_if_untrusted && git_dir_trust != gix_sec::Trust::Full {
             check_safe_directories(
@@ -316,7 +328,10 @@ impl ThreadSafeRepository {
 
         refs.write_reflog = config::cache::util::reflog_or_default(config.reflog, worktree_dir.is_some());
         refs.namespace.clone_from(&config.refs_namespace);
+        end_phase(&mut metrics.worktree);
+
         let replacements = replacement_objects_refs_prefix(&config.resolved, lenient_config, filter_config_section)?
+            .filter(|_| !lazy)
             .and_then(|prefix| {
                 let _span = gix_trace::detail!("find replacement objects");
                 let platform = refs.iter().ok()?;
@@ -334,18 +349,28 @@ impl ThreadSafeRepository {
                 Some(replacements)
             })
             .unwrap_or_default();
+        end_phase(&mut metrics.replacement_objects);
 
-        Ok(ThreadSafeRepository {
-            objects: OwnShared::new(gix_odb::Store::at_opts(
-                common_dir_ref.join("objects"),
-                &mut replacements.into_iter(),
-                gix_odb::store::init::Options {
-                    slots: object_store_slots,
-                    object_hash: config.object_hash,
-                    use_multi_pack_index: config.use_multi_pack_index,
-                    current_dir: current_dir.to_owned().into(),
+        let objects = OwnShared::new(gix_odb::Store::at_opts(
+            common_dir_ref.join("objects"),
+            &mut replacements.into_iter(),
+            gix_odb::store::init::Options {
+                slots: match object_store_slots {
+                    gix_odb::store::init::Slots::AsNeededByDiskState { .. } if lazy => {
+                        gix_odb::store::init::Slots::Given(super::LAZY_OBJECT_STORE_SLOTS)
+                    }
+                    slots => slots,
                 },
-            )?),
+                object_hash: config.object_hash,
+                use_multi_pack_index: config.use_multi_pack_index,
+                current_dir: current_dir.to_owned().into(),
+            },
+        )?);
+        end_phase(&mut metrics.object_store);
+        metrics.total = start.elapsed();
+
+        Ok(ThreadSafeRepository {
+            objects,
             common_dir,
             refs,
             work_tree: worktree_dir,
@@ -357,6 +382,9 @@ impl ThreadSafeRepository {
             shallow_commits: gix_fs::SharedFileSnapshotMut::new().into(),
             #[cfg(feature = "attributes")]
             modules: gix_fs::SharedFileSnapshotMut::new().into(),
+            #[cfg(feature = "excludes")]
+            worktree_cache: None,
+            open_metrics: metrics,
         })
     }
 }
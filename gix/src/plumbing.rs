@@ -0,0 +1,95 @@
+//! Library entry points for plumbing operations that are otherwise only available through the `gix` and `ein` binaries,
+//! each with a typed outcome.
+//!
+//! Besides [`Repository::explode_pack()`](crate::Repository::explode_pack()) and
+//! [`Repository::verify_index()`](crate::Repository::verify_index()), see
+//! [`Repository::stats()`](crate::Repository::stats()) for object database statistics and
+//! [`Repository::verify_commit_graph()`](crate::Repository::verify_commit_graph()) to inspect the commit-graph.
+#![allow(clippy::empty_docs)]
+
+///
+pub mod explode_pack {
+    use std::path::PathBuf;
+
+    /// Options for use in [`Repository::explode_pack()`](crate::Repository::explode_pack()).
+    #[derive(Debug, Clone, Default)]
+    pub struct Options {
+        /// The directory to write loose objects into, or the object database of the repository if `None`, which is the default.
+        pub destination: Option<PathBuf>,
+        /// If `true`, each object is written even if it already exists in the destination.
+        /// Otherwise, which is the default, existing loose objects are skipped.
+        pub overwrite_existing: bool,
+    }
+
+    /// The outcome of [`Repository::explode_pack()`](crate::Repository::explode_pack()).
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct Outcome {
+        /// The amount of objects in the pack.
+        pub objects: u64,
+        /// The amount of objects that were written as loose objects, which is less than `objects` if some existed already.
+        pub written: u64,
+        /// The amount of commits in the pack.
+        pub commits: u64,
+        /// The amount of trees in the pack.
+        pub trees: u64,
+        /// The amount of blobs in the pack.
+        pub blobs: u64,
+        /// The amount of tags in the pack.
+        pub tags: u64,
+    }
+
+    /// The error returned by [`Repository::explode_pack()`](crate::Repository::explode_pack()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not open the pack or its index")]
+        Open(#[from] gix_pack::bundle::init::Error),
+        #[error("Could not decode object {id} from the pack")]
+        Decode {
+            id: gix_hash::ObjectId,
+            source: gix_pack::data::decode::Error,
+        },
+        #[error("Could not write object {id} into '{}'", destination.display())]
+        Write {
+            id: gix_hash::ObjectId,
+            destination: PathBuf,
+            source: gix_odb::write::Error,
+        },
+        #[error("Object {expected} was written as {actual}, the pack is corrupt")]
+        ObjectHashMismatch {
+            expected: gix_hash::ObjectId,
+            actual: gix_hash::ObjectId,
+        },
+        #[error("Interrupted")]
+        Interrupted,
+    }
+}
+
+///
+#[cfg(feature = "index")]
+pub mod verify_index {
+    /// The outcome of [`Repository::verify_index()`](crate::Repository::verify_index()).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Outcome {
+        /// The version of the index file.
+        pub version: gix_index::Version,
+        /// The amount of entries in the index.
+        pub entries: usize,
+        /// The checksum of the index file, or `None` if it wasn't written with one.
+        pub checksum: Option<gix_hash::ObjectId>,
+    }
+
+    /// The error returned by [`Repository::verify_index()`](crate::Repository::verify_index()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        OpenIndex(#[from] crate::worktree::open_index::Error),
+        #[error(transparent)]
+        Checksum(#[from] gix_index::file::verify::Error),
+        #[error(transparent)]
+        Entries(#[from] gix_index::verify::entries::Error),
+        #[error(transparent)]
+        Extensions(#[from] gix_index::verify::extensions::Error),
+    }
+}
@@ -20,6 +20,25 @@ pub mod edit {
     }
 }
 
+///
+pub mod symbolic {
+    use crate::config;
+
+    /// The error returned by [`Repository::symbolic_reference(…)`](crate::Repository::symbolic_reference()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Symbolic(#[from] gix_ref::file::transaction::symbolic::Error),
+        #[error(transparent)]
+        NameValidation(#[from] gix_validate::reference::name::Error),
+        #[error("Could not interpret core.filesRefLockTimeout or core.packedRefsTimeout, it must be the number in milliseconds to wait for locks or negative to wait forever")]
+        LockTimeoutConfiguration(#[from] config::lock_timeout::Error),
+        #[error(transparent)]
+        ParseCommitterTime(#[from] crate::config::time::Error),
+    }
+}
+
 ///
 pub mod peel {
     /// The error returned by [`Reference::peel_to_id_in_place(…)`](crate::Reference::peel_to_id_in_place()) and
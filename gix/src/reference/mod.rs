@@ -10,7 +10,7 @@ pub mod iter;
 pub mod remote;
 
 mod errors;
-pub use errors::{edit, find, follow, head_commit, head_id, head_tree_id, peel};
+pub use errors::{edit, find, follow, head_commit, head_id, head_tree_id, peel, symbolic};
 
 use crate::ext::ObjectIdExt;
 
@@ -27,6 +27,34 @@ impl<'repo> Remote<'repo> {
         self.fetch_tags
     }
 
+    /// Return `true` if local references that are written by our fetch ref-specs are deleted when fetching if they don't
+    /// exist on the remote anymore, as configured by `remote.<name>.prune` or `fetch.prune`.
+    pub fn prune(&self) -> bool {
+        self.prune
+    }
+
+    /// Return `true` if local tags are deleted when fetching if they don't exist on the remote anymore, which only has an effect
+    /// if [pruning](Self::prune()) is enabled, as configured by `remote.<name>.pruneTags` or `fetch.pruneTags`.
+    pub fn prune_tags(&self) -> bool {
+        self.prune_tags
+    }
+
+    /// Return `true` if pushing without ref-specs pushes all local references and deletes those on the remote that don't exist
+    /// locally, as configured by `remote.<name>.mirror`.
+    pub fn mirror(&self) -> bool {
+        self.mirror
+    }
+
+    /// Return how tags are handled when fetching, which is as if all tags are fetched if tags are pruned.
+    #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+    pub(crate) fn effective_fetch_tags(&self) -> remote::fetch::Tags {
+        if self.prune && self.prune_tags {
+            remote::fetch::Tags::All
+        } else {
+            self.fetch_tags
+        }
+    }
+
     /// Return the url used for the given `direction` with rewrites from `url.<base>.insteadOf|pushInsteadOf`, unless the instance
     /// was created with one of the `_without_url_rewrite()` methods.
     /// For pushing, this is the `remote.<name>.pushUrl` or the `remote.<name>.url` used for fetching, and for fetching it's
@@ -1,4 +1,7 @@
-use crate::{bstr::BStr, remote, Remote};
+use crate::{
+    bstr::{BStr, BString, ByteVec},
+    remote, Remote,
+};
 
 /// Builder methods
 impl Remote<'_> {
@@ -33,6 +36,27 @@ impl Remote<'_> {
         self
     }
 
+    /// If `enabled`, delete local references that are written by our fetch ref-specs but don't exist on the remote anymore
+    /// when fetching, similar to `git fetch --prune`.
+    pub fn with_prune(mut self, enabled: bool) -> Self {
+        self.prune = enabled;
+        self
+    }
+
+    /// If `enabled` along with [pruning](Self::with_prune()), also fetch all tags and delete local tags that don't exist
+    /// on the remote anymore, as if `refs/tags/*:refs/tags/*` was one of our fetch ref-specs, similar to `git fetch --prune-tags`.
+    pub fn with_prune_tags(mut self, enabled: bool) -> Self {
+        self.prune_tags = enabled;
+        self
+    }
+
+    /// If `enabled`, push all local references and delete those on the remote that don't exist locally if neither the remote
+    /// nor the caller provide push ref-specs, similar to `git push --mirror`.
+    pub fn with_mirror(mut self, enabled: bool) -> Self {
+        self.mirror = enabled;
+        self
+    }
+
     fn push_url_inner(
         mut self,
         push_url: gix_url::Url,
@@ -48,6 +72,51 @@ impl Remote<'_> {
         Ok(self)
     }
 
+    /// Place all references written by fetch refspecs into `namespace`, so that `+refs/heads/*:refs/remotes/origin/*` becomes
+    /// `+refs/heads/*:refs/namespaces/<namespace>/refs/remotes/origin/*`, and disable fetching tags as these would otherwise
+    /// be written outside of the namespace.
+    ///
+    /// This is useful to fetch arbitrary remotes into a repository that already has unrelated references and objects,
+    /// like a cache of objects shared among many remotes, without affecting any of its existing references.
+    /// When used with an anonymous remote as obtained with [`Repository::remote_at()`](crate::Repository::remote_at()),
+    /// the configuration of the repository is also left untouched.
+    /// This works with [shallow fetches](crate::remote::fetch::Shallow) of single branches as well, which
+    /// make the repository shallow without cutting off the history of commits that were already present.
+    ///
+    /// Note that specs without destination as well as negative specs are kept as is. Tag refspecs can be added afterwards
+    /// to have them end up in the namespace, too.
+    pub fn with_fetch_namespace(mut self, namespace: &gix_ref::Namespace) -> Result<Self, gix_refspec::parse::Error> {
+        use gix_refspec::{instruction::Fetch, Instruction};
+
+        let specs = std::mem::take(&mut self.fetch_specs);
+        for spec in &specs {
+            let spec = spec.to_ref();
+            let namespaced = match spec.instruction() {
+                Instruction::Fetch(Fetch::AndUpdate {
+                    src,
+                    dst,
+                    allow_non_fast_forward,
+                }) => {
+                    let mut out = BString::default();
+                    if allow_non_fast_forward {
+                        out.push(b'+');
+                    }
+                    out.push_str(src);
+                    out.push(b':');
+                    out.push_str(namespace.as_bstr());
+                    out.push_str(dst);
+                    gix_refspec::parse(out.as_ref(), gix_refspec::parse::Operation::Fetch)?.to_owned()
+                }
+                _ => spec.to_owned(),
+            };
+            if !self.fetch_specs.contains(&namespaced) {
+                self.fetch_specs.push(namespaced);
+            }
+        }
+        self.fetch_tags = remote::fetch::Tags::None;
+        Ok(self)
+    }
+
     /// Add `specs` as refspecs for `direction` to our list if they are unique, or ignore them otherwise.
     pub fn with_refspecs<Spec>(
         mut self,
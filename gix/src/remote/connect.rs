@@ -65,6 +65,7 @@ impl<'repo> Remote<'repo> {
             transport_options: None,
             transport,
             trace,
+            session_id: None,
         }
     }
 
@@ -85,6 +86,10 @@ impl<'repo> Remote<'repo> {
         let (url, version) = self.sanitized_url_and_version(direction)?;
         #[cfg(feature = "blocking-network-client")]
         let scheme_is_ssh = url.scheme == gix_url::Scheme::Ssh;
+        #[cfg(feature = "blocking-network-client")]
+        let proxy = (url.scheme == gix_url::Scheme::Git)
+            .then(|| self.git_proxy(&url))
+            .flatten();
         let transport = gix_protocol::transport::connect(
             url,
             gix_protocol::transport::client::connect::Options {
@@ -94,6 +99,8 @@ impl<'repo> Remote<'repo> {
                     .then(|| self.repo.ssh_connect_options())
                     .transpose()?
                     .unwrap_or_default(),
+                #[cfg(feature = "blocking-network-client")]
+                proxy,
                 trace: self.repo.config.trace_packet(),
             },
         )
@@ -101,6 +108,40 @@ impl<'repo> Remote<'repo> {
         Ok(self.to_connection_with_transport(transport))
     }
 
+    /// Return the SOCKS5 proxy to use when connecting to `url` via the `git://` protocol.
+    ///
+    /// As `git://` can't be proxied by HTTP proxies, only `socks5://` and `socks5h://` values of `remote.<name>.proxy`,
+    /// `http.proxy` or `ALL_PROXY` are used, unless the host of `url` is excluded by `NO_PROXY`.
+    #[cfg(feature = "blocking-network-client")]
+    fn git_proxy(&self, url: &gix_url::Url) -> Option<String> {
+        use crate::bstr::ByteSlice;
+        let config = &self.repo.config.resolved;
+        let mut trusted_only = self.repo.filter_config_section();
+        let proxy = self
+            .name()
+            .and_then(|name| config.string_filter(format!("remote.{}.proxy", name.as_bstr()), &mut trusted_only))
+            .or_else(|| config.string_filter("http.proxy", &mut trusted_only))
+            .or_else(|| config.string_filter("gitoxide.http.allProxy", &mut trusted_only))?;
+        let proxy = proxy.to_str().ok()?.trim();
+        gix_protocol::transport::client::socks5::Proxy::from_url(proxy)?;
+
+        let host = url.host()?;
+        let is_excluded = config
+            .string_filter("gitoxide.http.noProxy", &mut trusted_only)
+            .map_or(false, |no_proxy| {
+                no_proxy.split_str(",").any(|pattern| {
+                    let pattern = pattern.trim().trim_start_with(|c| c == '.');
+                    pattern == b"*"
+                        || pattern == host.as_bytes()
+                        || host
+                            .as_bytes()
+                            .strip_suffix(pattern)
+                            .map_or(false, |prefix| prefix.ends_with(b"."))
+                })
+            });
+        (!is_excluded).then(|| proxy.to_owned())
+    }
+
     /// Produce the sanitized URL and protocol version to use as obtained by querying the repository configuration.
     ///
     /// This can be useful when using custom transports to allow additional configuration.
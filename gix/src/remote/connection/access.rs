@@ -35,6 +35,15 @@ impl<'a, T> Connection<'a, '_, T> {
         self.transport_options = Some(config);
         self
     }
+
+    /// Send `id` to identify the operations performed with this connection in the logs of the remote, instead of the
+    /// [id of this process](crate::env::session_id()).
+    ///
+    /// Note that it's only sent if `transfer.advertiseSID` is enabled and the remote supports the `session-id` capability.
+    pub fn with_session_id(mut self, id: impl Into<String>) -> Self {
+        self.session_id = Some(id.into());
+        self
+    }
 }
 
 /// Mutation
@@ -53,6 +62,12 @@ impl<'a, T> Connection<'a, '_, T> {
         self.transport_options = Some(config);
         self
     }
+
+    /// Like [`with_session_id()`](Self::with_session_id()), but without consuming the connection.
+    pub fn set_session_id(&mut self, id: impl Into<String>) -> &mut Self {
+        self.session_id = Some(id.into());
+        self
+    }
 }
 
 /// Access
@@ -70,6 +85,17 @@ impl<'repo, T> Connection<'_, 'repo, T> {
             self.remote.repo.config_snapshot().credential_helpers(url)?;
         Ok(Box::new(move |action| cascade.invoke(action, prompt_opts.clone())) as AuthenticateFn<'_>)
     }
+    /// Return the session id to send to a remote with `capabilities`, if `transfer.advertiseSID` is enabled and
+    /// the remote supports it.
+    #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+    pub(crate) fn session_id_to_send(
+        &self,
+        capabilities: &gix_protocol::transport::client::Capabilities,
+    ) -> Option<&str> {
+        (self.remote.repo.config.advertise_session_id() && capabilities.contains("session-id"))
+            .then(|| self.session_id.as_deref().unwrap_or_else(|| crate::env::session_id()))
+    }
+
     /// Return the underlying remote that instantiate this connection.
     pub fn remote(&self) -> &Remote<'repo> {
         self.remote
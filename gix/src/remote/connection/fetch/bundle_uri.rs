@@ -0,0 +1,439 @@
+use std::{
+    io::{BufRead, Read},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use gix_hash::ObjectId;
+use gix_protocol::{bundle_uri::List, transport::client::Transport};
+use gix_ref::transaction::PreviousValue;
+
+use crate::{
+    bstr::{BStr, ByteSlice},
+    remote::fetch::{outcome, Error},
+    Repository,
+};
+
+/// The prefix of the references that the branches of each bundle are written to, like `git` does.
+const BUNDLE_REF_PREFIX: &str = "refs/bundles/";
+
+/// Ask the remote for its bundle list via the `bundle-uri` command, and download and unbundle all bundles that are needed
+/// so that the following negotiation only has to fetch what's missing on top of them.
+///
+/// Obtaining bundles is done on a best-effort basis, as they are merely an optimization, so only errors that leave the
+/// connection in an unknown state are fatal.
+#[gix_protocol::maybe_async::maybe_async]
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn obtain(
+    transport: &mut impl Transport,
+    capabilities: &gix_protocol::transport::client::Capabilities,
+    trace: bool,
+    repo: &Repository,
+    remote_url: Option<&gix_url::Url>,
+    progress: &mut dyn crate::DynNestedProgress,
+    should_interrupt: &AtomicBool,
+    options: gix_pack::bundle::write::Options,
+) -> Result<Vec<outcome::Bundle>, Error> {
+    let _span = gix_trace::coarse!("fetch::bundle_uri::obtain()");
+    progress.set_name("list bundles".into());
+    let list = match gix_protocol::bundle_uri(transport, capabilities, repo.config.user_agent_tuple(), trace).await {
+        Ok(list) => list,
+        Err(
+            err @ (gix_protocol::bundle_uri::Error::Io(_)
+            | gix_protocol::bundle_uri::Error::Transport(_)
+            | gix_protocol::bundle_uri::Error::DecodePacketline(_)),
+        ) => return Err(err.into()),
+        Err(_err) => {
+            gix_trace::warn!("ignoring bundle list: {_err}");
+            return Ok(Vec::new());
+        }
+    };
+    let mut out = Vec::new();
+    apply(
+        repo,
+        list,
+        remote_url,
+        0,
+        progress,
+        should_interrupt,
+        &options,
+        &mut out,
+    );
+    Ok(out)
+}
+
+/// Obtain the bundles of `list` whose relative URIs are based on `base_url`, and add their outcomes to `out`.
+///
+/// Returns `true` if at least one bundle could be obtained.
+#[allow(clippy::too_many_arguments)]
+fn apply(
+    repo: &Repository,
+    mut list: List,
+    base_url: Option<&gix_url::Url>,
+    depth: usize,
+    progress: &mut dyn crate::DynNestedProgress,
+    should_interrupt: &AtomicBool,
+    options: &gix_pack::bundle::write::Options,
+    out: &mut Vec<outcome::Bundle>,
+) -> bool {
+    // Bundles without creation token are assumed to be independent, and those with one are applied from oldest to newest.
+    list.bundles.sort_by_key(|bundle| bundle.creation_token);
+    let mut obtained_any = false;
+    for bundle in list.bundles {
+        if should_interrupt.load(Ordering::Relaxed) {
+            break;
+        }
+        let Some(uri) = bundle.uri else {
+            continue;
+        };
+        progress.set_name(format!("unbundle {}", bundle.id));
+        let res = resolve(uri.as_ref(), base_url)
+            .ok_or_else(|| bundle::Error::UnsupportedUri { uri: uri.clone() })
+            .and_then(|url| open(&url).map(|rd| (url, rd)))
+            .and_then(|(url, mut rd)| {
+                if bundle::Header::is_bundle(&mut rd)? {
+                    return Ok(Some(unbundle(repo, rd, progress, should_interrupt, options.clone())?));
+                }
+                if depth >= MAX_LIST_DEPTH {
+                    return Err(bundle::Error::ListTooDeep);
+                }
+                let nested_list = read_list(rd)?;
+                let obtained = apply(
+                    repo,
+                    nested_list,
+                    Some(&directory_of(url)),
+                    depth + 1,
+                    progress,
+                    should_interrupt,
+                    options,
+                    out,
+                );
+                obtained_any |= obtained;
+                Ok(None)
+            });
+        let (write_pack_bundle, refs) = match res {
+            Ok(Some((write_pack_bundle, refs))) => (Some(write_pack_bundle), refs),
+            Ok(None) => {
+                if obtained_any && list.mode == gix_protocol::bundle_uri::Mode::Any {
+                    break;
+                }
+                continue;
+            }
+            Err(_err) => {
+                gix_trace::warn!("ignoring bundle at {uri:?}: {_err}");
+                (None, Vec::new())
+            }
+        };
+        let is_obtained = write_pack_bundle.is_some();
+        obtained_any |= is_obtained;
+        out.push(outcome::Bundle {
+            uri,
+            write_pack_bundle,
+            refs,
+        });
+        if is_obtained && list.mode == gix_protocol::bundle_uri::Mode::Any {
+            break;
+        }
+    }
+    obtained_any
+}
+
+/// The maximum amount of bundle lists that may refer to each other, to prevent endless recursion.
+const MAX_LIST_DEPTH: usize = 4;
+
+/// Parse the bundle list in `git config` format from `rd`, as it may be served in place of a bundle.
+fn read_list(mut rd: Box<dyn BufRead>) -> Result<List, bundle::Error> {
+    let mut buf = Vec::new();
+    rd.read_to_end(&mut buf)?;
+    let config = gix_config::File::from_bytes_no_includes(&buf, gix_config::file::Metadata::api(), Default::default())?;
+    let mut lines = Vec::new();
+    for section in config.sections_by_name("bundle").into_iter().flatten() {
+        let prefix = match section.header().subsection_name() {
+            Some(id) => format!("bundle.{id}."),
+            None => "bundle.".into(),
+        };
+        for (key, value) in section.body().clone() {
+            lines.push(format!(
+                "{prefix}{key}={value}",
+                key = key.to_string().to_ascii_lowercase()
+            ));
+        }
+    }
+    Ok(List::from_lines(lines.iter().map(|line| line.as_str().into()))?)
+}
+
+/// Return `url` without its last path component, so relative URIs in the bundle list it points to can be resolved.
+fn directory_of(mut url: gix_url::Url) -> gix_url::Url {
+    if let Some(pos) = url.path.rfind_byte(b'/') {
+        url.path.truncate(pos + 1);
+    }
+    url
+}
+
+/// Read the bundle from `rd`, write its pack to the object database, and write its branches to `refs/bundles/`.
+fn unbundle(
+    repo: &Repository,
+    mut rd: Box<dyn BufRead>,
+    progress: &mut dyn crate::DynNestedProgress,
+    should_interrupt: &AtomicBool,
+    options: gix_pack::bundle::write::Options,
+) -> Result<(gix_pack::bundle::write::Outcome, Vec<(gix_ref::FullName, ObjectId)>), bundle::Error> {
+    let header = bundle::Header::from_read(&mut rd, repo.object_hash())?;
+    if let Some(id) = header.prerequisites.iter().find(|id| !repo.has_object(id)) {
+        return Err(bundle::Error::MissingPrerequisite { id: *id });
+    }
+
+    let mut write_pack_bundle = gix_pack::Bundle::write_to_directory(
+        &mut rd,
+        Some(&repo.objects.store_ref().path().join("pack")),
+        progress,
+        should_interrupt,
+        Some(Box::new({
+            let repo = repo.clone();
+            repo.objects
+        })),
+        options,
+    )?;
+
+    // Checking for the tips also assures the object database picks up the new pack before negotiation starts.
+    let mut refs = Vec::new();
+    for (name, id) in header.refs {
+        if !repo.has_object(id) {
+            continue;
+        }
+        if let Some(branch) = name.as_bstr().strip_prefix(b"refs/heads/") {
+            let bundle_ref = format!("{BUNDLE_REF_PREFIX}{}", branch.as_bstr());
+            if let Err(_err) = repo.reference(bundle_ref.as_str(), id, PreviousValue::Any, "fetch: bundle-uri") {
+                gix_trace::warn!("could not write reference {bundle_ref:?} of bundle: {_err}");
+            }
+        }
+        refs.push((name, id));
+    }
+    if let Some(path) = write_pack_bundle.keep_path.take() {
+        std::fs::remove_file(path)?;
+    }
+    Ok((write_pack_bundle, refs))
+}
+
+/// Turn `uri` into a URL, resolving it relative to `base_url` if it's not absolute.
+fn resolve(uri: &BStr, base_url: Option<&gix_url::Url>) -> Option<gix_url::Url> {
+    if uri.contains_str("://") || uri.starts_with(b"/") {
+        return gix_url::parse(uri).ok();
+    }
+    let mut url = base_url?.clone();
+    if !url.path.ends_with(b"/") {
+        url.path.push(b'/');
+    }
+    url.path.extend_from_slice(uri);
+    Some(url)
+}
+
+/// Open the resource at `url` for reading, supporting local files and, if HTTP transports are available, `http` and `https`.
+pub(crate) fn open(url: &gix_url::Url) -> Result<Box<dyn BufRead>, bundle::Error> {
+    match url.scheme {
+        gix_url::Scheme::File => Ok(Box::new(std::io::BufReader::new(
+            std::fs::File::open(gix_path::from_bstr(url.path.as_bstr())).map_err(bundle::Error::Open)?,
+        ))),
+        #[cfg(all(
+            feature = "blocking-network-client",
+            any(
+                feature = "blocking-http-transport-reqwest",
+                feature = "blocking-http-transport-curl"
+            )
+        ))]
+        gix_url::Scheme::Http | gix_url::Scheme::Https => {
+            use gix_protocol::transport::client::http::{self, Http};
+            let url = url.to_bstring().to_string();
+            let res = http::Impl::default().get(&url, &url, std::iter::empty::<&str>())?;
+            Ok(Box::new(res.body))
+        }
+        _ => Err(bundle::Error::UnsupportedUri { uri: url.to_bstring() }),
+    }
+}
+
+/// Parsing of files in the `git bundle` format.
+mod bundle {
+    use std::io::BufRead;
+
+    use gix_hash::ObjectId;
+
+    use crate::bstr::{BString, ByteSlice};
+
+    /// The error returned when obtaining a single bundle, which is never fatal.
+    #[derive(Debug, thiserror::Error)]
+    pub(crate) enum Error {
+        #[error("URI {uri:?} doesn't resolve to a supported location")]
+        UnsupportedUri { uri: BString },
+        #[error("Could not open bundle file")]
+        Open(#[source] std::io::Error),
+        #[cfg(all(
+            feature = "blocking-network-client",
+            any(
+                feature = "blocking-http-transport-reqwest",
+                feature = "blocking-http-transport-curl"
+            )
+        ))]
+        #[error(transparent)]
+        Http(#[from] gix_protocol::transport::client::http::Error),
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error("The bundle header is malformed: {message}")]
+        MalformedHeader { message: &'static str },
+        #[error("Bundle lists refer to each other too deeply")]
+        ListTooDeep,
+        #[error("Could not parse the bundle list")]
+        ParseListFile(#[from] gix_config::file::init::Error),
+        #[error(transparent)]
+        ParseList(#[from] gix_protocol::bundle_uri::Error),
+        #[error("The bundle needs object {id} to be present, but it is missing")]
+        MissingPrerequisite { id: ObjectId },
+        #[error(transparent)]
+        WritePack(#[from] gix_pack::bundle::write::Error),
+    }
+
+    /// The header of a bundle, which precedes the pack.
+    pub(crate) struct Header {
+        /// The objects that must be present for the pack to be complete.
+        pub(crate) prerequisites: Vec<ObjectId>,
+        /// The references contained in the bundle.
+        pub(crate) refs: Vec<(gix_ref::FullName, ObjectId)>,
+    }
+
+    impl Header {
+        /// Return `true` if `rd` is positioned at the signature line of a bundle, without consuming anything.
+        pub(crate) fn is_bundle(rd: &mut dyn BufRead) -> std::io::Result<bool> {
+            let buf = rd.fill_buf()?;
+            Ok(buf.starts_with(b"# v2 git bundle") || buf.starts_with(b"# v3 git bundle"))
+        }
+
+        /// Read the header of a version 2 or 3 bundle from `rd` and leave it positioned at the start of the pack.
+        pub(crate) fn from_read(rd: &mut dyn BufRead, object_hash: gix_hash::Kind) -> Result<Self, Error> {
+            let malformed = |message| Error::MalformedHeader { message };
+            let mut line = Vec::new();
+            if !read_line(rd, &mut line)? {
+                return Err(malformed("the bundle is empty"));
+            }
+            let is_v3 = match line.as_slice() {
+                b"# v2 git bundle" => false,
+                b"# v3 git bundle" => true,
+                _ => return Err(malformed("the signature line is unknown")),
+            };
+
+            let mut out = Header {
+                prerequisites: Vec::new(),
+                refs: Vec::new(),
+            };
+            let mut is_capability_section = is_v3;
+            loop {
+                if !read_line(rd, &mut line)? {
+                    return Err(malformed("the header isn't terminated by an empty line"));
+                }
+                if line.is_empty() {
+                    break;
+                }
+                if is_capability_section {
+                    if let Some(capability) = line.strip_prefix(b"@") {
+                        match capability.split_once_str("=") {
+                            Some((b"object-format", format)) => {
+                                let format = format.to_str().ok().and_then(|f| f.parse::<gix_hash::Kind>().ok());
+                                if format != Some(object_hash) {
+                                    return Err(malformed("the object format doesn't match the one of the repository"));
+                                }
+                            }
+                            _ => return Err(malformed("the bundle uses an unsupported capability")),
+                        }
+                        continue;
+                    }
+                    is_capability_section = false;
+                }
+                match line.strip_prefix(b"-") {
+                    Some(prerequisite) => {
+                        let hex = prerequisite.split_str(" ").next().unwrap_or_default();
+                        let id =
+                            ObjectId::from_hex(hex).map_err(|_| malformed("a prerequisite isn't a valid object id"))?;
+                        out.prerequisites.push(id);
+                    }
+                    None => {
+                        let (hex, name) = line
+                            .split_once_str(" ")
+                            .ok_or_else(|| malformed("a reference line doesn't contain a name"))?;
+                        let id =
+                            ObjectId::from_hex(hex).map_err(|_| malformed("a reference isn't a valid object id"))?;
+                        let name = gix_ref::FullName::try_from(name.as_bstr())
+                            .map_err(|_| malformed("a reference name is invalid"))?;
+                        out.refs.push((name, id));
+                    }
+                }
+            }
+            Ok(out)
+        }
+    }
+
+    /// Read a line into `buf` without its trailing newline, and return `false` if there was nothing left to read.
+    fn read_line(rd: &mut dyn BufRead, buf: &mut Vec<u8>) -> std::io::Result<bool> {
+        buf.clear();
+        if rd.read_until(b'\n', buf)? == 0 {
+            return Ok(false);
+        }
+        if buf.last() == Some(&b'\n') {
+            buf.pop();
+        }
+        Ok(true)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::Header;
+
+        #[test]
+        fn from_read_v3() -> Result<(), super::Error> {
+            let mut input: &[u8] = b"# v3 git bundle\n@object-format=sha1\n-1111111111111111111111111111111111111111 prerequisite\n2222222222222222222222222222222222222222 refs/heads/main\n\nPACK";
+            let header = Header::from_read(&mut input, gix_hash::Kind::Sha1)?;
+            assert_eq!(
+                header.prerequisites,
+                [gix_hash::ObjectId::from_hex(b"1111111111111111111111111111111111111111").unwrap()]
+            );
+            assert_eq!(header.refs.len(), 1);
+            assert_eq!(header.refs[0].0.as_bstr(), "refs/heads/main");
+            assert_eq!(input, b"PACK", "the reader is positioned at the start of the pack");
+            Ok(())
+        }
+
+        #[test]
+        fn from_read_rejects_unknown_signatures() {
+            let mut input: &[u8] = b"# v4 git bundle\n\n";
+            assert!(matches!(
+                Header::from_read(&mut input, gix_hash::Kind::Sha1),
+                Err(super::Error::MalformedHeader { .. })
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gix_protocol::bundle_uri::{Bundle, Mode};
+
+    #[test]
+    fn read_list_from_config_file() -> Result<(), super::bundle::Error> {
+        let input: &[u8] =
+            b"[bundle]\n\tversion = 1\n\tmode = any\n[bundle \"daily\"]\n\turi = daily.bundle\n\tcreationToken = 2\n";
+        let list = super::read_list(Box::new(input))?;
+        assert_eq!(list.mode, Mode::Any);
+        assert_eq!(
+            list.bundles,
+            [Bundle {
+                id: "daily".into(),
+                uri: Some("daily.bundle".into()),
+                creation_token: Some(2),
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn relative_uris_in_lists_are_resolved_against_the_directory_of_the_list() {
+        let list_url = gix_url::parse("https://example.com/bundles/list".into()).expect("valid");
+        let url = super::resolve("daily.bundle".into(), Some(&super::directory_of(list_url))).expect("resolvable");
+        assert_eq!(url.to_bstring(), "https://example.com/bundles/daily.bundle");
+    }
+}
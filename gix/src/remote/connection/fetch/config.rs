@@ -1,7 +1,11 @@
 use super::Error;
 use crate::{
-    config::{cache::util::ApplyLeniency, tree::Pack},
-    Repository,
+    bstr::BString,
+    config::{
+        cache::util::ApplyLeniency,
+        tree::{Extensions, Pack, Remote},
+    },
+    remote, Repository,
 };
 
 pub fn index_threads(repo: &Repository) -> Result<Option<usize>, Error> {
@@ -24,3 +28,37 @@ pub fn pack_index_version(repo: &Repository) -> Result<gix_pack::index::Version,
         .with_leniency(repo.options.lenient_config)?
         .unwrap_or(gix_pack::index::Version::V2))
 }
+
+/// Return `None` if the remote named `name` isn't a promisor remote, or `Some(filter)` with the object filter that is
+/// configured for it in `remote.<name>.partialCloneFilter`.
+///
+/// A remote is a promisor remote if it's the one set in `extensions.partialClone`, or if `remote.<name>.promisor` is true.
+pub fn promisor_filter(repo: &Repository, name: Option<&remote::Name<'_>>) -> Result<Option<Option<BString>>, Error> {
+    let Some(name) = name.and_then(remote::Name::as_symbol) else {
+        return Ok(None);
+    };
+    let config = &repo.config.resolved;
+    let mut filter = repo.filter_config_section();
+    let is_partial_clone_remote = config
+        .string(Extensions::PARTIAL_CLONE)
+        .map_or(false, |remote| remote.as_ref() == name);
+    let is_promisor = is_partial_clone_remote
+        || config
+            .boolean_filter(format!("remote.{name}.{key}", key = Remote::PROMISOR.name), &mut filter)
+            .map(|value| Remote::PROMISOR.enrich_error(value))
+            .transpose()
+            .with_leniency(repo.config.lenient_config)
+            .map_err(Error::PromisorConfig)?
+            .unwrap_or(false);
+    if !is_promisor {
+        return Ok(None);
+    }
+    Ok(Some(
+        config
+            .string_filter(
+                format!("remote.{name}.{key}", key = Remote::PARTIAL_CLONE_FILTER.name),
+                &mut filter,
+            )
+            .map(std::borrow::Cow::into_owned),
+    ))
+}
@@ -0,0 +1,79 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use gix_hash::ObjectId;
+use gix_object::Find;
+
+use crate::{remote::fetch::Error, Repository};
+
+/// Return all objects that are reachable from `tips` but missing from the object database, in the order they were encountered.
+///
+/// Only objects contained in one of the `new_packs` are traversed further, as these were received by this fetch. All other
+/// objects must merely exist, as they were present before and are assumed to be connected already, similar to
+/// `git rev-list --objects <tips> --not --all`. Parents of shallow commits aren't followed.
+pub(crate) fn missing_objects<'a>(
+    repo: &Repository,
+    tips: impl IntoIterator<Item = ObjectId>,
+    new_packs: impl IntoIterator<Item = &'a gix_pack::bundle::write::Outcome>,
+    should_interrupt: &AtomicBool,
+) -> Result<Vec<ObjectId>, Error> {
+    let _span = gix_trace::coarse!("gix::fetch::connectivity_check()");
+    let indices = new_packs
+        .into_iter()
+        .filter_map(|bundle| bundle.index_path.as_deref())
+        .map(|path| gix_pack::index::File::at(path, repo.object_hash()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let is_new = |id: &gix_hash::oid| indices.iter().any(|index| index.lookup(id).is_some());
+    let shallow_commits = repo.shallow_commits()?;
+
+    let mut missing = Vec::new();
+    let mut seen = gix_hashtable::HashSet::default();
+    let mut stack: Vec<_> = tips.into_iter().collect();
+    let mut buf = Vec::new();
+    while let Some(id) = stack.pop() {
+        if !seen.insert(id) {
+            continue;
+        }
+        if should_interrupt.load(Ordering::Relaxed) {
+            return Err(Error::Interrupted);
+        }
+        if !is_new(&id) {
+            if !repo.has_object(id) {
+                missing.push(id);
+            }
+            continue;
+        }
+        let Some(data) = repo
+            .objects
+            .try_find(&id, &mut buf)
+            .map_err(|err| Error::ConnectivityFindObject { id, source: err })?
+        else {
+            missing.push(id);
+            continue;
+        };
+        let decode_err = |err| Error::ConnectivityDecodeObject { id, source: err };
+        match data.kind {
+            gix_object::Kind::Commit => {
+                let commit = data.decode().map_err(decode_err)?.into_commit().expect("commit");
+                stack.push(commit.tree());
+                if shallow_commits.as_ref().map_or(true, |shallow| !shallow.contains(&id)) {
+                    stack.extend(commit.parents());
+                }
+            }
+            gix_object::Kind::Tree => {
+                let tree = data.decode().map_err(decode_err)?.into_tree().expect("tree");
+                stack.extend(
+                    tree.entries
+                        .iter()
+                        .filter(|entry| !entry.mode.is_commit())
+                        .map(|entry| entry.oid.to_owned()),
+                );
+            }
+            gix_object::Kind::Tag => {
+                let tag = data.decode().map_err(decode_err)?.into_tag().expect("tag");
+                stack.push(tag.target());
+            }
+            gix_object::Kind::Blob => {}
+        }
+    }
+    Ok(missing)
+}
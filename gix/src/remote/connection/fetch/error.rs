@@ -39,14 +39,85 @@ pub enum Error {
     WriteShallowFile(#[from] crate::shallow::write::Error),
     #[error("'shallow' file could not be locked in preparation for writing changes")]
     LockShallowFile(#[from] gix_lock::acquire::Error),
+    #[error(transparent)]
+    LockTimeoutConfiguration(#[from] config::lock_timeout::Error),
     #[error("Could not obtain configuration to learn if shallow remotes should be rejected")]
     RejectShallowRemoteConfig(#[from] config::boolean::Error),
     #[error("Receiving objects from shallow remotes is prohibited due to the value of `clone.rejectShallow`")]
     RejectShallowRemote,
     #[error(transparent)]
     NegotiationAlgorithmConfig(#[from] config::key::GenericErrorWithValue),
+    #[error("Could not obtain configuration to learn if bundle URIs should be used")]
+    BundleUriConfig(#[source] config::boolean::Error),
+    #[error("Could not list the bundles advertised by the remote")]
+    BundleUri(#[from] gix_protocol::bundle_uri::Error),
+    #[error("Could not obtain the pack at {uri:?} which the remote sent in place of parts of the pack")]
+    PackfileUri {
+        uri: crate::bstr::BString,
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+    #[error("The pack at {uri:?} has checksum {actual}, but the remote advertised {expected}")]
+    PackfileUriChecksumMismatch {
+        uri: crate::bstr::BString,
+        expected: gix_hash::ObjectId,
+        actual: gix_hash::ObjectId,
+    },
+    #[error("Could not obtain configuration to learn if the remote is a promisor remote")]
+    PromisorConfig(#[source] config::boolean::Error),
+    #[error("Could not write .promisor file at \"{}\"", path.display())]
+    WritePromisorFile {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Could not obtain configuration to learn if the commit-graph should be written")]
+    WriteCommitGraphConfig(#[source] config::boolean::Error),
+    #[error("Could not write the commit-graph after fetching")]
+    WriteCommitGraph(#[from] crate::repository::write_commit_graph::Error),
+    #[error(transparent)]
+    InitRefsIterator(#[from] crate::reference::iter::init::Error),
+    #[error(transparent)]
+    InitRefsIteratorPlatform(#[from] crate::reference::iter::Error),
+    #[error(transparent)]
+    IterateRefs(#[from] gix_ref::file::iter::loose_then_packed::Error),
+    #[error("Could not perform automatic maintenance after fetching")]
+    Maintenance(#[from] crate::maintenance::Error),
+    #[error("Could not write FETCH_HEAD file at \"{}\"", path.display())]
+    WriteFetchHead {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
     #[error("Failed to read remaining bytes in stream")]
     ReadRemainingBytes(#[source] std::io::Error),
+    #[error("Could not open the index of a received pack to check connectivity")]
+    ConnectivityOpenIndex(#[from] gix_pack::index::init::Error),
+    #[error("Could not read object {id} while checking connectivity")]
+    ConnectivityFindObject {
+        id: gix_hash::ObjectId,
+        source: gix_object::find::Error,
+    },
+    #[error("Could not decode object {id} while checking connectivity")]
+    ConnectivityDecodeObject {
+        id: gix_hash::ObjectId,
+        source: gix_object::decode::Error,
+    },
+    #[error("{} object(s) reachable from the fetched references are missing, like {}", missing.len(), missing[0])]
+    MissingObjects { missing: Vec<gix_hash::ObjectId> },
+    #[error("Interrupted")]
+    Interrupted,
+    #[error("The value of `fetch.unpackLimit` or `transfer.unpackLimit` should be a positive number of objects")]
+    UnpackLimitConfig(#[source] config::unsigned_integer::Error),
+    #[error("Could not open the index of the received pack to unpack it into loose objects")]
+    UnpackOpenIndex(#[source] gix_pack::index::init::Error),
+    #[error("Could not read object {id} to unpack it into a loose object")]
+    UnpackFindObject {
+        id: gix_hash::ObjectId,
+        source: gix_object::find::existing::Error,
+    },
+    #[error("Could not write object {id} as loose object")]
+    UnpackWriteObject {
+        id: gix_hash::ObjectId,
+        source: gix_odb::write::Error,
+    },
     #[error("None of the refspec(s) {} matched any of the {num_remote_refs} refs on the remote", refspecs.iter().map(|r| r.to_ref().instruction().to_bstring().to_string()).collect::<Vec<_>>().join(", "))]
     NoMapping {
         refspecs: Vec<gix_refspec::RefSpec>,
@@ -59,6 +130,7 @@ impl gix_protocol::transport::IsSpuriousError for Error {
         match self {
             Error::FetchResponse(err) => err.is_spurious(),
             Error::Client(err) => err.is_spurious(),
+            Error::BundleUri(err) => err.is_spurious(),
             _ => false,
         }
     }
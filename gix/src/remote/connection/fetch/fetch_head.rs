@@ -0,0 +1,151 @@
+use std::{collections::HashSet, io::Write};
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    remote,
+    remote::fetch::{
+        refs::update::{self, Mode},
+        Error, RefMap, SpecIndex,
+    },
+    Remote, Repository,
+};
+
+/// Write `FETCH_HEAD` into the git directory of `repo` with one line per ref that was fetched from `remote`, as described by
+/// `ref_map` and the `updates` performed for each of its mappings, in the same format as `git`.
+///
+/// Refs that are to be merged into the current branch are listed first, followed by all other refs marked as `not-for-merge`.
+pub(crate) fn write(
+    repo: &Repository,
+    remote: &Remote<'_>,
+    ref_map: &RefMap,
+    updates: &update::Outcome,
+) -> Result<(), Error> {
+    let url = remote
+        .url(remote::Direction::Fetch)
+        .map(shortened_url)
+        .unwrap_or_default();
+    let merge_ref = merge_ref_of_current_branch(repo, remote);
+    let first_spec_is_single_ref = remote.refspecs(remote::Direction::Fetch).first().map_or(false, |spec| {
+        spec.to_ref().source().map_or(false, |source| !source.contains(&b'*'))
+    });
+    let tag_spec = remote::fetch::Tags::All.to_refspec().expect("present");
+
+    let mut for_merge = Vec::new();
+    let mut not_for_merge = Vec::new();
+    let mut seen_first_spec_match = false;
+    let mut seen_names = HashSet::new();
+    for (update, mapping) in updates.updates.iter().zip(&ref_map.mappings) {
+        if matches!(
+            update.mode,
+            Mode::ImplicitTagNotSentByRemote | Mode::RejectedSourceObjectNotFound { .. }
+        ) {
+            continue;
+        }
+        let Some(id) = mapping.remote.as_id() else { continue };
+        let name = mapping.remote.as_name();
+        if name.map_or(false, |name| !seen_names.insert(name)) {
+            continue;
+        }
+        let is_for_merge = match &merge_ref {
+            Some(merge_ref) => name == Some(merge_ref.as_bstr()),
+            None => match mapping.spec_index {
+                SpecIndex::ExplicitInRemote(0) if first_spec_is_single_ref && !seen_first_spec_match => {
+                    seen_first_spec_match = true;
+                    true
+                }
+                SpecIndex::ExplicitInRemote(_) => false,
+                SpecIndex::Implicit(idx) => ref_map
+                    .extra_refspecs
+                    .get(idx)
+                    .map_or(false, |spec| spec.to_ref() != tag_spec),
+            },
+        };
+        let line = line(id, !is_for_merge, name, url.as_bstr());
+        if is_for_merge {
+            for_merge.push(line);
+        } else {
+            not_for_merge.push(line);
+        }
+    }
+
+    let path = repo.git_dir().join("FETCH_HEAD");
+    let mut buf = Vec::new();
+    for line in for_merge.into_iter().chain(not_for_merge) {
+        buf.write_all(&line).expect("write to vec never fails");
+    }
+    // Write to a uniquely named tempfile first so concurrent fetches never observe or produce a partially written file.
+    let mut file = gix_tempfile::new(
+        repo.git_dir(),
+        gix_tempfile::ContainingDirectory::Exists,
+        gix_tempfile::AutoRemove::Tempfile,
+    )
+    .map_err(|err| Error::WriteFetchHead {
+        path: path.clone(),
+        source: err,
+    })?;
+    file.write_all(&buf).map_err(|err| Error::WriteFetchHead {
+        path: path.clone(),
+        source: err,
+    })?;
+    file.persist(&path).map_err(|err| Error::WriteFetchHead {
+        path,
+        source: err.error,
+    })?;
+    Ok(())
+}
+
+/// Return the name of the remote ref configured in `branch.<current>.merge` if the current branch fetches from `remote`.
+fn merge_ref_of_current_branch(repo: &Repository, remote: &Remote<'_>) -> Option<BString> {
+    let head_name = repo.head_name().ok()??;
+    let branch_remote = repo.branch_remote_name(head_name.shorten(), remote::Direction::Fetch)?;
+    if Some(branch_remote.as_bstr()) != remote.name().map(remote::Name::as_bstr) {
+        return None;
+    }
+    let merge_ref = repo
+        .branch_remote_ref_name(head_name.as_ref(), remote::Direction::Fetch)?
+        .ok()?;
+    Some(merge_ref.as_bstr().to_owned())
+}
+
+/// Produce a `FETCH_HEAD` line for the remote ref `name` pointing to `id`, as fetched from `url`.
+fn line(id: &gix_hash::oid, not_for_merge: bool, name: Option<&BStr>, url: &BStr) -> Vec<u8> {
+    let mut out = Vec::new();
+    let marker = if not_for_merge { "not-for-merge" } else { "" };
+    write!(out, "{id}\t{marker}\t").expect("write to vec never fails");
+    match name {
+        None => {}
+        Some(name) if name == "HEAD" => {}
+        Some(name) => {
+            let (kind, short_name) = [
+                ("branch ", "refs/heads/"),
+                ("tag ", "refs/tags/"),
+                ("remote-tracking branch ", "refs/remotes/"),
+            ]
+            .iter()
+            .find_map(|(kind, prefix)| name.strip_prefix(prefix.as_bytes()).map(|short| (*kind, short)))
+            .unwrap_or(("", name.as_bytes()));
+            out.extend_from_slice(kind.as_bytes());
+            out.push(b'\'');
+            out.extend_from_slice(short_name);
+            out.extend_from_slice(b"' of ");
+        }
+    }
+    out.extend_from_slice(url);
+    out.push(b'\n');
+    out
+}
+
+/// Return `url` without credentials, trailing slashes and `.git` suffix, like `git` does when writing `FETCH_HEAD`.
+fn shortened_url(url: &gix_url::Url) -> BString {
+    let mut url = url.clone();
+    url.set_user(None);
+    url.set_password(None);
+    let mut url = url.to_bstring();
+    while url.last() == Some(&b'/') {
+        url.pop();
+    }
+    if let Some(len) = url.strip_suffix(b".git").map(<[u8]>::len) {
+        url.truncate(len);
+    }
+    url
+}
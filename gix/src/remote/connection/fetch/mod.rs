@@ -16,6 +16,7 @@ pub use error::Error;
 use crate::remote::fetch::WritePackedRefs;
 
 /// The way reflog messages should be composed whenever a ref is written with recent objects from a remote.
+#[derive(Debug, Clone)]
 pub enum RefLogMessage {
     /// Prefix the log with `action` and generate the typical suffix as `git` would.
     Prefixed {
@@ -61,6 +62,9 @@ pub enum Status {
         /// Information about the pack negotiation phase.
         negotiate: outcome::Negotiate,
         /// Information collected while writing the pack and its index.
+        ///
+        /// If the pack was [unpacked into loose objects](remote::fetch::ReceivedPack::UnpackBelow), its paths are `None`
+        /// as the pack doesn't exist anymore.
         write_pack_bundle: gix_pack::bundle::write::Outcome,
         /// Information collected while updating references.
         update_refs: refs::update::Outcome,
@@ -74,6 +78,25 @@ pub struct Outcome {
     pub ref_map: RefMap,
     /// The status of the operation to indicate what happened.
     pub status: Status,
+    /// All bundles advertised by the remote via `bundle-uri` that were attempted to be obtained before negotiating a pack,
+    /// which is empty unless bundle URIs are enabled.
+    pub bundles: Vec<outcome::Bundle>,
+    /// All packs the remote sent as URIs via `packfile-uris` in place of parts of the pack it sent inline, which were
+    /// downloaded and written to the object database. It's empty unless [packfile URIs are enabled](Prepare::with_packfile_uri_protocols()).
+    pub packfile_uris: Vec<outcome::PackfileUri>,
+    /// Information about the layer that was added to the commit-graph chain, if the commit-graph was written after the fetch
+    /// and if there were new commits.
+    pub commit_graph: Option<crate::repository::write_commit_graph::Outcome>,
+    /// The result of [automatic maintenance](crate::maintenance::auto()) after the fetch, or `None` if it
+    /// [wasn't enabled](Prepare::with_auto_maintenance()).
+    pub maintenance: Option<crate::maintenance::Outcome>,
+}
+
+impl Outcome {
+    /// Return the id the remote advertised to identify this operation in its logs, if it supports the `session-id` capability.
+    pub fn server_session_id(&self) -> Option<&crate::bstr::BStr> {
+        self.ref_map.handshake.session_id()
+    }
 }
 
 /// Additional types related to the outcome of a fetch operation.
@@ -83,12 +106,43 @@ pub mod outcome {
     /// Note that negotiation can happen even if no pack is ultimately produced.
     #[derive(Default, Debug, Clone)]
     pub struct Negotiate {
+        /// The algorithm that was used to negotiate, either as configured by `fetch.negotiationAlgorithm` or
+        /// [set explicitly](super::Prepare::with_negotiation_algorithm()).
+        pub algorithm: gix_negotiate::Algorithm,
         /// The negotiation graph indicating what kind of information 'the algorithm' collected in the end.
         pub graph: gix_negotiate::IdMap,
         /// Additional information for each round of negotiation.
         pub rounds: Vec<negotiate::Round>,
     }
 
+    /// Information about a bundle advertised by the remote via `bundle-uri`, which is applied before negotiating a pack
+    /// so only the objects that are missing on top of it are fetched.
+    #[derive(Debug, Clone)]
+    pub struct Bundle {
+        /// The location of the bundle as advertised by the remote.
+        pub uri: crate::bstr::BString,
+        /// Information collected while writing the pack of the bundle and its index, or `None` if the bundle couldn't be
+        /// downloaded or unbundled.
+        ///
+        /// As bundles are merely an optimization, this isn't an error and all objects are fetched from the remote instead.
+        pub write_pack_bundle: Option<gix_pack::bundle::write::Outcome>,
+        /// The references contained in the bundle along with the object they point to.
+        ///
+        /// Branches are written to `refs/bundles/` so they are used during negotiation, like `git` does.
+        pub refs: Vec<(gix_ref::FullName, gix_hash::ObjectId)>,
+    }
+
+    /// Information about a pack the remote sent as URI via `packfile-uris`, which was downloaded separately.
+    #[derive(Debug, Clone)]
+    pub struct PackfileUri {
+        /// The location of the pack as sent by the remote.
+        pub uri: crate::bstr::BString,
+        /// The hash of the pack as advertised by the remote, which matches the hash of the downloaded pack.
+        pub hash: gix_hash::ObjectId,
+        /// Information collected while writing the pack and its index.
+        pub write_pack_bundle: gix_pack::bundle::write::Outcome,
+    }
+
     ///
     pub mod negotiate {
         /// Key information about each round in the pack-negotiation.
@@ -112,6 +166,20 @@ pub mod outcome {
             ///
             /// This may also lead to the server responding with a pack.
             pub previous_response_had_at_least_one_in_common: bool,
+            /// The acknowledgements the server sent in response to the `HAVE`s of the previous round, which are all zero or `false`
+            /// in the first round.
+            pub previous_response_acknowledgements: Acknowledgements,
+        }
+
+        /// The kinds of acknowledgements received in a response to `HAVE` lines.
+        #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+        pub struct Acknowledgements {
+            /// The amount of `ACK` lines, each for an object that the server has in common with us.
+            pub common: usize,
+            /// If `true`, the server indicated that it is ready to send a pack.
+            pub ready: bool,
+            /// If `true`, the server sent a `NAK`, indicating that none of the `HAVE`s were in common.
+            pub nak: bool,
         }
     }
 }
@@ -185,14 +253,30 @@ where
         if self.remote.refspecs(remote::Direction::Fetch).is_empty() && options.extra_refspecs.is_empty() {
             return Err(prepare::Error::MissingRefSpecs);
         }
+        let capability_policy = options.capability_policy.clone();
         let ref_map = self.ref_map_inner(progress, options).await?;
         Ok(Prepare {
             con: Some(self),
             ref_map,
             dry_run: DryRun::No,
             reflog_message: None,
+            reflog_committer: None,
             write_packed_refs: WritePackedRefs::Never,
             shallow: Default::default(),
+            resumable: false,
+            refetch: false,
+            bundle_uri: None,
+            packfile_uri_protocols: None,
+            negotiation_window: Default::default(),
+            negotiation_algorithm: None,
+            write_commit_graph: None,
+            auto_maintenance: false,
+            write_fetch_head: true,
+            connectivity_check: false,
+            received_pack: None,
+            ref_update_retries: 3,
+            sideband_handler: None,
+            capability_policy,
         })
     }
 }
@@ -207,11 +291,16 @@ where
     }
 }
 
+mod bundle_uri;
 mod config;
+mod connectivity;
+mod fetch_head;
+mod packfile_uri;
 mod receive_pack;
 ///
 #[path = "update_refs/mod.rs"]
 pub mod refs;
+mod resume;
 
 /// A structure to hold the result of the handshake with the remote and configure the upcoming fetch operation.
 pub struct Prepare<'remote, 'repo, T>
@@ -222,12 +311,27 @@ where
     ref_map: RefMap,
     dry_run: DryRun,
     reflog_message: Option<RefLogMessage>,
+    reflog_committer: Option<gix_actor::Signature>,
     write_packed_refs: WritePackedRefs,
     shallow: remote::fetch::Shallow,
+    resumable: bool,
+    refetch: bool,
+    bundle_uri: Option<bool>,
+    packfile_uri_protocols: Option<Vec<String>>,
+    negotiation_window: remote::fetch::NegotiationWindow,
+    negotiation_algorithm: Option<remote::fetch::negotiate::Algorithm>,
+    write_commit_graph: Option<bool>,
+    auto_maintenance: bool,
+    write_fetch_head: bool,
+    connectivity_check: bool,
+    received_pack: Option<remote::fetch::ReceivedPack>,
+    ref_update_retries: usize,
+    sideband_handler: Option<remote::fetch::SidebandHandlerFn<'remote>>,
+    capability_policy: remote::fetch::CapabilityPolicy,
 }
 
 /// Builder
-impl<T> Prepare<'_, '_, T>
+impl<'remote, T> Prepare<'remote, '_, T>
 where
     T: Transport,
 {
@@ -253,11 +357,20 @@ where
     }
 
     /// Set the reflog message to use when updating refs after fetching a pack.
+    ///
+    /// If unset, the message is prefixed with the [reflog action](crate::Repository::reflog_action()), or `fetch`.
     pub fn with_reflog_message(mut self, reflog_message: RefLogMessage) -> Self {
         self.reflog_message = reflog_message.into();
         self
     }
 
+    /// Set the identity to write into the reference logs when updating refs after fetching a pack,
+    /// instead of the [configured committer](crate::Repository::committer()).
+    pub fn with_reflog_committer(mut self, committer: gix_actor::Signature) -> Self {
+        self.reflog_committer = committer.into();
+        self
+    }
+
     /// Define what to do when the current repository is a shallow clone.
     ///
     /// *Has no effect if the current repository is not as shallow clone.*
@@ -265,6 +378,191 @@ where
         self.shallow = shallow;
         self
     }
+
+    /// Only follow tags whose name matches one of the given `patterns`, like `v*` or `release/*`, which are relative
+    /// to `refs/tags/` and may contain a single `*` each.
+    ///
+    /// This is useful to avoid dealing with all tags of remotes with very large tag namespaces, and affects only tags that
+    /// are fetched due to the [tag configuration](crate::Remote::fetch_tags()) of the remote, but not those that are
+    /// fetched by ref-specs. Note that there is no equivalent in `git`.
+    pub fn with_tag_patterns<'a>(
+        mut self,
+        patterns: impl IntoIterator<Item = &'a crate::bstr::BStr>,
+    ) -> Result<Self, gix_refspec::parse::Error> {
+        use crate::bstr::{ByteSlice, ByteVec};
+        let patterns = patterns
+            .into_iter()
+            .map(|pattern| {
+                let mut name = BString::from("refs/tags/");
+                name.push_str(pattern);
+                let mut spec = name.clone();
+                spec.push_byte(b':');
+                spec.push_str(&name);
+                gix_refspec::parse(spec.as_bstr(), gix_refspec::parse::Operation::Fetch).map(|_| name)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let tag_spec = remote::fetch::Tags::All.to_refspec().expect("present");
+        let extra_refspecs = &self.ref_map.extra_refspecs;
+        self.ref_map.mappings.retain(|mapping| {
+            let is_implicit_tag = mapping
+                .spec_index
+                .implicit_index()
+                .and_then(|idx| extra_refspecs.get(idx))
+                .map_or(false, |spec| spec.to_ref() == tag_spec);
+            !is_implicit_tag
+                || mapping.remote.as_name().map_or(false, |name| {
+                    patterns.iter().any(|pattern| match pattern.find_byte(b'*') {
+                        Some(pos) => {
+                            name.len() >= pattern.len() - 1
+                                && name.starts_with(&pattern[..pos])
+                                && name.ends_with(&pattern[pos + 1..])
+                        }
+                        None => name == pattern.as_bstr(),
+                    })
+                })
+        });
+        Ok(self)
+    }
+
+    /// If `enabled`, the pack is also written to a temporary file in the `objects/pack` directory while it is received,
+    /// which is kept if receiving it fails, for instance due to a connection loss.
+    ///
+    /// When fetching with `enabled` the next time, all objects that were fully received are indexed first, and commits
+    /// whose history and trees are complete are offered to the remote as objects we have during negotiation.
+    /// That way, the remote will only send what's still missing.
+    ///
+    /// This is off by default.
+    pub fn with_resumable(mut self, enabled: bool) -> Self {
+        self.resumable = enabled;
+        self
+    }
+
+    /// If `enabled`, fetch all objects reachable from the wanted refs as if the repository was empty, similar to `git fetch --refetch`.
+    ///
+    /// No objects we have are sent to the remote during negotiation, and once the new pack was received, all packs whose
+    /// objects are fully contained in it are removed from the object database. This is useful to repair partial clones
+    /// whose filter changed, or to replace packs that are corrupted.
+    ///
+    /// Note that the removal of superseded packs is skipped if a `multi-pack-index` is present, or if packs are marked
+    /// with a `.keep` file.
+    pub fn with_refetch(mut self, enabled: bool) -> Self {
+        self.refetch = enabled;
+        self
+    }
+
+    /// If `enabled`, ask the remote for bundles via the `bundle-uri` capability and download and unbundle them
+    /// before negotiating the pack, so that only the objects that are missing on top of them are fetched from the remote.
+    ///
+    /// This overrides the value of `transfer.bundleURI`, which is off by default. Bundles aren't used if the remote doesn't
+    /// advertise them, in dry-run or refetch mode, or if the repository is shallow or is about to become shallow.
+    pub fn with_bundle_uri(mut self, enabled: bool) -> Self {
+        self.bundle_uri = Some(enabled);
+        self
+    }
+
+    /// Allow the remote to send parts of the pack as URIs using any of the given `protocols`, like `https`, via the
+    /// `packfile-uris` capability. The packs at these URIs are downloaded and verified against the hash the remote
+    /// advertised for them, and written to the object database along with the pack the remote sends inline.
+    ///
+    /// This overrides the value of `fetch.uriProtocols`, and an empty list of `protocols` turns the feature off, which is
+    /// the default. Packfile URIs aren't used if the remote doesn't advertise them, or in dry-run mode.
+    /// Note that only `http`, `https` and `file` URIs can be downloaded, the former only if an HTTP transport is available.
+    pub fn with_packfile_uri_protocols(mut self, protocols: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.packfile_uri_protocols = Some(protocols.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// If `enabled`, add the commits reachable from all references to the commit-graph chain after the fetch,
+    /// so that subsequent revision walks stay fast.
+    ///
+    /// This overrides the value of `fetch.writeCommitGraph`, which is off by default. The commit-graph isn't written in
+    /// dry-run mode, if no reference was updated, or if commit-graphs are disabled with `core.commitGraph`.
+    pub fn with_write_commit_graph(mut self, enabled: bool) -> Self {
+        self.write_commit_graph = Some(enabled);
+        self
+    }
+
+    /// If `enabled`, run [automatic maintenance](crate::maintenance::auto()) once the fetch is complete, which packs loose
+    /// objects and updates the commit-graph if the configuration says it's needed, similar to what `git fetch` does.
+    ///
+    /// This is off by default, and maintenance never runs in dry-run mode. Note that `maintenance.auto` is still honored.
+    pub fn with_auto_maintenance(mut self, enabled: bool) -> Self {
+        self.auto_maintenance = enabled;
+        self
+    }
+
+    /// If `enabled` (the default), write `FETCH_HEAD` with one line per fetched ref after the fetch, like `git fetch` does.
+    ///
+    /// Refs to be merged into the current branch are listed first, all others are marked `not-for-merge`.
+    /// Set it to `false` to leave `FETCH_HEAD` untouched, similar to `git fetch --no-write-fetch-head`.
+    /// It's never written in dry-run mode.
+    pub fn with_write_fetch_head(mut self, enabled: bool) -> Self {
+        self.write_fetch_head = enabled;
+        self
+    }
+
+    /// If `enabled`, verify that all objects reachable from the fetched references exist after receiving the pack,
+    /// similar to `git fsck --connectivity-only` limited to the newly received tips, and fail with
+    /// [`Error::MissingObjects`] listing all missing objects before any reference is updated.
+    ///
+    /// Only objects that were received are traversed, all others merely need to exist. This is off by default,
+    /// it never runs in dry-run mode, and it's skipped when fetching from a promisor remote, which omits objects by design.
+    pub fn with_connectivity_check(mut self, enabled: bool) -> Self {
+        self.connectivity_check = enabled;
+        self
+    }
+
+    /// Define what to do with the received pack once references were updated, overriding `fetch.unpackLimit` and
+    /// `transfer.unpackLimit`.
+    ///
+    /// By default, packs with fewer objects than the configured unpack limit are unpacked into loose objects, and all others
+    /// are made [repackable](remote::fetch::ReceivedPack::Repackable). Unlike `git`, which defaults to an unpack limit
+    /// of 100, packs are never unpacked if no limit is configured.
+    /// Packs are also never unpacked when refetching or when fetching from a promisor remote, as these need to remain packs.
+    pub fn with_received_pack(mut self, policy: remote::fetch::ReceivedPack) -> Self {
+        self.received_pack = Some(policy);
+        self
+    }
+
+    /// Retry updating local references up to `retries` times if a concurrent process, like another fetch into the same repository,
+    /// held a lock on one of them or changed it while we were computing the update. Defaults to 3.
+    ///
+    /// Each retry re-evaluates all ref updates against the references as they are now, so changes made by the concurrent
+    /// process are merged with ours while fast-forward rules remain enforced. Waiting for locks is controlled
+    /// by `core.filesRefLockTimeout` and `core.packedRefsTimeout` for each attempt.
+    /// Set it to 0 to fail on the first conflict instead.
+    pub fn with_ref_update_retries(mut self, retries: usize) -> Self {
+        self.ref_update_retries = retries;
+        self
+    }
+
+    /// Set how many `HAVE` lines are sent to the remote in each round of negotiation with `window`.
+    ///
+    /// By default, the amount grows with each round like in `git`, but it can be fixed to diagnose or work around slow negotiations
+    /// with particular remotes. See [`Round`](outcome::negotiate::Round) for statistics about each round.
+    pub fn with_negotiation_window(mut self, window: remote::fetch::NegotiationWindow) -> Self {
+        self.negotiation_window = window;
+        self
+    }
+
+    /// Use `algorithm` to decide which commits to announce as `HAVE` during negotiation, instead of the one configured
+    /// with `fetch.negotiationAlgorithm`.
+    ///
+    /// [`Skipping`](remote::fetch::negotiate::Algorithm::Skipping) needs far fewer rounds than the default if histories
+    /// have diverged a lot or there are many refs, at the cost of receiving packs that are possibly larger than needed.
+    pub fn with_negotiation_algorithm(mut self, algorithm: remote::fetch::negotiate::Algorithm) -> Self {
+        self.negotiation_algorithm = Some(algorithm);
+        self
+    }
+
+    /// Call `handler` with each message the remote sends on the progress or error sideband channel while preparing and
+    /// sending the pack, to allow showing them to the user like `git` does.
+    ///
+    /// Messages are still used to drive the `remote` progress as well.
+    pub fn with_sideband_handler(mut self, handler: impl FnMut(remote::fetch::SidebandMessage<'_>) + 'remote) -> Self {
+        self.sideband_handler = Some(Box::new(handler));
+        self
+    }
 }
 
 impl<T> Drop for Prepare<'_, '_, T>
@@ -5,7 +5,10 @@ use gix_negotiate::Flags;
 use gix_odb::HeaderExt;
 use gix_pack::Find;
 
-use crate::remote::{fetch, fetch::Shallow};
+use crate::remote::{
+    fetch,
+    fetch::{outcome::negotiate::Acknowledgements, Shallow},
+};
 
 type Queue = gix_revwalk::PriorityQueue<SecondsSinceUnixEpoch, gix_hash::ObjectId>;
 
@@ -68,6 +71,7 @@ pub(crate) fn mark_complete_and_common_ref(
     graph: &mut gix_negotiate::Graph<'_, '_>,
     ref_map: &fetch::RefMap,
     shallow: &fetch::Shallow,
+    resumed: Option<&super::resume::Restored>,
     mapping_is_ignored: impl Fn(&fetch::Mapping) -> bool,
 ) -> Result<Action, Error> {
     let _span = gix_trace::detail!("mark_complete_and_common_ref", mappings = ref_map.mappings.len());
@@ -79,11 +83,7 @@ pub(crate) fn mark_complete_and_common_ref(
         // perform the negotiation for some reason (couldn't find it in code).
         return Ok(Action::NoChange);
     }
-    if let Some(fetch::Mapping {
-        remote: fetch::Source::Ref(gix_protocol::handshake::Ref::Unborn { .. }),
-        ..
-    }) = ref_map.mappings.last().filter(|_| ref_map.mappings.len() == 1)
-    {
+    if remote_is_empty(ref_map) {
         // There is only an unborn branch, as the remote has an empty repository. This means there is nothing to do except for
         // possibly reproducing the unborn branch locally.
         return Ok(Action::SkipToRefUpdate);
@@ -113,13 +113,15 @@ pub(crate) fn mark_complete_and_common_ref(
             }
         }
 
-        if let Some(commit) = want_id
+        // Objects restored from a partial pack may lack parts of their history, so they don't count as present.
+        let present_want_id = want_id.filter(|id| resumed.map_or(true, |resumed| !resumed.incomplete.contains(*id)));
+        if let Some(commit) = present_want_id
             .and_then(|id| graph.get_or_insert_commit(id.into(), |_| {}).transpose())
             .transpose()?
         {
             remote_ref_target_known[mapping_idx] = true;
             cutoff_date = cutoff_date.unwrap_or_default().max(commit.commit_time).into();
-        } else if want_id.map_or(false, |maybe_annotated_tag| repo.objects.contains(maybe_annotated_tag)) {
+        } else if present_want_id.map_or(false, |maybe_annotated_tag| repo.objects.contains(maybe_annotated_tag)) {
             remote_ref_target_known[mapping_idx] = true;
         }
     }
@@ -142,6 +144,13 @@ pub(crate) fn mark_complete_and_common_ref(
     let mut queue = Queue::new();
     mark_all_refs_in_repo(repo, graph, &mut queue, Flags::COMPLETE)?;
     mark_alternate_complete(repo, graph, &mut queue)?;
+    mark_resumed_complete(
+        graph,
+        &mut queue,
+        resumed
+            .into_iter()
+            .flat_map(|resumed| resumed.complete_commits.iter().copied()),
+    )?;
     // Keep track of the tips, which happen to be on our queue right, before we traverse the graph with cutoff.
     let tips = if let Some(cutoff) = cutoff_date {
         let tips = Cow::Owned(queue.clone());
@@ -187,6 +196,31 @@ pub(crate) fn mark_complete_and_common_ref(
     })
 }
 
+/// Like [`mark_complete_and_common_ref()`], but act as if we didn't have any object so all of them will be sent again.
+///
+/// As no tips are added to the negotiator, no `HAVE` lines will be sent either.
+pub(crate) fn refetch_all(ref_map: &fetch::RefMap) -> Action {
+    if ref_map.mappings.is_empty() {
+        Action::NoChange
+    } else if remote_is_empty(ref_map) {
+        Action::SkipToRefUpdate
+    } else {
+        Action::MustNegotiate {
+            remote_ref_target_known: vec![false; ref_map.mappings.len()],
+        }
+    }
+}
+
+fn remote_is_empty(ref_map: &fetch::RefMap) -> bool {
+    matches!(
+        ref_map.mappings.last().filter(|_| ref_map.mappings.len() == 1),
+        Some(fetch::Mapping {
+            remote: fetch::Source::Ref(gix_protocol::handshake::Ref::Unborn { .. }),
+            ..
+        })
+    )
+}
+
 /// Create a predicate that checks if a refspec mapping should be ignored.
 ///
 /// We want to ignore mappings during negotiation if they would be handled implicitly by the server, which is the case
@@ -293,21 +327,43 @@ fn mark_all_refs_in_repo(
     let _span = gix_trace::detail!("mark_all_refs");
     for local_ref in repo.references()?.all()?.peeled()? {
         let local_ref = local_ref?;
-        let id = local_ref.id().detach();
-        let mut is_complete = false;
-        if let Some(commit) = graph
-            .get_or_insert_commit(id, |md| {
-                is_complete = md.flags.contains(Flags::COMPLETE);
-                md.flags |= mark;
-            })?
-            .filter(|_| !is_complete)
-        {
-            queue.insert(commit.commit_time, id);
-        };
+        mark_tip(graph, queue, local_ref.id().detach(), mark)?;
+    }
+    Ok(())
+}
+
+/// Mark commits received by a previous, interrupted fetch as complete and use them as tips.
+fn mark_resumed_complete(
+    graph: &mut gix_negotiate::Graph<'_, '_>,
+    queue: &mut Queue,
+    commits: impl Iterator<Item = gix_hash::ObjectId>,
+) -> Result<(), Error> {
+    let _span = gix_trace::detail!("mark_resumed_complete");
+    for id in commits {
+        mark_tip(graph, queue, id, Flags::COMPLETE)?;
     }
     Ok(())
 }
 
+fn mark_tip(
+    graph: &mut gix_negotiate::Graph<'_, '_>,
+    queue: &mut Queue,
+    id: gix_hash::ObjectId,
+    mark: Flags,
+) -> Result<(), Error> {
+    let mut is_complete = false;
+    if let Some(commit) = graph
+        .get_or_insert_commit(id, |md| {
+            is_complete = md.flags.contains(Flags::COMPLETE);
+            md.flags |= mark;
+        })?
+        .filter(|_| !is_complete)
+    {
+        queue.insert(commit.commit_time, id);
+    };
+    Ok(())
+}
+
 fn mark_alternate_complete(
     repo: &crate::Repository,
     graph: &mut gix_negotiate::Graph<'_, '_>,
@@ -326,10 +382,35 @@ fn mark_alternate_complete(
     Ok(())
 }
 
+/// Tell `negotiator` about the commits that the remote acknowledged as common during the negotiation of an interrupted fetch,
+/// and send them as `haves` right away, or add them to the `common` commits to repeat in each round if it is set.
+/// Return all of them that are present in the `graph`.
+pub(crate) fn add_resumed_common(
+    negotiator: &mut dyn gix_negotiate::Negotiator,
+    graph: &mut gix_negotiate::Graph<'_, '_>,
+    arguments: &mut gix_protocol::fetch::Arguments,
+    mut common: Option<&mut Vec<gix_hash::ObjectId>>,
+    ids: impl Iterator<Item = gix_hash::ObjectId>,
+) -> Result<Vec<gix_hash::ObjectId>, Error> {
+    let mut added = Vec::new();
+    for id in ids {
+        if graph.get_or_insert_commit(id, |_| {})?.is_none() {
+            continue;
+        }
+        negotiator.in_common_with_remote(id, graph)?;
+        match common.as_mut() {
+            Some(common) => common.push(id),
+            None => arguments.have(id),
+        }
+        added.push(id);
+    }
+    Ok(added)
+}
+
 /// Negotiate the nth `round` with `negotiator` sending `haves_to_send` after possibly making the known common commits
 /// as sent by the remote known to `negotiator` using `previous_response` if this isn't the first round.
 /// All `haves` are added to `arguments` accordingly.
-/// Returns the amount of haves actually sent.
+/// Returns the amount of haves actually sent, along with the acknowledgements seen in `previous_response`.
 pub(crate) fn one_round(
     negotiator: &mut dyn gix_negotiate::Negotiator,
     graph: &mut gix_negotiate::Graph<'_, '_>,
@@ -337,24 +418,26 @@ pub(crate) fn one_round(
     arguments: &mut gix_protocol::fetch::Arguments,
     previous_response: Option<&gix_protocol::fetch::Response>,
     mut common: Option<&mut Vec<gix_hash::ObjectId>>,
-) -> Result<(usize, bool), Error> {
-    let mut seen_ack = false;
+) -> Result<(usize, Acknowledgements), Error> {
+    let mut acks = Acknowledgements::default();
     if let Some(response) = previous_response {
         use gix_protocol::fetch::response::Acknowledgement;
         for ack in response.acknowledgements() {
             match ack {
                 Acknowledgement::Common(id) => {
-                    seen_ack = true;
-                    negotiator.in_common_with_remote(*id, graph)?;
-                    if let Some(ref mut common) = common {
+                    acks.common += 1;
+                    let was_known_as_common = negotiator.in_common_with_remote(*id, graph)?;
+                    // Commits we repeat are acknowledged again, and must not be repeated twice.
+                    if let Some(common) = common.as_mut().filter(|_| !was_known_as_common) {
                         common.push(*id);
                     }
                 }
                 Acknowledgement::Ready => {
                     // NOTE: In git, there is some logic dealing with whether to expect a DELIM or FLUSH package,
                     //       but we handle this with peeking.
+                    acks.ready = true;
                 }
-                Acknowledgement::Nak => {}
+                Acknowledgement::Nak => acks.nak = true,
             }
         }
     }
@@ -375,5 +458,5 @@ pub(crate) fn one_round(
     // Note that we are differing from the git implementation, which does an extra-round of with no new haves sent at all.
     // For us it seems better to just say we are done when we know we are done, as potentially additional acks won't affect the
     // queue of any of our implementation at all (so the negotiator won't come up with more haves next time either).
-    Ok((haves_sent, seen_ack))
+    Ok((haves_sent, acks))
 }
@@ -0,0 +1,164 @@
+use std::sync::atomic::AtomicBool;
+
+use gix_protocol::fetch::response::PackfileUri;
+
+use crate::{
+    bstr::ByteSlice,
+    config::tree::{Fetch, Key},
+    remote::{
+        connection::fetch::bundle_uri,
+        fetch::{outcome, Error},
+    },
+    Repository,
+};
+
+/// Return the protocols with which the remote may offload packs to other locations, with `protocols` overriding
+/// `fetch.uriProtocols`, or an empty list if packfile URIs shouldn't be used.
+pub(crate) fn protocols(protocols: Option<&[String]>, repo: &Repository) -> Vec<String> {
+    match protocols {
+        Some(protocols) => protocols.to_vec(),
+        None => repo
+            .config
+            .resolved
+            .string(Fetch::URI_PROTOCOLS.logical_name().as_str())
+            .map(|value| {
+                value
+                    .split_str(",")
+                    .map(|protocol| protocol.trim().to_str_lossy().into_owned())
+                    .filter(|protocol| !protocol.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// Download all packs at `uris` as sent by the remote in place of parts of the pack it sends inline, write them along
+/// with their index to the object database and verify that they match the hash the remote advertised for them.
+///
+/// Note that unlike bundles, these packs contain objects that aren't sent otherwise, so failing to obtain one is fatal.
+pub(crate) fn obtain(
+    repo: &Repository,
+    uris: &[PackfileUri],
+    progress: &mut dyn crate::DynNestedProgress,
+    should_interrupt: &AtomicBool,
+    options: gix_pack::bundle::write::Options,
+) -> Result<Vec<outcome::PackfileUri>, Error> {
+    let _span = gix_trace::coarse!("fetch::packfile_uri::obtain()", num_uris = uris.len());
+    let mut out = Vec::with_capacity(uris.len());
+    for PackfileUri { hash, uri } in uris {
+        progress.set_name(format!("download pack {hash}"));
+        let url = gix_url::parse(uri.as_ref()).map_err(|err| Error::PackfileUri {
+            uri: uri.clone(),
+            source: err.into(),
+        })?;
+        let mut rd = bundle_uri::open(&url).map_err(|err| Error::PackfileUri {
+            uri: uri.clone(),
+            source: err.into(),
+        })?;
+        let write_pack_bundle = gix_pack::Bundle::write_to_directory(
+            &mut rd,
+            Some(&repo.objects.store_ref().path().join("pack")),
+            progress,
+            should_interrupt,
+            Some(Box::new({
+                let repo = repo.clone();
+                repo.objects
+            })),
+            options.clone(),
+        )
+        .map_err(|err| Error::PackfileUri {
+            uri: uri.clone(),
+            source: err.into(),
+        })?;
+        if write_pack_bundle.index.data_hash != *hash {
+            // Only remove what we wrote, and not a pack that happened to be present already.
+            if let Some(keep_path) = write_pack_bundle.keep_path {
+                for path in write_pack_bundle
+                    .index_path
+                    .iter()
+                    .chain(write_pack_bundle.data_path.iter())
+                {
+                    std::fs::remove_file(path).ok();
+                }
+                std::fs::remove_file(keep_path).ok();
+            }
+            return Err(Error::PackfileUriChecksumMismatch {
+                uri: uri.clone(),
+                expected: *hash,
+                actual: write_pack_bundle.index.data_hash,
+            });
+        }
+        out.push(outcome::PackfileUri {
+            uri: uri.clone(),
+            hash: *hash,
+            write_pack_bundle,
+        });
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+
+    use gix_protocol::fetch::response::PackfileUri;
+    use gix_testtools::Result;
+
+    use crate::remote::fetch::Error;
+
+    /// A pack with a single blob containing `hi\n`, whose checksum is `244ab49bafbf4b874e72c60b8d2f6500e736ee75`.
+    const PACK: &[u8] = b"PACK\x00\x00\x00\x02\x00\x00\x00\x01\x33\x78\x9c\xcb\xc8\xe4\x02\x00\x02\x17\x00\xdc\x24\x4a\xb4\x9b\xaf\xbf\x4b\x87\x4e\x72\xc6\x0b\x8d\x2f\x65\x00\xe7\x36\xee\x75";
+
+    fn hex_to_id(hex: &str) -> gix_hash::ObjectId {
+        gix_hash::ObjectId::from_hex(hex.as_bytes()).expect("40 bytes hex")
+    }
+
+    #[test]
+    fn packs_are_written_to_the_object_database_and_verified() -> Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let repo = crate::init_bare(tmp.path().join("repo.git"))?;
+        let pack_path = tmp.path().join("pack.pack");
+        std::fs::write(&pack_path, PACK)?;
+        let uri: crate::bstr::BString = format!("file://{}", pack_path.display()).into();
+
+        let expected = hex_to_id("244ab49bafbf4b874e72c60b8d2f6500e736ee75");
+        let out = super::obtain(
+            &repo,
+            &[PackfileUri {
+                hash: expected,
+                uri: uri.clone(),
+            }],
+            &mut crate::progress::Discard,
+            &AtomicBool::default(),
+            Default::default(),
+        )?;
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].write_pack_bundle.index.data_hash, expected);
+        assert!(
+            repo.has_object(hex_to_id("45b983be36b73c0788dc9cbcb76cbb80fc7bb057")),
+            "the object database picks up the new pack"
+        );
+
+        let err = super::obtain(
+            &repo,
+            &[PackfileUri {
+                hash: hex_to_id("1111111111111111111111111111111111111111"),
+                uri,
+            }],
+            &mut crate::progress::Discard,
+            &AtomicBool::default(),
+            Default::default(),
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, Error::PackfileUriChecksumMismatch { actual, .. } if actual == expected),
+            "packs that don't match the advertised hash are rejected"
+        );
+        assert_eq!(
+            std::fs::read_dir(repo.objects.store_ref().path().join("pack"))?.count(),
+            3,
+            "the pack that was already present is left alone, along with its index and .keep file"
+        );
+        Ok(())
+    }
+}
@@ -5,18 +5,19 @@ use std::{
 
 use gix_odb::store::RefreshMode;
 use gix_protocol::{
-    fetch::Arguments,
+    fetch::{response::ShallowUpdate, Arguments},
     transport::{client::Transport, packetline::read::ProgressAction},
 };
 
 use crate::{
+    bstr::ByteSlice,
     config::{
         cache::util::ApplyLeniency,
-        tree::{Clone, Fetch, Key},
+        tree::{Clone, Fetch, Key, Transfer},
     },
     remote,
     remote::{
-        connection::fetch::config,
+        connection::fetch::{bundle_uri, config, connectivity, fetch_head, packfile_uri, resume},
         fetch,
         fetch::{
             negotiate, negotiate::Algorithm, outcome, refs, Error, Outcome, Prepare, ProgressId, RefLogMessage,
@@ -53,6 +54,9 @@ where
     /// A known application for this behaviour is in `remote-helper` implementations which should send this path via `lock <path>` to stdout
     /// to inform git about the file that it will remove once it updated the refs accordingly.
     ///
+    /// What happens to the received pack can be [configured](Prepare::with_received_pack()), which allows to keep the `.keep` file
+    /// or to unpack small packs into loose objects instead.
+    ///
     /// ### Deviation
     ///
     /// When **updating refs**, the `git-fetch` docs state that the following:
@@ -71,6 +75,11 @@ where
     /// ### Configuration
     ///
     /// - `gitoxide.userAgent` is read to obtain the application user agent for git servers and for HTTP servers as well.
+    /// - `remote.<name>.partialCloneFilter` is sent to the remote if it is a promisor remote, i.e. if it's set in `extensions.partialClone`
+    ///   or has `remote.<name>.promisor` enabled, and if it supports the `filter` capability. All packs received from promisor remotes
+    ///   are marked with a `.promisor` file.
+    /// - `fetch.unpackLimit` and `transfer.unpackLimit` define the amount of objects below which a received pack is unpacked into
+    ///   loose objects, but only if set.
     ///
     #[gix_protocol::maybe_async::maybe_async]
     pub async fn receive<P>(self, mut progress: P, should_interrupt: &AtomicBool) -> Result<Outcome, Error>
@@ -90,6 +99,8 @@ where
     ) -> Result<Outcome, Error> {
         let _span = gix_trace::coarse!("fetch::Prepare::receive()");
         let mut con = self.con.take().expect("receive() can only be called once");
+        // Shared as each round of negotiation may need to pass messages to it while the reader of the last one is kept.
+        let sideband_handler = self.sideband_handler.take().map(std::cell::RefCell::new);
 
         if self.ref_map.mappings.is_empty() && !self.ref_map.remote_refs.is_empty() {
             let mut specs = con.remote.fetch_specs.clone();
@@ -109,13 +120,18 @@ where
         let fetch_features = {
             let mut f = fetch.default_features(protocol_version, &handshake.capabilities);
             f.push(repo.config.user_agent_tuple());
+            if let Some(id) = con.session_id_to_send(&handshake.capabilities) {
+                f.push(("session-id", Some(id.to_owned().into())));
+            }
+            self.capability_policy
+                .retain_allowed_fetch_features(&mut f, &handshake.capabilities);
             f
         };
 
         gix_protocol::fetch::Response::check_required_features(protocol_version, &fetch_features)?;
         let sideband_all = fetch_features.iter().any(|(n, _)| *n == "sideband-all");
         let mut arguments = gix_protocol::fetch::Arguments::new(protocol_version, fetch_features, con.trace);
-        if matches!(con.remote.fetch_tags, fetch::Tags::Included) {
+        if matches!(con.remote.effective_fetch_tags(), fetch::Tags::Included) {
             if !arguments.can_use_include_tag() {
                 return Err(Error::MissingServerFeature {
                     feature: "include-tag",
@@ -127,6 +143,17 @@ where
             arguments.use_include_tag();
         }
         let (shallow_commits, mut shallow_lock) = add_shallow_args(&mut arguments, &self.shallow, repo)?;
+        let promisor_filter = config::promisor_filter(repo, con.remote.name())?;
+        if let Some(filter) = promisor_filter.as_ref().and_then(Option::as_ref) {
+            // Like `git`, we silently fetch everything if the remote doesn't support filtering.
+            if arguments.can_use_filter() {
+                arguments.filter(filter.to_str_lossy().as_ref());
+            }
+        }
+        if matches!(self.dry_run, fetch::DryRun::No) && arguments.can_use_packfile_uris() {
+            let protocols = packfile_uri::protocols(self.packfile_uri_protocols.as_deref(), repo);
+            arguments.use_packfile_uris(protocols.iter().map(String::as_str));
+        }
 
         if self.ref_map.object_hash != repo.object_hash() {
             return Err(Error::IncompatibleObjectHash {
@@ -135,19 +162,68 @@ where
             });
         }
 
+        let partial_pack = if self.resumable && matches!(self.dry_run, fetch::DryRun::No) {
+            resume::PartialPack::lock(
+                repo,
+                con.remote.url(remote::Direction::Fetch),
+                self.ref_map
+                    .mappings
+                    .iter()
+                    .filter_map(|mapping| mapping.remote.as_id()),
+            )
+        } else {
+            None
+        };
+        let resumed = match partial_pack.as_ref() {
+            Some(partial_pack) => resume::restore_partial_pack(repo, partial_pack, progress, should_interrupt)?,
+            None => None,
+        };
+
+        let bundles = if matches!(self.dry_run, fetch::DryRun::No)
+            && !self.refetch
+            && matches!(self.shallow, Shallow::NoChange)
+            && matches!(protocol_version, gix_protocol::transport::Protocol::V2)
+            && self.ref_map.handshake.capabilities.contains("bundle-uri")
+            && self.capability_policy.is_allowed("bundle-uri")
+            && !repo.is_shallow()
+            && use_bundle_uri(self.bundle_uri, repo)?
+        {
+            bundle_uri::obtain(
+                &mut con.transport,
+                &self.ref_map.handshake.capabilities,
+                con.trace,
+                repo,
+                con.remote.url(remote::Direction::Fetch),
+                progress,
+                should_interrupt,
+                gix_pack::bundle::write::Options {
+                    thread_limit: config::index_threads(repo)?,
+                    index_version: config::pack_index_version(repo)?,
+                    iteration_mode: gix_pack::data::input::Mode::Verify,
+                    object_hash: repo.object_hash(),
+                },
+            )
+            .await?
+        } else {
+            Vec::new()
+        };
+
         let negotiate_span = gix_trace::detail!(
             "negotiate",
             protocol_version = self.ref_map.handshake.server_protocol_version as usize
         );
-        let mut negotiator = repo
-            .config
-            .resolved
-            .string(Fetch::NEGOTIATION_ALGORITHM.logical_name().as_str())
-            .map(|n| Fetch::NEGOTIATION_ALGORITHM.try_into_negotiation_algorithm(n))
-            .transpose()
-            .with_leniency(repo.config.lenient_config)?
-            .unwrap_or(Algorithm::Consecutive)
-            .into_negotiator();
+        let algorithm = match self.negotiation_algorithm {
+            Some(algorithm) => algorithm,
+            None => repo
+                .config
+                .resolved
+                .string(Fetch::NEGOTIATION_ALGORITHM.logical_name().as_str())
+                .map(|n| Fetch::NEGOTIATION_ALGORITHM.try_into_negotiation_algorithm(n))
+                .transpose()
+                .with_leniency(repo.config.lenient_config)?
+                .unwrap_or(Algorithm::Consecutive),
+        };
+        let mut negotiator = algorithm.into_negotiator();
         let graph_repo = {
             let mut r = repo.clone();
             // assure that checking for unknown server refs doesn't trigger ODB refreshes.
@@ -158,15 +234,21 @@ where
         };
         let cache = graph_repo.commit_graph_if_enabled().ok().flatten();
         let mut graph = graph_repo.revision_graph(cache.as_ref());
-        let action = negotiate::mark_complete_and_common_ref(
-            &graph_repo,
-            negotiator.deref_mut(),
-            &mut graph,
-            &self.ref_map,
-            &self.shallow,
-            negotiate::make_refmapping_ignore_predicate(con.remote.fetch_tags, &self.ref_map),
-        )?;
+        let action = if self.refetch {
+            negotiate::refetch_all(&self.ref_map)
+        } else {
+            negotiate::mark_complete_and_common_ref(
+                &graph_repo,
+                negotiator.deref_mut(),
+                &mut graph,
+                &self.ref_map,
+                &self.shallow,
+                resumed.as_ref(),
+                negotiate::make_refmapping_ignore_predicate(con.remote.effective_fetch_tags(), &self.ref_map),
+            )?
+        };
         let mut previous_response = None::<gix_protocol::fetch::Response>;
+        let mut packfile_uris = Vec::new();
         let (mut write_pack_bundle, negotiate) = match &action {
             negotiate::Action::NoChange | negotiate::Action::SkipToRefUpdate => {
                 gix_protocol::indicate_end_of_interaction(&mut con.transport, con.trace)
@@ -183,15 +265,23 @@ where
                     &self.ref_map,
                     remote_ref_target_known,
                     &self.shallow,
-                    negotiate::make_refmapping_ignore_predicate(con.remote.fetch_tags, &self.ref_map),
+                    negotiate::make_refmapping_ignore_predicate(con.remote.effective_fetch_tags(), &self.ref_map),
                 );
                 let mut rounds = Vec::new();
                 let is_stateless =
                     arguments.is_stateless(!con.transport.connection_persists_across_multiple_requests());
-                let mut haves_to_send = gix_negotiate::window_size(is_stateless, None);
+                let mut haves_to_send = self.negotiation_window.size(is_stateless, None);
                 let mut seen_ack = false;
                 let mut in_vain = 0;
                 let mut common = is_stateless.then(Vec::new);
+                // The commits the remote acknowledged, to be remembered in case receiving the pack is interrupted.
+                let mut acknowledged = negotiate::add_resumed_common(
+                    negotiator.deref_mut(),
+                    &mut graph,
+                    &mut arguments,
+                    common.as_mut(),
+                    resumed.iter().flat_map(|resumed| resumed.common.iter().copied()),
+                )?;
                 let mut reader = 'negotiation: loop {
                     let _round = gix_trace::detail!("negotiate round", round = rounds.len() + 1);
                     progress.step();
@@ -205,7 +295,8 @@ where
                         previous_response.as_ref(),
                         common.as_mut(),
                     ) {
-                        Ok((haves_sent, ack_seen)) => {
+                        Ok((haves_sent, acks)) => {
+                            let ack_seen = acks.common > 0;
                             if ack_seen {
                                 in_vain = 0;
                             }
@@ -216,9 +307,10 @@ where
                                 in_vain,
                                 haves_to_send,
                                 previous_response_had_at_least_one_in_common: ack_seen,
+                                previous_response_acknowledgements: acks,
                             });
                             let is_done = haves_sent != haves_to_send || (seen_ack && in_vain >= 256);
-                            haves_to_send = gix_negotiate::window_size(is_stateless, Some(haves_to_send));
+                            haves_to_send = self.negotiation_window.size(is_stateless, Some(haves_to_send));
                             is_done
                         }
                         Err(err) => {
@@ -230,7 +322,7 @@ where
                     };
                     let mut reader = arguments.send(&mut con.transport, is_done).await?;
                     if sideband_all {
-                        setup_remote_progress(progress, &mut reader, should_interrupt);
+                        setup_remote_progress(progress, &mut reader, sideband_handler.as_ref(), should_interrupt);
                     }
                     let response = gix_protocol::fetch::Response::from_line_reader(
                         protocol_version,
@@ -240,12 +332,19 @@ where
                     )
                     .await?;
                     let has_pack = response.has_pack();
+                    acknowledged.extend(response.acknowledgements().iter().filter_map(|ack| match ack {
+                        gix_protocol::fetch::response::Acknowledgement::Common(id) => Some(*id),
+                        _ => None,
+                    }));
                     previous_response = Some(response);
                     if has_pack {
+                        if let Some(partial_pack) = partial_pack.as_ref() {
+                            partial_pack.write_common(&acknowledged);
+                        }
                         progress.step();
                         progress.set_name("receiving pack".into());
                         if !sideband_all {
-                            setup_remote_progress(progress, &mut reader, should_interrupt);
+                            setup_remote_progress(progress, &mut reader, sideband_handler.as_ref(), should_interrupt);
                         }
                         break 'negotiation reader;
                     }
@@ -270,6 +369,22 @@ where
                     }
                     shallow_lock = acquire_shallow_lock(repo).map(Some)?;
                 }
+                // All commits of non-shallow repositories are complete, so we must not cut off the history of those we already
+                // have, as it would affect unrelated references when fetching shallowly into an existing repository.
+                // Note that `git` would mark them as shallow nonetheless.
+                let complete_boundary_commits: Vec<_> = if shallow_commits.is_none() {
+                    previous_response
+                        .shallow_updates()
+                        .iter()
+                        .filter_map(|update| match update {
+                            ShallowUpdate::Shallow(id) => Some(*id),
+                            ShallowUpdate::Unshallow(_) => None,
+                        })
+                        .filter(|id| repo.has_object(id))
+                        .collect()
+                } else {
+                    Vec::new()
+                };
 
                 let options = gix_pack::bundle::write::Options {
                     thread_limit: config::index_threads(repo)?,
@@ -279,21 +394,43 @@ where
                 };
 
                 let write_pack_bundle = if matches!(self.dry_run, fetch::DryRun::No) {
+                    // Obtain offloaded packs first, as the inline pack may be thin and refer to their objects.
+                    packfile_uris = packfile_uri::obtain(
+                        repo,
+                        previous_response.packfile_uris(),
+                        progress,
+                        should_interrupt,
+                        options.clone(),
+                    )?;
                     #[cfg(not(feature = "async-network-client"))]
                     let mut rd = reader;
                     #[cfg(feature = "async-network-client")]
                     let mut rd = gix_protocol::futures_lite::io::BlockOn::new(reader);
-                    let res = gix_pack::Bundle::write_to_directory(
-                        &mut rd,
-                        Some(&repo.objects.store_ref().path().join("pack")),
-                        progress,
-                        should_interrupt,
-                        Some(Box::new({
-                            let repo = repo.clone();
-                            repo.objects
-                        })),
-                        options,
-                    )?;
+                    let res = {
+                        let mut tee;
+                        let pack: &mut dyn std::io::BufRead = match partial_pack.as_ref() {
+                            Some(partial_pack) => {
+                                tee = resume::Tee::new(&mut rd, partial_pack.path());
+                                &mut tee
+                            }
+                            None => &mut rd,
+                        };
+                        gix_pack::Bundle::write_to_directory(
+                            pack,
+                            Some(&repo.objects.store_ref().path().join("pack")),
+                            progress,
+                            should_interrupt,
+                            Some(Box::new({
+                                let repo = repo.clone();
+                                repo.objects
+                            })),
+                            options,
+                        )
+                    };
+                    if let Some(partial_pack) = partial_pack.as_ref().filter(|_| res.is_ok()) {
+                        partial_pack.remove();
+                    }
+                    let res = res?;
                     // Assure the final flush packet is consumed.
                     #[cfg(feature = "async-network-client")]
                     let has_read_to_end = { rd.get_ref().stopped_at().is_some() };
@@ -324,28 +461,123 @@ where
                 }
 
                 if let Some(shallow_lock) = shallow_lock {
-                    if !previous_response.shallow_updates().is_empty() {
-                        crate::shallow::write(shallow_lock, shallow_commits, previous_response.shallow_updates())?;
+                    let updates: Vec<_> = previous_response
+                        .shallow_updates()
+                        .iter()
+                        .filter(|update| {
+                            !matches!(update, ShallowUpdate::Shallow(id) if complete_boundary_commits.contains(id))
+                        })
+                        .cloned()
+                        .collect();
+                    if !updates.is_empty() {
+                        crate::shallow::write(shallow_lock, shallow_commits, &updates)?;
                     }
                 }
-                (write_pack_bundle, Some(outcome::Negotiate { graph, rounds }))
+                (
+                    write_pack_bundle,
+                    Some(outcome::Negotiate {
+                        algorithm,
+                        graph,
+                        rounds,
+                    }),
+                )
+            }
+        };
+
+        if self.connectivity_check && matches!(self.dry_run, fetch::DryRun::No) && promisor_filter.is_none() {
+            let is_implicit_tag =
+                negotiate::make_refmapping_ignore_predicate(con.remote.effective_fetch_tags(), &self.ref_map);
+            let missing = connectivity::missing_objects(
+                repo,
+                self.ref_map
+                    .mappings
+                    .iter()
+                    .filter(|mapping| !is_implicit_tag(mapping))
+                    .filter_map(|mapping| mapping.remote.as_id().map(ToOwned::to_owned)),
+                write_pack_bundle
+                    .iter()
+                    .chain(packfile_uris.iter().map(|pack| &pack.write_pack_bundle))
+                    .chain(bundles.iter().filter_map(|bundle| bundle.write_pack_bundle.as_ref())),
+                should_interrupt,
+            )?;
+            if !missing.is_empty() {
+                return Err(Error::MissingObjects { missing });
+            }
+        }
+
+        let received_pack = received_pack(self.received_pack, repo)?;
+        if let (Some(bundle), fetch::ReceivedPack::UnpackBelow { limit }) = (write_pack_bundle.as_mut(), received_pack)
+        {
+            if matches!(self.dry_run, fetch::DryRun::No)
+                && promisor_filter.is_none()
+                && !self.refetch
+                && u64::from(bundle.index.num_objects) < limit
+            {
+                unpack_into_loose_objects(repo, bundle, should_interrupt)?;
+            }
+        }
+
+        let pruned = if con.remote.prune {
+            let tag_spec = fetch::Tags::All.to_refspec().expect("present");
+            let specs: Vec<_> = con
+                .remote
+                .refspecs(remote::Direction::Fetch)
+                .iter()
+                .chain(
+                    self.ref_map
+                        .extra_refspecs
+                        .iter()
+                        .filter(|spec| con.remote.prune_tags || spec.to_ref() != tag_spec),
+                )
+                .cloned()
+                .collect();
+            refs::prune(repo, &specs, &self.ref_map.remote_refs, self.dry_run)?
+        } else {
+            Vec::new()
+        };
+        let reflog_message = self.reflog_message.take().unwrap_or_else(|| RefLogMessage::Prefixed {
+            action: repo
+                .reflog_action()
+                .map_or_else(|| "fetch".into(), |action| action.to_str_lossy().into_owned()),
+        });
+        let mut retries_left = self.ref_update_retries;
+        let mut update_refs = loop {
+            match refs::update(
+                repo,
+                reflog_message.clone(),
+                &self.ref_map.mappings,
+                con.remote.refspecs(remote::Direction::Fetch),
+                &self.ref_map.extra_refspecs,
+                con.remote.effective_fetch_tags(),
+                self.dry_run,
+                self.write_packed_refs,
+                self.reflog_committer.as_ref().map(gix_actor::Signature::to_ref),
+            ) {
+                Err(err) if retries_left > 0 && err.is_caused_by_concurrent_modification() => {
+                    gix_trace::debug!(
+                        "retrying ref update after concurrent modification ({retries_left} retries left): {err}"
+                    );
+                    retries_left -= 1;
+                }
+                res => break res?,
             }
         };
+        update_refs.pruned = pruned;
+        if self.write_fetch_head && matches!(self.dry_run, fetch::DryRun::No) {
+            fetch_head::write(repo, con.remote, &self.ref_map, &update_refs)?;
+        }
 
-        let update_refs = refs::update(
-            repo,
-            self.reflog_message
-                .take()
-                .unwrap_or_else(|| RefLogMessage::Prefixed { action: "fetch".into() }),
-            &self.ref_map.mappings,
-            con.remote.refspecs(remote::Direction::Fetch),
-            &self.ref_map.extra_refspecs,
-            con.remote.fetch_tags,
-            self.dry_run,
-            self.write_packed_refs,
-        )?;
-
-        if let Some(bundle) = write_pack_bundle.as_mut() {
+        if promisor_filter.is_some() {
+            write_promisor_files(
+                &self.ref_map,
+                write_pack_bundle
+                    .iter()
+                    .chain(packfile_uris.iter().map(|pack| &pack.write_pack_bundle)),
+            )?;
+        }
+
+        let keep_packs = received_pack == fetch::ReceivedPack::Keep;
+        if let Some(bundle) = write_pack_bundle.as_mut().filter(|_| !keep_packs) {
             if !update_refs.edits.is_empty() || bundle.index.num_objects == 0 {
                 if let Some(path) = bundle.keep_path.take() {
                     std::fs::remove_file(&path).map_err(|err| Error::RemovePackKeepFile { path, source: err })?;
@@ -353,6 +585,45 @@ where
             }
         }
 
+        if !update_refs.edits.is_empty() && !keep_packs {
+            for path in packfile_uris
+                .iter_mut()
+                .filter_map(|pack| pack.write_pack_bundle.keep_path.take())
+            {
+                std::fs::remove_file(&path).map_err(|err| Error::RemovePackKeepFile { path, source: err })?;
+            }
+        }
+
+        if let Some(path) = resumed.and_then(|resumed| resumed.keep_path) {
+            std::fs::remove_file(&path).map_err(|err| Error::RemovePackKeepFile { path, source: err })?;
+        }
+        if self.refetch {
+            if let Some(bundle) = write_pack_bundle.as_ref() {
+                remove_superseded_packs(repo, bundle);
+            }
+        }
+
+        let commit_graph = if matches!(self.dry_run, fetch::DryRun::No)
+            && !update_refs.edits.is_empty()
+            && write_commit_graph(self.write_commit_graph, repo)?
+        {
+            // Like `git`, write all commits reachable from references, not only those that were just fetched.
+            let mut tips = Vec::new();
+            let refs = repo.refs.iter()?;
+            for reference in refs.all().map_err(crate::reference::iter::init::Error::Io)? {
+                tips.extend(reference?.target.try_id().map(ToOwned::to_owned));
+            }
+            repo.write_commit_graph(tips)?
+        } else {
+            None
+        };
+
+        let maintenance = if self.auto_maintenance && matches!(self.dry_run, fetch::DryRun::No) {
+            Some(crate::maintenance::auto(repo, progress, should_interrupt)?)
+        } else {
+            None
+        };
+
         let out = Outcome {
             ref_map: std::mem::take(&mut self.ref_map),
             status: match write_pack_bundle {
@@ -367,14 +638,158 @@ where
                     update_refs,
                 },
             },
+            bundles,
+            packfile_uris,
+            commit_graph,
+            maintenance,
         };
         Ok(out)
     }
 }
 
+/// Return `true` if bundles advertised by the remote should be used, with `enabled` overriding `transfer.bundleURI`.
+fn use_bundle_uri(enabled: Option<bool>, repo: &Repository) -> Result<bool, Error> {
+    if let Some(enabled) = enabled {
+        return Ok(enabled);
+    }
+    Ok(repo
+        .config
+        .resolved
+        .boolean(&Transfer::BUNDLE_URI)
+        .map(|v| Transfer::BUNDLE_URI.enrich_error(v))
+        .transpose()
+        .with_leniency(repo.config.lenient_config)
+        .map_err(Error::BundleUriConfig)?
+        .unwrap_or_default())
+}
+
+/// Determine what to do with the received pack, with `policy` overriding `fetch.unpackLimit` and `transfer.unpackLimit`.
+fn received_pack(policy: Option<fetch::ReceivedPack>, repo: &Repository) -> Result<fetch::ReceivedPack, Error> {
+    if let Some(policy) = policy {
+        return Ok(policy);
+    }
+    let limit = [&Fetch::UNPACK_LIMIT, &Transfer::UNPACK_LIMIT]
+        .into_iter()
+        .find_map(|key| repo.config.resolved.integer(key).map(|value| key.try_into_u64(value)))
+        .transpose()
+        .with_leniency(repo.config.lenient_config)
+        .map_err(Error::UnpackLimitConfig)?;
+    Ok(match limit {
+        Some(limit) if limit > 0 => fetch::ReceivedPack::UnpackBelow { limit },
+        _ => fetch::ReceivedPack::Repackable,
+    })
+}
+
+/// Write all objects of the freshly received pack described by `bundle` as loose objects and remove the pack along
+/// with its `.keep` file, clearing the respective paths in `bundle`.
+///
+/// If the pack can't be removed, for instance because it's still in use on some platforms, it's left in place as the
+/// object database remains intact either way.
+fn unpack_into_loose_objects(
+    repo: &Repository,
+    bundle: &mut gix_pack::bundle::write::Outcome,
+    should_interrupt: &AtomicBool,
+) -> Result<(), Error> {
+    use gix_object::FindExt;
+    use gix_odb::Write;
+
+    let Some(index_path) = bundle.index_path.clone() else {
+        return Ok(());
+    };
+    let index = gix_pack::index::File::at(&index_path, repo.object_hash()).map_err(Error::UnpackOpenIndex)?;
+    let loose = gix_odb::loose::Store::at(repo.objects.store_ref().path(), repo.object_hash());
+    let mut buf = Vec::new();
+    for entry in index.iter() {
+        if should_interrupt.load(Ordering::Relaxed) {
+            return Err(Error::Interrupted);
+        }
+        let id = entry.oid;
+        let data = repo
+            .objects
+            .find(&id, &mut buf)
+            .map_err(|err| Error::UnpackFindObject { id, source: err })?;
+        loose
+            .write_buf(data.kind, data.data)
+            .map_err(|err| Error::UnpackWriteObject { id, source: err })?;
+    }
+    drop(index);
+
+    if std::fs::remove_file(&index_path).is_err() {
+        return Ok(());
+    }
+    bundle.index_path = None;
+    if let Some(data_path) = bundle.data_path.take() {
+        std::fs::remove_file(data_path).ok();
+    }
+    if let Some(path) = bundle.keep_path.take() {
+        std::fs::remove_file(&path).map_err(|err| Error::RemovePackKeepFile { path, source: err })?;
+    }
+    Ok(())
+}
+
+/// Write a `.promisor` file next to each pack in `bundles`, listing the references from `ref_map` that were fetched,
+/// to mark their objects as coming from a promisor remote which may provide objects that are missing locally.
+fn write_promisor_files<'a>(
+    ref_map: &fetch::RefMap,
+    bundles: impl Iterator<Item = &'a gix_pack::bundle::write::Outcome>,
+) -> Result<(), Error> {
+    let mut refs = Vec::new();
+    for mapping in &ref_map.mappings {
+        if let (Some(id), Some(name)) = (mapping.remote.as_id(), mapping.remote.as_name()) {
+            refs.extend_from_slice(format!("{id} ").as_bytes());
+            refs.extend_from_slice(name);
+            refs.push(b'\n');
+        }
+    }
+    for data_path in bundles.filter_map(|bundle| bundle.data_path.as_ref()) {
+        let path = data_path.with_extension("promisor");
+        std::fs::write(&path, &refs).map_err(|source| Error::WritePromisorFile { path, source })?;
+    }
+    Ok(())
+}
+
+/// Remove all packs whose objects are also contained in the freshly received pack described by `bundle`.
+///
+/// This is done on a best-effort basis, as these packs are merely redundant, and failing to remove them
+/// (for instance because they are still in use on some platforms) leaves the object database intact.
+fn remove_superseded_packs(repo: &Repository, bundle: &gix_pack::bundle::write::Outcome) {
+    let pack_dir = repo.objects.store_ref().path().join("pack");
+    if pack_dir.join("multi-pack-index").is_file() {
+        return;
+    }
+    let Some(received_index) = bundle
+        .index_path
+        .as_ref()
+        .and_then(|path| gix_pack::index::File::at(path, repo.object_hash()).ok())
+    else {
+        return;
+    };
+    let Ok(entries) = std::fs::read_dir(&pack_dir) else {
+        return;
+    };
+    for index_path in entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "idx"))
+        .filter(|path| Some(path) != bundle.index_path.as_ref())
+    {
+        if index_path.with_extension("keep").exists() {
+            continue;
+        }
+        let Ok(index) = gix_pack::index::File::at(&index_path, repo.object_hash()) else {
+            continue;
+        };
+        if index.iter().all(|entry| received_index.lookup(entry.oid).is_some()) {
+            for extension in ["pack", "rev", "bitmap", "promisor", "mtimes", "idx"] {
+                std::fs::remove_file(index_path.with_extension(extension)).ok();
+            }
+        }
+    }
+}
+
 fn acquire_shallow_lock(repo: &Repository) -> Result<gix_lock::File, Error> {
-    gix_lock::File::acquire_to_update_resource(repo.shallow_file(), gix_lock::acquire::Fail::Immediately, None)
-        .map_err(Into::into)
+    let lock_mode = repo.config.other_lock_timeout()?;
+    gix_lock::File::acquire_to_update_resource(repo.shallow_file(), lock_mode, None).map_err(Into::into)
 }
 
 fn add_shallow_args(
@@ -423,9 +838,10 @@ fn add_shallow_args(
     Ok((shallow_commits, shallow_lock))
 }
 
-fn setup_remote_progress<'a>(
+fn setup_remote_progress<'a, 'handler: 'a>(
     progress: &mut dyn crate::DynNestedProgress,
     reader: &mut Box<dyn gix_protocol::transport::client::ExtendedBufRead<'a> + Unpin + 'a>,
+    sideband_handler: Option<&'a std::cell::RefCell<fetch::SidebandHandlerFn<'handler>>>,
     should_interrupt: &'a AtomicBool,
 ) {
     use gix_protocol::transport::client::ExtendedBufRead;
@@ -433,6 +849,16 @@ fn setup_remote_progress<'a>(
         let mut remote_progress = progress.add_child_with_id("remote".to_string(), ProgressId::RemoteProgress.into());
         move |is_err: bool, data: &[u8]| {
             gix_protocol::RemoteProgress::translate_to_progress(is_err, data, &mut remote_progress);
+            if let Some(handler) = sideband_handler {
+                // ignore keep-alive packets sent with 'sideband-all'
+                if !data.is_empty() {
+                    (handler.borrow_mut())(if is_err {
+                        fetch::SidebandMessage::Error(data.into())
+                    } else {
+                        fetch::SidebandMessage::Progress(data.into())
+                    });
+                }
+            }
             if should_interrupt.load(Ordering::Relaxed) {
                 ProgressAction::Interrupt
             } else {
@@ -441,3 +867,25 @@ fn setup_remote_progress<'a>(
         }
     }) as gix_protocol::transport::client::HandleProgress<'a>));
 }
+
+/// Return `true` if the commit-graph should be written after fetching, with `enabled` overriding `fetch.writeCommitGraph`.
+/// It's never written if commit-graphs are disabled via `core.commitGraph`.
+fn write_commit_graph(enabled: Option<bool>, repo: &Repository) -> Result<bool, Error> {
+    let enabled = match enabled {
+        Some(enabled) => enabled,
+        None => repo
+            .config
+            .resolved
+            .boolean(&Fetch::WRITE_COMMIT_GRAPH)
+            .map(|v| Fetch::WRITE_COMMIT_GRAPH.enrich_error(v))
+            .transpose()
+            .with_leniency(repo.config.lenient_config)
+            .map_err(Error::WriteCommitGraphConfig)?
+            .unwrap_or_default(),
+    };
+    Ok(enabled
+        && repo
+            .config
+            .may_use_commit_graph()
+            .map_err(Error::WriteCommitGraphConfig)?)
+}
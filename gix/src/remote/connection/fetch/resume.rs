@@ -0,0 +1,401 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::{BufRead, Read, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use gix_hash::ObjectId;
+use gix_odb::HeaderExt;
+
+use crate::{remote::fetch::Error, Repository};
+
+/// The prefix of files in the `objects/pack` directory which hold the pack received by an interrupted fetch, followed
+/// by a key derived from the remote and the objects it was asked for.
+///
+/// Like all files starting with `tmp_`, `git gc` will remove them once they are old enough.
+const PARTIAL_PACK_FILE_PREFIX: &str = "tmp_gix_partial_pack-";
+
+/// The file holding the pack received by an interrupted fetch of the same objects from the same remote, along with the
+/// commits that the remote acknowledged as common during the negotiation that preceded it.
+///
+/// It's locked for as long as this instance exists, so concurrent fetches can't interfere with each other's downloads.
+pub(crate) struct PartialPack {
+    path: PathBuf,
+    _lock: gix_lock::Marker,
+}
+
+impl PartialPack {
+    /// Lock the partial pack of a fetch of `wants` from `url`, or return `None` if another fetch of the same objects
+    /// holds it already, in which case the current fetch can't be resumed.
+    pub(crate) fn lock<'a>(
+        repo: &Repository,
+        url: Option<&gix_url::Url>,
+        wants: impl IntoIterator<Item = &'a gix_hash::oid>,
+    ) -> Option<Self> {
+        let mut wants: Vec<_> = wants.into_iter().collect();
+        wants.sort();
+        wants.dedup();
+        let mut hasher = gix_features::hash::hasher(repo.object_hash());
+        hasher.update(&url.map(gix_url::Url::to_bstring).unwrap_or_default());
+        for id in wants {
+            hasher.update(b"\n");
+            hasher.update(id.to_hex().to_string().as_bytes());
+        }
+        let path = repo
+            .objects
+            .store_ref()
+            .path()
+            .join("pack")
+            .join(format!("{PARTIAL_PACK_FILE_PREFIX}{}", ObjectId::from(hasher.digest())));
+        let lock =
+            gix_lock::Marker::acquire_to_hold_resource(&path, gix_lock::acquire::Fail::Immediately, None).ok()?;
+        Some(PartialPack { path, _lock: lock })
+    }
+
+    /// The path to the partial pack itself.
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn common_path(&self) -> PathBuf {
+        self.path.with_extension("common")
+    }
+
+    /// Remember the commits the remote acknowledged as `common` before sending the pack, to avoid negotiating them again
+    /// when resuming.
+    ///
+    /// Failing to write the file isn't fatal, it merely means more negotiation rounds are needed when resuming.
+    pub(crate) fn write_common(&self, common: &[ObjectId]) {
+        let mut buf = Vec::new();
+        for id in common {
+            writeln!(buf, "{id}").expect("writing to memory works");
+        }
+        std::fs::write(self.common_path(), buf).ok();
+    }
+
+    fn read_common(&self) -> Vec<ObjectId> {
+        std::fs::read_to_string(self.common_path())
+            .map(|buf| {
+                buf.lines()
+                    .filter_map(|line| ObjectId::from_hex(line.as_bytes()).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Remove the partial pack along with the state of the negotiation that led to it.
+    pub(crate) fn remove(&self) {
+        std::fs::remove_file(&self.path).ok();
+        std::fs::remove_file(self.common_path()).ok();
+    }
+}
+
+/// The result of [`restore_partial_pack()`].
+pub(crate) struct Restored {
+    /// All commits in the restored pack whose history and trees are fully present in the object database,
+    /// which makes them suitable for use as tips during negotiation.
+    pub(crate) complete_commits: Vec<ObjectId>,
+    /// All commits and annotated tags in the restored pack which are missing parts of their history or trees,
+    /// and thus must not be considered present even though the objects themselves exist.
+    pub(crate) incomplete: HashSet<ObjectId>,
+    /// The `.keep` file protecting the restored pack, to be removed once references were updated.
+    pub(crate) keep_path: Option<PathBuf>,
+    /// The commits the remote acknowledged as common during the negotiation of the interrupted fetch.
+    pub(crate) common: Vec<ObjectId>,
+}
+
+/// Index all objects that were fully received by a previous fetch which got interrupted, if there is such a `partial` pack,
+/// and return all commits that can serve as additional tips for negotiation.
+///
+/// Restoring is done on a best-effort basis, and the partial pack is removed unless we are interrupted, so that
+/// a corrupt partial pack can't get in the way of future fetches.
+pub(crate) fn restore_partial_pack(
+    repo: &Repository,
+    partial: &PartialPack,
+    progress: &mut dyn crate::DynNestedProgress,
+    should_interrupt: &AtomicBool,
+) -> Result<Option<Restored>, Error> {
+    let path = partial.path();
+    let Ok(file) = std::fs::File::open(path) else {
+        return Ok(None);
+    };
+    progress.set_name("restoring partial pack".into());
+    let res = gix_pack::Bundle::write_to_directory(
+        &mut std::io::BufReader::new(file),
+        path.parent(),
+        progress,
+        should_interrupt,
+        Some(Box::new({
+            let repo = repo.clone();
+            repo.objects
+        })),
+        gix_pack::bundle::write::Options {
+            thread_limit: super::config::index_threads(repo)?,
+            index_version: super::config::pack_index_version(repo)?,
+            iteration_mode: gix_pack::data::input::Mode::Restore,
+            object_hash: repo.object_hash(),
+        },
+    );
+    let outcome = match res {
+        Ok(outcome) => outcome,
+        Err(err) if should_interrupt.load(Ordering::Relaxed) => return Err(err.into()),
+        Err(_) => {
+            partial.remove();
+            return Ok(None);
+        }
+    };
+    let common = partial.read_common();
+    partial.remove();
+
+    let mut complete_commits = Vec::new();
+    let mut incomplete = HashSet::new();
+    if let Some(index) = outcome
+        .index_path
+        .as_ref()
+        .and_then(|path| gix_pack::index::File::at(path, repo.object_hash()).ok())
+    {
+        let mut is_complete = Closure::new(repo, index.iter().map(|entry| entry.oid).collect());
+        for entry in index.iter() {
+            match repo.objects.header(entry.oid).map(|header| header.kind()) {
+                Ok(gix_object::Kind::Commit) => {
+                    if is_complete.commit(entry.oid) {
+                        complete_commits.push(entry.oid);
+                    } else {
+                        incomplete.insert(entry.oid);
+                    }
+                }
+                Ok(gix_object::Kind::Tag) => {
+                    let target = repo
+                        .find_object(entry.oid)
+                        .ok()
+                        .and_then(|tag| tag.peel_tags_to_end().ok())
+                        .map(|target| (target.id, target.kind));
+                    let target_is_complete = match target {
+                        Some((id, gix_object::Kind::Commit)) => is_complete.commit(id),
+                        Some((id, gix_object::Kind::Tree)) => is_complete.tree(id),
+                        Some(_) => true,
+                        None => false,
+                    };
+                    if !target_is_complete {
+                        incomplete.insert(entry.oid);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(Some(Restored {
+        complete_commits,
+        incomplete,
+        keep_path: outcome.keep_path,
+        common,
+    }))
+}
+
+/// Determine if all objects reachable from a commit are present in the object database, memoizing results.
+///
+/// Only objects of the restored pack are checked in full, as everything else was received by previous fetches.
+/// Commits that aren't part of it are complete only if they are reachable from references, which is determined by
+/// walking from all references only as far back in time as needed.
+struct Closure<'repo> {
+    repo: &'repo Repository,
+    in_pack: HashSet<ObjectId>,
+    commits: HashMap<ObjectId, bool>,
+    complete_trees: HashSet<ObjectId>,
+    reachable: HashSet<ObjectId>,
+    unwalked: gix_revwalk::PriorityQueue<gix_date::SecondsSinceUnixEpoch, ObjectId>,
+}
+
+impl<'repo> Closure<'repo> {
+    fn new(repo: &'repo Repository, in_pack: HashSet<ObjectId>) -> Self {
+        let mut closure = Closure {
+            repo,
+            in_pack,
+            commits: Default::default(),
+            complete_trees: Default::default(),
+            reachable: Default::default(),
+            unwalked: gix_revwalk::PriorityQueue::new(),
+        };
+        let tips: Vec<_> = repo
+            .references()
+            .ok()
+            .and_then(|platform| {
+                Some(
+                    platform
+                        .all()
+                        .ok()?
+                        .peeled()
+                        .ok()?
+                        .filter_map(Result::ok)
+                        .map(|reference| reference.id().detach())
+                        .collect(),
+                )
+            })
+            .unwrap_or_default();
+        for id in tips {
+            if let Some(time) = closure.commit_time(id) {
+                closure.unwalked.insert(time, id);
+            }
+        }
+        closure
+    }
+
+    fn commit(&mut self, id: ObjectId) -> bool {
+        // Histories can be deep, so avoid recursion in favor of a stack which revisits each commit once its parents are known.
+        let mut stack = vec![(id, false)];
+        while let Some((id, parents_are_known)) = stack.pop() {
+            if parents_are_known {
+                let parents = self.parents(id);
+                let is_complete = parents
+                    .iter()
+                    .all(|parent| self.commits.get(parent).copied().unwrap_or(false));
+                self.commits.insert(id, is_complete);
+                continue;
+            }
+            if self.commits.contains_key(&id) {
+                continue;
+            }
+            if !self.in_pack.contains(&id) {
+                let is_complete = self.is_reachable_from_refs(id);
+                self.commits.insert(id, is_complete);
+                continue;
+            }
+            let tree_is_complete = self
+                .repo
+                .find_object(id)
+                .ok()
+                .and_then(|obj| obj.try_into_commit().ok())
+                .and_then(|commit| commit.tree_id().ok().map(crate::Id::detach))
+                .map_or(false, |tree| self.tree(tree));
+            if !tree_is_complete {
+                self.commits.insert(id, false);
+                continue;
+            }
+            stack.push((id, true));
+            stack.extend(
+                self.parents(id)
+                    .into_iter()
+                    .filter(|parent| !self.commits.contains_key(parent))
+                    .map(|parent| (parent, false)),
+            );
+        }
+        self.commits.get(&id).copied().unwrap_or(false)
+    }
+
+    /// Walk the history of all references until commits older than `id` are reached, to see if `id` was among them.
+    fn is_reachable_from_refs(&mut self, id: ObjectId) -> bool {
+        let Some(time) = self.commit_time(id) else {
+            return false;
+        };
+        while self.unwalked.peek().map_or(false, |(tip_time, _)| *tip_time >= time) {
+            let (_, tip) = self.unwalked.pop().expect("peeked");
+            if !self.reachable.insert(tip) {
+                continue;
+            }
+            for parent in self.parents(tip) {
+                if self.reachable.contains(&parent) {
+                    continue;
+                }
+                if let Some(time) = self.commit_time(parent) {
+                    self.unwalked.insert(time, parent);
+                }
+            }
+        }
+        self.reachable.contains(&id)
+    }
+
+    fn commit_time(&self, id: ObjectId) -> Option<gix_date::SecondsSinceUnixEpoch> {
+        let commit = self.repo.find_object(id).ok()?.try_into_commit().ok()?;
+        Some(commit.time().ok()?.seconds)
+    }
+
+    fn parents(&self, id: ObjectId) -> Vec<ObjectId> {
+        self.repo
+            .find_object(id)
+            .ok()
+            .and_then(|obj| obj.try_into_commit().ok())
+            .map(|commit| commit.parent_ids().map(crate::Id::detach).collect())
+            .unwrap_or_default()
+    }
+
+    fn tree(&mut self, id: ObjectId) -> bool {
+        if self.complete_trees.contains(&id) {
+            return true;
+        }
+        if !self.in_pack.contains(&id) {
+            return self.repo.has_object(id);
+        }
+        let Some(tree) = self.repo.find_object(id).ok().and_then(|obj| obj.try_into_tree().ok()) else {
+            return false;
+        };
+        let Ok(decoded) = tree.decode() else {
+            return false;
+        };
+        let entries: Vec<_> = decoded
+            .entries
+            .iter()
+            .filter(|entry| !entry.mode.is_commit())
+            .map(|entry| (entry.oid.to_owned(), entry.mode.is_tree()))
+            .collect();
+        drop(tree);
+        let is_complete = entries.into_iter().all(|(oid, is_tree)| {
+            if is_tree {
+                self.tree(oid)
+            } else {
+                self.repo.has_object(oid)
+            }
+        });
+        if is_complete {
+            self.complete_trees.insert(id);
+        }
+        is_complete
+    }
+}
+
+/// A reader which writes all bytes consumed from the pack stream to a file, so they can be [restored](restore_partial_pack())
+/// should the fetch be interrupted.
+///
+/// Failing to write the file isn't fatal, it merely means the fetch can't be resumed.
+pub(crate) struct Tee<'a> {
+    inner: &'a mut dyn BufRead,
+    out: Option<std::io::BufWriter<std::fs::File>>,
+}
+
+impl<'a> Tee<'a> {
+    pub(crate) fn new(inner: &'a mut dyn BufRead, path: &std::path::Path) -> Self {
+        Tee {
+            inner,
+            out: std::fs::File::create(path).ok().map(std::io::BufWriter::new),
+        }
+    }
+}
+
+impl Read for Tee<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let available = self.fill_buf()?;
+        let amt = available.len().min(buf.len());
+        buf[..amt].copy_from_slice(&available[..amt]);
+        self.consume(amt);
+        Ok(amt)
+    }
+}
+
+impl BufRead for Tee<'_> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if let Some(out) = self.out.as_mut() {
+            let written = self
+                .inner
+                .fill_buf()
+                .and_then(|buf| out.write_all(&buf[..amt.min(buf.len())]));
+            if written.is_err() {
+                self.out = None;
+            }
+        }
+        self.inner.consume(amt);
+    }
+}
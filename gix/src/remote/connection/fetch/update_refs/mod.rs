@@ -22,6 +22,9 @@ use crate::{
 ///
 pub mod update;
 
+mod prune;
+pub(crate) use prune::prune;
+
 /// Information about the update of a single reference, corresponding the respective entry in [`RefMap::mappings`][crate::remote::fetch::RefMap::mappings].
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Update {
@@ -49,6 +52,7 @@ impl From<Mode> for Update {
 /// `repo` is not actually changed. Also it won't perform an 'object exists' check as these are likely not to exist as the pack
 /// wasn't fetched either.
 /// `action` is the prefix used for reflog entries, and is typically "fetch".
+/// `committer` is the identity to write into reflog entries, and if `None` the configured committer is used.
 ///
 /// It can be used to produce typical information that one is used to from `git fetch`.
 ///
@@ -69,6 +73,7 @@ pub(crate) fn update(
     fetch_tags: fetch::Tags,
     dry_run: fetch::DryRun,
     write_packed_refs: fetch::WritePackedRefs,
+    committer: Option<gix_actor::SignatureRef<'_>>,
 ) -> Result<update::Outcome, update::Error> {
     let _span = gix_trace::detail!("update_refs()", mappings = mappings.len());
     let mut edits = Vec::new();
@@ -333,13 +338,20 @@ pub(crate) fn update(
                 )
                 .prepare(edits, file_lock_fail, packed_refs_lock_fail)
                 .map_err(crate::reference::edit::Error::from)?
-                .commit(repo.committer().transpose().map_err(|err| update::Error::EditReferences(crate::reference::edit::Error::ParseCommitterTime(err)))?)
+                .commit(match committer {
+                    Some(committer) => Some(committer),
+                    None => repo.committer().transpose().map_err(|err| update::Error::EditReferences(crate::reference::edit::Error::ParseCommitterTime(err)))?,
+                })
                 .map_err(crate::reference::edit::Error::from)?
         }
         fetch::DryRun::Yes => edits,
     };
 
-    Ok(update::Outcome { edits, updates })
+    Ok(update::Outcome {
+        edits,
+        updates,
+        pruned: Vec::new(),
+    })
 }
 
 /// Figure out if target of `edit` points to a reference that doesn't exist in `repo` and won't exist as it's not in any of `edits`.
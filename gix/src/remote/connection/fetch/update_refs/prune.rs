@@ -0,0 +1,84 @@
+use std::collections::BTreeSet;
+
+use gix_ref::{
+    transaction::{Change, PreviousValue, RefEdit, RefLog},
+    TargetRef,
+};
+
+use super::{update, worktree_branches};
+use crate::{
+    bstr::{BStr, ByteSlice},
+    remote::fetch,
+    Repository,
+};
+
+/// Delete all local references that are written by `refspecs` but whose source doesn't exist in `remote_refs` anymore,
+/// and return them as they were before the deletion.
+///
+/// References whose source is excluded by a negative ref-spec are kept, as are symbolic references and branches
+/// that are checked out in any worktree.
+/// If `dry_run` is enabled, nothing is deleted but the returned references would have been.
+pub(crate) fn prune(
+    repo: &Repository,
+    refspecs: &[gix_refspec::RefSpec],
+    remote_refs: &[gix_protocol::handshake::Ref],
+    dry_run: fetch::DryRun,
+) -> Result<Vec<gix_ref::Reference>, update::Error> {
+    let _span = gix_trace::detail!("prune_refs()", refspecs = refspecs.len());
+    let remote_names: BTreeSet<&BStr> = remote_refs.iter().map(|r| r.unpack().0).collect();
+    let group = gix_refspec::MatchGroup::from_fetch_specs(refspecs.iter().map(gix_refspec::RefSpec::to_ref));
+    let null = repo.object_hash().null();
+    let checked_out_branches = worktree_branches(repo)?;
+
+    let mut stale = Vec::new();
+    for reference in repo.references()?.all()? {
+        let reference = reference.map_err(update::Error::IterateReferences)?;
+        if matches!(reference.target(), TargetRef::Symbolic(_)) || checked_out_branches.contains_key(reference.name()) {
+            continue;
+        }
+        let sources: Vec<_> = refspecs
+            .iter()
+            .filter_map(|spec| crate::remote::fetch_source_of(spec.to_ref(), reference.name().as_bstr()))
+            .collect();
+        let is_stale = !sources.is_empty()
+            && !sources.iter().any(|source| {
+                exists_on_remote(source.as_ref(), &remote_names)
+                    || group.is_excluded(gix_refspec::match_group::Item {
+                        full_ref_name: source.as_ref(),
+                        target: &null,
+                        object: None,
+                    })
+            });
+        if is_stale {
+            stale.push(reference.detach());
+        }
+    }
+
+    if matches!(dry_run, fetch::DryRun::No) && !stale.is_empty() {
+        repo.edit_references(stale.iter().map(|reference| RefEdit {
+            change: Change::Delete {
+                expected: PreviousValue::MustExistAndMatch(reference.target.clone()),
+                log: RefLog::AndReference,
+            },
+            name: reference.name.clone(),
+            deref: false,
+        }))?;
+    }
+    Ok(stale)
+}
+
+/// Return `true` if `source`, which may be a partial name, names any of the references in `remote_names`,
+/// using the same rules as `git` to expand partial names.
+fn exists_on_remote(source: &BStr, remote_names: &BTreeSet<&BStr>) -> bool {
+    if remote_names.contains(source) {
+        return true;
+    }
+    if source.starts_with(b"refs/") {
+        return false;
+    }
+    ["refs/", "refs/tags/", "refs/heads/", "refs/remotes/"]
+        .iter()
+        .map(|prefix| format!("{prefix}{source}"))
+        .chain(Some(format!("refs/remotes/{source}/HEAD")))
+        .any(|name| remote_names.contains(name.as_bytes().as_bstr()))
+}
@@ -153,6 +153,7 @@ mod update {
                 fetch::Tags::None,
                 reflog_message.map_or(fetch::DryRun::No, |_| fetch::DryRun::Yes),
                 fetch::WritePackedRefs::Never,
+                None,
             )
             .unwrap();
 
@@ -190,6 +191,142 @@ mod update {
         }
     }
 
+    #[test]
+    fn summaries_classify_updates_like_git_fetch() -> Result {
+        use crate::bstr::ByteSlice;
+        use fetch::refs::update::Kind;
+        let repo = repo("two-origins");
+        let id = |name: &str| -> Result<gix_hash::ObjectId> { Ok(repo.find_reference(name)?.id().detach()) };
+        for (spec, expected_kind, expected_flag, expected_summary) in [
+            (
+                "refs/heads/main:refs/remotes/origin/main",
+                Kind::UpToDate,
+                '=',
+                "[up to date]".to_string(),
+            ),
+            (
+                "refs/heads/main:refs/remotes/origin/new-main",
+                Kind::NewBranch,
+                '*',
+                "[new branch]".into(),
+            ),
+            (
+                "+refs/heads/main:refs/tags/b-tag",
+                Kind::TagUpdate {
+                    previous: Some(id("refs/tags/b-tag")?),
+                    new: Some(id("refs/heads/main")?),
+                },
+                't',
+                "[tag update]".into(),
+            ),
+            (
+                "refs/heads/main:refs/tags/b-tag",
+                Kind::Rejected(fetch::refs::update::Mode::RejectedTagUpdate),
+                '!',
+                "[rejected]".into(),
+            ),
+            (
+                "refs/remotes/origin/g:refs/heads/not-currently-checked-out",
+                Kind::FastForward {
+                    previous: Some(id("refs/heads/not-currently-checked-out")?),
+                    new: id("refs/remotes/origin/g")?,
+                },
+                ' ',
+                format!(
+                    "{}..{}",
+                    id("refs/heads/not-currently-checked-out")?.to_hex_with_len(7),
+                    id("refs/remotes/origin/g")?.to_hex_with_len(7)
+                ),
+            ),
+        ] {
+            let (mappings, specs) = mapping_from_spec(spec, &repo);
+            let out = fetch::refs::update(
+                &repo,
+                prefixed("action"),
+                &mappings,
+                &specs,
+                &[],
+                fetch::Tags::None,
+                fetch::DryRun::Yes,
+                fetch::WritePackedRefs::Never,
+                None,
+            )?;
+
+            let summaries: Vec<_> = out.iter_summaries(&mappings).collect();
+            assert_eq!(summaries.len(), 1, "{spec}: one summary per mapping");
+            let summary = &summaries[0];
+            assert_eq!(summary.kind, expected_kind, "{spec}");
+            assert_eq!(summary.kind.flag(), expected_flag, "{spec}");
+            assert_eq!(summary.kind.to_string(), expected_summary, "{spec}");
+            assert_eq!(summary.remote, mappings[0].remote.as_name(), "{spec}");
+            assert_eq!(
+                summary.local,
+                mappings[0].local.as_ref().map(|name| name.as_bstr()),
+                "{spec}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn summaries_can_be_written_like_git_fetch() -> Result {
+        use crate::bstr::ByteSlice;
+        use fetch::refs::update::Format;
+        let repo = repo("two-origins");
+        let id = |name: &str| -> Result<gix_hash::ObjectId> { Ok(repo.find_reference(name)?.id().detach()) };
+        let null = repo.object_hash().null();
+        for (spec, include_up_to_date, expected_full, expected_porcelain) in [
+            (
+                "refs/heads/main:refs/remotes/origin/new-main",
+                false,
+                " * [new branch]      main       -> origin/new-main\n".to_string(),
+                format!("* {null} {} refs/remotes/origin/new-main\n", id("refs/heads/main")?),
+            ),
+            ("refs/heads/main:refs/remotes/origin/main", false, "".into(), "".into()),
+            (
+                "refs/heads/main:refs/remotes/origin/main",
+                true,
+                " = [up to date]      main       -> origin/main\n".into(),
+                format!(
+                    "= {main} {main} refs/remotes/origin/main\n",
+                    main = id("refs/heads/main")?
+                ),
+            ),
+            (
+                "refs/heads/main:refs/tags/b-tag",
+                false,
+                " ! [rejected]        main       -> b-tag  (would clobber existing tag)\n".into(),
+                format!("! {null} {} refs/tags/b-tag\n", id("refs/heads/main")?),
+            ),
+            (
+                "refs/heads/main",
+                false,
+                " * branch            main       -> FETCH_HEAD\n".into(),
+                format!("* {null} {} FETCH_HEAD\n", id("refs/heads/main")?),
+            ),
+        ] {
+            let (mappings, specs) = mapping_from_spec(spec, &repo);
+            let out = fetch::refs::update(
+                &repo,
+                prefixed("action"),
+                &mappings,
+                &specs,
+                &[],
+                fetch::Tags::None,
+                fetch::DryRun::Yes,
+                fetch::WritePackedRefs::Never,
+                None,
+            )?;
+
+            for (format, expected) in [(Format::Full, expected_full), (Format::Porcelain, expected_porcelain)] {
+                let mut buf = Vec::new();
+                out.write_summaries(&mappings, format, include_up_to_date, repo.object_hash(), &mut buf)?;
+                assert_eq!(buf.as_bstr(), expected.as_str(), "{spec}: {format:?}");
+            }
+        }
+        Ok(())
+    }
+
     #[test]
     fn checked_out_branches_in_worktrees_are_rejected_with_additional_information() -> Result {
         let root = gix_path::realpath(gix_testtools::scripted_fixture_read_only_with_args_single_archive(
@@ -217,6 +354,7 @@ mod update {
                 fetch::Tags::None,
                 fetch::DryRun::Yes,
                 fetch::WritePackedRefs::Never,
+                None,
             )?;
 
             assert_eq!(
@@ -249,6 +387,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            None,
         )?;
         assert_eq!(
             out.updates,
@@ -276,6 +415,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            None,
         )?;
         assert_eq!(
             out.updates,
@@ -316,6 +456,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            None,
         )?;
         assert_eq!(
             out.updates,
@@ -367,6 +508,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            None,
         )?;
         assert_eq!(
             out.updates,
@@ -415,6 +557,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            None,
         )?;
         assert_eq!(
             out.updates,
@@ -445,6 +588,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            None,
         )?;
         assert_eq!(
             out.updates,
@@ -589,6 +733,7 @@ mod update {
                 fetch::Tags::None,
                 fetch::DryRun::Yes,
                 fetch::WritePackedRefs::Never,
+                None,
             )
             .unwrap();
 
@@ -621,6 +766,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            None,
         )
         .unwrap();
 
@@ -666,6 +812,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            None,
         )
         .unwrap();
 
@@ -693,6 +840,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            None,
         )
         .unwrap();
 
@@ -744,6 +892,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            None,
         )
         .unwrap();
 
@@ -794,6 +943,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            None,
         )
         .unwrap();
 
@@ -829,6 +979,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::No,
             fetch::WritePackedRefs::Never,
+            None,
         )
         .unwrap();
 
@@ -852,6 +1003,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::No,
             fetch::WritePackedRefs::Never,
+            None,
         )
         .unwrap();
 
@@ -873,6 +1025,48 @@ mod update {
         }
     }
 
+    #[test]
+    fn locked_refs_are_reported_as_concurrent_modification() -> Result {
+        let (repo, _tmp) = repo_rw("two-origins");
+        let (mappings, specs) = mapping_from_spec("refs/heads/main:refs/remotes/origin/g", &repo);
+        let lock = gix_lock::Marker::acquire_to_hold_resource(
+            repo.git_dir().join("refs/remotes/origin/g"),
+            gix_lock::acquire::Fail::Immediately,
+            None,
+        )?;
+        let err = fetch::refs::update(
+            &repo,
+            prefixed("action"),
+            &mappings,
+            &specs,
+            &[],
+            fetch::Tags::None,
+            fetch::DryRun::No,
+            fetch::WritePackedRefs::Never,
+            None,
+        )
+        .expect_err("the ref is locked by another process");
+        assert!(
+            err.is_caused_by_concurrent_modification(),
+            "this allows to retry the update once the lock is released: {err:?}"
+        );
+
+        drop(lock);
+        let out = fetch::refs::update(
+            &repo,
+            prefixed("action"),
+            &mappings,
+            &specs,
+            &[],
+            fetch::Tags::None,
+            fetch::DryRun::No,
+            fetch::WritePackedRefs::Never,
+            None,
+        )?;
+        assert_eq!(out.updates[0].mode, fetch::refs::update::Mode::FastForward);
+        Ok(())
+    }
+
     #[test]
     fn fast_forwards_are_called_out_even_if_force_is_given() {
         let (repo, _tmp) = repo_rw("two-origins");
@@ -886,6 +1080,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::No,
             fetch::WritePackedRefs::Never,
+            None,
         )
         .unwrap();
 
@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 
-use crate::remote::fetch;
+use crate::{
+    bstr::{BStr, ByteSlice},
+    remote::fetch,
+};
 
 mod error {
     /// The error returned when updating references.
@@ -23,11 +26,35 @@ mod error {
         PeelToId(#[from] crate::reference::peel::Error),
         #[error("Failed to follow a symbolic reference to assure worktree isn't affected")]
         FollowSymref(#[from] gix_ref::file::find::existing::Error),
+        #[error(transparent)]
+        ReferencesPlatform(#[from] crate::reference::iter::Error),
+        #[error(transparent)]
+        ReferencesInit(#[from] crate::reference::iter::init::Error),
+        #[error("Failed to iterate local references to find those to prune")]
+        IterateReferences(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
     }
 }
 
 pub use error::Error;
 
+impl Error {
+    /// Return `true` if this error occurred because another process held a lock on a reference to update, or changed it
+    /// after we determined how to update it, so that trying again may succeed.
+    pub fn is_caused_by_concurrent_modification(&self) -> bool {
+        use gix_ref::file::transaction::prepare::Error as Prepare;
+        matches!(
+            self,
+            Error::EditReferences(crate::reference::edit::Error::FileTransactionPrepare(
+                Prepare::LockAcquire { .. }
+                    | Prepare::PackedTransactionAcquire(_)
+                    | Prepare::ReferenceOutOfDate { .. }
+                    | Prepare::MustExist { .. }
+                    | Prepare::MustNotExist { .. }
+            ))
+        )
+    }
+}
+
 /// The outcome of the refs-update operation at the end of a fetch.
 #[derive(Debug, Clone)]
 pub struct Outcome {
@@ -37,6 +64,11 @@ pub struct Outcome {
     /// Use [`iter_mapping_updates()`][Self::iter_mapping_updates()] to recombine the update information with ref-edits and their
     /// mapping.
     pub updates: Vec<super::Update>,
+    /// All local references that were deleted as they don't exist on the remote anymore, as they were before the deletion,
+    /// which is empty unless [pruning](crate::Remote::prune()) is enabled.
+    ///
+    /// In dry-run mode, these references would have been deleted.
+    pub pruned: Vec<gix_ref::Reference>,
 }
 
 /// Describe the way a ref was updated, with particular focus on how the (peeled) target commit was affected.
@@ -122,7 +154,293 @@ pub enum TypeChange {
     SymbolicToDirect,
 }
 
+/// The classification of a single reference as shown in the summary table printed by `git fetch`.
+///
+/// Use [`Outcome::iter_summaries()`] to obtain it for all references touched by a fetch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Kind {
+    /// The local reference already pointed to the remote object, or there was no local reference to update at all.
+    UpToDate,
+    /// The local reference was fast-forwarded from `previous` to `new`, shown as `previous..new`.
+    FastForward {
+        /// The object the local reference pointed to before, or `None` if it was a symbolic reference.
+        previous: Option<gix_hash::ObjectId>,
+        /// The object the local reference points to now.
+        new: gix_hash::ObjectId,
+    },
+    /// The local reference was forcibly set to `new` without regard to its ancestry, shown as `previous...new`.
+    Forced {
+        /// The object the local reference pointed to before, or `None` if it was symbolic or unborn.
+        previous: Option<gix_hash::ObjectId>,
+        /// The object the local reference points to now, or `None` if the remote reference is unborn.
+        new: Option<gix_hash::ObjectId>,
+    },
+    /// A tag was forcibly moved from `previous` to `new`.
+    TagUpdate {
+        /// The object the tag pointed to before, or `None` if it was a symbolic reference.
+        previous: Option<gix_hash::ObjectId>,
+        /// The object the tag points to now.
+        new: Option<gix_hash::ObjectId>,
+    },
+    /// A local reference was created for a remote branch.
+    NewBranch,
+    /// A local reference was created for a remote tag.
+    NewTag,
+    /// A local reference was created for a remote reference that is neither a branch nor a tag.
+    NewRef,
+    /// The local reference was deleted as it doesn't exist on the remote anymore.
+    Pruned,
+    /// The update was rejected, with the given `Rejected*` [mode](Mode) explaining why.
+    Rejected(Mode),
+}
+
+impl Kind {
+    /// Return the single character that `git fetch` uses to flag this kind of update.
+    pub fn flag(&self) -> char {
+        match self {
+            Kind::UpToDate => '=',
+            Kind::FastForward { .. } => ' ',
+            Kind::Forced { .. } => '+',
+            Kind::TagUpdate { .. } => 't',
+            Kind::NewBranch | Kind::NewTag | Kind::NewRef => '*',
+            Kind::Pruned => '-',
+            Kind::Rejected(_) => '!',
+        }
+    }
+}
+
+/// Display the summary column like `git fetch` does, using abbreviated object ids for ranges.
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn range(
+            f: &mut std::fmt::Formatter<'_>,
+            previous: Option<&gix_hash::ObjectId>,
+            new: Option<&gix_hash::ObjectId>,
+            separator: &str,
+            fallback: &str,
+        ) -> std::fmt::Result {
+            match (previous, new) {
+                (Some(previous), Some(new)) => write!(
+                    f,
+                    "{}{separator}{}",
+                    previous.to_hex_with_len(7),
+                    new.to_hex_with_len(7)
+                ),
+                _ => f.write_str(fallback),
+            }
+        }
+        match self {
+            Kind::UpToDate => f.write_str("[up to date]"),
+            Kind::FastForward { previous, new } => range(f, previous.as_ref(), Some(new), "..", "[fast-forward]"),
+            Kind::Forced { previous, new } => range(f, previous.as_ref(), new.as_ref(), "...", "[forced update]"),
+            Kind::TagUpdate { .. } => f.write_str("[tag update]"),
+            Kind::NewBranch => f.write_str("[new branch]"),
+            Kind::NewTag => f.write_str("[new tag]"),
+            Kind::NewRef => f.write_str("[new ref]"),
+            Kind::Pruned => f.write_str("[deleted]"),
+            Kind::Rejected(_) => f.write_str("[rejected]"),
+        }
+    }
+}
+
+/// A single row of the summary table printed by `git fetch`, as returned by [`Outcome::iter_summaries()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary<'a> {
+    /// The way the local reference was affected.
+    pub kind: Kind,
+    /// The name of the remote reference, or `None` if the source was an object id or if the local reference was pruned.
+    pub remote: Option<&'a BStr>,
+    /// The name of the local reference, or `None` if there was no local destination, for instance when only writing `FETCH_HEAD`.
+    pub local: Option<&'a BStr>,
+    /// The object the local reference pointed to before the update, or `None` if it was symbolic, unborn or if the update was rejected.
+    pub previous: Option<gix_hash::ObjectId>,
+    /// The object the local reference points to after the update, or `None` if the remote reference is unborn or if it was pruned.
+    pub new: Option<gix_hash::ObjectId>,
+}
+
+/// Determine how [`Outcome::write_summaries()`] formats each line.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// The human-readable table `git fetch` prints by default, like ` * [new branch]      main       -> origin/main`.
+    #[default]
+    Full,
+    /// The machine-readable format of `git fetch --porcelain`, that is `<flag> <old-hex> <new-hex> <local-ref>`,
+    /// with null-ids in place of unknown or non-existing objects.
+    Porcelain,
+}
+
+/// Strip the well-known prefixes from `name` like `git` does when displaying reference names.
+fn prettify_ref_name(name: &BStr) -> &BStr {
+    ["refs/heads/", "refs/tags/", "refs/remotes/"]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix.as_bytes()))
+        .unwrap_or(name)
+        .as_bstr()
+}
+
 impl Outcome {
+    /// Produce an iterator classifying each reference update like `git fetch` would in its summary table, using the `mappings`
+    /// used when producing the ref update, followed by all [pruned](Self::pruned) references.
+    ///
+    /// Updates of tags that the remote didn't send as they were unrelated to the fetched history are skipped, just like `git` does.
+    pub fn iter_summaries<'a>(&'a self, mappings: &'a [fetch::Mapping]) -> impl Iterator<Item = Summary<'a>> + 'a {
+        self.updates
+            .iter()
+            .zip(mappings.iter())
+            .filter_map(move |(update, mapping)| {
+                let remote = mapping.remote.as_name();
+                let local = mapping.local.as_ref().map(|name| name.as_bstr());
+                let new = mapping.remote.as_id().map(ToOwned::to_owned);
+                let previous =
+                    update
+                        .edit_index
+                        .and_then(|idx| self.edits.get(idx))
+                        .and_then(|edit| match &edit.change {
+                            gix_ref::transaction::Change::Update {
+                                expected:
+                                    gix_ref::transaction::PreviousValue::MustExistAndMatch(gix_ref::Target::Object(id)),
+                                ..
+                            } => Some(*id),
+                            _ => None,
+                        });
+                let kind = match &update.mode {
+                    Mode::NoChangeNeeded => Kind::UpToDate,
+                    Mode::ImplicitTagNotSentByRemote => return None,
+                    Mode::FastForward => match new {
+                        Some(new) => Kind::FastForward { previous, new },
+                        None => Kind::Forced { previous, new },
+                    },
+                    Mode::Forced if local.map_or(false, |name| name.starts_with(b"refs/tags/")) => {
+                        Kind::TagUpdate { previous, new }
+                    }
+                    Mode::Forced => Kind::Forced { previous, new },
+                    Mode::New => {
+                        let name = remote.or(local).map(|name| name.as_bytes()).unwrap_or_default();
+                        if name.starts_with(b"refs/tags/") {
+                            Kind::NewTag
+                        } else if name.starts_with(b"refs/heads/") {
+                            Kind::NewBranch
+                        } else {
+                            Kind::NewRef
+                        }
+                    }
+                    rejected @ (Mode::RejectedSourceObjectNotFound { .. }
+                    | Mode::RejectedTagUpdate
+                    | Mode::RejectedNonFastForward
+                    | Mode::RejectedToReplaceWithUnborn
+                    | Mode::RejectedCurrentlyCheckedOut { .. }) => Kind::Rejected(rejected.clone()),
+                };
+                Some(Summary {
+                    kind,
+                    remote,
+                    local,
+                    previous,
+                    new,
+                })
+            })
+            .chain(self.pruned.iter().map(|r| Summary {
+                kind: Kind::Pruned,
+                remote: None,
+                local: Some(r.name.as_bstr()),
+                previous: r.target.try_id().map(ToOwned::to_owned),
+                new: None,
+            }))
+    }
+
+    /// Write the summary of all reference updates to `out` in the given `format`, one line per update, just like `git fetch`
+    /// does after the `From <url>` header, using the `mappings` used when producing the ref update.
+    ///
+    /// Lines for references that were already up-to-date are only written if `include_up_to_date` is `true`,
+    /// similar to `git fetch --verbose`. Mappings without local destination are listed as updating `FETCH_HEAD`.
+    /// `object_hash` is used to produce null-ids in [porcelain](Format::Porcelain) mode.
+    pub fn write_summaries(
+        &self,
+        mappings: &[fetch::Mapping],
+        format: Format,
+        include_up_to_date: bool,
+        object_hash: gix_hash::Kind,
+        out: &mut dyn std::io::Write,
+    ) -> std::io::Result<()> {
+        let summaries: Vec<_> = self
+            .iter_summaries(mappings)
+            .filter(|s| include_up_to_date || s.local.is_none() || s.kind != Kind::UpToDate)
+            .collect();
+        match format {
+            Format::Porcelain => {
+                let null = object_hash.null();
+                for s in summaries {
+                    let flag = if s.local.is_some() { s.kind.flag() } else { '*' };
+                    writeln!(
+                        out,
+                        "{flag} {} {} {}",
+                        s.previous.unwrap_or(null),
+                        s.new.unwrap_or(null),
+                        s.local.unwrap_or("FETCH_HEAD".into())
+                    )?;
+                }
+            }
+            Format::Full => {
+                const SUMMARY_WIDTH: usize = 2 * 7 + 3;
+                let remote_name = |s: &Summary<'_>| match (s.remote, s.new) {
+                    (Some(name), _) => prettify_ref_name(name).to_str_lossy().into_owned(),
+                    (None, Some(id)) if s.kind != Kind::Pruned => id.to_hex_with_len(7).to_string(),
+                    (None, _) => "(none)".into(),
+                };
+                let refcol_width = summaries
+                    .iter()
+                    .filter(|s| s.local.is_some())
+                    .map(|s| remote_name(s).chars().count())
+                    .max()
+                    .unwrap_or_default()
+                    .max(10);
+                for s in &summaries {
+                    let Some(local) = s.local else {
+                        let (kind, name) = match s.remote {
+                            Some(name) => {
+                                let kind = if name.starts_with(b"refs/tags/") {
+                                    "tag"
+                                } else if name.starts_with(b"refs/remotes/") {
+                                    "remote-tracking branch"
+                                } else {
+                                    "branch"
+                                };
+                                (kind, remote_name(s))
+                            }
+                            None => ("branch", "HEAD".into()),
+                        };
+                        writeln!(out, " * {kind:<SUMMARY_WIDTH$} {name:<refcol_width$} -> FETCH_HEAD")?;
+                        continue;
+                    };
+                    write!(
+                        out,
+                        " {} {:<SUMMARY_WIDTH$} {:<refcol_width$} -> {}",
+                        s.kind.flag(),
+                        s.kind.to_string(),
+                        remote_name(s),
+                        prettify_ref_name(local)
+                    )?;
+                    let note = match &s.kind {
+                        Kind::Forced { .. } => Some("forced update"),
+                        Kind::Rejected(mode) => Some(match mode {
+                            Mode::RejectedSourceObjectNotFound { .. } => "object not found",
+                            Mode::RejectedTagUpdate => "would clobber existing tag",
+                            Mode::RejectedNonFastForward => "non-fast-forward",
+                            Mode::RejectedToReplaceWithUnborn => "refusing to overwrite existing with unborn ref",
+                            Mode::RejectedCurrentlyCheckedOut { .. } => "can't fetch into checked-out branch",
+                            _ => "rejected",
+                        }),
+                        _ => None,
+                    };
+                    match note {
+                        Some(note) => writeln!(out, "  ({note})")?,
+                        None => writeln!(out)?,
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Produce an iterator over all information used to produce the this outcome, ref-update by ref-update, using the `mappings`
     /// used when producing the ref update.
     ///
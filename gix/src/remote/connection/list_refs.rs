@@ -0,0 +1,187 @@
+use gix_features::progress::Progress;
+use gix_protocol::{handshake::Ref, transport::client::Transport};
+
+use crate::{
+    bstr::{BString, ByteSlice, ByteVec},
+    remote::{fetch::CapabilityPolicy, ref_map, Connection},
+};
+
+mod error {
+    /// The error returned by [`Remote::list_refs()`][crate::Remote::list_refs()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Connect(#[from] crate::remote::connect::Error),
+        #[error(transparent)]
+        ListRefs(#[from] crate::remote::ref_map::Error),
+    }
+
+    impl gix_protocol::transport::IsSpuriousError for Error {
+        fn is_spurious(&self) -> bool {
+            match self {
+                Error::Connect(err) => err.is_spurious(),
+                Error::ListRefs(err) => err.is_spurious(),
+            }
+        }
+    }
+}
+pub use error::Error;
+
+/// For use in [`Connection::list_refs()`] and [`Remote::list_refs()`](crate::Remote::list_refs()).
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Only list references whose full name starts with any of the given prefixes, like `refs/heads/` or `refs/tags/v1`,
+    /// or all references if empty, which is the default.
+    ///
+    /// With protocol V2, the prefixes are passed to the server which can then omit all other references.
+    pub prefixes: Vec<BString>,
+    /// If `true`, symbolic references like `HEAD` are listed as such along with their target, otherwise they are listed
+    /// like the reference they point to, and unborn references are omitted. Defaults to `true`.
+    pub symrefs: bool,
+    /// If `true`, references to annotated tags are listed along with the object the tag points to, otherwise only
+    /// the tag object is known. Defaults to `true`.
+    pub peel: bool,
+    /// Parameters in the form of `(name, optional value)` to add to the handshake.
+    ///
+    /// This is useful in case of custom servers.
+    pub handshake_parameters: Vec<(String, Option<String>)>,
+    /// Control which of the capabilities advertised by the server may be used when listing refs.
+    pub capability_policy: CapabilityPolicy,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            prefixes: Vec::new(),
+            symrefs: true,
+            peel: true,
+            handshake_parameters: Vec::new(),
+            capability_policy: Default::default(),
+        }
+    }
+}
+
+impl<T> Connection<'_, '_, T>
+where
+    T: Transport,
+{
+    /// List the references on the remote without matching them against the remote's ref-specs, similar to `git ls-remote`,
+    /// and return them in the order in which the remote advertised them.
+    ///
+    /// Unlike [`ref_map()`](Self::ref_map()), only the references are listed, and all other
+    /// information about the remote obtained during the handshake is discarded.
+    ///
+    /// # Consumption
+    ///
+    /// Like [`ref_map()`](Self::ref_map()), this consumes the connection.
+    #[allow(clippy::result_large_err)]
+    #[gix_protocol::maybe_async::maybe_async]
+    pub async fn list_refs(mut self, progress: impl Progress, options: Options) -> Result<Vec<Ref>, ref_map::Error> {
+        let res = self.list_refs_inner(progress, options).await;
+        gix_protocol::indicate_end_of_interaction(&mut self.transport, self.trace)
+            .await
+            .ok();
+        res
+    }
+
+    #[allow(clippy::result_large_err)]
+    #[gix_protocol::maybe_async::maybe_async]
+    async fn list_refs_inner(
+        &mut self,
+        progress: impl Progress,
+        Options {
+            prefixes,
+            symrefs,
+            peel,
+            handshake_parameters,
+            capability_policy,
+        }: Options,
+    ) -> Result<Vec<Ref>, ref_map::Error> {
+        let _span = gix_trace::coarse!("remote::Connection::list_refs()");
+        let remote = self
+            .fetch_refs(handshake_parameters, &capability_policy, progress, |arguments| {
+                arguments.retain(|arg| (symrefs || *arg != "symrefs") && (peel || *arg != "peel"));
+                for prefix in &prefixes {
+                    let mut arg: BString = "ref-prefix ".into();
+                    arg.push_str(prefix);
+                    arguments.push(arg);
+                }
+            })
+            .await?;
+        // Protocol V1 always advertises all refs with all information, so we have to filter them ourselves.
+        Ok(remote
+            .refs
+            .into_iter()
+            .filter(|r| {
+                prefixes.is_empty() || {
+                    let (name, _, _) = r.unpack();
+                    prefixes.iter().any(|prefix| name.starts_with_str(prefix))
+                }
+            })
+            .filter_map(|r| if symrefs { Some(r) } else { without_symref(r) })
+            .map(|r| if peel { r } else { without_peeled(r) })
+            .collect())
+    }
+}
+
+impl crate::Remote<'_> {
+    /// Connect to the remote for fetching and [list its references](Connection::list_refs()) with the given `options`,
+    /// similar to `git ls-remote`.
+    ///
+    /// Use [`connect()`](Self::connect()) and [`Connection::list_refs()`] instead to configure the connection, for example
+    /// to provide credentials.
+    #[cfg(any(feature = "blocking-network-client", feature = "async-network-client-async-std"))]
+    #[allow(clippy::result_large_err)]
+    #[gix_protocol::maybe_async::maybe_async]
+    pub async fn list_refs(&self, progress: impl Progress, options: Options) -> Result<Vec<Ref>, Error> {
+        let connection = self.connect(crate::remote::Direction::Fetch).await?;
+        Ok(connection.list_refs(progress, options).await?)
+    }
+}
+
+/// Turn symbolic refs into the kind of ref they point to, and drop unborn refs.
+fn without_symref(r: Ref) -> Option<Ref> {
+    Some(match r {
+        Ref::Symbolic {
+            full_ref_name,
+            tag: Some(tag),
+            object,
+            ..
+        } => Ref::Peeled {
+            full_ref_name,
+            tag,
+            object,
+        },
+        Ref::Symbolic {
+            full_ref_name,
+            tag: None,
+            object,
+            ..
+        } => Ref::Direct { full_ref_name, object },
+        Ref::Unborn { .. } => return None,
+        r @ (Ref::Peeled { .. } | Ref::Direct { .. }) => r,
+    })
+}
+
+/// Forget the object that annotated tags point to.
+fn without_peeled(r: Ref) -> Ref {
+    match r {
+        Ref::Peeled { full_ref_name, tag, .. } => Ref::Direct {
+            full_ref_name,
+            object: tag,
+        },
+        Ref::Symbolic {
+            full_ref_name,
+            target,
+            tag: Some(tag),
+            ..
+        } => Ref::Symbolic {
+            full_ref_name,
+            target,
+            tag: None,
+            object: tag,
+        },
+        r => r,
+    }
+}
@@ -18,6 +18,7 @@ pub struct Connection<'a, 'repo, T> {
     pub(crate) transport_options: Option<Box<dyn std::any::Any>>,
     pub(crate) transport: T,
     pub(crate) trace: bool,
+    pub(crate) session_id: Option<String>,
 }
 
 mod access;
@@ -27,3 +28,15 @@ pub mod ref_map;
 
 ///
 pub mod fetch;
+
+///
+pub mod push;
+
+///
+pub mod prune;
+
+///
+pub mod list_refs;
+
+///
+pub mod object_info;
@@ -0,0 +1,100 @@
+use gix_protocol::transport::{client::Transport, Protocol};
+
+use crate::remote::Connection;
+
+mod error {
+    /// The error returned by [`Connection::object_info()`](crate::remote::Connection::object_info()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Handshake(#[from] crate::remote::ref_map::Error),
+        #[error("The remote doesn't support the 'object-info' command, which also requires protocol V2")]
+        Unsupported,
+        #[error(transparent)]
+        ObjectInfo(#[from] gix_protocol::object_info::Error),
+    }
+
+    impl gix_protocol::transport::IsSpuriousError for Error {
+        fn is_spurious(&self) -> bool {
+            match self {
+                Error::Handshake(err) => err.is_spurious(),
+                Error::ObjectInfo(err) => err.is_spurious(),
+                Error::Unsupported => false,
+            }
+        }
+    }
+}
+pub use error::Error;
+
+/// Information about an object on the remote, as returned by [`Connection::object_info()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Info {
+    /// The id of the object.
+    pub id: gix_hash::ObjectId,
+    /// The size of the object in bytes, or `None` if the remote doesn't have the object.
+    pub size: Option<u64>,
+    /// The kind of the object, or `None` if the remote doesn't have the object or doesn't support sending its kind.
+    pub kind: Option<gix_object::Kind>,
+}
+
+impl<T> Connection<'_, '_, T>
+where
+    T: Transport,
+{
+    /// Ask the remote for information about the objects with the given `ids` without fetching them, most notably their size,
+    /// and return it in the order the remote sent it, which typically is the order of `ids`.
+    ///
+    /// This uses the `object-info` command, which requires protocol V2 and that the remote advertises it, which `git` does if
+    /// `transfer.advertiseObjectInfo` is set. Otherwise, [`Error::Unsupported`] is returned.
+    ///
+    /// # Consumption
+    ///
+    /// Like [`ref_map()`](Self::ref_map()), this consumes the connection.
+    #[allow(clippy::result_large_err)]
+    #[gix_protocol::maybe_async::maybe_async]
+    pub async fn object_info(
+        mut self,
+        ids: impl IntoIterator<Item = impl Into<gix_hash::ObjectId>>,
+    ) -> Result<Vec<Info>, Error> {
+        let res = self.object_info_inner(ids).await;
+        gix_protocol::indicate_end_of_interaction(&mut self.transport, self.trace)
+            .await
+            .ok();
+        res
+    }
+
+    #[allow(clippy::result_large_err)]
+    #[gix_protocol::maybe_async::maybe_async]
+    async fn object_info_inner(
+        &mut self,
+        ids: impl IntoIterator<Item = impl Into<gix_hash::ObjectId>>,
+    ) -> Result<Vec<Info>, Error> {
+        let _span = gix_trace::coarse!("remote::Connection::object_info()");
+        let outcome = self.handshake(Vec::new(), &mut crate::progress::Discard).await?;
+        if outcome.server_protocol_version != Protocol::V2
+            || outcome
+                .capabilities
+                .capability(gix_protocol::Command::ObjectInfo.as_str())
+                .is_none()
+        {
+            return Err(Error::Unsupported);
+        }
+        let infos = gix_protocol::object_info(
+            &mut self.transport,
+            &outcome.capabilities,
+            ids.into_iter().map(Into::into),
+            self.remote.repo.config.user_agent_tuple(),
+            self.trace,
+        )
+        .await?;
+        Ok(infos
+            .into_iter()
+            .map(|info| Info {
+                id: info.id,
+                size: info.size,
+                kind: info.kind.and_then(|kind| gix_object::Kind::from_bytes(&kind).ok()),
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,69 @@
+use gix_features::progress::Progress;
+use gix_protocol::transport::client::Transport;
+
+use crate::remote::{fetch, ref_map, Connection};
+
+mod error {
+    /// The error returned by [`Connection::prune()`][crate::remote::Connection::prune()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        RefMap(#[from] crate::remote::ref_map::Error),
+        #[error(transparent)]
+        Prune(#[from] crate::remote::fetch::refs::update::Error),
+    }
+
+    impl gix_protocol::transport::IsSpuriousError for Error {
+        fn is_spurious(&self) -> bool {
+            match self {
+                Error::RefMap(err) => err.is_spurious(),
+                _ => false,
+            }
+        }
+    }
+}
+pub use error::Error;
+
+/// For use in [`Connection::prune()`].
+#[derive(Default, Debug, Clone)]
+pub struct Options {
+    /// If `true`, don't delete any reference, but return all references that would have been deleted.
+    pub dry_run: bool,
+    /// The options to use when listing the references on the remote.
+    pub ref_map: ref_map::Options,
+}
+
+impl<T> Connection<'_, '_, T>
+where
+    T: Transport,
+{
+    /// List the references on the remote and delete all local references written by the remote's fetch ref-specs
+    /// whose source doesn't exist on the remote anymore, similar to `git remote prune`, and return them as they were
+    /// before deletion.
+    ///
+    /// Unlike [pruning while fetching](crate::Remote::with_prune()), this doesn't fetch any objects or update other references.
+    /// Tags are only pruned if the remote's ref-specs write them.
+    /// With [`Options::dry_run`] enabled, the returned references would have been deleted, but were left untouched.
+    ///
+    /// # Consumption
+    ///
+    /// Like [`ref_map()`](Self::ref_map()), this consumes the connection.
+    #[allow(clippy::result_large_err)]
+    #[gix_protocol::maybe_async::maybe_async]
+    pub async fn prune(
+        self,
+        progress: impl Progress,
+        Options { dry_run, ref_map }: Options,
+    ) -> Result<Vec<gix_ref::Reference>, Error> {
+        let _span = gix_trace::coarse!("remote::Connection::prune()");
+        let remote = self.remote;
+        let ref_map = self.ref_map(progress, ref_map).await?;
+        Ok(fetch::refs::prune(
+            remote.repo,
+            &remote.fetch_specs,
+            &ref_map.remote_refs,
+            if dry_run { fetch::DryRun::Yes } else { fetch::DryRun::No },
+        )?)
+    }
+}
@@ -0,0 +1,76 @@
+use std::sync::atomic::AtomicBool;
+
+use crate::{
+    bstr::{BStr, ByteSlice},
+    remote::{push, Direction},
+    Remote,
+};
+
+mod error {
+    use crate::bstr::BString;
+
+    /// The error returned by [`Remote::delete_refs()`][crate::Remote::delete_refs()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The reference name {name:?} can't be used to delete a reference on the remote")]
+        InvalidName {
+            name: BString,
+            source: gix_refspec::parse::Error,
+        },
+        #[error(transparent)]
+        Connect(#[from] crate::remote::connect::Error),
+        #[error(transparent)]
+        Prepare(#[from] crate::remote::push::prepare::Error),
+        #[error(transparent)]
+        Send(#[from] crate::remote::push::Error),
+    }
+}
+pub use error::Error;
+
+impl Remote<'_> {
+    /// Delete the references with the given `names` on the remote, similar to `git push <remote> --delete <name>…`,
+    /// and return the outcome with one update for each of them.
+    ///
+    /// `names` may be full reference names or partial ones like `topic`. The push ref-specs of the remote are ignored, so nothing
+    /// but the deletions is sent. References that don't exist on the remote are [rejected](push::Mode::RejectedNoSuchRef),
+    /// and those the remote refused to delete, for instance because they are protected, are
+    /// [rejected by the remote](push::Mode::RejectedByRemote) along with the reason it provided.
+    ///
+    /// Use [`connect()`](Self::connect()) and [`prepare_push()`](crate::remote::Connection::prepare_push()) with ref-specs
+    /// like `:refs/heads/topic` for more control.
+    #[allow(clippy::result_large_err)]
+    #[gix_protocol::maybe_async::maybe_async]
+    pub async fn delete_refs<P>(
+        &self,
+        names: impl IntoIterator<Item = impl AsRef<BStr>>,
+        mut progress: P,
+        should_interrupt: &AtomicBool,
+    ) -> Result<push::Outcome, Error>
+    where
+        P: gix_features::progress::NestedProgress,
+        P::SubProgress: 'static,
+    {
+        let specs = names
+            .into_iter()
+            .map(|name| {
+                let name = name.as_ref();
+                let spec = format!(":{name}");
+                gix_refspec::parse(spec.as_bytes().as_bstr(), gix_refspec::parse::Operation::Push)
+                    .map(|spec| spec.to_owned())
+                    .map_err(|err| Error::InvalidName {
+                        name: name.to_owned(),
+                        source: err,
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut remote = self.clone();
+        remote.push_specs.clear();
+        let prepare = remote
+            .connect(Direction::Push)
+            .await?
+            .prepare_push(progress.add_child("handshake"), specs)
+            .await?;
+        Ok(prepare.send(progress, should_interrupt).await?)
+    }
+}
@@ -0,0 +1,58 @@
+use crate::bstr::BString;
+
+/// The error returned by [`send()`][super::Prepare::send()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Push(#[from] gix_protocol::push::Error),
+    #[error("The remote doesn't support atomic pushes")]
+    AtomicUnsupported,
+    #[error("The remote failed to unpack the objects we sent: {message}")]
+    Unpack { message: BString },
+    #[error("Could not open the object database to create a pack")]
+    OpenObjectDatabase(#[from] std::io::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    OpenCommitGraph(#[from] crate::repository::commit_graph_if_enabled::Error),
+    #[error(transparent)]
+    FindTree(#[from] gix_object::find::existing_iter::Error),
+    #[error(transparent)]
+    DecodeTree(#[from] gix_object::decode::Error),
+    #[error("Could not traverse the commits to send")]
+    TraverseCommits(#[from] gix_revwalk::graph::get_or_insert_default::Error),
+    #[error(transparent)]
+    CountObjects(#[from] gix_pack::data::output::count::objects::Error),
+    #[error(transparent)]
+    WritePack(#[from] gix_pack::data::output::bytes::Error<gix_pack::data::output::entry::iter_from_counts::Error>),
+    #[error("The operation was interrupted")]
+    Interrupted,
+    #[error("A committer identity is needed to sign the push certificate")]
+    MissingPusher,
+    #[error(transparent)]
+    PusherTime(#[from] crate::config::time::Error),
+    #[error("The committer identity can't be used as pusher")]
+    InvalidPusher(#[source] std::io::Error),
+    #[error(transparent)]
+    Hook(#[from] crate::repository::hook_command::Error),
+    #[error("Could not run the pre-push hook at {path:?}")]
+    SpawnPrePushHook {
+        source: std::io::Error,
+        path: std::path::PathBuf,
+    },
+    #[error("The pre-push hook at {path:?} failed with {status}, aborting the push")]
+    PrePushHook {
+        status: std::process::ExitStatus,
+        path: std::path::PathBuf,
+    },
+}
+
+impl gix_protocol::transport::IsSpuriousError for Error {
+    fn is_spurious(&self) -> bool {
+        match self {
+            Error::Push(err) => err.is_spurious(),
+            _ => false,
+        }
+    }
+}
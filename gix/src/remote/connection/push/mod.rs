@@ -0,0 +1,567 @@
+use gix_protocol::transport::client::Transport;
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    remote::{fetch::DryRun, Connection, Direction},
+    Progress,
+};
+
+mod error;
+pub use error::Error;
+
+/// What happens, or happened, to a single reference on the remote as part of a push.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// The remote reference already points to the object we would push, so nothing needs to be done.
+    UpToDate,
+    /// The reference doesn't exist on the remote yet and will be created.
+    Create,
+    /// The reference will be deleted on the remote.
+    Delete,
+    /// The object on the remote is an ancestor of the one we push, so the remote reference will be fast-forwarded.
+    FastForward,
+    /// The object on the remote isn't an ancestor of the one we push, but the ref-spec allowed to force the update.
+    Forced,
+    /// The object on the remote isn't an ancestor of the one we push, and the ref-spec didn't allow to force the update.
+    RejectedNonFastForward,
+    /// The object on the remote isn't present locally, so we can't know if the update would be a fast-forward.
+    ///
+    /// Fetch first to obtain it, or force the update.
+    RejectedFetchFirst,
+    /// The tag already exists on the remote and the ref-spec didn't allow to force replacing it.
+    RejectedAlreadyExists,
+    /// The reference to delete doesn't exist on the remote.
+    RejectedNoSuchRef,
+    /// The remote reference doesn't point to the object we [expected](Update::lease), so it was changed by someone else
+    /// since we last looked and the update is refused to avoid losing their work.
+    RejectedStale,
+    /// The update would have been sent as part of an atomic push, but another update was rejected so none was sent.
+    RejectedAtomicPushFailed,
+    /// The remote received the update, but refused to apply it for the given `reason`, for instance due to a hook.
+    RejectedByRemote {
+        /// The reason as sent by the remote.
+        reason: BString,
+    },
+}
+
+impl Mode {
+    /// Return `true` if this update was refused, either by us or by the remote.
+    pub fn is_rejected(&self) -> bool {
+        matches!(
+            self,
+            Mode::RejectedNonFastForward
+                | Mode::RejectedFetchFirst
+                | Mode::RejectedAlreadyExists
+                | Mode::RejectedNoSuchRef
+                | Mode::RejectedStale
+                | Mode::RejectedAtomicPushFailed
+                | Mode::RejectedByRemote { .. }
+        )
+    }
+}
+
+impl std::fmt::Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Mode::UpToDate => "up-to-date",
+            Mode::Create => "new",
+            Mode::Delete => "deleted",
+            Mode::FastForward => "fast-forward",
+            Mode::Forced => "forced-update",
+            Mode::RejectedNonFastForward => "rejected (non-fast-forward)",
+            Mode::RejectedFetchFirst => "rejected (fetch first)",
+            Mode::RejectedAlreadyExists => "rejected (already exists)",
+            Mode::RejectedNoSuchRef => "rejected (remote ref does not exist)",
+            Mode::RejectedStale => "rejected (stale info)",
+            Mode::RejectedAtomicPushFailed => "rejected (atomic push failed)",
+            Mode::RejectedByRemote { reason } => return write!(f, "rejected by remote ({reason})"),
+        })
+    }
+}
+
+/// A single reference on the remote which is affected by a push, as determined by matching our push ref-specs
+/// against local and remote references.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Update {
+    /// The full name of the local reference that is pushed, or `None` if an object id was pushed directly or if the
+    /// remote reference is deleted.
+    pub local: Option<BString>,
+    /// The full name of the reference on the remote, or the name as given if a reference to delete
+    /// [doesn't exist on the remote](Mode::RejectedNoSuchRef).
+    pub remote: BString,
+    /// The object the remote reference points to, or `None` if it doesn't exist on the remote.
+    pub old: Option<gix_hash::ObjectId>,
+    /// The object the remote reference should point to, or `None` if it should be deleted.
+    pub new: Option<gix_hash::ObjectId>,
+    /// The index into the push ref-specs of the remote, followed by the extra ref-specs, that produced this update.
+    pub spec_index: usize,
+    /// If set, the update is a compare-and-swap, also known as *force-with-lease*, which is only performed if the remote
+    /// reference still points to the contained object, or doesn't exist if it is `None`.
+    ///
+    /// It's set with [`Prepare::with_lease()`].
+    pub lease: Option<Option<gix_hash::ObjectId>>,
+    /// What happens, or happened, to the remote reference.
+    pub mode: Mode,
+}
+
+impl Update {
+    /// Return the command to send to the remote to perform this update, or `None` if it doesn't have to be sent.
+    pub(crate) fn to_command(&self, object_hash: gix_hash::Kind) -> Option<gix_protocol::push::Command> {
+        matches!(
+            self.mode,
+            Mode::Create | Mode::Delete | Mode::FastForward | Mode::Forced
+        )
+        .then(|| gix_protocol::push::Command {
+            old: self.lease.unwrap_or(self.old).unwrap_or_else(|| object_hash.null()),
+            new: self.new.unwrap_or_else(|| object_hash.null()),
+            name: self.remote.clone(),
+        })
+    }
+}
+
+/// The outcome of sending updates to the remote via [`Prepare::send()`].
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    /// The result of the handshake with the remote, including the references it advertised.
+    pub handshake: gix_protocol::handshake::Outcome,
+    /// All references affected by the push along with what happened to them.
+    pub updates: Vec<Update>,
+    /// The status report of the remote, or `None` if nothing was sent as no update was needed or dry-run mode was enabled.
+    pub report: Option<gix_protocol::push::Report>,
+    /// The amount of objects in the pack we sent, or would have sent in dry-run mode, which is `0` if no pack was needed.
+    pub num_objects: usize,
+    /// Information about the objects that would have been sent, or `None` if dry-run mode wasn't enabled or if no update
+    /// was needed.
+    pub dry_run: Option<outcome::DryRun>,
+}
+
+impl Outcome {
+    /// Return the id the remote advertised to identify this operation in its logs, if it supports the `session-id` capability.
+    pub fn server_session_id(&self) -> Option<&crate::bstr::BStr> {
+        self.handshake.session_id()
+    }
+}
+
+/// Additional types related to the outcome of a push operation.
+pub mod outcome {
+    /// What would have been sent to the remote in [dry-run mode](super::Prepare::with_dry_run()).
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct DryRun {
+        /// The objects the pack would contain, which are all objects the remote needs to apply the updates.
+        pub objects: Vec<gix_hash::ObjectId>,
+        /// An estimate of the size of the pack in bytes, based on the compressed size of the objects as they are stored
+        /// in the object database, including the pack header and trailer.
+        ///
+        /// As objects that are stored as deltas in our packs are sent in full, the actual pack may be larger.
+        pub estimated_pack_size: u64,
+    }
+}
+
+///
+pub mod prepare {
+    use crate::bstr::BString;
+
+    /// The error returned by [`prepare_push()`][super::Connection::prepare_push()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Cannot perform a meaningful push operation without any configured ref-specs")]
+        MissingRefSpecs,
+        #[error("Failed to configure the transport before connecting to {url:?}")]
+        GatherTransportConfig {
+            url: BString,
+            source: crate::config::transport::Error,
+        },
+        #[error("Failed to configure the transport layer")]
+        ConfigureTransport(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
+        #[error(transparent)]
+        ConfigureCredentials(#[from] crate::config::credential_helpers::Error),
+        #[error(transparent)]
+        Handshake(#[from] gix_protocol::handshake::Error),
+        #[error("The remote didn't advertise its references, which is required for pushing")]
+        MissingRefAdvertisement,
+        #[error(transparent)]
+        References(#[from] crate::reference::iter::Error),
+        #[error(transparent)]
+        ReferencesInit(#[from] crate::reference::iter::init::Error),
+        #[error("Failed to iterate local references")]
+        IterateReferences(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+        #[error("The destination {destination:?} is not a full reference name and can't be inferred from the source {local:?}")]
+        UnqualifiedDestination { destination: BString, local: BString },
+        #[error("Multiple sources would update the remote reference {name:?} to different objects")]
+        ConflictingDestination { name: BString },
+        #[error(transparent)]
+        PushDefaultConfig(#[from] crate::config::key::GenericErrorWithValue),
+        #[error(transparent)]
+        HeadName(#[from] crate::reference::find::existing::Error),
+        #[error("push.default needs the current branch to know what to push, but HEAD is detached")]
+        DetachedHead,
+        #[error("The current branch {} has no upstream branch to push to", branch.as_bstr())]
+        NoUpstream { branch: gix_ref::FullName },
+        #[error(transparent)]
+        UpstreamName(#[from] crate::repository::branch_remote_ref_name::Error),
+        #[error("The current branch {} is not fetched from remote {remote:?}, so its upstream can't be pushed to", branch.as_bstr())]
+        NotUpstreamRemote {
+            branch: gix_ref::FullName,
+            remote: Option<BString>,
+        },
+        #[error("The upstream {} of the current branch {} has a different name, which push.default=simple doesn't allow", upstream.as_bstr(), branch.as_bstr())]
+        UpstreamNameMismatch {
+            branch: gix_ref::FullName,
+            upstream: gix_ref::FullName,
+        },
+    }
+
+    impl gix_protocol::transport::IsSpuriousError for Error {
+        fn is_spurious(&self) -> bool {
+            match self {
+                Error::Handshake(err) => err.is_spurious(),
+                _ => false,
+            }
+        }
+    }
+}
+
+///
+pub mod lease {
+    /// The value a remote reference is expected to have for an update to be performed, for use in
+    /// [`Prepare::with_lease()`](super::Prepare::with_lease()).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Expected {
+        /// The remote reference must not exist.
+        Absent,
+        /// The remote reference must point to the given object.
+        Id(gix_hash::ObjectId),
+        /// The remote reference must point to the same object as the remote tracking reference it is fetched into,
+        /// as determined by the fetch ref-specs of the remote.
+        ///
+        /// If there is no such remote tracking reference, the update is rejected as [stale](super::Mode::RejectedStale).
+        TrackingRef,
+    }
+
+    /// The error returned by [`Prepare::with_lease()`](super::Prepare::with_lease()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindTrackingRef(#[from] crate::reference::find::Error),
+    }
+}
+
+///
+#[cfg(any(feature = "blocking-network-client", feature = "async-network-client-async-std"))]
+pub mod delete_refs;
+mod send;
+pub(crate) mod update;
+
+impl<'remote, 'repo, T> Connection<'remote, 'repo, T>
+where
+    T: Transport,
+{
+    /// Perform a handshake with the `git-receive-pack` of the remote and determine how each remote reference that is
+    /// affected by our [push ref-specs][crate::Remote::refspecs()] and `extra_refspecs` would change.
+    ///
+    /// Note that at this point, the `transport` should already be configured using the [`transport_mut()`][Self::transport_mut()]
+    /// method, as it will be consumed here.
+    ///
+    /// Use [`Prepare::updates()`] to inspect the planned updates, and [`Prepare::send()`] to perform them.
+    ///
+    /// If there are no ref-specs, the remote is a [mirror](crate::Remote::mirror()) and all local references are force-pushed,
+    /// while remote references that don't exist locally are deleted. Otherwise, `push.default` decides what is pushed.
+    ///
+    /// ### Configuration
+    ///
+    /// - `gitoxide.userAgent` is read to obtain the application user agent for git servers and for HTTP servers as well.
+    #[allow(clippy::result_large_err)]
+    #[gix_protocol::maybe_async::maybe_async]
+    pub async fn prepare_push(
+        mut self,
+        mut progress: impl Progress,
+        extra_refspecs: Vec<gix_refspec::RefSpec>,
+    ) -> Result<Prepare<'remote, 'repo, T>, prepare::Error> {
+        let _span = gix_trace::coarse!("remote::Connection::prepare_push()");
+        let specs = {
+            let mut s = self.remote.push_specs.clone();
+            s.extend(extra_refspecs);
+            s
+        };
+        let mirror = specs.is_empty() && self.remote.mirror;
+        let specs = if mirror {
+            vec![
+                gix_refspec::parse("+refs/*:refs/*".into(), gix_refspec::parse::Operation::Push)
+                    .expect("valid")
+                    .to_owned(),
+            ]
+        } else if specs.is_empty() {
+            vec![self.default_push_spec()?.ok_or(prepare::Error::MissingRefSpecs)?]
+        } else {
+            specs
+        };
+
+        let mut credentials_storage;
+        let url = self.transport.to_url();
+        let authenticate = match self.authenticate.as_mut() {
+            Some(f) => f,
+            None => {
+                let url = self.remote.url(Direction::Push).map_or_else(
+                    || gix_url::parse(url.as_ref()).expect("valid URL to be provided by transport"),
+                    ToOwned::to_owned,
+                );
+                credentials_storage = self.configured_credentials(url)?;
+                &mut credentials_storage
+            }
+        };
+        if self.transport_options.is_none() {
+            self.transport_options = self
+                .remote
+                .repo
+                .transport_options(url.as_ref(), self.remote.name().map(crate::remote::Name::as_bstr))
+                .map_err(|err| prepare::Error::GatherTransportConfig {
+                    source: err,
+                    url: url.into_owned(),
+                })?;
+        }
+        if let Some(config) = self.transport_options.as_ref() {
+            self.transport.configure(&**config)?;
+        }
+        let mut handshake = gix_protocol::handshake(
+            &mut self.transport,
+            gix_protocol::transport::Service::ReceivePack,
+            authenticate,
+            Vec::new(),
+            &mut progress,
+        )
+        .await?;
+        let remote_refs = handshake.refs.take().ok_or(prepare::Error::MissingRefAdvertisement)?;
+        let updates = update::compute(self.remote.repo, &specs, &remote_refs, mirror)?;
+        handshake.refs = Some(remote_refs);
+        Ok(Prepare {
+            con: Some(self),
+            handshake: Some(handshake),
+            updates,
+            dry_run: DryRun::No,
+            atomic: false,
+            options: Vec::new(),
+            sign: None,
+            run_hooks: true,
+        })
+    }
+}
+
+impl<T> Connection<'_, '_, T>
+where
+    T: Transport,
+{
+    /// Derive the ref-spec to push with if neither the remote nor the caller provided one, according to `push.default`,
+    /// or return `None` if nothing should be pushed.
+    #[allow(clippy::result_large_err)]
+    fn default_push_spec(&self) -> Result<Option<gix_refspec::RefSpec>, prepare::Error> {
+        use crate::push::Default;
+        let repo = self.remote.repo;
+        let push_default = repo.config.push_default()?;
+        let spec: BString = match push_default {
+            Default::Nothing => return Ok(None),
+            Default::Matching => ":".into(),
+            Default::Current | Default::Upstream | Default::Simple => {
+                let head = repo.head_name()?.ok_or(prepare::Error::DetachedHead)?;
+                let short_name = head.as_ref().shorten();
+                let is_triangular = self
+                    .remote
+                    .name()
+                    .map(crate::remote::Name::as_bstr)
+                    .zip(repo.branch_remote_name(short_name, Direction::Fetch))
+                    .map_or(true, |(ours, upstream)| ours != upstream.as_bstr());
+                match push_default {
+                    Default::Current => format!("{head}:{head}", head = head.as_bstr()).into(),
+                    Default::Simple if is_triangular => format!("{head}:{head}", head = head.as_bstr()).into(),
+                    Default::Upstream if is_triangular => {
+                        return Err(prepare::Error::NotUpstreamRemote {
+                            branch: head,
+                            remote: self.remote.name().map(|name| name.as_bstr().to_owned()),
+                        })
+                    }
+                    _ => {
+                        let upstream = repo
+                            .branch_remote_ref_name(head.as_ref(), Direction::Fetch)
+                            .ok_or_else(|| prepare::Error::NoUpstream { branch: head.clone() })??;
+                        if push_default == Default::Simple && upstream.as_ref() != head.as_ref() {
+                            return Err(prepare::Error::UpstreamNameMismatch {
+                                branch: head.clone(),
+                                upstream: upstream.into_owned(),
+                            });
+                        }
+                        format!("{}:{}", head.as_bstr(), upstream.as_bstr()).into()
+                    }
+                }
+            }
+        };
+        Ok(Some(
+            gix_refspec::parse(spec.as_ref(), gix_refspec::parse::Operation::Push)
+                .expect("generated ref-specs are valid")
+                .to_owned(),
+        ))
+    }
+}
+
+/// A structure to hold the result of the handshake with the remote along with all planned updates, to configure the
+/// upcoming push operation.
+pub struct Prepare<'remote, 'repo, T>
+where
+    T: Transport,
+{
+    con: Option<Connection<'remote, 'repo, T>>,
+    handshake: Option<gix_protocol::handshake::Outcome>,
+    updates: Vec<Update>,
+    dry_run: DryRun,
+    atomic: bool,
+    options: Vec<BString>,
+    sign: Option<Box<SignFn>>,
+    run_hooks: bool,
+}
+
+/// A function to produce a detached and ASCII-armored signature over the given data.
+pub type SignFn = dyn FnMut(&[u8]) -> Result<BString, Box<dyn std::error::Error + Send + Sync + 'static>>;
+
+impl<T> Prepare<'_, '_, T>
+where
+    T: Transport,
+{
+    /// Return all references on the remote which are affected by the push, along with what would happen to them.
+    ///
+    /// Rejected updates won't be sent, but the remaining ones will be.
+    pub fn updates(&self) -> &[Update] {
+        &self.updates
+    }
+
+    /// Return the result of the handshake with the remote, including all references it advertised.
+    pub fn handshake(&self) -> &gix_protocol::handshake::Outcome {
+        self.handshake.as_ref().expect("present until sent")
+    }
+}
+
+/// Builder
+impl<T> Prepare<'_, '_, T>
+where
+    T: Transport,
+{
+    /// If dry run is enabled, the remote won't be changed, similar to `git push --dry-run`.
+    ///
+    /// This works by not actually sending the updates or the pack to the remote. Instead, the objects the pack would contain
+    /// are determined along with an estimate of its size, which is available in [`Outcome::dry_run`].
+    pub fn with_dry_run(mut self, enabled: bool) -> Self {
+        self.dry_run = if enabled { DryRun::Yes } else { DryRun::No };
+        self
+    }
+
+    /// Only update the remote reference named `remote_ref` if it currently matches `expected`, similar to
+    /// `git push --force-with-lease=<remote_ref>:<expected>`.
+    ///
+    /// `remote_ref` may be a full reference name or a partial one like `main`.
+    /// If the remote reference matches, the update is performed even if it's not a fast-forward, and the expected object is
+    /// sent to the remote for it to verify the reference didn't change in the meantime.
+    /// Otherwise, the update is [rejected as stale](Mode::RejectedStale).
+    ///
+    /// Nothing happens if `remote_ref` isn't affected by the push.
+    pub fn with_lease(mut self, remote_ref: &BStr, expected: lease::Expected) -> Result<Self, lease::Error> {
+        let con = self.con.as_ref().expect("present until sent");
+        let repo = con.remote.repo;
+        let Some(update) = self
+            .updates
+            .iter_mut()
+            .find(|update| update::matches_partial(update.remote.as_ref(), remote_ref))
+        else {
+            return Ok(self);
+        };
+        let expected = match expected {
+            lease::Expected::Absent => None,
+            lease::Expected::Id(id) => Some(id),
+            lease::Expected::TrackingRef => {
+                let tracking_id =
+                    match update::tracking_ref(con.remote.refspecs(Direction::Fetch), update.remote.as_ref()) {
+                        Some(name) => repo
+                            .try_find_reference(name.as_bstr())?
+                            .and_then(|reference| reference.try_id().map(crate::Id::detach)),
+                        None => None,
+                    };
+                let Some(id) = tracking_id else {
+                    update.mode = Mode::RejectedStale;
+                    return Ok(self);
+                };
+                Some(id)
+            }
+        };
+        update::apply_lease(repo, update, expected);
+        Ok(self)
+    }
+
+    /// Send `options` along with the updates, similar to `git push --push-option=<option>`, to be interpreted by the
+    /// hooks of the remote or by the server itself, like `merge_request.create` for GitLab.
+    ///
+    /// Note that [sending](Self::send()) fails if the remote doesn't advertise support for push options, which for
+    /// `git` requires `receive.advertisePushOptions` to be set.
+    pub fn with_options(mut self, options: impl IntoIterator<Item = impl Into<BString>>) -> Self {
+        self.options = options.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Send the updates as part of a push certificate signed by `sign`, similar to `git push --signed`, to allow the remote
+    /// to verify who requested them.
+    ///
+    /// `sign` is called with the certificate, which contains the committer identity of the repository as pusher,
+    /// and must return a detached and ASCII-armored signature over it, as created by `gpg --detach-sign --armor`
+    /// or `ssh-keygen -Y sign` for example.
+    ///
+    /// Note that [sending](Self::send()) fails if the remote doesn't support push certificates, which for `git` requires
+    /// `receive.certNonceSeed` to be set.
+    pub fn with_certificate(
+        mut self,
+        sign: impl FnMut(&[u8]) -> Result<BString, Box<dyn std::error::Error + Send + Sync + 'static>> + 'static,
+    ) -> Self {
+        self.sign = Some(Box::new(sign));
+        self
+    }
+
+    /// If enabled, which is the default, the `pre-push` hook of the local repository is run before sending the updates,
+    /// which aborts the push if it fails. Disabling it is similar to `git push --no-verify`.
+    ///
+    /// Note that the hook is only run if the repository is fully trusted.
+    pub fn with_hooks(mut self, enabled: bool) -> Self {
+        self.run_hooks = enabled;
+        self
+    }
+
+    /// If atomic is enabled, either all updates are applied on the remote or none of them, similar to `git push --atomic`.
+    ///
+    /// This means that nothing is sent if any update is rejected by us, and that [sending](Self::send()) fails
+    /// if the remote doesn't support atomic pushes.
+    pub fn with_atomic(mut self, enabled: bool) -> Self {
+        self.atomic = enabled;
+        self
+    }
+}
+
+impl<T> Drop for Prepare<'_, '_, T>
+where
+    T: Transport,
+{
+    fn drop(&mut self) {
+        if let Some(mut con) = self.con.take() {
+            #[cfg(feature = "async-network-client")]
+            {
+                // TODO: this should be an async drop once the feature is available.
+                //       Right now we block the executor by forcing this communication, but that only
+                //       happens if the user didn't actually try to send the updates, which consumes the
+                //       connection in an async context.
+                gix_protocol::futures_lite::future::block_on(gix_protocol::indicate_end_of_interaction(
+                    &mut con.transport,
+                    con.trace,
+                ))
+                .ok();
+            }
+            #[cfg(not(feature = "async-network-client"))]
+            {
+                gix_protocol::indicate_end_of_interaction(&mut con.transport, con.trace).ok();
+            }
+        }
+    }
+}
@@ -0,0 +1,497 @@
+use std::{
+    io::Write,
+    process::Stdio,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use gix_features::{
+    parallel::InOrderIter,
+    progress::{self, Count},
+};
+use gix_protocol::transport::client::Transport;
+
+use super::{Error, Mode, Outcome, Prepare, Update};
+use crate::{
+    bstr::ByteSlice,
+    remote::{fetch::DryRun, Direction},
+    Progress,
+};
+
+impl<T> Prepare<'_, '_, T>
+where
+    T: Transport,
+{
+    /// Send all [updates](Self::updates()) that aren't rejected or up-to-date to the remote, along with a pack containing
+    /// all objects the remote needs to apply them, and return the updates along with what happened to them.
+    ///
+    /// Updates may still be rejected by the remote, which is reflected in their [mode](Update::mode).
+    ///
+    /// Note that nothing is sent if no update is needed or if dry-run mode is enabled, or if any update was rejected
+    /// by us while [atomic mode](Self::with_atomic()) is enabled.
+    ///
+    /// # Async Experimental
+    ///
+    /// Note that creating the pack is inherently blocking and it's not offloaded to a thread, making this call block the executor.
+    /// It's best to unblock it by placing it into its own thread or offload it should usage in an async context be truly required.
+    #[allow(clippy::result_large_err)]
+    #[gix_protocol::maybe_async::maybe_async]
+    pub async fn send<P>(mut self, mut progress: P, should_interrupt: &AtomicBool) -> Result<Outcome, Error>
+    where
+        P: gix_features::progress::NestedProgress,
+        P::SubProgress: 'static,
+    {
+        let _span = gix_trace::coarse!("remote::push::Prepare::send()");
+        let mut con = self.con.take().expect("send() can only be called once");
+        let handshake = self.handshake.take().expect("present until sent");
+        let mut updates = std::mem::take(&mut self.updates);
+        let repo = con.remote.repo;
+
+        let mut commands: Vec<_> = updates
+            .iter()
+            .filter_map(|update| update.to_command(repo.object_hash()))
+            .collect();
+        if self.atomic && !commands.is_empty() {
+            if !handshake.capabilities.contains("atomic") {
+                gix_protocol::indicate_end_of_interaction(&mut con.transport, con.trace)
+                    .await
+                    .ok();
+                return Err(Error::AtomicUnsupported);
+            }
+            if updates.iter().any(|update| update.mode.is_rejected()) {
+                for update in updates
+                    .iter_mut()
+                    .filter(|update| update.to_command(repo.object_hash()).is_some())
+                {
+                    update.mode = Mode::RejectedAtomicPushFailed;
+                }
+                commands.clear();
+            }
+        }
+        if self.run_hooks && !commands.is_empty() {
+            if let Err(err) = run_pre_push_hook(con.remote, &updates) {
+                gix_protocol::indicate_end_of_interaction(&mut con.transport, con.trace)
+                    .await
+                    .ok();
+                return Err(err);
+            }
+        }
+        if commands.is_empty() {
+            gix_protocol::indicate_end_of_interaction(&mut con.transport, con.trace)
+                .await
+                .ok();
+            return Ok(Outcome {
+                handshake,
+                updates,
+                report: None,
+                num_objects: 0,
+                dry_run: None,
+            });
+        }
+
+        let remote_ids: Vec<_> = handshake
+            .refs
+            .iter()
+            .flatten()
+            .filter_map(|r| r.unpack().1.map(ToOwned::to_owned))
+            .collect();
+        let counts = if commands.iter().all(gix_protocol::push::Command::is_delete) {
+            Vec::new()
+        } else {
+            count_objects(repo, &updates, &remote_ids, &mut progress, should_interrupt)?
+        };
+        let num_objects = counts.len();
+        if self.dry_run == DryRun::Yes {
+            gix_protocol::indicate_end_of_interaction(&mut con.transport, con.trace)
+                .await
+                .ok();
+            let dry_run = dry_run_outcome(repo, &counts);
+            return Ok(Outcome {
+                handshake,
+                updates,
+                report: None,
+                num_objects,
+                dry_run: Some(dry_run),
+            });
+        }
+        let pack = if commands.iter().all(gix_protocol::push::Command::is_delete) {
+            None
+        } else {
+            Some(write_pack(repo, counts, &mut progress, should_interrupt)?)
+        };
+
+        let mut pusher = Vec::new();
+        let pushee = con.remote.url(Direction::Push).map(|url| {
+            let mut url = url.clone();
+            url.set_user(None);
+            url.set_password(None);
+            url.to_bstring()
+        });
+        let certificate = match self.sign.as_mut() {
+            Some(sign) => {
+                repo.committer()
+                    .ok_or(Error::MissingPusher)??
+                    .write_to(&mut pusher)
+                    .map_err(Error::InvalidPusher)?;
+                Some(gix_protocol::push::Certificate {
+                    pusher: pusher.as_bstr(),
+                    pushee: pushee.as_ref().map(AsRef::as_ref),
+                    sign: &mut **sign,
+                })
+            }
+            None => None,
+        };
+
+        progress.set_name("sending".into());
+        let session_id = con.session_id_to_send(&handshake.capabilities).map(ToOwned::to_owned);
+        let report = gix_protocol::push(
+            &mut con.transport,
+            &commands,
+            &handshake.capabilities,
+            repo.config.user_agent_tuple(),
+            session_id.as_deref(),
+            pack,
+            self.atomic,
+            &self.options,
+            certificate,
+            con.trace,
+        )
+        .await?;
+        if let Some(message) = report.unpack_error.clone() {
+            return Err(Error::Unpack { message });
+        }
+        for status in &report.refs {
+            if let Some(reason) = status.rejection.as_ref() {
+                if let Some(update) = updates.iter_mut().find(|update| update.remote == status.name) {
+                    update.mode = Mode::RejectedByRemote { reason: reason.clone() };
+                }
+            }
+        }
+        Ok(Outcome {
+            handshake,
+            updates,
+            report: Some(report),
+            num_objects,
+            dry_run: None,
+        })
+    }
+}
+
+/// Run the `pre-push` hook with the name and URL of `remote` as arguments and all `updates` that are sent on stdin,
+/// like `git push` would, if it exists and the repository is trusted.
+#[allow(clippy::result_large_err)]
+fn run_pre_push_hook(remote: &crate::Remote<'_>, updates: &[Update]) -> Result<(), Error> {
+    let repo = remote.repo;
+    let Some((path, mut cmd)) = repo.hook_command("pre-push")? else {
+        return Ok(());
+    };
+    let spawn_err = |source, path: &std::path::Path| Error::SpawnPrePushHook {
+        source,
+        path: path.to_owned(),
+    };
+
+    let url = remote
+        .url(Direction::Push)
+        .map(gix_url::Url::to_bstring)
+        .unwrap_or_default();
+    let name = remote
+        .name()
+        .map_or_else(|| url.clone(), |name| name.as_bstr().to_owned());
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .args([
+            gix_path::from_bstring(name).into_os_string(),
+            gix_path::from_bstring(url).into_os_string(),
+        ])
+        .spawn()
+        .map_err(|err| spawn_err(err, &path))?;
+
+    let null = repo.object_hash().null();
+    let mut input = Vec::new();
+    for update in updates
+        .iter()
+        .filter(|update| update.to_command(repo.object_hash()).is_some())
+    {
+        let (local, new) = match update.new {
+            Some(new) => (update.local.clone().unwrap_or_else(|| new.to_string().into()), new),
+            None => ("(delete)".into(), null),
+        };
+        input.extend_from_slice(local.as_slice());
+        writeln!(
+            input,
+            " {new} {remote} {old}",
+            remote = update.remote,
+            old = update.old.unwrap_or(null)
+        )
+        .expect("writing to memory works");
+    }
+    let mut stdin = child.stdin.take().expect("configured");
+    // The hook isn't required to read its input.
+    match stdin.write_all(&input) {
+        Err(err) if err.kind() != std::io::ErrorKind::BrokenPipe => {
+            child.kill().ok();
+            child.wait().ok();
+            return Err(spawn_err(err, &path));
+        }
+        _ => {}
+    }
+    drop(stdin);
+
+    let status = child.wait().map_err(|err| spawn_err(err, &path))?;
+    if !status.success() {
+        return Err(Error::PrePushHook { status, path });
+    }
+    Ok(())
+}
+
+/// Find all objects reachable from the new objects of `updates` that aren't reachable from `remote_ids`, which is what
+/// the remote needs to apply the updates.
+#[allow(clippy::result_large_err)]
+fn count_objects<P>(
+    repo: &crate::Repository,
+    updates: &[Update],
+    remote_ids: &[gix_hash::ObjectId],
+    progress: &mut P,
+    should_interrupt: &AtomicBool,
+) -> Result<Vec<gix_pack::data::output::Count>, Error>
+where
+    P: gix_features::progress::NestedProgress,
+    P::SubProgress: 'static,
+{
+    let mut tips = Vec::new();
+    let mut objects = Vec::new();
+    for update in updates
+        .iter()
+        .filter(|update| matches!(update.mode, Mode::Create | Mode::FastForward | Mode::Forced) && update.new.is_some())
+    {
+        let object = repo.find_object(update.new.expect("checked"))?;
+        if object.kind == gix_object::Kind::Tag {
+            objects.push(object.id);
+        }
+        let object = object.peel_tags_to_end()?;
+        if object.kind == gix_object::Kind::Commit {
+            tips.push(object.id);
+        } else {
+            objects.push(object.id);
+        }
+    }
+    let remote_tips: Vec<_> = remote_ids
+        .iter()
+        .filter(|id| repo.has_object(id))
+        .filter_map(|id| repo.find_object(*id).ok()?.peel_to_kind(gix_object::Kind::Commit).ok())
+        .map(|commit| commit.id)
+        .collect();
+    let (commits, boundary) = commits_to_send(repo, &tips, &remote_tips)?;
+    objects.extend(commits);
+
+    let mut db = repo.objects.clone().into_arc()?.into_inner();
+    db.prevent_pack_unload();
+    let mut progress = progress.add_child("counting");
+    progress.init(None, progress::count("objects"));
+    let (mut counts, _outcome) = gix_pack::data::output::count::objects_unthreaded(
+        &db,
+        &mut objects.into_iter().map(Ok),
+        &progress,
+        should_interrupt,
+        // Merge commits would lose objects that only one of their parents introduced if trees were only diffed
+        // against their ancestors, so expand the complete trees of all new commits and remove what the remote has.
+        gix_pack::data::output::count::objects::ObjectExpansion::TreeContents,
+    )?;
+    let remote_objects = objects_in_trees(repo, &boundary, should_interrupt)?;
+    counts.retain(|count| !remote_objects.contains(&count.id));
+    Ok(counts)
+}
+
+/// Return all commits reachable from `tips` that aren't reachable from `remote_tips`, without walking more of
+/// the remote history than needed, along with the commits of the remote that are parents of these.
+///
+/// Both histories are walked together from the newest to the oldest commit while marking which side reaches them,
+/// and the walk stops as soon as only commits that the remote has are left to be visited.
+#[allow(clippy::result_large_err)]
+fn commits_to_send(
+    repo: &crate::Repository,
+    tips: &[gix_hash::ObjectId],
+    remote_tips: &[gix_hash::ObjectId],
+) -> Result<(Vec<gix_hash::ObjectId>, Vec<gix_hash::ObjectId>), Error> {
+    const LOCAL: u8 = 1;
+    const REMOTE: u8 = 1 << 1;
+
+    let cache = repo.commit_graph_if_enabled()?;
+    let mut graph = repo.revision_graph::<gix_revwalk::graph::Commit<u8>>(cache.as_ref());
+    let mut queue = gix_revwalk::PriorityQueue::<gix_date::SecondsSinceUnixEpoch, gix_hash::ObjectId>::new();
+    // All queued commits that the remote isn't known to have, as the walk is done once there are none left.
+    let mut pending = gix_hashtable::HashSet::default();
+    for (id, flags) in tips
+        .iter()
+        .map(|id| (*id, LOCAL))
+        .chain(remote_tips.iter().map(|id| (*id, REMOTE)))
+    {
+        let mut is_remote = false;
+        if let Some(commit) = graph.get_or_insert_commit(id, |data| {
+            *data |= flags;
+            is_remote = *data & REMOTE != 0;
+        })? {
+            queue.insert(commit.commit_time, id);
+            if is_remote {
+                pending.remove(&id);
+            } else {
+                pending.insert(id);
+            }
+        }
+    }
+
+    let mut visited = Vec::new();
+    while !pending.is_empty() {
+        let (_, id) = queue.pop().expect("pending commits are queued");
+        pending.remove(&id);
+        let commit = graph.get(&id).expect("queued commits are in the graph");
+        let flags = commit.data;
+        if flags == LOCAL {
+            visited.push(id);
+        }
+        for parent_id in commit.parents.clone() {
+            let mut is_new = false;
+            let mut is_remote = false;
+            if let Some(parent) = graph.get_or_insert_commit(parent_id, |data| {
+                is_new = *data | flags != *data;
+                *data |= flags;
+                is_remote = *data & REMOTE != 0;
+            })? {
+                if is_new {
+                    queue.insert(parent.commit_time, parent_id);
+                    if is_remote {
+                        pending.remove(&parent_id);
+                    } else {
+                        pending.insert(parent_id);
+                    }
+                }
+            }
+        }
+    }
+
+    let is_local_only = |id: &gix_hash::ObjectId| graph.get(id).map_or(false, |commit| commit.data == LOCAL);
+    let commits: Vec<_> = visited.into_iter().filter(is_local_only).collect();
+    let mut boundary: Vec<_> = commits
+        .iter()
+        .filter_map(|id| graph.get(id))
+        .flat_map(|commit| commit.parents.iter().copied())
+        .filter(|id| graph.get(id).map_or(false, |commit| commit.data & REMOTE != 0))
+        .collect();
+    boundary.sort();
+    boundary.dedup();
+    Ok((commits, boundary))
+}
+
+/// Return the ids of all trees and blobs reachable from the trees of `commits`, which the remote is known to have.
+#[allow(clippy::result_large_err)]
+fn objects_in_trees(
+    repo: &crate::Repository,
+    commits: &[gix_hash::ObjectId],
+    should_interrupt: &AtomicBool,
+) -> Result<gix_hashtable::HashSet, Error> {
+    use gix_object::FindExt;
+
+    let mut seen = gix_hashtable::HashSet::default();
+    let mut trees = Vec::new();
+    let mut buf = Vec::new();
+    for id in commits {
+        let tree_id = repo.objects.find_commit_iter(id, &mut buf)?.tree_id()?;
+        if seen.insert(tree_id) {
+            trees.push(tree_id);
+        }
+    }
+    while let Some(id) = trees.pop() {
+        if should_interrupt.load(Ordering::Relaxed) {
+            return Err(Error::Interrupted);
+        }
+        for entry in repo.objects.find_tree_iter(&id, &mut buf)? {
+            let entry = entry?;
+            if entry.mode.is_commit() {
+                continue;
+            }
+            let id = entry.oid.to_owned();
+            if seen.insert(id) && entry.mode.is_tree() {
+                trees.push(id);
+            }
+        }
+    }
+    Ok(seen)
+}
+
+/// Describe the objects in `counts` that would be sent in a pack, along with an estimate of its size.
+fn dry_run_outcome(repo: &crate::Repository, counts: &[gix_pack::data::output::Count]) -> super::outcome::DryRun {
+    const PACK_HEADER_LEN: u64 = 12;
+    let loose = gix_odb::loose::Store::at(repo.objects.store_ref().path(), repo.object_hash());
+    let mut estimated_pack_size = PACK_HEADER_LEN + repo.object_hash().len_in_bytes() as u64;
+    for count in counts {
+        estimated_pack_size += match &count.entry_pack_location {
+            gix_pack::data::output::count::PackLocation::LookedUp(Some(location)) => location.entry_size as u64,
+            _ => std::fs::metadata(loose.object_path(&count.id)).map_or_else(
+                |_| repo.find_header(count.id).map_or(0, |header| header.size()),
+                |meta| meta.len(),
+            ),
+        };
+    }
+    super::outcome::DryRun {
+        objects: counts.iter().map(|count| count.id).collect(),
+        estimated_pack_size,
+    }
+}
+
+/// Create a pack with the objects in `counts`, which is produced in chunks while it's being sent.
+#[allow(clippy::result_large_err)]
+fn write_pack<'a, P>(
+    repo: &crate::Repository,
+    counts: Vec<gix_pack::data::output::Count>,
+    progress: &mut P,
+    should_interrupt: &'a AtomicBool,
+) -> Result<impl Iterator<Item = Result<Vec<u8>, Error>> + 'a, Error>
+where
+    P: gix_features::progress::NestedProgress,
+    P::SubProgress: 'static,
+{
+    let mut db = repo.objects.clone().into_arc()?.into_inner();
+    db.prevent_pack_unload();
+    let num_objects = counts.len();
+    let entries = InOrderIter::from(gix_pack::data::output::entry::iter_from_counts(
+        counts,
+        db,
+        Box::new(progress.add_child("creating entries")),
+        Default::default(),
+    ));
+    let buf = PackBuffer::default();
+    let mut write_progress = progress.add_child("writing");
+    write_progress.init(None, progress::bytes());
+    let mut chunks = gix_pack::data::output::bytes::FromEntriesIter::new(
+        entries,
+        buf.clone(),
+        num_objects as u32,
+        gix_pack::data::Version::V2,
+        repo.object_hash(),
+    );
+    Ok(std::iter::from_fn(move || {
+        let written = match chunks.next()? {
+            Ok(written) => written,
+            Err(err) => return Some(Err(err.into())),
+        };
+        write_progress.inc_by(written as usize);
+        if should_interrupt.load(Ordering::Relaxed) {
+            return Some(Err(Error::Interrupted));
+        }
+        Some(Ok(std::mem::take(&mut *gix_features::threading::lock(&buf.0))))
+    }))
+}
+
+/// A writer for pack bytes that are taken out again as soon as a chunk of the pack is complete.
+#[derive(Default, Clone)]
+struct PackBuffer(gix_features::threading::OwnShared<gix_features::threading::Mutable<Vec<u8>>>);
+
+impl std::io::Write for PackBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        gix_features::threading::lock(&self.0).extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,325 @@
+use gix_hash::ObjectId;
+use gix_protocol::handshake::Ref;
+use gix_refspec::{
+    instruction::Push,
+    match_group::{Item, SourceRef},
+    Instruction, MatchGroup, RefSpec,
+};
+
+use super::{prepare::Error, Mode, Update};
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    ext::ObjectIdExt,
+};
+
+/// The prefixes to try, in order, when looking up a partial reference name on the remote.
+const PARTIAL_NAME_PREFIXES: &[&str] = &["refs/", "refs/tags/", "refs/heads/", "refs/remotes/"];
+
+/// Match all push `specs` against the local references of `repo` and `remote_refs` to determine what would happen
+/// to each affected remote reference.
+/// If `mirror` is `true`, remote references that don't exist locally are deleted as well, as if by the first of the `specs`.
+#[allow(clippy::result_large_err)]
+pub(crate) fn compute(
+    repo: &crate::Repository,
+    specs: &[RefSpec],
+    remote_refs: &[Ref],
+    mirror: bool,
+) -> Result<Vec<Update>, Error> {
+    let remote: Vec<(&BStr, ObjectId)> = remote_refs
+        .iter()
+        .filter_map(|r| {
+            let (name, target, _peeled) = r.unpack();
+            target.map(|id| (name, id.to_owned()))
+        })
+        .collect();
+    let remote_id = |name: &BStr| remote.iter().find_map(|(n, id)| (*n == name).then_some(*id));
+    let local = local_refs(repo)?;
+
+    let group = MatchGroup::from_push_specs(specs.iter().map(RefSpec::to_ref));
+    let outcome = group.match_remotes(local.iter().map(|(name, id)| Item {
+        full_ref_name: name.as_ref(),
+        target: id,
+        object: None,
+    }));
+
+    let mut out = Vec::new();
+    for mapping in &outcome.mappings {
+        let force = matches!(
+            outcome.group.specs[mapping.spec_index].instruction(),
+            Instruction::Push(Push::Matching {
+                allow_non_fast_forward: true,
+                ..
+            })
+        );
+        let (local_name, new) = match mapping.lhs {
+            SourceRef::FullName(name) => (
+                Some(name.to_owned()),
+                local[mapping.item_index.expect("always set for matched references")].1,
+            ),
+            SourceRef::ObjectId(id) => (None, id),
+        };
+        let destination = match (mapping.rhs.as_ref(), local_name.as_ref()) {
+            (Some(dst), _) => dst.as_ref(),
+            (None, Some(name)) => name.as_bstr(),
+            (None, None) => {
+                return Err(Error::UnqualifiedDestination {
+                    destination: Default::default(),
+                    local: new.to_string().into(),
+                })
+            }
+        };
+        let remote_name = qualify(repo, destination, local_name.as_ref().map(AsRef::as_ref), &remote)?;
+        let old = remote_id(remote_name.as_ref());
+        let mode = classify(repo, remote_name.as_ref(), old, new, force);
+        add(
+            &mut out,
+            Update {
+                local: local_name,
+                remote: remote_name,
+                old,
+                new: Some(new),
+                spec_index: mapping.spec_index,
+                lease: None,
+                mode,
+            },
+        )?;
+    }
+
+    for (spec_index, spec) in outcome.group.specs.iter().enumerate() {
+        match spec.instruction() {
+            Instruction::Push(Push::Delete { ref_or_pattern }) => {
+                let Some((name, old)) = remote.iter().find(|(name, _)| matches_partial(name, ref_or_pattern)) else {
+                    add(
+                        &mut out,
+                        Update {
+                            local: None,
+                            remote: ref_or_pattern.to_owned(),
+                            old: None,
+                            new: None,
+                            spec_index,
+                            lease: None,
+                            mode: Mode::RejectedNoSuchRef,
+                        },
+                    )?;
+                    continue;
+                };
+                add(
+                    &mut out,
+                    Update {
+                        local: None,
+                        remote: (*name).to_owned(),
+                        old: Some(*old),
+                        new: None,
+                        spec_index,
+                        lease: None,
+                        mode: Mode::Delete,
+                    },
+                )?;
+            }
+            Instruction::Push(Push::AllMatchingBranches { allow_non_fast_forward }) => {
+                for (name, new) in local.iter().filter(|(name, id)| {
+                    name.starts_with(b"refs/heads/")
+                        && !outcome.group.is_excluded(Item {
+                            full_ref_name: name.as_ref(),
+                            target: id,
+                            object: None,
+                        })
+                }) {
+                    let Some(old) = remote_id(name.as_ref()) else {
+                        continue;
+                    };
+                    add(
+                        &mut out,
+                        Update {
+                            local: Some(name.clone()),
+                            remote: name.clone(),
+                            old: Some(old),
+                            new: Some(*new),
+                            spec_index,
+                            lease: None,
+                            mode: classify(repo, name.as_ref(), Some(old), *new, allow_non_fast_forward),
+                        },
+                    )?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if mirror {
+        for (name, old) in &remote {
+            if !name.starts_with(b"refs/") || local.iter().any(|(local_name, _)| local_name.as_bstr() == *name) {
+                continue;
+            }
+            add(
+                &mut out,
+                Update {
+                    local: None,
+                    remote: (*name).to_owned(),
+                    old: Some(*old),
+                    new: None,
+                    spec_index: 0,
+                    lease: None,
+                    mode: Mode::Delete,
+                },
+            )?;
+        }
+    }
+    Ok(out)
+}
+
+#[allow(clippy::result_large_err)]
+fn add(out: &mut Vec<Update>, update: Update) -> Result<(), Error> {
+    match out.iter().find(|existing| existing.remote == update.remote) {
+        Some(existing) if existing.new == update.new => Ok(()),
+        Some(_) => Err(Error::ConflictingDestination { name: update.remote }),
+        None => {
+            out.push(update);
+            Ok(())
+        }
+    }
+}
+
+/// Return all references of `repo` along with the object they point to, following symbolic references but not peeling tags.
+/// `HEAD` is included as well, unless it's unborn.
+#[allow(clippy::result_large_err)]
+fn local_refs(repo: &crate::Repository) -> Result<Vec<(BString, ObjectId)>, Error> {
+    let mut out = Vec::new();
+    if let Ok(id) = repo.head_id() {
+        out.push(("HEAD".into(), id.detach()));
+    }
+    let platform = repo.references()?;
+    let references = platform
+        .all()?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(Error::IterateReferences)?;
+    for mut reference in references {
+        let id = match reference.try_id() {
+            Some(id) => id,
+            None => match reference.follow_to_object() {
+                Ok(id) => id,
+                // Symbolic references to unborn branches can't be pushed.
+                Err(_) => continue,
+            },
+        }
+        .detach();
+        out.push((reference.name().as_bstr().to_owned(), id));
+    }
+    Ok(out)
+}
+
+/// Return `true` if `partial` is the same as `full_name` or a shortened version of it.
+pub(super) fn matches_partial(full_name: &BStr, partial: &BStr) -> bool {
+    full_name == partial
+        || PARTIAL_NAME_PREFIXES
+            .iter()
+            .any(|prefix| full_name.strip_prefix(prefix.as_bytes()) == Some(partial.as_bytes()))
+}
+
+/// Turn `destination` into a full reference name on the remote, like `git` does, by looking it up among the `remote`
+/// references or by placing it into the same category as the `local` reference that is pushed.
+#[allow(clippy::result_large_err)]
+fn qualify(
+    repo: &crate::Repository,
+    destination: &BStr,
+    local: Option<&BStr>,
+    remote: &[(&BStr, ObjectId)],
+) -> Result<BString, Error> {
+    if destination.starts_with(b"refs/") {
+        return Ok(destination.to_owned());
+    }
+    if destination == "HEAD" && local == Some("HEAD".into()) {
+        if let Some(name) = repo.head_name().ok().flatten() {
+            return Ok(name.as_bstr().to_owned());
+        }
+    }
+    if let Some((name, _)) = remote.iter().find(|(name, _)| matches_partial(name, destination)) {
+        return Ok((*name).to_owned());
+    }
+    let prefix = match local {
+        Some(name) if name.starts_with(b"refs/tags/") => "refs/tags/",
+        Some(name) if name.starts_with(b"refs/heads/") || name == "HEAD" => "refs/heads/",
+        _ => {
+            return Err(Error::UnqualifiedDestination {
+                destination: destination.to_owned(),
+                local: local.map(ToOwned::to_owned).unwrap_or_default(),
+            })
+        }
+    };
+    let mut name = BString::from(prefix);
+    name.extend_from_slice(destination);
+    Ok(name)
+}
+
+/// Return the full name of the remote tracking reference that the remote reference `name` is fetched into according to
+/// `fetch_specs`, if there is one.
+pub(super) fn tracking_ref(fetch_specs: &[RefSpec], name: &BStr) -> Option<BString> {
+    let null = ObjectId::null(gix_hash::Kind::Sha1);
+    let outcome =
+        MatchGroup::from_fetch_specs(fetch_specs.iter().map(RefSpec::to_ref)).match_remotes(std::iter::once(Item {
+            full_ref_name: name,
+            target: &null,
+            object: None,
+        }));
+    outcome
+        .mappings
+        .into_iter()
+        .find_map(|mapping| mapping.rhs.map(std::borrow::Cow::into_owned))
+}
+
+/// Change `update` to only be performed if the remote reference currently points to `expected`, or doesn't exist
+/// if it's `None`, in which case non-fast-forward updates are allowed as well.
+pub(super) fn apply_lease(repo: &crate::Repository, update: &mut Update, expected: Option<ObjectId>) {
+    update.lease = Some(expected);
+    update.mode = if update.old != expected {
+        Mode::RejectedStale
+    } else {
+        match update.new {
+            Some(new) => classify(repo, update.remote.as_ref(), update.old, new, true),
+            None => Mode::Delete,
+        }
+    };
+}
+
+/// Determine what happens if the remote reference `name` is changed from `old` to `new`, with `force` indicating that
+/// non-fast-forward updates are allowed.
+fn classify(repo: &crate::Repository, name: &BStr, old: Option<ObjectId>, new: ObjectId, force: bool) -> Mode {
+    let Some(old) = old else {
+        return Mode::Create;
+    };
+    if old == new {
+        return Mode::UpToDate;
+    }
+    let is_tag = name.starts_with(b"refs/tags/");
+    let has_old = repo.has_object(old);
+    if !is_tag && has_old && is_ancestor(repo, old, new) {
+        Mode::FastForward
+    } else if force {
+        Mode::Forced
+    } else if is_tag {
+        Mode::RejectedAlreadyExists
+    } else if !has_old {
+        Mode::RejectedFetchFirst
+    } else {
+        Mode::RejectedNonFastForward
+    }
+}
+
+/// Return `true` if the commit `ancestor` is reachable from the commit `id`.
+fn is_ancestor(repo: &crate::Repository, ancestor: ObjectId, id: ObjectId) -> bool {
+    repo.find_object(ancestor)
+        .ok()
+        .and_then(|obj| obj.try_into_commit().ok())
+        .and_then(|commit| commit.committer().ok().map(|c| c.time.seconds))
+        .and_then(|ancestor_commit_time| {
+            id.ancestors(&repo.objects)
+                .sorting(gix_traverse::commit::simple::Sorting::ByCommitTimeCutoff {
+                    order: Default::default(),
+                    seconds: ancestor_commit_time,
+                })
+                .ok()
+        })
+        .map_or(false, |mut ancestors| {
+            ancestors.any(|info| info.map_or(false, |info| info.id == ancestor))
+        })
+}
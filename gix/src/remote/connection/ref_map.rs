@@ -59,6 +59,9 @@ pub struct Options {
     ///
     /// This is useful for handling `remote.<name>.tagOpt` for example.
     pub extra_refspecs: Vec<gix_refspec::RefSpec>,
+    /// Control which of the capabilities advertised by the server may be used when listing refs, and when fetching
+    /// after [`prepare_fetch()`](crate::remote::Connection::prepare_fetch()).
+    pub capability_policy: crate::remote::fetch::CapabilityPolicy,
 }
 
 impl Default for Options {
@@ -67,6 +70,7 @@ impl Default for Options {
             prefix_from_spec_as_filter_on_remote: true,
             handshake_parameters: Vec::new(),
             extra_refspecs: Vec::new(),
+            capability_policy: Default::default(),
         }
     }
 }
@@ -110,12 +114,18 @@ where
             prefix_from_spec_as_filter_on_remote,
             handshake_parameters,
             mut extra_refspecs,
+            capability_policy,
         }: Options,
     ) -> Result<fetch::RefMap, Error> {
         let _span = gix_trace::coarse!("remote::Connection::ref_map()");
         let null = gix_hash::ObjectId::null(gix_hash::Kind::Sha1); // OK to hardcode Sha1, it's not supposed to match, ever.
 
-        if let Some(tag_spec) = self.remote.fetch_tags.to_refspec().map(|spec| spec.to_owned()) {
+        if let Some(tag_spec) = self
+            .remote
+            .effective_fetch_tags()
+            .to_refspec()
+            .map(|spec| spec.to_owned())
+        {
             if !extra_refspecs.contains(&tag_spec) {
                 extra_refspecs.push(tag_spec);
             }
@@ -126,12 +136,22 @@ where
             s
         };
         let remote = self
-            .fetch_refs(
-                prefix_from_spec_as_filter_on_remote,
-                handshake_parameters,
-                &specs,
-                progress,
-            )
+            .fetch_refs(handshake_parameters, &capability_policy, progress, |arguments| {
+                if prefix_from_spec_as_filter_on_remote {
+                    let mut seen = HashSet::new();
+                    for spec in &specs {
+                        let spec = spec.to_ref();
+                        if seen.insert(spec.instruction()) {
+                            let mut prefixes = Vec::with_capacity(1);
+                            spec.expand_prefixes(&mut prefixes);
+                            for mut prefix in prefixes {
+                                prefix.insert_str(0, "ref-prefix ");
+                                arguments.push(prefix);
+                            }
+                        }
+                    }
+                }
+            })
             .await?;
         let num_explicit_specs = self.remote.fetch_specs.len();
         let group = gix_refspec::MatchGroup::from_fetch_specs(specs.iter().map(gix_refspec::RefSpec::to_ref));
@@ -181,14 +201,50 @@ where
 
     #[allow(clippy::result_large_err)]
     #[gix_protocol::maybe_async::maybe_async]
-    async fn fetch_refs(
+    pub(crate) async fn fetch_refs(
         &mut self,
-        filter_by_prefix: bool,
         extra_parameters: Vec<(String, Option<String>)>,
-        refspecs: &[gix_refspec::RefSpec],
+        capability_policy: &crate::remote::fetch::CapabilityPolicy,
         mut progress: impl Progress,
+        prepare_ls_refs_arguments: impl FnOnce(&mut Vec<BString>),
     ) -> Result<HandshakeWithRefs, Error> {
         let _span = gix_trace::coarse!("remote::Connection::fetch_refs()");
+        let mut outcome = self.handshake(extra_parameters, &mut progress).await?;
+        let refs = match outcome.refs.take() {
+            Some(refs) => refs,
+            None => {
+                let agent_feature = self.remote.repo.config.user_agent_tuple();
+                let session_id_feature = self
+                    .session_id_to_send(&outcome.capabilities)
+                    .map(|id| ("session-id", Some(id.to_owned().into())));
+                gix_protocol::ls_refs(
+                    &mut self.transport,
+                    &outcome.capabilities,
+                    move |_capabilities, arguments, features| {
+                        features.push(agent_feature);
+                        features.extend(session_id_feature);
+                        capability_policy.retain_allowed(features);
+                        prepare_ls_refs_arguments(arguments);
+                        Ok(gix_protocol::ls_refs::Action::Continue)
+                    },
+                    &mut progress,
+                    self.trace,
+                )
+                .await?
+            }
+        };
+        Ok(HandshakeWithRefs { outcome, refs })
+    }
+
+    /// Configure the transport, obtain credentials if needed and perform the handshake with the remote,
+    /// passing `extra_parameters` along.
+    #[allow(clippy::result_large_err)]
+    #[gix_protocol::maybe_async::maybe_async]
+    pub(crate) async fn handshake(
+        &mut self,
+        extra_parameters: Vec<(String, Option<String>)>,
+        progress: &mut impl Progress,
+    ) -> Result<gix_protocol::handshake::Outcome, Error> {
         let mut credentials_storage;
         let url = self.transport.to_url();
         let authenticate = match self.authenticate.as_mut() {
@@ -216,40 +272,7 @@ where
         if let Some(config) = self.transport_options.as_ref() {
             self.transport.configure(&**config)?;
         }
-        let mut outcome =
-            gix_protocol::fetch::handshake(&mut self.transport, authenticate, extra_parameters, &mut progress).await?;
-        let refs = match outcome.refs.take() {
-            Some(refs) => refs,
-            None => {
-                let agent_feature = self.remote.repo.config.user_agent_tuple();
-                gix_protocol::ls_refs(
-                    &mut self.transport,
-                    &outcome.capabilities,
-                    move |_capabilities, arguments, features| {
-                        features.push(agent_feature);
-                        if filter_by_prefix {
-                            let mut seen = HashSet::new();
-                            for spec in refspecs {
-                                let spec = spec.to_ref();
-                                if seen.insert(spec.instruction()) {
-                                    let mut prefixes = Vec::with_capacity(1);
-                                    spec.expand_prefixes(&mut prefixes);
-                                    for mut prefix in prefixes {
-                                        prefix.insert_str(0, "ref-prefix ");
-                                        arguments.push(prefix);
-                                    }
-                                }
-                            }
-                        }
-                        Ok(gix_protocol::ls_refs::Action::Continue)
-                    },
-                    &mut progress,
-                    self.trace,
-                )
-                .await?
-            }
-        };
-        Ok(HandshakeWithRefs { outcome, refs })
+        Ok(gix_protocol::fetch::handshake(&mut self.transport, authenticate, extra_parameters, progress).await?)
     }
 }
 
@@ -0,0 +1,142 @@
+use std::path::PathBuf;
+
+use crate::{
+    bstr::BString,
+    config::tree::{gitoxide, Core, Http, Key, Protocol, Remote as RemoteKey, Ssh},
+    remote, Remote,
+};
+
+/// A configuration value that affects how a connection to a remote is established, along with where it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Setting {
+    /// The full name of the configuration key that provided the value, like `remote.origin.proxy` or `http.proxy`.
+    pub key: BString,
+    /// The value as configured.
+    pub value: BString,
+    /// The kind of configuration file or the environment the value was read from.
+    pub source: gix_config::Source,
+    /// The path to the configuration file that contains the value, if it was read from a file.
+    pub path: Option<PathBuf>,
+}
+
+/// The effective parameters used to connect to a remote in a given direction, as obtained by
+/// [`Remote::connection_parameters()`].
+///
+/// These are resolved from configuration without connecting, making it possible to display them or to learn why
+/// a particular url or proxy is used. Only the parameters relevant to the scheme of [`url`](Self::url) are set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parameters {
+    /// The url as configured in `remote.<name>.url` or `remote.<name>.pushUrl`, before applying rewrite rules.
+    pub configured_url: gix_url::Url,
+    /// The url that is connected to, which differs from [`configured_url`](Self::configured_url) if it was rewritten
+    /// by `url.<base>.insteadOf` or `url.<base>.pushInsteadOf`.
+    pub url: gix_url::Url,
+    /// The value of `protocol.version`, if set.
+    pub protocol_version: Option<Setting>,
+    /// The proxy to use for HTTP connections, from `remote.<name>.proxy`, `http.proxy` or the respective environment variables,
+    /// in that order.
+    pub proxy: Option<Setting>,
+    /// The hosts not to use a proxy for, from the `no_proxy` environment variable.
+    pub no_proxy: Option<Setting>,
+    /// How to authenticate with the proxy, from `remote.<name>.proxyAuthMethod` or `http.proxyAuthMethod`.
+    pub proxy_auth_method: Option<Setting>,
+    /// How to authenticate with the server, from `gitoxide.http.authMethod`.
+    pub auth_method: Option<Setting>,
+    /// The HTTP version to use, from `http.version`.
+    pub http_version: Option<Setting>,
+    /// The program to use for `ssh` connections, from `core.sshCommand` or `GIT_SSH_COMMAND`.
+    pub ssh_command: Option<Setting>,
+    /// The kind of `ssh` program, from `ssh.variant` or `GIT_SSH_VARIANT`.
+    pub ssh_variant: Option<Setting>,
+}
+
+/// Diagnostics
+impl Remote<'_> {
+    /// Resolve the parameters used to connect to this remote in `direction` from configuration without connecting,
+    /// or return `None` if there is no url for `direction`.
+    ///
+    /// This is useful to display the effective connection parameters, or to learn why a remote uses an unexpected url or proxy.
+    /// Just like when connecting, only configuration from trusted sources is considered.
+    pub fn connection_parameters(&self, direction: remote::Direction) -> Option<Parameters> {
+        let url = self.url(direction)?.to_owned();
+        let configured_url = match direction {
+            remote::Direction::Fetch => self.url.as_ref(),
+            remote::Direction::Push => self.push_url.as_ref().or(self.url.as_ref()),
+        }
+        .cloned()
+        .unwrap_or_else(|| url.clone());
+
+        let repo = self.repo;
+        let name = self.name().map(remote::Name::as_bstr);
+        let remote_key = |key: &'static str| name.map(|name| format!("remote.{name}.{key}"));
+        let is_http = matches!(url.scheme, gix_url::Scheme::Http | gix_url::Scheme::Https);
+        let is_ssh = url.scheme == gix_url::Scheme::Ssh;
+        Some(Parameters {
+            protocol_version: setting(repo, Protocol::VERSION.logical_name().as_str()),
+            proxy: is_http
+                .then(|| {
+                    remote_key(RemoteKey::PROXY.name)
+                        .and_then(|key| setting(repo, &key))
+                        .or_else(|| setting(repo, Http::PROXY.logical_name().as_str()))
+                        .or_else(|| setting(repo, gitoxide::Http::PROXY.logical_name().as_str()))
+                        .or_else(|| {
+                            (url.scheme == gix_url::Scheme::Https)
+                                .then(|| setting(repo, gitoxide::Https::PROXY.logical_name().as_str()))
+                                .flatten()
+                        })
+                        .or_else(|| setting(repo, gitoxide::Http::ALL_PROXY.logical_name().as_str()))
+                })
+                .flatten(),
+            no_proxy: is_http
+                .then(|| setting(repo, gitoxide::Http::NO_PROXY.logical_name().as_str()))
+                .flatten(),
+            proxy_auth_method: is_http
+                .then(|| {
+                    setting(repo, gitoxide::Http::PROXY_AUTH_METHOD.logical_name().as_str())
+                        .or_else(|| remote_key(RemoteKey::PROXY_AUTH_METHOD.name).and_then(|key| setting(repo, &key)))
+                        .or_else(|| setting(repo, Http::PROXY_AUTH_METHOD.logical_name().as_str()))
+                })
+                .flatten(),
+            auth_method: is_http
+                .then(|| setting(repo, gitoxide::Http::AUTH_METHOD.logical_name().as_str()))
+                .flatten(),
+            http_version: is_http
+                .then(|| setting(repo, Http::VERSION.logical_name().as_str()))
+                .flatten(),
+            ssh_command: is_ssh
+                .then(|| {
+                    setting(repo, Core::SSH_COMMAND.logical_name().as_str()).or_else(|| {
+                        setting(
+                            repo,
+                            gitoxide::Ssh::COMMAND_WITHOUT_SHELL_FALLBACK.logical_name().as_str(),
+                        )
+                    })
+                })
+                .flatten(),
+            ssh_variant: is_ssh
+                .then(|| setting(repo, Ssh::VARIANT.logical_name().as_str()))
+                .flatten(),
+            configured_url,
+            url,
+        })
+    }
+}
+
+/// Return the value of `key` from the last trusted section that contains it, along with information about its origin.
+fn setting(repo: &crate::Repository, key: &str) -> Option<Setting> {
+    let parsed = gix_config::KeyRef::parse_unvalidated(key.into())?;
+    let trusted = repo.filter_config_section();
+    let config = &repo.config.resolved;
+    let (section, value) = config
+        .sections_by_name(parsed.section_name)?
+        .filter(|section| section.header().subsection_name() == parsed.subsection_name && trusted(section.meta()))
+        .filter_map(|section| section.value(parsed.value_name).map(|value| (section, value)))
+        .last()?;
+    let meta = section.meta();
+    Some(Setting {
+        key: key.into(),
+        value: value.into_owned(),
+        source: meta.source,
+        path: meta.path.clone(),
+    })
+}
@@ -22,6 +22,8 @@ pub mod find {
             remote_name: BString,
             source: config::url::Error,
         },
+        #[error("The value for `remote.<name>.prune`, `remote.<name>.pruneTags` or `remote.<name>.mirror` is invalid")]
+        Prune(#[from] config::boolean::Error),
         #[error(transparent)]
         Init(#[from] remote::init::Error),
     }
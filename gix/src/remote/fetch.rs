@@ -7,7 +7,8 @@ pub mod negotiate {
     pub use super::super::connection::fetch::negotiate::Error;
     #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
     pub(crate) use super::super::connection::fetch::negotiate::{
-        add_wants, make_refmapping_ignore_predicate, mark_complete_and_common_ref, one_round, Action,
+        add_resumed_common, add_wants, make_refmapping_ignore_predicate, mark_complete_and_common_ref, one_round,
+        refetch_all, Action,
     };
 }
 
@@ -16,6 +17,76 @@ pub use super::connection::fetch::{
     outcome, prepare, refs, Error, Outcome, Prepare, ProgressId, RefLogMessage, Status,
 };
 
+/// A message sent by the remote on a sideband channel while it prepares and sends a pack.
+///
+/// Besides progress information like `Enumerating objects: 42, done.`, servers may use this channel to send hints or
+/// notices that are meant to be shown to the user, like `git` does with its `remote: ` prefix.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+pub enum SidebandMessage<'a> {
+    /// A message sent on the progress channel, which may contain multiple lines separated by `\r` or `\n`.
+    Progress(&'a crate::bstr::BStr),
+    /// A message sent on the error channel, typically right before the remote aborts the operation.
+    Error(&'a crate::bstr::BStr),
+}
+
+/// A function to receive all [messages](SidebandMessage) sent by the remote on a sideband channel.
+#[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+pub type SidebandHandlerFn<'a> = Box<dyn FnMut(SidebandMessage<'_>) + 'a>;
+
+/// A policy to control which of the capabilities advertised by the server may be used when listing refs and fetching,
+/// for working around quirks of particular servers or for debugging.
+///
+/// By default, all capabilities supported by both sides are used.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+pub struct CapabilityPolicy {
+    /// If set, only the capabilities with these names may be used.
+    pub allow: Option<Vec<String>>,
+    /// The capabilities with these names will never be used, even if they are allowed.
+    pub deny: Vec<String>,
+}
+
+#[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+impl CapabilityPolicy {
+    /// Never use the capability named `name`, like `filter` or `side-band-64k`.
+    pub fn with_denied(mut self, name: impl Into<String>) -> Self {
+        self.deny.push(name.into());
+        self
+    }
+
+    /// Return `true` if the capability named `name` may be used.
+    pub fn is_allowed(&self, name: &str) -> bool {
+        self.allow
+            .as_ref()
+            .map_or(true, |allowed| allowed.iter().any(|allowed| allowed == name))
+            && !self.deny.iter().any(|denied| denied == name)
+    }
+
+    /// Remove all `features` that aren't allowed.
+    pub(crate) fn retain_allowed(&self, features: &mut Vec<(&str, Option<std::borrow::Cow<'static, str>>)>) {
+        features.retain(|(name, _)| self.is_allowed(name));
+    }
+
+    /// Like [`retain_allowed()`](Self::retain_allowed()), but fall back to `side-band` if `side-band-64k` was removed from
+    /// the fetch `features` despite being supported by the server, as advertised in `server_capabilities`.
+    pub(crate) fn retain_allowed_fetch_features(
+        &self,
+        features: &mut Vec<gix_protocol::command::Feature>,
+        server_capabilities: &gix_protocol::transport::client::Capabilities,
+    ) {
+        let had_sideband_64k = features.iter().any(|(name, _)| *name == "side-band-64k");
+        self.retain_allowed(features);
+        if had_sideband_64k
+            && !features.iter().any(|(name, _)| *name == "side-band-64k")
+            && server_capabilities.contains("side-band")
+            && self.is_allowed("side-band")
+        {
+            features.push(("side-band", None));
+        }
+    }
+}
+
 /// If `Yes`, don't really make changes but do as much as possible to get an idea of what would be done.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
@@ -65,6 +136,45 @@ impl Tags {
     }
 }
 
+/// Define how many `HAVE` lines are sent in each round of pack negotiation.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NegotiationWindow {
+    /// Start with 16 `HAVE`s and grow the amount with each round like `git` does, faster if the transport is stateless.
+    #[default]
+    Adaptive,
+    /// Send the given amount of `HAVE`s in each round.
+    Fixed(std::num::NonZeroUsize),
+}
+
+#[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+impl NegotiationWindow {
+    /// Return the amount of `HAVE`s to send in the next round, given the amount sent in the `previous` round, if there was one.
+    pub(crate) fn size(&self, transport_is_stateless: bool, previous: Option<usize>) -> usize {
+        match self {
+            NegotiationWindow::Adaptive => gix_negotiate::window_size(transport_is_stateless, previous),
+            NegotiationWindow::Fixed(size) => size.get(),
+        }
+    }
+}
+
+/// Define what happens to the pack received from the remote once references were updated.
+///
+/// While a pack is being received, a `.keep` file protects it from garbage collection until the references pointing
+/// to its objects are written.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReceivedPack {
+    /// Remove the `.keep` file so the pack is immediately eligible for repacking, like when `git fetch` receives a large pack.
+    Repackable,
+    /// Leave the `.keep` file in place so the pack is never repacked, similar to `git fetch --keep`.
+    Keep,
+    /// Write all objects of the pack as loose objects and remove the pack if it contains fewer than `limit` objects,
+    /// or make it [repackable](Self::Repackable) otherwise, similar to `fetch.unpackLimit`.
+    UnpackBelow {
+        /// The amount of objects below which a pack is unpacked.
+        limit: u64,
+    },
+}
+
 /// Describe how shallow clones are handled when fetching, with variants defining how the *shallow boundary* is handled.
 ///
 /// The *shallow boundary* is a set of commits whose parents are not present in the repository.
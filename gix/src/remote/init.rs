@@ -1,9 +1,9 @@
 use gix_refspec::RefSpec;
 
-use crate::{config, remote, Remote, Repository};
+use crate::{config, config::cache::util::ApplyLeniency, remote, Remote, Repository};
 
 mod error {
-    use crate::bstr::BString;
+    use crate::{bstr::BString, config};
 
     /// The error returned by [`Repository::remote_at(…)`][crate::Repository::remote_at()].
     #[derive(Debug, thiserror::Error)]
@@ -17,6 +17,8 @@ mod error {
             rewritten_url: BString,
             source: gix_url::parse::Error,
         },
+        #[error("Could not read `fetch.prune` or `fetch.pruneTags`")]
+        PruneConfig(#[from] config::boolean::Error),
     }
 }
 pub use error::Error;
@@ -34,6 +36,9 @@ impl<'repo> Remote<'repo> {
         push_specs: Vec<RefSpec>,
         should_rewrite_urls: bool,
         fetch_tags: remote::fetch::Tags,
+        prune: Option<bool>,
+        prune_tags: Option<bool>,
+        mirror: bool,
         repo: &'repo Repository,
     ) -> Result<Self, Error> {
         debug_assert!(
@@ -43,6 +48,13 @@ impl<'repo> Remote<'repo> {
         let (url_alias, push_url_alias) = should_rewrite_urls
             .then(|| rewrite_urls(&repo.config, url.as_ref(), push_url.as_ref()))
             .unwrap_or(Ok((None, None)))?;
+        let (prune, prune_tags) = match (prune, prune_tags) {
+            (Some(prune), Some(prune_tags)) => (prune, prune_tags),
+            _ => {
+                let (default_prune, default_prune_tags) = prune_defaults(repo)?;
+                (prune.unwrap_or(default_prune), prune_tags.unwrap_or(default_prune_tags))
+            }
+        };
         Ok(Remote {
             name: name_or_url.map(Into::into),
             url,
@@ -52,6 +64,9 @@ impl<'repo> Remote<'repo> {
             fetch_specs,
             push_specs,
             fetch_tags,
+            prune,
+            prune_tags,
+            mirror,
             repo,
         })
     }
@@ -80,6 +95,7 @@ impl<'repo> Remote<'repo> {
         let (url_alias, _) = should_rewrite_urls
             .then(|| rewrite_urls(&repo.config, Some(&url), None))
             .unwrap_or(Ok((None, None)))?;
+        let (prune, prune_tags) = prune_defaults(repo)?;
         Ok(Remote {
             name: None,
             url: Some(url),
@@ -89,11 +105,33 @@ impl<'repo> Remote<'repo> {
             fetch_specs: Vec::new(),
             push_specs: Vec::new(),
             fetch_tags: Default::default(),
+            prune,
+            prune_tags,
+            mirror: false,
             repo,
         })
     }
 }
 
+/// Return the values of `fetch.prune` and `fetch.pruneTags`, which are used by all remotes that don't configure them.
+fn prune_defaults(repo: &Repository) -> Result<(bool, bool), Error> {
+    let mut filter = repo.filter_config_section();
+    let mut boolean = |key: &'static config::tree::keys::Boolean| -> Result<bool, Error> {
+        Ok(repo
+            .config
+            .resolved
+            .boolean_filter(format!("fetch.{}", key.name), &mut filter)
+            .map(|value| key.enrich_error(value))
+            .transpose()
+            .with_leniency(repo.config.lenient_config)?
+            .unwrap_or_default())
+    };
+    Ok((
+        boolean(&config::tree::Fetch::PRUNE)?,
+        boolean(&config::tree::Fetch::PRUNE_TAGS)?,
+    ))
+}
+
 pub(crate) fn rewrite_url(
     config: &config::Cache,
     url: Option<&gix_url::Url>,
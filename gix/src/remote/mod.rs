@@ -1,4 +1,4 @@
-use crate::bstr::BStr;
+use crate::bstr::{BStr, BString, ByteSlice};
 use std::borrow::Cow;
 use std::collections::BTreeSet;
 
@@ -49,18 +49,51 @@ pub mod init;
 ///
 pub mod fetch;
 
+///
+#[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+pub mod push;
+
 ///
 #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
 pub mod connect;
 
 #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
-mod connection;
+pub(crate) mod connection;
 #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
-pub use connection::{ref_map, AuthenticateFn, Connection};
+pub use connection::{list_refs, object_info, prune, ref_map, AuthenticateFn, Connection};
 
 ///
 pub mod save;
 
+///
+pub mod connection_parameters;
+
 mod access;
 ///
 pub mod url;
+
+/// Return the name of the remote reference that `spec` would write into `local`, or `None` if `spec` doesn't write `local`.
+pub(crate) fn fetch_source_of(spec: gix_refspec::RefSpecRef<'_>, local: &BStr) -> Option<BString> {
+    let gix_refspec::Instruction::Fetch(gix_refspec::instruction::Fetch::AndUpdate { src, dst, .. }) =
+        spec.instruction()
+    else {
+        return None;
+    };
+    if gix_hash::ObjectId::from_hex(src).is_ok() {
+        return None;
+    }
+    match (src.find_byte(b'*'), dst.find_byte(b'*')) {
+        (Some(src_star), Some(dst_star)) => {
+            let (prefix, suffix) = (&dst[..dst_star], &dst[dst_star + 1..]);
+            if local.len() < prefix.len() + suffix.len() || !local.starts_with(prefix) || !local.ends_with(suffix) {
+                return None;
+            }
+            let mut source = BString::from(&src[..src_star]);
+            source.extend_from_slice(&local[prefix.len()..local.len() - suffix.len()]);
+            source.extend_from_slice(&src[src_star + 1..]);
+            Some(source)
+        }
+        (None, None) => (local == dst).then(|| src.to_owned()),
+        _ => None,
+    }
+}
@@ -0,0 +1,3 @@
+#[cfg(any(feature = "blocking-network-client", feature = "async-network-client-async-std"))]
+pub use super::connection::push::delete_refs;
+pub use super::connection::push::{lease, prepare, Error, Mode, Outcome, Prepare, SignFn, Update};
@@ -0,0 +1,137 @@
+//! Best-effort routines to repair common kinds of repository corruption, to be used as first-aid in automated maintenance.
+#![allow(clippy::empty_docs)]
+use std::path::PathBuf;
+
+/// Options for use in [`Repository::repair()`](crate::Repository::repair()), with each non-destructive routine enabled by default.
+#[derive(Debug, Copy, Clone)]
+pub struct Options {
+    /// If `true`, rewrite the `packed-refs` file if it can't be parsed, keeping all of its valid lines and adding all loose references.
+    pub packed_refs: bool,
+    /// If `true`, write new indices for all packs whose index is missing, corrupt or doesn't belong to the pack.
+    ///
+    /// Packs with truncated or corrupt data are rewritten to contain all objects that could be salvaged.
+    pub pack_indices: bool,
+    /// If `true`, remove loose objects that are truncated, can't be decoded, or whose contents don't match their id,
+    /// by moving them into the `corrupt-objects` directory of the repository.
+    ///
+    /// This is `false` by default as it changes the object database, even though removed objects can be recovered by hand.
+    pub loose_objects: bool,
+    /// If `true`, create the `info/refs` file used by dumb transports if it is missing, like `git update-server-info` would.
+    pub info_refs: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            packed_refs: true,
+            pack_indices: true,
+            loose_objects: false,
+            info_refs: true,
+        }
+    }
+}
+
+/// Information about a rewritten `packed-refs` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackedRefs {
+    /// The amount of references that were kept from the previous `packed-refs` file.
+    pub num_kept: usize,
+    /// The amount of lines of the previous `packed-refs` file that couldn't be parsed and were dropped.
+    pub num_dropped_lines: usize,
+    /// The amount of loose references that were added.
+    pub num_loose: usize,
+}
+
+/// Information about a pack whose index was written anew.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackIndex {
+    /// The path to the pack whose index was missing or corrupt.
+    pub pack_path: PathBuf,
+    /// The path to the newly written index, which is next to a new pack if the pack data was corrupt as well.
+    pub index_path: PathBuf,
+    /// The amount of objects in the new index.
+    pub num_objects: u32,
+}
+
+/// The reason for a loose object to be removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LooseObjectProblem {
+    /// The object couldn't be decompressed or its header couldn't be parsed, typically because it is truncated.
+    Unreadable,
+    /// The object data couldn't be decoded as object of its kind.
+    Undecodable,
+    /// The hash of the object doesn't match its id.
+    HashMismatch {
+        /// The hash of the object's data.
+        actual: gix_hash::ObjectId,
+    },
+}
+
+/// A loose object that was removed from the object database as it was corrupt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemovedLooseObject {
+    /// The id of the object, as derived from its path.
+    pub id: gix_hash::ObjectId,
+    /// The path at which the object was stored.
+    pub path: PathBuf,
+    /// The path to which the object was moved, in case its data is still needed.
+    pub quarantine_path: PathBuf,
+    /// Why the object was removed.
+    pub problem: LooseObjectProblem,
+}
+
+/// The outcome of [`Repository::repair()`](crate::Repository::repair()), describing all changes that were made.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Outcome {
+    /// Set if the `packed-refs` file was rewritten.
+    pub packed_refs: Option<PackedRefs>,
+    /// All packs whose indices were written anew.
+    pub pack_indices: Vec<PackIndex>,
+    /// All loose objects that were removed.
+    pub removed_loose_objects: Vec<RemovedLooseObject>,
+    /// The path to the `info/refs` file if it was created.
+    pub info_refs: Option<PathBuf>,
+}
+
+impl Outcome {
+    /// Return `true` if no change was made.
+    pub fn is_empty(&self) -> bool {
+        self.packed_refs.is_none()
+            && self.pack_indices.is_empty()
+            && self.removed_loose_objects.is_empty()
+            && self.info_refs.is_none()
+    }
+}
+
+/// The error returned by [`Repository::repair()`](crate::Repository::repair()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not access '{}'", path.display())]
+    Io { source: std::io::Error, path: PathBuf },
+    #[error("Failed to lock the packed-refs file for writing")]
+    LockPackedRefs(#[from] gix_lock::acquire::Error),
+    #[error("Failed to commit the rewritten packed-refs file")]
+    CommitPackedRefs(#[from] gix_lock::commit::Error<gix_lock::File>),
+    #[error(transparent)]
+    LockTimeoutConfiguration(#[from] crate::config::lock_timeout::Error),
+    #[error(transparent)]
+    InitRefsIterator(#[from] crate::reference::iter::init::Error),
+    #[error(transparent)]
+    InitRefsIteratorPlatform(#[from] crate::reference::iter::Error),
+    #[error(transparent)]
+    ObtainRefDuringIteration(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error(transparent)]
+    ReadLooseObject(gix_odb::loose::find::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::Error),
+    #[error(transparent)]
+    PeelObject(#[from] crate::object::find::existing::Error),
+    #[error("Could not write a new index for pack at '{}'", path.display())]
+    WritePackIndex {
+        source: gix_pack::bundle::write::Error,
+        path: PathBuf,
+    },
+    #[error("Interrupted")]
+    Interrupted,
+}
@@ -12,6 +12,22 @@ pub enum Error {
 }
 
 impl Repository {
+    /// Share parsed `.gitattributes` and `.gitignore` files among all attribute and exclude stacks created by this instance
+    /// and its clones through `cache`, including the ones used by status, checkout, diffs and dirwalks, so that each of these files
+    /// is parsed only once. Set it to `None` to stop sharing.
+    ///
+    /// As `cache` is thread-safe, clones of it can also be set on repositories used in other threads.
+    /// Note that it's not retained when converting this instance [into a `ThreadSafeRepository`](Self::into_sync()).
+    pub fn set_worktree_cache(&mut self, cache: Option<crate::WorktreeCache>) -> &mut Self {
+        self.worktree_cache = cache;
+        self
+    }
+
+    /// Return the cache for parsed attribute and ignore files if one was set with [`set_worktree_cache()`](Self::set_worktree_cache()).
+    pub fn worktree_cache(&self) -> Option<&crate::WorktreeCache> {
+        self.worktree_cache.as_ref()
+    }
+
     /// Configure a file-system cache for accessing git attributes *and* excludes on a per-path basis.
     ///
     /// Use `attribute_source` to specify where to read attributes from. Also note that exclude information will
@@ -47,17 +63,16 @@ impl Repository {
                 .assemble_exclude_globals(self.git_dir(), exclude_overrides, ignore_source, &mut buf)?;
         let state = gix_worktree::stack::State::AttributesAndIgnoreStack { attributes, ignore };
         let attribute_list = state.id_mappings_from_index(index, index.path_backing(), case);
-        Ok(AttributeStack::new(
-            gix_worktree::Stack::new(
-                // this is alright as we don't cause mutation of that directory, it's virtual.
-                self.work_dir().unwrap_or(self.git_dir()),
-                state,
-                case,
-                buf,
-                attribute_list,
-            ),
-            self,
-        ))
+        let mut stack = gix_worktree::Stack::new(
+            // this is alright as we don't cause mutation of that directory, it's virtual.
+            self.work_dir().unwrap_or(self.git_dir()),
+            state,
+            case,
+            buf,
+            attribute_list,
+        );
+        stack.set_shared_cache(self.worktree_cache.clone());
+        Ok(AttributeStack::new(stack, self))
     }
 
     /// Like [attributes()][Self::attributes()], but without access to exclude/ignore information.
@@ -79,17 +94,16 @@ impl Repository {
         )?;
         let state = gix_worktree::stack::State::AttributesStack(attributes);
         let attribute_list = state.id_mappings_from_index(index, index.path_backing(), case);
-        Ok(AttributeStack::new(
-            gix_worktree::Stack::new(
-                // this is alright as we don't cause mutation of that directory, it's virtual.
-                self.work_dir().unwrap_or(self.git_dir()),
-                state,
-                case,
-                buf,
-                attribute_list,
-            ),
-            self,
-        ))
+        let mut stack = gix_worktree::Stack::new(
+            // this is alright as we don't cause mutation of that directory, it's virtual.
+            self.work_dir().unwrap_or(self.git_dir()),
+            state,
+            case,
+            buf,
+            attribute_list,
+        );
+        stack.set_shared_cache(self.worktree_cache.clone());
+        Ok(AttributeStack::new(stack, self))
     }
 
     /// Configure a file-system cache checking if files below the repository are excluded, reading `.gitignore` files from
@@ -124,16 +138,15 @@ impl Repository {
             .assemble_exclude_globals(self.git_dir(), overrides, source, &mut buf)?;
         let state = gix_worktree::stack::State::IgnoreStack(ignore);
         let attribute_list = state.id_mappings_from_index(index, index.path_backing(), case);
-        Ok(AttributeStack::new(
-            gix_worktree::Stack::new(
-                // this is alright as we don't cause mutation of that directory, it's virtual.
-                self.work_dir().unwrap_or(self.git_dir()),
-                state,
-                case,
-                buf,
-                attribute_list,
-            ),
-            self,
-        ))
+        let mut stack = gix_worktree::Stack::new(
+            // this is alright as we don't cause mutation of that directory, it's virtual.
+            self.work_dir().unwrap_or(self.git_dir()),
+            state,
+            case,
+            buf,
+            attribute_list,
+        );
+        stack.set_shared_cache(self.worktree_cache.clone());
+        Ok(AttributeStack::new(stack, self))
     }
 }
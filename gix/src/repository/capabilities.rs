@@ -0,0 +1,165 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use crate::{
+    bstr::BStr,
+    capabilities::{Error, Feature, Item, Outcome, Support},
+    config::tree::{keys, Core, Extensions},
+    Repository,
+};
+
+impl Repository {
+    /// Report which git features this repository uses and how well each of them is supported, so applications can check for
+    /// compatibility before starting an operation instead of failing in the middle of it.
+    ///
+    /// Extensions are only read from the repository-local configuration, as `git` would.
+    /// Note that the index is loaded if present to learn about its version and sparseness.
+    pub fn capabilities(&self) -> Result<Outcome, Error> {
+        let mut items = Vec::new();
+        let mut push = |feature: Feature, support: Support| items.push(Item { feature, support });
+
+        let version = self
+            .config
+            .apply_leniency(
+                self.config
+                    .resolved
+                    .integer(&Core::REPOSITORY_FORMAT_VERSION)
+                    .map(|v| Core::REPOSITORY_FORMAT_VERSION.try_into_u64(v)),
+            )?
+            .unwrap_or_default();
+        push(
+            Feature::RepositoryFormatVersion(version),
+            if version <= 1 { Support::Full } else { Support::None },
+        );
+
+        let mut has_ref_storage = false;
+        for (name, value) in self.extensions() {
+            let is_true = |key: &'static keys::Boolean| -> Result<bool, Error> {
+                let value = value
+                    .clone()
+                    .map_or(Ok(true), |v| gix_config::Boolean::try_from(v).map(|b| b.0));
+                Ok(self
+                    .config
+                    .apply_leniency(Some(key.enrich_error(value)))?
+                    .unwrap_or_default())
+            };
+            let value = || value.clone().map(Cow::into_owned).unwrap_or_default();
+            match name.as_str() {
+                "objectformat" => {
+                    let format = value();
+                    let support = if format.eq_ignore_ascii_case(b"sha1") {
+                        Support::Full
+                    } else {
+                        Support::None
+                    };
+                    push(Feature::ObjectFormat(format), support);
+                }
+                "worktreeconfig" => {
+                    if is_true(&Extensions::WORKTREE_CONFIG)? {
+                        push(Feature::WorktreeConfig, Support::Full);
+                    }
+                }
+                "refstorage" => {
+                    let storage = value();
+                    let support = if storage == "files" {
+                        Support::Full
+                    } else {
+                        Support::None
+                    };
+                    has_ref_storage = true;
+                    push(Feature::RefStorage(storage), support);
+                }
+                "partialclone" => push(
+                    Feature::PartialClone { remote: value() },
+                    Support::Partial {
+                        note: "Missing objects are not fetched from the promisor remote on demand",
+                    },
+                ),
+                "preciousobjects" => {
+                    if is_true(&Extensions::PRECIOUS_OBJECTS)? {
+                        push(
+                            Feature::PreciousObjects,
+                            Support::Partial {
+                                note: "Fetching with refetch enabled may still remove packs that were superseded",
+                            },
+                        );
+                    }
+                }
+                "noop" => {}
+                _ => push(Feature::UnknownExtension { name: name.into() }, Support::None),
+            }
+        }
+        if !has_ref_storage && self.common_dir().join("reftable").is_dir() {
+            push(Feature::RefStorage("reftable".into()), Support::None);
+        }
+
+        let sparse_checkout = self
+            .config
+            .apply_leniency(
+                self.config
+                    .resolved
+                    .boolean(&Core::SPARSE_CHECKOUT)
+                    .map(|v| Core::SPARSE_CHECKOUT.enrich_error(v)),
+            )?
+            .unwrap_or_default();
+        if sparse_checkout && !self.is_bare() {
+            let cone = self
+                .config
+                .apply_leniency(
+                    self.config
+                        .resolved
+                        .boolean(&Core::SPARSE_CHECKOUT_CONE)
+                        .map(|v| Core::SPARSE_CHECKOUT_CONE.enrich_error(v)),
+                )?
+                .unwrap_or_default();
+            push(
+                Feature::SparseCheckout { cone },
+                Support::Partial {
+                    note: "Sparse checkout patterns are not respected when checking out or obtaining the status",
+                },
+            );
+        }
+
+        #[cfg(feature = "index")]
+        if let Some(index) = self.try_index()? {
+            let version = index.version();
+            push(
+                Feature::IndexVersion(version),
+                match version {
+                    gix_index::Version::V2 | gix_index::Version::V3 => Support::Full,
+                    gix_index::Version::V4 => Support::Partial {
+                        note: "The index can be read, but is written as version 2 or 3",
+                    },
+                },
+            );
+            if index.is_sparse() {
+                push(
+                    Feature::SparseIndex,
+                    Support::Partial {
+                        note: "Directory entries are expanded on demand, e.g. for committing or looking up paths, but not when checking out",
+                    },
+                );
+            }
+        }
+
+        Ok(Outcome { items })
+    }
+
+    /// Return all values in the `extensions` section of the repository-local configuration by their lower-cased name,
+    /// with `None` as value for implicit booleans.
+    fn extensions(&self) -> BTreeMap<String, Option<Cow<'_, BStr>>> {
+        let mut out = BTreeMap::new();
+        let Some(sections) = self.config.resolved.sections_by_name_and_filter("extensions", |meta| {
+            matches!(meta.source, gix_config::Source::Local | gix_config::Source::Worktree)
+        }) else {
+            return out;
+        };
+        for section in sections {
+            for name in section.body().value_names() {
+                if let Some(value) = section.body().value_implicit(name.as_ref()) {
+                    out.insert(name.as_ref().to_ascii_lowercase(), value);
+                }
+            }
+        }
+        out
+    }
+}
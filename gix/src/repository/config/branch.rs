@@ -2,11 +2,10 @@ use std::{borrow::Cow, collections::BTreeSet};
 
 use gix_ref::{FullName, FullNameRef};
 
-use crate::bstr::BStr;
-use crate::config::cache::util::ApplyLeniencyDefault;
-use crate::config::tree::{Branch, Push};
-use crate::repository::{branch_remote_ref_name, branch_remote_tracking_ref_name};
-use crate::{push, remote};
+use crate::bstr::{BStr, BString};
+use crate::config::tree::Branch;
+use crate::repository::{branch_remote_ref_name, branch_remote_tracking_ref_name, new_branch_tracking};
+use crate::{branch, push, remote};
 
 /// Query configuration related to branches.
 impl crate::Repository {
@@ -55,19 +54,10 @@ impl crate::Repository {
                     Err(err) => return Some(Err(err.into())),
                 };
                 if remote.push_specs.is_empty() {
-                    let push_default =
-                        match self
-                            .config
-                            .resolved
-                            .string(Push::DEFAULT)
-                            .map_or(Ok(Default::default()), |v| {
-                                Push::DEFAULT
-                                    .try_into_default(v)
-                                    .with_lenient_default(self.config.lenient_config)
-                            }) {
-                            Ok(v) => v,
-                            Err(err) => return Some(Err(err.into())),
-                        };
+                    let push_default = match self.config.push_default() {
+                        Ok(v) => v,
+                        Err(err) => return Some(Err(err.into())),
+                    };
                     match push_default {
                         push::Default::Nothing => None,
                         push::Default::Current | push::Default::Matching => Some(Ok(Cow::Owned(name.to_owned()))),
@@ -183,6 +173,86 @@ impl crate::Repository {
     }
 }
 
+/// Setting up branches
+impl crate::Repository {
+    /// Return the tracking configuration that `git branch` would set up for the branch `new_branch` if it was created at
+    /// the reference `start_point`, according to `branch.autoSetupMerge` and `branch.autoSetupRebase`,
+    /// or `None` if no tracking should be set up.
+    ///
+    /// * if `start_point` is a remote tracking branch, the remote whose fetch ref-specs write it is tracked,
+    ///   and its branch on the remote is merged.
+    /// * if `start_point` is a local branch, it is tracked with `.` as remote if `branch.autoSetupMerge` is `always`,
+    ///   or its own tracking configuration is copied if it is `inherit`.
+    ///
+    /// Use [`Tracking::write_to()`](branch::Tracking::write_to()) to persist the returned configuration once the branch was created.
+    pub fn new_branch_tracking(
+        &self,
+        new_branch: &FullNameRef,
+        start_point: &FullNameRef,
+    ) -> Result<Option<branch::Tracking>, new_branch_tracking::Error> {
+        let mode = self.config.auto_setup_merge()?;
+        let tracking = match start_point.category() {
+            _ if mode == branch::AutoSetupMerge::Never => None,
+            Some(gix_ref::Category::LocalBranch) => match mode {
+                branch::AutoSetupMerge::Always => Some((BString::from("."), start_point.to_owned())),
+                branch::AutoSetupMerge::Inherit => {
+                    let short_name = start_point.shorten();
+                    let remote = self
+                        .config
+                        .resolved
+                        .string_by("branch", Some(short_name), Branch::REMOTE.name);
+                    match (
+                        remote,
+                        self.branch_remote_ref_name(start_point, remote::Direction::Fetch),
+                    ) {
+                        (Some(remote), Some(merge)) => Some((remote.into_owned(), merge?.into_owned())),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            },
+            Some(gix_ref::Category::RemoteBranch) if mode != branch::AutoSetupMerge::Inherit => {
+                let mut tracked = None;
+                for remote_name in self.remote_names() {
+                    if let Some(merge) = self
+                        .find_remote(remote_name.as_ref())?
+                        .refspecs(remote::Direction::Fetch)
+                        .iter()
+                        .find_map(|spec| remote::fetch_source_of(spec.to_ref(), start_point.as_bstr()))
+                    {
+                        if tracked.is_some() {
+                            return Err(new_branch_tracking::Error::AmbiguousRemote {
+                                start_point: start_point.to_owned(),
+                            });
+                        }
+                        let merge = if merge.starts_with(b"refs/") {
+                            merge
+                        } else {
+                            format!("refs/heads/{merge}").into()
+                        };
+                        tracked = Some((remote_name.into_owned(), gix_ref::FullName::try_from(merge)?));
+                    }
+                }
+                tracked.filter(|(_remote, merge)| {
+                    mode != branch::AutoSetupMerge::Simple || merge.as_ref().shorten() == new_branch.shorten()
+                })
+            }
+            _ => None,
+        };
+
+        let Some((remote, merge)) = tracking else {
+            return Ok(None);
+        };
+        let rebase = match self.config.auto_setup_rebase()? {
+            branch::AutoSetupRebase::Never => false,
+            branch::AutoSetupRebase::Local => remote == ".",
+            branch::AutoSetupRebase::Remote => remote != ".",
+            branch::AutoSetupRebase::Always => true,
+        };
+        Ok(Some(branch::Tracking { remote, merge, rebase }))
+    }
+}
+
 fn matching_remote<'a>(
     lhs: &FullNameRef,
     specs: impl IntoIterator<Item = &'a gix_refspec::RefSpec>,
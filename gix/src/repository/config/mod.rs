@@ -42,6 +42,11 @@ impl crate::Repository {
         &self.options
     }
 
+    /// The time spent in each phase of opening the repository, useful to learn what makes opening it slow.
+    pub fn open_metrics(&self) -> crate::open::Metrics {
+        self.open_metrics
+    }
+
     /// Obtain options for use when connecting via `ssh`.
     #[cfg(feature = "blocking-network-client")]
     pub fn ssh_connect_options(
@@ -70,6 +75,16 @@ impl crate::Repository {
                 .and_then(|variant| Ssh::VARIANT.try_into_variant(variant).transpose())
                 .transpose()
                 .with_leniency(self.options.lenient_config)?,
+            config_file: config
+                .boolean_filter(gitoxide::Ssh::READ_CONFIG_FILE, &mut trusted)
+                .map(|value| gitoxide::Ssh::READ_CONFIG_FILE.enrich_error(value))
+                .transpose()
+                .with_leniency(self.options.lenient_config)?
+                .unwrap_or(true)
+                .then(|| self.config.home_dir())
+                .flatten()
+                .map(|home| home.join(".ssh").join("config")),
+            ..Default::default()
         };
         Ok(opts)
     }
@@ -128,12 +143,20 @@ impl crate::Repository {
     pub fn diff_algorithm(&self) -> Result<gix_diff::blob::Algorithm, config::diff::algorithm::Error> {
         self.config.diff_algorithm()
     }
+
+    /// Return the format in which dates of commits and reflog entries should be displayed, as configured by `log.date`.
+    ///
+    /// Use [`DateFormat::format()`](crate::log::DateFormat::format()) to display a time accordingly.
+    pub fn date_format(&self) -> Result<crate::log::DateFormat, config::key::GenericErrorWithValue> {
+        self.config.date_format()
+    }
 }
 
 mod branch;
 mod remote;
 #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
 mod transport;
+mod worktree;
 
 impl crate::Repository {
     pub(crate) fn filter_config_section(&self) -> fn(&gix_config::file::Metadata) -> bool {
@@ -266,6 +266,16 @@ impl crate::Repository {
                                     })
                             })
                     })?;
+                    opts.auth_method = {
+                        let key = "gitoxide.http.authMethod";
+                        debug_assert_eq!(key, gitoxide::Http::AUTH_METHOD.logical_name());
+                        config
+                            .string_filter(key, &mut trusted_only)
+                            .map(|v| {
+                                proxy_auth_method(Some((v, Cow::Borrowed(key.into()), &gitoxide::Http::AUTH_METHOD)))
+                            })
+                            .transpose()?
+                    };
                     opts.proxy_authenticate = opts
                         .proxy
                         .as_deref()
@@ -296,6 +306,30 @@ impl crate::Repository {
                             .transpose()
                             .with_leniency(lenient)?
                     };
+                    opts.retries = {
+                        let key = "gitoxide.http.retries";
+                        debug_assert_eq!(key, gitoxide::Http::RETRIES.logical_name());
+                        config
+                            .integer_filter(key, &mut trusted_only)
+                            .map(|value| gitoxide::Http::RETRIES.try_into_u32(value))
+                            .transpose()
+                            .with_leniency(lenient)
+                            .map_err(config::transport::http::Error::from)?
+                            .unwrap_or_default()
+                    };
+                    opts.retry_delay = {
+                        let key = "gitoxide.http.retryDelay";
+                        config
+                            .integer_filter(key, &mut trusted_only)
+                            .map(|v| {
+                                debug_assert_eq!(key, gitoxide::Http::RETRY_DELAY.logical_name());
+                                gitoxide::Http::RETRY_DELAY
+                                    .try_into_duration(v)
+                                    .map_err(crate::config::transport::http::Error::from)
+                            })
+                            .transpose()
+                            .with_leniency(lenient)?
+                    };
                     {
                         let key = "http.userAgent";
                         opts.user_agent = config
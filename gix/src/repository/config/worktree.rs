@@ -0,0 +1,87 @@
+use std::io::Write;
+
+use crate::{
+    bstr::{BStr, BString},
+    config,
+    config::tree::{Extensions, Key},
+};
+
+/// Per-worktree configuration
+impl crate::Repository {
+    /// Set the value at `key` to `new_value` in the configuration file of the current worktree and write it to disk, similar to
+    /// `git config --worktree`, and apply the change to the in-memory configuration as well.
+    /// The previous value is returned, if there was one.
+    ///
+    /// If `extensions.worktreeConfig` is enabled, the value is written to the `config.worktree` file in the [`git_dir()`](Self::git_dir()),
+    /// which allows worktrees to have their own settings, like the ones for sparse checkouts.
+    /// Otherwise, it's written to the shared repository configuration, which is only possible if there are no linked worktrees.
+    ///
+    /// Note that the configuration file is locked while writing according to `gitoxide.core.lockTimeout`.
+    pub fn set_worktree_config_value<'b>(
+        &mut self,
+        key: &'static dyn Key,
+        new_value: impl Into<&'b BStr>,
+    ) -> Result<Option<BString>, config::set_worktree_value::Error> {
+        use config::set_worktree_value::Error;
+
+        if let Some(config::tree::SubSectionRequirement::Parameter(_)) = key.subsection_requirement() {
+            return Err(config::set_value::Error::SubSectionRequired.into());
+        }
+        let value = new_value.into();
+        key.validate(value).map_err(config::set_value::Error::from)?;
+
+        let has_extension = self
+            .config
+            .apply_leniency(
+                self.config
+                    .resolved
+                    .boolean(&Extensions::WORKTREE_CONFIG)
+                    .map(|v| Extensions::WORKTREE_CONFIG.enrich_error(v)),
+            )?
+            .unwrap_or_default();
+        let (source, path) = if has_extension {
+            (gix_config::Source::Worktree, self.git_dir().join("config.worktree"))
+        } else {
+            let is_linked_worktree = self.git_dir() != self.common_dir();
+            if is_linked_worktree || !self.worktrees().map_err(Error::ListWorktrees)?.is_empty() {
+                return Err(Error::ExtensionRequired);
+            }
+            (gix_config::Source::Local, self.common_dir().join("config"))
+        };
+
+        let mut file = match gix_config::File::from_path_no_includes(path.clone(), source) {
+            Ok(file) => file,
+            Err(gix_config::file::init::from_paths::Error::Io { source: err, .. })
+                if err.kind() == std::io::ErrorKind::NotFound =>
+            {
+                gix_config::File::new(gix_config::file::Metadata::from(source).at(&path))
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let section = key.section();
+        match section.parent() {
+            Some(parent) => file.set_raw_value_by(parent.name(), Some(section.name().into()), key.name(), value),
+            None => file.set_raw_value_by(section.name(), None, key.name(), value),
+        }
+        .map_err(config::set_value::Error::from)?;
+
+        let lock_mode = self.config.other_lock_timeout()?;
+        let mut lock =
+            gix_lock::File::acquire_to_update_resource(&path, lock_mode, None).map_err(|err| Error::Lock {
+                source: err,
+                path: path.clone(),
+            })?;
+        file.write_to(&mut lock)
+            .and_then(|_| lock.flush())
+            .map_err(|err| Error::Write {
+                source: err,
+                path: path.clone(),
+            })?;
+        lock.commit()?;
+
+        let mut snapshot = self.config_snapshot_mut();
+        let previous = snapshot.set_value(key, value)?;
+        snapshot.commit()?;
+        Ok(previous)
+    }
+}
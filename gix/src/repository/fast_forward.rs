@@ -0,0 +1,187 @@
+use std::path::Path;
+
+use gix_hash::ObjectId;
+use gix_index::entry::{Flags, Mode};
+use gix_ref::transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog};
+
+use crate::{fast_forward, Repository};
+
+impl Repository {
+    /// Move `branch` forward to the commit `to`, similar to `git merge --ff-only`, which is only possible if `to` contains the
+    /// commit the branch currently points to.
+    ///
+    /// If the branch already contains `to`, nothing is changed. Otherwise, the branch is updated with a log entry as configured
+    /// in `options`, which fails if it was changed concurrently. If the branch is checked out in the worktree of this repository,
+    /// `HEAD` is updated as well, along with the index and the worktree if [enabled](fast_forward::Options::update_worktree).
+    ///
+    /// Updating the worktree fails if the index or tracked files have changes. Untracked files are left alone, and it's an error
+    /// if one of them would be overwritten.
+    /// Note that `to` may be anything that can be peeled to a commit, like an annotated tag.
+    pub fn fast_forward(
+        &self,
+        branch: &gix_ref::FullNameRef,
+        to: impl Into<ObjectId>,
+        options: fast_forward::Options,
+    ) -> Result<fast_forward::Outcome, fast_forward::Error> {
+        let _span = gix_trace::coarse!("gix::fast_forward()", branch = ?branch);
+        let current = self.find_reference(branch)?.peel_to_id_in_place()?.detach();
+        let to = self.find_object(to.into())?.peel_to_kind(gix_object::Kind::Commit)?.id;
+        let unchanged = fast_forward::Outcome {
+            previous: current,
+            new: current,
+            updated_worktree: false,
+        };
+        if current == to {
+            return Ok(unchanged);
+        }
+        let merge_base = match self.merge_base(current, to) {
+            Ok(id) => Some(id.detach()),
+            Err(crate::repository::merge_base::Error::NotFound { .. }) => None,
+            Err(err) => return Err(err.into()),
+        };
+        if merge_base == Some(to) {
+            return Ok(unchanged);
+        }
+        if merge_base != Some(current) {
+            return Err(fast_forward::Error::NotFastForward {
+                branch: branch.to_owned(),
+                current,
+                to,
+            });
+        }
+
+        let is_checked_out = self
+            .head_name()?
+            .map_or(false, |head| head.as_bstr() == branch.as_bstr());
+        let workdir = self.work_dir().filter(|_| is_checked_out && options.update_worktree);
+        if let Some(workdir) = workdir {
+            self.fast_forward_worktree(workdir, current, to)?;
+        }
+        let committer = match &options.reflog_committer {
+            Some(committer) => Some(committer.to_ref()),
+            None => self
+                .committer()
+                .transpose()
+                .map_err(crate::reference::edit::Error::from)?,
+        };
+        self.edit_references_as(
+            Some(RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: false,
+                        message: options.reflog_message,
+                    },
+                    expected: PreviousValue::MustExistAndMatch(gix_ref::Target::Object(current)),
+                    new: gix_ref::Target::Object(to),
+                },
+                name: if is_checked_out {
+                    "HEAD".try_into().expect("valid")
+                } else {
+                    branch.to_owned()
+                },
+                deref: is_checked_out,
+            }),
+            committer,
+        )?;
+        Ok(fast_forward::Outcome {
+            previous: current,
+            new: to,
+            updated_worktree: workdir.is_some(),
+        })
+    }
+
+    /// Change the index and the files in `workdir` from matching the commit `current` to matching the commit `to`.
+    fn fast_forward_worktree(
+        &self,
+        workdir: &Path,
+        current: ObjectId,
+        to: ObjectId,
+    ) -> Result<(), fast_forward::Error> {
+        use fast_forward::Error;
+
+        let should_interrupt = &crate::interrupt::IS_INTERRUPTED;
+        let mut index = self.index_or_empty()?;
+        if index.is_sparse() {
+            let mut full_index = gix_index::File::clone(&index);
+            full_index.expand_sparse_directories(&self.objects)?;
+            index = crate::worktree::Index::new(gix_fs::FileSnapshot::new(full_index));
+        }
+        let current_index = self.index_from_tree(&self.find_object(current)?.peel_to_tree()?.id)?;
+        let index_matches_current = index.entries().len() == current_index.entries().len()
+            && index.entries().iter().zip(current_index.entries()).all(|(a, b)| {
+                a.id == b.id
+                    && a.mode == b.mode
+                    && a.stage_raw() == b.stage_raw()
+                    && a.path(&index) == b.path(&current_index)
+            });
+        if !index_matches_current || self.is_dirty()? {
+            return Err(Error::Dirty);
+        }
+
+        let mut new_index = self.index_from_tree(&self.find_object(to)?.peel_to_tree()?.id)?;
+        let mut temporarily_skipped = Vec::new();
+        for (idx, (entry, rela_path)) in new_index.entries_mut_with_paths().enumerate() {
+            match index.entry_by_path(rela_path) {
+                Some(previous) if previous.id == entry.id && previous.mode == entry.mode => {
+                    entry.stat = previous.stat;
+                    if !previous.flags.contains(Flags::SKIP_WORKTREE) {
+                        temporarily_skipped.push(idx);
+                    }
+                    entry.flags.insert(Flags::SKIP_WORKTREE);
+                }
+                Some(_) => {}
+                None => {
+                    let path = workdir.join(gix_path::from_bstr(rela_path));
+                    if std::fs::symlink_metadata(&path).map_or(false, |metadata| !metadata.is_dir()) {
+                        return Err(Error::WouldOverwriteUntracked { path });
+                    }
+                }
+            }
+        }
+
+        for entry in index.entries() {
+            let rela_path = entry.path(&index);
+            if entry.mode == Mode::COMMIT
+                || entry.flags.contains(Flags::SKIP_WORKTREE)
+                || new_index.entry_by_path(rela_path).is_some()
+            {
+                continue;
+            }
+            let path = workdir.join(gix_path::from_bstr(rela_path));
+            match std::fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(source) => return Err(Error::Io { source, path }),
+            }
+            for dir in path.ancestors().skip(1).take_while(|dir| *dir != workdir) {
+                if std::fs::remove_dir(dir).is_err() {
+                    break;
+                }
+            }
+        }
+
+        let mut opts = self
+            .config
+            .checkout_options(self, gix_worktree::stack::state::attributes::Source::IdMapping)?;
+        opts.overwrite_existing = true;
+        opts.destination_is_initially_empty = false;
+        gix_worktree_state::checkout(
+            &mut new_index,
+            workdir,
+            self.objects.clone().into_arc()?,
+            &crate::progress::Discard,
+            &crate::progress::Discard,
+            should_interrupt,
+            opts,
+        )?;
+        for idx in temporarily_skipped {
+            new_index.entries_mut()[idx].flags.remove(Flags::SKIP_WORKTREE);
+        }
+        new_index.write(gix_index::write::Options {
+            lock_mode: self.config.other_lock_timeout()?,
+            ..Default::default()
+        })?;
+        Ok(())
+    }
+}
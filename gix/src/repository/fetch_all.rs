@@ -0,0 +1,183 @@
+use std::sync::atomic::AtomicBool;
+
+use crate::{
+    bstr::{BStr, BString},
+    config::{
+        cache::util::ApplyLeniency,
+        tree::{Fetch, Remote},
+    },
+    remote, Repository,
+};
+
+/// Options for [Repository::fetch_all()].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Options {
+    /// The amount of remotes to fetch from at the same time, with `0` meaning one for each logical CPU core.
+    ///
+    /// If `None`, `fetch.parallel` is used, which defaults to fetching from one remote after another.
+    /// Note that without the `parallel` feature, remotes are always fetched from one after another.
+    pub jobs: Option<usize>,
+}
+
+/// The outcome of fetching from a single remote as part of [Repository::fetch_all()].
+#[derive(Debug)]
+pub struct Outcome {
+    /// The name of the remote that was fetched from.
+    pub remote_name: BString,
+    /// The result of the fetch, which failed if the remote couldn't be reached or the fetch couldn't be completed.
+    pub result: Result<remote::fetch::Outcome, RemoteError>,
+}
+
+/// The error returned by [Repository::fetch_all()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    ParallelConfig(#[from] crate::config::unsigned_integer::Error),
+    #[error(transparent)]
+    SkipFetchAllConfig(#[from] crate::config::boolean::Error),
+    #[error("Could not spawn a thread to fetch remotes concurrently")]
+    SpawnThread(#[from] std::io::Error),
+}
+
+/// The error of fetching from a single remote, as part of the [outcome](Outcome) of [Repository::fetch_all()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum RemoteError {
+    #[error(transparent)]
+    FindRemote(#[from] remote::find::existing::Error),
+    #[error(transparent)]
+    Connect(#[from] remote::connect::Error),
+    #[error(transparent)]
+    PrepareFetch(#[from] remote::fetch::prepare::Error),
+    #[error(transparent)]
+    Fetch(#[from] remote::fetch::Error),
+}
+
+impl Repository {
+    /// Fetch from all configured remotes like `git fetch --all`, except for those with `remote.<name>.skipFetchAll` set,
+    /// using the fetch ref-specs of each remote.
+    ///
+    /// `options.jobs` remotes are fetched from at the same time, each with its own child of `progress`, while
+    /// `should_interrupt` stops all of them.
+    /// A remote that can't be fetched from doesn't abort the operation, which is why the [outcome](Outcome) of each
+    /// remote is returned in the order of their names, with its individual error, if any.
+    pub fn fetch_all(
+        &self,
+        progress: &mut dyn crate::DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        options: Options,
+    ) -> Result<Vec<Outcome>, Error> {
+        let mut names = Vec::new();
+        for name in self.remote_names() {
+            let skip = self
+                .config
+                .resolved
+                .boolean_filter(
+                    format!("remote.{name}.{key}", key = Remote::SKIP_FETCH_ALL.name),
+                    &mut self.filter_config_section(),
+                )
+                .map(|value| Remote::SKIP_FETCH_ALL.enrich_error(value))
+                .transpose()
+                .with_leniency(self.config.lenient_config)?
+                .unwrap_or(false);
+            if !skip {
+                names.push(name.into_owned());
+            }
+        }
+        let jobs = match options.jobs {
+            Some(jobs) => jobs,
+            None => self
+                .config
+                .resolved
+                .integer(Fetch::PARALLEL)
+                .map(|jobs| Fetch::PARALLEL.try_into_usize(jobs))
+                .transpose()
+                .with_leniency(self.config.lenient_config)?
+                .unwrap_or(1),
+        };
+        #[cfg_attr(not(feature = "parallel"), allow(unused_variables))]
+        let jobs = match jobs {
+            0 => std::thread::available_parallelism().map_or(1, Into::into),
+            jobs => jobs,
+        }
+        .min(names.len());
+
+        let work: Vec<_> = names
+            .into_iter()
+            .map(|name| {
+                let progress = progress.add_child(name.to_string());
+                (name, progress)
+            })
+            .collect();
+        #[cfg(feature = "parallel")]
+        if jobs > 1 {
+            return fetch_in_parallel(self, work, jobs, should_interrupt);
+        }
+        Ok(work
+            .into_iter()
+            .map(|(name, mut progress)| Outcome {
+                result: fetch(self, name.as_ref(), &mut progress, should_interrupt),
+                remote_name: name,
+            })
+            .collect())
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn fetch_in_parallel(
+    repo: &Repository,
+    work: Vec<(BString, crate::progress::BoxedDynNestedProgress)>,
+    jobs: usize,
+    should_interrupt: &AtomicBool,
+) -> Result<Vec<Outcome>, Error> {
+    use std::sync::Mutex;
+
+    let num_remotes = work.len();
+    let work = Mutex::new(work.into_iter().enumerate());
+    let out = Mutex::new(Vec::with_capacity(num_remotes));
+    let repo = repo.clone().into_sync();
+    std::thread::scope(|scope| -> Result<(), Error> {
+        for thread_id in 0..jobs {
+            std::thread::Builder::new()
+                .name(format!("gix::fetch_all::{thread_id}"))
+                .spawn_scoped(scope, {
+                    let (repo, work, out) = (&repo, &work, &out);
+                    move || {
+                        let repo = repo.to_thread_local();
+                        loop {
+                            let Some((idx, (name, mut progress))) = work.lock().expect("not poisoned").next() else {
+                                break;
+                            };
+                            let result = fetch(&repo, name.as_ref(), &mut progress, should_interrupt);
+                            out.lock().expect("not poisoned").push((
+                                idx,
+                                Outcome {
+                                    remote_name: name,
+                                    result,
+                                },
+                            ));
+                        }
+                    }
+                })?;
+        }
+        Ok(())
+    })?;
+
+    let mut out = out.into_inner().expect("not poisoned");
+    out.sort_by_key(|(idx, _)| *idx);
+    Ok(out.into_iter().map(|(_, outcome)| outcome).collect())
+}
+
+fn fetch(
+    repo: &Repository,
+    name: &BStr,
+    progress: &mut dyn crate::DynNestedProgress,
+    should_interrupt: &AtomicBool,
+) -> Result<remote::fetch::Outcome, RemoteError> {
+    Ok(repo
+        .find_remote(name)?
+        .connect(remote::Direction::Fetch)?
+        .prepare_fetch(&mut *progress, Default::default())?
+        .receive_inner(progress, should_interrupt)?)
+}
@@ -21,6 +21,27 @@ pub mod pipeline {
     }
 }
 
+///
+pub mod write_blob_from_worktree_file {
+    use std::path::PathBuf;
+
+    /// The error returned by [Repository::write_blob_from_worktree_file()](crate::Repository::write_blob_from_worktree_file()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("A worktree is required to read files from")]
+        MissingWorkDir,
+        #[error("Could not read '{}' from the worktree", path.display())]
+        Io { source: std::io::Error, path: PathBuf },
+        #[error(transparent)]
+        FilterPipeline(#[from] super::pipeline::Error),
+        #[error(transparent)]
+        ConvertToGit(#[from] crate::filter::pipeline::convert_to_git::Error),
+        #[error(transparent)]
+        WriteBlob(#[from] crate::object::write::Error),
+    }
+}
+
 impl Repository {
     /// Configure a pipeline for converting byte buffers to the worktree representation, and byte streams to the git-internal
     /// representation. Also return the index that was used when initializing the pipeline as it may be useful when calling
@@ -61,4 +82,44 @@ impl Repository {
         };
         Ok((filter::Pipeline::new(self, cache.detach())?, index))
     }
+
+    /// Write the file at `rela_path`, relative to the root of the worktree, as blob into the object database and return its id,
+    /// which is the same id that `git add` would produce for it.
+    ///
+    /// Files are passed through all clean filters and end-of-line conversions configured by `.gitattributes` and the
+    /// git configuration at `rela_path`, just like `git hash-object --path` would. Symlinks aren't followed, instead
+    /// their target path is written as blob.
+    ///
+    /// ### Performance
+    ///
+    /// A new [filter pipeline](Self::filter_pipeline()) is created for each call, which requires loading the index and attributes.
+    /// When writing many files, create the pipeline once and use [`convert_to_git()`](filter::Pipeline::convert_to_git()) instead.
+    pub fn write_blob_from_worktree_file(
+        &self,
+        rela_path: impl AsRef<std::path::Path>,
+    ) -> Result<Id<'_>, write_blob_from_worktree_file::Error> {
+        use std::io::Read;
+
+        use write_blob_from_worktree_file::Error;
+
+        let rela_path = rela_path.as_ref();
+        let path = self.work_dir().ok_or(Error::MissingWorkDir)?.join(rela_path);
+        let io_err = |source| Error::Io {
+            source,
+            path: path.clone(),
+        };
+        if std::fs::symlink_metadata(&path).map_err(io_err)?.is_symlink() {
+            let target = std::fs::read_link(&path).map_err(io_err)?;
+            return Ok(self.write_blob(&*gix_path::to_unix_separators_on_windows(gix_path::into_bstr(target)))?);
+        }
+
+        let (mut pipeline, index) = self.filter_pipeline(None)?;
+        let file = std::fs::File::open(&path).map_err(io_err)?;
+        let mut buf = Vec::new();
+        pipeline
+            .convert_to_git(file, rela_path, &index)?
+            .read_to_end(&mut buf)
+            .map_err(io_err)?;
+        Ok(self.write_blob(&buf)?)
+    }
 }
@@ -42,4 +42,368 @@ impl crate::Repository {
                 _ => Err(err),
             })?)
     }
+
+    /// Add a new layer to the commit-graph chain in `objects/info/commit-graphs` which contains all commits reachable
+    /// from `tips` that aren't yet part of the commit-graph, similar to `git commit-graph write --split`.
+    ///
+    /// Annotated tags are peeled, and `tips` that don't point to a commit are ignored.
+    /// Like `git`, the topmost layers of the chain are merged into the new layer as long as they contain no more than
+    /// twice as many commits, and a monolithic `objects/info/commit-graph` file becomes the base of the chain.
+    ///
+    /// Return `None` if there was no new commit to write, or if the repository is shallow as commit-graphs aren't compatible
+    /// with it.
+    /// Note that `core.commitGraph` isn't consulted, use [`commit_graph_if_enabled()`](crate::Repository::commit_graph_if_enabled())
+    /// to learn if commit-graphs would be used at all.
+    pub fn write_commit_graph(
+        &self,
+        tips: impl IntoIterator<Item = impl Into<gix_hash::ObjectId>>,
+    ) -> Result<Option<super::write_commit_graph::Outcome>, super::write_commit_graph::Error> {
+        use std::io::Write;
+
+        use gix_commitgraph::{file::write, File, Graph};
+
+        use super::write_commit_graph::{Error, Outcome};
+
+        if self.is_shallow() {
+            return Ok(None);
+        }
+        let info_dir = self.objects.store_ref().path().join("info");
+        let graphs_dir = info_dir.join("commit-graphs");
+        let monolithic_path = info_dir.join("commit-graph");
+        let chain_path = graphs_dir.join("commit-graph-chain");
+        let io_err = |path: &std::path::Path| {
+            let path = path.to_owned();
+            move |source| Error::Io { source, path }
+        };
+
+        let (layer_paths, has_monolithic_graph) = layer_paths(&info_dir).map_err(io_err(&chain_path))?;
+        let open = |path: &std::path::Path| {
+            File::at(path).map_err(|err| gix_commitgraph::init::Error::File {
+                err,
+                path: path.to_owned(),
+            })
+        };
+        let mut layers = Vec::with_capacity(layer_paths.len());
+        let mut files = Vec::with_capacity(layer_paths.len());
+        for path in layer_paths {
+            let file = open(&path)?;
+            layers.push((path, file.checksum().to_owned(), file.num_commits()));
+            files.push(file);
+        }
+        let graph = if files.is_empty() {
+            None
+        } else {
+            Some(Graph::new(files)?)
+        };
+
+        let mut commits = Vec::new();
+        let mut seen = gix_hashtable::HashSet::default();
+        let mut queue = Vec::new();
+        for tip in tips {
+            let tip = self.find_object(tip.into())?.peel_tags_to_end()?;
+            if tip.kind == gix_object::Kind::Commit {
+                queue.push(tip.id);
+            }
+        }
+        while let Some(id) = queue.pop() {
+            if !seen.insert(id) || graph.as_ref().map_or(false, |graph| graph.lookup(id).is_some()) {
+                continue;
+            }
+            let object = self.find_object(id)?;
+            let commit = object.try_to_commit_ref()?;
+            let parents: Vec<_> = commit.parents().collect();
+            queue.extend(parents.iter().copied());
+            commits.push(write::Commit {
+                id,
+                tree: commit.tree(),
+                parents,
+                commit_time: commit.committer().time.seconds.max(0) as u64,
+            });
+        }
+        if commits.is_empty() {
+            return Ok(None);
+        }
+
+        let mut num_base_layers = layers.len();
+        let mut num_commits = commits.len() as u64;
+        while let Some((_, _, layer_commits)) = num_base_layers.checked_sub(1).map(|idx| &layers[idx]) {
+            if u64::from(*layer_commits) > num_commits * 2 {
+                break;
+            }
+            num_commits += u64::from(*layer_commits);
+            num_base_layers -= 1;
+        }
+        if let Some(graph) = graph.as_ref() {
+            let num_base_commits: u32 = layers[..num_base_layers].iter().map(|(_, _, num)| num).sum();
+            for pos in num_base_commits..graph.num_commits() {
+                let commit = graph.commit_at(gix_commitgraph::Position(pos));
+                commits.push(write::Commit {
+                    id: commit.id().to_owned(),
+                    tree: commit.root_tree_id().to_owned(),
+                    parents: commit
+                        .iter_parents()
+                        .map(|parent| parent.map(|pos| graph.id_at(pos).to_owned()))
+                        .collect::<Result<_, _>>()?,
+                    commit_time: commit.committer_timestamp(),
+                });
+            }
+        }
+        drop(graph);
+        let base_layers = &layers[..num_base_layers];
+        let base = if base_layers.is_empty() {
+            None
+        } else {
+            Some(Graph::new(
+                base_layers
+                    .iter()
+                    .map(|(path, _, _)| open(path))
+                    .collect::<Result<_, _>>()?,
+            )?)
+        };
+
+        std::fs::create_dir_all(&graphs_dir).map_err(io_err(&graphs_dir))?;
+        let mut out = std::io::BufWriter::new(
+            gix_tempfile::new(
+                &graphs_dir,
+                gix_tempfile::ContainingDirectory::Exists,
+                gix_tempfile::AutoRemove::Tempfile,
+            )
+            .map_err(io_err(&graphs_dir))?,
+        );
+        let outcome = File::write_to(
+            commits,
+            base.as_ref(),
+            &mut out,
+            write::Options {
+                object_hash: self.object_hash(),
+            },
+        )?;
+        drop(base);
+        let layer_path = graphs_dir.join(format!("graph-{}.graph", outcome.checksum));
+        out.into_inner()
+            .map_err(|err| io_err(&layer_path)(err.into_error()))?
+            .persist(&layer_path)
+            .map_err(|err| io_err(&layer_path)(err.error))?;
+
+        if has_monolithic_graph && num_base_layers != 0 {
+            let (path, checksum, _) = &base_layers[0];
+            let chain_layer_path = graphs_dir.join(format!("graph-{checksum}.graph"));
+            std::fs::copy(path, &chain_layer_path).map_err(io_err(&chain_layer_path))?;
+        }
+        let mut chain = gix_lock::File::acquire_to_update_resource(
+            &chain_path,
+            self.config.other_lock_timeout()?,
+            Some(self.objects.store_ref().path().into()),
+        )?;
+        for checksum in base_layers
+            .iter()
+            .map(|(_, checksum, _)| checksum)
+            .chain(Some(&outcome.checksum))
+        {
+            writeln!(chain, "{checksum}").map_err(io_err(&chain_path))?;
+        }
+        chain.commit()?;
+
+        // Layers that were merged are unused now, and so is the monolithic file, which is preferred over the chain if present.
+        for (path, _, _) in &layers[num_base_layers..] {
+            std::fs::remove_file(path).ok();
+        }
+        if has_monolithic_graph {
+            std::fs::remove_file(&monolithic_path).ok();
+        }
+
+        Ok(Some(Outcome {
+            path: layer_path,
+            num_commits: outcome.num_commits,
+            num_layers: num_base_layers + 1,
+        }))
+    }
+
+    /// Verify all layers of the commit-graph against the object database, checking the root tree, parents and committer time
+    /// of each commit as well as the generation numbers, in addition to the integrity of each layer and the chain they form.
+    ///
+    /// All mismatches are collected and returned per layer instead of stopping at the first one, similar to
+    /// `git commit-graph verify`. Use `options` to remove invalid layers, which assures that commit walks don't silently
+    /// rely on wrong information.
+    ///
+    /// Note that `core.commitGraph` isn't consulted, and that the outcome has no layers if there is no commit-graph.
+    pub fn verify_commit_graph(
+        &self,
+        options: super::verify_commit_graph::Options,
+    ) -> Result<super::verify_commit_graph::Outcome, super::verify_commit_graph::Error> {
+        use std::{cmp::min, io::Write};
+
+        use gix_commitgraph::{File, Graph, Position, GENERATION_NUMBER_MAX};
+
+        use super::verify_commit_graph::{Error, Layer, Mismatch, Outcome};
+
+        let info_dir = self.objects.store_ref().path().join("info");
+        let graphs_dir = info_dir.join("commit-graphs");
+        let chain_path = graphs_dir.join("commit-graph-chain");
+        let io_err = |path: &std::path::Path| {
+            let path = path.to_owned();
+            move |source| Error::Io { source, path }
+        };
+        let (layer_paths, has_monolithic_graph) = layer_paths(&info_dir).map_err(io_err(&chain_path))?;
+
+        let mut out = Outcome::default();
+        let mut files = Vec::with_capacity(layer_paths.len());
+        let mut checksums: Vec<gix_hash::ObjectId> = Vec::with_capacity(layer_paths.len());
+        for path in layer_paths {
+            let mut layer = Layer {
+                path,
+                num_commits: 0,
+                mismatches: Vec::new(),
+            };
+            if files.len() != out.layers.len() {
+                layer.mismatches.push(Mismatch::DependsOnCorruptLayer);
+                out.layers.push(layer);
+                continue;
+            }
+            let file = File::at(&layer.path).map_err(|err| err.to_string()).and_then(|file| {
+                file.traverse(|_| Ok::<_, std::convert::Infallible>(()))
+                    .map_err(|err| err.to_string())?;
+                if usize::from(file.base_graph_count()) != files.len()
+                    || !file.iter_base_graph_ids().eq(checksums.iter().map(|id| &**id))
+                {
+                    return Err("the layer doesn't build on the layers below it".to_owned());
+                }
+                Ok(file)
+            });
+            match file {
+                Ok(file) => {
+                    layer.num_commits = file.num_commits();
+                    checksums.push(file.checksum().to_owned());
+                    files.push(file);
+                }
+                Err(message) => layer.mismatches.push(Mismatch::Corrupt { message }),
+            }
+            out.layers.push(layer);
+        }
+
+        if !files.is_empty() {
+            let graph = Graph::new(files)?;
+            let mut layer_end = 0;
+            let mut layers = out.layers.iter_mut();
+            let mut layer = layers.next().expect("at least one layer per file");
+            layer_end += layer.num_commits;
+            for pos in 0..graph.num_commits() {
+                while pos >= layer_end {
+                    layer = layers.next().expect("all commits are in a layer");
+                    layer_end += layer.num_commits;
+                }
+                let commit = graph.commit_at(Position(pos));
+                let id = commit.id().to_owned();
+                let mut parents = Vec::new();
+                let mut max_parent_generation = 0;
+                for parent in commit.iter_parents() {
+                    match parent {
+                        Ok(parent) if parent.0 < layer_end => {
+                            parents.push(graph.id_at(parent).to_owned());
+                            max_parent_generation = max_parent_generation.max(graph.commit_at(parent).generation());
+                        }
+                        Ok(parent) => layer.mismatches.push(Mismatch::Corrupt {
+                            message: format!("commit {id} has parent position {} which is out of range", parent.0),
+                        }),
+                        Err(err) => layer.mismatches.push(Mismatch::Corrupt {
+                            message: format!("commit {id} has invalid parents: {err}"),
+                        }),
+                    }
+                }
+                let expected_generation = min(max_parent_generation + 1, GENERATION_NUMBER_MAX);
+                if commit.generation() != expected_generation {
+                    layer.mismatches.push(Mismatch::Generation {
+                        id,
+                        expected: expected_generation,
+                        actual: commit.generation(),
+                    });
+                }
+
+                let Some(object) = self.try_find_object(id)?.filter(|object| object.kind.is_commit()) else {
+                    layer.mismatches.push(Mismatch::MissingCommit { id });
+                    continue;
+                };
+                let odb_commit = object.try_to_commit_ref()?;
+                if odb_commit.tree() != commit.root_tree_id() {
+                    layer.mismatches.push(Mismatch::RootTree {
+                        id,
+                        expected: odb_commit.tree(),
+                        actual: commit.root_tree_id().to_owned(),
+                    });
+                }
+                let odb_parents: Vec<_> = odb_commit.parents().collect();
+                if odb_parents != parents {
+                    layer.mismatches.push(Mismatch::Parents {
+                        id,
+                        expected: odb_parents,
+                        actual: parents,
+                    });
+                }
+                let odb_time = odb_commit.committer().time.seconds.max(0) as u64;
+                if odb_time != commit.committer_timestamp() {
+                    layer.mismatches.push(Mismatch::CommitTime {
+                        id,
+                        expected: odb_time,
+                        actual: commit.committer_timestamp(),
+                    });
+                }
+            }
+        }
+
+        let Some(first_invalid) = out.layers.iter().position(|layer| !layer.mismatches.is_empty()) else {
+            return Ok(out);
+        };
+        if !options.remove_invalid_layers {
+            return Ok(out);
+        }
+        if has_monolithic_graph {
+            let path = &out.layers[0].path;
+            std::fs::remove_file(path).map_err(io_err(path))?;
+        } else {
+            let mut chain = gix_lock::File::acquire_to_update_resource(
+                &chain_path,
+                self.config.other_lock_timeout()?,
+                Some(self.objects.store_ref().path().into()),
+            )?;
+            if first_invalid == 0 {
+                drop(chain);
+                std::fs::remove_file(&chain_path).map_err(io_err(&chain_path))?;
+            } else {
+                for checksum in &checksums[..first_invalid] {
+                    writeln!(chain, "{checksum}").map_err(io_err(&chain_path))?;
+                }
+                chain.commit()?;
+            }
+            // Layers that aren't part of the chain anymore are unused, and it's fine if they couldn't be removed.
+            for layer in &out.layers[first_invalid..] {
+                std::fs::remove_file(&layer.path).ok();
+            }
+        }
+        out.removed_layers = out.layers[first_invalid..]
+            .iter()
+            .map(|layer| layer.path.clone())
+            .collect();
+        Ok(out)
+    }
+}
+
+/// Return the paths to all layers of the commit-graph in `info_dir` from bottom to top, which is either the monolithic
+/// `commit-graph` file or the files listed in the commit-graph chain, along with `true` if it's the former.
+fn layer_paths(info_dir: &std::path::Path) -> std::io::Result<(Vec<std::path::PathBuf>, bool)> {
+    let monolithic_path = info_dir.join("commit-graph");
+    if monolithic_path.is_file() {
+        return Ok((vec![monolithic_path], true));
+    }
+    let graphs_dir = info_dir.join("commit-graphs");
+    Ok(match std::fs::read_to_string(graphs_dir.join("commit-graph-chain")) {
+        Ok(chain) => (
+            chain
+                .lines()
+                .map(|hash| graphs_dir.join(format!("graph-{hash}.graph")))
+                .collect(),
+            false,
+        ),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => (Vec::new(), false),
+        Err(err) => return Err(err),
+    })
 }
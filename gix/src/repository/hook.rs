@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+
+use crate::{config::tree::Core, Repository};
+
+impl Repository {
+    /// Return the path to the hook named `name` along with a command to run it, or `None` if the repository isn't
+    /// [fully trusted](gix_sec::Trust::Full) or if the hook doesn't exist or isn't executable.
+    ///
+    /// Hooks are looked up in `core.hooksPath` as configured in trusted configuration files, or in the `hooks` directory
+    /// of the repository otherwise.
+    /// Like `git`, the command runs in the worktree, or in the git directory of bare repositories, and the
+    /// [command context](Self::command_context()) is passed to it with absolute paths.
+    /// It inherits `stdout`, and arguments or input are left to the caller.
+    pub(crate) fn hook_command(
+        &self,
+        name: &str,
+    ) -> Result<Option<(PathBuf, std::process::Command)>, crate::repository::hook_command::Error> {
+        if self.git_dir_trust() != gix_sec::Trust::Full {
+            return Ok(None);
+        }
+        let run_dir = self.work_dir().unwrap_or_else(|| self.git_dir());
+        let hooks_dir = match self.config.trusted_file_path(Core::HOOKS_PATH).transpose()? {
+            // Relative paths are relative to where hooks are run.
+            Some(path) => run_dir.join(path),
+            None => self.common_dir().join("hooks"),
+        };
+        let path = hooks_dir.join(name);
+        let is_executable_file = std::fs::metadata(&path).map_or(false, |meta| {
+            meta.is_file() && (cfg!(not(unix)) || gix_fs::is_executable(&meta))
+        });
+        if !is_executable_file {
+            return Ok(None);
+        }
+
+        // The hook runs in another directory, so relative paths would be wrong.
+        let cwd = std::env::current_dir().map_err(crate::repository::hook_command::Error::CurrentDir)?;
+        let mut ctx = self.command_context()?;
+        ctx.git_dir = ctx.git_dir.map(|dir| cwd.join(dir));
+        ctx.worktree_dir = ctx.worktree_dir.map(|dir| cwd.join(dir));
+        let path = cwd.join(path);
+        let mut cmd: std::process::Command = gix_command::prepare(path.as_os_str())
+            .with_context(ctx)
+            .stdout(std::process::Stdio::inherit())
+            .into();
+        cmd.current_dir(cwd.join(run_dir));
+        Ok(Some((path, cmd)))
+    }
+}
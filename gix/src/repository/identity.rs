@@ -72,6 +72,131 @@ impl crate::Repository {
     }
 }
 
+/// Identity handling with explanation.
+impl crate::Repository {
+    /// Like [`committer()`](Self::committer()), but return the name, email and time along with the [source](Source)
+    /// each of them was obtained from, even if some of them are missing.
+    ///
+    /// This allows to tell users which configuration to fix, or which key to set if the identity is incomplete.
+    pub fn committer_with_source(&self) -> WithSource {
+        self.identity_with_source(
+            [&Committer::NAME, &gitoxide::Committer::NAME_FALLBACK, &User::NAME],
+            [
+                &Committer::EMAIL,
+                &gitoxide::Committer::EMAIL_FALLBACK,
+                &User::EMAIL,
+                &gitoxide::User::EMAIL_FALLBACK,
+            ],
+            self.config.personas().committer.time.as_ref(),
+            &gitoxide::Commit::COMMITTER_DATE,
+        )
+    }
+
+    /// Like [`author()`](Self::author()), but return the name, email and time along with the [source](Source)
+    /// each of them was obtained from, even if some of them are missing.
+    ///
+    /// This allows to tell users which configuration to fix, or which key to set if the identity is incomplete.
+    pub fn author_with_source(&self) -> WithSource {
+        self.identity_with_source(
+            [&Author::NAME, &gitoxide::Author::NAME_FALLBACK, &User::NAME],
+            [
+                &Author::EMAIL,
+                &gitoxide::Author::EMAIL_FALLBACK,
+                &User::EMAIL,
+                &gitoxide::User::EMAIL_FALLBACK,
+            ],
+            self.config.personas().author.time.as_ref(),
+            &gitoxide::Commit::AUTHOR_DATE,
+        )
+    }
+
+    fn identity_with_source(
+        &self,
+        name_keys: [&'static keys::Any; 3],
+        email_keys: [&'static keys::Any; 4],
+        time: Option<&Result<gix_date::Time, gix_date::parse::Error>>,
+        time_key: &'static keys::Time,
+    ) -> WithSource {
+        let config = &self.config.resolved;
+        WithSource {
+            name: name_keys.into_iter().find_map(|key| value_with_source(config, key)),
+            email: email_keys.into_iter().find_map(|key| value_with_source(config, key)),
+            time: extract_time_or_default(time, time_key).map(|time| {
+                let source = value_with_source(config, time_key).map_or(Source::Now, |(_, source)| source);
+                (time, source)
+            }),
+        }
+    }
+}
+
+/// Where a field of an identity was obtained from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// The value was set in the configuration.
+    Config {
+        /// The name of the key holding the value, like `user.email`.
+        key: BString,
+        /// The kind of configuration the value was set in, like the global or the repository-local configuration.
+        kind: gix_config::Source,
+        /// The path to the configuration file, if the value was set in a file.
+        path: Option<std::path::PathBuf>,
+    },
+    /// The value was set in the environment `variable`, like `GIT_COMMITTER_EMAIL`.
+    Environment {
+        /// The name of the environment variable.
+        variable: &'static str,
+    },
+    /// The time wasn't configured, so the current time is used.
+    Now,
+}
+
+/// An identity as returned by [`Repository::committer_with_source()`](crate::Repository::committer_with_source())
+/// along with the [source](Source) of each of its fields.
+#[derive(Debug)]
+pub struct WithSource {
+    /// The name, or `None` if it isn't configured, which can be fixed by setting `user.name`.
+    pub name: Option<(BString, Source)>,
+    /// The email, or `None` if it isn't configured, which can be fixed by setting `user.email`.
+    pub email: Option<(BString, Source)>,
+    /// The time, or the error if the configured time couldn't be parsed.
+    pub time: Result<(gix_date::Time, Source), config::time::Error>,
+}
+
+impl WithSource {
+    /// Return the signature of this identity, or `None` if name or email are missing.
+    pub fn to_signature(&self) -> Option<Result<gix_actor::SignatureRef<'_>, &config::time::Error>> {
+        let (name, email) = (&self.name.as_ref()?.0, &self.email.as_ref()?.0);
+        Some(self.time.as_ref().map(|(time, _)| gix_actor::SignatureRef {
+            name: name.as_ref(),
+            email: email.as_ref(),
+            time: *time,
+        }))
+    }
+}
+
+/// Find the value of `key` in `config` along with the source of the section that defines it last.
+fn value_with_source(config: &gix_config::File<'static>, key: &'static dyn Key) -> Option<(BString, Source)> {
+    let name = key.logical_name();
+    let key_ref = gix_config::KeyRef::parse_unvalidated(name.as_str().into())?;
+    let sections: Vec<_> = config
+        .sections_by_name(key_ref.section_name)?
+        .filter(|section| section.header().subsection_name() == key_ref.subsection_name)
+        .collect();
+    sections.into_iter().rev().find_map(|section| {
+        let value = section.body().value(key_ref.value_name)?.into_owned();
+        let meta = section.meta();
+        let source = match (meta.source, key.environment_override()) {
+            (gix_config::Source::EnvOverride, Some(variable)) => Source::Environment { variable },
+            _ => Source::Config {
+                key: name.as_str().into(),
+                kind: meta.source,
+                path: meta.path.clone(),
+            },
+        };
+        Some((value, source))
+    })
+}
+
 fn extract_time_or_default(
     time: Option<&Result<gix_date::Time, gix_date::parse::Error>>,
     config_key: &'static keys::Time,
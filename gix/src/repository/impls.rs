@@ -7,17 +7,19 @@ impl Clone for crate::Repository {
             self.common_dir.clone(),
             self.config.clone(),
             self.options.clone(),
+            self.open_metrics,
             #[cfg(feature = "index")]
             self.index.clone(),
             self.shallow_commits.clone(),
             #[cfg(feature = "attributes")]
             self.modules.clone(),
+            #[cfg(feature = "excludes")]
+            self.worktree_cache.clone(),
         );
 
         if self.bufs.is_none() {
             new.bufs.take();
         }
-
         new
     }
 }
@@ -49,11 +51,14 @@ impl From<&crate::ThreadSafeRepository> for crate::Repository {
             repo.common_dir.clone(),
             repo.config.clone(),
             repo.linked_worktree_options.clone(),
+            repo.open_metrics,
             #[cfg(feature = "index")]
             repo.index.clone(),
             repo.shallow_commits.clone(),
             #[cfg(feature = "attributes")]
             repo.modules.clone(),
+            #[cfg(feature = "excludes")]
+            repo.worktree_cache.clone(),
         )
     }
 }
@@ -67,11 +72,14 @@ impl From<crate::ThreadSafeRepository> for crate::Repository {
             repo.common_dir,
             repo.config,
             repo.linked_worktree_options,
+            repo.open_metrics,
             #[cfg(feature = "index")]
             repo.index,
             repo.shallow_commits,
             #[cfg(feature = "attributes")]
             repo.modules.clone(),
+            #[cfg(feature = "excludes")]
+            repo.worktree_cache,
         )
     }
 }
@@ -85,11 +93,14 @@ impl From<crate::Repository> for crate::ThreadSafeRepository {
             common_dir: r.common_dir,
             config: r.config,
             linked_worktree_options: r.options,
+            open_metrics: r.open_metrics,
             #[cfg(feature = "index")]
             index: r.index,
             #[cfg(feature = "attributes")]
             modules: r.modules,
             shallow_commits: r.shallow_commits,
+            #[cfg(feature = "excludes")]
+            worktree_cache: r.worktree_cache,
         }
     }
 }
@@ -9,9 +9,11 @@ impl crate::Repository {
         common_dir: Option<std::path::PathBuf>,
         config: crate::config::Cache,
         linked_worktree_options: crate::open::Options,
+        open_metrics: crate::open::Metrics,
         #[cfg(feature = "index")] index: crate::worktree::IndexStorage,
         shallow_commits: crate::shallow::CommitsStorage,
         #[cfg(feature = "attributes")] modules: crate::submodule::ModulesFileStorage,
+        #[cfg(feature = "excludes")] worktree_cache: Option<crate::WorktreeCache>,
     ) -> Self {
         setup_objects(&mut objects, &config);
         crate::Repository {
@@ -22,11 +24,14 @@ impl crate::Repository {
             refs,
             config,
             options: linked_worktree_options,
+            open_metrics,
             #[cfg(feature = "index")]
             index,
             shallow_commits,
             #[cfg(feature = "attributes")]
             modules,
+            #[cfg(feature = "excludes")]
+            worktree_cache,
         }
     }
 
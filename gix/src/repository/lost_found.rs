@@ -0,0 +1,227 @@
+use gix_hash::ObjectId;
+use gix_hashtable::HashSet;
+
+use crate::{lost_found, Repository};
+
+impl Repository {
+    /// Find all objects that aren't reachable from references, the `HEAD` of all worktrees and, depending on `options`,
+    /// reference logs and the index, similar to `git fsck --unreachable`.
+    ///
+    /// The returned [outcome](lost_found::Outcome) can be used to [write dangling objects](lost_found::Outcome::write_lost_found())
+    /// to the `lost-found` directory like `git fsck --lost-found` would, and to provide suggestions for recovering commits
+    /// that are only mentioned in reference logs, like those of deleted branches.
+    ///
+    /// Note that objects that are missing, for instance beyond the boundary of shallow clones, are silently skipped,
+    /// and that objects of alternate object databases are only used to determine reachability, but are never reported
+    /// as they belong to other repositories.
+    pub fn lost_found(&self, options: lost_found::Options) -> Result<lost_found::Outcome, lost_found::Error> {
+        let _span = gix_trace::coarse!("gix::lost_found()");
+        let mut roots = Vec::new();
+        let mut log_entries = Vec::new();
+
+        let refs = self.refs.iter()?;
+        for reference in refs.all().map_err(crate::reference::iter::init::Error::Io)? {
+            let reference = crate::Reference::from_ref(reference?, self);
+            roots.extend(reference.target().try_id().map(|id| (id.to_owned(), None)));
+            collect_log_entries(&reference, &mut log_entries)?;
+        }
+        if let Ok(head) = self.find_reference("HEAD") {
+            collect_log_entries(&head, &mut log_entries)?;
+        }
+        roots.extend(self.head_id().ok().map(|id| (id.detach(), None)));
+        for proxy in self.worktrees().map_err(lost_found::Error::ListWorktrees)? {
+            let repo = proxy.into_repo_with_possibly_inaccessible_worktree()?;
+            roots.extend(repo.head_id().ok().map(|id| (id.detach(), None)));
+            if let Ok(head) = repo.find_reference("HEAD") {
+                collect_log_entries(&head, &mut log_entries)?;
+            }
+        }
+        #[cfg(feature = "index")]
+        if options.index_is_reachable {
+            if let Some(index) = self.try_index()? {
+                roots.extend(
+                    index
+                        .entries()
+                        .iter()
+                        .filter(|entry| !entry.mode.contains(gix_index::entry::Mode::COMMIT))
+                        .map(|entry| (entry.id, Some(gix_object::Kind::Blob))),
+                );
+            }
+        }
+        if options.reflogs_are_reachable {
+            roots.extend(log_entries.iter().map(|entry| (entry.id, None)));
+        }
+
+        let mut reachable = HashSet::default();
+        let mut buf = Vec::new();
+        while let Some((id, kind)) = roots.pop() {
+            if !reachable.insert(id) {
+                continue;
+            }
+            // Blobs don't reference other objects, so avoid loading them by relying on the kind their referrer indicates.
+            let kind = match kind {
+                Some(kind) => kind,
+                None => match self.try_find_header(id)? {
+                    Some(header) => header.kind(),
+                    None => continue,
+                },
+            };
+            if kind.is_blob() {
+                continue;
+            }
+            if let Some(obj) = self.try_find_object(id)? {
+                buf.clear();
+                referenced_ids(&obj, &mut buf)?;
+                roots.extend(buf.drain(..).filter(|(id, _)| !reachable.contains(id)));
+            }
+        }
+
+        let mut seen = HashSet::default();
+        let mut referenced = HashSet::default();
+        let mut unreachable = Vec::new();
+        for id in own_objects(self)? {
+            if reachable.contains(&id) || !seen.insert(id) {
+                continue;
+            }
+            let kind = self.find_header(id)?.kind();
+            let time = if kind.is_blob() {
+                loose_object_mtime(self, &id)
+            } else {
+                let obj = self.find_object(id)?;
+                buf.clear();
+                referenced_ids(&obj, &mut buf)?;
+                referenced.extend(buf.drain(..).map(|(id, _)| id));
+                let decoded = gix_object::Data::new(obj.kind, &obj.data).decode()?;
+                match decoded {
+                    gix_object::ObjectRef::Commit(commit) => Some(commit.committer.time),
+                    gix_object::ObjectRef::Tag(tag) => tag.tagger.map(|signature| signature.time),
+                    gix_object::ObjectRef::Tree(_) | gix_object::ObjectRef::Blob(_) => loose_object_mtime(self, &id),
+                }
+            };
+            unreachable.push(lost_found::Object {
+                id,
+                kind,
+                time,
+                is_dangling: false,
+            });
+        }
+        for obj in &mut unreachable {
+            obj.is_dangling = !referenced.contains(&obj.id);
+        }
+        unreachable.sort_by_key(|obj| obj.id);
+
+        let mut suggestions: Vec<lost_found::Suggestion> = Vec::new();
+        if !options.reflogs_are_reachable {
+            log_entries.sort_by_key(|entry| std::cmp::Reverse(entry.time.seconds));
+            let mut seen = HashSet::default();
+            for entry in log_entries {
+                if reachable.contains(&entry.id) || !seen.insert(entry.id) {
+                    continue;
+                }
+                if self
+                    .try_find_header(entry.id)?
+                    .map_or(false, |header| header.kind().is_commit())
+                {
+                    suggestions.push(entry);
+                }
+            }
+        }
+        Ok(lost_found::Outcome {
+            unreachable,
+            suggestions,
+        })
+    }
+}
+
+/// Collect the ids of all objects mentioned in the reference log of `reference`, if there is one, into `out`.
+fn collect_log_entries(
+    reference: &crate::Reference<'_>,
+    out: &mut Vec<lost_found::Suggestion>,
+) -> Result<(), lost_found::Error> {
+    let mut platform = reference.log_iter();
+    let Some(lines) = platform.all()? else {
+        return Ok(());
+    };
+    for line in lines {
+        let line = line?;
+        for id in [line.previous_oid(), line.new_oid()] {
+            if id.is_null() {
+                continue;
+            }
+            out.push(lost_found::Suggestion {
+                id,
+                log: reference.name().to_owned(),
+                message: line.message.to_owned(),
+                time: line.signature.time,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Put the ids of all objects directly referenced by `obj` into `out`, along with their kind if `obj` indicates it.
+fn referenced_ids(
+    obj: &crate::Object<'_>,
+    out: &mut Vec<(ObjectId, Option<gix_object::Kind>)>,
+) -> Result<(), gix_object::decode::Error> {
+    match gix_object::Data::new(obj.kind, &obj.data).decode()? {
+        gix_object::ObjectRef::Commit(commit) => {
+            out.push((commit.tree(), Some(gix_object::Kind::Tree)));
+            out.extend(commit.parents().map(|id| (id, Some(gix_object::Kind::Commit))));
+        }
+        gix_object::ObjectRef::Tree(tree) => out.extend(
+            tree.entries
+                .iter()
+                .filter(|entry| !entry.mode.is_commit())
+                .map(|entry| {
+                    let kind = if entry.mode.is_tree() {
+                        gix_object::Kind::Tree
+                    } else {
+                        gix_object::Kind::Blob
+                    };
+                    (entry.oid.to_owned(), Some(kind))
+                }),
+        ),
+        gix_object::ObjectRef::Tag(tag) => out.push((tag.target(), Some(tag.target_kind))),
+        gix_object::ObjectRef::Blob(_) => {}
+    }
+    Ok(())
+}
+
+/// Return the ids of all objects in the object database of `repo` itself, leaving out those of its alternates.
+fn own_objects(repo: &Repository) -> Result<Vec<ObjectId>, lost_found::Error> {
+    let objects_dir = repo.objects.store_ref().path();
+    let mut ids: Vec<_> = gix_odb::loose::Store::at(objects_dir, repo.object_hash())
+        .iter()
+        .filter_map(Result::ok)
+        .collect();
+    let pack_dir = objects_dir.join("pack");
+    let entries = match std::fs::read_dir(&pack_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(ids),
+        Err(err) => return Err(lost_found::Error::ListPacks(err)),
+    };
+    for entry in entries {
+        let path = entry.map_err(lost_found::Error::ListPacks)?.path();
+        if path.extension().map_or(true, |ext| ext != "idx") {
+            continue;
+        }
+        let index = gix_pack::index::File::at(&path, repo.object_hash())?;
+        ids.extend(index.iter().map(|entry| entry.oid));
+    }
+    Ok(ids)
+}
+
+/// Use the modification time of the loose object with `id` as approximation of its creation time.
+fn loose_object_mtime(repo: &Repository, id: &gix_hash::oid) -> Option<gix_date::Time> {
+    let hex = id.to_hex().to_string();
+    let path = repo.objects.store_ref().path().join(&hex[..2]).join(&hex[2..]);
+    let seconds = std::fs::metadata(path)
+        .ok()?
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_secs();
+    Some(gix_date::Time::new(seconds as gix_date::SecondsSinceUnixEpoch, 0))
+}
@@ -20,6 +20,7 @@ pub enum Kind {
 #[cfg(any(feature = "attributes", feature = "excludes"))]
 pub mod attributes;
 mod cache;
+mod capabilities;
 mod config;
 ///
 #[cfg(feature = "blob-diff")]
@@ -27,19 +28,32 @@ mod diff;
 ///
 #[cfg(feature = "dirwalk")]
 mod dirwalk;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod fast_forward;
+///
+#[cfg(feature = "blocking-network-client")]
+pub mod fetch_all;
 ///
 #[cfg(feature = "attributes")]
 pub mod filter;
 ///
 pub mod freelist;
 mod graph;
-pub(crate) mod identity;
+#[cfg(any(
+    feature = "async-network-client",
+    feature = "blocking-network-client",
+    feature = "worktree-mutation"
+))]
+mod hook;
+///
+pub mod identity;
 mod impls;
 #[cfg(feature = "index")]
 mod index;
 pub(crate) mod init;
 mod kind;
 mod location;
+mod lost_found;
 #[cfg(feature = "mailmap")]
 mod mailmap;
 ///
@@ -48,14 +62,19 @@ mod merge;
 mod object;
 #[cfg(feature = "attributes")]
 mod pathspec;
+mod plumbing;
 mod reference;
 mod remote;
+#[cfg(feature = "repair")]
+mod repair;
 mod revision;
 mod shallow;
 mod state;
+mod stats;
 #[cfg(feature = "attributes")]
 mod submodule;
 mod thread_safe;
+mod topology;
 mod worktree;
 
 ///
@@ -178,6 +197,26 @@ pub mod merge_base_with_graph {
     }
 }
 
+///
+#[cfg(any(
+    feature = "async-network-client",
+    feature = "blocking-network-client",
+    feature = "worktree-mutation"
+))]
+pub mod hook_command {
+    /// The error returned when looking up a hook to run it.
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        CommandContext(#[from] crate::config::command_context::Error),
+        #[error("The hooks directory configured in core.hooksPath could not be interpolated")]
+        HooksPath(#[from] gix_config::path::interpolate::Error),
+        #[error("Could not obtain the current directory to run hooks from")]
+        CurrentDir(#[source] std::io::Error),
+    }
+}
+
 ///
 pub mod commit_graph_if_enabled {
     /// The error returned by [Repository::commit_graph_if_enabled()](crate::Repository::commit_graph_if_enabled()).
@@ -191,6 +230,196 @@ pub mod commit_graph_if_enabled {
     }
 }
 
+///
+pub mod write_commit_graph {
+    use std::path::PathBuf;
+
+    /// The error returned by [Repository::write_commit_graph()](crate::Repository::write_commit_graph()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        OpenCommitGraph(#[from] gix_commitgraph::init::Error),
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        DecodeCommit(#[from] crate::object::conversion::Error),
+        #[error(transparent)]
+        ParentOfGraphCommit(#[from] gix_commitgraph::file::commit::Error),
+        #[error(transparent)]
+        WriteCommitGraph(#[from] gix_commitgraph::file::write::Error),
+        #[error("Could not read or write commit-graph data at '{}'", path.display())]
+        Io { source: std::io::Error, path: PathBuf },
+        #[error(transparent)]
+        LockTimeoutConfiguration(#[from] crate::config::lock_timeout::Error),
+        #[error("Failed to lock the commit-graph chain for writing")]
+        LockChain(#[from] gix_lock::acquire::Error),
+        #[error("Failed to commit the commit-graph chain")]
+        CommitChain(#[from] gix_lock::commit::Error<gix_lock::File>),
+    }
+
+    /// The outcome of [Repository::write_commit_graph()](crate::Repository::write_commit_graph()).
+    #[derive(Debug, Clone)]
+    pub struct Outcome {
+        /// The path to the newly written layer of the commit-graph chain.
+        pub path: PathBuf,
+        /// The amount of commits in the new layer, including the ones of the layers that were merged into it.
+        pub num_commits: u32,
+        /// The amount of layers in the commit-graph chain, including the new one.
+        pub num_layers: usize,
+    }
+}
+
+///
+pub mod verify_commit_graph {
+    use std::path::PathBuf;
+
+    use gix_hash::ObjectId;
+
+    /// The error returned by [Repository::verify_commit_graph()](crate::Repository::verify_commit_graph()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        OpenCommitGraph(#[from] gix_commitgraph::init::Error),
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::Error),
+        #[error(transparent)]
+        DecodeCommit(#[from] crate::object::conversion::Error),
+        #[error("Could not read or write commit-graph data at '{}'", path.display())]
+        Io { source: std::io::Error, path: PathBuf },
+        #[error(transparent)]
+        LockTimeoutConfiguration(#[from] crate::config::lock_timeout::Error),
+        #[error("Failed to lock the commit-graph chain for writing")]
+        LockChain(#[from] gix_lock::acquire::Error),
+        #[error("Failed to commit the commit-graph chain")]
+        CommitChain(#[from] gix_lock::commit::Error<gix_lock::File>),
+    }
+
+    /// Options for use in [Repository::verify_commit_graph()](crate::Repository::verify_commit_graph()).
+    #[derive(Debug, Default, Copy, Clone)]
+    pub struct Options {
+        /// If `true`, remove the first layer with mismatches along with all layers on top of it, as these depend on it.
+        ///
+        /// That way, commit walks fall back to reading commits from the object database instead of using wrong information,
+        /// and the removed layers are recreated the next time the commit-graph is written.
+        pub remove_invalid_layers: bool,
+    }
+
+    /// A way in which a commit-graph layer doesn't match the object database, or itself.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Mismatch {
+        /// The layer couldn't be read, or is inconsistent in itself or with the layers below it.
+        Corrupt {
+            /// A description of the problem.
+            message: String,
+        },
+        /// The layer wasn't verified as one of the layers below it is corrupt.
+        DependsOnCorruptLayer,
+        /// The commit is stored in the commit-graph, but isn't a commit in the object database.
+        MissingCommit {
+            /// The id of the commit.
+            id: ObjectId,
+        },
+        /// The root tree of a commit doesn't match.
+        RootTree {
+            /// The id of the commit.
+            id: ObjectId,
+            /// The root tree as stored in the object database.
+            expected: ObjectId,
+            /// The root tree as stored in the commit-graph.
+            actual: ObjectId,
+        },
+        /// The parents of a commit don't match.
+        Parents {
+            /// The id of the commit.
+            id: ObjectId,
+            /// The parents as stored in the object database.
+            expected: Vec<ObjectId>,
+            /// The parents as stored in the commit-graph.
+            actual: Vec<ObjectId>,
+        },
+        /// The committer time of a commit doesn't match.
+        CommitTime {
+            /// The id of the commit.
+            id: ObjectId,
+            /// The time in seconds since the Unix epoch as stored in the object database.
+            expected: u64,
+            /// The time in seconds since the Unix epoch as stored in the commit-graph.
+            actual: u64,
+        },
+        /// The generation number of a commit doesn't follow from the generation numbers of its parents.
+        Generation {
+            /// The id of the commit.
+            id: ObjectId,
+            /// The generation number derived from the parents of the commit.
+            expected: u32,
+            /// The generation number as stored in the commit-graph.
+            actual: u32,
+        },
+    }
+
+    impl std::fmt::Display for Mismatch {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Mismatch::Corrupt { message } => write!(f, "the layer is corrupt: {message}"),
+                Mismatch::DependsOnCorruptLayer => f.write_str("the layer depends on a corrupt layer"),
+                Mismatch::MissingCommit { id } => write!(f, "commit {id} isn't a commit in the object database"),
+                Mismatch::RootTree { id, expected, actual } => {
+                    write!(f, "commit {id} has root tree {actual}, but it should be {expected}")
+                }
+                Mismatch::Parents { id, expected, actual } => write!(
+                    f,
+                    "commit {id} has parents {actual:?}, but they should be {expected:?}",
+                    actual = actual.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                    expected = expected.iter().map(ToString::to_string).collect::<Vec<_>>()
+                ),
+                Mismatch::CommitTime { id, expected, actual } => {
+                    write!(f, "commit {id} has commit time {actual}, but it should be {expected}")
+                }
+                Mismatch::Generation { id, expected, actual } => {
+                    write!(f, "commit {id} has generation {actual}, but it should be {expected}")
+                }
+            }
+        }
+    }
+
+    /// Information about a single layer of the commit-graph, which is either the monolithic `commit-graph` file or one
+    /// of the files listed in the commit-graph chain.
+    #[derive(Debug, Clone)]
+    pub struct Layer {
+        /// The path to the file of the layer.
+        pub path: PathBuf,
+        /// The amount of commits in the layer, or `0` if it couldn't be read.
+        pub num_commits: u32,
+        /// All mismatches found in the layer, which is empty if the layer is valid.
+        pub mismatches: Vec<Mismatch>,
+    }
+
+    /// The outcome of [Repository::verify_commit_graph()](crate::Repository::verify_commit_graph()).
+    #[derive(Debug, Clone, Default)]
+    pub struct Outcome {
+        /// All layers of the commit-graph from the bottom to the top of the chain, or empty if there is no commit-graph.
+        pub layers: Vec<Layer>,
+        /// The paths to the layers that were removed if [`Options::remove_invalid_layers`] was set.
+        pub removed_layers: Vec<PathBuf>,
+    }
+
+    impl Outcome {
+        /// Return `true` if no layer has any mismatch.
+        pub fn is_valid(&self) -> bool {
+            self.layers.iter().all(|layer| layer.mismatches.is_empty())
+        }
+
+        /// Iterate over all mismatches of all layers, along with the path to the layer they were found in.
+        pub fn iter_mismatches(&self) -> impl Iterator<Item = (&std::path::Path, &Mismatch)> + '_ {
+            self.layers
+                .iter()
+                .flat_map(|layer| layer.mismatches.iter().map(move |m| (layer.path.as_path(), m)))
+        }
+    }
+}
+
 ///
 #[cfg(feature = "index")]
 pub mod index_from_tree {
@@ -240,6 +469,203 @@ pub mod branch_remote_tracking_ref_name {
     }
 }
 
+///
+pub mod new_branch_tracking {
+    /// The error returned by [Repository::new_branch_tracking()](crate::Repository::new_branch_tracking()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        AutoSetupConfig(#[from] crate::config::key::GenericErrorWithValue),
+        #[error(transparent)]
+        UpstreamName(#[from] super::branch_remote_ref_name::Error),
+        #[error(transparent)]
+        FindRemote(#[from] crate::remote::find::existing::Error),
+        #[error("The name of the branch on the remote was invalid")]
+        ValidateRemoteRefName(#[from] gix_validate::reference::name::Error),
+        #[error("The start point {start_point:?} is written by the fetch ref-specs of more than one remote")]
+        AmbiguousRemote { start_point: gix_ref::FullName },
+    }
+}
+
+///
+pub mod write_local_config {
+    /// The error returned when writing the repository-local configuration file, for instance by
+    /// [Repository::add_remote()](crate::Repository::add_remote()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        LockTimeoutConfiguration(#[from] crate::config::lock_timeout::Error),
+        #[error("Could not lock the local configuration file at '{}'", path.display())]
+        Lock {
+            path: std::path::PathBuf,
+            source: gix_lock::acquire::Error,
+        },
+        #[error("Could not write the local configuration file at '{}'", path.display())]
+        Write {
+            path: std::path::PathBuf,
+            source: std::io::Error,
+        },
+    }
+}
+
+///
+pub mod edit_remote_url {
+    use crate::bstr::BString;
+
+    /// The error returned by [Repository::set_remote_url()](crate::Repository::set_remote_url()),
+    /// [Repository::add_remote_push_url()](crate::Repository::add_remote_push_url()) and
+    /// [Repository::delete_remote_url()](crate::Repository::delete_remote_url()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Url(#[from] gix_url::parse::Error),
+        #[error("The remote named {name:?} does not exist")]
+        NotFound { name: BString },
+        #[error(
+            "The remote named {name:?} isn't configured in the repository-local configuration and can't be changed"
+        )]
+        NotInLocalConfig { name: BString },
+        #[error("The remote named {name:?} has no url {url:?}")]
+        UrlNotFound { name: BString, url: BString },
+        #[error("Refusing to delete all fetch urls of the remote named {name:?}")]
+        DeleteAllFetchUrls { name: BString },
+        #[error(transparent)]
+        WriteConfig(#[from] crate::repository::write_local_config::Error),
+        #[error(transparent)]
+        ApplyConfig(#[from] crate::config::Error),
+    }
+}
+
+///
+pub mod add_remote {
+    use crate::bstr::BString;
+
+    /// The error returned by [Repository::add_remote()](crate::Repository::add_remote()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Name(#[from] crate::remote::name::Error),
+        #[error(transparent)]
+        Url(#[from] gix_url::parse::Error),
+        #[error("A remote named {name:?} already exists")]
+        AlreadyExists { name: BString },
+        #[error(transparent)]
+        WriteConfig(#[from] crate::repository::write_local_config::Error),
+        #[error(transparent)]
+        ApplyConfig(#[from] crate::config::Error),
+        #[error(transparent)]
+        FindRemote(#[from] crate::remote::find::existing::Error),
+    }
+}
+
+///
+pub mod rename_remote {
+    use crate::bstr::BString;
+
+    /// The error returned by [Repository::rename_remote()](crate::Repository::rename_remote()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Name(#[from] crate::remote::name::Error),
+        #[error("The remote named {name:?} does not exist")]
+        NotFound { name: BString },
+        #[error("A remote named {name:?} already exists")]
+        AlreadyExists { name: BString },
+        #[error(
+            "The remote named {name:?} isn't configured in the repository-local configuration and can't be renamed"
+        )]
+        NotInLocalConfig { name: BString },
+        #[error(transparent)]
+        FindRemote(#[from] crate::remote::find::existing::Error),
+        #[error(transparent)]
+        InitReferenceIter(#[from] crate::reference::iter::Error),
+        #[error(transparent)]
+        PrefixedReferenceIter(#[from] crate::reference::iter::init::Error),
+        #[error("Could not obtain a remote-tracking reference")]
+        IterReference(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+        #[error("A renamed remote-tracking reference had an invalid name")]
+        InvalidRefName(#[from] gix_validate::reference::name::Error),
+        #[error(transparent)]
+        WriteConfig(#[from] crate::repository::write_local_config::Error),
+        #[error(transparent)]
+        ApplyConfig(#[from] crate::config::Error),
+        #[error(transparent)]
+        RenameReferences(#[from] crate::reference::edit::Error),
+    }
+}
+
+///
+pub mod remove_remote {
+    use crate::bstr::BString;
+
+    /// The error returned by [Repository::remove_remote()](crate::Repository::remove_remote()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The remote named {name:?} does not exist")]
+        NotFound { name: BString },
+        #[error(
+            "The remote named {name:?} isn't configured in the repository-local configuration and can't be removed"
+        )]
+        NotInLocalConfig { name: BString },
+        #[error(transparent)]
+        FindRemote(#[from] crate::remote::find::existing::Error),
+        #[error(transparent)]
+        InitReferenceIter(#[from] crate::reference::iter::Error),
+        #[error(transparent)]
+        PrefixedReferenceIter(#[from] crate::reference::iter::init::Error),
+        #[error("Could not obtain a remote-tracking reference")]
+        IterReference(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+        #[error(transparent)]
+        WriteConfig(#[from] crate::repository::write_local_config::Error),
+        #[error(transparent)]
+        ApplyConfig(#[from] crate::config::Error),
+        #[error(transparent)]
+        DeleteReferences(#[from] crate::reference::edit::Error),
+    }
+}
+
+///
+#[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+pub mod expand_refspecs {
+    use crate::bstr::BString;
+
+    /// The error returned by [Repository::expand_refspecs()](crate::Repository::expand_refspecs()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        MappingValidation(#[from] gix_refspec::match_group::validate::Error),
+        #[error(transparent)]
+        Push(#[from] crate::remote::push::prepare::Error),
+        #[error(transparent)]
+        FindReference(#[from] crate::reference::find::Error),
+    }
+
+    /// A concrete mapping from a source to a destination reference, as produced by expanding ref-specs
+    /// with [Repository::expand_refspecs()](crate::Repository::expand_refspecs()).
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Mapping {
+        /// The full name of the source reference, remote for fetches and local for pushes, or the object that is
+        /// fetched or pushed directly. It's `None` if the destination is deleted by a push.
+        pub source: Option<gix_refspec::match_group::Source>,
+        /// The object the source points to, if known.
+        pub source_id: Option<gix_hash::ObjectId>,
+        /// The full name of the reference that is written, local for fetches and remote for pushes,
+        /// or `None` if a fetch ref-spec has no destination.
+        pub destination: Option<BString>,
+        /// The object the destination currently points to, or `None` if it doesn't exist yet.
+        pub destination_id: Option<gix_hash::ObjectId>,
+        /// The index into the ref-specs that produced this mapping.
+        pub spec_index: usize,
+    }
+}
+
 ///
 #[cfg(feature = "attributes")]
 pub mod pathspec_defaults_ignore_case {
@@ -116,6 +116,27 @@ impl crate::Repository {
         }
     }
 
+    /// Return `true` for each of the given `ids` if it exists in the object database, in the same order as `ids`.
+    ///
+    /// This is faster than calling [`has_object()`](Self::has_object()) for each id, which is useful for connectivity checks
+    /// or to find out which of many objects are missing.
+    /// Like [`has_object()`](Self::has_object()), the empty tree is always considered present.
+    ///
+    /// # Performance
+    ///
+    /// The object database is refreshed at most once if ids are missing, which can still be avoided by using
+    /// [`repo.objects.refresh_never()`](gix_odb::store::Handle::refresh_never).
+    #[doc(alias = "exists", alias = "git2")]
+    pub fn has_objects(&self, ids: &[impl AsRef<gix_hash::oid>]) -> Vec<bool> {
+        let mut out = self.objects.contains_many(ids);
+        for (id, present) in ids.iter().zip(out.iter_mut()) {
+            if !*present && id.as_ref().to_owned().is_empty_tree() {
+                *present = true;
+            }
+        }
+        out
+    }
+
     /// Obtain information about an object without fully decoding it, or `None` if the object doesn't exist.
     ///
     /// Note that despite being cheaper than [`Self::try_find_object()`], there is still some effort traversing delta-chains.
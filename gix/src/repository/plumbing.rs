@@ -0,0 +1,84 @@
+use std::{
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{plumbing::explode_pack, Repository};
+
+impl Repository {
+    /// Write all objects of the pack at `pack_path`, which may also be the path to its index, as loose objects,
+    /// similar to `gix free pack explode` or `git unpack-objects`.
+    ///
+    /// Objects are written into the object database of this repository unless another
+    /// [destination](explode_pack::Options::destination) is configured. Each object is hashed while writing it and
+    /// compared to the id stored in the pack index, so corrupt packs are detected. Deltas are resolved within the pack only,
+    /// which is why thin packs can't be exploded.
+    pub fn explode_pack(
+        &self,
+        pack_path: &Path,
+        options: explode_pack::Options,
+        should_interrupt: &AtomicBool,
+    ) -> Result<explode_pack::Outcome, explode_pack::Error> {
+        use explode_pack::Error;
+        use gix_odb::Write;
+
+        let _span = gix_trace::coarse!("gix::explode_pack()", path = ?pack_path);
+        let bundle = gix_pack::Bundle::at(pack_path, self.object_hash())?;
+        let destination = options
+            .destination
+            .unwrap_or_else(|| self.objects.store_ref().path().to_owned());
+        let loose = gix_odb::loose::Store::at(&destination, self.object_hash());
+
+        let mut out = explode_pack::Outcome::default();
+        let mut buf = Vec::new();
+        let mut inflate = gix_features::zlib::Inflate::default();
+        for (idx, entry) in bundle.index.iter().enumerate() {
+            if should_interrupt.load(Ordering::Relaxed) {
+                return Err(Error::Interrupted);
+            }
+            let id = entry.oid;
+            let (data, _location) = bundle
+                .get_object_by_index(idx as u32, &mut buf, &mut inflate, &mut gix_pack::cache::Never)
+                .map_err(|err| Error::Decode { id, source: err })?;
+            out.objects += 1;
+            match data.kind {
+                gix_object::Kind::Commit => out.commits += 1,
+                gix_object::Kind::Tree => out.trees += 1,
+                gix_object::Kind::Blob => out.blobs += 1,
+                gix_object::Kind::Tag => out.tags += 1,
+            }
+            if !options.overwrite_existing && loose.contains(&id) {
+                continue;
+            }
+            let actual = loose.write_buf(data.kind, data.data).map_err(|err| Error::Write {
+                id,
+                destination: destination.clone(),
+                source: err,
+            })?;
+            if actual != id {
+                return Err(Error::ObjectHashMismatch { expected: id, actual });
+            }
+            out.written += 1;
+        }
+        Ok(out)
+    }
+
+    /// Verify the index of this repository's worktree, similar to `gix index verify`, and return information about it.
+    ///
+    /// This validates the checksum of the index file, the order of its entries and, with the help of the object database,
+    /// its extensions, like the cache-tree.
+    /// Note that this fails if there is no index.
+    #[cfg(feature = "index")]
+    pub fn verify_index(&self) -> Result<crate::plumbing::verify_index::Outcome, crate::plumbing::verify_index::Error> {
+        let _span = gix_trace::coarse!("gix::verify_index()");
+        let index = self.open_index()?;
+        index.verify_integrity()?;
+        index.verify_entries()?;
+        index.verify_extensions(true, &self.objects)?;
+        Ok(crate::plumbing::verify_index::Outcome {
+            version: index.version(),
+            entries: index.entries().len(),
+            checksum: index.checksum(),
+        })
+    }
+}
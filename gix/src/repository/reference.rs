@@ -4,7 +4,11 @@ use gix_ref::{
     FullName, PartialNameRef, Target,
 };
 
-use crate::{bstr::BString, ext::ReferenceExt, reference, Reference};
+use crate::{
+    bstr::{BString, ByteSlice},
+    ext::ReferenceExt,
+    reference, Reference,
+};
 
 /// Obtain and alter references comfortably
 impl crate::Repository {
@@ -125,6 +129,45 @@ impl crate::Repository {
         .attach(self))
     }
 
+    /// Let the symbolic reference `name`, like `HEAD` or `refs/remotes/origin/HEAD`, point to the reference `target`,
+    /// like `refs/heads/main`, adhering to `constraint` and writing `log_message` into the reflog along with the objects `name`
+    /// resolved to before and after, like `git symbolic-ref -m` would.
+    /// Note that nothing is logged if `target` doesn't exist yet, and that `HEAD` may only point to references in `refs/`.
+    ///
+    /// The symbolic reference is returned.
+    pub fn symbolic_reference<Name, TargetName, E, TE>(
+        &self,
+        name: Name,
+        target: TargetName,
+        constraint: PreviousValue,
+        log_message: impl Into<BString>,
+    ) -> Result<Reference<'_>, reference::symbolic::Error>
+    where
+        Name: TryInto<FullName, Error = E>,
+        gix_validate::reference::name::Error: From<E>,
+        TargetName: TryInto<FullName, Error = TE>,
+        gix_validate::reference::name::Error: From<TE>,
+    {
+        let name = name.try_into().map_err(gix_validate::reference::name::Error::from)?;
+        let target = target.try_into().map_err(gix_validate::reference::name::Error::from)?;
+        let (file_lock_fail, _) = self.config.lock_timeout()?;
+        let log_message = log_message.into();
+        let edit = self.refs.set_symbolic_reference(
+            name.as_ref(),
+            target.as_ref(),
+            constraint,
+            Some(log_message.as_bstr()),
+            self.committer().transpose()?,
+            file_lock_fail,
+        )?;
+        Ok(gix_ref::Reference {
+            name: edit.name,
+            target: Target::Symbolic(target),
+            peeled: None,
+        }
+        .attach(self))
+    }
+
     /// Edit a single reference as described in `edit`, and write reference logs as `log_committer`.
     ///
     /// One or more `RefEdit`s  are returned - symbolic reference splits can cause more edits to be performed. All edits have the previous
@@ -135,22 +178,51 @@ impl crate::Repository {
 
     /// Edit one or more references as described by their `edits`.
     /// Note that one can set the committer name for use in the ref-log by temporarily
-    /// [overriding the git-config][crate::Repository::config_snapshot_mut()].
+    /// [overriding the git-config][crate::Repository::config_snapshot_mut()], or pass it directly
+    /// with [`edit_references_as()`](Self::edit_references_as()).
     ///
     /// Returns all reference edits, which might be more than where provided due the splitting of symbolic references, and
     /// whose previous (_old_) values are the ones seen on in storage after the reference was locked.
     pub fn edit_references(
         &self,
         edits: impl IntoIterator<Item = RefEdit>,
+    ) -> Result<Vec<RefEdit>, reference::edit::Error> {
+        self.edit_references_as(edits, self.committer().transpose()?)
+    }
+
+    /// Edit one or more references as described by their `edits`, with `committer` as the identity to write into reference logs,
+    /// which is independent of the [configured committer](Self::committer()) used for new commits.
+    ///
+    /// Note that if `committer` is `None`, the edits will fail if a reference log has to be written.
+    ///
+    /// Returns all reference edits, which might be more than where provided due the splitting of symbolic references, and
+    /// whose previous (_old_) values are the ones seen on in storage after the reference was locked.
+    pub fn edit_references_as(
+        &self,
+        edits: impl IntoIterator<Item = RefEdit>,
+        committer: Option<gix_actor::SignatureRef<'_>>,
     ) -> Result<Vec<RefEdit>, reference::edit::Error> {
         let (file_lock_fail, packed_refs_lock_fail) = self.config.lock_timeout()?;
         self.refs
             .transaction()
             .prepare(edits, file_lock_fail, packed_refs_lock_fail)?
-            .commit(self.committer().transpose()?)
+            .commit(committer)
             .map_err(Into::into)
     }
 
+    /// Return the action to prefix reference log messages with instead of the one of the operation itself,
+    /// as configured by `gitoxide.core.reflogAction` or the `GIT_REFLOG_ACTION` environment variable.
+    ///
+    /// Tools like `git pull` set it so that the reference logs written by the operations they perform, like fetching,
+    /// show up as `pull: …`.
+    pub fn reflog_action(&self) -> Option<BString> {
+        self.config
+            .resolved
+            .string("gitoxide.core.reflogAction")
+            .filter(|action| !action.is_empty())
+            .map(std::borrow::Cow::into_owned)
+    }
+
     /// Return the repository head, an abstraction to help dealing with the `HEAD` reference.
     ///
     /// The `HEAD` reference can be in various states, for more information, the documentation of [`Head`][crate::Head].
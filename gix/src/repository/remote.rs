@@ -1,5 +1,18 @@
 #![allow(clippy::result_large_err)]
-use crate::{bstr::BStr, config, remote, remote::find, Remote};
+use std::borrow::Cow;
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    config,
+    config::cache::util::ApplyLeniency,
+    remote,
+    remote::find,
+    repository::{add_remote, edit_remote_url, remove_remote, rename_remote},
+    Remote,
+};
+
+#[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+use crate::repository::expand_refspecs;
 
 impl crate::Repository {
     /// Create a new remote available at the given `url`.
@@ -93,6 +106,542 @@ impl crate::Repository {
         })
     }
 
+    /// Add a remote with the given `name` that fetches from `url` to the repository-local configuration, similar to `git remote add`,
+    /// and return it.
+    ///
+    /// The remote is configured to fetch all branches into `refs/remotes/<name>/*`, and it's an error if a remote
+    /// of the same name already exists.
+    pub fn add_remote<'a, Url, E>(
+        &mut self,
+        name: impl Into<&'a BStr>,
+        url: Url,
+    ) -> Result<Remote<'_>, add_remote::Error>
+    where
+        Url: TryInto<gix_url::Url, Error = E>,
+        gix_url::parse::Error: From<E>,
+    {
+        let name = remote::name::validated(name.into())?;
+        if self.remote_names().contains(name.as_bstr()) {
+            return Err(add_remote::Error::AlreadyExists { name });
+        }
+        let url = url.try_into().map_err(|err| add_remote::Error::Url(err.into()))?;
+
+        let lock_mode = self
+            .config
+            .other_lock_timeout()
+            .map_err(crate::repository::write_local_config::Error::from)?;
+        let mut config = self.config_snapshot_mut();
+        {
+            let mut section = config
+                .new_section("remote", Some(Cow::Owned(name.clone())))
+                .expect("remote name is validated and 'remote' is acceptable");
+            section.push(
+                config::tree::Remote::URL
+                    .name
+                    .try_into()
+                    .expect("valid at compile time"),
+                Some(url.to_bstring().as_ref()),
+            );
+            section.push(
+                config::tree::Remote::FETCH
+                    .name
+                    .try_into()
+                    .expect("valid at compile time"),
+                Some(format!("+refs/heads/*:refs/remotes/{name}/*").as_str().into()),
+            );
+        }
+        let path = local_config_path(&config);
+        write_local_config(&config, &path, lock_mode)?;
+        config.commit()?;
+
+        Ok(self.find_remote(name.as_bstr())?)
+    }
+
+    /// Rename the remote with the given `name` to `new_name` in the repository-local configuration, similar to `git remote rename`,
+    /// and return the new names of all renamed references.
+    ///
+    /// This renames all `remote.<name>` sections and rewrites their fetch ref-specs to write into `refs/remotes/<new_name>/`,
+    /// and updates `branch.<branch>.remote`, `branch.<branch>.pushRemote` and `remote.pushDefault` if they refer to the remote.
+    /// All remote-tracking references below `refs/remotes/<name>/` that are destinations of the remote's fetch ref-specs
+    /// are moved to `refs/remotes/<new_name>/`, along with symbolic references pointing to them.
+    ///
+    /// Changes are written to the local configuration file and applied to the configuration of this instance.
+    /// Note that remotes configured outside the repository-local configuration can't be renamed and yield an error.
+    pub fn rename_remote<'a>(
+        &mut self,
+        name: impl Into<&'a BStr>,
+        new_name: impl Into<BString>,
+    ) -> Result<Vec<gix_ref::FullName>, rename_remote::Error> {
+        use gix_ref::transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog};
+
+        let name = name.into();
+        let new_name = remote::name::validated(new_name)?;
+        let names = self.remote_names();
+        if !names.contains(name) {
+            return Err(rename_remote::Error::NotFound { name: name.to_owned() });
+        }
+        if names.contains(new_name.as_bstr()) {
+            return Err(rename_remote::Error::AlreadyExists { name: new_name });
+        }
+        let own_specs = self.find_remote(name)?.fetch_specs;
+        let old_prefix = BString::from(format!("refs/remotes/{name}/"));
+        let new_prefix = BString::from(format!("refs/remotes/{new_name}/"));
+        let rename = |ref_name: &BStr| -> Option<BString> {
+            ref_name.strip_prefix(old_prefix.as_bytes()).map(|suffix| {
+                let mut renamed = new_prefix.clone();
+                renamed.push_str(suffix);
+                renamed
+            })
+        };
+
+        let message = BString::from(format!("remote: renamed {name} to {new_name}"));
+        let mut edits = Vec::new();
+        for reference in self.references()?.prefixed("refs/remotes/")? {
+            let reference = reference.map_err(rename_remote::Error::IterReference)?;
+            let ref_name = reference.name().as_bstr();
+            let Some(new_ref_name) =
+                rename(ref_name).filter(|_| own_specs.iter().any(|spec| is_destination_of(spec.to_ref(), ref_name)))
+            else {
+                continue;
+            };
+            let new_target = match &reference.inner.target {
+                gix_ref::Target::Symbolic(target) => match rename(target.as_bstr()) {
+                    Some(new_target) => gix_ref::Target::Symbolic(new_target.try_into()?),
+                    None => reference.inner.target.clone(),
+                },
+                target @ gix_ref::Target::Object(_) => target.clone(),
+            };
+            edits.push(RefEdit {
+                change: Change::Delete {
+                    expected: PreviousValue::MustExistAndMatch(reference.inner.target.clone()),
+                    log: RefLog::AndReference,
+                },
+                name: reference.inner.name.clone(),
+                deref: false,
+            });
+            edits.push(RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: false,
+                        message: message.clone(),
+                    },
+                    expected: PreviousValue::MustNotExist,
+                    new: new_target,
+                },
+                name: new_ref_name.try_into()?,
+                deref: false,
+            });
+        }
+
+        let lock_mode = self
+            .config
+            .other_lock_timeout()
+            .map_err(crate::repository::write_local_config::Error::from)?;
+        let mut config = self.config_snapshot_mut();
+        let mut renamed_section = false;
+        while config
+            .rename_section_filter(
+                "remote",
+                Some(name),
+                "remote",
+                Some(Cow::Owned(new_name.clone())),
+                is_local,
+            )
+            .is_ok()
+        {
+            renamed_section = true;
+        }
+        if !renamed_section {
+            config.forget();
+            return Err(rename_remote::Error::NotInLocalConfig { name: name.to_owned() });
+        }
+
+        let fetch_key = config::tree::Remote::FETCH.name;
+        let (old_destination, new_destination) = (format!(":{old_prefix}"), format!(":{new_prefix}"));
+        let mut edits_by_section = Vec::new();
+        for (section, id) in config.sections_and_ids_by_name("remote").into_iter().flatten() {
+            if !is_local(section.meta()) {
+                continue;
+            }
+            match section.header().subsection_name() {
+                Some(subsection) if subsection == new_name.as_bstr() => {
+                    let specs: Vec<BString> = section
+                        .values(fetch_key)
+                        .iter()
+                        .map(|spec| spec.replace(&old_destination, &new_destination).into())
+                        .collect();
+                    edits_by_section.push((id, fetch_key, Some(specs)));
+                }
+                None if section.value(config::tree::Remote::PUSH_DEFAULT.name).as_deref() == Some(name) => {
+                    edits_by_section.push((id, config::tree::Remote::PUSH_DEFAULT.name, None));
+                }
+                _ => {}
+            }
+        }
+        for (section, id) in config.sections_and_ids_by_name("branch").into_iter().flatten() {
+            if !is_local(section.meta()) {
+                continue;
+            }
+            for key in [
+                config::tree::Branch::REMOTE.name,
+                config::tree::Branch::PUSH_REMOTE.name,
+            ] {
+                if section.value(key).as_deref() == Some(name) {
+                    edits_by_section.push((id, key, None));
+                }
+            }
+        }
+        for (id, key, values) in edits_by_section {
+            let mut section = config.section_mut_by_id(id).expect("id was just obtained");
+            while section.remove(key).is_some() {}
+            for value in values.unwrap_or_else(|| vec![new_name.clone()]) {
+                section.push(key.try_into().expect("valid at compile time"), Some(value.as_bstr()));
+            }
+        }
+
+        let path = local_config_path(&config);
+        write_local_config(&config, &path, lock_mode)?;
+        config.commit()?;
+
+        Ok(self
+            .edit_references(edits)?
+            .into_iter()
+            .filter(|edit| matches!(edit.change, Change::Update { .. }))
+            .map(|edit| edit.name)
+            .collect())
+    }
+
+    /// Remove the remote with the given `name` from the repository-local configuration, similar to `git remote remove`,
+    /// and return the names of all deleted references.
+    ///
+    /// This removes all `remote.<name>` sections along with their URLs and ref-specs, clears `branch.<branch>.remote`
+    /// and `branch.<branch>.merge` for all branches that were configured to track the remote, and deletes all remote-tracking
+    /// references below `refs/remotes/` that are destinations of the remote's fetch ref-specs.
+    /// References that are also written by the fetch ref-specs of another remote are kept.
+    ///
+    /// Changes are written to the local configuration file and applied to the configuration of this instance.
+    /// Note that remotes configured outside the repository-local configuration can't be removed and yield an error.
+    pub fn remove_remote<'a>(
+        &mut self,
+        name: impl Into<&'a BStr>,
+    ) -> Result<Vec<gix_ref::FullName>, remove_remote::Error> {
+        use gix_ref::transaction::{Change, PreviousValue, RefEdit, RefLog};
+
+        let name = name.into();
+        if !self.remote_names().contains(name) {
+            return Err(remove_remote::Error::NotFound { name: name.to_owned() });
+        }
+        let own_specs = self.find_remote(name)?.fetch_specs;
+        let other_specs: Vec<_> = self
+            .remote_names()
+            .into_iter()
+            .filter(|other| other.as_bstr() != name)
+            .filter_map(|other| self.try_find_remote(other.as_bstr()).and_then(Result::ok))
+            .flat_map(|remote| remote.fetch_specs)
+            .collect();
+
+        let mut edits = Vec::new();
+        for reference in self.references()?.prefixed("refs/remotes/")? {
+            let reference = reference.map_err(remove_remote::Error::IterReference)?;
+            let ref_name = reference.name().as_bstr();
+            let writes_ref = |spec: &gix_refspec::RefSpec| is_destination_of(spec.to_ref(), ref_name);
+            if own_specs.iter().any(writes_ref) && !other_specs.iter().any(writes_ref) {
+                edits.push(RefEdit {
+                    change: Change::Delete {
+                        expected: PreviousValue::MustExistAndMatch(reference.inner.target.clone()),
+                        log: RefLog::AndReference,
+                    },
+                    name: reference.inner.name.clone(),
+                    deref: false,
+                });
+            }
+        }
+
+        let lock_mode = self
+            .config
+            .other_lock_timeout()
+            .map_err(crate::repository::write_local_config::Error::from)?;
+        let mut config = self.config_snapshot_mut();
+        let mut removed_section = false;
+        while config.remove_section_filter("remote", Some(name), is_local).is_some() {
+            removed_section = true;
+        }
+        if !removed_section {
+            config.forget();
+            return Err(remove_remote::Error::NotInLocalConfig { name: name.to_owned() });
+        }
+        let tracking_branch_ids: Vec<_> = config
+            .sections_and_ids_by_name("branch")
+            .into_iter()
+            .flatten()
+            .filter(|(section, _)| is_local(section.meta()) && section.value("remote").as_deref() == Some(name))
+            .map(|(_, id)| id)
+            .collect();
+        for id in tracking_branch_ids {
+            let mut section = config.section_mut_by_id(id).expect("id was just obtained");
+            for key in ["remote", "merge"] {
+                while section.remove(key).is_some() {}
+            }
+        }
+
+        let path = local_config_path(&config);
+        write_local_config(&config, &path, lock_mode)?;
+        config.commit()?;
+
+        Ok(self.edit_references(edits)?.into_iter().map(|edit| edit.name).collect())
+    }
+
+    /// Replace all urls of the remote with the given `name` for use in `direction` with `url`, similar to `git remote set-url`,
+    /// and persist the change in the local configuration file.
+    ///
+    /// For [pushing](remote::Direction::Push), this sets `remote.<name>.pushUrl`, and `remote.<name>.url` otherwise.
+    /// The `url` is stored as given, so `url.<base>.insteadOf` and `url.<base>.pushInsteadOf` rewrites keep applying to it
+    /// when the remote is loaded again.
+    pub fn set_remote_url<'a, Url, E>(
+        &mut self,
+        name: impl Into<&'a BStr>,
+        url: Url,
+        direction: remote::Direction,
+    ) -> Result<(), edit_remote_url::Error>
+    where
+        Url: TryInto<gix_url::Url, Error = E>,
+        gix_url::parse::Error: From<E>,
+    {
+        let url = url.try_into().map_err(|err| edit_remote_url::Error::Url(err.into()))?;
+        let key = url_key(direction);
+        self.edit_local_remote_section(name.into(), |config, ids| {
+            let (last_id, other_ids) = ids.split_last().expect("at least one section");
+            for id in other_ids {
+                let mut section = config.section_mut_by_id(*id).expect("valid id");
+                while section.remove(key).is_some() {}
+            }
+            let mut section = config.section_mut_by_id(*last_id).expect("valid id");
+            for _ in 1..section.values(key).len() {
+                section.remove(key);
+            }
+            section.set(
+                key.try_into().expect("valid at compile time"),
+                url.to_bstring().as_ref(),
+            );
+            Ok(())
+        })
+    }
+
+    /// Add `url` as another url to push to for the remote with the given `name`, similar to `git remote set-url --add --push`,
+    /// and persist the change in the local configuration file.
+    ///
+    /// Note that [`Remote`] instances only use the last of multiple configured push urls.
+    pub fn add_remote_push_url<'a, Url, E>(
+        &mut self,
+        name: impl Into<&'a BStr>,
+        url: Url,
+    ) -> Result<(), edit_remote_url::Error>
+    where
+        Url: TryInto<gix_url::Url, Error = E>,
+        gix_url::parse::Error: From<E>,
+    {
+        let url = url.try_into().map_err(|err| edit_remote_url::Error::Url(err.into()))?;
+        self.edit_local_remote_section(name.into(), |config, ids| {
+            let last_id = ids.last().expect("at least one section");
+            config.section_mut_by_id(*last_id).expect("valid id").push(
+                url_key(remote::Direction::Push)
+                    .try_into()
+                    .expect("valid at compile time"),
+                Some(url.to_bstring().as_ref()),
+            );
+            Ok(())
+        })
+    }
+
+    /// Delete all urls of the remote with the given `name` for use in `direction` that are equal to `url`, similar to
+    /// `git remote set-url --delete`, and persist the change in the local configuration file.
+    ///
+    /// Configured urls also match if they are equal to `url` after applying `url.<base>.insteadOf` or `url.<base>.pushInsteadOf`
+    /// rewrites. It's an error if no url matches, or if all fetch urls would be deleted.
+    pub fn delete_remote_url<'a, Url, E>(
+        &mut self,
+        name: impl Into<&'a BStr>,
+        url: Url,
+        direction: remote::Direction,
+    ) -> Result<(), edit_remote_url::Error>
+    where
+        Url: TryInto<gix_url::Url, Error = E>,
+        gix_url::parse::Error: From<E>,
+    {
+        let url = url
+            .try_into()
+            .map_err(|err| edit_remote_url::Error::Url(err.into()))?
+            .to_bstring();
+        let name = name.into();
+        let key = url_key(direction);
+        let rewrite = self.config.url_rewrite().clone();
+        let matches_url = |value: &BStr| {
+            value == url
+                || gix_url::parse(value)
+                    .ok()
+                    .and_then(|value| rewrite.longest(&value, direction))
+                    .map_or(false, |rewritten| rewritten == url)
+        };
+        self.edit_local_remote_section(name, |config, ids| {
+            let mut deleted_any = false;
+            let mut num_remaining = 0;
+            let mut sections_to_edit = Vec::new();
+            for id in ids {
+                let section = config.section_mut_by_id(*id).expect("valid id");
+                let values = section.values(key);
+                let remaining: Vec<_> = values
+                    .iter()
+                    .filter(|value| !matches_url(value))
+                    .map(|value| value.clone().into_owned())
+                    .collect();
+                num_remaining += remaining.len();
+                if remaining.len() != values.len() {
+                    deleted_any = true;
+                    sections_to_edit.push((*id, remaining));
+                }
+            }
+            if !deleted_any {
+                return Err(edit_remote_url::Error::UrlNotFound {
+                    name: name.to_owned(),
+                    url: url.clone(),
+                });
+            }
+            if direction == remote::Direction::Fetch && num_remaining == 0 {
+                return Err(edit_remote_url::Error::DeleteAllFetchUrls { name: name.to_owned() });
+            }
+            for (id, remaining) in sections_to_edit {
+                let mut section = config.section_mut_by_id(id).expect("valid id");
+                while section.remove(key).is_some() {}
+                for value in remaining {
+                    section.push(key.try_into().expect("valid at compile time"), Some(value.as_bstr()));
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Call `edit` with the local configuration and the ids of all local sections of the remote named `name`,
+    /// and write the local configuration file and apply the changes if it succeeds.
+    fn edit_local_remote_section(
+        &mut self,
+        name: &BStr,
+        edit: impl FnOnce(
+            &mut gix_config::File<'static>,
+            &[gix_config::file::SectionId],
+        ) -> Result<(), edit_remote_url::Error>,
+    ) -> Result<(), edit_remote_url::Error> {
+        if !self.remote_names().contains(name) {
+            return Err(edit_remote_url::Error::NotFound { name: name.to_owned() });
+        }
+        let lock_mode = self
+            .config
+            .other_lock_timeout()
+            .map_err(crate::repository::write_local_config::Error::from)?;
+        let mut config = self.config_snapshot_mut();
+        let ids: Vec<_> = config
+            .sections_and_ids_by_name("remote")
+            .into_iter()
+            .flatten()
+            .filter(|(section, _)| is_local(section.meta()) && section.header().subsection_name() == Some(name))
+            .map(|(_, id)| id)
+            .collect();
+        if ids.is_empty() {
+            config.forget();
+            return Err(edit_remote_url::Error::NotInLocalConfig { name: name.to_owned() });
+        }
+        if let Err(err) = edit(&mut config, &ids) {
+            config.forget();
+            return Err(err);
+        }
+        let path = local_config_path(&config);
+        write_local_config(&config, &path, lock_mode)?;
+        config.commit()?;
+        Ok(())
+    }
+
+    /// Expand `specs` against our references and `remote_refs`, the references advertised by a remote, to learn which
+    /// references a fetch or push in `direction` would write, without connecting to the remote.
+    ///
+    /// Only the ref-specs matching `direction` are used, and the returned mappings are in the order of matching.
+    ///
+    /// * **fetch** - `remote_refs` are matched against `specs`, and destinations are looked up among our references.
+    ///   Conflicting destinations are an error, just like they would be when fetching.
+    /// * **push** - our references are matched against `specs`, and partial destinations are qualified just like
+    ///   `git push` would, by looking them up among `remote_refs`. Deletions and pushes of matching branches
+    ///   are expanded as well, and each destination is written at most once.
+    #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+    pub fn expand_refspecs(
+        &self,
+        specs: &[gix_refspec::RefSpec],
+        direction: remote::Direction,
+        remote_refs: &[gix_protocol::handshake::Ref],
+    ) -> Result<Vec<expand_refspecs::Mapping>, expand_refspecs::Error> {
+        use gix_refspec::match_group::{Item, Source, SourceRef};
+
+        use expand_refspecs::Mapping;
+
+        match direction {
+            remote::Direction::Fetch => {
+                let null = gix_hash::ObjectId::null(self.object_hash());
+                let (outcome, _fixes) =
+                    gix_refspec::MatchGroup::from_fetch_specs(specs.iter().map(gix_refspec::RefSpec::to_ref))
+                        .match_remotes(remote_refs.iter().map(|r| {
+                            let (full_ref_name, target, object) = r.unpack();
+                            Item {
+                                full_ref_name,
+                                target: target.unwrap_or(&null),
+                                object,
+                            }
+                        }))
+                        .validated()?;
+                let mut out = Vec::with_capacity(outcome.mappings.len());
+                for mapping in outcome.mappings {
+                    let source_id = match mapping.lhs {
+                        SourceRef::ObjectId(id) => Some(id),
+                        SourceRef::FullName(_) => mapping
+                            .item_index
+                            .and_then(|idx| remote_refs[idx].unpack().1)
+                            .map(ToOwned::to_owned),
+                    };
+                    let destination = mapping.rhs.map(std::borrow::Cow::into_owned);
+                    let destination_id = match destination.as_ref() {
+                        Some(name) => self
+                            .try_find_reference(name.as_bstr())?
+                            .and_then(|mut r| r.follow_to_object().ok())
+                            .map(crate::Id::detach),
+                        None => None,
+                    };
+                    out.push(Mapping {
+                        source: Some(mapping.lhs.to_owned()),
+                        source_id,
+                        destination,
+                        destination_id,
+                        spec_index: mapping.spec_index,
+                    });
+                }
+                Ok(out)
+            }
+            remote::Direction::Push => {
+                let updates = crate::remote::connection::push::update::compute(self, specs, remote_refs, false)?;
+                Ok(updates
+                    .into_iter()
+                    .map(|update| Mapping {
+                        source: match (update.local, update.new) {
+                            (Some(name), _) => Some(Source::FullName(name)),
+                            (None, Some(id)) => Some(Source::ObjectId(id)),
+                            (None, None) => None,
+                        },
+                        source_id: update.new,
+                        destination: Some(update.remote),
+                        destination_id: update.old,
+                        spec_index: update.spec_index,
+                    })
+                    .collect())
+            }
+        }
+    }
+
     /// Similar to [`try_find_remote()`][Self::try_find_remote()], but removes a failure mode if rewritten URLs turn out to be invalid
     /// as it skips rewriting them.
     /// Use this in conjunction with [`Remote::rewrite_urls()`] to non-destructively apply the rules and keep the failed urls unchanged.
@@ -182,6 +731,26 @@ impl crate::Repository {
             Some(Err(err)) => return Some(Err(err)),
             None => Default::default(),
         };
+        let mut config_bool = |key: &'static config::tree::keys::Boolean| {
+            config
+                .boolean_filter(format!("remote.{}.{}", name_or_url, key.name), &mut filter)
+                .map(|value| key.enrich_error(value))
+                .transpose()
+                .with_leniency(self.config.lenient_config)
+                .map_err(find::Error::from)
+        };
+        let prune = match config_bool(&config::tree::Remote::PRUNE) {
+            Ok(v) => v,
+            Err(err) => return Some(Err(err)),
+        };
+        let prune_tags = match config_bool(&config::tree::Remote::PRUNE_TAGS) {
+            Ok(v) => v,
+            Err(err) => return Some(Err(err)),
+        };
+        let mirror = match config_bool(&config::tree::Remote::MIRROR) {
+            Ok(v) => v.unwrap_or_default(),
+            Err(err) => return Some(Err(err)),
+        };
 
         match (url, fetch_specs, push_url, push_specs) {
             (None, None, None, None) => None,
@@ -217,6 +786,9 @@ impl crate::Repository {
                         push_specs,
                         rewrite_urls,
                         fetch_tags,
+                        prune,
+                        prune_tags,
+                        mirror,
                         self,
                     )
                     .map_err(Into::into),
@@ -225,3 +797,62 @@ impl crate::Repository {
         }
     }
 }
+
+/// Return `true` if `name` is written by the destination of the fetch ref-`spec`, which may contain a single glob.
+fn is_destination_of(spec: gix_refspec::RefSpecRef<'_>, name: &BStr) -> bool {
+    let Some(destination) = spec.destination() else {
+        return false;
+    };
+    match destination.find_byte(b'*') {
+        Some(pos) => {
+            let (prefix, suffix) = (&destination[..pos], &destination[pos + 1..]);
+            name.len() >= prefix.len() + suffix.len() && name.starts_with(prefix) && name.ends_with(suffix)
+        }
+        None => destination == name,
+    }
+}
+
+fn url_key(direction: remote::Direction) -> &'static str {
+    match direction {
+        remote::Direction::Fetch => config::tree::Remote::URL.name,
+        remote::Direction::Push => config::tree::Remote::PUSH_URL.name,
+    }
+}
+
+fn is_local(meta: &gix_config::file::Metadata) -> bool {
+    meta.source == gix_config::Source::Local
+}
+
+fn local_config_path(config: &gix_config::File<'static>) -> std::path::PathBuf {
+    config
+        .meta()
+        .path
+        .clone()
+        .expect("local configuration always has a path")
+}
+
+/// Write all sections of `config` that were read from the local configuration file back to the file at `path`,
+/// holding its lock which is acquired with `lock_mode`.
+fn write_local_config(
+    config: &gix_config::File<'static>,
+    path: &std::path::Path,
+    lock_mode: gix_lock::acquire::Fail,
+) -> Result<(), crate::repository::write_local_config::Error> {
+    use std::io::Write;
+
+    use crate::repository::write_local_config::Error;
+    let mut lock = gix_lock::File::acquire_to_update_resource(path, lock_mode, None).map_err(|source| Error::Lock {
+        source,
+        path: path.to_owned(),
+    })?;
+    let write_err = |source| Error::Write {
+        source,
+        path: path.to_owned(),
+    };
+    config
+        .write_to_filter(&mut lock, |section| is_local(section.meta()))
+        .and_then(|_| lock.flush())
+        .map_err(write_err)?;
+    lock.commit().map_err(|err| write_err(err.error))?;
+    Ok(())
+}
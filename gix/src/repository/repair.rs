@@ -0,0 +1,307 @@
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use gix_hash::ObjectId;
+
+use crate::{
+    bstr::{BString, ByteSlice},
+    repair, Repository,
+};
+
+impl Repository {
+    /// Perform best-effort repairs of common kinds of corruption as enabled in `options`, using `progress` and checking
+    /// `should_interrupt` to stop the operation.
+    ///
+    /// Routines are run in an order that makes the most of previous repairs, i.e. loose objects are checked before
+    /// pack indices are regenerated and before `packed-refs` and `info/refs` are written.
+    /// The returned [outcome](repair::Outcome) describes all changes that were made, and is empty if nothing needed repair.
+    ///
+    /// Note that this operation assumes that no other process is writing to the repository at the same time.
+    pub fn repair<P>(
+        &self,
+        mut progress: P,
+        should_interrupt: &AtomicBool,
+        options: repair::Options,
+    ) -> Result<repair::Outcome, repair::Error>
+    where
+        P: gix_features::progress::NestedProgress,
+        P::SubProgress: 'static,
+    {
+        self.repair_inner(&mut progress, should_interrupt, options)
+    }
+
+    fn repair_inner(
+        &self,
+        progress: &mut dyn crate::DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        options: repair::Options,
+    ) -> Result<repair::Outcome, repair::Error> {
+        let _span = gix_trace::coarse!("gix::repair()");
+        let mut out = repair::Outcome::default();
+        if options.loose_objects {
+            progress.set_name("check loose objects".into());
+            out.removed_loose_objects = remove_corrupt_loose_objects(self, should_interrupt)?;
+        }
+        if options.pack_indices {
+            progress.set_name("check pack indices".into());
+            out.pack_indices = regenerate_pack_indices(self, progress, should_interrupt)?;
+        }
+        if options.packed_refs {
+            progress.set_name("check packed-refs".into());
+            out.packed_refs = rebuild_packed_refs(self)?;
+        }
+        if options.info_refs {
+            out.info_refs = create_info_refs_if_missing(self)?;
+        }
+        Ok(out)
+    }
+}
+
+/// The name of the directory in the common git directory into which corrupt loose objects are moved.
+const QUARANTINE_DIR_NAME: &str = "corrupt-objects";
+
+fn io_err(path: &Path) -> impl FnOnce(std::io::Error) -> repair::Error + '_ {
+    move |source| repair::Error::Io {
+        source,
+        path: path.to_owned(),
+    }
+}
+
+fn remove_corrupt_loose_objects(
+    repo: &Repository,
+    should_interrupt: &AtomicBool,
+) -> Result<Vec<repair::RemovedLooseObject>, repair::Error> {
+    use gix_odb::loose::find::Error as FindError;
+
+    let store = gix_odb::loose::Store::at(repo.objects.store_ref().path(), repo.object_hash());
+    let quarantine_dir = repo.common_dir().join(QUARANTINE_DIR_NAME);
+    let mut buf = Vec::new();
+    let mut removed = Vec::new();
+    for id in store.iter().filter_map(Result::ok) {
+        if should_interrupt.load(Ordering::Relaxed) {
+            return Err(repair::Error::Interrupted);
+        }
+        let problem = match store.try_find(&id, &mut buf) {
+            Ok(Some(obj)) => {
+                let actual = gix_object::compute_hash(repo.object_hash(), obj.kind, obj.data);
+                if actual != id {
+                    repair::LooseObjectProblem::HashMismatch { actual }
+                } else if obj.decode().is_err() {
+                    repair::LooseObjectProblem::Undecodable
+                } else {
+                    continue;
+                }
+            }
+            // The object was removed in the meantime.
+            Ok(None) => continue,
+            // Inflating data that was read already is reported as IO error, but it's a problem of the data.
+            Err(
+                FindError::DecompressFile { .. }
+                | FindError::SizeMismatch { .. }
+                | FindError::Decode(_)
+                | FindError::Io { action: "deflate", .. },
+            ) => repair::LooseObjectProblem::Unreadable,
+            // Failing to read an object says nothing about its data, so it must not be touched.
+            Err(err @ (FindError::Io { .. } | FindError::OutOfMemory { .. })) => {
+                return Err(repair::Error::ReadLooseObject(err))
+            }
+        };
+        let path = store.object_path(&id);
+        std::fs::create_dir_all(&quarantine_dir).map_err(io_err(&quarantine_dir))?;
+        let quarantine_path = quarantine_dir.join(id.to_hex().to_string());
+        std::fs::rename(&path, &quarantine_path).map_err(io_err(&path))?;
+        removed.push(repair::RemovedLooseObject {
+            id,
+            path,
+            quarantine_path,
+            problem,
+        });
+    }
+    Ok(removed)
+}
+
+fn regenerate_pack_indices(
+    repo: &Repository,
+    progress: &mut dyn crate::DynNestedProgress,
+    should_interrupt: &AtomicBool,
+) -> Result<Vec<repair::PackIndex>, repair::Error> {
+    let pack_dir = repo.objects.store_ref().path().join("pack");
+    let pack_paths: Vec<_> = match std::fs::read_dir(&pack_dir) {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().map_or(false, |ext| ext == "pack"))
+            .collect(),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(io_err(&pack_dir)(err)),
+    };
+
+    // Keep files that existed before are protecting packs for a reason, and must survive even if a pack is rewritten.
+    let existing_keep_paths: Vec<_> = pack_paths
+        .iter()
+        .map(|path| path.with_extension("keep"))
+        .filter(|path| path.is_file())
+        .collect();
+    let mut out = Vec::new();
+    for pack_path in pack_paths {
+        let index_path = pack_path.with_extension("idx");
+        if index_belongs_to_pack(repo, &pack_path, &index_path) {
+            continue;
+        }
+        if should_interrupt.load(Ordering::Relaxed) {
+            return Err(repair::Error::Interrupted);
+        }
+        match std::fs::remove_file(&index_path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(io_err(&index_path)(err)),
+        }
+        let pack = std::fs::File::open(&pack_path).map_err(io_err(&pack_path))?;
+        let bundle = gix_pack::Bundle::write_to_directory(
+            &mut std::io::BufReader::new(pack),
+            Some(&pack_dir),
+            progress,
+            should_interrupt,
+            None::<gix_object::find::Never>,
+            gix_pack::bundle::write::Options {
+                thread_limit: None,
+                index_version: Default::default(),
+                // Salvage as many objects as possible from truncated or otherwise corrupt packs.
+                iteration_mode: gix_pack::data::input::Mode::Restore,
+                object_hash: repo.object_hash(),
+            },
+        )
+        .map_err(|source| repair::Error::WritePackIndex {
+            source,
+            path: pack_path.clone(),
+        })?;
+
+        if let Some(keep_path) = bundle.keep_path.filter(|path| !existing_keep_paths.contains(path)) {
+            std::fs::remove_file(&keep_path).map_err(io_err(&keep_path))?;
+        }
+        let Some(new_index_path) = bundle.index_path else {
+            // Nothing could be salvaged, so the pack is useless.
+            std::fs::remove_file(&pack_path).map_err(io_err(&pack_path))?;
+            continue;
+        };
+        if bundle.data_path.as_ref() != Some(&pack_path) {
+            // The pack data was corrupt and a new pack with all salvaged objects was written.
+            std::fs::remove_file(&pack_path).map_err(io_err(&pack_path))?;
+        }
+        out.push(repair::PackIndex {
+            pack_path,
+            index_path: new_index_path,
+            num_objects: bundle.index.num_objects,
+        });
+    }
+    Ok(out)
+}
+
+/// Compare the pack checksum recorded in the index trailer with the trailer of the pack, without hashing either file.
+fn index_belongs_to_pack(repo: &Repository, pack_path: &Path, index_path: &Path) -> bool {
+    let (Ok(pack), Ok(index)) = (
+        gix_pack::data::File::at(pack_path, repo.object_hash()),
+        gix_pack::index::File::at(index_path, repo.object_hash()),
+    ) else {
+        return false;
+    };
+    index.pack_checksum() == pack.checksum()
+}
+
+fn rebuild_packed_refs(repo: &Repository) -> Result<Option<repair::PackedRefs>, repair::Error> {
+    let is_intact = match repo.refs.open_packed_buffer() {
+        Ok(None) => return Ok(None),
+        Ok(Some(buffer)) => buffer.iter().map_or(false, |mut iter| iter.all(|r| r.is_ok())),
+        Err(_) => false,
+    };
+    if is_intact {
+        return Ok(None);
+    }
+
+    let path = repo.refs.packed_refs_path();
+    let data = std::fs::read(&path).map_err(io_err(&path))?;
+    let mut refs = BTreeMap::<BString, ObjectId>::new();
+    let mut num_dropped_lines = 0;
+    for line in data.lines() {
+        if line.starts_with(b"#") || line.starts_with(b"^") || line.trim().is_empty() {
+            // Headers and peeled values are recomputed.
+            continue;
+        }
+        let parsed = line.split_once_str(b" ").and_then(|(hex, name)| {
+            let id = ObjectId::from_hex(hex).ok()?;
+            let name = gix_ref::FullName::try_from(name.as_bstr()).ok()?;
+            Some((name.as_bstr().to_owned(), id))
+        });
+        match parsed {
+            Some((name, id)) => {
+                refs.insert(name, id);
+            }
+            None => num_dropped_lines += 1,
+        }
+    }
+    let num_kept = refs.len();
+
+    let mut num_loose = 0;
+    for reference in repo.refs.loose_iter().map_err(io_err(repo.refs.git_dir()))? {
+        let Ok(reference) = reference else { continue };
+        if let gix_ref::Target::Object(id) = reference.target {
+            refs.insert(reference.name.as_bstr().to_owned(), id);
+            num_loose += 1;
+        }
+    }
+
+    let (_, packed_refs_lock_fail) = repo.config.lock_timeout()?;
+    let mut lock = gix_lock::File::acquire_to_update_resource(&path, packed_refs_lock_fail, None)?;
+    writeln!(lock, "# pack-refs with: peeled fully-peeled sorted ").map_err(io_err(&path))?;
+    for (name, id) in &refs {
+        writeln!(lock, "{id} {name}").map_err(io_err(&path))?;
+        if let Some(peeled) = peeled_tag_target(repo, *id)? {
+            writeln!(lock, "^{peeled}").map_err(io_err(&path))?;
+        }
+    }
+    lock.commit()?;
+
+    Ok(Some(repair::PackedRefs {
+        num_kept,
+        num_dropped_lines,
+        num_loose,
+    }))
+}
+
+/// Return the object that `id` ultimately points to if it is an annotated tag.
+fn peeled_tag_target(repo: &Repository, id: ObjectId) -> Result<Option<ObjectId>, repair::Error> {
+    Ok(match repo.try_find_object(id)? {
+        Some(obj) if obj.kind == gix_object::Kind::Tag => Some(obj.peel_tags_to_end()?.id),
+        _ => None,
+    })
+}
+
+fn create_info_refs_if_missing(repo: &Repository) -> Result<Option<PathBuf>, repair::Error> {
+    let path = repo.common_dir().join("info").join("refs");
+    if path.exists() {
+        return Ok(None);
+    }
+    let mut refs = BTreeMap::<BString, ObjectId>::new();
+    for reference in repo.references()?.all()? {
+        let reference = reference?;
+        // Like `git update-server-info`, but symbolic refs are skipped as they can't be fetched via dumb transports anyway.
+        if let Some(id) = reference.target().try_id() {
+            refs.insert(reference.name().as_bstr().to_owned(), id.to_owned());
+        }
+    }
+    let mut buf = Vec::new();
+    for (name, id) in &refs {
+        writeln!(buf, "{id}\t{name}").expect("write to memory works");
+        if let Some(peeled) = peeled_tag_target(repo, *id)? {
+            writeln!(buf, "{peeled}\t{name}^{{}}").expect("write to memory works");
+        }
+    }
+    let dir = path.parent().expect("has parent");
+    std::fs::create_dir_all(dir).map_err(io_err(dir))?;
+    std::fs::write(&path, buf).map_err(io_err(&path))?;
+    Ok(Some(path))
+}
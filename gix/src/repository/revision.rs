@@ -114,4 +114,40 @@ impl crate::Repository {
     ) -> revision::walk::Platform<'_> {
         revision::walk::Platform::new(tips, self)
     }
+
+    /// Return all objects along with the names of the references pointing to them, similar to `git log --decorate`.
+    ///
+    /// See [`decorations_cached()`](Self::decorations_cached()) for a version that avoids peeling annotated tags
+    /// if no reference changed since the previous invocation.
+    pub fn decorations(&self) -> Result<revision::Decorations, revision::decorations::Error> {
+        let refs = revision::decorations::direct_refs(self)?;
+        revision::decorations::compute(self, revision::decorations::fingerprint(self, &refs), refs)
+    }
+
+    /// Like [`decorations()`](Self::decorations()), but load them from the cache file at `cache_path`
+    /// if they were computed from exactly the references that are present now, or compute them and rewrite the cache otherwise.
+    ///
+    /// Any change to the references, like creating, updating or deleting one, invalidates the cache.
+    /// An unreadable or corrupted cache is treated as if it didn't exist.
+    /// Note that the cache is only ever written to `cache_path`, whose directory must exist, and which is up to the caller
+    /// to choose and to clean up.
+    pub fn decorations_cached(
+        &self,
+        cache_path: impl AsRef<std::path::Path>,
+    ) -> Result<revision::Decorations, revision::decorations::Error> {
+        use revision::decorations;
+        let _span = gix_trace::coarse!("gix::Repository::decorations_cached()");
+        let refs = decorations::direct_refs(self)?;
+        let fingerprint = decorations::fingerprint(self, &refs);
+        let cache_path = cache_path.as_ref();
+        if let Some(cached) = std::fs::read(cache_path)
+            .ok()
+            .and_then(|data| decorations::parse_cache(&data, fingerprint, self.object_hash()))
+        {
+            return Ok(cached);
+        }
+        let out = decorations::compute(self, fingerprint, refs)?;
+        decorations::write_cache(self, cache_path, &out)?;
+        Ok(out)
+    }
 }
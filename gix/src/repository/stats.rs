@@ -0,0 +1,93 @@
+use std::path::Path;
+
+use crate::{stats, Repository};
+
+impl Repository {
+    /// Obtain a snapshot of statistics about objects, packs, references and the index of this repository, along with the
+    /// time of the last maintenance, for monitoring purposes.
+    ///
+    /// Everything is obtained by looking at the file system only, without decoding objects or the index, so this is
+    /// cheap enough to be run periodically for many repositories.
+    /// Objects in alternates are not counted.
+    pub fn stats(&self) -> Result<stats::Snapshot, stats::Error> {
+        let _span = gix_trace::coarse!("gix::Repository::stats()");
+        let objects_dir = self.objects.store_ref().path();
+        let mut out = stats::Snapshot::default();
+
+        let loose = gix_odb::loose::Store::at(objects_dir, self.object_hash());
+        for id in loose.iter().filter_map(Result::ok) {
+            let path = loose.object_path(&id);
+            if let Some(metadata) = metadata(&path)? {
+                out.loose_objects.count += 1;
+                out.loose_objects.size_in_bytes += metadata.len();
+            }
+        }
+
+        let pack_dir = objects_dir.join("pack");
+        let entries = match std::fs::read_dir(&pack_dir) {
+            Ok(entries) => entries.filter_map(Result::ok).map(|entry| entry.path()).collect(),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(io_err(&pack_dir)(err)),
+        };
+        for path in entries {
+            let Some(metadata) = metadata(&path)? else { continue };
+            match path.extension().and_then(|ext| ext.to_str()) {
+                Some("pack") => {
+                    out.packs.count += 1;
+                    out.packs.size_in_bytes += metadata.len();
+                }
+                Some("idx") => {
+                    out.packs.index_size_in_bytes += metadata.len();
+                    out.packs.object_count +=
+                        u64::from(gix_pack::index::File::at(&path, self.object_hash())?.num_objects());
+                }
+                Some("keep") => out.packs.kept += 1,
+                _ => {}
+            }
+        }
+
+        for reference in self.refs.loose_iter().map_err(io_err(self.refs.git_dir()))? {
+            if reference.is_ok() {
+                out.refs.loose += 1;
+            }
+        }
+        if let Some(packed) = self.refs.cached_packed_buffer()? {
+            out.refs.packed = packed.iter()?.flatten().count() as u64;
+        }
+
+        out.index_size_in_bytes = metadata(&self.index_path())?.map(|metadata| metadata.len());
+
+        let info_dir = objects_dir.join("info");
+        let multi_pack_index = pack_dir.join("multi-pack-index");
+        out.packs.has_multi_pack_index = metadata(&multi_pack_index)?.is_some();
+        for path in [
+            self.common_dir().join("packed-refs"),
+            info_dir.join("commit-graph"),
+            info_dir.join("commit-graphs").join("commit-graph-chain"),
+            multi_pack_index,
+        ] {
+            let modified = metadata(&path)?
+                .and_then(|metadata| metadata.modified().ok())
+                .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|since_epoch| since_epoch.as_secs() as gix_date::SecondsSinceUnixEpoch);
+            out.last_maintenance = out.last_maintenance.max(modified);
+        }
+        Ok(out)
+    }
+}
+
+/// Return the metadata of `path`, or `None` if it doesn't exist.
+fn metadata(path: &Path) -> Result<Option<std::fs::Metadata>, stats::Error> {
+    match std::fs::metadata(path) {
+        Ok(metadata) => Ok(Some(metadata)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(io_err(path)(err)),
+    }
+}
+
+fn io_err(path: &Path) -> impl FnOnce(std::io::Error) -> stats::Error + '_ {
+    move |source| stats::Error::Io {
+        source,
+        path: path.to_owned(),
+    }
+}
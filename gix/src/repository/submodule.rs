@@ -73,6 +73,9 @@ impl Repository {
     }
 
     /// Return the list of available submodules, or `None` if there is no submodule configuration.
+    ///
+    /// Submodules with [invalid names](gix_validate::submodule::name()) are skipped, just like `git` does, as their name
+    /// is used as location of their repository within `.git/modules` which could otherwise be escaped.
     #[doc(alias = "git2")]
     pub fn submodules(&self) -> Result<Option<impl Iterator<Item = crate::Submodule<'_>>>, submodule::modules::Error> {
         let modules = match self.modules()? {
@@ -84,6 +87,7 @@ impl Repository {
             shared_state
                 .modules
                 .names()
+                .filter(|name| gix_validate::submodule::name(name).is_ok())
                 .map(ToOwned::to_owned)
                 .collect::<Vec<_>>()
                 .into_iter()
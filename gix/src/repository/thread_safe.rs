@@ -4,6 +4,11 @@ mod access {
         pub fn to_thread_local(&self) -> crate::Repository {
             self.into()
         }
+
+        /// The time spent in each phase of opening the repository, useful to learn what makes opening it slow.
+        pub fn open_metrics(&self) -> crate::open::Metrics {
+            self.open_metrics
+        }
     }
 }
 
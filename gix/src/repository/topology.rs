@@ -0,0 +1,169 @@
+use gix_hashtable::HashMap;
+use gix_object::FindExt;
+
+use crate::{topology, Repository};
+
+impl Repository {
+    /// Analyse the graph of all commits in the object database to find root commits, tips along with the references
+    /// pointing to them, and subgraphs of commits that aren't reachable from any reference or the `HEAD` of any worktree.
+    ///
+    /// This is useful to visualize a repository or to validate it after an import, and is computed in a single pass over
+    /// all commits without traversing history from each reference.
+    ///
+    /// Note that objects of alternate object databases are considered as well, and that references which can't be peeled
+    /// to a commit, for instance because they point to missing objects, are ignored.
+    pub fn topology(&self) -> Result<topology::Outcome, topology::Error> {
+        let _span = gix_trace::coarse!("gix::topology()");
+        let mut ids = Vec::new();
+        let mut index_by_id = HashMap::default();
+        for id in self.objects.iter()?.filter_map(Result::ok) {
+            if index_by_id.contains_key(&id) || !self.find_header(id)?.kind().is_commit() {
+                continue;
+            }
+            index_by_id.insert(id, ids.len());
+            ids.push(id);
+        }
+
+        let mut buf = Vec::new();
+        let mut parents = Vec::with_capacity(ids.len());
+        let mut has_children = vec![false; ids.len()];
+        let mut boundary = Vec::new();
+        for id in &ids {
+            let mut commit_parents = Vec::new();
+            for parent_id in self.objects.find_commit_iter(id, &mut buf)?.parent_ids() {
+                match index_by_id.get(&parent_id) {
+                    Some(&parent) => {
+                        has_children[parent] = true;
+                        commit_parents.push(parent);
+                    }
+                    None => {
+                        if boundary.last() != Some(id) {
+                            boundary.push(*id);
+                        }
+                    }
+                }
+            }
+            parents.push(commit_parents);
+        }
+        boundary.sort();
+
+        let mut names_by_commit: std::collections::HashMap<usize, Vec<gix_ref::FullName>> = Default::default();
+        let mut anchors = Vec::new();
+        for reference in self.references()?.all()? {
+            let mut reference = reference?;
+            let Some(&commit) = reference
+                .peel_to_id_in_place()
+                .ok()
+                .and_then(|id| index_by_id.get(&id.detach()))
+            else {
+                continue;
+            };
+            names_by_commit
+                .entry(commit)
+                .or_default()
+                .push(reference.name().to_owned());
+            anchors.push(commit);
+        }
+        anchors.extend(
+            self.head_id()
+                .ok()
+                .and_then(|id| index_by_id.get(&id.detach()).copied()),
+        );
+        for proxy in self.worktrees().map_err(topology::Error::ListWorktrees)? {
+            let repo = proxy.into_repo_with_possibly_inaccessible_worktree()?;
+            anchors.extend(
+                repo.head_id()
+                    .ok()
+                    .and_then(|id| index_by_id.get(&id.detach()).copied()),
+            );
+        }
+
+        let mut reachable = vec![false; ids.len()];
+        while let Some(commit) = anchors.pop() {
+            if std::mem::replace(&mut reachable[commit], true) {
+                continue;
+            }
+            anchors.extend(parents[commit].iter().filter(|&&parent| !reachable[parent]));
+        }
+
+        // Connect all unreachable commits with their unreachable parents to find the subgraphs they form.
+        let mut component: Vec<usize> = (0..ids.len()).collect();
+        for (commit, commit_parents) in parents.iter().enumerate().filter(|(commit, _)| !reachable[*commit]) {
+            for &parent in commit_parents.iter().filter(|&&parent| !reachable[parent]) {
+                let (a, b) = (find_root(&mut component, commit), find_root(&mut component, parent));
+                component[a.max(b)] = a.min(b);
+            }
+        }
+        let mut subgraph_by_component: std::collections::HashMap<usize, topology::Subgraph> = Default::default();
+        for commit in (0..ids.len()).filter(|&commit| !reachable[commit]) {
+            let subgraph = subgraph_by_component
+                .entry(find_root(&mut component, commit))
+                .or_insert_with(|| topology::Subgraph {
+                    tips: Vec::new(),
+                    roots: Vec::new(),
+                    attached_to: Vec::new(),
+                    num_commits: 0,
+                });
+            subgraph.num_commits += 1;
+            if !has_children[commit] {
+                subgraph.tips.push(ids[commit]);
+            }
+            if parents[commit].is_empty() && boundary.binary_search(&ids[commit]).is_err() {
+                subgraph.roots.push(ids[commit]);
+            }
+            subgraph.attached_to.extend(
+                parents[commit]
+                    .iter()
+                    .filter(|&&parent| reachable[parent])
+                    .map(|&parent| ids[parent]),
+            );
+        }
+        let mut orphaned: Vec<_> = subgraph_by_component
+            .into_values()
+            .map(|mut subgraph| {
+                subgraph.tips.sort();
+                subgraph.roots.sort();
+                subgraph.attached_to.sort();
+                subgraph.attached_to.dedup();
+                subgraph
+            })
+            .collect();
+        orphaned.sort_by(|a, b| a.tips.cmp(&b.tips));
+
+        let mut roots: Vec<_> = (0..ids.len())
+            .filter(|&commit| parents[commit].is_empty())
+            .map(|commit| ids[commit])
+            .filter(|id| boundary.binary_search(id).is_err())
+            .collect();
+        roots.sort();
+        let mut tips: Vec<_> = (0..ids.len())
+            .filter(|&commit| !has_children[commit])
+            .map(|commit| topology::Tip {
+                id: ids[commit],
+                references: {
+                    let mut names = names_by_commit.remove(&commit).unwrap_or_default();
+                    names.sort();
+                    names
+                },
+            })
+            .collect();
+        tips.sort_by_key(|tip| tip.id);
+
+        Ok(topology::Outcome {
+            num_commits: ids.len(),
+            roots,
+            tips,
+            boundary,
+            orphaned,
+        })
+    }
+}
+
+/// Return the representative of the set that `commit` belongs to, compressing the path to it along the way.
+fn find_root(component: &mut [usize], mut commit: usize) -> usize {
+    while component[commit] != commit {
+        component[commit] = component[component[commit]];
+        commit = component[commit];
+    }
+    commit
+}
@@ -0,0 +1,176 @@
+//! Associate objects with the names of the references pointing to them, like `git log --decorate` does,
+//! optionally backed by a cache file on disk that the caller chooses.
+#![allow(clippy::empty_docs)]
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use gix_hash::{oid, ObjectId};
+use gix_ref::FullName;
+
+use crate::bstr::ByteSlice;
+
+/// The header of the cache file, followed by a space and the fingerprint of the references it was computed from.
+const CACHE_HEADER: &str = "# gix decorations v1";
+
+/// The error returned by [`Repository::decorations()`](crate::Repository::decorations())
+/// and [`Repository::decorations_cached()`](crate::Repository::decorations_cached()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    InitRefsIterator(#[from] crate::reference::iter::init::Error),
+    #[error(transparent)]
+    InitRefsIteratorPlatform(#[from] crate::reference::iter::Error),
+    #[error(transparent)]
+    IterateRefs(#[from] gix_ref::file::iter::loose_then_packed::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::Error),
+    #[error(transparent)]
+    PeelObject(#[from] crate::object::find::existing::Error),
+    #[error("Could not write the decorations cache at '{}'", path.display())]
+    Io { source: std::io::Error, path: PathBuf },
+    #[error("Failed to lock the decorations cache for writing")]
+    LockCache(#[from] gix_lock::acquire::Error),
+    #[error("Failed to commit the decorations cache")]
+    CommitCache(#[from] gix_lock::commit::Error<gix_lock::File>),
+    #[error(transparent)]
+    LockTimeoutConfiguration(#[from] crate::config::lock_timeout::Error),
+}
+
+/// A mapping of objects to the full names of all references pointing to them.
+///
+/// Annotated tags decorate both the tag object and the object they ultimately point to.
+/// Symbolic references like `HEAD` aren't included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decorations {
+    /// A hash over the names and targets of all references the decorations were computed from.
+    pub(crate) fingerprint: ObjectId,
+    pub(crate) names_by_id: BTreeMap<ObjectId, Vec<FullName>>,
+}
+
+impl Decorations {
+    /// Return the names of all references pointing to `id`, sorted by name, or an empty slice if there is none.
+    pub fn get(&self, id: impl AsRef<oid>) -> &[FullName] {
+        self.names_by_id.get(id.as_ref()).map_or(&[], Vec::as_slice)
+    }
+
+    /// Return an iterator over all decorated objects and the names of the references pointing to them, sorted by object id.
+    pub fn iter(&self) -> impl Iterator<Item = (&oid, &[FullName])> + '_ {
+        self.names_by_id
+            .iter()
+            .map(|(id, names)| (id.as_ref(), names.as_slice()))
+    }
+
+    /// Return the amount of decorated objects.
+    pub fn len(&self) -> usize {
+        self.names_by_id.len()
+    }
+
+    /// Return `true` if there is no decorated object.
+    pub fn is_empty(&self) -> bool {
+        self.names_by_id.is_empty()
+    }
+
+    /// Return a hash over the names and targets of all references these decorations were computed from.
+    ///
+    /// It changes whenever a reference is created, deleted or updated, which is used to invalidate a cache on disk.
+    pub fn fingerprint(&self) -> ObjectId {
+        self.fingerprint
+    }
+}
+
+/// Write `decorations` to the cache file at `path`, replacing the previous cache.
+pub(crate) fn write_cache(repo: &crate::Repository, path: &Path, decorations: &Decorations) -> Result<(), Error> {
+    let io_err = |source| Error::Io {
+        source,
+        path: path.to_owned(),
+    };
+    let lock_mode = repo.config.other_lock_timeout()?;
+    let mut lock = gix_lock::File::acquire_to_update_resource(path, lock_mode, None)?;
+    writeln!(lock, "{CACHE_HEADER} {}", decorations.fingerprint).map_err(io_err)?;
+    for (id, names) in &decorations.names_by_id {
+        for name in names {
+            writeln!(lock, "{id} {name}", name = name.as_bstr()).map_err(io_err)?;
+        }
+    }
+    lock.commit()?;
+    Ok(())
+}
+
+/// Return the names and targets of all references that point to an object, sorted by name.
+pub(crate) fn direct_refs(repo: &crate::Repository) -> Result<Vec<(FullName, ObjectId)>, Error> {
+    let mut out = Vec::new();
+    let refs = repo.refs.iter()?;
+    for reference in refs.all().map_err(crate::reference::iter::init::Error::Io)? {
+        let reference = crate::Reference::from_ref(reference?, repo);
+        if let gix_ref::Target::Object(id) = reference.inner.target {
+            out.push((reference.inner.name, id));
+        }
+    }
+    out.sort();
+    Ok(out)
+}
+
+/// Return a hash over the names and targets of `refs`.
+pub(crate) fn fingerprint(repo: &crate::Repository, refs: &[(FullName, ObjectId)]) -> ObjectId {
+    let mut buf = Vec::new();
+    for (name, id) in refs {
+        buf.extend_from_slice(id.as_bytes());
+        buf.extend_from_slice(name.as_bstr());
+        buf.push(b'\n');
+    }
+    gix_object::compute_hash(repo.object_hash(), gix_object::Kind::Blob, &buf)
+}
+
+/// Compute the decorations for `refs`, peeling annotated tags to their target.
+pub(crate) fn compute(
+    repo: &crate::Repository,
+    fingerprint: ObjectId,
+    refs: Vec<(FullName, ObjectId)>,
+) -> Result<Decorations, Error> {
+    let mut names_by_id = BTreeMap::<_, Vec<_>>::new();
+    for (name, id) in refs {
+        if let Some(obj) = repo.try_find_object(id)? {
+            if obj.kind == gix_object::Kind::Tag {
+                let peeled = obj.peel_tags_to_end()?.id;
+                names_by_id.entry(peeled).or_default().push(name.clone());
+            }
+        }
+        names_by_id.entry(id).or_default().push(name);
+    }
+    for names in names_by_id.values_mut() {
+        names.sort();
+        names.dedup();
+    }
+    Ok(Decorations {
+        fingerprint,
+        names_by_id,
+    })
+}
+
+/// Parse the cache in `data`, returning `None` if it's corrupt or if it wasn't computed for references with `fingerprint`.
+pub(crate) fn parse_cache(data: &[u8], fingerprint: ObjectId, object_hash: gix_hash::Kind) -> Option<Decorations> {
+    let mut lines = data.lines();
+    let stored = lines
+        .next()?
+        .strip_prefix(CACHE_HEADER.as_bytes())?
+        .strip_prefix(b" ")
+        .and_then(|hex| ObjectId::from_hex(hex).ok())?;
+    if stored != fingerprint {
+        return None;
+    }
+    let mut names_by_id = BTreeMap::<_, Vec<_>>::new();
+    for line in lines {
+        let (hex, name) = line.split_at(line.find_byte(b' ')?);
+        let id = ObjectId::from_hex(hex).ok().filter(|id| id.kind() == object_hash)?;
+        let name = FullName::try_from(name[1..].as_bstr()).ok()?;
+        names_by_id.entry(id).or_default().push(name);
+    }
+    Some(Decorations {
+        fingerprint,
+        names_by_id,
+    })
+}
@@ -0,0 +1,196 @@
+//! Export the commits of a revision walk as a graph in the DOT or JSON format, to visualize and debug history.
+#![allow(clippy::empty_docs)]
+use std::{collections::HashSet, io::Write};
+
+use gix_hash::ObjectId;
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    revision::{walk, Decorations},
+};
+
+/// The error returned by [`write()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Walk(#[from] walk::iter::Error),
+    #[error(transparent)]
+    FindCommit(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error("Could not write the graph")]
+    Io(#[from] std::io::Error),
+}
+
+/// The format to export a graph in.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    /// The `graphviz` DOT language, with edges pointing from each commit to its parents.
+    ///
+    /// Parents that aren't part of the exported commits are drawn as dashed boundary nodes.
+    Dot,
+    /// A JSON object with a `commits` array, listing each commit with its `id`, `parents` and `labels`, along with
+    /// its `title` if enabled.
+    Json,
+}
+
+/// Options for use in [`write()`].
+#[derive(Debug, Copy, Clone)]
+pub struct Options<'a> {
+    /// The format to write the graph in.
+    pub format: Format,
+    /// The maximum amount of commits to export, or `None` to export all commits of the walk.
+    pub limit: Option<usize>,
+    /// If set, commits are labelled with the short names of all references pointing to them.
+    pub decorations: Option<&'a Decorations>,
+    /// If `true`, commits are labelled with the first line of their message, which requires loading each commit.
+    pub titles: bool,
+}
+
+impl Default for Options<'_> {
+    fn default() -> Self {
+        Options {
+            format: Format::Dot,
+            limit: None,
+            decorations: None,
+            titles: false,
+        }
+    }
+}
+
+struct Node {
+    id: ObjectId,
+    parents: Vec<ObjectId>,
+    labels: Vec<BString>,
+    title: Option<BString>,
+}
+
+/// Write all commits of `walk` as graph to `out`, configured by `options`, and return the amount of exported commits.
+///
+/// The range of commits is determined by `walk`, which is typically created with
+/// [`Repository::rev_walk()`](crate::Repository::rev_walk()) and bounded with
+/// [`with_pruned()`](walk::Platform::with_pruned()), like `repo.rev_walk([tip]).with_pruned([base]).all()?` for `base..tip`.
+pub fn write(walk: crate::revision::Walk<'_>, options: Options<'_>, out: &mut dyn Write) -> Result<usize, Error> {
+    let mut nodes = Vec::new();
+    for info in walk.take(options.limit.unwrap_or(usize::MAX)) {
+        let info = info?;
+        let id = info.id;
+        let labels = options.decorations.map_or_else(Vec::new, |decorations| {
+            decorations
+                .get(id)
+                .iter()
+                .map(|name| name.shorten().to_owned())
+                .collect()
+        });
+        let title = if options.titles {
+            Some(info.object()?.message()?.title.trim().as_bstr().to_owned())
+        } else {
+            None
+        };
+        nodes.push(Node {
+            id,
+            parents: info.parent_ids.iter().copied().collect(),
+            labels,
+            title,
+        });
+    }
+
+    match options.format {
+        Format::Dot => write_dot(&nodes, out)?,
+        Format::Json => write_json(&nodes, out)?,
+    }
+    Ok(nodes.len())
+}
+
+fn write_dot(nodes: &[Node], out: &mut dyn Write) -> std::io::Result<()> {
+    let exported: HashSet<_> = nodes.iter().map(|node| node.id).collect();
+    let mut boundary = HashSet::new();
+    writeln!(out, "digraph commits {{")?;
+    writeln!(out, "  node [shape=box];")?;
+    for node in nodes {
+        let mut label = BString::from(node.id.to_hex_with_len(7).to_string());
+        for name in &node.labels {
+            label.push(b'\n');
+            label.extend_from_slice(name);
+        }
+        if let Some(title) = &node.title {
+            label.push(b'\n');
+            label.extend_from_slice(title);
+        }
+        writeln!(out, "  \"{}\" [label=\"{}\"];", node.id, escape_dot(label.as_bstr()))?;
+    }
+    for node in nodes {
+        for parent in &node.parents {
+            if !exported.contains(parent) && boundary.insert(*parent) {
+                writeln!(
+                    out,
+                    "  \"{parent}\" [label=\"{}\", style=dashed];",
+                    parent.to_hex_with_len(7)
+                )?;
+            }
+            writeln!(out, "  \"{}\" -> \"{parent}\";", node.id)?;
+        }
+    }
+    writeln!(out, "}}")
+}
+
+fn write_json(nodes: &[Node], out: &mut dyn Write) -> std::io::Result<()> {
+    write!(out, "{{\"commits\":[")?;
+    for (index, node) in nodes.iter().enumerate() {
+        if index != 0 {
+            write!(out, ",")?;
+        }
+        write!(out, "{{\"id\":\"{}\",\"parents\":[", node.id)?;
+        for (index, parent) in node.parents.iter().enumerate() {
+            write!(out, "{}\"{parent}\"", if index == 0 { "" } else { "," })?;
+        }
+        write!(out, "],\"labels\":[")?;
+        for (index, name) in node.labels.iter().enumerate() {
+            write!(
+                out,
+                "{}\"{}\"",
+                if index == 0 { "" } else { "," },
+                escape_json(name.as_bstr())
+            )?;
+        }
+        write!(out, "]")?;
+        if let Some(title) = &node.title {
+            write!(out, ",\"title\":\"{}\"", escape_json(title.as_bstr()))?;
+        }
+        write!(out, "}}")?;
+    }
+    writeln!(out, "]}}")
+}
+
+fn escape_dot(input: &BStr) -> String {
+    let mut out = String::with_capacity(input.len());
+    for c in input.to_str_lossy().chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn escape_json(input: &BStr) -> String {
+    let mut out = String::with_capacity(input.len());
+    for c in input.to_str_lossy().chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                use std::fmt::Write;
+                write!(out, "\\u{:04x}", c as u32).expect("writing to a string can't fail");
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
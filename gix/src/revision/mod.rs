@@ -9,6 +9,14 @@ pub use gix_revision as plumbing;
 pub mod walk;
 pub use walk::iter_impl::Walk;
 
+pub mod decorations;
+pub use decorations::Decorations;
+
+pub mod export;
+
+#[cfg(feature = "blob-diff")]
+pub mod path_history;
+
 ///
 #[cfg(feature = "revision")]
 pub mod spec;
@@ -0,0 +1,243 @@
+//! An index of all commits touching each path, including rename edges, to quickly answer file-history queries
+//! similar to `git log --follow -- <path>`.
+#![allow(clippy::empty_docs)]
+use std::collections::BTreeMap;
+
+use gix_hash::ObjectId;
+use gix_object::TreeRefIter;
+
+use crate::bstr::{BStr, BString, ByteSlice};
+
+/// The error returned by [`Index::update()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    DiffOptions(#[from] crate::diff::options::init::Error),
+    #[error(transparent)]
+    CreateResourceCache(#[from] crate::repository::diff_resource_cache::Error),
+    #[error(transparent)]
+    TraverseCommits(#[from] gix_traverse::commit::simple::Error),
+    #[error(transparent)]
+    FindCommit(#[from] crate::object::find::existing::with_conversion::Error),
+    #[error(transparent)]
+    FindParent(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] crate::object::commit::Error),
+    #[error(transparent)]
+    TreeDiff(#[from] gix_diff::tree_with_rewrites::Error),
+}
+
+/// The way a commit changed a path.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Change {
+    /// The path was added.
+    Addition,
+    /// The path was removed, which includes being the source of a rename.
+    Deletion,
+    /// The content or mode at the path changed.
+    Modification,
+    /// The path was created by renaming or copying `source`.
+    Rewrite {
+        /// The path the content was taken from.
+        source: BString,
+        /// If `true`, `source` still exists after the commit.
+        copy: bool,
+    },
+}
+
+/// A commit touching a path.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Entry {
+    /// The commit which changed the path compared to its first parent.
+    pub commit: ObjectId,
+    /// The time at which `commit` was committed, in seconds since the unix epoch.
+    pub commit_time: gix_date::SecondsSinceUnixEpoch,
+    /// How the path was changed.
+    pub change: Change,
+}
+
+/// A mapping of paths to all commits that touched them, which can be [updated](Index::update()) incrementally
+/// as new commits are added.
+///
+/// Each commit is compared to its first parent only, or to the empty tree if it has no parent, which means that
+/// changes brought in by merges are attributed to the commits on the merged branch. Only non-tree entries are indexed,
+/// and renames are tracked as configured in `diff.renames`.
+#[derive(Debug, Clone, Default)]
+pub struct Index {
+    /// All indexed commits along with the order in which they were indexed, with newer commits having higher numbers.
+    commits: gix_hashtable::HashMap<ObjectId, usize>,
+    by_path: BTreeMap<BString, Vec<Entry>>,
+}
+
+impl Index {
+    /// Index all commits reachable from `tips` that weren't indexed yet, and return the amount of newly indexed commits.
+    ///
+    /// As ancestors of indexed commits are indexed as well, the traversal stops at the first commit that is already known,
+    /// which makes keeping the index up-to-date with, for instance, a moving branch very cheap.
+    ///
+    /// Note that the index should be discarded if an error occurs as it may be partially updated.
+    pub fn update(
+        &mut self,
+        repo: &crate::Repository,
+        tips: impl IntoIterator<Item = impl Into<ObjectId>>,
+    ) -> Result<usize, Error> {
+        let _span = gix_trace::coarse!("gix::revision::path_history::Index::update()");
+        let new_commits = gix_traverse::commit::Simple::filtered(
+            tips.into_iter()
+                .map(Into::into)
+                .filter(|id| !self.commits.contains_key(id)),
+            &repo.objects,
+            |id| !self.commits.contains_key(id),
+        )
+        .map(|info| info.map(|info| info.id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+        let mut cache = repo.diff_resource_cache_for_tree_diff()?;
+        let options: gix_diff::tree_with_rewrites::Options =
+            crate::diff::Options::from_configuration(&repo.config)?.into();
+        let empty_tree = repo.empty_tree();
+        let mut state = Default::default();
+        let mut changes = Vec::new();
+        for id in &new_commits {
+            let commit = repo.find_commit(*id)?;
+            let commit_time = commit.time()?.seconds;
+            let new_tree = commit.tree()?;
+            let old_tree = match commit.parent_ids().next() {
+                Some(parent) => Some(parent.object()?.into_commit().tree()?),
+                None => None,
+            };
+            gix_diff::tree_with_rewrites(
+                TreeRefIter::from_bytes(&old_tree.as_ref().unwrap_or(&empty_tree).data),
+                TreeRefIter::from_bytes(&new_tree.data),
+                &mut cache,
+                &mut state,
+                &repo.objects,
+                |change| -> Result<_, std::convert::Infallible> {
+                    if !change.entry_mode().is_tree() {
+                        changes.push(change.into_owned());
+                    }
+                    Ok(gix_diff::tree_with_rewrites::Action::Continue)
+                },
+                options.clone(),
+            )?;
+            cache.clear_resource_cache_keep_allocation();
+
+            let mut record = |path: BString, change: Change| {
+                self.by_path.entry(path).or_default().push(Entry {
+                    commit: *id,
+                    commit_time,
+                    change,
+                });
+            };
+            for change in changes.drain(..) {
+                use gix_diff::tree_with_rewrites::Change::*;
+                match change {
+                    Addition { location, .. } => record(location, Change::Addition),
+                    Deletion { location, .. } => record(location, Change::Deletion),
+                    Modification { location, .. } => record(location, Change::Modification),
+                    Rewrite {
+                        source_location,
+                        location,
+                        copy,
+                        ..
+                    } => {
+                        if !copy {
+                            record(source_location.clone(), Change::Deletion);
+                        }
+                        record(
+                            location,
+                            Change::Rewrite {
+                                source: source_location,
+                                copy,
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        // Commits are traversed from the tips to their ancestors, so the last one is the oldest.
+        let num_indexed = self.commits.len();
+        self.commits.extend(
+            new_commits
+                .iter()
+                .rev()
+                .enumerate()
+                .map(|(idx, id)| (*id, num_indexed + idx)),
+        );
+        if !new_commits.is_empty() {
+            let commits = &self.commits;
+            for entries in self.by_path.values_mut() {
+                entries.sort_by_key(|entry| std::cmp::Reverse(recency(commits, entry)));
+            }
+        }
+        Ok(new_commits.len())
+    }
+}
+
+/// Access
+impl Index {
+    /// Return all commits that changed `path`, most recent first, without following renames.
+    pub fn history(&self, path: &BStr) -> &[Entry] {
+        self.by_path.get(path).map_or(&[], Vec::as_slice)
+    }
+
+    /// Return all commits that changed `path`, most recent first, along with the path they changed, following renames
+    /// to the paths `path` was previously known as like `git log --follow` does.
+    pub fn follow(&self, path: &BStr) -> Vec<(BString, Entry)> {
+        let mut out = Vec::new();
+        let mut path = path.to_owned();
+        let mut before = None;
+        loop {
+            let mut renamed_from = None;
+            for entry in self.history(path.as_ref()) {
+                if let Some(rename) = before {
+                    if recency(&self.commits, entry) >= rename {
+                        continue;
+                    }
+                }
+                out.push((path.clone(), entry.clone()));
+                if let Change::Rewrite { source, copy: false } = &entry.change {
+                    renamed_from = Some((source.clone(), recency(&self.commits, entry)));
+                    break;
+                }
+            }
+            match renamed_from {
+                Some((source, rename)) => {
+                    path = source;
+                    before = Some(rename);
+                }
+                None => break,
+            }
+        }
+        out
+    }
+
+    /// Return `true` if `commit` is part of this index.
+    pub fn contains(&self, commit: impl AsRef<gix_hash::oid>) -> bool {
+        self.commits.contains_key(commit.as_ref())
+    }
+
+    /// Return the amount of indexed commits.
+    pub fn num_commits(&self) -> usize {
+        self.commits.len()
+    }
+
+    /// Return an iterator over all paths that were touched by any indexed commit, in lexicographical order.
+    pub fn paths(&self) -> impl Iterator<Item = &BStr> + '_ {
+        self.by_path.keys().map(|path| path.as_bstr())
+    }
+}
+
+/// A key to order entries by, with more recent ones comparing greater, using the indexing order in `commits`
+/// for commits with the same commit time.
+fn recency(
+    commits: &gix_hashtable::HashMap<ObjectId, usize>,
+    entry: &Entry,
+) -> (gix_date::SecondsSinceUnixEpoch, usize) {
+    (
+        entry.commit_time,
+        commits.get(&entry.commit).copied().unwrap_or_default(),
+    )
+}
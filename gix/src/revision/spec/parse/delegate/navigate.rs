@@ -312,39 +312,57 @@ impl delegate::Navigate for Delegate<'_> {
         };
         self.unset_disambiguate_call();
         match self.repo.index() {
-            Ok(index) => match index.entry_by_path_and_stage(path, stage) {
-                Some(entry) => {
-                    self.objs[self.idx]
-                        .get_or_insert_with(HashSet::default)
-                        .insert(entry.id);
-
-                    self.paths[self.idx] = Some((
-                        path.to_owned(),
-                        entry
-                            .mode
-                            .to_tree_entry_mode()
-                            .unwrap_or(gix_object::tree::EntryKind::Blob.into()),
-                    ));
-                    Some(())
-                }
-                None => {
-                    let stage_hint = [Stage::Unconflicted, Stage::Base, Stage::Ours]
+            Ok(mut index) => {
+                if index.entry_by_path_and_stage(path, stage).is_none()
+                    && index
+                        .entries()
                         .iter()
-                        .filter(|our_stage| **our_stage != stage)
-                        .find_map(|stage| index.entry_index_by_path_and_stage(path, *stage).map(|_| *stage));
-                    let exists = self
-                        .repo
-                        .work_dir()
-                        .map_or(false, |root| root.join(gix_path::from_bstr(path)).exists());
-                    self.err.push(Error::IndexLookup {
-                        desired_path: path.into(),
-                        desired_stage: stage,
-                        exists,
-                        stage_hint,
-                    });
-                    None
+                        .any(|e| e.mode.is_sparse() && path.starts_with(e.path(&index)))
+                {
+                    // The path is hidden in a sparse directory entry, so expand only the directories leading up to it.
+                    let mut expanded = gix_index::File::clone(&index);
+                    if let Err(err) =
+                        expanded.expand_sparse_directories_by(&self.repo.objects, |dir| path.starts_with(dir))
+                    {
+                        self.err.push(err.into());
+                        return None;
+                    }
+                    index = crate::worktree::Index::new(gix_fs::FileSnapshot::new(expanded));
                 }
-            },
+                match index.entry_by_path_and_stage(path, stage) {
+                    Some(entry) => {
+                        self.objs[self.idx]
+                            .get_or_insert_with(HashSet::default)
+                            .insert(entry.id);
+
+                        self.paths[self.idx] = Some((
+                            path.to_owned(),
+                            entry
+                                .mode
+                                .to_tree_entry_mode()
+                                .unwrap_or(gix_object::tree::EntryKind::Blob.into()),
+                        ));
+                        Some(())
+                    }
+                    None => {
+                        let stage_hint = [Stage::Unconflicted, Stage::Base, Stage::Ours]
+                            .iter()
+                            .filter(|our_stage| **our_stage != stage)
+                            .find_map(|stage| index.entry_index_by_path_and_stage(path, *stage).map(|_| *stage));
+                        let exists = self
+                            .repo
+                            .work_dir()
+                            .map_or(false, |root| root.join(gix_path::from_bstr(path)).exists());
+                        self.err.push(Error::IndexLookup {
+                            desired_path: path.into(),
+                            desired_stage: stage,
+                            exists,
+                            stage_hint,
+                        });
+                        None
+                    }
+                }
+            }
             Err(err) => {
                 self.err.push(err.into());
                 None
@@ -115,6 +115,8 @@ pub enum Error {
     #[error(transparent)]
     Index(#[from] crate::worktree::open_index::Error),
     #[error(transparent)]
+    ExpandSparseIndex(#[from] gix_index::sparse::expand::Error),
+    #[error(transparent)]
     RevWalkIterInit(#[from] crate::reference::iter::init::Error),
     #[error(transparent)]
     RevWalkAllReferences(#[from] gix_ref::packed::buffer::open::Error),
@@ -0,0 +1,74 @@
+//! A snapshot of repository statistics, suitable for monitoring many repositories at once.
+#![allow(clippy::empty_docs)]
+use std::path::PathBuf;
+
+/// Statistics about loose objects, i.e. objects stored in their own file.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LooseObjects {
+    /// The amount of loose objects.
+    pub count: u64,
+    /// The size of all loose object files on disk.
+    pub size_in_bytes: u64,
+}
+
+/// Statistics about pack files in the repository's own object database, excluding alternates.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Packs {
+    /// The amount of pack files.
+    pub count: u64,
+    /// The amount of pack files that are marked to be kept with a `.keep` file.
+    pub kept: u64,
+    /// The amount of objects in all packs, as listed in their indices.
+    pub object_count: u64,
+    /// The size of all pack files on disk.
+    pub size_in_bytes: u64,
+    /// The size of all pack index files on disk.
+    pub index_size_in_bytes: u64,
+    /// If `true`, there is a multi-pack index.
+    pub has_multi_pack_index: bool,
+}
+
+/// Statistics about references.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Refs {
+    /// The amount of references stored in their own file.
+    pub loose: u64,
+    /// The amount of references stored in the `packed-refs` file.
+    pub packed: u64,
+}
+
+/// The result of [`Repository::stats()`](crate::Repository::stats()).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot {
+    /// Statistics about loose objects.
+    pub loose_objects: LooseObjects,
+    /// Statistics about packs.
+    pub packs: Packs,
+    /// Statistics about references.
+    pub refs: Refs,
+    /// The size of the index file on disk, or `None` if there is no index, as is typical for bare repositories.
+    pub index_size_in_bytes: Option<u64>,
+    /// The time at which maintenance was last performed in seconds since the unix epoch, or `None` if there is no sign of it.
+    ///
+    /// It's the latest modification time of files written by `git gc` or `git maintenance`, like the `packed-refs` file,
+    /// commit-graphs or the multi-pack index.
+    pub last_maintenance: Option<gix_date::SecondsSinceUnixEpoch>,
+}
+
+/// The error returned by [`Repository::stats()`](crate::Repository::stats()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not access '{}'", path.display())]
+    Io { source: std::io::Error, path: PathBuf },
+    #[error(transparent)]
+    PackIndex(#[from] gix_pack::index::init::Error),
+    #[error(transparent)]
+    OpenPackedRefs(#[from] gix_ref::packed::buffer::open::Error),
+    #[error(transparent)]
+    IterPackedRefs(#[from] gix_ref::packed::iter::Error),
+}
@@ -55,6 +55,10 @@ pub struct Options {
     /// Otherwise, usually use as many threads as there are logical cores.
     /// A value of 0 is interpreted as no-limit
     pub thread_limit: Option<usize>,
+    /// Control how tracked files are compared if they are named pipes, sockets or other non-regular files in the worktree.
+    ///
+    /// Untracked non-regular files are controlled by [`dirwalk_options`](Self::dirwalk_options).
+    pub non_regular_files: gix_fs::NonRegularFiles,
 }
 
 impl Repository {
@@ -160,6 +164,7 @@ impl Repository {
                     fs: fs_caps,
                     thread_limit: options.thread_limit,
                     stat: self.stat_options()?,
+                    non_regular_files: options.non_regular_files,
                 },
                 dirwalk: options.dirwalk_options.map(Into::into),
                 rewrites: options.rewrites,
@@ -327,6 +332,7 @@ pub mod iter {
         /// The index that was used for the operation.
         pub index: crate::worktree::IndexPersistedOrInMemory,
         skip_hash: bool,
+        lock_mode: gix_lock::acquire::Fail,
         changes: Option<Vec<(usize, iter::ApplyChange)>>,
     }
 
@@ -365,6 +371,7 @@ pub mod iter {
             Some(index.write(crate::index::write::Options {
                 extensions: Default::default(),
                 skip_hash: self.skip_hash,
+                lock_mode: self.lock_mode,
             }))
         }
     }
@@ -606,6 +613,8 @@ pub mod iter {
         #[error(transparent)]
         ConfigSkipHash(#[from] crate::config::boolean::Error),
         #[error(transparent)]
+        LockTimeoutConfiguration(#[from] crate::config::lock_timeout::Error),
+        #[error(transparent)]
         PrepareSubmodules(#[from] crate::submodule::modules::Error),
     }
 
@@ -637,6 +646,7 @@ pub mod iter {
                 .transpose()
                 .with_lenient_default(self.repo.config.lenient_config)?
                 .unwrap_or_default();
+            let lock_mode = self.repo.config.other_lock_timeout()?;
             let should_interrupt = self.should_interrupt.clone().unwrap_or_default();
             let submodule = BuiltinSubmoduleStatus::new(self.repo.clone().into_sync(), self.submodules)?;
             #[cfg(feature = "parallel")]
@@ -668,6 +678,7 @@ pub mod iter {
                                 index,
                                 changes: None,
                                 skip_hash,
+                                lock_mode,
                             })
                         }
                     })
@@ -703,6 +714,7 @@ pub mod iter {
                     index,
                     changes: None,
                     skip_hash,
+                    lock_mode,
                 };
                 let mut iter = super::Iter {
                     items: Vec::new().into_iter(),
@@ -109,6 +109,7 @@ impl Repository {
                 dirwalk_options: Some(self.dirwalk_options()?),
                 rewrites: None,
                 thread_limit: None,
+                non_regular_files: Default::default(),
             },
         };
 
@@ -145,6 +145,23 @@ impl Submodule<'_> {
         self.state.modules.shallow(self.name())
     }
 
+    /// Return how to limit the history when cloning this submodule, given the per-clone choice of `shallow_submodules`,
+    /// similar to `git clone --shallow-submodules`.
+    ///
+    /// If `shallow_submodules` is `true`, the submodule is cloned with a depth of 1, and with its full history otherwise.
+    /// Either can be overridden by setting the [`shallow`](Self::shallow()) field of this submodule.
+    pub fn clone_shallow(
+        &self,
+        shallow_submodules: bool,
+    ) -> Result<crate::remote::fetch::Shallow, gix_config::value::Error> {
+        use crate::remote::fetch::Shallow;
+        Ok(if self.shallow()?.unwrap_or(shallow_submodules) {
+            Shallow::DepthAtRemote(std::num::NonZeroU32::new(1).expect("non-zero"))
+        } else {
+            Shallow::NoChange
+        })
+    }
+
     /// Returns true if this submodule is considered active and can thus participate in an operation.
     ///
     /// Please see the [plumbing crate documentation](gix_submodule::IsActivePlatform::is_active()) for details.
@@ -0,0 +1,70 @@
+//! Analyse the shape of the commit graph to find root commits, tips and subgraphs that aren't reachable from any reference.
+#![allow(clippy::empty_docs)]
+use gix_hash::ObjectId;
+
+/// A commit that isn't the parent of any other commit in the object database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tip {
+    /// The id of the commit.
+    pub id: ObjectId,
+    /// The names of all references that point to the commit after peeling, sorted by name.
+    ///
+    /// If empty, the tip is only reachable through `HEAD` in detached state, or it is part of an [orphaned subgraph](Subgraph).
+    pub references: Vec<gix_ref::FullName>,
+}
+
+/// A set of commits connected through their parents, none of which is reachable from any reference or `HEAD`.
+///
+/// These are typically left over from deleted branches, rewritten history or interrupted imports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subgraph {
+    /// The commits of the subgraph that aren't the parent of any other commit, sorted by id.
+    pub tips: Vec<ObjectId>,
+    /// The commits of the subgraph without any parent, sorted by id.
+    pub roots: Vec<ObjectId>,
+    /// The reachable commits that are parents of commits in this subgraph, sorted by id, which is where the subgraph
+    /// branched off the reachable history.
+    ///
+    /// If empty, the subgraph has its own history.
+    pub attached_to: Vec<ObjectId>,
+    /// The amount of commits in the subgraph.
+    pub num_commits: usize,
+}
+
+/// The outcome of [`Repository::topology()`](crate::Repository::topology()).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Outcome {
+    /// The amount of commits in the object database.
+    pub num_commits: usize,
+    /// All commits without a parent, sorted by id.
+    pub roots: Vec<ObjectId>,
+    /// All commits that aren't the parent of any other commit, sorted by id.
+    pub tips: Vec<Tip>,
+    /// All commits with at least one parent that isn't present in the object database, as it's typical for shallow clones,
+    /// sorted by id.
+    pub boundary: Vec<ObjectId>,
+    /// All subgraphs of commits that aren't reachable from any reference or `HEAD`, sorted by their first tip.
+    pub orphaned: Vec<Subgraph>,
+}
+
+/// The error returned by [`Repository::topology()`](crate::Repository::topology()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    IterateObjects(#[from] gix_odb::store::load_index::Error),
+    #[error(transparent)]
+    FindHeader(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    FindCommit(#[from] gix_object::find::existing_iter::Error),
+    #[error(transparent)]
+    InitRefsIterator(#[from] crate::reference::iter::init::Error),
+    #[error(transparent)]
+    InitRefsIteratorPlatform(#[from] crate::reference::iter::Error),
+    #[error(transparent)]
+    ObtainRefDuringIteration(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error("Could not list linked worktrees")]
+    ListWorktrees(#[source] std::io::Error),
+    #[error(transparent)]
+    OpenWorktree(#[from] crate::open::Error),
+}
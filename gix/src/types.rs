@@ -158,11 +158,16 @@ pub struct Repository {
     ///
     /// Particularly useful when following linked worktrees and instantiating new equally configured worktree repositories.
     pub(crate) options: crate::open::Options,
+    /// The time it took to open the repository.
+    pub(crate) open_metrics: crate::open::Metrics,
     #[cfg(feature = "index")]
     pub(crate) index: crate::worktree::IndexStorage,
     #[cfg(feature = "attributes")]
     pub(crate) modules: crate::submodule::ModulesFileStorage,
     pub(crate) shallow_commits: crate::shallow::CommitsStorage,
+    /// A cache for parsed attribute and ignore files to share among all attribute stacks, if set.
+    #[cfg(feature = "excludes")]
+    pub(crate) worktree_cache: Option<crate::WorktreeCache>,
 }
 
 /// An instance with access to everything a git repository entails, best imagined as container implementing `Sync + Send` for _most_
@@ -187,12 +192,17 @@ pub struct ThreadSafeRepository {
     pub(crate) config: crate::config::Cache,
     /// options obtained when instantiating this repository for use when following linked worktrees.
     pub(crate) linked_worktree_options: crate::open::Options,
+    /// The time it took to open the repository.
+    pub(crate) open_metrics: crate::open::Metrics,
     /// The index of this instances worktree.
     #[cfg(feature = "index")]
     pub(crate) index: crate::worktree::IndexStorage,
     #[cfg(feature = "attributes")]
     pub(crate) modules: crate::submodule::ModulesFileStorage,
     pub(crate) shallow_commits: crate::shallow::CommitsStorage,
+    /// A cache for parsed attribute and ignore files to share among all attribute stacks, if set.
+    #[cfg(feature = "excludes")]
+    pub(crate) worktree_cache: Option<crate::WorktreeCache>,
 }
 
 /// A remote which represents a way to interact with hosts for remote clones of the parent repository.
@@ -215,10 +225,12 @@ pub struct Remote<'repo> {
     pub(crate) push_specs: Vec<gix_refspec::RefSpec>,
     /// Tell us what to do with tags when fetched.
     pub(crate) fetch_tags: remote::fetch::Tags,
-    // /// Delete local tracking branches that don't exist on the remote anymore.
-    // pub(crate) prune: bool,
-    // /// Delete tags that don't exist on the remote anymore, equivalent to pruning the refspec `refs/tags/*:refs/tags/*`.
-    // pub(crate) prune_tags: bool,
+    /// Delete local tracking branches that don't exist on the remote anymore.
+    pub(crate) prune: bool,
+    /// Delete tags that don't exist on the remote anymore, equivalent to pruning the refspec `refs/tags/*:refs/tags/*`.
+    pub(crate) prune_tags: bool,
+    /// Push all references and delete those on the remote that don't exist locally if no push ref-spec is given.
+    pub(crate) mirror: bool,
     pub(crate) repo: &'repo Repository,
 }
 
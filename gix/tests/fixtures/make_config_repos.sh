@@ -15,6 +15,8 @@ git init http-config
   git config http.proxyAuthMethod basic
   git config http.userAgent agentJustForHttp
   git config gitoxide.http.connectTimeout 60k
+  git config gitoxide.http.retries 3
+  git config gitoxide.http.retryDelay 500
   git config http.schannelCheckRevoke true
   git config http.sslCAInfo ./CA.pem
   git config http.sslVersion sslv2
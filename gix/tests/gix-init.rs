@@ -49,6 +49,7 @@ mod with_overrides {
             .set("GIX_OBJECT_CACHE_MEMORY", "5m")
             .set("GIX_CREDENTIALS_HELPER_STDERR", "creds-stderr")
             .set("GIX_EXTERNAL_COMMAND_STDERR", "filter-stderr")
+            .set("GIT_REFLOG_ACTION", "reflog-action")
             .set("GIT_SSL_CAINFO", "./env.pem")
             .set("GIT_SSL_VERSION", "tlsv1.3")
             .set("GIT_SSH_VARIANT", "ssh-variant-env")
@@ -247,6 +248,7 @@ mod with_overrides {
             ("gitoxide.credentials.terminalPrompt", "42"),
             ("gitoxide.credentials.helperStderr", "creds-stderr"),
             ("gitoxide.core.externalCommandStderr", "filter-stderr"),
+            ("gitoxide.core.reflogAction", "reflog-action"),
         ] {
             assert_eq!(
                 config
@@ -232,6 +232,96 @@ mod blocking_io {
         Ok(())
     }
 
+    #[test]
+    fn fetch_only_with_default_remote_name_from_configuration() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, _out) = gix::prepare_clone_bare(remote::repo("base").path(), tmp.path())?
+            .with_in_memory_config_overrides(Some(Clone::DEFAULT_REMOTE_NAME.validated_assignment_fmt(&"upstream")?))
+            .fetch_only(gix::progress::Discard, &AtomicBool::default())?;
+
+        assert_eq!(repo.remote_names().len(), 1, "only ever one remote");
+        let remote = repo.find_remote("upstream")?;
+        assert_eq!(
+            remote.refspecs(Direction::Fetch)[0].to_ref().to_bstring(),
+            "+refs/heads/*:refs/remotes/upstream/*",
+            "the configured name is used instead of `origin`"
+        );
+        assert!(repo.find_reference("refs/remotes/upstream/main").is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_into_existing_repository() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let repo = gix::init_bare(tmp.path())?;
+        let (repo, _out) = gix::clone::PrepareFetch::from_repository(remote::repo("base").path(), repo)?
+            .fetch_only(gix::progress::Discard, &AtomicBool::default())?;
+
+        assert!(repo.find_reference("refs/remotes/origin/main").is_ok());
+        assert_eq!(
+            repo.head_name()?.expect("set").as_bstr(),
+            "refs/heads/main",
+            "HEAD is set up like in any other clone"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn dropping_clone_of_existing_repository_keeps_it() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let repo = gix::init_bare(tmp.path())?;
+        let git_dir = repo.path().to_owned();
+        drop(gix::clone::PrepareFetch::from_repository(
+            remote::repo("base").path(),
+            repo,
+        )?);
+        assert!(
+            git_dir.join("HEAD").is_file(),
+            "repositories provided by the caller are never removed"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_then_checkout_with_shallow_submodules() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let mut prepare = gix::prepare_clone(remote::repo("base").path(), tmp.path())?;
+        let (checkout, _out) = prepare.fetch_then_checkout(gix::progress::Discard, &AtomicBool::default())?;
+        assert!(
+            !checkout.shallow_submodules(),
+            "submodules are cloned with full history by default"
+        );
+
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let mut prepare = gix::prepare_clone(remote::repo("base").path(), tmp.path())?.with_shallow_submodules(true);
+        let (checkout, _out) = prepare.fetch_then_checkout(gix::progress::Discard, &AtomicBool::default())?;
+        assert!(
+            checkout.shallow_submodules(),
+            "the choice is passed on to those who clone submodules after checkout"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_with_invalid_default_remote_name_from_configuration() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let err = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            gix::open::Options::isolated().strict_config(true),
+        )?
+        .with_in_memory_config_overrides(Some("clone.defaultRemoteName=invalid name"))
+        .fetch_only(gix::progress::Discard, &AtomicBool::default())
+        .unwrap_err();
+        assert!(
+            matches!(err, gix::clone::fetch::Error::RemoteName(_)),
+            "the configured name must be usable in refspecs: {err:?}"
+        );
+        Ok(())
+    }
+
     #[test]
     fn fetch_only_with_configuration() -> crate::Result {
         let tmp = gix_testtools::tempfile::TempDir::new()?;
@@ -250,6 +340,7 @@ mod blocking_io {
             ]),
         )?
         .with_remote_name(remote_name)?
+        .with_local(gix::clone::Local::Disabled)
         .configure_remote({
             let called_configure_remote = called_configure_remote.clone();
             move |r| {
@@ -504,6 +595,79 @@ mod blocking_io {
         assure_index_entries_on_disk(&index, repo.work_dir().expect("non-bare"));
         Ok(())
     }
+
+    #[test]
+    #[cfg(unix)]
+    fn fetch_and_checkout_runs_post_checkout_hook() -> crate::Result {
+        use std::os::unix::fs::PermissionsExt;
+
+        let hooks = gix_testtools::tempfile::TempDir::new()?;
+        let hook = hooks.path().join("post-checkout");
+        std::fs::write(
+            &hook,
+            "#!/bin/sh\necho \"$@\" > \"$GIT_DIR/hook-args\"\npwd > \"$GIT_DIR/hook-cwd\"\n",
+        )?;
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755))?;
+
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let mut prepare = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            restricted().config_overrides([Core::HOOKS_PATH.validated_assignment_fmt(&hooks.path().display())?]),
+        )?;
+        let (mut checkout, _out) = prepare.fetch_then_checkout(gix::progress::Discard, &AtomicBool::default())?;
+        let (repo, _) = checkout.main_worktree(gix::progress::Discard, &AtomicBool::default())?;
+
+        let head_id = repo.head_id()?;
+        assert_eq!(
+            std::fs::read_to_string(repo.git_dir().join("hook-args"))?,
+            format!("{} {head_id} 1\n", gix::ObjectId::null(repo.object_hash())),
+            "the hook receives the null-id as previous HEAD, and indicates a branch checkout"
+        );
+        assert_eq!(
+            gix_path::realpath(std::fs::read_to_string(repo.git_dir().join("hook-cwd"))?.trim_end())?,
+            gix_path::realpath(repo.work_dir().expect("non-bare"))?,
+            "hooks run in the root of the worktree"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn fetch_and_checkout_fails_if_post_checkout_hook_fails() -> crate::Result {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let mut prepare = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            restricted(),
+        )?;
+        let hook = tmp.path().join(".git").join("hooks").join("post-checkout");
+        std::fs::write(&hook, "#!/bin/sh\nexit 42\n")?;
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755))?;
+
+        let (mut checkout, _out) = prepare.fetch_then_checkout(gix::progress::Discard, &AtomicBool::default())?;
+        let err = checkout
+            .main_worktree(gix::progress::Discard, &AtomicBool::default())
+            .unwrap_err();
+        assert!(
+            matches!(err, gix::clone::checkout::main_worktree::Error::PostCheckoutHook { status, .. } if status.code() == Some(42)),
+            "{err:?}"
+        );
+        drop(checkout);
+        let repo = gix::open_opts(tmp.path(), restricted())?;
+        assert_eq!(
+            repo.index()?.entries().len(),
+            1,
+            "the checkout is complete nonetheless and isn't deleted"
+        );
+        Ok(())
+    }
     #[test]
     fn fetch_and_checkout_specific_ref() -> crate::Result {
         let tmp = gix_testtools::tempfile::TempDir::new()?;
@@ -618,21 +782,101 @@ mod blocking_io {
             remote_repo.references()?.all()?.count(),
             "all references have been cloned, + remote HEAD (not listed in remote_repo)"
         );
-        let checked_out_ref = repo.head_ref()?.expect("head points to ref");
-        let remote_ref_name = format!("refs/tags/{ref_to_checkout}");
+        let head = repo.head()?;
+        assert!(
+            head.is_detached(),
+            "like git, tags are checked out with a detached HEAD"
+        );
+        let tag_ref_name = format!("refs/tags/{ref_to_checkout}");
+        let remote_tag_id = remote_repo.find_reference(tag_ref_name.as_str())?.id().detach();
         assert_eq!(
-            checked_out_ref.name().as_bstr(),
-            remote_ref_name,
-            "it also works with tags"
+            repo.head_id()?,
+            remote_repo
+                .find_object(remote_tag_id)?
+                .peel_to_kind(gix::object::Kind::Commit)?
+                .id,
+            "HEAD points to the commit, not the tag object"
+        );
+        assert_eq!(
+            repo.find_reference(tag_ref_name.as_str())?.id(),
+            remote_tag_id,
+            "the tag itself is unchanged"
         );
+        Ok(())
+    }
 
+    #[test]
+    fn fetch_and_checkout_specific_revision_by_id() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let remote_repo = remote::repo("base");
+        let revision = remote_repo.rev_parse_single("g")?.detach();
+        let mut prepare = gix::prepare_clone(remote_repo.path(), tmp.path())?.with_revision(Some(revision));
+        let (mut checkout, _out) = prepare.fetch_then_checkout(gix::progress::Discard, &AtomicBool::default())?;
+        let (repo, _) = checkout.main_worktree(gix::progress::Discard, &AtomicBool::default())?;
+
+        assert!(repo.head()?.is_detached());
+        assert_eq!(repo.head_id()?, revision);
         assert_eq!(
-            checked_out_ref
-                .remote_ref_name(gix::remote::Direction::Fetch)
-                .transpose()?,
-            None,
-            "there is no merge configuration for tags"
+            repo.references()?.local_branches()?.count(),
+            0,
+            "like `git clone --revision`, no local branch is created"
+        );
+        assert_eq!(std::fs::read(repo.work_dir().expect("non-bare").join("file"))?, b"g\n");
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_and_checkout_specific_revision_by_name() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let remote_repo = remote::repo("base");
+        let mut prepare = gix::prepare_clone(remote_repo.path(), tmp.path())?
+            .with_ref_name(Some("main"))?
+            .with_revision(Some(gix::refs::PartialName::try_from("i-tag")?));
+        let (mut checkout, _out) = prepare.fetch_then_checkout(gix::progress::Discard, &AtomicBool::default())?;
+        let (repo, _) = checkout.main_worktree(gix::progress::Discard, &AtomicBool::default())?;
+
+        assert!(
+            repo.head()?.is_detached(),
+            "the revision takes precedence over the ref-name"
+        );
+        assert_eq!(
+            repo.head_id()?,
+            remote_repo.rev_parse_single("i-tag^{commit}")?.detach()
         );
+        assert_eq!(std::fs::read(repo.work_dir().expect("non-bare").join("file"))?, b"i\n");
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_and_checkout_specific_revision_by_name_fails_early_if_missing() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let mut prepare = gix::prepare_clone(remote::repo("base").path(), tmp.path())?
+            .with_revision(Some(gix::refs::PartialName::try_from("does-not-exist")?));
+        let err = prepare
+            .fetch_then_checkout(gix::progress::Discard, &AtomicBool::default())
+            .unwrap_err();
+        assert!(matches!(err, gix::clone::fetch::Error::RefNameMissing { .. }));
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_then_detach_at_revspec_before_checkout() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let remote_repo = remote::repo("base");
+        let mut prepare = gix::prepare_clone(remote_repo.path(), tmp.path())?;
+        let (mut checkout, _out) = prepare.fetch_then_checkout(gix::progress::Discard, &AtomicBool::default())?;
+        assert!(
+            checkout.detach_at("does-not-exist").is_err(),
+            "revspecs must resolve in the fetched repository"
+        );
+
+        let id = checkout.detach_at("origin/g")?;
+        assert_eq!(id, remote_repo.rev_parse_single("g")?);
+        let (repo, _) = checkout.main_worktree(gix::progress::Discard, &AtomicBool::default())?;
+
+        assert!(repo.head()?.is_detached());
+        assert_eq!(repo.head_id()?, id);
+        assert_eq!(std::fs::read(repo.work_dir().expect("non-bare").join("file"))?, b"g\n");
         Ok(())
     }
 
@@ -704,6 +948,7 @@ mod blocking_io {
             Default::default(),
             restricted(),
         )?
+        .with_local(gix::clone::Local::Disabled)
         .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
         assert!(repo.find_remote("origin").is_ok(), "default remote name is 'origin'");
         match out.status {
@@ -717,6 +962,169 @@ mod blocking_io {
         }
         Ok(())
     }
+
+    #[test]
+    fn fetch_only_with_sideband_handler() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let messages = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let (_repo, _out) = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_local(gix::clone::Local::Disabled)
+        .with_sideband_handler({
+            let messages = messages.clone();
+            move |msg| {
+                if let gix::remote::fetch::SidebandMessage::Progress(text) = msg {
+                    messages.lock().unwrap().push(text.to_owned());
+                }
+            }
+        })
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        let messages = messages.lock().unwrap();
+        assert!(
+            messages.iter().any(|msg| msg.starts_with(b"Enumerating objects")),
+            "the server's progress messages are passed on verbatim: {messages:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_into_existing_worktree() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let file = tmp.path().join("file");
+        std::fs::write(&file, b"hello")?;
+        let (repo, _out) = gix::clone::PrepareFetch::new_in_existing_worktree(
+            remote::repo("base").path(),
+            tmp.path(),
+            Default::default(),
+            restricted(),
+        )?
+        .with_local(gix::clone::Local::Disabled)
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        assert_eq!(
+            gix::path::realpath(repo.work_dir().expect("non-bare"))?,
+            gix::path::realpath(tmp.path())?
+        );
+        assert!(repo.head_id().is_ok(), "HEAD points to what was fetched");
+        assert!(!repo.index_path().exists(), "nothing was checked out");
+        assert_eq!(std::fs::read(&file)?, b"hello", "existing files are left untouched");
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_without_tags() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, _out) = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_local(gix::clone::Local::Disabled)
+        .with_tags(gix::remote::fetch::Tags::None)
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        assert_eq!(
+            repo.references()?.tags()?.count(),
+            0,
+            "no tags are fetched, not even the implicit ones"
+        );
+        assert_eq!(
+            repo.config_snapshot().string("remote.origin.tagOpt").as_deref(),
+            Some("--no-tags".into()),
+            "the choice is persisted so future fetches won't get tags either"
+        );
+        assert_eq!(repo.find_remote("origin")?.fetch_tags(), gix::remote::fetch::Tags::None);
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_from_local_path_links_or_copies_objects() -> crate::Result {
+        for local in [gix::clone::Local::HardlinkOrCopy, gix::clone::Local::Copy] {
+            let tmp = gix_testtools::tempfile::TempDir::new()?;
+            let source = remote::repo("base");
+            let (repo, out) = gix::clone::PrepareFetch::new(
+                source.path(),
+                tmp.path(),
+                gix::create::Kind::Bare,
+                Default::default(),
+                restricted(),
+            )?
+            .with_local(local)
+            .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+            match out.status {
+                gix::remote::fetch::Status::NoPackReceived { update_refs, .. } => {
+                    assert!(
+                        !update_refs.edits.is_empty(),
+                        "refs are still updated, but objects are already present"
+                    );
+                }
+                _ => unreachable!("{local:?}: no pack is needed as all objects are obtained from the source directly"),
+            }
+            assert_eq!(
+                repo.head_id()?.detach(),
+                source.head_id()?.detach(),
+                "{local:?}: the clone is complete and points to the same commit"
+            );
+            assert_eq!(
+                repo.head_id()?.ancestors().all()?.count(),
+                source.head_id()?.ancestors().all()?.count(),
+                "{local:?}: all history is present"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_from_local_path_skips_temporary_files() -> crate::Result {
+        let source = gix_testtools::tempfile::TempDir::new()?;
+        gix_testtools::copy_recursively_into_existing_dir(remote::repo("base").path(), source.path())?;
+        let objects = source.path().join("objects");
+        let temporary = [
+            "pack/tmp_pack_123",
+            "pack/tmp_gix_partial_pack-abc",
+            "pack/tmp_gix_partial_pack-abc.lock",
+            "pack/tmp_gix_partial_pack-abc.common",
+            "info/commit-graphs/commit-graph-chain.lock",
+            "incoming-XYZ/pack/pack-abc.pack",
+            "tmp_objdir-incoming-XYZ/pack/pack-abc.pack",
+        ];
+        for path in temporary {
+            let path = objects.join(path);
+            std::fs::create_dir_all(path.parent().expect("in directory"))?;
+            std::fs::write(path, b"garbage")?;
+        }
+
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, _out) = gix::clone::PrepareFetch::new(
+            source.path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_local(gix::clone::Local::Copy)
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+        let objects = repo.objects.store_ref().path();
+        for path in temporary {
+            assert!(!objects.join(path).exists(), "{path} is temporary and isn't copied");
+        }
+        assert!(!objects.join("incoming-XYZ").exists());
+        assert!(!objects.join("tmp_objdir-incoming-XYZ").exists());
+        assert_eq!(
+            repo.head_id()?.ancestors().all()?.count(),
+            remote::repo("base").head_id()?.ancestors().all()?.count(),
+            "all history is present"
+        );
+        Ok(())
+    }
 }
 
 #[test]
@@ -789,3 +1197,26 @@ fn clone_into_empty_directory_and_early_drop() -> crate::Result {
     assert!(!head.is_file(), "we cleanup if the clone isn't followed through");
     Ok(())
 }
+
+#[test]
+fn clone_into_existing_worktree_and_early_drop() -> crate::Result {
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let file = tmp.path().join("file");
+    std::fs::write(&file, b"hello")?;
+    let prep = gix::clone::PrepareFetch::new_in_existing_worktree(
+        remote::repo("base").path(),
+        tmp.path(),
+        Default::default(),
+        restricted(),
+    )?;
+    let dot_git = tmp.path().join(".git");
+    assert!(dot_git.join("HEAD").is_file(), "now a basic repo is present");
+    drop(prep);
+
+    assert!(
+        !dot_git.exists(),
+        "we cleanup the git directory if the clone isn't followed through"
+    );
+    assert_eq!(std::fs::read(&file)?, b"hello", "but existing files are left untouched");
+    Ok(())
+}
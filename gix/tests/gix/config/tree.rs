@@ -176,6 +176,49 @@ mod status {
     }
 }
 
+mod log {
+    use gix::{
+        config::tree::Log,
+        log::{DateFormat, DateStyle},
+    };
+
+    use crate::config::tree::bcow;
+
+    #[test]
+    fn date() -> crate::Result {
+        for (actual, style, local) in [
+            ("default", DateStyle::Default, false),
+            ("relative", DateStyle::Default, false),
+            ("human", DateStyle::Default, false),
+            ("local", DateStyle::Default, true),
+            ("iso", DateStyle::Iso8601, false),
+            ("iso8601-local", DateStyle::Iso8601, true),
+            ("iso-strict", DateStyle::Iso8601Strict, false),
+            ("iso8601-strict", DateStyle::Iso8601Strict, false),
+            ("rfc", DateStyle::Rfc2822, false),
+            ("rfc2822-local", DateStyle::Rfc2822, true),
+            ("short", DateStyle::Short, false),
+            ("raw", DateStyle::Raw, false),
+            ("unix-local", DateStyle::Unix, true),
+            ("format:%Y", DateStyle::Custom("%Y".into()), false),
+            ("format-local:%Y:%m", DateStyle::Custom("%Y:%m".into()), true),
+        ] {
+            assert_eq!(
+                Log::DATE.try_into_date_format(bcow(actual))?,
+                DateFormat { style, local },
+                "{actual}"
+            );
+        }
+
+        assert_eq!(
+            Log::DATE.try_into_date_format(bcow("ISO")).unwrap_err().to_string(),
+            "The key \"log.date=ISO\" was invalid",
+            "case-sensitive comparisons"
+        );
+        Ok(())
+    }
+}
+
 mod push {
     use crate::config::tree::bcow;
     use gix::config::tree::Push;
@@ -843,6 +886,20 @@ mod gitoxide {
             );
             Ok(())
         }
+
+        #[test]
+        fn retries() -> crate::Result {
+            assert_eq!(
+                gitoxide::Http::RETRIES.validated_assignment_fmt(&3)?,
+                "gitoxide.http.retries=3"
+            );
+            assert!(gitoxide::Http::RETRIES.validate("-1".into()).is_err());
+            assert_eq!(
+                gitoxide::Http::RETRY_DELAY.validated_assignment_fmt(&Duration::from_millis(500).as_millis())?,
+                "gitoxide.http.retryDelay=500"
+            );
+            Ok(())
+        }
     }
     mod allow {
         use gix::config::tree::{gitoxide, Key};
@@ -0,0 +1,124 @@
+use gix_testtools::tempfile;
+
+#[test]
+fn open_or_init_initializes_new_repositories_and_opens_existing_ones() -> crate::Result {
+    let tmp = tempfile::tempdir()?;
+    let dir = tmp.path().join("repo");
+    let repo = gix::easy::open_or_init(&dir)?;
+    assert!(!repo.is_bare());
+    assert!(repo.head()?.is_unborn(), "the repository was just initialized");
+
+    let repo = gix::easy::open_or_init(&dir)?;
+    assert_eq!(
+        repo.work_dir(),
+        Some(dir.as_path()),
+        "the same repository is opened the second time"
+    );
+    Ok(())
+}
+
+#[cfg(all(feature = "dirwalk", feature = "tree-editor"))]
+mod commit_all {
+    use gix_testtools::tempfile;
+
+    use crate::util::restricted;
+
+    #[test]
+    fn new_modified_and_deleted_files_are_committed_and_the_index_is_updated() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let repo = gix::ThreadSafeRepository::init_opts(
+            &tmp,
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            restricted(),
+        )?
+        .to_thread_local();
+        std::fs::write(tmp.path().join("a"), "a")?;
+        std::fs::create_dir(tmp.path().join("dir"))?;
+        std::fs::write(tmp.path().join("dir").join("b"), "b")?;
+        std::fs::write(tmp.path().join(".gitignore"), "ignored\n")?;
+        std::fs::write(tmp.path().join("ignored"), "not committed")?;
+
+        let first = gix::easy::commit_all(&repo, "first")?.detach();
+        let commit = repo.find_commit(first)?;
+        assert_eq!(commit.parent_ids().count(), 0);
+        let tree = commit.tree()?;
+        for path in [".gitignore", "a", "dir/b"] {
+            assert!(tree.lookup_entry_by_path(path)?.is_some(), "{path} was committed");
+        }
+        assert!(tree.lookup_entry_by_path("ignored")?.is_none());
+
+        std::fs::write(tmp.path().join("a"), "changed")?;
+        std::fs::remove_dir_all(tmp.path().join("dir"))?;
+        std::fs::write(tmp.path().join("c"), "c")?;
+        let second = gix::easy::commit_all(&repo, "second")?.detach();
+        assert_eq!(repo.head_id()?, second);
+        let commit = repo.find_commit(second)?;
+        assert_eq!(commit.parent_ids().collect::<Vec<_>>(), [first]);
+        let tree = commit.tree()?;
+        assert_eq!(
+            tree.lookup_entry_by_path("a")?.expect("present").object()?.data,
+            b"changed"
+        );
+        assert!(tree.lookup_entry_by_path("c")?.is_some());
+        assert!(tree.lookup_entry_by_path("dir/b")?.is_none(), "deletions are committed");
+
+        let index = repo.open_index()?;
+        let paths: Vec<_> = index.entries().iter().map(|e| e.path(&index).to_owned()).collect();
+        assert_eq!(paths, [".gitignore", "a", "c"], "the index matches the new commit");
+
+        assert!(matches!(
+            gix::easy::commit_all(&repo, "nothing changed"),
+            Err(gix::easy::commit_all::Error::NothingToCommit)
+        ));
+        Ok(())
+    }
+}
+
+#[cfg(all(
+    feature = "worktree-mutation",
+    feature = "blocking-network-client",
+    feature = "status",
+    feature = "revision",
+    feature = "tree-editor"
+))]
+mod fetch_and_fast_forward {
+    use crate::util::restricted;
+
+    #[test]
+    fn clone_then_fast_forward_to_new_upstream_commits() -> crate::Result {
+        let remote_repos = gix_testtools::scripted_fixture_writable("make_remote_repos.sh")?;
+        let origin_dir = remote_repos.path().join("base");
+        let tmp = gix_testtools::tempfile::tempdir()?;
+        let clone_dir = tmp.path().join("clone");
+        let clone = gix::easy::clone_url_to_dir(origin_dir.as_path(), &clone_dir)?;
+        let clone_head = clone.head_id()?.detach();
+
+        let clone = gix::open_opts(&clone_dir, restricted())?;
+        let outcome = gix::easy::fetch_and_fast_forward(&clone)?;
+        assert_eq!(outcome.previous_head, clone_head);
+        assert_eq!(outcome.head, clone_head, "nothing to do as nothing changed");
+
+        let origin = gix::open_opts(&origin_dir, restricted())?;
+        std::fs::write(origin_dir.join("file"), "changed upstream\n")?;
+        std::fs::write(origin_dir.join("new"), "new\n")?;
+        let upstream_head = gix::easy::commit_all(&origin, "new upstream commit")?.detach();
+
+        let outcome = gix::easy::fetch_and_fast_forward(&clone)?;
+        assert_eq!(outcome.previous_head, clone_head);
+        assert_eq!(outcome.head, upstream_head);
+        assert_eq!(clone.head_id()?, upstream_head, "HEAD was fast-forwarded");
+        assert_eq!(std::fs::read(clone_dir.join("file"))?, b"changed upstream\n");
+        assert_eq!(std::fs::read(clone_dir.join("new"))?, b"new\n");
+        assert!(!clone.is_dirty()?, "worktree and index match the new HEAD");
+
+        std::fs::write(clone_dir.join("file"), "local change")?;
+        std::fs::write(origin_dir.join("new"), "changed again\n")?;
+        gix::easy::commit_all(&origin, "another upstream commit")?;
+        assert!(matches!(
+            gix::easy::fetch_and_fast_forward(&clone),
+            Err(gix::easy::fetch_and_fast_forward::Error::Dirty)
+        ));
+        Ok(())
+    }
+}
@@ -52,6 +52,7 @@ mod bare {
 }
 
 mod non_bare {
+    use gix::config::tree::Key;
     use gix_testtools::tempfile;
 
     #[test]
@@ -125,4 +126,122 @@ mod non_bare {
         );
         Ok(())
     }
+
+    fn template_dir() -> crate::Result<gix_testtools::tempfile::TempDir> {
+        let dir = tempfile::tempdir()?;
+        std::fs::create_dir_all(dir.path().join("hooks"))?;
+        std::fs::write(dir.path().join("hooks").join("post-commit"), b"#!/bin/sh\n")?;
+        std::fs::create_dir_all(dir.path().join("info"))?;
+        std::fs::write(dir.path().join("info").join("exclude"), b"from-template\n")?;
+        std::fs::write(dir.path().join(".hidden"), b"")?;
+        std::fs::write(dir.path().join("HEAD"), b"ref: refs/heads/template\n")?;
+        std::fs::write(
+            dir.path().join("config"),
+            b"[core]\n\tbare = true\n[template]\n\tkey = value\n",
+        )?;
+        Ok(dir)
+    }
+
+    #[test]
+    fn init_with_template_dir() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let template = template_dir()?;
+        let path = gix::create::into(
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            gix::create::Options {
+                ignore_templates: true,
+                ..Default::default()
+            },
+        )?;
+        let (git_dir, _worktree_dir) = path.into_repository_and_work_tree_directories();
+        assert!(gix::create::copy_templates(template.path(), &git_dir)?);
+        let repo = gix::open_opts(&git_dir, gix::open::Options::isolated())?;
+        assert_templates_were_used(&repo)
+    }
+
+    #[test]
+    fn init_with_template_dir_from_configuration() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let template = template_dir()?;
+        let repo: gix::Repository = gix::ThreadSafeRepository::init_opts(
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            gix::create::Options::default(),
+            gix::open::Options::isolated().config_overrides([
+                gix::config::tree::Init::TEMPLATE_DIR.validated_assignment_fmt(&template.path().display())?
+            ]),
+        )?
+        .into();
+        assert_templates_were_used(&repo)
+    }
+
+    #[test]
+    fn init_without_templates() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let template = template_dir()?;
+        let repo: gix::Repository = gix::ThreadSafeRepository::init_opts(
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            gix::create::Options {
+                ignore_templates: true,
+                ..Default::default()
+            },
+            gix::open::Options::isolated().config_overrides([
+                gix::config::tree::Init::TEMPLATE_DIR.validated_assignment_fmt(&template.path().display())?
+            ]),
+        )?
+        .into();
+        for name in ["hooks", "info", "description"] {
+            assert!(!repo.git_dir().join(name).exists(), "{name} is a template and not used");
+        }
+        assert!(repo.head()?.is_unborn(), "the repository is still usable");
+        Ok(())
+    }
+
+    #[test]
+    fn init_with_builtin_templates() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let repo = gix::init(tmp.path())?;
+        assert!(repo.git_dir().join("hooks").join("pre-commit.sample").is_file());
+        assert!(repo.git_dir().join("info").join("exclude").is_file());
+        assert!(repo.git_dir().join("description").is_file());
+        Ok(())
+    }
+
+    fn assert_templates_were_used(repo: &gix::Repository) -> crate::Result {
+        let git_dir = repo.git_dir();
+        assert!(git_dir.join("hooks").join("post-commit").is_file());
+        assert!(
+            !git_dir.join("hooks").join("pre-commit.sample").exists(),
+            "built-in templates aren't used"
+        );
+        assert!(
+            !git_dir.join("description").exists(),
+            "it's a built-in template as well"
+        );
+        assert_eq!(std::fs::read(git_dir.join("info").join("exclude"))?, b"from-template\n");
+        assert!(
+            !git_dir.join(".hidden").exists(),
+            "hidden files are ignored, like in git"
+        );
+        assert_eq!(
+            repo.head()?.referent_name().expect("symbolic").as_bstr(),
+            "refs/heads/main",
+            "HEAD isn't taken from the template"
+        );
+        let config = repo.config_snapshot();
+        assert_eq!(
+            config.string("template.key").expect("present").as_ref(),
+            "value",
+            "the template configuration is merged"
+        );
+        assert_eq!(
+            config.boolean("core.bare"),
+            Some(false),
+            "values of the new repository take precedence"
+        );
+        assert!(!repo.is_bare());
+        Ok(())
+    }
 }
@@ -7,6 +7,7 @@ mod commit;
 mod config;
 #[cfg(feature = "blob-diff")]
 mod diff;
+mod easy;
 mod head;
 mod id;
 mod init;
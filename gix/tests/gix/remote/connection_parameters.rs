@@ -0,0 +1,50 @@
+use gix::{
+    config::tree::{Http, Remote, Url},
+    remote::Direction,
+};
+
+#[test]
+fn url_rewrites_and_proxy_precedence_are_explained() -> crate::Result {
+    let mut repo = crate::remote::repo("clone");
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_subsection_value(&Remote::URL, "origin", "https://example.com/repo")?;
+        config.set_subsection_value(&Url::INSTEAD_OF, "https://mirror.example.com/", "https://example.com/")?;
+        config.set_value(&Http::PROXY, "http://http-proxy.example.com")?;
+        config.set_value(&Http::VERSION, "HTTP/1.1")?;
+        config.commit()?;
+    }
+
+    let params = repo
+        .find_remote("origin")?
+        .connection_parameters(Direction::Fetch)
+        .expect("url is set");
+    assert_eq!(params.configured_url.to_bstring(), "https://example.com/repo");
+    assert_eq!(
+        params.url.to_bstring(),
+        "https://mirror.example.com/repo",
+        "the rewritten url is the one that is connected to"
+    );
+    let proxy = params.proxy.expect("set");
+    assert_eq!(proxy.key, "http.proxy");
+    assert_eq!(proxy.value, "http://http-proxy.example.com");
+    let http_version = params.http_version.expect("set");
+    assert_eq!(http_version.key, "http.version");
+    assert_eq!(http_version.value, "HTTP/1.1");
+    assert_eq!(params.ssh_command, None, "ssh settings don't apply to http urls");
+
+    repo.config_snapshot_mut()
+        .set_subsection_value(&Remote::PROXY, "origin", "http://remote-proxy.example.com")?;
+    let proxy = repo
+        .find_remote("origin")?
+        .connection_parameters(Direction::Fetch)
+        .expect("url is set")
+        .proxy
+        .expect("set");
+    assert_eq!(
+        proxy.key, "remote.origin.proxy",
+        "the remote-specific proxy takes precedence"
+    );
+    assert_eq!(proxy.value, "http://remote-proxy.example.com");
+    Ok(())
+}
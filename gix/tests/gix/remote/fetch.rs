@@ -112,6 +112,133 @@ mod blocking_and_async_io {
         Ok(())
     }
 
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_into_namespace_of_existing_repository() -> crate::Result {
+        let (repo, _tmp) = repo_rw("two-origins");
+        let config_before = std::fs::read(repo.path().join("config"))?;
+        let refs_outside_of_namespace = |repo: &gix::Repository| -> crate::Result<Vec<gix::refs::FullName>> {
+            Ok(repo
+                .references()?
+                .all()?
+                .map(|r| r.map(|r| r.detach().name))
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .filter(|name| !name.as_bstr().starts_with(b"refs/namespaces/"))
+                .collect())
+        };
+        let refs_before = refs_outside_of_namespace(&repo)?;
+
+        let namespace = gix::refs::namespace::expand("cache")?;
+        let remote = repo
+            .remote_at(base_repo_path())?
+            .with_refspecs(Some("+refs/heads/main:refs/remotes/origin/main"), Fetch)?
+            .with_fetch_namespace(&namespace)?;
+        assert_eq!(
+            remote.refspecs(Fetch)[0].to_ref().to_bstring(),
+            "+refs/heads/main:refs/namespaces/cache/refs/remotes/origin/main"
+        );
+        assert_eq!(
+            remote.fetch_tags(),
+            fetch::Tags::None,
+            "tags would end up outside of the namespace"
+        );
+
+        remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+
+        assert!(repo
+            .try_find_reference("refs/namespaces/cache/refs/remotes/origin/main")?
+            .is_some());
+        assert_eq!(
+            refs_outside_of_namespace(&repo)?,
+            refs_before,
+            "no reference outside of the namespace was touched"
+        );
+        assert_eq!(
+            std::fs::read(repo.path().join("config"))?,
+            config_before,
+            "the configuration isn't affected by anonymous remotes"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn shallow_fetch_into_namespace_of_existing_repository() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        gix::init_bare(tmp.path())?;
+        let repo = gix::open_opts(tmp.path(), crate::restricted())?;
+        let empty_tree = repo.write_object(gix::objs::Tree::empty())?;
+        let unrelated = repo.commit(
+            "refs/heads/unrelated",
+            "unrelated",
+            empty_tree,
+            gix::commit::NO_PARENT_IDS,
+        )?;
+        let child = repo.commit("refs/heads/unrelated", "child", empty_tree, [unrelated])?;
+
+        let namespace = gix::refs::namespace::expand("cache")?;
+        let fetch_shallow = |url: &str| -> crate::Result {
+            repo.remote_at(url)?
+                .with_refspecs(Some("+refs/heads/main:refs/remotes/origin/main"), Fetch)?
+                .with_fetch_namespace(&namespace)?
+                .connect(Fetch)?
+                .prepare_fetch(gix::progress::Discard, Default::default())?
+                .with_shallow(fetch::Shallow::DepthAtRemote(1.try_into().expect("non-zero")))
+                .receive(gix::progress::Discard, &AtomicBool::default())?;
+            Ok(())
+        };
+        fetch_shallow(&format!("file://{}", base_repo_path()))?;
+
+        let tip = repo
+            .find_reference("refs/namespaces/cache/refs/remotes/origin/main")?
+            .id()
+            .detach();
+        assert_eq!(
+            repo.shallow_commits()?.expect("shallow file written").as_slice(),
+            [tip],
+            "only the single fetched commit is at the shallow boundary"
+        );
+        assert_eq!(
+            repo.find_reference("refs/heads/unrelated")?.id(),
+            child,
+            "existing references are untouched"
+        );
+        assert_eq!(
+            child.ancestors().all()?.count(),
+            2,
+            "the history of unrelated references remains complete"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn shallow_fetch_does_not_cut_off_history_that_is_already_present() -> crate::Result {
+        let (repo, _tmp) = repo_rw("two-origins");
+        assert!(!repo.is_shallow());
+        let namespace = gix::refs::namespace::expand("cache")?;
+        let num_commits_before = repo.head_id()?.ancestors().all()?.count();
+        let outcome = repo
+            .remote_at(format!("file://{}", base_repo_path()))?
+            .with_refspecs(Some("+refs/heads/main:refs/remotes/origin/main"), Fetch)?
+            .with_fetch_namespace(&namespace)?
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_shallow(fetch::Shallow::DepthAtRemote(1.try_into().expect("non-zero")))
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert!(matches!(outcome.status, Status::Change { .. }));
+        assert!(
+            !repo.is_shallow(),
+            "the boundary commit is already present with its complete history"
+        );
+        assert_eq!(repo.head_id()?.ancestors().all()?.count(), num_commits_before);
+        Ok(())
+    }
+
     #[test]
     #[cfg(feature = "blocking-network-client")]
     fn fetch_with_alternates_adds_tips_from_alternates() -> crate::Result<()> {
@@ -124,6 +251,7 @@ mod blocking_and_async_io {
             Default::default(),
             gix::open::Options::isolated(),
         )?
+        .with_local(gix::clone::Local::Disabled)
         .configure_remote({
             move |r| {
                 std::fs::write(
@@ -160,6 +288,694 @@ mod blocking_and_async_io {
         Ok(())
     }
 
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_refetch_receives_all_objects_and_removes_superseded_packs() -> crate::Result<()> {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, out) = gix::clone::PrepareFetch::new(
+            remote::repo("multi_round/server").path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            gix::open::Options::isolated(),
+        )?
+        .with_local(gix::clone::Local::Disabled)
+        .fetch_only(gix::progress::Discard, &AtomicBool::default())?;
+        let (original_num_objects, original_data_path) = match out.status {
+            Status::Change { write_pack_bundle, .. } => (
+                write_pack_bundle.index.num_objects,
+                write_pack_bundle.data_path.expect("pack was written"),
+            ),
+            _ => unreachable!("a fresh clone receives a pack"),
+        };
+        // A copy of the pack is superseded by the pack received when refetching, unlike the pack itself which is received again.
+        let copy_data_path = original_data_path.with_file_name(format!("pack-{}.pack", "0".repeat(40)));
+        std::fs::copy(&original_data_path, &copy_data_path)?;
+        std::fs::copy(
+            original_data_path.with_extension("idx"),
+            copy_data_path.with_extension("idx"),
+        )?;
+        for extension in ["promisor", "mtimes"] {
+            std::fs::write(copy_data_path.with_extension(extension), b"")?;
+        }
+
+        let out = repo
+            .find_remote("origin")?
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_refetch(true)
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        match out.status {
+            Status::Change {
+                write_pack_bundle,
+                negotiate,
+                ..
+            } => {
+                assert_eq!(
+                    write_pack_bundle.index.num_objects, original_num_objects,
+                    "all objects are received again even though nothing changed"
+                );
+                assert_eq!(negotiate.rounds.len(), 1);
+                assert_eq!(negotiate.rounds[0].haves_sent, 0, "we pretend to not have anything");
+            }
+            _ => unreachable!("refetching always receives a pack"),
+        }
+        assert_eq!(
+            std::fs::read_dir(repo.objects.store_ref().path().join("pack"))?
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().extension().map_or(false, |ext| ext == "idx"))
+                .count(),
+            1,
+            "packs superseded by the received one are removed"
+        );
+        for extension in ["promisor", "mtimes"] {
+            assert!(
+                !copy_data_path.with_extension(extension).exists(),
+                "the files accompanying superseded packs are removed as well"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    #[allow(clippy::result_large_err)]
+    fn fetch_from_promisor_remote_marks_received_packs() -> crate::Result<()> {
+        use gix::{bstr::ByteSlice, config::tree::Remote};
+
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (mut repo, _out) = gix::clone::PrepareFetch::new(
+            remote::repo("multi_round/server").path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            gix::open::Options::isolated(),
+        )?
+        .with_local(gix::clone::Local::Disabled)
+        .fetch_only(gix::progress::Discard, &AtomicBool::default())?;
+        let promisor_files = |repo: &gix::Repository| -> std::io::Result<Vec<std::path::PathBuf>> {
+            Ok(std::fs::read_dir(repo.objects.store_ref().path().join("pack"))?
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().map_or(false, |ext| ext == "promisor"))
+                .collect())
+        };
+        assert_eq!(
+            promisor_files(&repo)?.len(),
+            0,
+            "regular remotes don't produce promisor packs"
+        );
+
+        {
+            let mut config = repo.config_snapshot_mut();
+            config.set_subsection_value(&Remote::PROMISOR, "origin", "true")?;
+            config.set_subsection_value(&Remote::PARTIAL_CLONE_FILTER, "origin", "blob:none")?;
+        }
+        let out = repo
+            .find_remote("origin")?
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_refetch(true)
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        let data_path = match out.status {
+            Status::Change { write_pack_bundle, .. } => write_pack_bundle.data_path.expect("a new pack was written"),
+            _ => unreachable!("refetching always receives a pack"),
+        };
+
+        let promisor_files = promisor_files(&repo)?;
+        assert_eq!(
+            promisor_files,
+            [data_path.with_extension("promisor")],
+            "the pack received from the promisor remote is marked as such"
+        );
+        let content = std::fs::read(&promisor_files[0])?;
+        assert!(
+            content.lines().all(|line| line.contains_str(" refs/")),
+            "the file lists the fetched references: {:?}",
+            content.as_bstr()
+        );
+        assert!(!content.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    #[allow(clippy::result_large_err)]
+    fn connectivity_check_passes_if_all_received_objects_are_connected() -> crate::Result<()> {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, _out) = gix::clone::PrepareFetch::new(
+            remote::repo("multi_round/server").path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            gix::open::Options::isolated(),
+        )?
+        .with_local(gix::clone::Local::Disabled)
+        .fetch_only(gix::progress::Discard, &AtomicBool::default())?;
+
+        let out = repo
+            .find_remote("origin")?
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_refetch(true)
+            .with_connectivity_check(true)
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert!(
+            matches!(out.status, Status::Change { .. }),
+            "all objects were received again and traversed, without finding missing objects"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn received_packs_can_be_kept_or_unpacked_into_loose_objects() -> crate::Result {
+        use gix::{config::tree::Transfer, remote::fetch::ReceivedPack};
+
+        for policy in [
+            Some(ReceivedPack::Keep),
+            Some(ReceivedPack::UnpackBelow { limit: 100 }),
+            None,
+        ] {
+            let (mut repo, _tmp) = repo_rw("two-origins");
+            if policy.is_none() {
+                repo.config_snapshot_mut().set_value(&Transfer::UNPACK_LIMIT, "100")?;
+            }
+            let remote = repo.find_remote("changes-on-top-of-origin")?;
+            let mut prepare = remote
+                .connect(Fetch)?
+                .prepare_fetch(gix::progress::Discard, Default::default())?;
+            if let Some(policy) = policy {
+                prepare = prepare.with_received_pack(policy);
+            }
+            let out = prepare.receive(gix::progress::Discard, &AtomicBool::default())?;
+            let write_pack_bundle = match out.status {
+                Status::Change {
+                    write_pack_bundle,
+                    update_refs,
+                    ..
+                } => {
+                    assert!(!update_refs.edits.is_empty(), "{policy:?}");
+                    write_pack_bundle
+                }
+                _ => unreachable!("the remote has new objects"),
+            };
+
+            if policy == Some(ReceivedPack::Keep) {
+                assert!(
+                    write_pack_bundle
+                        .keep_path
+                        .as_deref()
+                        .map_or(false, std::path::Path::is_file),
+                    "the .keep file remains despite refs being updated"
+                );
+                assert!(write_pack_bundle
+                    .data_path
+                    .as_deref()
+                    .map_or(false, std::path::Path::is_file));
+            } else {
+                assert!(write_pack_bundle.index.num_objects < 100, "{policy:?}");
+                assert_eq!(
+                    (
+                        write_pack_bundle.data_path,
+                        write_pack_bundle.index_path,
+                        write_pack_bundle.keep_path
+                    ),
+                    (None, None, None),
+                    "the pack is gone as it was small enough to be unpacked: {policy:?}"
+                );
+                let tip = repo
+                    .find_reference("refs/remotes/changes-on-top-of-origin/main")?
+                    .id()
+                    .to_hex()
+                    .to_string();
+                assert!(
+                    repo.objects
+                        .store_ref()
+                        .path()
+                        .join(&tip[..2])
+                        .join(&tip[2..])
+                        .is_file(),
+                    "received objects are available as loose objects: {policy:?}"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_all_fetches_every_remote_and_reports_failures_individually() -> crate::Result {
+        use gix::config::tree::Remote;
+
+        let (mut repo, _tmp) = repo_rw("two-origins");
+        {
+            let mut config = repo.config_snapshot_mut();
+            config.set_subsection_value(&Remote::URL, "broken", "/does/not/exist")?;
+            config.set_subsection_value(&Remote::URL, "skipped", "/does/not/exist")?;
+            config.set_subsection_value(&Remote::SKIP_FETCH_ALL, "skipped", "true")?;
+        }
+
+        for jobs in [Some(1), Some(0), None] {
+            let out = repo.fetch_all(
+                &mut progress::Discard,
+                &AtomicBool::default(),
+                gix::repository::fetch_all::Options { jobs },
+            )?;
+            assert_eq!(
+                out.iter().map(|o| o.remote_name.to_string()).collect::<Vec<_>>(),
+                ["broken", "changes-on-top-of-origin", "origin"],
+                "remotes are fetched in order of their names, unless they are skipped"
+            );
+            assert!(out[0].result.is_err(), "a broken remote doesn't abort the operation");
+            assert!(out[1].result.is_ok());
+            assert!(out[2].result.is_ok());
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_with_prune_deletes_refs_that_are_gone_on_the_remote() -> crate::Result<()> {
+        let server_tmp = gix_testtools::scripted_fixture_writable("make_remote_repos.sh")?;
+        let server = gix::open_opts(server_tmp.path().join("base"), crate::restricted())?;
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, _out) = gix::clone::PrepareFetch::new(
+            server.path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            crate::restricted(),
+        )?
+        .with_local(gix::clone::Local::Disabled)
+        .fetch_only(gix::progress::Discard, &AtomicBool::default())?;
+        for name in ["refs/heads/a", "refs/heads/b", "refs/tags/b-tag"] {
+            server.find_reference(name)?.delete()?;
+        }
+
+        let fetch = |remote: gix::Remote<'_>, dry_run: bool| -> crate::Result<Vec<String>> {
+            let out = remote
+                .with_refspecs(Some("^refs/heads/b"), Fetch)?
+                .connect(Fetch)?
+                .prepare_fetch(gix::progress::Discard, Default::default())?
+                .with_dry_run(dry_run)
+                .receive(gix::progress::Discard, &AtomicBool::default())?;
+            let update_refs = match out.status {
+                Status::Change { update_refs, .. } | Status::NoPackReceived { update_refs, .. } => update_refs,
+            };
+            Ok(update_refs
+                .pruned
+                .iter()
+                .map(|r| r.name.as_bstr().to_string())
+                .collect())
+        };
+        let exists = |name: &str| repo.try_find_reference(name).map(|r| r.is_some());
+
+        assert_eq!(
+            fetch(repo.find_remote("origin")?, false)?,
+            Vec::<String>::new(),
+            "pruning is off by default"
+        );
+        assert!(exists("refs/remotes/origin/a")?);
+
+        let remote = repo.find_remote("origin")?.with_prune(true);
+        assert_eq!(
+            fetch(remote.clone(), true)?,
+            ["refs/remotes/origin/a"],
+            "negative specs protect refs from pruning"
+        );
+        assert!(exists("refs/remotes/origin/a")?, "dry-run doesn't delete anything");
+
+        assert_eq!(fetch(remote.clone(), false)?, ["refs/remotes/origin/a"]);
+        assert!(!exists("refs/remotes/origin/a")?);
+        assert!(exists("refs/remotes/origin/b")?);
+        assert!(exists("refs/tags/b-tag")?, "tags are only pruned if configured");
+
+        assert_eq!(fetch(remote.with_prune_tags(true), false)?, ["refs/tags/b-tag"]);
+        assert!(!exists("refs/tags/b-tag")?);
+        assert!(exists("refs/tags/i-tag")?);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn prune_without_fetching_deletes_stale_tracking_refs() -> crate::Result<()> {
+        let server_tmp = gix_testtools::scripted_fixture_writable("make_remote_repos.sh")?;
+        let server = gix::open_opts(server_tmp.path().join("base"), crate::restricted())?;
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, _out) = gix::clone::PrepareFetch::new(
+            server.path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            crate::restricted(),
+        )?
+        .with_local(gix::clone::Local::Disabled)
+        .fetch_only(gix::progress::Discard, &AtomicBool::default())?;
+        for name in ["refs/heads/a", "refs/tags/b-tag"] {
+            server.find_reference(name)?.delete()?;
+        }
+        let main_before = repo.find_reference("refs/remotes/origin/main")?.id().detach();
+
+        let prune = |dry_run: bool| -> crate::Result<Vec<String>> {
+            let pruned = repo.find_remote("origin")?.connect(Fetch)?.prune(
+                gix::progress::Discard,
+                gix::remote::prune::Options {
+                    dry_run,
+                    ..Default::default()
+                },
+            )?;
+            Ok(pruned.iter().map(|r| r.name.as_bstr().to_string()).collect())
+        };
+        let exists = |name: &str| repo.try_find_reference(name).map(|r| r.is_some());
+
+        assert_eq!(prune(true)?, ["refs/remotes/origin/a"]);
+        assert!(exists("refs/remotes/origin/a")?, "dry-run doesn't delete anything");
+
+        assert_eq!(prune(false)?, ["refs/remotes/origin/a"]);
+        assert!(!exists("refs/remotes/origin/a")?);
+        assert!(
+            exists("refs/tags/b-tag")?,
+            "tags aren't written by the remote's ref-specs"
+        );
+        assert_eq!(
+            repo.find_reference("refs/remotes/origin/main")?.id().detach(),
+            main_before,
+            "nothing is fetched or updated"
+        );
+        assert_eq!(prune(false)?, Vec::<String>::new(), "nothing left to prune");
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_writes_fetch_head_unless_disabled() -> crate::Result<()> {
+        let server = gix::open_opts(remote::repo_path("base"), crate::restricted())?;
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, _out) = gix::clone::PrepareFetch::new(
+            server.path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            crate::restricted(),
+        )?
+        .with_local(gix::clone::Local::Disabled)
+        .fetch_only(gix::progress::Discard, &AtomicBool::default())?;
+        let fetch_head = repo.path().join("FETCH_HEAD");
+        assert!(!fetch_head.exists(), "clones don't write FETCH_HEAD, just like git");
+
+        let fetch = |write_fetch_head: bool, dry_run: bool| -> crate::Result {
+            repo.find_remote("origin")?
+                .connect(Fetch)?
+                .prepare_fetch(gix::progress::Discard, Default::default())?
+                .with_write_fetch_head(write_fetch_head)
+                .with_dry_run(dry_run)
+                .receive(gix::progress::Discard, &AtomicBool::default())?;
+            Ok(())
+        };
+        fetch(true, true)?;
+        assert!(!fetch_head.exists(), "dry-runs don't write FETCH_HEAD");
+        fetch(false, false)?;
+        assert!(!fetch_head.exists(), "it can be disabled");
+
+        fetch(true, false)?;
+        let content = std::fs::read_to_string(&fetch_head)?;
+        let mut lines = content.lines();
+        let main = server.find_reference("refs/heads/main")?.id();
+        assert!(
+            lines
+                .next()
+                .expect("at least one line")
+                .starts_with(&format!("{main}\t\tbranch 'main' of ")),
+            "the branch configured in branch.main.merge is for merge, and listed first"
+        );
+        assert!(
+            lines.clone().all(|line| line.contains("\tnot-for-merge\t")),
+            "all other refs are not for merge"
+        );
+        let b_tag = server.find_reference("refs/tags/b-tag")?.id();
+        assert!(
+            lines.any(|line| line.starts_with(&format!("{b_tag}\tnot-for-merge\ttag 'b-tag' of "))),
+            "tags are listed with their own object id"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn tag_patterns_limit_implicitly_followed_tags() -> crate::Result<()> {
+        let repo = remote::repo("clone");
+        let tag_mappings = |patterns: Option<&[&str]>| -> crate::Result<Vec<String>> {
+            let remote = repo
+                .find_remote("origin")?
+                .with_fetch_tags(gix::remote::fetch::Tags::All);
+            let mut prepare = remote
+                .connect(Fetch)?
+                .prepare_fetch(gix::progress::Discard, Default::default())?;
+            if let Some(patterns) = patterns {
+                prepare = prepare.with_tag_patterns(patterns.iter().map(|p| (*p).into()))?;
+            }
+            Ok(prepare
+                .ref_map()
+                .mappings
+                .iter()
+                .filter_map(|m| m.local.as_ref().map(ToString::to_string))
+                .filter(|local| local.starts_with("refs/tags/"))
+                .collect())
+        };
+        let all = tag_mappings(None)?;
+        let matching = tag_mappings(Some(&["annotated-*"]))?;
+        assert!(matching.len() < all.len(), "some tags were filtered");
+        assert!(!matching.is_empty());
+        assert!(
+            matching.iter().all(|name| name.starts_with("refs/tags/annotated-")),
+            "only matching tags are followed: {matching:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_with_capability_policy_does_not_use_denied_capabilities() -> crate::Result<()> {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let repo = gix::init_bare(tmp.path())?;
+        let fetch =
+            |options: gix::remote::ref_map::Options, shallow: fetch::Shallow| -> crate::Result<fetch::Outcome> {
+                Ok(repo
+                    .remote_at(remote::repo_path("base"))?
+                    .with_refspecs(Some("+refs/heads/*:refs/remotes/origin/*"), Fetch)?
+                    .connect(Fetch)?
+                    .prepare_fetch(gix::progress::Discard, options)?
+                    .with_shallow(shallow)
+                    .receive(gix::progress::Discard, &AtomicBool::default())?)
+            };
+        let options = gix::remote::ref_map::Options {
+            capability_policy: fetch::CapabilityPolicy::default()
+                .with_denied("shallow")
+                .with_denied("side-band-64k"),
+            ..Default::default()
+        };
+        assert!(options.capability_policy.is_allowed("filter"));
+        assert!(!options.capability_policy.is_allowed("shallow"));
+
+        let err = fetch(
+            options.clone(),
+            fetch::Shallow::DepthAtRemote(1u32.try_into().expect("non-zero")),
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("shallow"),
+            "denied capabilities aren't used even if the server supports them: {err}"
+        );
+
+        let outcome = fetch(options, fetch::Shallow::NoChange)?;
+        assert!(matches!(outcome.status, Status::Change { .. }));
+        assert!(repo.try_find_reference("refs/remotes/origin/main")?.is_some());
+
+        let allow_nothing = gix::remote::ref_map::Options {
+            capability_policy: fetch::CapabilityPolicy {
+                allow: Some(Vec::new()),
+                deny: Vec::new(),
+            },
+            ..Default::default()
+        };
+        assert!(!allow_nothing.capability_policy.is_allowed("agent"));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn prune_settings_are_read_from_configuration() -> crate::Result<()> {
+        let (repo, _tmp) = repo_rw("two-origins");
+        let remote = repo.find_remote("origin")?;
+        assert!(!remote.prune() && !remote.prune_tags(), "off by default");
+
+        let mut config = std::fs::OpenOptions::new()
+            .append(true)
+            .open(repo.path().join("config"))?;
+        std::io::Write::write_all(
+            &mut config,
+            b"[fetch]\n\tprune = true\n\tpruneTags = true\n[remote \"origin\"]\n\tpruneTags = false\n",
+        )?;
+        let repo = gix::open_opts(repo.path(), crate::restricted())?;
+        let remote = repo.find_remote("origin")?;
+        assert!(remote.prune(), "fetch.prune is the default for all remotes");
+        assert!(
+            !remote.prune_tags(),
+            "remote.<name>.pruneTags overrides fetch.pruneTags"
+        );
+        assert!(
+            repo.remote_at("https://example.com/repo")?.prune_tags(),
+            "anonymous remotes use the defaults as well"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_resumable_reuses_objects_of_partial_pack() -> crate::Result<()> {
+        fn clone(
+            dir: &std::path::Path,
+            partial_pack: Option<Vec<u8>>,
+        ) -> crate::Result<(gix::Repository, gix::remote::fetch::Outcome)> {
+            clone_with_lock(dir, partial_pack, false)
+        }
+        fn clone_with_lock(
+            dir: &std::path::Path,
+            partial_pack: Option<Vec<u8>>,
+            locked: bool,
+        ) -> crate::Result<(gix::Repository, gix::remote::fetch::Outcome)> {
+            Ok(gix::clone::PrepareFetch::new(
+                remote::repo("multi_round/server").path(),
+                dir,
+                gix::create::Kind::Bare,
+                Default::default(),
+                gix::open::Options::isolated(),
+            )?
+            .with_local(gix::clone::Local::Disabled)
+            .with_resumable(true)
+            .configure_remote(move |r| {
+                if let Some(pack) = &partial_pack {
+                    let path = partial_pack_path(&r)?;
+                    std::fs::write(&path, pack)?;
+                    if locked {
+                        let mut lock = path.into_os_string();
+                        lock.push(".lock");
+                        std::fs::write(lock, b"")?;
+                    }
+                }
+                Ok(r)
+            })
+            .fetch_only(gix::progress::Discard, &AtomicBool::default())?)
+        }
+        /// Derive the path of the partial pack of a fetch of all references of `remote` like `gix` does.
+        fn partial_pack_path(
+            remote: &gix::Remote<'_>,
+        ) -> Result<std::path::PathBuf, Box<dyn std::error::Error + Send + Sync + 'static>> {
+            let ref_map = remote
+                .clone()
+                .connect(gix::remote::Direction::Fetch)?
+                .ref_map(gix::progress::Discard, Default::default())?;
+            let mut wants: Vec<_> = ref_map
+                .mappings
+                .iter()
+                .filter_map(|mapping| mapping.remote.as_id())
+                .collect();
+            wants.sort();
+            wants.dedup();
+            let mut hasher = gix::features::hash::hasher(gix::hash::Kind::Sha1);
+            hasher.update(&remote.url(gix::remote::Direction::Fetch).expect("set").to_bstring());
+            for id in wants {
+                hasher.update(b"\n");
+                hasher.update(id.to_hex().to_string().as_bytes());
+            }
+            Ok(remote
+                .repo()
+                .objects
+                .store_ref()
+                .path()
+                .join("pack")
+                .join(format!("tmp_gix_partial_pack-{}", gix::ObjectId::from(hasher.digest()))))
+        }
+        fn partial_packs(repo: &gix::Repository) -> crate::Result<usize> {
+            Ok(std::fs::read_dir(repo.objects.store_ref().path().join("pack"))?
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_name().to_string_lossy().starts_with("tmp_gix_partial_pack"))
+                .count())
+        }
+
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, out) = clone(tmp.path(), None)?;
+        let pack = match out.status {
+            Status::Change { write_pack_bundle, .. } => {
+                std::fs::read(write_pack_bundle.data_path.expect("pack was written"))?
+            }
+            _ => unreachable!("a fresh clone receives a pack"),
+        };
+        assert_eq!(
+            partial_packs(&repo)?,
+            0,
+            "the partial pack is removed once the pack was received successfully"
+        );
+
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, out) = clone(tmp.path(), Some(pack.clone()))?;
+        assert!(
+            matches!(out.status, Status::NoPackReceived { .. }),
+            "all objects were restored from the partial pack, so there is nothing left to receive"
+        );
+        assert_eq!(partial_packs(&repo)?, 0);
+        assert_eq!(
+            std::fs::read_dir(repo.objects.store_ref().path().join("pack"))?
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().extension().map_or(false, |ext| ext == "keep"))
+                .count(),
+            0,
+            "the restored pack isn't kept once references point to its objects"
+        );
+
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, out) = clone_with_lock(tmp.path(), Some(pack.clone()), true)?;
+        assert!(
+            matches!(out.status, Status::Change { .. }),
+            "a partial pack locked by another fetch isn't used"
+        );
+        assert_eq!(
+            partial_packs(&repo)?,
+            2,
+            "neither the partial pack nor its lock are touched, as they belong to another fetch"
+        );
+
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, out) = clone(tmp.path(), Some(pack[..pack.len() / 2].to_vec()))?;
+        assert!(
+            matches!(out.status, Status::Change { .. }),
+            "the objects missing from the truncated pack are received"
+        );
+        assert_eq!(partial_packs(&repo)?, 0);
+        let repo = gix::open_opts(repo.git_dir(), gix::open::Options::isolated())?;
+        let outcome = repo.objects.store_ref().verify_integrity(
+            &mut progress::Discard,
+            &AtomicBool::default(),
+            Default::default(),
+        )?;
+        assert_eq!(
+            outcome.index_statistics.len(),
+            2,
+            "the objects restored from the partial pack are kept next to the received pack, and both are valid"
+        );
+        for reference in repo.references()?.all()? {
+            let id = reference?.peel_to_id_in_place()?;
+            for commit in repo.rev_walk([id]).all()? {
+                let commit = repo.find_commit(commit?.id)?;
+                for entry in commit.tree()?.traverse().breadthfirst.files()? {
+                    assert!(repo.has_object(entry.oid), "the history is complete");
+                }
+            }
+        }
+        Ok(())
+    }
+
     #[maybe_async::test(
         feature = "blocking-network-client",
         async(feature = "async-network-client-async-std", async_std::test)
@@ -223,6 +1039,7 @@ mod blocking_and_async_io {
                         negotiate,
                         ..
                     } => {
+                        assert_eq!(negotiate.algorithm, algorithm, "the configured algorithm is used");
                         assert_eq!(
                             negotiate.rounds.len(),
                             expected_negotiation_rounds,
@@ -241,6 +1058,226 @@ mod blocking_and_async_io {
         Ok(())
     }
 
+    #[maybe_async::test(
+        feature = "blocking-network-client",
+        async(feature = "async-network-client-async-std", async_std::test)
+    )]
+    async fn fetch_with_fixed_negotiation_window() -> crate::Result {
+        let (mut client_repo, _tmp) = {
+            let client_repo = remote::repo("multi_round/client");
+            let daemon = spawn_git_daemon_if_async(client_repo.work_dir().expect("non-bare"))?;
+            let tmp = TempDir::new()?;
+            let repo = gix::prepare_clone_bare(
+                daemon.as_ref().map_or_else(
+                    || client_repo.git_dir().to_owned(),
+                    |d| std::path::PathBuf::from(format!("{}/", d.url)),
+                ),
+                tmp.path(),
+            )?
+            .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())
+            .await?
+            .0;
+            (repo, tmp)
+        };
+        client_repo.config_snapshot_mut().set_value(
+            &gix::config::tree::Fetch::NEGOTIATION_ALGORITHM,
+            gix::negotiate::Algorithm::Consecutive.to_string().as_str(),
+        )?;
+
+        let server_repo = remote::repo("multi_round/server");
+        let daemon = spawn_git_daemon_if_async(server_repo.work_dir().expect("non-bare"))?;
+        let remote = into_daemon_remote_if_async(
+            client_repo.remote_at(server_repo.work_dir().expect("non-bare"))?,
+            daemon.as_ref(),
+            None,
+        );
+        let changes = remote
+            .with_refspecs(Some("refs/heads/*:refs/remotes/origin/*"), Fetch)?
+            .connect(Fetch)
+            .await?
+            .prepare_fetch(gix::progress::Discard, Default::default())
+            .await?
+            .with_negotiation_window(gix::remote::fetch::NegotiationWindow::Fixed(
+                1.try_into().expect("non-zero"),
+            ))
+            .receive(gix::progress::Discard, &AtomicBool::default())
+            .await?;
+
+        match changes.status {
+            Status::Change { negotiate, .. } => {
+                assert!(
+                    negotiate.rounds.len() > 4,
+                    "sending a single HAVE per round needs more rounds than the adaptive window: {}",
+                    negotiate.rounds.len()
+                );
+                assert!(
+                    negotiate.rounds.iter().all(|round| round.haves_to_send == 1),
+                    "the window doesn't grow"
+                );
+                assert_eq!(
+                    negotiate.rounds[0].previous_response_acknowledgements,
+                    Default::default(),
+                    "there is no response before the first round"
+                );
+                assert!(
+                    negotiate
+                        .rounds
+                        .iter()
+                        .any(|round| round.previous_response_acknowledgements.common > 0),
+                    "the server acknowledges some of the commits we have in common"
+                );
+            }
+            _ => unreachable!("We expect a pack for sure"),
+        }
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        feature = "blocking-network-client",
+        async(feature = "async-network-client-async-std", async_std::test)
+    )]
+    async fn fetch_with_negotiation_algorithm_override() -> crate::Result {
+        let (mut client_repo, _tmp) = {
+            let client_repo = remote::repo("multi_round/client");
+            let daemon = spawn_git_daemon_if_async(client_repo.work_dir().expect("non-bare"))?;
+            let tmp = TempDir::new()?;
+            let repo = gix::prepare_clone_bare(
+                daemon.as_ref().map_or_else(
+                    || client_repo.git_dir().to_owned(),
+                    |d| std::path::PathBuf::from(format!("{}/", d.url)),
+                ),
+                tmp.path(),
+            )?
+            .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())
+            .await?
+            .0;
+            (repo, tmp)
+        };
+        client_repo.config_snapshot_mut().set_value(
+            &gix::config::tree::Fetch::NEGOTIATION_ALGORITHM,
+            gix::negotiate::Algorithm::Consecutive.to_string().as_str(),
+        )?;
+
+        let server_repo = remote::repo("multi_round/server");
+        let daemon = spawn_git_daemon_if_async(server_repo.work_dir().expect("non-bare"))?;
+        let remote = into_daemon_remote_if_async(
+            client_repo.remote_at(server_repo.work_dir().expect("non-bare"))?,
+            daemon.as_ref(),
+            None,
+        );
+        let changes = remote
+            .with_refspecs(Some("refs/heads/*:refs/remotes/origin/*"), Fetch)?
+            .connect(Fetch)
+            .await?
+            .prepare_fetch(gix::progress::Discard, Default::default())
+            .await?
+            .with_negotiation_algorithm(gix::negotiate::Algorithm::Skipping)
+            .receive(gix::progress::Discard, &AtomicBool::default())
+            .await?;
+
+        match changes.status {
+            Status::Change { negotiate, .. } => {
+                assert_eq!(
+                    negotiate.algorithm,
+                    gix::negotiate::Algorithm::Skipping,
+                    "the algorithm set on the fetch takes precedence over the configuration"
+                );
+                assert_eq!(
+                    negotiate.rounds.len(),
+                    2,
+                    "skipping needs fewer rounds than consecutive"
+                );
+            }
+            _ => unreachable!("We expect a pack for sure"),
+        }
+        Ok(())
+    }
+
+    #[maybe_async::test(
+        feature = "blocking-network-client",
+        async(feature = "async-network-client-async-std", async_std::test)
+    )]
+    async fn fetch_writes_commit_graph_if_enabled() -> crate::Result {
+        let (client_repo, _tmp) = {
+            let client_repo = remote::repo("multi_round/client");
+            let daemon = spawn_git_daemon_if_async(client_repo.work_dir().expect("non-bare"))?;
+            let tmp = TempDir::new()?;
+            let repo = gix::prepare_clone_bare(
+                daemon.as_ref().map_or_else(
+                    || client_repo.git_dir().to_owned(),
+                    |d| std::path::PathBuf::from(format!("{}/", d.url)),
+                ),
+                tmp.path(),
+            )?
+            // Copying local objects would also copy the commit-graph of the source repository.
+            .with_local(gix::clone::Local::Disabled)
+            .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())
+            .await?
+            .0;
+            (repo, tmp)
+        };
+
+        let tips = client_repo
+            .references()?
+            .all()?
+            .filter_map(|r| r.map(|r| r.target().try_id().map(ToOwned::to_owned)).transpose())
+            .collect::<Result<Vec<_>, _>>()?;
+        let initial = client_repo.write_commit_graph(tips)?.expect("commits to write");
+
+        let server_repo = remote::repo("multi_round/server");
+        let daemon = spawn_git_daemon_if_async(server_repo.work_dir().expect("non-bare"))?;
+        for write_commit_graph in [true, false] {
+            let remote = into_daemon_remote_if_async(
+                client_repo.remote_at(server_repo.work_dir().expect("non-bare"))?,
+                daemon.as_ref(),
+                None,
+            );
+            let changes = remote
+                .with_refspecs(Some("refs/heads/*:refs/remotes/origin/*"), Fetch)?
+                .connect(Fetch)
+                .await?
+                .prepare_fetch(gix::progress::Discard, Default::default())
+                .await?
+                .with_write_commit_graph(write_commit_graph)
+                .receive(gix::progress::Discard, &AtomicBool::default())
+                .await?;
+
+            if !write_commit_graph {
+                assert!(
+                    changes.commit_graph.is_none(),
+                    "it is disabled, and nothing changed the second time around either"
+                );
+                continue;
+            }
+            let outcome = changes.commit_graph.expect("refs were updated, so commits were added");
+            assert_eq!(
+                outcome.num_layers, 2,
+                "the new commits are added as layer on top of the existing commit-graph"
+            );
+            assert_eq!(outcome.num_commits, 2, "only the fetched commits are written");
+            assert!(initial.path.is_file(), "the existing layer is kept as is");
+            let verify = std::process::Command::new(gix::path::env::exe_invocation())
+                .args(["commit-graph", "verify"])
+                .current_dir(client_repo.git_dir())
+                .output()?;
+            assert!(
+                verify.status.success(),
+                "git accepts the commit-graph chain: {}",
+                String::from_utf8_lossy(&verify.stderr)
+            );
+            let graph = client_repo.commit_graph()?;
+            assert_eq!(graph.num_commits(), initial.num_commits + outcome.num_commits);
+            for remote_ref in client_repo.references()?.remote_branches()? {
+                let id = remote_ref?.peel_to_id_in_place()?;
+                assert!(
+                    graph.lookup(id).is_some(),
+                    "all fetched tips are part of the commit-graph"
+                );
+            }
+        }
+        Ok(())
+    }
+
     #[maybe_async::test(
         feature = "blocking-network-client",
         async(feature = "async-network-client-async-std", async_std::test)
@@ -0,0 +1,698 @@
+use std::sync::atomic::AtomicBool;
+
+use gix::remote::{push::Mode, Direction::Push};
+use gix_testtools::tempfile::TempDir;
+
+fn local_and_empty_remote() -> crate::Result<(gix::Repository, TempDir, TempDir)> {
+    let local_tmp = gix_testtools::scripted_fixture_writable("make_remote_repos.sh")?;
+    let local = gix::open_opts(local_tmp.path().join("base"), crate::restricted())?;
+    let remote_tmp = TempDir::new()?;
+    gix::init_bare(remote_tmp.path())?;
+    Ok((local, local_tmp, remote_tmp))
+}
+
+fn push(local: &gix::Repository, remote: &TempDir, specs: &[&str]) -> crate::Result<gix::remote::push::Outcome> {
+    Ok(local
+        .remote_at(remote.path())?
+        .with_refspecs(specs.iter().copied(), Push)?
+        .connect(Push)?
+        .prepare_push(gix::progress::Discard, Vec::new())?
+        .send(gix::progress::Discard, &AtomicBool::default())?)
+}
+
+fn modes(outcome: &gix::remote::push::Outcome) -> Vec<(String, Mode)> {
+    outcome
+        .updates
+        .iter()
+        .map(|update| (update.remote.to_string(), update.mode.clone()))
+        .collect()
+}
+
+fn id(repo: &gix::Repository, name: &str) -> crate::Result<gix::ObjectId> {
+    Ok(repo.find_reference(name)?.id().detach())
+}
+
+#[test]
+fn create_then_fast_forward_then_up_to_date() -> crate::Result {
+    let (local, _local_tmp, remote_tmp) = local_and_empty_remote()?;
+
+    let out = push(
+        &local,
+        &remote_tmp,
+        &["refs/heads/g:refs/heads/main", "refs/tags/b-tag"],
+    )?;
+    assert_eq!(
+        modes(&out),
+        [
+            ("refs/heads/main".into(), Mode::Create),
+            ("refs/tags/b-tag".into(), Mode::Create)
+        ]
+    );
+    assert!(out.num_objects > 0, "a pack was sent");
+    let report = out.report.expect("updates were sent");
+    assert_eq!(report.unpack_error, None);
+    assert!(report.refs.iter().all(|status| status.rejection.is_none()));
+
+    let remote = gix::open_opts(remote_tmp.path(), crate::restricted())?;
+    assert_eq!(id(&remote, "refs/heads/main")?, id(&local, "refs/heads/g")?);
+    let tag_id = id(&local, "refs/tags/b-tag")?;
+    assert_eq!(
+        id(&remote, "refs/tags/b-tag")?,
+        tag_id,
+        "annotated tags are pushed as is"
+    );
+    assert_eq!(remote.find_object(tag_id)?.kind, gix::object::Kind::Tag);
+
+    let out = push(&local, &remote_tmp, &["main"])?;
+    assert_eq!(
+        modes(&out),
+        [("refs/heads/main".into(), Mode::FastForward)],
+        "partial names are qualified like the local reference they refer to"
+    );
+    let remote = gix::open_opts(remote_tmp.path(), crate::restricted())?;
+    let main = id(&local, "refs/heads/main")?;
+    assert_eq!(id(&remote, "refs/heads/main")?, main);
+    assert_eq!(
+        remote.find_object(main)?.into_commit().tree()?.id,
+        local.find_object(main)?.into_commit().tree()?.id,
+        "the tree of the new tip was sent as well"
+    );
+
+    let out = push(&local, &remote_tmp, &["main"])?;
+    assert_eq!(modes(&out), [("refs/heads/main".into(), Mode::UpToDate)]);
+    assert!(out.report.is_none(), "nothing was sent");
+    assert_eq!(out.num_objects, 0);
+    Ok(())
+}
+
+#[test]
+fn fast_forward_only_sends_objects_the_remote_does_not_have() -> crate::Result {
+    let (local, _local_tmp, remote_tmp) = local_and_empty_remote()?;
+    push(&local, &remote_tmp, &["main"])?;
+    let main = id(&local, "refs/heads/main")?;
+    let tree = local.find_object(main)?.into_commit().tree_id()?.detach();
+    let new = local
+        .commit("refs/heads/main", "unchanged tree", tree, [main])?
+        .detach();
+
+    let out = local
+        .remote_at(remote_tmp.path())?
+        .with_refspecs(Some("main"), Push)?
+        .connect(Push)?
+        .prepare_push(gix::progress::Discard, Vec::new())?
+        .with_dry_run(true)
+        .send(gix::progress::Discard, &AtomicBool::default())?;
+    assert_eq!(modes(&out), [("refs/heads/main".into(), Mode::FastForward)]);
+    let objects = out.dry_run.expect("an update would have been sent").objects;
+    assert_eq!(
+        objects,
+        [new],
+        "the tree of the new commit and all its blobs are already on the remote"
+    );
+    let remote = gix::open_opts(remote_tmp.path(), crate::restricted())?;
+    assert!(
+        objects.iter().all(|id| !remote.has_object(id)),
+        "trees and blobs of the commits the remote already has aren't sent again"
+    );
+    Ok(())
+}
+
+#[test]
+fn non_fast_forward_is_rejected_unless_forced() -> crate::Result {
+    let (local, _local_tmp, remote_tmp) = local_and_empty_remote()?;
+    push(&local, &remote_tmp, &["main"])?;
+
+    let out = push(&local, &remote_tmp, &["refs/heads/c:refs/heads/main"])?;
+    assert_eq!(modes(&out), [("refs/heads/main".into(), Mode::RejectedNonFastForward)]);
+    assert!(out.report.is_none(), "rejected updates aren't sent");
+    let remote = gix::open_opts(remote_tmp.path(), crate::restricted())?;
+    assert_eq!(id(&remote, "refs/heads/main")?, id(&local, "refs/heads/main")?);
+
+    let out = push(&local, &remote_tmp, &["+refs/heads/c:refs/heads/main"])?;
+    assert_eq!(modes(&out), [("refs/heads/main".into(), Mode::Forced)]);
+    let remote = gix::open_opts(remote_tmp.path(), crate::restricted())?;
+    assert_eq!(id(&remote, "refs/heads/main")?, id(&local, "refs/heads/c")?);
+    Ok(())
+}
+
+#[test]
+fn delete_and_rejection_by_remote() -> crate::Result {
+    let (local, _local_tmp, remote_tmp) = local_and_empty_remote()?;
+    push(&local, &remote_tmp, &["main", "main:other"])?;
+
+    let out = push(&local, &remote_tmp, &[":other"])?;
+    assert_eq!(modes(&out), [("refs/heads/other".into(), Mode::Delete)]);
+    let remote = gix::open_opts(remote_tmp.path(), crate::restricted())?;
+    assert!(remote.try_find_reference("refs/heads/other")?.is_none());
+
+    let mut config = std::fs::OpenOptions::new()
+        .append(true)
+        .open(remote_tmp.path().join("config"))?;
+    std::io::Write::write_all(&mut config, b"[receive]\n\tdenyNonFastForwards = true\n")?;
+
+    let out = push(&local, &remote_tmp, &["+refs/heads/c:refs/heads/main"])?;
+    let update = &out.updates[0];
+    assert!(
+        matches!(&update.mode, Mode::RejectedByRemote { reason } if reason == "non-fast-forward"),
+        "the remote refuses forced updates: {:?}",
+        update.mode
+    );
+    assert!(update.mode.is_rejected());
+    let remote = gix::open_opts(remote_tmp.path(), crate::restricted())?;
+    assert_eq!(id(&remote, "refs/heads/main")?, id(&local, "refs/heads/main")?);
+    Ok(())
+}
+
+#[test]
+fn delete_refs_reports_each_deletion_and_ignores_push_specs() -> crate::Result {
+    let (local, _local_tmp, remote_tmp) = local_and_empty_remote()?;
+    push(&local, &remote_tmp, &["main", "main:topic", "main:protected"])?;
+
+    let remote = local
+        .remote_at(remote_tmp.path())?
+        .with_refspecs(Some("refs/heads/c:refs/heads/main"), Push)?;
+    let out = remote.delete_refs(
+        ["topic", "refs/heads/missing"],
+        gix::progress::Discard,
+        &AtomicBool::default(),
+    )?;
+    assert_eq!(
+        modes(&out),
+        [
+            ("refs/heads/topic".into(), Mode::Delete),
+            ("refs/heads/missing".into(), Mode::RejectedNoSuchRef)
+        ],
+        "the push ref-specs of the remote aren't used, and missing references can't be deleted"
+    );
+    let remote_repo = gix::open_opts(remote_tmp.path(), crate::restricted())?;
+    assert!(remote_repo.try_find_reference("refs/heads/topic")?.is_none());
+    assert_eq!(
+        id(&remote_repo, "refs/heads/main")?,
+        id(&local, "refs/heads/main")?,
+        "main wasn't touched"
+    );
+
+    let mut config = std::fs::OpenOptions::new()
+        .append(true)
+        .open(remote_tmp.path().join("config"))?;
+    std::io::Write::write_all(&mut config, b"[receive]\n\tdenyDeletes = true\n")?;
+    let out = remote.delete_refs(Some("protected"), gix::progress::Discard, &AtomicBool::default())?;
+    let update = &out.updates[0];
+    assert!(
+        matches!(&update.mode, Mode::RejectedByRemote { reason } if reason == "deletion prohibited"),
+        "the remote refuses to delete references: {:?}",
+        update.mode
+    );
+    assert!(remote_repo.try_find_reference("refs/heads/protected")?.is_some());
+    Ok(())
+}
+
+#[test]
+fn atomic_pushes_apply_all_updates_or_none() -> crate::Result {
+    let (local, _local_tmp, remote_tmp) = local_and_empty_remote()?;
+    push(&local, &remote_tmp, &["main"])?;
+
+    let remote = local
+        .remote_at(remote_tmp.path())?
+        .with_refspecs(["refs/heads/c:refs/heads/main", "refs/heads/b:refs/heads/other"], Push)?;
+    let prepare = remote.connect(Push)?.prepare_push(gix::progress::Discard, Vec::new())?;
+    assert_eq!(
+        prepare.updates().iter().map(|u| u.mode.clone()).collect::<Vec<_>>(),
+        [Mode::RejectedNonFastForward, Mode::Create]
+    );
+    let out = prepare
+        .with_atomic(true)
+        .send(gix::progress::Discard, &AtomicBool::default())?;
+    assert_eq!(
+        modes(&out),
+        [
+            ("refs/heads/main".into(), Mode::RejectedNonFastForward),
+            ("refs/heads/other".into(), Mode::RejectedAtomicPushFailed)
+        ],
+        "a single rejection prevents all other updates"
+    );
+    assert!(out.report.is_none(), "nothing was sent");
+    let remote = gix::open_opts(remote_tmp.path(), crate::restricted())?;
+    assert!(remote.try_find_reference("refs/heads/other")?.is_none());
+
+    let out = local
+        .remote_at(remote_tmp.path())?
+        .with_refspecs(["+refs/heads/c:refs/heads/main", "refs/heads/b:refs/heads/other"], Push)?
+        .connect(Push)?
+        .prepare_push(gix::progress::Discard, Vec::new())?
+        .with_atomic(true)
+        .send(gix::progress::Discard, &AtomicBool::default())?;
+    assert_eq!(
+        modes(&out),
+        [
+            ("refs/heads/main".into(), Mode::Forced),
+            ("refs/heads/other".into(), Mode::Create)
+        ]
+    );
+    assert!(out.updates.iter().all(|u| !u.mode.is_rejected()));
+    let remote = gix::open_opts(remote_tmp.path(), crate::restricted())?;
+    assert_eq!(id(&remote, "refs/heads/main")?, id(&local, "refs/heads/c")?);
+    assert_eq!(id(&remote, "refs/heads/other")?, id(&local, "refs/heads/b")?);
+    Ok(())
+}
+
+fn leased_update(
+    remote: &gix::Remote<'_>,
+    name: &str,
+    expected: gix::remote::push::lease::Expected,
+) -> crate::Result<gix::remote::push::Update> {
+    Ok(remote
+        .connect(Push)?
+        .prepare_push(gix::progress::Discard, Vec::new())?
+        .with_lease(name.into(), expected)?
+        .updates()[0]
+        .clone())
+}
+
+#[test]
+fn force_with_lease() -> crate::Result {
+    use gix::remote::push::lease::Expected;
+
+    let (local, _local_tmp, remote_tmp) = local_and_empty_remote()?;
+    push(&local, &remote_tmp, &["main"])?;
+    let main = id(&local, "refs/heads/main")?;
+    let c = id(&local, "refs/heads/c")?;
+    let remote = local
+        .remote_at(remote_tmp.path())?
+        .with_refspecs(["refs/heads/c:refs/heads/main"], Push)?
+        .with_refspecs(["+refs/heads/*:refs/remotes/origin/*"], gix::remote::Direction::Fetch)?;
+
+    let update = leased_update(&remote, "main", Expected::Id(c))?;
+    assert_eq!(update.mode, Mode::RejectedStale, "the remote doesn't point to c");
+    assert_eq!(update.lease, Some(Some(c)));
+    assert_eq!(
+        leased_update(&remote, "refs/heads/main", Expected::Absent)?.mode,
+        Mode::RejectedStale,
+        "the remote reference exists"
+    );
+    assert_eq!(
+        leased_update(&remote, "main", Expected::TrackingRef)?.mode,
+        Mode::RejectedStale,
+        "there is no remote tracking reference yet"
+    );
+    let update = leased_update(&remote, "other", Expected::Absent)?;
+    assert_eq!(
+        update.mode,
+        Mode::RejectedNonFastForward,
+        "leases for references that aren't pushed are ignored"
+    );
+    assert_eq!(update.lease, None);
+
+    local.reference(
+        "refs/remotes/origin/main",
+        main,
+        gix::refs::transaction::PreviousValue::MustNotExist,
+        "fake fetch",
+    )?;
+    let out = remote
+        .connect(Push)?
+        .prepare_push(gix::progress::Discard, Vec::new())?
+        .with_lease("main".into(), Expected::TrackingRef)?
+        .send(gix::progress::Discard, &AtomicBool::default())?;
+    assert_eq!(
+        modes(&out),
+        [("refs/heads/main".into(), Mode::Forced)],
+        "the lease holds, so the non-fast-forward update is forced"
+    );
+    assert_eq!(out.updates[0].lease, Some(Some(main)));
+    let remote = gix::open_opts(remote_tmp.path(), crate::restricted())?;
+    assert_eq!(id(&remote, "refs/heads/main")?, c);
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn push_options_are_passed_to_hooks_of_the_remote() -> crate::Result {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (local, _local_tmp, remote_tmp) = local_and_empty_remote()?;
+    let remote = local.remote_at(remote_tmp.path())?.with_refspecs(Some("main"), Push)?;
+    let err = remote
+        .connect(Push)?
+        .prepare_push(gix::progress::Discard, Vec::new())?
+        .with_options(Some("ci.skip"))
+        .send(gix::progress::Discard, &AtomicBool::default())
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            gix::remote::push::Error::Push(gix::protocol::push::Error::MissingCapability { name: "push-options" })
+        ),
+        "push options must be advertised by the remote"
+    );
+
+    let mut config = std::fs::OpenOptions::new()
+        .append(true)
+        .open(remote_tmp.path().join("config"))?;
+    std::io::Write::write_all(&mut config, b"[receive]\n\tadvertisePushOptions = true\n")?;
+    let hook = remote_tmp.path().join("hooks").join("pre-receive");
+    std::fs::create_dir_all(hook.parent().expect("in hooks dir"))?;
+    std::fs::write(
+        &hook,
+        "#!/bin/sh\necho \"$GIT_PUSH_OPTION_COUNT $GIT_PUSH_OPTION_0 $GIT_PUSH_OPTION_1\" > push-options\n",
+    )?;
+    std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755))?;
+
+    let out = remote
+        .connect(Push)?
+        .prepare_push(gix::progress::Discard, Vec::new())?
+        .with_options(["ci.skip", "merge_request.create"])
+        .send(gix::progress::Discard, &AtomicBool::default())?;
+    assert_eq!(modes(&out), [("refs/heads/main".into(), Mode::Create)]);
+    assert_eq!(
+        std::fs::read_to_string(remote_tmp.path().join("push-options"))?,
+        "2 ci.skip merge_request.create\n"
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn signed_pushes_send_a_push_certificate() -> crate::Result {
+    use std::os::unix::fs::PermissionsExt;
+
+    const SIGNATURE: &str = "-----BEGIN PGP SIGNATURE-----\n\nnot-a-signature\n-----END PGP SIGNATURE-----\n";
+    let (local, _local_tmp, remote_tmp) = local_and_empty_remote()?;
+    let remote = local.remote_at(remote_tmp.path())?.with_refspecs(Some("main"), Push)?;
+    let err = remote
+        .connect(Push)?
+        .prepare_push(gix::progress::Discard, Vec::new())?
+        .with_certificate(|_| unreachable!("the remote can't accept certificates"))
+        .send(gix::progress::Discard, &AtomicBool::default())
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            gix::remote::push::Error::Push(gix::protocol::push::Error::MissingCapability { name: "push-cert" })
+        ),
+        "push certificates must be supported by the remote"
+    );
+
+    let mut config = std::fs::OpenOptions::new()
+        .append(true)
+        .open(remote_tmp.path().join("config"))?;
+    std::io::Write::write_all(&mut config, b"[receive]\n\tcertNonceSeed = seed\n")?;
+    let hook = remote_tmp.path().join("hooks").join("pre-receive");
+    std::fs::create_dir_all(hook.parent().expect("in hooks dir"))?;
+    std::fs::write(
+        &hook,
+        "#!/bin/sh\necho \"$GIT_PUSH_CERT_NONCE_STATUS\" > push-cert-nonce-status\ngit cat-file blob \"$GIT_PUSH_CERT\" > push-cert\n",
+    )?;
+    std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755))?;
+
+    let out = remote
+        .connect(Push)?
+        .prepare_push(gix::progress::Discard, Vec::new())?
+        .with_certificate(|certificate| {
+            assert!(certificate.starts_with(b"certificate version 0.1\npusher gitoxide <gitoxide@localhost> "));
+            Ok(SIGNATURE.into())
+        })
+        .send(gix::progress::Discard, &AtomicBool::default())?;
+    assert_eq!(modes(&out), [("refs/heads/main".into(), Mode::Create)]);
+    assert_eq!(
+        std::fs::read_to_string(remote_tmp.path().join("push-cert-nonce-status"))?,
+        "OK\n",
+        "the nonce handed out by the remote was signed"
+    );
+    let certificate = std::fs::read_to_string(remote_tmp.path().join("push-cert"))?;
+    let main = id(&local, "refs/heads/main")?;
+    assert!(
+        certificate.contains(&format!("\n\n{} {main} refs/heads/main\n", local.object_hash().null())),
+        "the certificate contains the commands: {certificate}"
+    );
+    assert!(certificate.ends_with(SIGNATURE), "the signature is transmitted as well");
+    Ok(())
+}
+
+#[test]
+fn negative_specs_exclude_local_refs() -> crate::Result {
+    let (local, _local_tmp, remote_tmp) = local_and_empty_remote()?;
+
+    let out = push(&local, &remote_tmp, &["refs/heads/*:refs/heads/*", "^refs/heads/main"])?;
+    let names: Vec<_> = modes(&out).into_iter().map(|(name, _mode)| name).collect();
+    assert!(names.iter().any(|name| name == "refs/heads/g"));
+    assert!(
+        !names.iter().any(|name| name == "refs/heads/main"),
+        "main was excluded: {names:?}"
+    );
+
+    let out = push(&local, &remote_tmp, &[":", "^refs/heads/g"])?;
+    let modes = modes(&out);
+    assert_eq!(
+        modes.len(),
+        names.len() - 1,
+        "all matching branches but the excluded one are considered"
+    );
+    assert!(modes
+        .iter()
+        .all(|(name, mode)| name != "refs/heads/g" && *mode == Mode::UpToDate));
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn pre_push_hook_receives_updates_and_can_abort_the_push() -> crate::Result {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (local, _local_tmp, remote_tmp) = local_and_empty_remote()?;
+    let remote = local
+        .remote_at(remote_tmp.path())?
+        .with_refspecs(["main", "refs/heads/a:refs/heads/renamed"], Push)?;
+    let hook = local.git_dir().join("hooks").join("pre-push");
+    let input = local.git_dir().join("pre-push-input");
+    std::fs::create_dir_all(hook.parent().expect("in hooks dir"))?;
+    std::fs::write(
+        &hook,
+        format!("#!/bin/sh\n{{ echo \"$1 $2\"; cat; }} > {input:?}\nexit 1\n"),
+    )?;
+    std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755))?;
+
+    let err = remote
+        .connect(Push)?
+        .prepare_push(gix::progress::Discard, Vec::new())?
+        .send(gix::progress::Discard, &AtomicBool::default())
+        .unwrap_err();
+    assert!(
+        matches!(err, gix::remote::push::Error::PrePushHook { .. }),
+        "a failing hook aborts the push: {err:?}"
+    );
+    assert_eq!(
+        gix::open(remote_tmp.path())?.references()?.all()?.count(),
+        0,
+        "nothing was sent"
+    );
+    let url = remote_tmp.path().display().to_string();
+    let null = gix::ObjectId::null(local.object_hash());
+    assert_eq!(
+        std::fs::read_to_string(&input)?,
+        format!(
+            "{url} {url}\nrefs/heads/main {main} refs/heads/main {null}\nrefs/heads/a {a} refs/heads/renamed {null}\n",
+            main = id(&local, "main")?,
+            a = id(&local, "a")?,
+        ),
+        "the remote is unnamed, so its URL is used as name"
+    );
+
+    let out = remote
+        .connect(Push)?
+        .prepare_push(gix::progress::Discard, Vec::new())?
+        .with_hooks(false)
+        .send(gix::progress::Discard, &AtomicBool::default())?;
+    assert_eq!(
+        modes(&out),
+        [
+            ("refs/heads/main".into(), Mode::Create),
+            ("refs/heads/renamed".into(), Mode::Create)
+        ],
+        "hooks can be disabled"
+    );
+    Ok(())
+}
+
+fn push_without_specs(remote: gix::Remote<'_>) -> crate::Result<gix::remote::push::Outcome> {
+    Ok(remote
+        .connect(Push)?
+        .prepare_push(gix::progress::Discard, Vec::new())?
+        .send(gix::progress::Discard, &AtomicBool::default())?)
+}
+
+#[test]
+fn push_default_determines_what_to_push_without_ref_specs() -> crate::Result {
+    use gix::config::tree::{Branch, Push as PushKeys, Remote};
+
+    let (mut local, _local_tmp, remote_tmp) = local_and_empty_remote()?;
+    local.config_snapshot_mut().set_value(&PushKeys::DEFAULT, "nothing")?;
+    let err = local
+        .remote_at(remote_tmp.path())?
+        .connect(Push)?
+        .prepare_push(gix::progress::Discard, Vec::new())
+        .err()
+        .expect("the push is refused");
+    assert!(
+        matches!(err, gix::remote::push::prepare::Error::MissingRefSpecs),
+        "nothing is pushed by default: {err:?}"
+    );
+
+    local.config_snapshot_mut().set_value(&PushKeys::DEFAULT, "simple")?;
+    let out = push_without_specs(local.remote_at(remote_tmp.path())?)?;
+    assert_eq!(
+        modes(&out),
+        [("refs/heads/main".into(), Mode::Create)],
+        "without upstream remote, 'simple' pushes the current branch to the same name"
+    );
+
+    local.config_snapshot_mut().set_value(&PushKeys::DEFAULT, "upstream")?;
+    let err = local
+        .remote_at(remote_tmp.path())?
+        .connect(Push)?
+        .prepare_push(gix::progress::Discard, Vec::new())
+        .err()
+        .expect("the push is refused");
+    assert!(
+        matches!(err, gix::remote::push::prepare::Error::NotUpstreamRemote { .. }),
+        "'upstream' only pushes to the remote the branch is fetched from: {err:?}"
+    );
+
+    {
+        let mut config = local.config_snapshot_mut();
+        let url = remote_tmp.path().display().to_string();
+        config.set_subsection_value(&Remote::URL, "origin", url.as_str())?;
+        config.set_subsection_value(&Branch::REMOTE, "main", "origin")?;
+        config.set_subsection_value(&Branch::MERGE, "main", "refs/heads/upstream")?;
+    }
+    let out = push_without_specs(local.find_remote("origin")?)?;
+    assert_eq!(
+        modes(&out),
+        [("refs/heads/upstream".into(), Mode::Create)],
+        "'upstream' pushes to the configured upstream branch"
+    );
+
+    local.config_snapshot_mut().set_value(&PushKeys::DEFAULT, "simple")?;
+    let err = local
+        .find_remote("origin")?
+        .connect(Push)?
+        .prepare_push(gix::progress::Discard, Vec::new())
+        .err()
+        .expect("the push is refused");
+    assert!(
+        matches!(err, gix::remote::push::prepare::Error::UpstreamNameMismatch { .. }),
+        "'simple' refuses to push to an upstream of a different name: {err:?}"
+    );
+
+    local.config_snapshot_mut().set_value(&PushKeys::DEFAULT, "matching")?;
+    let out = push_without_specs(local.find_remote("origin")?)?;
+    assert_eq!(
+        modes(&out),
+        [("refs/heads/main".into(), Mode::UpToDate)],
+        "'matching' pushes all branches that exist on both sides"
+    );
+    Ok(())
+}
+
+#[test]
+fn mirror_pushes_all_refs_and_deletes_those_missing_locally() -> crate::Result {
+    use gix::config::tree::Remote;
+
+    let (mut local, _local_tmp, remote_tmp) = local_and_empty_remote()?;
+    push(&local, &remote_tmp, &["refs/heads/main:refs/heads/extra"])?;
+    {
+        let mut config = local.config_snapshot_mut();
+        let url = remote_tmp.path().display().to_string();
+        config.set_subsection_value(&Remote::URL, "origin", url.as_str())?;
+        config.set_subsection_value(&Remote::MIRROR, "origin", "true")?;
+    }
+    let remote = local.find_remote("origin")?;
+    assert!(remote.mirror());
+
+    let out = push_without_specs(remote)?;
+    let local_refs = local
+        .references()?
+        .all()?
+        .map(|r| r.map(|r| r.name().as_bstr().to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut expected: Vec<_> = local_refs.into_iter().map(|name| (name, Mode::Create)).collect();
+    expected.push(("refs/heads/extra".into(), Mode::Delete));
+    let mut actual = modes(&out);
+    actual.sort_by(|a, b| a.0.cmp(&b.0));
+    expected.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        actual, expected,
+        "all local references are created, and the one that only exists on the remote is deleted"
+    );
+    let remote_repo = gix::open_opts(remote_tmp.path(), crate::restricted())?;
+    assert!(remote_repo.try_find_reference("refs/heads/extra")?.is_none());
+
+    let out = push_without_specs(local.find_remote("origin")?)?;
+    assert!(
+        out.updates.iter().all(|update| update.mode == Mode::UpToDate),
+        "now both sides are the same"
+    );
+
+    let out = push_without_specs(
+        local
+            .find_remote("origin")?
+            .with_refspecs(Some("refs/heads/main:refs/heads/other"), Push)?,
+    )?;
+    assert_eq!(
+        modes(&out),
+        [("refs/heads/other".into(), Mode::Create)],
+        "explicit ref-specs disable mirroring"
+    );
+    Ok(())
+}
+
+#[test]
+fn dry_run_reports_what_would_be_sent_without_changing_the_remote() -> crate::Result {
+    let (local, _local_tmp, remote_tmp) = local_and_empty_remote()?;
+    let dry_run = |specs: &[&str]| -> crate::Result<gix::remote::push::Outcome> {
+        Ok(local
+            .remote_at(remote_tmp.path())?
+            .with_refspecs(specs.iter().copied(), Push)?
+            .connect(Push)?
+            .prepare_push(gix::progress::Discard, Vec::new())?
+            .with_dry_run(true)
+            .send(gix::progress::Discard, &AtomicBool::default())?)
+    };
+
+    let out = dry_run(&["main"])?;
+    assert_eq!(modes(&out), [("refs/heads/main".into(), Mode::Create)]);
+    assert!(out.report.is_none(), "nothing was sent");
+    let objects = out.dry_run.expect("an update would have been sent");
+    assert_eq!(objects.objects.len(), out.num_objects);
+    assert!(
+        objects.objects.contains(&id(&local, "refs/heads/main")?),
+        "the new tip is part of the pack"
+    );
+    assert!(
+        objects.estimated_pack_size > 32,
+        "there is more than the header and the trailer"
+    );
+    assert!(
+        gix::open_opts(remote_tmp.path(), crate::restricted())?
+            .try_find_reference("refs/heads/main")?
+            .is_none(),
+        "the remote wasn't changed"
+    );
+
+    let sent = push(&local, &remote_tmp, &["main"])?;
+    assert_eq!(
+        sent.num_objects, out.num_objects,
+        "the dry-run predicted the amount of objects correctly"
+    );
+
+    let out = dry_run(&["main"])?;
+    assert_eq!(modes(&out), [("refs/heads/main".into(), Mode::UpToDate)]);
+    assert!(out.dry_run.is_none(), "there is nothing to send");
+
+    let out = dry_run(&[":main"])?;
+    assert_eq!(modes(&out), [("refs/heads/main".into(), Mode::Delete)]);
+    let objects = out.dry_run.expect("the deletion would have been sent");
+    assert!(objects.objects.is_empty(), "deletions don't need a pack");
+    assert_eq!(out.num_objects, 0);
+    Ok(())
+}
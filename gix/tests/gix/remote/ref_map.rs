@@ -85,4 +85,126 @@ mod blocking_and_async_io {
         }
         Ok(())
     }
+
+    #[maybe_async::test(
+        feature = "blocking-network-client",
+        async(feature = "async-network-client-async-std", async_std::test)
+    )]
+    async fn list_refs() -> crate::Result {
+        use gix::protocol::handshake::Ref;
+
+        let daemon = spawn_git_daemon_if_async(remote::repo_path("base"))?;
+        for version in [
+            gix::protocol::transport::Protocol::V1,
+            gix::protocol::transport::Protocol::V2,
+        ] {
+            let mut repo = remote::repo("clone");
+            repo.config_snapshot_mut()
+                .set_raw_value(&Protocol::VERSION, (version as u8).to_string().as_str())?;
+            let remote = into_daemon_remote_if_async(repo.find_remote("origin")?, daemon.as_ref(), None);
+
+            let refs = remote.list_refs(progress::Discard, Default::default()).await?;
+            assert!(
+                refs.iter()
+                    .any(|r| matches!(r, Ref::Symbolic { full_ref_name, .. } if full_ref_name == "HEAD")),
+                "{version:?}: symbolic refs are listed by default"
+            );
+
+            let tags = remote
+                .list_refs(
+                    progress::Discard,
+                    gix::remote::list_refs::Options {
+                        prefixes: vec!["refs/tags/".into()],
+                        ..Default::default()
+                    },
+                )
+                .await?;
+            assert!(
+                tags.iter().all(|r| r.unpack().0.starts_with(b"refs/tags/")),
+                "{version:?}: only refs matching the prefix are listed"
+            );
+            assert!(
+                tags.iter()
+                    .any(|r| matches!(r, Ref::Peeled { full_ref_name, .. } if full_ref_name == "refs/tags/i-tag")),
+                "{version:?}: annotated tags are peeled by default"
+            );
+
+            let refs = remote
+                .list_refs(
+                    progress::Discard,
+                    gix::remote::list_refs::Options {
+                        symrefs: false,
+                        peel: false,
+                        ..Default::default()
+                    },
+                )
+                .await?;
+            assert!(
+                refs.iter().all(|r| matches!(r, Ref::Direct { .. })),
+                "{version:?}: without symrefs and peeling, all refs are direct"
+            );
+            assert!(
+                refs.iter().any(|r| r.unpack().0 == "HEAD"),
+                "{version:?}: symbolic refs are still listed, but like the ref they point to"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn object_info() -> crate::Result {
+        let tmp = gix_testtools::scripted_fixture_writable("make_remote_repos.sh")?;
+        let server_path = tmp.path().join("base");
+        let server = gix::open_opts(&server_path, crate::restricted())?;
+        let commit_id = server.head_id()?.detach();
+        let tree_id = server.head_commit()?.tree_id()?.detach();
+        let missing_id = gix::ObjectId::from_hex(b"ffffffffffffffffffffffffffffffffffffffff")?;
+
+        let status = std::process::Command::new("git")
+            .args(["config", "transfer.advertiseObjectInfo", "true"])
+            .current_dir(&server_path)
+            .status()?;
+        assert!(
+            status.success(),
+            "newer versions of git don't advertise the command by default"
+        );
+
+        let mut repo = remote::repo("clone");
+        let mut object_info = |version: &str| -> crate::Result<Result<_, gix::remote::object_info::Error>> {
+            repo.config_snapshot_mut().set_raw_value(&Protocol::VERSION, version)?;
+            Ok(repo
+                .remote_at(server_path.clone())?
+                .connect(Fetch)?
+                .object_info([commit_id, tree_id, missing_id]))
+        };
+        assert!(
+            matches!(object_info("1")?, Err(gix::remote::object_info::Error::Unsupported)),
+            "the command needs protocol V2"
+        );
+
+        let infos = object_info("2")??;
+        assert_eq!(
+            infos,
+            [
+                gix::remote::object_info::Info {
+                    id: commit_id,
+                    size: Some(server.find_object(commit_id)?.data.len() as u64),
+                    kind: None,
+                },
+                gix::remote::object_info::Info {
+                    id: tree_id,
+                    size: Some(server.find_object(tree_id)?.data.len() as u64),
+                    kind: None,
+                },
+                gix::remote::object_info::Info {
+                    id: missing_id,
+                    size: None,
+                    kind: None,
+                },
+            ],
+            "sizes are provided in order, and missing objects have no size"
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,57 @@
+use gix::capabilities::{Feature, Support};
+
+use crate::util::restricted;
+
+#[test]
+fn new_repository_is_fully_supported() -> crate::Result {
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let repo = gix::init(tmp.path())?;
+    let out = repo.capabilities()?;
+    assert!(out.is_fully_supported(), "{out:?}");
+    assert_eq!(out.limitations().count(), 0);
+    assert_eq!(
+        out.items[0].feature,
+        Feature::RepositoryFormatVersion(0),
+        "the repository format version is always reported"
+    );
+    Ok(())
+}
+
+#[test]
+fn extensions_and_sparse_checkouts_are_reported() -> crate::Result {
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let repo = gix::init(tmp.path())?;
+    let config_path = repo.path().join("config");
+    let mut config = std::fs::read(&config_path)?;
+    config.extend_from_slice(
+        b"[core]\n\trepositoryFormatVersion = 1\n\tsparseCheckout = true\n[extensions]\n\tpartialClone = origin\n\tworktreeConfig\n\tcompatObjectFormat = sha256\n",
+    );
+    std::fs::write(&config_path, config)?;
+    let repo = gix::open_opts(repo.path(), restricted())?;
+
+    let out = repo.capabilities()?;
+    assert!(!out.is_fully_supported());
+    let features: Vec<_> = out.items.iter().map(|item| item.feature.clone()).collect();
+    assert_eq!(
+        features,
+        [
+            Feature::RepositoryFormatVersion(1),
+            Feature::UnknownExtension {
+                name: "compatobjectformat".into()
+            },
+            Feature::PartialClone {
+                remote: "origin".into()
+            },
+            Feature::WorktreeConfig,
+            Feature::SparseCheckout { cone: false },
+        ]
+    );
+    assert_eq!(
+        out.limitations()
+            .map(|item| matches!(item.support, Support::None))
+            .collect::<Vec<_>>(),
+        [true, false, false],
+        "unknown extensions are unsupported, but partial clones and sparse checkouts are partially supported"
+    );
+    Ok(())
+}
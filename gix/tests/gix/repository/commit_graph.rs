@@ -0,0 +1,154 @@
+use gix::repository::verify_commit_graph::{Mismatch, Options};
+
+use crate::util::{freeze_time, restricted_and_git};
+
+#[test]
+#[serial_test::serial]
+fn write_adds_layers_and_merges_small_ones() -> crate::Result {
+    let _env = freeze_time();
+    let (repo, _keep) = crate::repo_rw_opts("make_basic_repo.sh", restricted_and_git())?;
+    let head = repo.head_id()?.detach();
+    let outcome = repo.write_commit_graph(Some(head))?.expect("new commits were written");
+    assert_eq!(outcome.num_commits, 2);
+    assert_eq!(outcome.num_layers, 1);
+    assert!(outcome.path.is_file());
+    assert_eq!(repo.commit_graph()?.num_commits(), 2);
+
+    assert!(
+        repo.write_commit_graph(Some(head))?.is_none(),
+        "there is nothing to do if all commits are known"
+    );
+
+    let tree = repo.find_commit(head)?.tree_id()?.detach();
+    let mut tip = head;
+    for message in ["c3", "c4", "c5", "c6", "c7"] {
+        tip = repo.commit("HEAD", message, tree, Some(tip))?.detach();
+    }
+    let outcome = repo.write_commit_graph(Some(tip))?.expect("new commits were written");
+    assert_eq!(
+        outcome.num_layers, 1,
+        "the existing layer isn't more than twice as big as the new one, so it's merged into it"
+    );
+    assert_eq!(outcome.num_commits, 7);
+    let graphs_dir = outcome.path.parent().expect("in commit-graphs directory");
+    assert_eq!(
+        std::fs::read_dir(graphs_dir)?
+            .filter(|entry| entry
+                .as_ref()
+                .map_or(false, |e| e.path().extension().map_or(false, |ext| ext == "graph")))
+            .count(),
+        1,
+        "the merged layer was removed"
+    );
+    assert_git_accepts_commit_graph(&repo)?;
+
+    let tip = repo.commit("HEAD", "c8", tree, Some(tip))?.detach();
+    let outcome = repo.write_commit_graph(Some(tip))?.expect("a new commit was written");
+    assert_eq!(outcome.num_layers, 2, "the existing layer is big enough to be kept");
+    assert_eq!(outcome.num_commits, 1);
+
+    let graph = repo.commit_graph()?;
+    assert_eq!(graph.num_commits(), 8);
+    graph.verify_integrity(|_| Ok::<_, std::convert::Infallible>(()))?;
+    assert_eq!(
+        graph.commit_by_id(tip).expect("present").generation(),
+        8,
+        "generations are computed across layers"
+    );
+    assert_git_accepts_commit_graph(&repo)?;
+    Ok(())
+}
+
+fn assert_git_accepts_commit_graph(repo: &gix::Repository) -> crate::Result {
+    let out = std::process::Command::new(gix::path::env::exe_invocation())
+        .args(["commit-graph", "verify"])
+        .current_dir(repo.git_dir())
+        .output()?;
+    assert!(
+        out.status.success(),
+        "git accepts the commit-graph we wrote: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    Ok(())
+}
+
+#[test]
+#[serial_test::serial]
+fn verify_reports_mismatches_and_removes_invalid_layers() -> crate::Result {
+    let _env = freeze_time();
+    let (repo, _keep) = crate::repo_rw_opts("make_basic_repo.sh", restricted_and_git())?;
+    assert!(
+        repo.verify_commit_graph(Options::default())?.layers.is_empty(),
+        "without commit-graph there is nothing to verify"
+    );
+
+    let head = repo.head_id()?.detach();
+    let tree = repo.find_commit(head)?.tree_id()?.detach();
+    let mut tip = head;
+    for message in ["c3", "c4", "c5", "c6", "c7"] {
+        tip = repo.commit("HEAD", message, tree, Some(tip))?.detach();
+    }
+    repo.write_commit_graph(Some(tip))?.expect("new commits were written");
+    let tip = repo.commit("HEAD", "c8", tree, Some(tip))?.detach();
+    let top = repo.write_commit_graph(Some(tip))?.expect("a new commit was written");
+    assert_eq!(top.num_layers, 2);
+
+    let outcome = repo.verify_commit_graph(Options::default())?;
+    assert!(outcome.is_valid());
+    assert_eq!(
+        outcome.layers.iter().map(|layer| layer.num_commits).collect::<Vec<_>>(),
+        [7, 1]
+    );
+
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let other = gix::init_bare(tmp.path())?;
+    let graphs_dir = top.path.parent().expect("in commit-graphs directory");
+    let other_graphs_dir = other.objects.store_ref().path().join("info").join("commit-graphs");
+    std::fs::create_dir_all(&other_graphs_dir)?;
+    for entry in std::fs::read_dir(graphs_dir)? {
+        let path = entry?.path();
+        std::fs::copy(&path, other_graphs_dir.join(path.file_name().expect("file")))?;
+    }
+    let outcome = other.verify_commit_graph(Options::default())?;
+    assert_eq!(
+        outcome.iter_mismatches().count(),
+        8,
+        "each commit is reported, even in layers on top of invalid ones"
+    );
+    assert!(outcome
+        .iter_mismatches()
+        .all(|(_, mismatch)| matches!(mismatch, Mismatch::MissingCommit { .. })));
+    let outcome = other.verify_commit_graph(Options {
+        remove_invalid_layers: true,
+    })?;
+    assert_eq!(outcome.removed_layers.len(), 2, "all layers were invalid");
+    assert!(
+        !other_graphs_dir.join("commit-graph-chain").exists(),
+        "the chain is removed if no layer is left"
+    );
+
+    let mut data = std::fs::read(&top.path)?;
+    *data.last_mut().expect("non-empty") ^= 0xff;
+    std::fs::write(&top.path, data)?;
+    let outcome = repo.verify_commit_graph(Options::default())?;
+    assert!(!outcome.is_valid());
+    assert!(outcome.layers[0].mismatches.is_empty());
+    assert!(
+        matches!(outcome.layers[1].mismatches.as_slice(), [Mismatch::Corrupt { .. }]),
+        "the checksum of the top layer doesn't match"
+    );
+    assert!(outcome.removed_layers.is_empty(), "nothing is removed by default");
+
+    let outcome = repo.verify_commit_graph(Options {
+        remove_invalid_layers: true,
+    })?;
+    assert_eq!(outcome.removed_layers, std::slice::from_ref(&top.path));
+    assert!(!top.path.exists());
+    assert!(repo.verify_commit_graph(Options::default())?.is_valid());
+    assert_eq!(
+        repo.commit_graph()?.num_commits(),
+        7,
+        "the valid base layer is still used"
+    );
+    Ok(())
+}
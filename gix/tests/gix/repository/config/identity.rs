@@ -191,3 +191,93 @@ fn author_from_different_config_sections() -> crate::Result {
     );
     Ok(())
 }
+
+#[test]
+#[serial]
+fn identity_with_source() -> crate::Result {
+    use gix::repository::identity::Source;
+
+    let repo = named_repo("make_signatures_repo.sh")?;
+    let work_dir = repo.work_dir().unwrap().canonicalize()?;
+
+    let _env = Env::new()
+        .set("GIT_CONFIG_GLOBAL", work_dir.join("global.config").to_str().unwrap())
+        .set("GIT_CONFIG_SYSTEM", work_dir.join("system.config").to_str().unwrap())
+        .set("GIT_AUTHOR_DATE", "1979-02-26 18:30:00")
+        .unset("GIT_COMMITTER_DATE")
+        .unset("EMAIL");
+
+    let repo = gix::open_opts(
+        repo.git_dir(),
+        repo.open_options()
+            .clone()
+            .config_overrides(None::<&str>)
+            .with(gix_sec::Trust::Full)
+            .permissions(gix::open::Permissions {
+                env: gix::open::permissions::Environment {
+                    xdg_config_home: Permission::Deny,
+                    home: Permission::Deny,
+                    ..gix::open::permissions::Environment::all()
+                },
+                ..Default::default()
+            }),
+    )?;
+
+    let config_source = |source: &Source| match source {
+        Source::Config { key, kind, .. } => Some((key.to_string(), *kind)),
+        _ => None,
+    };
+    let author = repo.author_with_source();
+    let (name, source) = author.name.as_ref().expect("set");
+    assert_eq!(name, "global name");
+    assert_eq!(
+        config_source(source),
+        Some(("user.name".into(), gix::config::Source::Git)),
+        "the name falls back to `user.name` of the global configuration"
+    );
+    let (email, source) = author.email.as_ref().expect("set");
+    assert_eq!(email, "local@example.com");
+    assert_eq!(
+        config_source(source),
+        Some(("user.email".into(), gix::config::Source::Local))
+    );
+    assert_eq!(
+        author.time.as_ref().expect("valid").1,
+        Source::Environment {
+            variable: "GIT_AUTHOR_DATE"
+        }
+    );
+    assert_eq!(
+        author.to_signature().expect("complete").expect("valid time"),
+        repo.author().expect("complete")?,
+        "the signature is the same as the one without explanation"
+    );
+
+    let committer = repo.committer_with_source();
+    assert_eq!(
+        committer
+            .name
+            .as_ref()
+            .map(|(name, source)| (name.to_string(), config_source(source))),
+        Some((
+            "local committer".into(),
+            Some(("committer.name".into(), gix::config::Source::Local))
+        ))
+    );
+    assert_eq!(
+        committer
+            .email
+            .as_ref()
+            .map(|(email, source)| (email.to_string(), config_source(source))),
+        Some((
+            "global-committer@example.com".into(),
+            Some(("committer.email".into(), gix::config::Source::Git))
+        ))
+    );
+    assert_eq!(
+        committer.time.as_ref().expect("valid").1,
+        Source::Now,
+        "the committer date isn't set, so the current time is used"
+    );
+    Ok(())
+}
@@ -1,6 +1,38 @@
 mod config_snapshot;
 mod identity;
 mod remote;
+mod worktree;
+
+mod date_format {
+    use gix::{config::tree::Log, log::DateStyle};
+
+    use crate::util::named_repo;
+
+    #[test]
+    fn defaults_and_overrides() -> crate::Result {
+        let mut repo = named_repo("make_basic_repo.sh")?;
+        let time = gix::date::Time::new(1662302706, -4 * 3600);
+        let format = repo.date_format()?;
+        assert_eq!(format.style, DateStyle::Default, "it's the default if unset");
+        assert_eq!(format.format(time), "Sun Sep 4 10:45:06 2022 -0400");
+
+        repo.config_snapshot_mut().set_value(&Log::DATE, "iso")?;
+        assert_eq!(repo.date_format()?.format(time), "2022-09-04 10:45:06 -0400");
+
+        repo.config_snapshot_mut().set_value(&Log::DATE, "format:%d.%m.%Y")?;
+        assert_eq!(repo.date_format()?.format(time), "04.09.2022");
+
+        repo.config_snapshot_mut().set_value(&Log::DATE, "unix-local")?;
+        let format = repo.date_format()?;
+        assert!(format.local);
+        assert_eq!(
+            format.format(time),
+            "1662302706",
+            "the point in time doesn't change with the timezone"
+        );
+        Ok(())
+    }
+}
 
 #[cfg(feature = "blocking-network-client")]
 mod ssh_options {
@@ -33,6 +65,24 @@ mod ssh_options {
         );
         Ok(())
     }
+
+    #[test]
+    fn config_file_is_read_unless_disabled() -> crate::Result {
+        let mut repo = repo("ssh-all-options");
+        let opts = repo.ssh_connect_options()?;
+        assert!(
+            opts.config_file
+                .as_deref()
+                .map_or(true, |path| path.ends_with(std::path::Path::new(".ssh").join("config"))),
+            "by default, the configuration file in the home directory is used"
+        );
+
+        repo.config_snapshot_mut()
+            .set_value(&gix::config::tree::gitoxide::Ssh::READ_CONFIG_FILE, "false")?;
+        let opts = repo.ssh_connect_options()?;
+        assert_eq!(opts.config_file, None);
+        Ok(())
+    }
 }
 
 #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
@@ -321,3 +321,88 @@ mod branch_remote {
         named_subrepo_opts("make_remote_config_repos.sh", name, gix::open::Options::isolated())
     }
 }
+
+mod new_branch_tracking {
+    use gix::{
+        branch::Tracking,
+        config::tree::{Branch, Key},
+    };
+
+    use crate::remote;
+
+    fn tracking(
+        repo: &gix::Repository,
+        new_branch: &str,
+        start_point: &str,
+    ) -> crate::Result<Option<(String, String, bool)>> {
+        Ok(repo
+            .new_branch_tracking(new_branch.try_into()?, start_point.try_into()?)?
+            .map(|Tracking { remote, merge, rebase }| (remote.to_string(), merge.as_bstr().to_string(), rebase)))
+    }
+
+    fn set(repo: &mut gix::Repository, key: &'static dyn Key, value: &str) -> crate::Result {
+        repo.config_snapshot_mut().set_value(key, value)?;
+        Ok(())
+    }
+
+    #[test]
+    fn remote_tracking_branches_are_tracked_by_default() -> crate::Result {
+        let mut repo = remote::repo("clone");
+        assert_eq!(
+            tracking(&repo, "refs/heads/feature", "refs/remotes/origin/main")?,
+            Some(("origin".into(), "refs/heads/main".into(), false)),
+            "the remote and its branch are found through the fetch ref-specs"
+        );
+        assert_eq!(
+            tracking(&repo, "refs/heads/feature", "refs/heads/main")?,
+            None,
+            "local branches aren't tracked by default"
+        );
+
+        set(&mut repo, &Branch::AUTO_SETUP_REBASE, "remote")?;
+        assert_eq!(
+            tracking(&repo, "refs/heads/feature", "refs/remotes/origin/main")?,
+            Some(("origin".into(), "refs/heads/main".into(), true)),
+        );
+
+        set(&mut repo, &Branch::AUTO_SETUP_MERGE, "false")?;
+        assert_eq!(tracking(&repo, "refs/heads/feature", "refs/remotes/origin/main")?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn auto_setup_merge_modes() -> crate::Result {
+        let mut repo = remote::repo("clone");
+        set(&mut repo, &Branch::AUTO_SETUP_MERGE, "simple")?;
+        assert_eq!(
+            tracking(&repo, "refs/heads/feature", "refs/remotes/origin/main")?,
+            None,
+            "'simple' needs the names to match"
+        );
+        assert_eq!(
+            tracking(&repo, "refs/heads/main", "refs/remotes/origin/main")?,
+            Some(("origin".into(), "refs/heads/main".into(), false)),
+        );
+
+        set(&mut repo, &Branch::AUTO_SETUP_MERGE, "always")?;
+        set(&mut repo, &Branch::AUTO_SETUP_REBASE, "local")?;
+        assert_eq!(
+            tracking(&repo, "refs/heads/feature", "refs/heads/main")?,
+            Some((".".into(), "refs/heads/main".into(), true)),
+            "local branches are tracked through the '.' remote"
+        );
+
+        set(&mut repo, &Branch::AUTO_SETUP_MERGE, "inherit")?;
+        assert_eq!(
+            tracking(&repo, "refs/heads/feature", "refs/heads/main")?,
+            Some(("origin".into(), "refs/heads/main".into(), false)),
+            "the tracking configuration of the start point is copied"
+        );
+        assert_eq!(
+            tracking(&repo, "refs/heads/feature", "refs/heads/a")?,
+            None,
+            "there is nothing to inherit"
+        );
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@
 ))]
 mod http {
     use gix_transport::client::http::options::{
-        FollowRedirects, HttpVersion, ProxyAuthMethod, SslVersion, SslVersionRangeInclusive,
+        AuthMethod, FollowRedirects, HttpVersion, ProxyAuthMethod, SslVersion, SslVersionRangeInclusive,
     };
 
     use crate::repository::config::{repo, repo_opts};
@@ -50,8 +50,11 @@ mod http {
             no_proxy,
             proxy_auth_method,
             proxy_authenticate,
+            auth_method,
             user_agent,
             connect_timeout,
+            retries,
+            retry_delay,
             verbose,
             ssl_ca_info,
             ssl_version,
@@ -73,8 +76,11 @@ mod http {
             "no username means no authentication required"
         );
         assert_eq!(proxy_auth_method, ProxyAuthMethod::Basic);
+        assert_eq!(auth_method, None, "basic authentication headers are sent by default");
         assert_eq!(user_agent.as_deref(), Some("agentJustForHttp"));
         assert_eq!(connect_timeout, Some(std::time::Duration::from_millis(60 * 1024)));
+        assert_eq!(retries, 3);
+        assert_eq!(retry_delay, Some(std::time::Duration::from_millis(500)));
         assert_eq!(no_proxy, None);
         assert!(!verbose, "verbose is disabled by default");
         assert_eq!(ssl_ca_info.as_deref(), Some(std::path::Path::new("./CA.pem")));
@@ -155,6 +161,15 @@ mod http {
         assert!(opts.ssl_version.is_none(), "empty strings reset what was there");
     }
 
+    #[test]
+    fn http_auth_method() {
+        let repo = repo_opts("http-config", |opts| {
+            opts.config_overrides(["gitoxide.http.authMethod=ntlm"])
+        });
+        let opts = http_options(&repo, None, "https://example.com/does/not/matter");
+        assert_eq!(opts.auth_method, Some(AuthMethod::Ntlm));
+    }
+
     #[test]
     fn http_verbose() {
         let repo = repo("http-verbose");
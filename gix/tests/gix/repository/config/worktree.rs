@@ -0,0 +1,61 @@
+use gix::config::tree::Core;
+
+use crate::util::{named_subrepo_opts, restricted};
+
+#[test]
+fn set_value_in_worktree_config() -> crate::Result {
+    // Worktrees refer to their repository by absolute path, so copies would still write into the original fixture.
+    let tmp = gix_testtools::scripted_fixture_writable_with_args(
+        "make_worktree_repo_with_configs.sh",
+        None::<String>,
+        gix_testtools::Creation::ExecuteScript,
+    )?;
+    let mut wt1 = gix::open_opts(tmp.path().join("wt-1"), restricted())?;
+
+    let previous = wt1.set_worktree_config_value(&Core::SPARSE_CHECKOUT, "true")?;
+    assert_eq!(previous, None, "the value wasn't set before");
+    assert_eq!(
+        wt1.config_snapshot().boolean("core.sparseCheckout"),
+        Some(true),
+        "the in-memory configuration is updated"
+    );
+
+    let config = std::fs::read_to_string(wt1.git_dir().join("config.worktree"))?;
+    assert!(
+        config.contains("setting = set in wt-1") && config.contains("sparseCheckout = true"),
+        "the value is added to the worktree configuration, keeping existing values: {config}"
+    );
+
+    let wt1 = gix::open_opts(tmp.path().join("wt-1"), restricted())?;
+    assert_eq!(wt1.config_snapshot().boolean("core.sparseCheckout"), Some(true));
+    let wt2 = gix::open_opts(tmp.path().join("wt-2"), restricted())?;
+    assert_eq!(
+        wt2.config_snapshot().boolean("core.sparseCheckout"),
+        None,
+        "other worktrees are unaffected"
+    );
+    Ok(())
+}
+
+#[test]
+fn set_value_without_extension_and_linked_worktrees_writes_shared_config() -> crate::Result {
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let mut repo = gix::init(tmp.path())?;
+
+    repo.set_worktree_config_value(&Core::SPARSE_CHECKOUT, "true")?;
+    assert!(!repo.git_dir().join("config.worktree").exists());
+
+    let repo = gix::open_opts(repo.path(), restricted())?;
+    assert_eq!(repo.config_snapshot().boolean("core.sparseCheckout"), Some(true));
+    Ok(())
+}
+
+#[test]
+fn set_value_without_extension_fails_with_linked_worktrees() -> crate::Result {
+    let mut repo = named_subrepo_opts("make_worktree_repo.sh", "repo", restricted())?;
+    let err = repo
+        .set_worktree_config_value(&Core::SPARSE_CHECKOUT, "true")
+        .unwrap_err();
+    assert!(matches!(err, gix::config::set_worktree_value::Error::ExtensionRequired));
+    Ok(())
+}
@@ -26,3 +26,32 @@ fn empty_core_excludes() -> crate::Result {
         .expect("empty paths are now just skipped");
     Ok(())
 }
+
+#[test]
+fn worktree_cache_is_shared_by_all_stacks_of_a_repository_and_its_clones() -> crate::Result {
+    let tmp = gix_testtools::tempfile::tempdir()?;
+    let mut repo = gix::ThreadSafeRepository::init_opts(
+        tmp.path(),
+        gix::create::Kind::WithWorktree,
+        Default::default(),
+        crate::util::restricted(),
+    )?
+    .to_thread_local();
+    std::fs::create_dir(tmp.path().join("dir"))?;
+    std::fs::write(tmp.path().join("dir").join(".gitignore"), "ignored\n")?;
+    assert!(repo.worktree_cache().is_none(), "there is no cache by default");
+    repo.set_worktree_cache(Some(gix::WorktreeCache::default()));
+
+    let index = repo.index_or_empty()?;
+    for (repo, expected_hits) in [(repo.clone(), 0), (repo, 1)] {
+        let mut excludes = repo.excludes(&index, None, Source::WorktreeThenIdMappingIfNotSkipped)?;
+        assert!(excludes.at_entry("dir/ignored", None)?.is_excluded());
+        assert!(!excludes.at_entry("dir/not-ignored", None)?.is_excluded());
+        assert_eq!(
+            excludes.statistics().ignore.shared_cache_hits,
+            expected_hits,
+            "the second stack obtains the parsed ignore file from the cache"
+        );
+    }
+    Ok(())
+}
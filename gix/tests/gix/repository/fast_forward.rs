@@ -0,0 +1,149 @@
+use gix::{
+    fast_forward::{Error, Options},
+    refs::transaction::PreviousValue,
+};
+
+use crate::util::restricted_and_git;
+
+fn commit_with_new_file(
+    repo: &gix::Repository,
+    reference: &str,
+    parent: gix::ObjectId,
+    filename: &str,
+) -> crate::Result<gix::ObjectId> {
+    let mut tree: gix::objs::Tree = repo.find_commit(parent)?.tree()?.decode()?.into();
+    tree.entries.push(gix::objs::tree::Entry {
+        mode: gix::objs::tree::EntryKind::Blob.into(),
+        filename: filename.into(),
+        oid: repo.write_blob(filename)?.detach(),
+    });
+    tree.entries.sort();
+    let tree_id = repo.write_object(&tree)?;
+    Ok(repo.commit(reference, filename, tree_id, Some(parent))?.detach())
+}
+
+#[test]
+fn checked_out_branch_is_fast_forwarded_along_with_index_and_worktree() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw_opts("make_basic_repo.sh", restricted_and_git())?;
+    let branch = repo.head_name()?.expect("on a branch");
+    let previous = repo.head_id()?.detach();
+    let ahead = commit_with_new_file(&repo, "refs/heads/ahead", previous, "new")?;
+
+    let outcome = repo.fast_forward(branch.as_ref(), ahead, Options::default())?;
+    assert_eq!(outcome.previous, previous);
+    assert_eq!(outcome.new, ahead);
+    assert!(outcome.updated_worktree, "the branch is checked out");
+    assert_eq!(repo.head_id()?, ahead, "HEAD was moved along with the branch");
+    assert_eq!(std::fs::read(repo.work_dir().expect("non-bare").join("new"))?, b"new");
+    assert!(!repo.is_dirty()?, "index and worktree match the new commit");
+
+    let last_log_message = repo
+        .find_reference("HEAD")?
+        .log_iter()
+        .rev()?
+        .expect("log present")
+        .next()
+        .expect("one line")?
+        .message;
+    assert_eq!(last_log_message, "fast-forward", "HEAD receives a log entry as well");
+
+    let outcome = repo.fast_forward(branch.as_ref(), previous, Options::default())?;
+    assert_eq!(
+        (outcome.previous, outcome.new, outcome.updated_worktree),
+        (ahead, ahead, false),
+        "the branch already contains the desired commit, so nothing changes"
+    );
+    Ok(())
+}
+
+#[test]
+fn branches_that_are_not_checked_out_are_fast_forwarded_without_touching_the_worktree() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw_opts("make_basic_repo.sh", restricted_and_git())?;
+    let head = repo.head_id()?.detach();
+    let other = repo.reference("refs/heads/other", head, PreviousValue::MustNotExist, "create")?;
+    let ahead = commit_with_new_file(&repo, "refs/heads/ahead", head, "new")?;
+
+    let outcome = repo.fast_forward(
+        other.name(),
+        ahead,
+        Options {
+            reflog_message: "custom".into(),
+            ..Default::default()
+        },
+    )?;
+    assert!(!outcome.updated_worktree);
+    assert_eq!(repo.find_reference("refs/heads/other")?.id(), ahead);
+    assert_eq!(repo.head_id()?, head, "the checked out branch wasn't touched");
+    assert!(
+        !repo.work_dir().expect("non-bare").join("new").exists(),
+        "the worktree wasn't changed"
+    );
+    let last_log_message = repo
+        .find_reference("refs/heads/other")?
+        .log_iter()
+        .rev()?
+        .expect("log present")
+        .next()
+        .expect("one line")?
+        .message;
+    assert_eq!(last_log_message, "custom");
+    Ok(())
+}
+
+#[test]
+fn reflog_committer_is_independent_of_the_configured_committer() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw_opts("make_basic_repo.sh", restricted_and_git())?;
+    let head = repo.head_id()?.detach();
+    let other = repo.reference("refs/heads/other", head, PreviousValue::MustNotExist, "create")?;
+    let ahead = commit_with_new_file(&repo, "refs/heads/ahead", head, "new")?;
+
+    repo.fast_forward(
+        other.name(),
+        ahead,
+        Options {
+            reflog_committer: Some(gix::actor::Signature {
+                name: "reflog committer".into(),
+                email: "reflog@example.com".into(),
+                time: gix::date::Time::new(42, 0),
+            }),
+            ..Default::default()
+        },
+    )?;
+    let other = repo.find_reference("refs/heads/other")?;
+    let mut log = other.log_iter();
+    let last_log_line = log.rev()?.expect("log present").next().expect("one line")?;
+    assert_eq!(last_log_line.signature.name, "reflog committer");
+    assert_eq!(last_log_line.signature.email, "reflog@example.com");
+    Ok(())
+}
+
+#[test]
+fn diverged_branches_and_dirty_worktrees_are_rejected() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw_opts("make_basic_repo.sh", restricted_and_git())?;
+    let branch = repo.head_name()?.expect("on a branch");
+    let head = repo.head_id()?.detach();
+    let parent = repo
+        .find_commit(head)?
+        .parent_ids()
+        .next()
+        .expect("has parent")
+        .detach();
+    let diverged = commit_with_new_file(&repo, "refs/heads/diverged", parent, "diverged")?;
+
+    let err = repo
+        .fast_forward(branch.as_ref(), diverged, Options::default())
+        .unwrap_err();
+    assert!(
+        matches!(err, Error::NotFastForward { current, to, .. } if current == head && to == diverged),
+        "a merge would be required: {err:?}"
+    );
+
+    let ahead = commit_with_new_file(&repo, "refs/heads/ahead", head, "new")?;
+    std::fs::write(repo.work_dir().expect("non-bare").join("this"), "local change")?;
+    assert!(matches!(
+        repo.fast_forward(branch.as_ref(), ahead, Options::default()),
+        Err(Error::Dirty)
+    ));
+    assert_eq!(repo.head_id()?, head, "nothing changed");
+    Ok(())
+}
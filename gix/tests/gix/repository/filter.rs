@@ -60,3 +60,47 @@ fn pipeline_with_autocrlf() -> crate::Result {
     }
     Ok(())
 }
+
+#[test]
+fn write_blob_from_worktree_file_applies_eol_conversion_from_attributes() -> crate::Result {
+    let (repo, _tmp) = crate::util::repo_rw("make_basic_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare");
+    std::fs::write(workdir.join(".gitattributes"), "*.txt text\n")?;
+    std::fs::write(workdir.join("crlf.txt"), "hi\r\n")?;
+    std::fs::write(workdir.join("crlf.bin"), "hi\r\n")?;
+
+    let id = repo.write_blob_from_worktree_file("crlf.txt")?;
+    assert_eq!(
+        id,
+        gix::objs::compute_hash(repo.object_hash(), gix::object::Kind::Blob, b"hi\n"),
+        "text files are normalized to LF just like `git add` would"
+    );
+    assert_eq!(id.object()?.data.as_bstr(), "hi\n", "the blob was written as well");
+
+    let id = repo.write_blob_from_worktree_file("crlf.bin")?;
+    assert_eq!(
+        id,
+        gix::objs::compute_hash(repo.object_hash(), gix::object::Kind::Blob, b"hi\r\n"),
+        "without attributes or configuration, content is written as is"
+    );
+
+    assert!(matches!(
+        repo.write_blob_from_worktree_file("does-not-exist"),
+        Err(gix::repository::filter::write_blob_from_worktree_file::Error::Io { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn write_blob_from_worktree_file_writes_symlink_targets() -> crate::Result {
+    let (repo, _tmp) = crate::util::repo_rw("make_basic_repo.sh")?;
+    std::os::unix::fs::symlink("some/target", repo.work_dir().expect("non-bare").join("link"))?;
+
+    let id = repo.write_blob_from_worktree_file("link")?;
+    assert_eq!(
+        id,
+        gix::objs::compute_hash(repo.object_hash(), gix::object::Kind::Blob, b"some/target")
+    );
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use gix::refs::transaction::PreviousValue;
+
+use crate::util::{freeze_time, restricted_and_git};
+
+#[test]
+#[serial_test::serial]
+fn unreachable_objects_and_recovery_suggestions() -> crate::Result {
+    let _env = freeze_time();
+    let (repo, _keep) = crate::repo_rw_opts("make_basic_repo.sh", restricted_and_git())?;
+    let before = repo.lost_found(Default::default())?;
+
+    let blob_id = repo.write_blob(b"lost content")?.detach();
+    let head_id = repo.head_id()?.detach();
+    let tree_id = repo.find_commit(head_id)?.tree_id()?.detach();
+    let lost_commit_id = repo.commit("HEAD", "lost work", tree_id, Some(head_id))?.detach();
+    let branch = repo.head_name()?.expect("not detached");
+    repo.reference(branch, head_id, PreviousValue::Any, "reset: moving to HEAD~1")?;
+
+    let outcome = repo.lost_found(Default::default())?;
+    let new_unreachable: Vec<_> = outcome
+        .unreachable
+        .iter()
+        .filter(|obj| !before.unreachable.contains(obj))
+        .collect();
+    assert_eq!(
+        new_unreachable.len(),
+        1,
+        "the lost commit is still reachable through reflogs"
+    );
+    assert_eq!(new_unreachable[0].id, blob_id);
+    assert!(new_unreachable[0].is_dangling);
+    assert!(
+        new_unreachable[0].time.is_some(),
+        "loose objects have a modification time"
+    );
+    assert!(outcome.suggestions.is_empty(), "reflogs count as reachable by default");
+
+    let tree_blob_id = repo.write_blob(b"lost in tree")?.detach();
+    let lost_tree_id = repo
+        .write_object(gix::objs::Tree {
+            entries: vec![gix::objs::tree::Entry {
+                mode: gix::objs::tree::EntryKind::Blob.into(),
+                filename: "file".into(),
+                oid: tree_blob_id,
+            }],
+        })?
+        .detach();
+
+    let outcome = repo.lost_found(gix::lost_found::Options {
+        reflogs_are_reachable: false,
+        ..Default::default()
+    })?;
+    let lost_commit = outcome
+        .unreachable
+        .iter()
+        .find(|obj| obj.id == lost_commit_id)
+        .expect("only reachable through reflogs");
+    assert_eq!(lost_commit.kind, gix::object::Kind::Commit);
+    assert!(lost_commit.is_dangling);
+    assert!(lost_commit.time.is_some(), "commits have a committer time");
+    assert!(
+        outcome.suggestions.iter().any(|s| s.id == lost_commit_id),
+        "commits only found in reflogs are suggested for recovery"
+    );
+
+    let written = outcome.write_lost_found(&repo)?;
+    let commit_path = repo
+        .path()
+        .join("lost-found")
+        .join("commit")
+        .join(lost_commit_id.to_hex().to_string());
+    assert!(written.contains(&commit_path));
+    assert_eq!(std::fs::read(commit_path)?, format!("{lost_commit_id}\n").as_bytes());
+    assert_eq!(
+        std::fs::read(
+            repo.path()
+                .join("lost-found")
+                .join("other")
+                .join(blob_id.to_hex().to_string())
+        )?,
+        b"lost content",
+        "blobs are written with their content"
+    );
+    assert_eq!(
+        std::fs::read(
+            repo.path()
+                .join("lost-found")
+                .join("other")
+                .join(lost_tree_id.to_hex().to_string())
+        )?,
+        format!("{lost_tree_id}\n").as_bytes(),
+        "other objects are written with their id, like git does"
+    );
+    assert!(
+        !repo
+            .path()
+            .join("lost-found")
+            .join("other")
+            .join(tree_blob_id.to_hex().to_string())
+            .exists(),
+        "objects referenced by other unreachable objects aren't dangling"
+    );
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use std::sync::atomic::AtomicBool;
+
+use gix::config::tree::{maintenance, Gc, Maintenance};
+
+use crate::util::{freeze_time, restricted_and_git};
+
+fn auto(repo: &gix::Repository) -> Result<gix::maintenance::Outcome, gix::maintenance::Error> {
+    gix::maintenance::auto(repo, &mut gix::progress::Discard, &AtomicBool::default())
+}
+
+#[test]
+#[serial_test::serial]
+fn commit_graph_is_updated_once_enough_commits_are_missing() -> crate::Result {
+    let _env = freeze_time();
+    let (mut repo, _keep) = crate::repo_rw_opts("make_basic_repo.sh", restricted_and_git())?;
+    repo.config_snapshot_mut()
+        .set_value(&maintenance::CommitGraph::AUTO, "3")?;
+
+    let out = auto(&repo)?;
+    assert!(
+        out.loose_objects.is_none(),
+        "there are far fewer loose objects than `gc.auto`"
+    );
+    assert!(
+        out.commit_graph.is_none(),
+        "only two commits are missing from the commit-graph"
+    );
+
+    let head = repo.head_id()?.detach();
+    let tree = repo.find_commit(head)?.tree_id()?.detach();
+    repo.commit("HEAD", "c3", tree, Some(head))?;
+    let out = auto(&repo)?;
+    assert_eq!(
+        out.commit_graph.expect("three commits are missing").num_commits,
+        3,
+        "all reachable commits are written"
+    );
+    assert!(auto(&repo)?.commit_graph.is_none(), "the commit-graph is up to date");
+    Ok(())
+}
+
+#[test]
+#[serial_test::serial]
+fn loose_objects_are_packed_once_there_are_too_many() -> crate::Result {
+    let _env = freeze_time();
+    let (mut repo, _keep) = crate::repo_rw_opts("make_basic_repo.sh", restricted_and_git())?;
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_value(&Gc::AUTO, "1")?;
+        config.set_value(&Maintenance::AUTO, "false")?;
+    }
+    let probe_dir = repo.objects.store_ref().path().join("17");
+    let mut blobs = Vec::new();
+    while std::fs::read_dir(&probe_dir).map_or(0, Iterator::count) < 2 {
+        blobs.push(repo.write_blob(blobs.len().to_string())?.detach());
+    }
+    let num_loose_objects = repo.stats()?.loose_objects.count;
+
+    let out = auto(&repo)?;
+    assert!(
+        out.loose_objects.is_none() && out.commit_graph.is_none(),
+        "nothing happens if maintenance is disabled"
+    );
+
+    repo.config_snapshot_mut().set_value(&Maintenance::AUTO, "true")?;
+    let out = auto(&repo)?;
+    let loose = out
+        .loose_objects
+        .expect("more loose objects than the estimate for `gc.auto` allows");
+    assert_eq!(
+        loose.num_removed as u64, num_loose_objects,
+        "all loose objects were packed"
+    );
+    assert_eq!(loose.write_pack_bundle.index.num_objects as u64, num_loose_objects);
+    assert!(
+        loose.write_pack_bundle.keep_path.is_none(),
+        "the pack doesn't need protection anymore"
+    );
+    assert_eq!(repo.stats()?.loose_objects.count, 0);
+    for id in blobs {
+        assert!(repo.has_object(id), "objects are now found in the new pack");
+    }
+    assert!(
+        out.commit_graph.is_some(),
+        "the commit-graph is written along with packing loose objects as `gc.writeCommitGraph` is true by default"
+    );
+    Ok(())
+}
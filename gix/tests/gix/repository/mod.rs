@@ -1,20 +1,32 @@
 use gix::Repository;
 
+mod capabilities;
+mod commit_graph;
 mod config;
 #[cfg(feature = "excludes")]
 mod excludes;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod fast_forward;
 #[cfg(feature = "attributes")]
 mod filter;
+mod lost_found;
+#[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+mod maintenance;
 mod object;
 mod open;
 #[cfg(feature = "attributes")]
 mod pathspec;
+mod plumbing;
 mod reference;
 mod remote;
+#[cfg(feature = "repair")]
+mod repair;
 mod shallow;
 mod state;
+mod stats;
 #[cfg(feature = "attributes")]
 mod submodule;
+mod topology;
 mod worktree;
 
 #[cfg(feature = "dirwalk")]
@@ -80,7 +92,7 @@ mod dirwalk {
 #[test]
 fn size_in_memory() {
     let actual_size = std::mem::size_of::<Repository>();
-    let limit = 1200;
+    let limit = 1250;
     assert!(
         actual_size <= limit,
         "size of Repository shouldn't change without us noticing, it's meant to be cloned: should have been below {limit:?}, was {actual_size} (bigger on windows)"
@@ -278,6 +278,27 @@ mod write_blob {
     }
 }
 
+#[test]
+fn has_objects() -> crate::Result {
+    let mut repo = crate::named_repo("make_packed_and_loose.sh")?;
+    let mut ids = repo.objects.iter()?.collect::<Result<Vec<_>, _>>()?;
+    let num_existing = ids.len();
+    ids.push(gix::hash::ObjectId::empty_tree(repo.object_hash()));
+    ids.push(repo.object_hash().null());
+    repo.objects.enable_object_memory();
+    ids.push(repo.write_blob("in memory")?.detach());
+
+    let expected: Vec<_> = ids.iter().map(|id| repo.has_object(id)).collect();
+    assert_eq!(repo.has_objects(&ids), expected, "it's the same as checking one by one");
+    assert_eq!(
+        expected.iter().filter(|present| !**present).count(),
+        1,
+        "only the null id is missing, the empty tree is always present"
+    );
+    assert!(expected[..num_existing].iter().all(|present| *present));
+    Ok(())
+}
+
 #[test]
 fn writes_avoid_io_using_duplicate_check() -> crate::Result {
     let mut repo = crate::named_repo("make_packed_and_loose.sh")?;
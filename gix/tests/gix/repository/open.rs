@@ -239,6 +239,36 @@ mod open_path_as_is {
     }
 }
 
+mod lazy {
+    use crate::util::repo_opts;
+
+    #[test]
+    fn repositories_are_usable_and_metrics_are_recorded() -> crate::Result {
+        for lazy in [false, true] {
+            let repo = repo_opts("make_basic_repo.sh", gix::open::Options::isolated().lazy(lazy))?.to_thread_local();
+            assert!(
+                repo.head_commit()?.tree()?.iter().count() > 0,
+                "objects and references can be accessed"
+            );
+
+            let metrics = repo.open_metrics();
+            assert_ne!(metrics.total, std::time::Duration::default());
+            assert!(
+                metrics.total
+                    >= metrics.repository_config
+                        + metrics.references
+                        + metrics.config
+                        + metrics.worktree
+                        + metrics.replacement_objects
+                        + metrics.object_store,
+                "the total is the sum of all phases"
+            );
+            assert_eq!(repo.clone().into_sync().open_metrics(), metrics, "metrics are retained");
+        }
+        Ok(())
+    }
+}
+
 mod submodules {
     use std::path::Path;
 
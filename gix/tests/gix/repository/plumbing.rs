@@ -0,0 +1,56 @@
+use std::sync::atomic::AtomicBool;
+
+use gix::plumbing::explode_pack;
+
+#[test]
+fn explode_pack_into_directory() -> crate::Result {
+    let repo = crate::named_repo("make_packed_and_loose.sh")?;
+    let pack_path = std::fs::read_dir(repo.objects.store_ref().path().join("pack"))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .find(|path| path.extension().map_or(false, |ext| ext == "pack"))
+        .expect("a pack was written by `git gc`");
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let options = explode_pack::Options {
+        destination: Some(tmp.path().to_owned()),
+        ..Default::default()
+    };
+
+    let outcome = repo.explode_pack(&pack_path, options.clone(), &AtomicBool::default())?;
+    assert_eq!(
+        outcome,
+        explode_pack::Outcome {
+            objects: 6,
+            written: 6,
+            commits: 2,
+            trees: 2,
+            blobs: 2,
+            tags: 0,
+        }
+    );
+    let loose = gix::odb::loose::Store::at(tmp.path(), repo.object_hash());
+    assert_eq!(loose.iter().filter_map(Result::ok).count(), 6);
+
+    let outcome = repo.explode_pack(&pack_path.with_extension("idx"), options, &AtomicBool::default())?;
+    assert_eq!(
+        (outcome.objects, outcome.written),
+        (6, 0),
+        "existing objects aren't written again, and the index path works as well"
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "index")]
+fn verify_index() -> crate::Result {
+    let repo = crate::named_repo("make_packed_and_loose.sh")?;
+    let outcome = repo.verify_index()?;
+    assert_eq!(outcome.entries, 2, "'this' and 'that'");
+    assert_eq!(outcome.version, gix::index::Version::V2);
+    assert!(outcome.checksum.is_some());
+
+    let repo = crate::named_subrepo_opts("make_basic_repo.sh", "bare.git", crate::util::restricted())?;
+    assert!(repo.verify_index().is_err(), "there is no index");
+    Ok(())
+}
@@ -225,4 +225,44 @@ mod head {
         assert!(head.referent_name().is_none());
         Ok(())
     }
+
+    #[test]
+    fn attach_symbolically() -> crate::Result {
+        let (repo, _keep) = crate::basic_rw_repo()?;
+        let id = hex_to_id("3189cd3cb0af8586c39a838aa3e54fd72a872a41");
+        repo.reference("HEAD", id, PreviousValue::Any, "detach")?;
+
+        let head = repo.symbolic_reference(
+            "HEAD",
+            "refs/heads/main",
+            PreviousValue::MustExistAndMatch(id.into()),
+            "checkout: moving from 3189cd3 to main",
+        )?;
+        assert_eq!(head.name().as_bstr(), "HEAD");
+        assert_eq!(
+            head.target().try_name().map(gix::refs::FullNameRef::as_bstr),
+            Some("refs/heads/main".into())
+        );
+        assert_eq!(repo.head_name()?.expect("attached").as_bstr(), "refs/heads/main");
+
+        let line = repo
+            .find_reference("HEAD")?
+            .log_iter()
+            .rev()?
+            .expect("log present")
+            .next()
+            .expect("one line")?;
+        assert_eq!(line.message, "checkout: moving from 3189cd3 to main");
+        assert_eq!(
+            (line.previous_oid, line.new_oid),
+            (id, id),
+            "HEAD resolves to the same commit as before"
+        );
+
+        assert!(matches!(
+            repo.symbolic_reference("HEAD", "FETCH_HEAD", PreviousValue::Any, ""),
+            Err(gix::reference::symbolic::Error::Symbolic(_))
+        ));
+        Ok(())
+    }
 }
@@ -350,3 +350,361 @@ mod find_default_remote {
         Ok(())
     }
 }
+
+mod add_remote {
+    use gix::remote::Direction;
+
+    #[test]
+    fn writes_url_and_default_fetch_spec() -> crate::Result {
+        let tmp = gix_testtools::scripted_fixture_writable("make_remote_repos.sh")?;
+        let repo_path = tmp.path().join("clone");
+        let mut repo = gix::open_opts(&repo_path, gix::open::Options::isolated())?;
+
+        let remote = repo.add_remote("upstream", "https://example.com/upstream.git")?;
+        assert_eq!(remote.name().map(|n| n.as_bstr().to_string()), Some("upstream".into()));
+
+        for repo in [repo, gix::open_opts(&repo_path, gix::open::Options::isolated())?] {
+            let remote = repo.find_remote("upstream")?;
+            assert_eq!(
+                remote.url(Direction::Fetch).map(|url| url.to_bstring().to_string()),
+                Some("https://example.com/upstream.git".into()),
+                "the configuration is changed in memory and on disk"
+            );
+            let specs: Vec<_> = remote
+                .refspecs(Direction::Fetch)
+                .iter()
+                .map(|spec| spec.to_ref().to_bstring().to_string())
+                .collect();
+            assert_eq!(specs, ["+refs/heads/*:refs/remotes/upstream/*"]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn existing_remote_is_an_error() -> crate::Result {
+        let tmp = gix_testtools::scripted_fixture_writable("make_remote_repos.sh")?;
+        let mut repo = gix::open_opts(tmp.path().join("clone"), gix::open::Options::isolated())?;
+        assert!(matches!(
+            repo.add_remote("origin", "https://example.com/other.git"),
+            Err(gix::repository::add_remote::Error::AlreadyExists { .. })
+        ));
+        Ok(())
+    }
+}
+
+mod rename_remote {
+    use gix::remote::Direction;
+
+    #[test]
+    fn renames_config_tracking_refs_and_branch_configuration() -> crate::Result {
+        let tmp = gix_testtools::scripted_fixture_writable("make_remote_repos.sh")?;
+        let repo_path = tmp.path().join("clone");
+        let mut repo = gix::open_opts(&repo_path, crate::util::restricted())?;
+        let num_tracking_refs = repo.references()?.prefixed("refs/remotes/origin/")?.count();
+        assert_ne!(num_tracking_refs, 0, "there are remote-tracking branches initially");
+
+        let renamed = repo.rename_remote("origin", "upstream")?;
+        assert_eq!(
+            renamed.len(),
+            num_tracking_refs,
+            "all remote-tracking branches were moved"
+        );
+        assert!(renamed
+            .iter()
+            .all(|name| name.as_bstr().starts_with(b"refs/remotes/upstream/")));
+
+        for repo in [repo, gix::open_opts(&repo_path, crate::util::restricted())?] {
+            assert_eq!(
+                repo.remote_names().iter().map(ToString::to_string).collect::<Vec<_>>(),
+                ["myself", "upstream"],
+                "the configuration is changed in memory and on disk"
+            );
+            assert!(repo.try_find_remote("origin").is_none());
+            assert_eq!(repo.references()?.prefixed("refs/remotes/origin/")?.count(), 0);
+            assert_eq!(
+                repo.references()?.prefixed("refs/remotes/upstream/")?.count(),
+                num_tracking_refs
+            );
+            assert_eq!(
+                repo.config_snapshot().string("branch.main.remote").as_deref(),
+                Some("upstream".into())
+            );
+            let remote = repo.find_remote("upstream")?;
+            assert!(remote.refspecs(Direction::Fetch).iter().all(|spec| spec
+                .to_ref()
+                .destination()
+                .map_or(false, |dst| dst.starts_with(b"refs/remotes/upstream/"))));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn renaming_to_existing_remote_is_an_error() -> crate::Result {
+        let tmp = gix_testtools::scripted_fixture_writable("make_remote_repos.sh")?;
+        let mut repo = gix::open_opts(tmp.path().join("clone"), gix::open::Options::isolated())?;
+        assert!(matches!(
+            repo.rename_remote("origin", "myself"),
+            Err(gix::repository::rename_remote::Error::AlreadyExists { .. })
+        ));
+        assert!(matches!(
+            repo.rename_remote("does-not-exist", "other"),
+            Err(gix::repository::rename_remote::Error::NotFound { .. })
+        ));
+        Ok(())
+    }
+}
+
+mod remove_remote {
+    use gix::remote::Direction;
+
+    #[test]
+    fn removes_config_tracking_refs_and_branch_configuration() -> crate::Result {
+        let tmp = gix_testtools::scripted_fixture_writable("make_remote_repos.sh")?;
+        let repo_path = tmp.path().join("clone");
+        let mut repo = gix::open_opts(&repo_path, gix::open::Options::isolated())?;
+        assert!(
+            repo.config_snapshot().string("branch.main.remote").is_some(),
+            "the branch tracks the remote initially"
+        );
+
+        let deleted = repo.remove_remote("origin")?;
+        assert!(!deleted.is_empty(), "remote-tracking branches were deleted");
+        assert!(deleted
+            .iter()
+            .all(|name| name.as_bstr().starts_with(b"refs/remotes/origin/")));
+
+        for repo in [repo, gix::open_opts(&repo_path, gix::open::Options::isolated())?] {
+            assert_eq!(
+                repo.remote_names().iter().map(ToString::to_string).collect::<Vec<_>>(),
+                ["myself"],
+                "the configuration is changed in memory and on disk"
+            );
+            assert!(repo.try_find_remote("origin").is_none());
+            assert_eq!(repo.references()?.prefixed("refs/remotes/origin/")?.count(), 0);
+            let config = repo.config_snapshot();
+            assert_eq!(config.string("branch.main.remote"), None);
+            assert_eq!(config.string("branch.main.merge"), None);
+            assert_eq!(
+                repo.find_remote("myself")?.refspecs(Direction::Fetch).len(),
+                1,
+                "other remotes are left alone"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn non_existing_remote_is_an_error() -> crate::Result {
+        let tmp = gix_testtools::scripted_fixture_writable("make_remote_repos.sh")?;
+        let mut repo = gix::open_opts(tmp.path().join("clone"), gix::open::Options::isolated())?;
+        assert!(matches!(
+            repo.remove_remote("does-not-exist"),
+            Err(gix::repository::remove_remote::Error::NotFound { .. })
+        ));
+        Ok(())
+    }
+}
+
+mod edit_remote_url {
+    use std::io::Write;
+
+    use gix::{bstr::ByteSlice, remote::Direction};
+
+    fn writable_clone() -> crate::Result<(gix_testtools::tempfile::TempDir, std::path::PathBuf)> {
+        let tmp = gix_testtools::scripted_fixture_writable("make_remote_repos.sh")?;
+        let repo_path = tmp.path().join("clone");
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(repo_path.join(".git").join("config"))?
+            .write_all(b"[url \"https://example.com/\"]\n\tpushInsteadOf = git://host/\n")?;
+        Ok((tmp, repo_path))
+    }
+
+    fn push_urls(repo: &gix::Repository) -> Vec<String> {
+        repo.config_snapshot()
+            .plumbing()
+            .strings("remote.origin.pushurl")
+            .unwrap_or_default()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    fn open(path: &std::path::Path) -> crate::Result<gix::Repository> {
+        Ok(gix::open_opts(path, gix::open::Options::isolated())?)
+    }
+
+    #[test]
+    fn set_replaces_the_url_in_memory_and_on_disk() -> crate::Result {
+        let (_tmp, repo_path) = writable_clone()?;
+        let mut repo = open(&repo_path)?;
+        repo.set_remote_url("origin", "https://example.com/fetch", Direction::Fetch)?;
+        repo.set_remote_url("origin", "https://example.com/push", Direction::Push)?;
+
+        for repo in [repo, open(&repo_path)?] {
+            let remote = repo.find_remote("origin")?;
+            assert_eq!(
+                remote.url(Direction::Fetch).expect("set").to_bstring(),
+                "https://example.com/fetch"
+            );
+            assert_eq!(
+                remote.url(Direction::Push).expect("set").to_bstring(),
+                "https://example.com/push"
+            );
+            assert_eq!(
+                repo.config_snapshot()
+                    .plumbing()
+                    .strings("remote.origin.url")
+                    .expect("present")
+                    .len(),
+                1,
+                "the previous url was replaced"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn add_push_url_and_delete_it_by_its_rewritten_form() -> crate::Result {
+        let (_tmp, repo_path) = writable_clone()?;
+        let mut repo = open(&repo_path)?;
+        repo.add_remote_push_url("origin", "git://host/a")?;
+        repo.add_remote_push_url("origin", "git://host/b")?;
+        assert_eq!(
+            push_urls(&repo),
+            ["git://host/a", "git://host/b"],
+            "urls are stored as given"
+        );
+        assert_eq!(
+            repo.find_remote("origin")?
+                .url(Direction::Push)
+                .expect("set")
+                .to_bstring(),
+            "https://example.com/b",
+            "the last one is used, and rewritten"
+        );
+
+        repo.delete_remote_url("origin", "https://example.com/a", Direction::Push)?;
+        let repo = open(&repo_path)?;
+        assert_eq!(push_urls(&repo), ["git://host/b"]);
+        Ok(())
+    }
+
+    #[test]
+    fn delete_fails_if_nothing_matches_or_if_no_fetch_url_would_remain() -> crate::Result {
+        let (_tmp, repo_path) = writable_clone()?;
+        let mut repo = open(&repo_path)?;
+        assert!(matches!(
+            repo.delete_remote_url("origin", "https://example.com/unknown", Direction::Fetch),
+            Err(gix::repository::edit_remote_url::Error::UrlNotFound { .. })
+        ));
+        let url = repo
+            .find_remote("origin")?
+            .url(Direction::Fetch)
+            .expect("set")
+            .to_bstring();
+        assert!(matches!(
+            repo.delete_remote_url("origin", url.as_bstr(), Direction::Fetch),
+            Err(gix::repository::edit_remote_url::Error::DeleteAllFetchUrls { .. })
+        ));
+        assert!(
+            repo.find_remote("origin")?.url(Direction::Fetch).is_some(),
+            "nothing was changed"
+        );
+        assert!(matches!(
+            repo.set_remote_url("does-not-exist", "https://example.com", Direction::Fetch),
+            Err(gix::repository::edit_remote_url::Error::NotFound { .. })
+        ));
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+mod expand_refspecs {
+    use gix::{
+        protocol::handshake::Ref,
+        refspec::{match_group::Source, parse::Operation},
+        remote::Direction,
+    };
+
+    fn spec(spec: &str, op: Operation) -> gix_refspec::RefSpec {
+        gix::refspec::parse(spec.into(), op).unwrap().to_owned()
+    }
+
+    fn clone() -> crate::Result<gix::Repository> {
+        let dir = gix_testtools::scripted_fixture_read_only("make_remote_repos.sh")?;
+        Ok(gix::open_opts(dir.join("clone"), gix::open::Options::isolated())?)
+    }
+
+    #[test]
+    fn fetch_maps_remote_refs_to_local_destinations() -> crate::Result {
+        let repo = clone()?;
+        let head_id = repo.head_id()?.detach();
+        let remote_refs = [
+            Ref::Direct {
+                full_ref_name: "refs/heads/main".into(),
+                object: head_id,
+            },
+            Ref::Direct {
+                full_ref_name: "refs/heads/new".into(),
+                object: head_id,
+            },
+        ];
+        let mappings = repo.expand_refspecs(
+            &[
+                spec("+refs/heads/*:refs/remotes/origin/*", Operation::Fetch),
+                spec("refs/heads/main:refs/heads/main", Operation::Push),
+            ],
+            Direction::Fetch,
+            &remote_refs,
+        )?;
+
+        assert_eq!(mappings.len(), 2, "push specs are ignored");
+        assert_eq!(mappings[0].source, Some(Source::FullName("refs/heads/main".into())));
+        assert_eq!(mappings[0].source_id, Some(head_id));
+        assert_eq!(mappings[0].destination.as_ref().unwrap(), "refs/remotes/origin/main");
+        assert_eq!(
+            mappings[0].destination_id,
+            Some(repo.find_reference("refs/remotes/origin/main")?.id().detach()),
+            "existing destinations are looked up"
+        );
+        assert_eq!(mappings[1].destination.as_ref().unwrap(), "refs/remotes/origin/new");
+        assert_eq!(mappings[1].destination_id, None, "the destination doesn't exist yet");
+        assert!(mappings.iter().all(|m| m.spec_index == 0));
+        Ok(())
+    }
+
+    #[test]
+    fn push_maps_local_refs_to_remote_destinations() -> crate::Result {
+        let repo = clone()?;
+        let head_id = repo.head_id()?.detach();
+        let old_id = repo.find_reference("refs/remotes/origin/a")?.id().detach();
+        let remote_refs = [Ref::Direct {
+            full_ref_name: "refs/heads/old".into(),
+            object: old_id,
+        }];
+        let mappings = repo.expand_refspecs(
+            &[spec("main:feature", Operation::Push), spec(":old", Operation::Push)],
+            Direction::Push,
+            &remote_refs,
+        )?;
+
+        assert_eq!(mappings.len(), 2);
+        assert_eq!(mappings[0].source, Some(Source::FullName("refs/heads/main".into())));
+        assert_eq!(mappings[0].source_id, Some(head_id));
+        assert_eq!(
+            mappings[0].destination.as_ref().unwrap(),
+            "refs/heads/feature",
+            "partial destinations are qualified like the source"
+        );
+        assert_eq!(mappings[0].destination_id, None);
+        assert_eq!(mappings[0].spec_index, 0);
+
+        assert_eq!(mappings[1].source, None, "deletions have no source");
+        assert_eq!(mappings[1].source_id, None);
+        assert_eq!(mappings[1].destination.as_ref().unwrap(), "refs/heads/old");
+        assert_eq!(mappings[1].destination_id, Some(old_id));
+        assert_eq!(mappings[1].spec_index, 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use std::sync::atomic::AtomicBool;
+
+use gix::repair::LooseObjectProblem;
+
+#[test]
+fn nothing_to_do_in_intact_repository() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_packed_and_loose.sh")?;
+    let out = repo.repair(gix::progress::Discard, &AtomicBool::default(), Default::default())?;
+    assert!(out.is_empty(), "{out:?}");
+    Ok(())
+}
+
+#[test]
+fn repair_all_kinds_of_corruption() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_packed_and_loose.sh")?;
+    let git_dir = repo.path().to_owned();
+    let objects_dir = repo.objects.store_ref().path().to_owned();
+
+    let head_id = repo.head_id()?.detach();
+    let head_path = {
+        let hex = head_id.to_hex().to_string();
+        objects_dir.join(&hex[..2]).join(&hex[2..])
+    };
+    let data = std::fs::read(&head_path)?;
+    std::fs::write(&head_path, &data[..data.len() / 2])?;
+
+    let mut packed_refs = std::fs::read(git_dir.join("packed-refs"))?;
+    packed_refs.extend_from_slice(b"this line is garbage\n");
+    std::fs::write(git_dir.join("packed-refs"), packed_refs)?;
+
+    let index_path = std::fs::read_dir(objects_dir.join("pack"))?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| path.extension().map_or(false, |ext| ext == "idx"))
+        .expect("the fixture has one pack");
+    std::fs::remove_file(&index_path)?;
+    let keep_path = index_path.with_extension("keep");
+    std::fs::write(&keep_path, b"kept by the user")?;
+    std::fs::remove_file(git_dir.join("info").join("refs")).ok();
+
+    let options = gix::repair::Options {
+        loose_objects: true,
+        ..Default::default()
+    };
+    let out = repo.repair(gix::progress::Discard, &AtomicBool::default(), options)?;
+    assert_eq!(out.removed_loose_objects.len(), 1);
+    assert_eq!(out.removed_loose_objects[0].id, head_id);
+    assert_eq!(out.removed_loose_objects[0].problem, LooseObjectProblem::Unreadable);
+    assert!(!head_path.exists(), "truncated objects are removed");
+    assert_eq!(
+        std::fs::read(&out.removed_loose_objects[0].quarantine_path)?,
+        &data[..data.len() / 2],
+        "but they are kept in quarantine"
+    );
+
+    assert_eq!(out.pack_indices.len(), 1);
+    assert_eq!(out.pack_indices[0].index_path, index_path, "the pack data was intact");
+    assert_eq!(out.pack_indices[0].num_objects, 6);
+    assert!(index_path.is_file());
+    assert_eq!(
+        std::fs::read(&keep_path)?,
+        b"kept by the user",
+        "keep files that existed before are left alone"
+    );
+
+    let packed_refs = out.packed_refs.expect("packed-refs was rewritten");
+    assert_eq!(packed_refs.num_dropped_lines, 1);
+    assert_eq!(packed_refs.num_kept, 1);
+    assert_eq!(packed_refs.num_loose, 1);
+    assert!(
+        repo.refs
+            .open_packed_buffer()?
+            .expect("present")
+            .iter()?
+            .all(|r| r.is_ok()),
+        "the new packed-refs file can be parsed"
+    );
+
+    let info_refs = out.info_refs.expect("info/refs was missing");
+    assert_eq!(
+        std::fs::read(info_refs)?,
+        format!("{head_id}\trefs/heads/main\n").as_bytes(),
+        "the reference points to the removed commit, but that's not for us to fix"
+    );
+
+    let out = repo.repair(gix::progress::Discard, &AtomicBool::default(), options)?;
+    assert!(out.is_empty(), "everything was repaired: {out:?}");
+    Ok(())
+}
+
+#[test]
+fn corrupt_loose_objects_are_kept_by_default() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_packed_and_loose.sh")?;
+    let head_id = repo.head_id()?.detach();
+    let head_path = {
+        let hex = head_id.to_hex().to_string();
+        repo.objects.store_ref().path().join(&hex[..2]).join(&hex[2..])
+    };
+    let data = std::fs::read(&head_path)?;
+    std::fs::write(&head_path, &data[..data.len() / 2])?;
+
+    let out = repo.repair(gix::progress::Discard, &AtomicBool::default(), Default::default())?;
+    assert!(
+        out.removed_loose_objects.is_empty(),
+        "removing objects must be opted into"
+    );
+    assert!(head_path.exists());
+    Ok(())
+}
@@ -0,0 +1,31 @@
+#[test]
+fn empty_repository() -> crate::Result {
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let repo = gix::init(tmp.path())?;
+    assert_eq!(
+        repo.stats()?,
+        gix::stats::Snapshot::default(),
+        "there is nothing in a new repository, and no index either"
+    );
+    Ok(())
+}
+
+#[test]
+fn packed_and_loose() -> crate::Result {
+    let repo = crate::named_repo("make_packed_and_loose.sh")?;
+    let stats = repo.stats()?;
+    assert_eq!(stats.loose_objects.count, 3, "a blob, a tree and a commit");
+    assert!(stats.loose_objects.size_in_bytes > 0);
+    assert_eq!(stats.packs.count, 1);
+    assert_eq!(stats.packs.kept, 0);
+    assert_eq!(stats.packs.object_count, 6, "two commits with a tree and a blob each");
+    assert!(stats.packs.size_in_bytes > 0 && stats.packs.index_size_in_bytes > 0);
+    assert_eq!(
+        stats.refs,
+        gix::stats::Refs { loose: 1, packed: 1 },
+        "main was packed and then updated"
+    );
+    assert!(stats.index_size_in_bytes.is_some());
+    assert!(stats.last_maintenance.is_some(), "`git gc` wrote packed-refs");
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use crate::util::{freeze_time, restricted_and_git};
+
+#[test]
+#[serial_test::serial]
+fn roots_tips_and_orphaned_subgraphs() -> crate::Result {
+    let _env = freeze_time();
+    let (repo, _keep) = crate::repo_rw_opts("make_packed_and_loose.sh", restricted_and_git())?;
+    let head_id = repo.head_id()?.detach();
+    let parent_id = |id| -> crate::Result<gix::ObjectId> {
+        Ok(repo.find_commit(id)?.parent_ids().next().expect("has parent").detach())
+    };
+    let root_id = parent_id(parent_id(head_id)?)?;
+
+    let outcome = repo.topology()?;
+    assert_eq!(outcome.num_commits, 3);
+    assert_eq!(outcome.roots, [root_id]);
+    assert_eq!(
+        outcome.tips,
+        [gix::topology::Tip {
+            id: head_id,
+            references: vec!["refs/heads/main".try_into()?],
+        }]
+    );
+    assert!(outcome.boundary.is_empty());
+    assert!(outcome.orphaned.is_empty(), "all commits are reachable");
+
+    let tree_id = repo.find_commit(head_id)?.tree_id()?.detach();
+    let child_id = repo
+        .commit("refs/heads/child", "child", tree_id, Some(head_id))?
+        .detach();
+    let orphan_id = repo
+        .commit("refs/heads/orphan", "orphan", tree_id, gix::commit::NO_PARENT_IDS)?
+        .detach();
+    for name in ["refs/heads/child", "refs/heads/orphan"] {
+        repo.find_reference(name)?.delete()?;
+    }
+
+    let outcome = repo.topology()?;
+    assert_eq!(outcome.num_commits, 5);
+    let mut expected_roots = vec![root_id, orphan_id];
+    expected_roots.sort();
+    assert_eq!(outcome.roots, expected_roots);
+    assert!(
+        outcome.tips.iter().all(|tip| tip.references.is_empty()),
+        "main isn't a tip anymore as the unreachable child builds on top of it"
+    );
+    assert_eq!(outcome.tips.len(), 2);
+
+    let subgraph = |id| {
+        outcome
+            .orphaned
+            .iter()
+            .find(|subgraph| subgraph.tips == [id])
+            .cloned()
+            .expect("each unreachable commit forms its own subgraph")
+    };
+    assert_eq!(outcome.orphaned.len(), 2);
+    assert_eq!(
+        subgraph(child_id),
+        gix::topology::Subgraph {
+            tips: vec![child_id],
+            roots: vec![],
+            attached_to: vec![head_id],
+            num_commits: 1,
+        },
+        "the child branched off the reachable history"
+    );
+    assert_eq!(
+        subgraph(orphan_id),
+        gix::topology::Subgraph {
+            tips: vec![orphan_id],
+            roots: vec![orphan_id],
+            attached_to: vec![],
+            num_commits: 1,
+        },
+        "the orphan has its own history"
+    );
+    Ok(())
+}
@@ -0,0 +1,77 @@
+fn id(repo: &gix::Repository, name: &str) -> crate::Result<gix::ObjectId> {
+    Ok(repo.find_reference(name)?.id().detach())
+}
+
+#[test]
+fn annotated_tags_decorate_their_target_too() -> crate::Result {
+    let tmp = gix_testtools::scripted_fixture_writable("make_remote_repos.sh")?;
+    let repo = gix::open_opts(tmp.path().join("base"), crate::restricted())?;
+    let decorations = repo.decorations()?;
+
+    let tag = id(&repo, "refs/tags/b-tag")?;
+    let names = |id| {
+        decorations
+            .get(id)
+            .iter()
+            .map(|name| name.as_bstr().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(tag), ["refs/tags/b-tag"]);
+    assert_eq!(
+        names(repo.find_object(tag)?.peel_tags_to_end()?.id),
+        ["refs/heads/b", "refs/tags/b-tag"],
+        "the tag decorates the commit it points to, along with the branch"
+    );
+    assert!(decorations.get(repo.object_hash().null()).is_empty());
+    assert!(!decorations.is_empty());
+    Ok(())
+}
+
+#[test]
+fn cache_is_reused_until_references_change() -> crate::Result {
+    let tmp = gix_testtools::scripted_fixture_writable("make_remote_repos.sh")?;
+    let repo = gix::open_opts(tmp.path().join("base"), crate::restricted())?;
+    let cache = tmp.path().join("decorations");
+
+    let computed = repo.decorations_cached(&cache)?;
+    assert!(cache.is_file(), "the cache was written");
+    assert!(
+        !repo.common_dir().join("gix").exists(),
+        "nothing is written into the repository"
+    );
+    assert_eq!(computed, repo.decorations()?);
+    assert_eq!(repo.decorations_cached(&cache)?, computed, "the cache round-trips");
+
+    let main = id(&repo, "refs/heads/main")?;
+    std::fs::write(
+        &cache,
+        format!(
+            "# gix decorations v1 {}\n{main} refs/heads/bogus\n",
+            computed.fingerprint()
+        ),
+    )?;
+    assert_eq!(
+        repo.decorations_cached(&cache)?.get(main)[0].as_bstr(),
+        "refs/heads/bogus",
+        "a cache with a matching fingerprint is trusted"
+    );
+
+    repo.reference(
+        "refs/heads/new",
+        main,
+        gix::refs::transaction::PreviousValue::MustNotExist,
+        "create",
+    )?;
+    let updated = repo.decorations_cached(&cache)?;
+    assert_ne!(updated.fingerprint(), computed.fingerprint(), "references changed");
+    assert_eq!(updated, repo.decorations()?, "the stale cache was recomputed");
+    assert!(updated.get(main).iter().any(|name| name.as_bstr() == "refs/heads/new"));
+
+    std::fs::write(&cache, "garbage")?;
+    assert_eq!(
+        repo.decorations_cached(&cache)?,
+        updated,
+        "corrupted caches are ignored and rewritten"
+    );
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use gix::bstr::ByteSlice;
+use gix::revision::export::{self, Format, Options};
+
+fn id(repo: &gix::Repository, name: &str) -> crate::Result<gix::ObjectId> {
+    Ok(repo.find_reference(name)?.peel_to_id_in_place()?.detach())
+}
+
+#[test]
+fn json_with_decorations_and_titles() -> crate::Result {
+    let repo = crate::named_subrepo_opts("make_remote_repos.sh", "base", crate::restricted())?;
+    let (a, b, c, f) = (
+        id(&repo, "refs/heads/a")?,
+        id(&repo, "refs/heads/b")?,
+        id(&repo, "refs/heads/c")?,
+        id(&repo, "refs/heads/f")?,
+    );
+    let decorations = repo.decorations()?;
+    let mut out = Vec::new();
+    let num_commits = export::write(
+        repo.rev_walk([a]).with_pruned([b]).all()?,
+        Options {
+            format: Format::Json,
+            decorations: Some(&decorations),
+            titles: true,
+            ..Default::default()
+        },
+        &mut out,
+    )?;
+    assert_eq!(num_commits, 2, "only the commits of the range b..a are exported");
+    assert_eq!(
+        out.to_str()?,
+        format!(
+            "{{\"commits\":[\
+             {{\"id\":\"{a}\",\"parents\":[\"{b}\",\"{c}\"],\"labels\":[\"a\",\"main\"],\"title\":\"A\"}},\
+             {{\"id\":\"{c}\",\"parents\":[\"{f}\"],\"labels\":[\"c\"],\"title\":\"C\"}}]}}\n"
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn dot_with_limit_and_boundary_parents() -> crate::Result {
+    let repo = crate::named_subrepo_opts("make_remote_repos.sh", "base", crate::restricted())?;
+    let (a, b, c) = (
+        id(&repo, "refs/heads/a")?,
+        id(&repo, "refs/heads/b")?,
+        id(&repo, "refs/heads/c")?,
+    );
+    let mut out = Vec::new();
+    let num_commits = export::write(
+        repo.rev_walk([a]).all()?,
+        Options {
+            limit: Some(1),
+            ..Default::default()
+        },
+        &mut out,
+    )?;
+    assert_eq!(num_commits, 1);
+    assert_eq!(
+        out.to_str()?,
+        format!(
+            "digraph commits {{\n  node [shape=box];\n  \"{a}\" [label=\"{}\"];\n  \
+             \"{b}\" [label=\"{}\", style=dashed];\n  \"{a}\" -> \"{b}\";\n  \
+             \"{c}\" [label=\"{}\", style=dashed];\n  \"{a}\" -> \"{c}\";\n}}\n",
+            a.to_hex_with_len(7),
+            b.to_hex_with_len(7),
+            c.to_hex_with_len(7)
+        ),
+        "parents outside of the exported commits are boundary nodes"
+    );
+    Ok(())
+}
@@ -1 +1,5 @@
+mod decorations;
+mod export;
+#[cfg(feature = "blob-diff")]
+mod path_history;
 mod spec;
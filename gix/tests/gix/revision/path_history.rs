@@ -0,0 +1,103 @@
+use gix::{bstr::ByteSlice, revision::path_history::Change};
+
+use crate::util::named_repo;
+
+fn summary(repo: &gix::Repository, id: gix::ObjectId) -> crate::Result<String> {
+    Ok(repo.find_commit(id)?.message()?.summary().to_string())
+}
+
+fn commit_with_summary(repo: &gix::Repository, expected: &str) -> crate::Result<gix::ObjectId> {
+    for info in repo.rev_walk([repo.head_id()?]).all()? {
+        let id = info?.id;
+        if summary(repo, id)? == expected {
+            return Ok(id);
+        }
+    }
+    unreachable!("commit with summary {expected:?} not found")
+}
+
+#[test]
+fn history_and_follow_across_renames() -> crate::Result {
+    let repo = named_repo("make_diff_repo.sh")?;
+    let mut index = gix::revision::path_history::Index::default();
+    let num_commits = index.update(&repo, Some(repo.head_id()?))?;
+    assert_eq!(num_commits, repo.rev_walk([repo.head_id()?]).all()?.count());
+    assert_eq!(index.num_commits(), num_commits);
+
+    let history = |path: &str| -> crate::Result<Vec<String>> {
+        index
+            .history(path.into())
+            .iter()
+            .map(|entry| summary(&repo, entry.commit))
+            .collect()
+    };
+    assert_eq!(
+        history("dir/c")?,
+        ["r3-simple", "c3-modification", "c2", "c1 - initial"],
+        "most recent first"
+    );
+    assert_eq!(index.history("dir/c".into())[0].change, Change::Deletion);
+    assert_eq!(
+        history("dir/c-moved")?,
+        ["r1-base", "r3-simple"],
+        "the path is deleted again later"
+    );
+    assert_eq!(index.history("dir/c-moved".into())[0].change, Change::Deletion);
+    assert_eq!(
+        index.history("dir/c-moved".into())[1].change,
+        Change::Rewrite {
+            source: "dir/c".into(),
+            copy: false
+        }
+    );
+    assert!(index.history("does-not-exist".into()).is_empty());
+    assert!(index.paths().all(|path| path != "dir"), "directories aren't indexed");
+
+    let followed = index
+        .follow("dir/c-moved".into())
+        .into_iter()
+        .map(|(path, entry)| Ok((path.to_str_lossy().into_owned(), summary(&repo, entry.commit)?)))
+        .collect::<crate::Result<Vec<_>>>()?;
+    assert_eq!(
+        followed,
+        [
+            ("dir/c-moved".to_string(), "r1-base".to_string()),
+            ("dir/c-moved".into(), "r3-simple".into()),
+            ("dir/c".into(), "c3-modification".into()),
+            ("dir/c".into(), "c2".into()),
+            ("dir/c".into(), "c1 - initial".into()),
+        ],
+        "renames are followed to the previous name of the path"
+    );
+    Ok(())
+}
+
+#[test]
+fn updates_are_incremental() -> crate::Result {
+    let repo = named_repo("make_diff_repo.sh")?;
+    let mut index = gix::revision::path_history::Index::default();
+    let r3 = commit_with_summary(&repo, "r3-simple")?;
+    assert_eq!(index.update(&repo, Some(r3))?, 7, "all commits up to and including r3");
+    assert!(index.contains(r3));
+    assert_eq!(index.history("dir/c-moved".into()).len(), 1);
+
+    let total = repo.rev_walk([repo.head_id()?]).all()?.count();
+    assert_eq!(
+        index.update(&repo, Some(repo.head_id()?))?,
+        total - 7,
+        "only commits that weren't indexed yet are processed"
+    );
+    assert_eq!(index.update(&repo, Some(repo.head_id()?))?, 0, "nothing new");
+    assert_eq!(index.num_commits(), total);
+
+    let mut full = gix::revision::path_history::Index::default();
+    full.update(&repo, Some(repo.head_id()?))?;
+    for path in full.paths() {
+        assert_eq!(
+            index.history(path),
+            full.history(path),
+            "incremental updates yield the same result as a full update"
+        );
+    }
+    Ok(())
+}
@@ -308,6 +308,23 @@ mod open {
         Ok(())
     }
 
+    #[test]
+    fn clone_shallow() -> crate::Result {
+        let repo = repo("with-submodules")?;
+        let sm = repo.submodules()?.into_iter().flatten().next().expect("one submodule");
+        assert_eq!(
+            sm.shallow()?,
+            None,
+            "the field isn't set, so the per-clone choice is used"
+        );
+        assert_eq!(
+            sm.clone_shallow(true)?,
+            gix::remote::fetch::Shallow::DepthAtRemote(1.try_into().expect("non-zero"))
+        );
+        assert_eq!(sm.clone_shallow(false)?, gix::remote::fetch::Shallow::NoChange);
+        Ok(())
+    }
+
     #[test]
     fn old_form() -> crate::Result {
         for name in ["old-form-invalid-worktree-path", "old-form"] {
@@ -452,7 +452,10 @@ pub fn main() -> Result<()> {
                 },
             )
             .map(|_| ()),
-            commitgraph::Subcommands::Verify { statistics } => prepare_and_run(
+            commitgraph::Subcommands::Verify {
+                statistics,
+                remove_invalid_layers,
+            } => prepare_and_run(
                 "commitgraph-verify",
                 trace,
                 auto_verbose,
@@ -467,6 +470,7 @@ pub fn main() -> Result<()> {
                             err,
                             out,
                             output_statistics,
+                            remove_invalid_layers,
                         },
                     )
                 },
@@ -478,6 +482,8 @@ pub fn main() -> Result<()> {
             handshake_info,
             bare,
             no_tags,
+            no_local,
+            no_hardlinks,
             ref_name,
             remote,
             shallow,
@@ -490,6 +496,13 @@ pub fn main() -> Result<()> {
                 no_tags,
                 ref_name,
                 shallow: shallow.into(),
+                local: if no_local {
+                    gix::clone::Local::Disabled
+                } else if no_hardlinks {
+                    gix::clone::Local::Copy
+                } else {
+                    gix::clone::Local::HardlinkOrCopy
+                },
             };
             prepare_and_run(
                 "clone",
@@ -509,6 +522,7 @@ pub fn main() -> Result<()> {
             open_negotiation_graph,
             remote,
             shallow,
+            refetch,
             ref_spec,
         }) => {
             let opts = core::repository::fetch::Options {
@@ -519,6 +533,7 @@ pub fn main() -> Result<()> {
                 negotiation_info,
                 open_negotiation_graph,
                 shallow: shallow.into(),
+                refetch,
                 ref_specs: ref_spec,
             };
             prepare_and_run(
@@ -452,6 +452,12 @@ pub mod fetch {
         #[clap(flatten)]
         pub shallow: ShallowOptions,
 
+        /// Fetch all objects as if the repository was empty, and remove packs whose objects were all received again.
+        ///
+        /// This can repair repositories with corrupted packs, or partial clones whose filter changed.
+        #[clap(long)]
+        pub refetch: bool,
+
         /// The name of the remote to connect to, or the url of the remote to connect to directly.
         ///
         /// If unset, the current branch will determine the remote.
@@ -528,6 +534,14 @@ pub mod clone {
         #[clap(long)]
         pub no_tags: bool,
 
+        /// If the remote is a local path, receive a pack through the transport instead of hardlinking or copying its objects.
+        #[clap(long, conflicts_with = "no_hardlinks")]
+        pub no_local: bool,
+
+        /// If the remote is a local path, copy its objects instead of hardlinking them.
+        #[clap(long)]
+        pub no_hardlinks: bool,
+
         #[clap(flatten)]
         pub shallow: ShallowOptions,
 
@@ -810,11 +824,14 @@ pub mod credential {
 pub mod commitgraph {
     #[derive(Debug, clap::Subcommand)]
     pub enum Subcommands {
-        /// Verify the integrity of a commit graph file
+        /// Verify the integrity of a commit graph file, and that it matches the object database.
         Verify {
             /// output statistical information about the graph.
             #[clap(long, short = 's')]
             statistics: bool,
+            /// Remove the first invalid layer of the commit-graph chain and all layers on top of it.
+            #[clap(long)]
+            remove_invalid_layers: bool,
         },
         /// List all entries in the commit-graph file as reachable by starting from `HEAD`.
         List {
@@ -229,10 +229,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "fetch.fsck.skipList",
         usage: Puzzled
     },
-    Record {
-        config: "fetch.unpackLimit",
-        usage: Planned("")
-    },
     Record {
         config: "fetch.prune",
         usage: Planned("")
@@ -31,6 +31,11 @@ pub enum Subcommands {
     /// Initialize the repository in the current directory.
     #[clap(visible_alias = "initialize")]
     Init {
+        /// The directory from which to copy templates like hooks instead of using the built-in ones.
+        ///
+        /// An empty value means no templates are used.
+        #[clap(long)]
+        template: Option<PathBuf>,
         /// The directory in which to initialize a new git repository.
         ///
         /// Defaults to the current working directory.
@@ -1,3 +1,6 @@
+#[cfg(feature = "trace2")]
+pub mod trace2;
+
 #[cfg(any(feature = "prodash-render-line", feature = "prodash-render-tui"))]
 pub const DEFAULT_FRAME_RATE: f32 = 6.0;
 
@@ -102,7 +105,7 @@ pub mod pretty {
         reverse_lines: bool,
         progress: &gix::progress::prodash::tree::Root,
     ) -> anyhow::Result<()> {
-        if enable {
+        let forest = enable.then(|| {
             let processor = tracing_forest::Printer::new().formatter({
                 let progress = std::sync::Mutex::new(progress.add_child("tracing"));
                 move |tree: &tracing_forest::tree::Tree| -> Result<String, std::fmt::Error> {
@@ -122,11 +125,32 @@ pub mod pretty {
                     Ok(String::new())
                 }
             });
+            tracing_forest::ForestLayer::from(processor)
+        });
+        use tracing_subscriber::layer::SubscriberExt;
+        let subscriber = tracing_subscriber::Registry::default()
+            .with(forest)
+            .with(trace2_layer());
+        tracing::subscriber::set_global_default(subscriber)?;
+        Ok(())
+    }
+
+    #[cfg(feature = "trace2")]
+    fn trace2_layer() -> Option<crate::shared::trace2::Layer> {
+        crate::shared::trace2::Layer::from_env()
+    }
+
+    #[cfg(all(feature = "tracing", not(feature = "trace2")))]
+    fn trace2_layer() -> Option<tracing_subscriber::layer::Identity> {
+        None
+    }
+
+    /// Emit trace2 events if configured, for when there is no progress to show `--trace` output with.
+    #[cfg(feature = "trace2")]
+    fn init_trace2() -> anyhow::Result<()> {
+        if let Some(layer) = trace2_layer() {
             use tracing_subscriber::layer::SubscriberExt;
-            let subscriber = tracing_subscriber::Registry::default().with(tracing_forest::ForestLayer::from(processor));
-            tracing::subscriber::set_global_default(subscriber)?;
-        } else {
-            tracing::subscriber::set_global_default(tracing_subscriber::Registry::default())?;
+            tracing::subscriber::set_global_default(tracing_subscriber::Registry::default().with(layer))?;
         }
         Ok(())
     }
@@ -149,8 +173,10 @@ pub mod pretty {
     ) -> Result<T> {
         crate::shared::init_env_logger();
 
-        match (verbose, progress) {
+        let res = match (verbose, progress) {
             (false, false) => {
+                #[cfg(feature = "trace2")]
+                init_trace2()?;
                 let stdout = stdout();
                 let mut stdout_lock = stdout.lock();
                 run(progress::DoOrDiscard::from(None), &mut stdout_lock, &mut stderr())
@@ -244,7 +270,10 @@ pub mod pretty {
                     }
                 }
             }
-        }
+        };
+        #[cfg(feature = "trace2")]
+        crate::shared::trace2::exit(if res.is_ok() { 0 } else { 1 });
+        res
     }
 }
 
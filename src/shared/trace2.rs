@@ -0,0 +1,424 @@
+//! Emit events in the format of git's [trace2](https://git-scm.com/docs/api-trace2) facility, so tools consuming it can do the same with `gix` and `ein`.
+//!
+//! Like in git, targets are configured with `GIT_TRACE2` for the *normal* format and `GIT_TRACE2_EVENT` for the *event* (JSON) format.
+//! Each of these may be `1`, `2` or `true` to write to `stderr`, or an absolute path to a file to append to.
+//! Other destinations, like file descriptors or unix domain sockets, are ignored.
+//!
+//! Spans become regions, and their accumulated durations are emitted as timers when the program exits.
+//! Events become data points, or errors if their level is `ERROR`, or child process starts if they are emitted by `gix-command`.
+use std::{
+    cell::Cell,
+    collections::BTreeMap,
+    fs::OpenOptions,
+    io::Write,
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant, SystemTime},
+};
+
+use gix::date::time::CustomFormat;
+use once_cell::sync::OnceCell;
+use serde_json::{json, Map, Value};
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Level, Metadata, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan};
+
+static STATE: OnceCell<Arc<State>> = OnceCell::new();
+
+thread_local! {
+    static NESTING: Cell<usize> = Cell::new(0);
+}
+
+/// A `tracing` layer to translate spans and events into trace2 events.
+pub struct Layer {
+    state: Arc<State>,
+}
+
+impl Layer {
+    /// Return a new instance if at least one trace2 target is configured in the environment, after emitting the `version` and `start` events.
+    ///
+    /// Only the first instance will be returned, as trace2 sessions span the whole process.
+    pub fn from_env() -> Option<Self> {
+        let targets: Vec<_> = [("GIT_TRACE2", Format::Normal), ("GIT_TRACE2_EVENT", Format::Event)]
+            .into_iter()
+            .filter_map(|(name, format)| Target::from_env(name, format))
+            .collect();
+        if targets.is_empty() {
+            return None;
+        }
+        let state = Arc::new(State {
+            targets,
+            sid: session_id(),
+            start: Instant::now(),
+            next_child_id: AtomicUsize::default(),
+            timers: Default::default(),
+        });
+        STATE.set(state.clone()).ok()?;
+
+        let version = env!("CARGO_PKG_VERSION");
+        state.emit(
+            "version",
+            file!(),
+            line!(),
+            json!({ "evt": "3", "exe": version }),
+            Some(format!("version {version}")),
+        );
+        let argv: Vec<_> = std::env::args_os()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        state.emit(
+            "start",
+            file!(),
+            line!(),
+            json!({ "t_abs": state.elapsed(), "argv": argv }),
+            Some(format!("start {}", argv.join(" "))),
+        );
+        Some(Layer { state })
+    }
+}
+
+/// Emit all timers along with the `exit` and `atexit` events with `code`, if a [`Layer`] was created.
+pub fn exit(code: i32) {
+    if let Some(state) = STATE.get() {
+        state.exit(code);
+    }
+}
+
+impl<S> tracing_subscriber::Layer<S> for Layer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let nesting = NESTING.with(|n| {
+            n.set(n.get() + 1);
+            n.get()
+        });
+        span.extensions_mut().replace(Entered(Instant::now()));
+        let (file, line) = location(span.metadata());
+        self.state.emit(
+            "region_enter",
+            file,
+            line,
+            json!({
+                "t_abs": self.state.elapsed(),
+                "nesting": nesting,
+                "category": span.metadata().target(),
+                "label": span.metadata().name(),
+            }),
+            None,
+        );
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let nesting = NESTING.with(|n| {
+            let nesting = n.get();
+            n.set(nesting.saturating_sub(1));
+            nesting
+        });
+        let elapsed = span
+            .extensions_mut()
+            .remove::<Entered>()
+            .map(|entered| entered.0.elapsed())
+            .unwrap_or_default();
+        let metadata = span.metadata();
+        self.state.record_timer(metadata.target(), metadata.name(), elapsed);
+        let (file, line) = location(metadata);
+        self.state.emit(
+            "region_leave",
+            file,
+            line,
+            json!({
+                "t_abs": self.state.elapsed(),
+                "t_rel": elapsed.as_secs_f64(),
+                "nesting": nesting,
+                "category": metadata.target(),
+                "label": metadata.name(),
+            }),
+            None,
+        );
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let metadata = event.metadata();
+        let (file, line) = location(metadata);
+
+        if *metadata.level() == Level::ERROR {
+            let msg = fields.message();
+            self.state.emit(
+                "error",
+                file,
+                line,
+                json!({ "msg": msg, "fmt": msg }),
+                Some(format!("error {msg}")),
+            );
+            return;
+        }
+        if metadata.target().starts_with("gix_command") {
+            if let Some((_, cmd)) = fields.0.iter().find(|(name, _)| *name == "cmd") {
+                let child_id = self.state.next_child_id.fetch_add(1, Ordering::SeqCst);
+                self.state.emit(
+                    "child_start",
+                    file,
+                    line,
+                    json!({
+                        "t_abs": self.state.elapsed(),
+                        "child_id": child_id,
+                        "child_class": "?",
+                        "use_shell": false,
+                        "argv": [cmd],
+                    }),
+                    Some(format!("child_start[{child_id}] {cmd}")),
+                );
+                return;
+            }
+        }
+
+        let t_rel = ctx
+            .event_span(event)
+            .and_then(|span| span.extensions().get::<Entered>().map(|entered| entered.0.elapsed()))
+            .unwrap_or_default();
+        let nesting = NESTING.with(Cell::get);
+        for (key, value) in fields.0 {
+            self.state.emit(
+                "data",
+                file,
+                line,
+                json!({
+                    "t_abs": self.state.elapsed(),
+                    "t_rel": t_rel.as_secs_f64(),
+                    "nesting": nesting,
+                    "category": metadata.target(),
+                    "key": key,
+                    "value": value,
+                }),
+                None,
+            );
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Format {
+    Normal,
+    Event,
+}
+
+struct Target {
+    format: Format,
+    out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl Target {
+    fn from_env(name: &str, format: Format) -> Option<Self> {
+        let value = std::env::var_os(name)?;
+        let out: Box<dyn Write + Send> = match value.to_str() {
+            Some(value) if value == "1" || value == "2" || value.eq_ignore_ascii_case("true") => {
+                Box::new(std::io::stderr())
+            }
+            _ if Path::new(&value).is_absolute() => {
+                Box::new(OpenOptions::new().create(true).append(true).open(&value).ok()?)
+            }
+            _ => return None,
+        };
+        Some(Target {
+            format,
+            out: Mutex::new(out),
+        })
+    }
+}
+
+#[derive(Default)]
+struct Timer {
+    intervals: usize,
+    total: Duration,
+    min: Option<Duration>,
+    max: Duration,
+}
+
+struct State {
+    targets: Vec<Target>,
+    sid: String,
+    start: Instant,
+    next_child_id: AtomicUsize,
+    timers: Mutex<BTreeMap<(&'static str, &'static str), Timer>>,
+}
+
+impl State {
+    fn elapsed(&self) -> f64 {
+        self.start.elapsed().as_secs_f64()
+    }
+
+    /// Write `event` to all targets, using `fields` for the event format and `normal` for the normal format,
+    /// which doesn't show the event if `None`.
+    fn emit(&self, event: &str, file: &str, line: u32, fields: Value, normal: Option<String>) {
+        let now = SystemTime::now();
+        for target in &self.targets {
+            let out_line = match target.format {
+                Format::Event => {
+                    let mut obj = Map::new();
+                    obj.insert("event".into(), event.into());
+                    obj.insert("sid".into(), self.sid.as_str().into());
+                    obj.insert("thread".into(), thread_name().into());
+                    obj.insert("time".into(), utc_time(now, "%Y-%m-%dT%H:%M:%S").into());
+                    obj.insert("file".into(), file.into());
+                    obj.insert("line".into(), line.into());
+                    if let Value::Object(fields) = &fields {
+                        obj.extend(fields.clone());
+                    }
+                    Value::Object(obj).to_string()
+                }
+                Format::Normal => match &normal {
+                    Some(msg) => format!("{} {:<50} {msg}", local_time_of_day(now), format!("{file}:{line}")),
+                    None => continue,
+                },
+            };
+            if let Ok(mut out) = target.out.lock() {
+                writeln!(out, "{out_line}").ok();
+            }
+        }
+    }
+
+    fn record_timer(&self, category: &'static str, name: &'static str, elapsed: Duration) {
+        let Ok(mut timers) = self.timers.lock() else { return };
+        let timer = timers.entry((category, name)).or_default();
+        timer.intervals += 1;
+        timer.total += elapsed;
+        timer.min = Some(timer.min.map_or(elapsed, |min| min.min(elapsed)));
+        timer.max = timer.max.max(elapsed);
+    }
+
+    fn exit(&self, code: i32) {
+        let timers = self
+            .timers
+            .lock()
+            .map(|mut timers| std::mem::take(&mut *timers))
+            .unwrap_or_default();
+        for ((category, name), timer) in timers {
+            self.emit(
+                "timer",
+                file!(),
+                line!(),
+                json!({
+                    "category": category,
+                    "name": name,
+                    "intervals": timer.intervals,
+                    "t_total": timer.total.as_secs_f64(),
+                    "t_min": timer.min.unwrap_or_default().as_secs_f64(),
+                    "t_max": timer.max.as_secs_f64(),
+                }),
+                None,
+            );
+        }
+        let t_abs = self.elapsed();
+        for event in ["exit", "atexit"] {
+            self.emit(
+                event,
+                file!(),
+                line!(),
+                json!({ "t_abs": t_abs, "code": code }),
+                Some(format!("{event} elapsed:{t_abs:.6} code:{code}")),
+            );
+        }
+    }
+}
+
+/// Stored in the extensions of spans that are currently entered.
+struct Entered(Instant);
+
+#[derive(Default)]
+struct Fields(Vec<(&'static str, String)>);
+
+impl Fields {
+    fn message(&self) -> String {
+        match self.0.iter().find(|(name, _)| *name == "message") {
+            Some((_, message)) => message.clone(),
+            None => self
+                .0
+                .iter()
+                .map(|(name, value)| format!("{name}={value}"))
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+}
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name(), value.to_owned()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.push((field.name(), format!("{value:?}")));
+    }
+}
+
+fn location(metadata: &Metadata<'_>) -> (&'static str, u32) {
+    (metadata.file().unwrap_or("?"), metadata.line().unwrap_or_default())
+}
+
+fn thread_name() -> String {
+    let thread = std::thread::current();
+    thread
+        .name()
+        .map_or_else(|| format!("{:?}", thread.id()), ToOwned::to_owned)
+}
+
+/// Format `time` in UTC with `format`, followed by microseconds and the `Z` suffix.
+fn utc_time(time: SystemTime, format: &'static str) -> String {
+    let since_epoch = time.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+    format!(
+        "{}.{:06}Z",
+        gix::date::Time::new(since_epoch.as_secs() as gix::date::SecondsSinceUnixEpoch, 0)
+            .format(CustomFormat::new(format)),
+        since_epoch.subsec_micros()
+    )
+}
+
+fn local_time_of_day(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+    let seconds = since_epoch.as_secs() as gix::date::SecondsSinceUnixEpoch;
+    format!(
+        "{}.{:06}",
+        gix::date::Time::new(seconds, 0)
+            .to_local()
+            .format(CustomFormat::new("%H:%M:%S")),
+        since_epoch.subsec_micros()
+    )
+}
+
+/// Produce a session id similar to the one used by git, prefixed with the session id of the parent process if there is one.
+fn session_id() -> String {
+    let sid = format!(
+        "{}-P{:08x}",
+        utc_time(SystemTime::now(), "%Y%m%dT%H%M%S"),
+        std::process::id()
+    );
+    match std::env::var("GIT_TRACE2_PARENT_SID") {
+        Ok(parent) if !parent.is_empty() => format!("{parent}/{sid}"),
+        _ => sid,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn utc_time() {
+        let time = SystemTime::UNIX_EPOCH + Duration::from_micros(1_700_000_000_123_456);
+        assert_eq!(
+            super::utc_time(time, "%Y-%m-%dT%H:%M:%S"),
+            "2023-11-14T22:13:20.123456Z"
+        );
+        assert_eq!(super::utc_time(time, "%Y%m%dT%H%M%S"), "20231114T221320.123456Z");
+    }
+}
@@ -27,6 +27,8 @@ use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 pub use tempfile;
 
+pub mod repository;
+
 /// A result type to allow using the try operator `?` in unit tests.
 ///
 /// Use it like so:
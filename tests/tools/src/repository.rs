@@ -0,0 +1,293 @@
+//! Build repositories deterministically from Rust, without the need for `bash` or `git`.
+//!
+//! This is useful on platforms where scripts can't be run, or to produce fixtures whose layout depends on
+//! parameters of the test. The identities and dates used for commits and tags are the same as those of scripted fixtures,
+//! so equal histories produce equal object ids.
+//!
+//! ```no_run
+//! use gix_testtools::repository::Builder;
+//!
+//! let tmp = gix_testtools::tempfile::TempDir::new()?;
+//! let mut repo = Builder::init(tmp.path())?;
+//! let first = repo.commit("c1", [("a", "content"), ("dir/b", "other content")])?;
+//! repo.branch("feature")?;
+//! repo.commit("c2", [("a", "changed")])?;
+//! repo.tag("v1.0", Some("annotated"))?;
+//! repo.checkout("feature")?;
+//! repo.submodule("sub", "https://example.com/sub.git", &first);
+//! repo.commit("add submodule", None::<(&str, &str)>)?;
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    ops::Bound,
+    path::{Path, PathBuf},
+};
+
+use bstr::{BString, ByteSlice};
+use gix_object::{tree::EntryKind, Kind, WriteTo};
+
+/// The name and email of the author of all commits, the same as in scripted fixtures.
+const AUTHOR: &str = "author <author@example.com> 946684800 +0000";
+/// The name and email of the committer of all commits and the tagger of all tags, the same as in scripted fixtures.
+const COMMITTER: &str = "committer <committer@example.com> 946771200 +0000";
+
+/// An entry in the tree of the next commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Entry {
+    /// A file with the given content.
+    Blob(Vec<u8>),
+    /// A submodule checked out at the commit with the given hexadecimal id.
+    Commit(String),
+}
+
+/// A builder for repositories with deterministic history, written directly into the `.git` directory.
+///
+/// All operations act on the branch `HEAD` points to, which is `main` initially, and write objects and references immediately,
+/// so the repository can be opened at any time.
+/// Only the repository is written, which is why the index and the worktree of non-bare repositories remain empty.
+pub struct Builder {
+    git_dir: PathBuf,
+    /// The branch `HEAD` points to, without the `refs/heads/` prefix.
+    head: String,
+    /// The files of the tip of each branch by path, to allow building the next commit from them.
+    files: BTreeMap<String, BTreeMap<BString, Entry>>,
+    /// The tip of each branch as hexadecimal id, with unborn branches missing.
+    tips: BTreeMap<String, String>,
+}
+
+/// Initialization
+impl Builder {
+    /// Initialize a new repository with a worktree at `dir`, with `HEAD` pointing to the unborn branch `main`.
+    pub fn init(dir: impl AsRef<Path>) -> std::io::Result<Self> {
+        Self::init_inner(dir.as_ref().join(".git"), false)
+    }
+
+    /// Initialize a new bare repository at `dir`, with `HEAD` pointing to the unborn branch `main`.
+    pub fn init_bare(dir: impl AsRef<Path>) -> std::io::Result<Self> {
+        Self::init_inner(dir.as_ref().to_owned(), true)
+    }
+
+    fn init_inner(git_dir: PathBuf, bare: bool) -> std::io::Result<Self> {
+        for dir in ["objects/info", "objects/pack", "refs/heads", "refs/tags"] {
+            std::fs::create_dir_all(git_dir.join(dir))?;
+        }
+        std::fs::write(
+            git_dir.join("config"),
+            format!("[core]\n\trepositoryformatversion = 0\n\tfilemode = true\n\tbare = {bare}\n\tlogallrefupdates = true\n"),
+        )?;
+        let mut out = Builder {
+            git_dir,
+            head: String::new(),
+            files: Default::default(),
+            tips: Default::default(),
+        };
+        out.checkout("main")?;
+        Ok(out)
+    }
+}
+
+/// Mutation
+impl Builder {
+    /// Create a commit on the current branch whose tree is the one of its parent with all `files` added or replaced,
+    /// and return its id. `files` are pairs of slash-separated paths and their content.
+    ///
+    /// The first commit on a branch has no parent. Submodules added with [`submodule()`](Self::submodule()) are committed
+    /// as well.
+    pub fn commit<P, C>(&mut self, message: &str, files: impl IntoIterator<Item = (P, C)>) -> std::io::Result<String>
+    where
+        P: AsRef<str>,
+        C: AsRef<[u8]>,
+    {
+        let parents: Vec<_> = self.tips.get(&self.head).cloned().into_iter().collect();
+        self.commit_with_parents(message, files, parents)
+    }
+
+    /// Create a merge commit on the current branch with the tip of `branch` as second parent, and return its id.
+    ///
+    /// The tree of the merge commit contains all files of both branches, with those of the current branch winning on conflict.
+    pub fn merge(&mut self, branch: &str, message: &str) -> std::io::Result<String> {
+        let other = self.tips.get(branch).cloned().ok_or_else(|| unborn(branch))?;
+        let current = self.tips.get(&self.head).cloned().ok_or_else(|| unborn(&self.head))?;
+        let other_files = self.files.get(branch).cloned().unwrap_or_default();
+        let files = self.files.entry(self.head.clone()).or_default();
+        for (path, entry) in other_files {
+            files.entry(path).or_insert(entry);
+        }
+        self.commit_with_parents(message, None::<(&str, &[u8])>, vec![current, other])
+    }
+
+    /// Remove the file at `path` from the tree of the next commit on the current branch.
+    pub fn remove(&mut self, path: &str) -> &mut Self {
+        if let Some(files) = self.files.get_mut(&self.head) {
+            files.remove(path.as_bytes().as_bstr());
+        }
+        self
+    }
+
+    /// Add a submodule at `path` that is checked out at the commit with the hexadecimal `id` and can be cloned from `url`
+    /// to the next commit on the current branch, which includes adding it to the `.gitmodules` file.
+    pub fn submodule(&mut self, path: &str, url: &str, id: &str) -> &mut Self {
+        let files = self.files.entry(self.head.clone()).or_default();
+        let mut modules = match files.get(b".gitmodules".as_bstr()) {
+            Some(Entry::Blob(content)) => content.clone(),
+            _ => Vec::new(),
+        };
+        modules.extend_from_slice(format!("[submodule \"{path}\"]\n\tpath = {path}\n\turl = {url}\n").as_bytes());
+        files.insert(".gitmodules".into(), Entry::Blob(modules));
+        files.insert(path.into(), Entry::Commit(id.into()));
+        self
+    }
+
+    /// Create the branch `name` pointing to the tip of the current branch, which must not be unborn.
+    pub fn branch(&mut self, name: &str) -> std::io::Result<&mut Self> {
+        let tip = self.tips.get(&self.head).cloned().ok_or_else(|| unborn(&self.head))?;
+        let files = self.files.get(&self.head).cloned().unwrap_or_default();
+        self.write_ref(&format!("refs/heads/{name}"), &tip)?;
+        self.tips.insert(name.into(), tip);
+        self.files.insert(name.into(), files);
+        Ok(self)
+    }
+
+    /// Point `HEAD` to the branch `name`, which is unborn if it doesn't exist yet.
+    pub fn checkout(&mut self, name: &str) -> std::io::Result<&mut Self> {
+        std::fs::write(self.git_dir.join("HEAD"), format!("ref: refs/heads/{name}\n"))?;
+        self.head = name.into();
+        Ok(self)
+    }
+
+    /// Create the tag `name` pointing to the tip of the current branch, which is an annotated tag if `message` is set,
+    /// and return the id the tag reference points to.
+    pub fn tag(&mut self, name: &str, message: Option<&str>) -> std::io::Result<String> {
+        let tip = self.tips.get(&self.head).cloned().ok_or_else(|| unborn(&self.head))?;
+        let id = match message {
+            Some(message) => self.write_object(
+                Kind::Tag,
+                format!("object {tip}\ntype commit\ntag {name}\ntagger {COMMITTER}\n\n{message}\n").as_bytes(),
+            )?,
+            None => tip,
+        };
+        self.write_ref(&format!("refs/tags/{name}"), &id)?;
+        Ok(id)
+    }
+}
+
+/// Access
+impl Builder {
+    /// Return the path to the `.git` directory of the repository, which is the repository itself if it is bare.
+    pub fn git_dir(&self) -> &Path {
+        &self.git_dir
+    }
+
+    /// Return the hexadecimal id of the tip of `branch`, or `None` if it is unborn.
+    pub fn tip(&self, branch: &str) -> Option<&str> {
+        self.tips.get(branch).map(String::as_str)
+    }
+}
+
+/// Utilities
+impl Builder {
+    fn commit_with_parents<P, C>(
+        &mut self,
+        message: &str,
+        files: impl IntoIterator<Item = (P, C)>,
+        parents: Vec<String>,
+    ) -> std::io::Result<String>
+    where
+        P: AsRef<str>,
+        C: AsRef<[u8]>,
+    {
+        let tree = {
+            let entries = self.files.entry(self.head.clone()).or_default();
+            for (path, content) in files {
+                entries.insert(path.as_ref().into(), Entry::Blob(content.as_ref().to_owned()));
+            }
+            let entries = entries.clone();
+            self.write_tree(&entries, b"")?
+        };
+        let mut commit = Vec::new();
+        writeln!(commit, "tree {tree}")?;
+        for parent in &parents {
+            writeln!(commit, "parent {parent}")?;
+        }
+        write!(commit, "author {AUTHOR}\ncommitter {COMMITTER}\n\n{message}\n")?;
+        let id = self.write_object(Kind::Commit, &commit)?;
+        self.write_ref(&format!("refs/heads/{}", self.head), &id)?;
+        self.tips.insert(self.head.clone(), id.clone());
+        Ok(id)
+    }
+
+    /// Write the tree for all `files` below `prefix`, which is empty or ends with a slash, and return its id.
+    fn write_tree(&self, files: &BTreeMap<BString, Entry>, prefix: &[u8]) -> std::io::Result<String> {
+        let mut tree = gix_object::Tree::empty();
+        let mut directories = Vec::<&[u8]>::new();
+        for (path, entry) in files
+            .range::<[u8], _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(|(path, _)| path.starts_with(prefix))
+        {
+            let relative = &path[prefix.len()..];
+            let (filename, mode, id) = match relative.find_byte(b'/') {
+                Some(pos) => {
+                    let name = &relative[..pos];
+                    if directories.last() == Some(&name) {
+                        continue;
+                    }
+                    directories.push(name);
+                    let mut prefix = prefix.to_owned();
+                    prefix.extend_from_slice(name);
+                    prefix.push(b'/');
+                    (name, EntryKind::Tree, self.write_tree(files, &prefix)?)
+                }
+                None => match entry {
+                    Entry::Blob(content) => (relative, EntryKind::Blob, self.write_object(Kind::Blob, content)?),
+                    Entry::Commit(id) => (relative, EntryKind::Commit, id.clone()),
+                },
+            };
+            tree.entries.push(gix_object::tree::Entry {
+                mode: mode.into(),
+                filename: filename.into(),
+                oid: to_id(&id)?,
+            });
+        }
+        // Git sorts directories as if their name ended with a slash, which is what the entry ordering does.
+        tree.entries.sort();
+
+        let mut buf = Vec::new();
+        tree.write_to(&mut buf)?;
+        self.write_object(Kind::Tree, &buf)
+    }
+
+    /// Write `data` as loose object of `kind` and return its hexadecimal id.
+    fn write_object(&self, kind: Kind, data: &[u8]) -> std::io::Result<String> {
+        let id = gix_object::compute_hash(gix_hash::Kind::Sha1, kind, data).to_string();
+
+        let path = self.git_dir.join("objects").join(&id[..2]).join(&id[2..]);
+        if !path.exists() {
+            std::fs::create_dir_all(path.parent().expect("object directory"))?;
+            let mut out = gix_features::zlib::stream::deflate::Write::new(std::fs::File::create(path)?);
+            out.write_all(&gix_object::encode::loose_header(kind, data.len() as u64))?;
+            out.write_all(data)?;
+            out.flush()?;
+        }
+        Ok(id)
+    }
+
+    fn write_ref(&self, name: &str, id: &str) -> std::io::Result<()> {
+        let path = self.git_dir.join(name);
+        std::fs::create_dir_all(path.parent().expect("refs directory"))?;
+        std::fs::write(path, format!("{id}\n"))
+    }
+}
+
+fn unborn(branch: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!("Branch '{branch}' doesn't have any commits yet"),
+    )
+}
+
+fn to_id(id: &str) -> std::io::Result<gix_hash::ObjectId> {
+    gix_hash::ObjectId::from_hex(id.as_bytes())
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))
+}
@@ -0,0 +1,61 @@
+use std::process::Command;
+
+use gix_testtools::repository::Builder;
+
+fn git(git_dir: &std::path::Path, args: &[&str]) -> String {
+    let out = Command::new("git")
+        .arg("--git-dir")
+        .arg(git_dir)
+        .args(args)
+        .output()
+        .expect("git is available");
+    assert!(
+        out.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    String::from_utf8(out.stdout).expect("utf8").trim_end().to_owned()
+}
+
+#[test]
+fn history_is_readable_by_git() -> gix_testtools::Result {
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let mut repo = Builder::init_bare(tmp.path())?;
+    let first = repo.commit("c1", [("a", "a\n"), ("dir/b", "b\n"), ("dir.txt", "c\n")])?;
+    repo.branch("feature")?;
+    repo.commit("c2", [("a", "changed\n")])?;
+    let tag = repo.tag("v1", Some("annotated"))?;
+    repo.checkout("feature")?;
+    repo.remove("dir/b")
+        .submodule("sub", "https://example.com/sub.git", &first);
+    repo.commit("c3", None::<(&str, &str)>)?;
+    repo.checkout("main")?;
+    let merge = repo.merge("feature", "merge")?;
+
+    git(repo.git_dir(), &["fsck", "--strict", "--no-dangling"]);
+    assert_eq!(git(repo.git_dir(), &["rev-parse", "HEAD"]), merge);
+    assert_eq!(git(repo.git_dir(), &["rev-parse", "v1^{tag}"]), tag);
+    assert_eq!(
+        git(repo.git_dir(), &["log", "--format=%s", "--topo-order"]),
+        "merge\nc3\nc2\nc1"
+    );
+    assert_eq!(
+        git(repo.git_dir(), &["ls-tree", "-r", "--name-only", "HEAD"]),
+        ".gitmodules\na\ndir.txt\ndir/b\nsub",
+        "the merge contains the files of both branches"
+    );
+    assert_eq!(git(repo.git_dir(), &["show", "HEAD:a"]), "changed", "main wins");
+    Ok(())
+}
+
+#[test]
+fn ids_are_deterministic() -> gix_testtools::Result {
+    let (a, b) = (
+        gix_testtools::tempfile::TempDir::new()?,
+        gix_testtools::tempfile::TempDir::new()?,
+    );
+    let first = Builder::init(a.path())?.commit("c1", [("file", "content")])?;
+    let second = Builder::init(b.path())?.commit("c1", [("file", "content")])?;
+    assert_eq!(first, second);
+    Ok(())
+}